@@ -0,0 +1,95 @@
+//! Measures code generation for a protocol of many messages.
+//!
+//! Run with `cargo bench --bench codegen`. Besides generating the C code as a
+//! whole, the chunks' buffers are compared: the chunks of each node are
+//! collected, and appended to their parent's, once into `Vec`s, which the
+//! generators use, and once into `LinkedList`s, which they used to.
+
+use robusto::bpir::representation::Protocol;
+use robusto::parser_generation::ragel::c::SourceAstNode;
+use robusto::parser_generation::Write;
+use robusto::utility::codegen::{CodeChunk, CodeGeneration, CodeGenerationState, CodeStyle};
+use std::collections::LinkedList;
+use std::time::{Duration, Instant};
+
+const MESSAGE_COUNT: usize = 500;
+const ITERATIONS: u32 = 20;
+
+/// Number of chunks a node of the AST produces, roughly
+const CHUNKS_PER_NODE: usize = 8;
+
+fn protocol() -> Protocol {
+    let messages = (0..MESSAGE_COUNT)
+        .map(|i| {
+            format!(
+                concat!(
+                    "{{\"name\": \"Message{0}\", \"attributes\": [], \"fields\": [",
+                    "{{\"name\": \"id\", \"field_type\": {{\"Regex\": {{\"regex\": \"\\\\x{1:02x}\\\\x{2:02x}\"}}}}, ",
+                    "\"attributes\": [{{\"MaxLength\": {{\"value\": 2}}}}]}}, ",
+                    "{{\"name\": \"a\", \"field_type\": {{\"Integer\": {{\"bit_width\": 16, \"signed\": false}}}}, ",
+                    "\"attributes\": []}}, ",
+                    "{{\"name\": \"b\", \"field_type\": {{\"Integer\": {{\"bit_width\": 32, \"signed\": true}}}}, ",
+                    "\"attributes\": []}}]}}"
+                ),
+                i,
+                i >> 8,
+                i & 0xff
+            )
+        })
+        .collect::<Vec<_>>();
+
+    Protocol::from_json(&format!("{{\"messages\": [{0}], \"attributes\": []}}", messages.join(", "))).unwrap()
+}
+
+fn measure(name: &str, mut routine: impl FnMut()) {
+    routine();
+    let mut total = Duration::ZERO;
+
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        routine();
+        total += start.elapsed();
+    }
+
+    println!("{0:<32} {1:>10.3} ms", name, total.as_secs_f64() * 1e3 / f64::from(ITERATIONS));
+}
+
+fn main() {
+    let protocol = protocol();
+    let source = SourceAstNode::from(&protocol);
+    let chunks = source.generate_code(&mut CodeGenerationState::with_style(CodeStyle::default()));
+    println!("{0} messages, {1} chunks", MESSAGE_COUNT, chunks.len());
+
+    measure("build AST", || {
+        std::hint::black_box(SourceAstNode::from(&protocol));
+    });
+    measure("generate chunks", || {
+        std::hint::black_box(source.generate_code(&mut CodeGenerationState::with_style(CodeStyle::default())));
+    });
+    measure("write (streaming)", || {
+        let mut buf_writer = std::io::BufWriter::new(std::io::sink());
+        source.write(&mut buf_writer).unwrap();
+    });
+    measure("collect chunks into Vec", || {
+        let mut ret = Vec::new();
+
+        for node in chunks.chunks(CHUNKS_PER_NODE) {
+            let mut node_chunks = Vec::new();
+            node_chunks.extend(node.iter().cloned());
+            ret.append(&mut node_chunks);
+        }
+
+        std::hint::black_box(ret);
+    });
+    measure("collect chunks into LinkedList", || {
+        let mut ret = LinkedList::<CodeChunk>::new();
+
+        for node in chunks.chunks(CHUNKS_PER_NODE) {
+            let mut node_chunks = LinkedList::new();
+            node_chunks.extend(node.iter().cloned());
+            ret.append(&mut node_chunks);
+        }
+
+        std::hint::black_box(ret);
+    });
+}
@@ -0,0 +1,58 @@
+/// Compares API manifests of two generations of a C parser library, and
+/// reports changes breaking binary compatibility with already compiled users.
+///
+/// Usage: api_manifest_diff OLD_MANIFEST NEW_MANIFEST
+///
+/// Exits with 1, if there are breaking changes, and with 2 on invalid input.
+use robusto::bpir::validation::LintResult;
+use robusto::parser_generation::ragel::c::ApiManifest;
+
+fn read_manifest(path: &str) -> ApiManifest {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) => {
+            log::error!("Unable to read {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+    };
+
+    match ApiManifest::parse(&text) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            log::error!("Unable to parse {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn main() {
+    env_logger::init();
+
+    let arguments: std::vec::Vec<std::string::String> = std::env::args().collect();
+
+    if arguments.len() != 3 {
+        eprintln!("Usage: {0} OLD_MANIFEST NEW_MANIFEST", arguments[0]);
+        std::process::exit(2);
+    }
+
+    let old = read_manifest(&arguments[1]);
+    let new = read_manifest(&arguments[2]);
+    let mut n_errors = 0usize;
+
+    for finding in ApiManifest::diff(&old, &new) {
+        match finding {
+            LintResult::Error(ref text) => {
+                n_errors += 1;
+                println!("Error: {0}", text);
+            }
+            LintResult::Warning(ref text) => println!("Warning: {0}", text),
+            LintResult::Ok => {}
+        }
+    }
+
+    println!("{0} breaking change(s)", n_errors);
+
+    if n_errors > 0 {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,482 @@
+/// Runs the whole chain on a protocol description, end to end:
+///
+/// BPIR (JSON) -> [ validation ] -> [ Ragel generator ] -> Ragel/C code ->
+/// [ Ragel ] -> C code -> [ C compiler ] -> self-test
+///
+/// Usage: example_pipeline PROTOCOL_FILE OUTPUT_DIRECTORY [--ragel] [--cc COMPILER] [--rewrite-rules RULES_FILE]
+/// [--lint-format human|json] [--deny-warnings] [--allow RULE] [--deny RULE] [--style STYLE_FILE]
+/// [--prologue PROLOGUE_FILE] [--reproducible] [--source-map] [--overrides OVERRIDES_FILE]
+/// [--identifier-prefix PREFIX] [--output-stem STEM] [--preserve-user-code]
+///
+/// Every artifact is written into the output directory. With `--ragel`, the
+/// generated source is compiled by Ragel, in the code style the protocol asks
+/// for, see `RagelOptions`. With `--cc` additionally, the result is built as
+/// the revision of C the protocol is generated for, and, if the protocol asks
+/// for a self-test, the test is run.
+///
+/// The findings of validation are printed as a report, colored if the output
+/// is a terminal, or, with `--lint-format json`, as a JSON document. With
+/// `--deny-warnings`, warnings are treated as errors. `--allow` and `--deny`
+/// drop the warnings of a rule, or turn them into errors, by the rule's ID or
+/// name.
+///
+/// The style file is a JSON object setting any of the fields of `CodeStyle`,
+/// e.g. `{"indent_style": "Tabs", "brace_style": "OwnLine"}`. It applies to
+/// the generated code, but not to the reports.
+///
+/// Each artifact opens with a prologue, warning against editing it, and
+/// telling when it has been generated. The prologue file is a JSON object
+/// setting any of the fields of `Prologue`, e.g. `{"spdx_license_identifier":
+/// "MIT"}`. `--reproducible` omits the time, so the same input always yields
+/// the same artifacts.
+///
+/// With `--source-map`, the lines of the generated source are traced back to
+/// the messages and fields they stem from, in a JSON sidecar file. Rewrite
+/// rules adding or removing lines are not accounted for.
+///
+/// The overrides file replaces fragments of the generated code with snippets,
+/// see `Overrides` for its form.
+///
+/// Names of the description which are not valid identifiers, or are reserved,
+/// are mangled before generation, see `Mangler`. `--identifier-prefix`
+/// prepends a prefix to the name of every message.
+///
+/// The artifacts are named after a stem, "output" by default, which
+/// `--output-stem` changes. They are rendered first, and only written once
+/// all of them have been, see `GeneratedArtifactSet`.
+///
+/// With `--preserve-user-code`, the contents of the user code blocks of the
+/// files already in the output directory are carried over into the new ones,
+/// see `UserCode`. Rewrite rules do not apply to them.
+///
+/// Exits with 1, if the protocol is invalid or a stage fails, and with 2 on
+/// invalid input.
+use robusto::bpir::validation::{LintLevel, ValidationConfig};
+use robusto::error::RobustoError;
+use robusto::utility::codegen::CodeStyle;
+use robusto::utility::identifier::Mangler;
+use robusto::parser_generation::artifacts::{ArtifactKind, ArtifactNaming, ArtifactSettings, GeneratedArtifactSet};
+use robusto::parser_generation::overrides::Overrides;
+use robusto::parser_generation::post_processing::{RewriteReport, RewriteRules};
+use robusto::parser_generation::prologue::Prologue;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+const SELF_TEST_SOURCE_FILE_NAME: &str = "self_test.c";
+const SELF_TEST_EXECUTABLE_FILE_NAME: &str = "self_test";
+
+/// Name of the generated self-test function, see `ProtocolAttribute::SelfTest`
+const SELF_TEST_FUNCTION_NAME: &str = "selfTest";
+
+struct Options {
+    protocol_file: String,
+    output_directory: PathBuf,
+    ragel: bool,
+    compiler: Option<String>,
+    rewrite_rules_file: Option<String>,
+    lint_format: LintFormat,
+    validation_config: ValidationConfig,
+    style_file: Option<String>,
+    prologue_file: Option<String>,
+    reproducible: bool,
+    source_map: bool,
+    overrides_file: Option<String>,
+    mangler: Mangler,
+    naming: ArtifactNaming,
+    preserve_user_code: bool,
+}
+
+/// How the findings of validation are printed
+#[derive(Clone, Copy, PartialEq)]
+enum LintFormat {
+    Human,
+    Json,
+}
+
+impl Options {
+    fn parse(arguments: &[String]) -> Result<Options, String> {
+        let mut positional = Vec::new();
+        let mut ragel = false;
+        let mut compiler = None;
+        let mut rewrite_rules_file = None;
+        let mut lint_format = LintFormat::Human;
+        let mut validation_config = ValidationConfig::default();
+        let mut style_file = None;
+        let mut prologue_file = None;
+        let mut reproducible = false;
+        let mut source_map = false;
+        let mut overrides_file = None;
+        let mut mangler = Mangler::default();
+        let mut naming = ArtifactNaming::default();
+        let mut preserve_user_code = false;
+        let mut arguments = arguments.iter();
+
+        while let Some(argument) = arguments.next() {
+            match argument.as_str() {
+                "--ragel" => ragel = true,
+                "--cc" => compiler = Some(arguments.next().ok_or("--cc expects a compiler")?.clone()),
+                "--rewrite-rules" => {
+                    rewrite_rules_file = Some(arguments.next().ok_or("--rewrite-rules expects a file")?.clone())
+                }
+                "--lint-format" => {
+                    lint_format = match arguments.next().map(String::as_str) {
+                        Some("human") => LintFormat::Human,
+                        Some("json") => LintFormat::Json,
+                        _ => return Err("--lint-format expects human or json".to_string()),
+                    }
+                }
+                "--deny-warnings" => validation_config.warnings_as_errors = true,
+                "--style" => style_file = Some(arguments.next().ok_or("--style expects a file")?.clone()),
+                "--prologue" => prologue_file = Some(arguments.next().ok_or("--prologue expects a file")?.clone()),
+                "--reproducible" => reproducible = true,
+                "--source-map" => source_map = true,
+                "--overrides" => overrides_file = Some(arguments.next().ok_or("--overrides expects a file")?.clone()),
+                "--identifier-prefix" => {
+                    mangler.prefix = arguments.next().ok_or("--identifier-prefix expects a prefix")?.clone()
+                }
+                "--output-stem" => {
+                    let stem = arguments.next().ok_or("--output-stem expects a stem")?;
+                    naming.stem = stem.clone();
+                    naming.split_stem = stem.clone();
+                }
+                "--preserve-user-code" => preserve_user_code = true,
+                "--allow" | "--deny" => {
+                    let rule = arguments.next().ok_or(format!("{0} expects a rule", argument))?;
+                    let level = match argument.as_str() {
+                        "--allow" => LintLevel::Allow,
+                        _ => LintLevel::Deny,
+                    };
+                    validation_config.levels.push((rule.clone(), level));
+                }
+                _ if argument.starts_with("--") => return Err(format!("unknown option {0}", argument)),
+                _ => positional.push(argument.clone()),
+            }
+        }
+
+        if positional.len() != 2 {
+            return Err("expected a protocol file and an output directory".to_string());
+        }
+
+        // Nothing to compile, unless Ragel has produced C code
+        if compiler.is_some() && !ragel {
+            return Err("--cc requires --ragel".to_string());
+        }
+
+        Ok(Options {
+            output_directory: PathBuf::from(positional.pop().unwrap()),
+            protocol_file: positional.pop().unwrap(),
+            ragel,
+            compiler,
+            rewrite_rules_file,
+            lint_format,
+            validation_config,
+            style_file,
+            prologue_file,
+            reproducible,
+            source_map,
+            overrides_file,
+            mangler,
+            naming,
+            preserve_user_code,
+        })
+    }
+}
+
+fn exit_on_write_error(name: &str, result: Result<(), RobustoError>) {
+    if let Err(error) = result {
+        log::error!("Unable to write {0}: {1}", name, error);
+        std::process::exit(1);
+    }
+}
+
+/// Runs an external tool, exits, if it fails
+fn run(command: &mut std::process::Command) {
+    log::info!("Running {0:?}", command);
+
+    match command.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            log::error!("{0:?} has failed: {1}", command, status);
+            std::process::exit(1);
+        }
+        Err(error) => {
+            log::error!("Unable to run {0:?}: {1}", command, error);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn read_protocol(path: &str) -> robusto::bpir::representation::Protocol {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) => {
+            log::error!("Unable to read {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+    };
+
+    match robusto::bpir::representation::Protocol::from_json(&text) {
+        Ok(protocol) => protocol.resolve_message_extensions().resolve_group_definitions(),
+        Err(error) => {
+            log::error!("Unable to parse {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn read_rewrite_rules(path: Option<&String>) -> RewriteRules {
+    let Some(path) = path else {
+        return RewriteRules::default();
+    };
+
+    match std::fs::read_to_string(path).map(|text| RewriteRules::parse(&text)) {
+        Ok(Ok(rewrite_rules)) => rewrite_rules,
+        Ok(Err(error)) => {
+            log::error!("Unable to parse {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+        Err(error) => {
+            log::error!("Unable to read {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn read_style(path: Option<&String>) -> CodeStyle {
+    let Some(path) = path else {
+        return CodeStyle::default();
+    };
+
+    match std::fs::read_to_string(path).map(|text| serde_json::from_str::<CodeStyle>(&text)) {
+        Ok(Ok(style)) => style,
+        Ok(Err(error)) => {
+            log::error!("Unable to parse {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+        Err(error) => {
+            log::error!("Unable to read {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn read_overrides(path: Option<&String>) -> Overrides {
+    let Some(path) = path else {
+        return Overrides::default();
+    };
+
+    match std::fs::read_to_string(path).map(|text| Overrides::parse(&text)) {
+        Ok(Ok(overrides)) => overrides,
+        Ok(Err(error)) => {
+            log::error!("Unable to parse {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+        Err(error) => {
+            log::error!("Unable to read {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn read_prologue(path: Option<&String>) -> Prologue {
+    let Some(path) = path else {
+        return Prologue::default();
+    };
+
+    match std::fs::read_to_string(path).map(|text| serde_json::from_str::<Prologue>(&text)) {
+        Ok(Ok(prologue)) => prologue,
+        Ok(Err(error)) => {
+            log::error!("Unable to parse {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+        Err(error) => {
+            log::error!("Unable to read {0}: {1}", path, error);
+            std::process::exit(2);
+        }
+    }
+}
+
+fn main() {
+    // Initialize logging
+    env_logger::init();
+
+    let arguments: Vec<String> = std::env::args().collect();
+    let options = match Options::parse(&arguments[1..]) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{0}", error);
+            eprintln!(
+                "Usage: {0} PROTOCOL_FILE OUTPUT_DIRECTORY [--ragel] [--cc COMPILER] [--rewrite-rules RULES_FILE] [--lint-format human|json] [--deny-warnings] [--allow RULE] [--deny RULE] [--style STYLE_FILE] [--prologue PROLOGUE_FILE] [--reproducible] [--source-map] [--overrides OVERRIDES_FILE] [--identifier-prefix PREFIX] [--output-stem STEM] [--preserve-user-code]",
+                arguments[0]
+            );
+            std::process::exit(2);
+        }
+    };
+
+    let protocol = read_protocol(&options.protocol_file);
+    let rewrite_rules = read_rewrite_rules(options.rewrite_rules_file.as_ref());
+    let mut settings = ArtifactSettings {
+        style: read_style(options.style_file.as_ref()),
+        prologue: read_prologue(options.prologue_file.as_ref()),
+        overrides: read_overrides(options.overrides_file.as_ref()),
+    };
+    settings.prologue.timestamp &= !options.reproducible;
+    let mut rewrite_report = RewriteReport::default();
+
+    // Generate nothing from an invalid description
+    let protocol_lint_result = options
+        .validation_config
+        .apply(robusto::bpir::validation::lint_protocol(&protocol));
+
+    match options.lint_format {
+        LintFormat::Human => print!("{0}", protocol_lint_result.render_report(std::io::stdout().is_terminal())),
+        LintFormat::Json => println!("{0}", protocol_lint_result.render_json()),
+    }
+
+    if protocol_lint_result.has_errors() {
+        log::error!("{0} is invalid", options.protocol_file);
+        std::process::exit(1);
+    }
+
+    // Diagnostics refer to the names of the description, the code to mangled ones
+    let protocol = protocol.mangle_identifiers(&options.mangler);
+    let directory = options.output_directory.as_path();
+
+    if let Err(error) = std::fs::create_dir_all(directory) {
+        log::error!("Unable to create {0}: {1}", directory.display(), error);
+        std::process::exit(2);
+    }
+
+    // Render every artifact, and only then write them, so a failure leaves
+    // the previous artifacts intact
+    let naming = &options.naming;
+    let mut artifact_set = match GeneratedArtifactSet::from_protocol(&protocol, naming, &settings) {
+        Ok(artifact_set) => artifact_set,
+        Err(error) => {
+            log::error!("Unable to generate the artifacts: {0}", error);
+            std::process::exit(1);
+        }
+    };
+    artifact_set.rewrite(&rewrite_rules, &mut rewrite_report);
+
+    if options.preserve_user_code {
+        match artifact_set.preserve_user_code(directory) {
+            Ok(warnings) => warnings.iter().for_each(|warning| log::warn!("{0}", warning)),
+            Err(error) => {
+                log::error!("Unable to preserve user code: {0}", error);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    // User code and overrides may have brought in what the generator avoids
+    if protocol.static_memory() {
+        let heap_allocations = artifact_set.heap_allocations();
+
+        for heap_allocation in &heap_allocations {
+            log::error!("{0}, though the protocol requires static memory", heap_allocation);
+        }
+
+        if !heap_allocations.is_empty() {
+            std::process::exit(1);
+        }
+    }
+
+    if options.source_map {
+        let c_ast =
+            robusto::parser_generation::ragel::c::SourceAstNode::from(&protocol).with_header_name(&naming.header());
+        artifact_set.add(
+            &naming.source_map(),
+            ArtifactKind::Documentation,
+            settings.source_map(&c_ast).render_json(),
+        );
+    }
+
+    // Record which rules have shaped the artifacts. Reports are left intact
+    if !rewrite_rules.rules.is_empty() {
+        exit_on_write_error(
+            &naming.rewrite_report(),
+            artifact_set.add_report(&naming.rewrite_report(), &rewrite_report),
+        );
+    }
+
+    exit_on_write_error(directory.to_string_lossy().as_ref(), artifact_set.write_to_directory(directory));
+
+    if !options.ragel {
+        return;
+    }
+
+    run(std::process::Command::new("ragel")
+        .arg("-C")
+        .arg(protocol.ragel_options().code_style.flag())
+        .arg("-o")
+        .arg(directory.join(naming.source()))
+        .arg(directory.join(naming.ragel_source())));
+
+    let Some(ref compiler) = options.compiler else {
+        return;
+    };
+
+    // The source includes the header declaring the types
+    let mut self_test_source = format!("#include \"{0}\"\n", naming.source());
+
+    if protocol.self_test() {
+        self_test_source += &format!(
+            "\nint main(void)\n{{\n\treturn {0}{1}();\n}}\n",
+            protocol.symbol_naming().symbol_prefix,
+            SELF_TEST_FUNCTION_NAME
+        );
+    }
+
+    exit_on_write_error(
+        SELF_TEST_SOURCE_FILE_NAME,
+        std::fs::write(directory.join(SELF_TEST_SOURCE_FILE_NAME), self_test_source).map_err(RobustoError::from),
+    );
+
+    // Built as the revision of C the code is generated for, as GCC and Clang take it
+    let mut command = std::process::Command::new(compiler);
+    command
+        .arg("-Wall")
+        .arg(format!("-std={0}", protocol.c_standard().name()))
+        .arg("-I")
+        .arg(directory);
+
+    // Without a self-test, there is nothing to link and run, so the code is
+    // only checked to compile
+    if !protocol.self_test() {
+        command
+            .arg("-c")
+            .arg("-o")
+            .arg(directory.join(SELF_TEST_EXECUTABLE_FILE_NAME).with_extension("o"))
+            .arg(directory.join(SELF_TEST_SOURCE_FILE_NAME));
+        run(&mut command);
+
+        return;
+    }
+
+    command
+        .arg("-o")
+        .arg(directory.join(SELF_TEST_EXECUTABLE_FILE_NAME))
+        .arg(directory.join(SELF_TEST_SOURCE_FILE_NAME));
+
+    if protocol.uses_runtime() {
+        command.arg(directory.join(robusto::parser_generation::runtime::SOURCE_FILE_NAME));
+    }
+
+    run(&mut command);
+
+    // The self-test returns the index of the first frame it failed to parse,
+    // counting from 1. A message may be tested with more than one frame, so it
+    // is not the number of the message
+    match std::process::Command::new(directory.join(SELF_TEST_EXECUTABLE_FILE_NAME)).status() {
+        Ok(status) if status.success() => println!("Self-test passed"),
+        Ok(status) => {
+            log::error!("Self-test has failed: {0}", status);
+            std::process::exit(1);
+        }
+        Err(error) => {
+            log::error!("Unable to run the self-test: {0}", error);
+            std::process::exit(1);
+        }
+    }
+}
@@ -1 +1 @@
-pub mod basic_c_ragel_generation_from_bpir;
+pub mod example_pipeline;
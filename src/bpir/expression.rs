@@ -0,0 +1,146 @@
+//! Expressions over the fields of a message, for attributes which relate
+//! fields to each other: conditions, lengths, checks.
+//!
+//! Arithmetic is carried out on signed 64-bit integers. Comparisons and logical
+//! operators yield 1 for true, and 0 for false, and any non-zero operand is
+//! considered true. Division by zero yields 0, so an expression always has a
+//! value.
+//!
+//! In JSON, expressions take the externally tagged form, e.g. `len * 4` is
+//! `{"Mul": [{"Field": "len"}, {"Constant": 4}]}`.
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub enum Expression {
+    /// Value of an integer field of the same message
+    Field(std::string::String),
+    Constant(i64),
+
+    Add(Box<Expression>, Box<Expression>),
+    Sub(Box<Expression>, Box<Expression>),
+    Mul(Box<Expression>, Box<Expression>),
+    Div(Box<Expression>, Box<Expression>),
+
+    Eq(Box<Expression>, Box<Expression>),
+    Ne(Box<Expression>, Box<Expression>),
+    Lt(Box<Expression>, Box<Expression>),
+    Le(Box<Expression>, Box<Expression>),
+    Gt(Box<Expression>, Box<Expression>),
+    Ge(Box<Expression>, Box<Expression>),
+
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+}
+
+impl Expression {
+    /// Shorthand for `field == value`, the condition of a "conditional on"
+    /// attribute
+    pub fn field_equals(field_name: &str, value: i64) -> Expression {
+        Expression::Eq(
+            Box::new(Expression::Field(field_name.to_string())),
+            Box::new(Expression::Constant(value)),
+        )
+    }
+
+    /// Names of the fields the expression refers to, in the order of
+    /// appearance, without repetitions
+    pub fn fields(&self) -> std::vec::Vec<&str> {
+        let mut ret = std::vec::Vec::new();
+        self.collect_fields(&mut ret);
+
+        ret
+    }
+
+    fn collect_fields<'a>(&'a self, fields: &mut std::vec::Vec<&'a str>) {
+        match self {
+            Expression::Field(ref name) => {
+                if !fields.contains(&name.as_str()) {
+                    fields.push(name);
+                }
+            }
+            Expression::Constant(_) => {}
+            Expression::Not(ref operand) => operand.collect_fields(fields),
+            _ => {
+                let (left, right) = self.operands().unwrap();
+                left.collect_fields(fields);
+                right.collect_fields(fields);
+            }
+        }
+    }
+
+    /// Renames the fields the expression refers to, e.g. after their names
+    /// have been mangled
+    pub fn rename_fields(&mut self, rename: &dyn Fn(&str) -> std::string::String) {
+        match self {
+            Expression::Field(ref mut name) => *name = rename(name),
+            Expression::Constant(_) => {}
+            Expression::Not(ref mut operand) => operand.rename_fields(rename),
+            Expression::Add(ref mut left, ref mut right)
+            | Expression::Sub(ref mut left, ref mut right)
+            | Expression::Mul(ref mut left, ref mut right)
+            | Expression::Div(ref mut left, ref mut right)
+            | Expression::Eq(ref mut left, ref mut right)
+            | Expression::Ne(ref mut left, ref mut right)
+            | Expression::Lt(ref mut left, ref mut right)
+            | Expression::Le(ref mut left, ref mut right)
+            | Expression::Gt(ref mut left, ref mut right)
+            | Expression::Ge(ref mut left, ref mut right)
+            | Expression::And(ref mut left, ref mut right)
+            | Expression::Or(ref mut left, ref mut right) => {
+                left.rename_fields(rename);
+                right.rename_fields(rename);
+            }
+        }
+    }
+
+    /// Operands of a binary operator
+    pub fn operands(&self) -> Option<(&Expression, &Expression)> {
+        match self {
+            Expression::Add(ref left, ref right)
+            | Expression::Sub(ref left, ref right)
+            | Expression::Mul(ref left, ref right)
+            | Expression::Div(ref left, ref right)
+            | Expression::Eq(ref left, ref right)
+            | Expression::Ne(ref left, ref right)
+            | Expression::Lt(ref left, ref right)
+            | Expression::Le(ref left, ref right)
+            | Expression::Gt(ref left, ref right)
+            | Expression::Ge(ref left, ref right)
+            | Expression::And(ref left, ref right)
+            | Expression::Or(ref left, ref right) => Some((left, right)),
+            _ => None,
+        }
+    }
+
+    /// Evaluates the expression, looking the fields' values up. `None`, if a
+    /// field has no value
+    pub fn evaluate(&self, value_of: &dyn Fn(&str) -> Option<i64>) -> Option<i64> {
+        if let Expression::Not(ref operand) = self {
+            return Some(i64::from(operand.evaluate(value_of)? == 0));
+        }
+
+        let (left, right) = match self {
+            Expression::Field(ref name) => return value_of(name),
+            Expression::Constant(value) => return Some(*value),
+            _ => self.operands().unwrap(),
+        };
+        let left = left.evaluate(value_of)?;
+        let right = right.evaluate(value_of)?;
+
+        Some(match self {
+            Expression::Add(_, _) => left.wrapping_add(right),
+            Expression::Sub(_, _) => left.wrapping_sub(right),
+            Expression::Mul(_, _) => left.wrapping_mul(right),
+            Expression::Div(_, _) => left.checked_div(right).unwrap_or(0i64),
+            Expression::Eq(_, _) => i64::from(left == right),
+            Expression::Ne(_, _) => i64::from(left != right),
+            Expression::Lt(_, _) => i64::from(left < right),
+            Expression::Le(_, _) => i64::from(left <= right),
+            Expression::Gt(_, _) => i64::from(left > right),
+            Expression::Ge(_, _) => i64::from(left >= right),
+            Expression::And(_, _) => i64::from(left != 0 && right != 0),
+            Expression::Or(_, _) => i64::from(left != 0 || right != 0),
+            _ => unreachable!(),
+        })
+    }
+}
@@ -4,5 +4,7 @@
 //! binary protocol: CRC checksums, preambles and parser synchronization,
 //! conditional interpretation, etc.
 
+pub mod expression;
+pub mod pattern;
 pub mod representation;
 pub mod validation;
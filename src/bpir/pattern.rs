@@ -0,0 +1,833 @@
+//! Patterns of regex fields, i.e. the regex dialect the bytes of a field are
+//! matched against. Patterns match bytes, not characters:
+//!
+//! - a byte matches itself, unless it is one of `\.[](){}|*+?^$`;
+//! - `\xHH` matches the byte of the hexadecimal value, and `\0`, `\a`, `\b`,
+//!   `\t`, `\n`, `\v`, `\f`, `\r` match the control bytes C names so. Note
+//!   that `\b` is a backspace, not a word boundary;
+//! - `\d`, `\w`, `\s` match ASCII digits, word characters, and whitespace;
+//! - a backslash followed by any other punctuation matches that character;
+//! - `.` matches any byte, line feeds included;
+//! - `[...]` matches a byte of the set, which may hold ranges (`a-z`), and
+//!   escapes. `[^...]` matches a byte out of it;
+//! - `(...)` groups, and `|` separates alternatives;
+//! - `*`, `+`, `?`, `{n}`, `{n,}`, `{,m}`, `{n,m}` repeat the preceding item.
+//!
+//! A field is matched as a whole, so anchors are not supported. Neither are
+//! lazy repetitions, or backreferences.
+
+use std::collections::BTreeSet;
+
+/// Repetitions are unrolled for the analysis, so their counts are limited
+const MAX_REPETITION_COUNT: usize = 4096usize;
+
+/// A set of byte values
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ByteSet([u64; 4]);
+
+impl ByteSet {
+    pub fn empty() -> ByteSet {
+        ByteSet::default()
+    }
+
+    pub fn full() -> ByteSet {
+        ByteSet([u64::MAX; 4])
+    }
+
+    pub fn range(first: u8, last: u8) -> ByteSet {
+        let mut ret = ByteSet::empty();
+
+        for byte in first..=last {
+            ret.insert(byte);
+        }
+
+        ret
+    }
+
+    pub fn insert(&mut self, byte: u8) {
+        self.0[usize::from(byte / 64)] |= 1u64 << (byte % 64);
+    }
+
+    pub fn contains(&self, byte: u8) -> bool {
+        self.0[usize::from(byte / 64)] & (1u64 << (byte % 64)) != 0
+    }
+
+    pub fn union(&self, other: &ByteSet) -> ByteSet {
+        ByteSet(std::array::from_fn(|i| self.0[i] | other.0[i]))
+    }
+
+    pub fn intersection(&self, other: &ByteSet) -> ByteSet {
+        ByteSet(std::array::from_fn(|i| self.0[i] & other.0[i]))
+    }
+
+    pub fn complement(&self) -> ByteSet {
+        ByteSet(std::array::from_fn(|i| !self.0[i]))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|word| *word == 0)
+    }
+
+    /// The lowest byte of the set
+    pub fn first(&self) -> Option<u8> {
+        (0u8..=255).find(|byte| self.contains(*byte))
+    }
+
+    /// Runs of consecutive bytes, as inclusive ranges, in ascending order
+    pub fn ranges(&self) -> std::vec::Vec<(u8, u8)> {
+        let mut ret: std::vec::Vec<(u8, u8)> = std::vec::Vec::new();
+
+        for byte in (0u8..=255).filter(|byte| self.contains(*byte)) {
+            match ret.last_mut() {
+                Some((_, last)) if *last as usize + 1 == byte as usize => *last = byte,
+                _ => ret.push((byte, byte)),
+            }
+        }
+
+        ret
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    /// Any one byte of the set
+    Bytes(ByteSet),
+    Sequence(std::vec::Vec<Pattern>),
+    Alternatives(std::vec::Vec<Pattern>),
+
+    /// The pattern, repeated `min` times at least, and `max` times at most,
+    /// if bounded
+    Repetition {
+        pattern: Box<Pattern>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+impl Pattern {
+    /// Parses the text of a regex field, see the module's documentation for
+    /// the syntax
+    pub fn parse(text: &str) -> Result<Pattern, std::string::String> {
+        let mut parser = Parser {
+            text: text.as_bytes(),
+            position: 0usize,
+        };
+        let ret = parser.alternatives()?;
+
+        match parser.peek() {
+            None => Ok(ret),
+            Some(b')') => Err(format!("unmatched ')' at {0}", parser.position)),
+            Some(byte) => Err(format!("unexpected '{0}' at {1}", char::from(byte), parser.position)),
+        }
+    }
+
+    /// A pattern matching the bytes, and nothing else
+    pub fn from_bytes(bytes: &[u8]) -> Pattern {
+        let mut byte_sets = bytes.iter().map(|byte| {
+            let mut byte_set = ByteSet::empty();
+            byte_set.insert(*byte);
+
+            Pattern::Bytes(byte_set)
+        });
+
+        match bytes.len() {
+            1 => byte_sets.next().unwrap(),
+            _ => Pattern::Sequence(byte_sets.collect()),
+        }
+    }
+
+    /// The only byte sequence the pattern matches, if it matches just one
+    pub fn literal(&self) -> Option<std::vec::Vec<u8>> {
+        match self {
+            Pattern::Bytes(ref byte_set) => match byte_set.ranges()[..] {
+                [(first, last)] if first == last => Some(vec![first]),
+                _ => None,
+            },
+            Pattern::Sequence(ref patterns) => {
+                let mut ret = std::vec::Vec::new();
+
+                for pattern in patterns {
+                    ret.extend(pattern.literal()?);
+                }
+
+                Some(ret)
+            }
+            Pattern::Alternatives(ref patterns) => {
+                let first = patterns.first()?.literal()?;
+
+                match patterns[1..].iter().all(|pattern| pattern.literal().as_ref() == Some(&first)) {
+                    true => Some(first),
+                    false => None,
+                }
+            }
+            Pattern::Repetition { ref pattern, min, max } if Some(*min) == *max => Some(pattern.literal()?.repeat(*min)),
+            Pattern::Repetition { .. } => None,
+        }
+    }
+
+    /// Length of the shortest match
+    pub fn min_length(&self) -> usize {
+        match self {
+            Pattern::Bytes(_) => 1usize,
+            Pattern::Sequence(ref patterns) => patterns.iter().map(Pattern::min_length).sum(),
+            Pattern::Alternatives(ref patterns) => patterns.iter().map(Pattern::min_length).min().unwrap_or(0usize),
+            Pattern::Repetition { ref pattern, min, .. } => pattern.min_length() * min,
+        }
+    }
+
+    /// Length of the longest match, if there is one
+    pub fn max_length(&self) -> Option<usize> {
+        match self {
+            Pattern::Bytes(_) => Some(1usize),
+            Pattern::Sequence(ref patterns) => patterns.iter().map(Pattern::max_length).sum(),
+            Pattern::Alternatives(ref patterns) => patterns
+                .iter()
+                .map(Pattern::max_length)
+                .try_fold(0usize, |ret, length| Some(ret.max(length?))),
+            Pattern::Repetition { ref pattern, max, .. } => match (pattern.max_length()?, max) {
+                (0, _) => Some(0usize),
+                (length, Some(max)) => Some(length * max),
+                (_, None) => None,
+            },
+        }
+    }
+
+    /// The shortest match, made of the lowest bytes the sets allow, e.g. for
+    /// canned frames
+    pub fn sample(&self) -> std::vec::Vec<u8> {
+        match self {
+            Pattern::Bytes(ref byte_set) => byte_set.first().into_iter().collect(),
+            Pattern::Sequence(ref patterns) => patterns.iter().flat_map(Pattern::sample).collect(),
+            Pattern::Alternatives(ref patterns) => patterns
+                .iter()
+                .map(Pattern::sample)
+                .min_by_key(std::vec::Vec::len)
+                .unwrap_or_default(),
+            Pattern::Repetition { ref pattern, min, .. } => pattern.sample().repeat(*min),
+        }
+    }
+
+    /// Pattern matching the prefixes of the matches, the matches and the
+    /// empty sequence included, e.g. to tell input cut off within a field
+    /// from a mismatch
+    pub fn prefixes(&self) -> Pattern {
+        match self {
+            Pattern::Bytes(_) => Pattern::Alternatives(vec![self.clone(), Pattern::Sequence(vec![])]),
+            Pattern::Sequence(ref patterns) => match patterns.split_last() {
+                Some((last, rest)) => rest.iter().rev().fold(last.prefixes(), |ret, pattern| {
+                    Pattern::Alternatives(vec![pattern.prefixes(), Pattern::Sequence(vec![pattern.clone(), ret])])
+                }),
+                None => Pattern::Sequence(vec![]),
+            },
+            Pattern::Alternatives(ref patterns) => {
+                Pattern::Alternatives(patterns.iter().map(Pattern::prefixes).collect())
+            }
+            // A number of whole repetitions, and a prefix of the next one
+            Pattern::Repetition { ref pattern, max, .. } => match max {
+                Some(0) => Pattern::Sequence(vec![]),
+                Some(1) => pattern.prefixes(),
+                _ => Pattern::Sequence(vec![
+                    Pattern::Repetition {
+                        pattern: pattern.clone(),
+                        min: 0usize,
+                        max: max.map(|max| max - 1),
+                    },
+                    pattern.prefixes(),
+                ]),
+            },
+        }
+    }
+
+    /// Bytes a match of the pattern may go on with, while still being a
+    /// prefix of another match
+    pub fn continuation_bytes(&self) -> ByteSet {
+        let automaton = Automaton::from(self);
+        let mut ret = ByteSet::empty();
+
+        for state in automaton.states() {
+            if automaton.is_accepting(&state) {
+                ret = ret.union(&automaton.next_bytes(&state));
+            }
+        }
+
+        ret
+    }
+
+    /// Bytes the matches of the pattern start with
+    pub fn first_bytes(&self) -> ByteSet {
+        let automaton = Automaton::from(self);
+
+        automaton.next_bytes(&automaton.start())
+    }
+
+    /// Whether a match of the pattern is also a prefix of a match of the
+    /// `other` pattern, i.e. a parser having matched this one can not tell
+    /// whether the other one is underway
+    pub fn is_prefix_of(&self, other: &Pattern) -> bool {
+        let (automaton, other_automaton) = (Automaton::from(self), Automaton::from(other));
+        let alphabet = alphabet(automaton.byte_sets.iter().chain(&other_automaton.byte_sets));
+        let start = (automaton.start(), other_automaton.start());
+        let mut visited = BTreeSet::from([start.clone()]);
+        let mut pending = vec![start];
+
+        while let Some((state, other_state)) = pending.pop() {
+            if automaton.is_accepting(&state) {
+                return true;
+            }
+
+            for byte in &alphabet {
+                let next = (automaton.next(&state, *byte), other_automaton.next(&other_state, *byte));
+
+                if !next.0.is_empty() && !next.1.is_empty() && visited.insert(next.clone()) {
+                    pending.push(next);
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Ragel machine expression matching the same bytes. It is
+    /// parenthesized, unless actions may be embedded into it as is
+    pub fn ragel(&self) -> std::string::String {
+        match self.ragel_term() {
+            (term, true) => term,
+            (term, false) => format!("( {0} )", term),
+        }
+    }
+
+    /// The expression, and whether operators may be applied to it without
+    /// parentheses
+    fn ragel_term(&self) -> (std::string::String, bool) {
+        let is_quotable = |byte: u8| (byte.is_ascii_graphic() || byte == b' ') && byte != b'\'' && byte != b'\\';
+        let single = |byte: u8| match is_quotable(byte) {
+            true => format!("'{0}'", char::from(byte)),
+            false => format!("0x{0:02X}", byte),
+        };
+
+        match self {
+            Pattern::Bytes(ref byte_set) if *byte_set == ByteSet::full() => ("any".to_string(), true),
+            Pattern::Bytes(ref byte_set) => {
+                let ranges = byte_set
+                    .ranges()
+                    .into_iter()
+                    .map(|(first, last)| match (first == last, is_quotable(first) && is_quotable(last)) {
+                        (true, _) => single(first),
+                        (false, true) => format!("'{0}'..'{1}'", char::from(first), char::from(last)),
+                        (false, false) => format!("0x{0:02X}..0x{1:02X}", first, last),
+                    })
+                    .collect::<std::vec::Vec<_>>();
+
+                match ranges[..] {
+                    [ref range] => (range.clone(), !range.contains("..")),
+                    _ => (format!("( {0} )", ranges.join(" | ")), true),
+                }
+            }
+            Pattern::Sequence(ref patterns) => {
+                // Printable runs read better as strings
+                let mut terms = std::vec::Vec::new();
+                let mut run = std::string::String::new();
+
+                for pattern in patterns {
+                    match pattern.literal() {
+                        Some(ref bytes) if bytes.len() == 1 && is_quotable(bytes[0]) => run.push(char::from(bytes[0])),
+                        _ => {
+                            if !run.is_empty() {
+                                terms.push((format!("'{0}'", std::mem::take(&mut run)), true));
+                            }
+
+                            terms.push(pattern.ragel_term());
+                        }
+                    }
+                }
+
+                if !run.is_empty() {
+                    terms.push((format!("'{0}'", run), true));
+                }
+
+                match terms.len() {
+                    0 => ("zlen".to_string(), true),
+                    1 => terms.pop().unwrap(),
+                    _ => (terms.into_iter().map(|(term, _)| term).collect::<std::vec::Vec<_>>().join(" "), false),
+                }
+            }
+            Pattern::Alternatives(ref patterns) => (
+                format!("( {0} )", patterns.iter().map(Pattern::ragel).collect::<std::vec::Vec<_>>().join(" | ")),
+                true,
+            ),
+            Pattern::Repetition { ref pattern, min, max } => {
+                let operand = match pattern.ragel_term() {
+                    (term, true) => term,
+                    (term, false) => format!("( {0} )", term),
+                };
+                let operator = match (min, max) {
+                    (0, None) => "*".to_string(),
+                    (1, None) => "+".to_string(),
+                    (0, Some(1)) => "?".to_string(),
+                    (min, Some(max)) if min == max => format!("{{{0}}}", min),
+                    (min, None) => format!("{{{0},}}", min),
+                    (0, Some(max)) => format!("{{,{0}}}", max),
+                    (min, Some(max)) => format!("{{{0},{1}}}", min, max),
+                };
+
+                (format!("{0}{1}", operand, operator), true)
+            }
+        }
+    }
+
+    /// ECMAScript regex source matching the same bytes in a string of char
+    /// codes 0..255, e.g. for `new RegExp(source, "y")`
+    pub fn ecmascript(&self) -> std::string::String {
+        let single = |byte: u8| match byte.is_ascii_alphanumeric() {
+            true => char::from(byte).to_string(),
+            false => format!("\\x{0:02X}", byte),
+        };
+
+        match self {
+            Pattern::Bytes(ref byte_set) => match byte_set.ranges()[..] {
+                [(first, last)] if first == last => single(first),
+                ref ranges => format!(
+                    "[{0}]",
+                    ranges
+                        .iter()
+                        .map(|(first, last)| match first == last {
+                            true => single(*first),
+                            false => format!("{0}-{1}", single(*first), single(*last)),
+                        })
+                        .collect::<std::string::String>()
+                ),
+            },
+            Pattern::Sequence(ref patterns) => patterns.iter().map(Pattern::ecmascript).collect(),
+            Pattern::Alternatives(ref patterns) => format!(
+                "(?:{0})",
+                patterns.iter().map(Pattern::ecmascript).collect::<std::vec::Vec<_>>().join("|")
+            ),
+            Pattern::Repetition { ref pattern, min, max } => {
+                let operand = match **pattern {
+                    Pattern::Bytes(_) | Pattern::Alternatives(_) => pattern.ecmascript(),
+                    _ => format!("(?:{0})", pattern.ecmascript()),
+                };
+                let operator = match (min, max) {
+                    (0, None) => "*".to_string(),
+                    (1, None) => "+".to_string(),
+                    (0, Some(1)) => "?".to_string(),
+                    (min, Some(max)) if min == max => format!("{{{0}}}", min),
+                    (min, None) => format!("{{{0},}}", min),
+                    (min, Some(max)) => format!("{{{0},{1}}}", min, max),
+                };
+
+                format!("{0}{1}", operand, operator)
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.position).copied()
+    }
+
+    fn take(&mut self) -> Option<u8> {
+        let ret = self.peek();
+        self.position += usize::from(ret.is_some());
+
+        ret
+    }
+
+    fn alternatives(&mut self) -> Result<Pattern, std::string::String> {
+        let mut ret = vec![self.sequence()?];
+
+        while self.peek() == Some(b'|') {
+            self.take();
+            ret.push(self.sequence()?);
+        }
+
+        Ok(match ret.len() {
+            1 => ret.pop().unwrap(),
+            _ => Pattern::Alternatives(ret),
+        })
+    }
+
+    fn sequence(&mut self) -> Result<Pattern, std::string::String> {
+        let mut ret = std::vec::Vec::new();
+
+        while let Some(byte) = self.peek() {
+            let item = match byte {
+                b'|' | b')' => break,
+                b'*' | b'+' | b'?' | b'{' => return Err(format!("nothing to repeat at {0}", self.position)),
+                b'^' | b'$' => return Err(format!("anchor '{0}' at {1} is not supported", char::from(byte), self.position)),
+                _ => self.atom()?,
+            };
+            ret.push(self.repetition(item)?);
+        }
+
+        Ok(match ret.len() {
+            1 => ret.pop().unwrap(),
+            _ => Pattern::Sequence(ret),
+        })
+    }
+
+    fn atom(&mut self) -> Result<Pattern, std::string::String> {
+        let start = self.position;
+
+        match self.take() {
+            Some(b'(') => {
+                if self.text[self.position..].starts_with(b"?:") {
+                    self.position += 2;
+                }
+
+                let ret = self.alternatives()?;
+
+                match self.take() {
+                    Some(b')') => Ok(ret),
+                    _ => Err(format!("unmatched '(' at {0}", start)),
+                }
+            }
+            Some(b'[') => self.class(start),
+            Some(b'.') => Ok(Pattern::Bytes(ByteSet::full())),
+            Some(b'\\') => Ok(Pattern::Bytes(self.escape(start)?)),
+            Some(byte) => Ok(Pattern::from_bytes(&[byte])),
+            None => Err(format!("unexpected end at {0}", start)),
+        }
+    }
+
+    /// Parses the quantifiers following an item, if any
+    fn repetition(&mut self, item: Pattern) -> Result<Pattern, std::string::String> {
+        let start = self.position;
+        let (min, max) = match self.peek() {
+            Some(b'*') => (0usize, None),
+            Some(b'+') => (1usize, None),
+            Some(b'?') => (0usize, Some(1usize)),
+            Some(b'{') => {
+                let end = self.text[start..]
+                    .iter()
+                    .position(|byte| *byte == b'}')
+                    .map(|length| start + length)
+                    .ok_or_else(|| format!("unmatched '{{' at {0}", start))?;
+                let counts = std::str::from_utf8(&self.text[start + 1..end]).unwrap_or_default();
+                let count = |text: &str| text.trim().parse::<usize>();
+                let invalid = || format!("invalid repetition '{{{0}}}' at {1}", counts, start);
+                self.position = end;
+
+                match counts.split_once(',') {
+                    None => {
+                        let count = count(counts).map_err(|_| invalid())?;
+                        (count, Some(count))
+                    }
+                    Some((min, "")) => (count(min).map_err(|_| invalid())?, None),
+                    Some(("", max)) => (0usize, Some(count(max).map_err(|_| invalid())?)),
+                    Some((min, max)) => (count(min).map_err(|_| invalid())?, Some(count(max).map_err(|_| invalid())?)),
+                }
+            }
+            _ => return Ok(item),
+        };
+        self.take();
+
+        if max.is_some_and(|max| max < min) {
+            return Err(format!("repetition at {0} has its bounds reversed", start));
+        }
+
+        if max.unwrap_or(min) > MAX_REPETITION_COUNT {
+            return Err(format!(
+                "repetition at {0} exceeds {1} times",
+                start, MAX_REPETITION_COUNT
+            ));
+        }
+
+        match self.peek() {
+            Some(b'?') => Err(format!("lazy repetition at {0} is not supported", start)),
+            Some(b'*' | b'+' | b'{') => Err(format!("nothing to repeat at {0}", self.position)),
+            _ => Ok(Pattern::Repetition {
+                pattern: Box::new(item),
+                min,
+                max,
+            }),
+        }
+    }
+
+    /// Parses an escape, whose backslash is at `start`
+    fn escape(&mut self, start: usize) -> Result<ByteSet, std::string::String> {
+        let single = |byte: u8| ByteSet::range(byte, byte);
+        let byte_set = match self.take() {
+            Some(b'x') => {
+                let digits = self.text.get(self.position..self.position + 2).unwrap_or_default();
+                let value = std::str::from_utf8(digits)
+                    .ok()
+                    .filter(|digits| digits.bytes().all(|byte| byte.is_ascii_hexdigit()))
+                    .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                    .ok_or_else(|| format!("escape at {0} takes 2 hexadecimal digits", start))?;
+                self.position += 2;
+
+                single(value)
+            }
+            Some(b'0') => single(0x00),
+            Some(b'a') => single(0x07),
+            Some(b'b') => single(0x08),
+            Some(b't') => single(b'\t'),
+            Some(b'n') => single(b'\n'),
+            Some(b'v') => single(0x0B),
+            Some(b'f') => single(0x0C),
+            Some(b'r') => single(b'\r'),
+            Some(b'd') => ByteSet::range(b'0', b'9'),
+            Some(b'w') => ByteSet::range(b'a', b'z')
+                .union(&ByteSet::range(b'A', b'Z'))
+                .union(&ByteSet::range(b'0', b'9'))
+                .union(&single(b'_')),
+            Some(b's') => ByteSet::range(b'\t', b'\r').union(&single(b' ')),
+            Some(byte) if byte.is_ascii_punctuation() || byte == b' ' => single(byte),
+            Some(byte) => {
+                return Err(format!("escape '\\{0}' at {1} is not supported", char::from(byte), start));
+            }
+            None => return Err(format!("unexpected end at {0}", self.position)),
+        };
+
+        Ok(byte_set)
+    }
+
+    /// Parses a set, whose opening bracket is at `start`
+    fn class(&mut self, start: usize) -> Result<Pattern, std::string::String> {
+        let negated = self.peek() == Some(b'^');
+        self.position += usize::from(negated);
+        let mut ret = ByteSet::empty();
+
+        loop {
+            let item_start = self.position;
+            let first = match self.take() {
+                Some(b']') if item_start > start + 1 + usize::from(negated) => break,
+                Some(b'\\') => self.escape(item_start)?,
+                Some(byte) if byte.is_ascii() => ByteSet::range(byte, byte),
+                Some(_) => return Err(format!("non-ASCII character in the set at {0}", start)),
+                None => return Err(format!("unmatched '[' at {0}", start)),
+            };
+
+            let is_range = self.peek() == Some(b'-') && self.text.get(self.position + 1).is_some_and(|byte| *byte != b']');
+
+            if !is_range {
+                ret = ret.union(&first);
+                continue;
+            }
+
+            self.take();
+            let last_start = self.position;
+            let last = match self.take() {
+                Some(b'\\') => self.escape(last_start)?,
+                Some(byte) if byte.is_ascii() => ByteSet::range(byte, byte),
+                _ => return Err(format!("non-ASCII character in the set at {0}", start)),
+            };
+
+            match (&first.ranges()[..], &last.ranges()[..]) {
+                (&[(first, first_end)], &[(last, last_end)]) if first == first_end && last == last_end && first <= last => {
+                    ret = ret.union(&ByteSet::range(first, last));
+                }
+                _ => return Err(format!("invalid range in the set at {0}", item_start)),
+            }
+        }
+
+        if negated {
+            ret = ret.complement();
+        }
+
+        match ret.is_empty() {
+            true => Err(format!("set at {0} matches no byte", start)),
+            false => Ok(Pattern::Bytes(ret)),
+        }
+    }
+}
+
+/// A byte of each class of bytes the sets do not tell apart
+fn alphabet<'a>(byte_sets: impl Iterator<Item = &'a ByteSet>) -> std::vec::Vec<u8> {
+    let mut boundaries = BTreeSet::from([0usize]);
+
+    for byte_set in byte_sets {
+        for (first, last) in byte_set.ranges() {
+            boundaries.insert(usize::from(first));
+            boundaries.insert(usize::from(last) + 1);
+        }
+    }
+
+    boundaries.into_iter().filter(|boundary| *boundary < 256).map(|boundary| boundary as u8).collect()
+}
+
+/// Linear form of a pattern, whose repetitions are unrolled, and whose byte
+/// sets are numbered positions
+enum Linear {
+    Position(usize),
+    Sequence(std::vec::Vec<Linear>),
+    Alternatives(std::vec::Vec<Linear>),
+    Star(Box<Linear>),
+    Optional(Box<Linear>),
+}
+
+/// Position (Glushkov) automaton of a pattern. A state is the set of positions
+/// the bytes read so far may end on, the start one being a position of its own
+struct Automaton {
+    byte_sets: std::vec::Vec<ByteSet>,
+
+    /// Positions which may follow each one, the start position last
+    follow: std::vec::Vec<BTreeSet<usize>>,
+
+    /// Positions a match may end on
+    last: BTreeSet<usize>,
+}
+
+impl From<&Pattern> for Automaton {
+    fn from(pattern: &Pattern) -> Self {
+        let mut byte_sets = std::vec::Vec::new();
+        let linear = Automaton::linearize(pattern, &mut byte_sets);
+        let mut follow = vec![BTreeSet::new(); byte_sets.len() + 1];
+        let (nullable, first, mut last) = Automaton::analyze(&linear, &mut follow);
+        let start = byte_sets.len();
+        follow[start] = first;
+
+        if nullable {
+            last.insert(start);
+        }
+
+        Automaton {
+            byte_sets,
+            follow,
+            last,
+        }
+    }
+}
+
+impl Automaton {
+    fn linearize(pattern: &Pattern, byte_sets: &mut std::vec::Vec<ByteSet>) -> Linear {
+        match pattern {
+            Pattern::Bytes(ref byte_set) => {
+                byte_sets.push(*byte_set);
+
+                Linear::Position(byte_sets.len() - 1)
+            }
+            Pattern::Sequence(ref patterns) => {
+                Linear::Sequence(patterns.iter().map(|pattern| Automaton::linearize(pattern, byte_sets)).collect())
+            }
+            Pattern::Alternatives(ref patterns) => {
+                Linear::Alternatives(patterns.iter().map(|pattern| Automaton::linearize(pattern, byte_sets)).collect())
+            }
+            Pattern::Repetition { ref pattern, min, max } => {
+                let mut ret = (0..*min)
+                    .map(|_| Automaton::linearize(pattern, byte_sets))
+                    .collect::<std::vec::Vec<_>>();
+
+                match max {
+                    Some(max) => ret.extend(
+                        (*min..*max).map(|_| Linear::Optional(Box::new(Automaton::linearize(pattern, byte_sets)))),
+                    ),
+                    None => ret.push(Linear::Star(Box::new(Automaton::linearize(pattern, byte_sets)))),
+                }
+
+                Linear::Sequence(ret)
+            }
+        }
+    }
+
+    /// Whether the form matches nothing, and its first and last positions.
+    /// Records the positions following each other
+    fn analyze(linear: &Linear, follow: &mut [BTreeSet<usize>]) -> (bool, BTreeSet<usize>, BTreeSet<usize>) {
+        match linear {
+            Linear::Position(position) => (false, BTreeSet::from([*position]), BTreeSet::from([*position])),
+            Linear::Sequence(ref items) => {
+                let (mut nullable, mut first, mut last) = (true, BTreeSet::<usize>::new(), BTreeSet::<usize>::new());
+
+                for item in items {
+                    let (item_nullable, item_first, item_last) = Automaton::analyze(item, follow);
+
+                    for position in &last {
+                        follow[*position].extend(item_first.iter().copied());
+                    }
+
+                    if nullable {
+                        first.extend(item_first.iter().copied());
+                    }
+
+                    if !item_nullable {
+                        last.clear();
+                    }
+
+                    last.extend(item_last);
+                    nullable &= item_nullable;
+                }
+
+                (nullable, first, last)
+            }
+            Linear::Alternatives(ref items) => {
+                let (mut nullable, mut first, mut last) = (false, BTreeSet::<usize>::new(), BTreeSet::<usize>::new());
+
+                for item in items {
+                    let (item_nullable, item_first, item_last) = Automaton::analyze(item, follow);
+                    nullable |= item_nullable;
+                    first.extend(item_first);
+                    last.extend(item_last);
+                }
+
+                (nullable, first, last)
+            }
+            Linear::Star(ref item) => {
+                let (_, first, last) = Automaton::analyze(item, follow);
+
+                for position in &last {
+                    follow[*position].extend(first.iter().copied());
+                }
+
+                (true, first, last)
+            }
+            Linear::Optional(ref item) => {
+                let (_, first, last) = Automaton::analyze(item, follow);
+
+                (true, first, last)
+            }
+        }
+    }
+
+    fn start(&self) -> BTreeSet<usize> {
+        BTreeSet::from([self.byte_sets.len()])
+    }
+
+    fn is_accepting(&self, state: &BTreeSet<usize>) -> bool {
+        state.iter().any(|position| self.last.contains(position))
+    }
+
+    fn next(&self, state: &BTreeSet<usize>, byte: u8) -> BTreeSet<usize> {
+        state
+            .iter()
+            .flat_map(|position| self.follow[*position].iter().copied())
+            .filter(|position| self.byte_sets[*position].contains(byte))
+            .collect()
+    }
+
+    /// Bytes the state may go on with
+    fn next_bytes(&self, state: &BTreeSet<usize>) -> ByteSet {
+        state
+            .iter()
+            .flat_map(|position| self.follow[*position].iter())
+            .fold(ByteSet::empty(), |ret, position| ret.union(&self.byte_sets[*position]))
+    }
+
+    /// States reachable from the start one
+    fn states(&self) -> std::vec::Vec<BTreeSet<usize>> {
+        let alphabet = alphabet(self.byte_sets.iter());
+        let mut ret = vec![self.start()];
+        let mut visited = BTreeSet::from([self.start()]);
+        let mut i = 0usize;
+
+        while i < ret.len() {
+            for byte in &alphabet {
+                let next = self.next(&ret[i], *byte);
+
+                if !next.is_empty() && visited.insert(next.clone()) {
+                    ret.push(next);
+                }
+            }
+
+            i += 1;
+        }
+
+        ret
+    }
+}
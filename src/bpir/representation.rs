@@ -11,9 +11,9 @@
 //!
 //! Consider the message of the following structure:
 //!
-//! ```
+//! ```text
 //! struct SimpleUserMessage {
-//!	    sync: u8 = 0xFE,
+//!     sync: u8 = 0xFE,
 //!     payload: u8[4],
 //!     checksum_crc32: u32,
 //! }
@@ -27,21 +27,30 @@
 //! use robusto::bpir::representation::Message;
 //! use robusto::bpir::representation::Field;
 //! use robusto::bpir::representation::FieldAttribute;
+//! use robusto::bpir::representation::FieldType;
+//! use robusto::bpir::representation::MaxLengthFieldAttribute;
+//! use robusto::bpir::representation::RegexFieldType;
 //! let bpir = Message {
 //!     name: std::string::String::from("SimpleUserMessage"),
 //!     fields: vec![
 //!         // `sync`
 //!         Field {
 //!             name: std::string::String::from("sync"),
+//!             field_type: FieldType::Regex(RegexFieldType {
+//!                 regex: std::string::String::from("\\xfe"),
+//!             }),
 //!             attributes: vec![
-//!                 FieldAttribute::ConstSequence(vec![0xfe as u8]),
+//!                 FieldAttribute::MaxLength(MaxLengthFieldAttribute { value: 1usize }),
 //!             ]
 //!         },
 //!         // `payload`
 //!         Field {
 //!             name: std::string::String::from("payload"),
+//!             field_type: FieldType::Regex(RegexFieldType {
+//!                 regex: std::string::String::from(".{4}"),
+//!             }),
 //!             attributes: vec![
-//!                 FieldAttribute::Length(4usize),
+//!                 FieldAttribute::MaxLength(MaxLengthFieldAttribute { value: 4usize }),
 //!             ]
 //!         },
 //!     ],
@@ -50,10 +59,11 @@
 //! ```
 //!
 
+pub use crate::bpir::expression::Expression;
+use crate::utility::identifier::{MangledNames, Mangler};
 pub use std;
-use log;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Deserialize)]
 pub struct MaxLengthFieldAttribute {
     pub value: usize,
 }
@@ -64,77 +74,2379 @@ impl MaxLengthFieldAttribute {
     }
 }
 
+/// Declares that an integer field represents a physical value:
+/// `physical = raw * factor + offset`
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ScaleFieldAttribute {
+    pub factor: f64,
+    pub offset: f64,
+}
+
+/// Restricts the values an integer field may take, both bounds inclusive.
+/// Values outside of the range are reported by the parser as errors.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct RangeFieldAttribute {
+    pub min: i64,
+    pub max: i64,
+}
+
+/// Makes the field one of the alternative interpretations of a byte region.
+/// The field is only present, if an earlier integer field has the given
+/// value. Consecutive fields conditional on the same field share the region.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ConditionalOnFieldAttribute {
+    /// Name of the field which selects the interpretation
+    pub field: std::string::String,
+    pub value: i64,
+}
+
+/// Parameters of a CRC in terms of the Rocksoft model. Input bytes and the
+/// result are either both reflected, or both not. Descriptions give either
+/// the parameters, or the name of a common algorithm, see `CrcPreset`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(from = "CrcAlgorithmDescription")]
+pub struct CrcAlgorithm {
+    /// Width of the CRC in bits: 8, 16, 32, or 64
+    pub width: usize,
+
+    /// Generator polynomial, not reflected, without the leading term
+    pub polynomial: u64,
+    pub init: u64,
+    pub reflected: bool,
+    pub xor_out: u64,
+}
+
+/// Common CRC algorithms, named after the catalogue of parametrised CRC
+/// algorithms. Each comment gives the CRC of the ASCII string `123456789`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum CrcPreset {
+    /// CRC-8/SMBUS, 0xF4
+    Crc8,
+
+    /// CRC-16/IBM-3740, also known as CRC-16-CCITT-FALSE, 0x29B1
+    Crc16Ccitt,
+
+    /// CRC-16/KERMIT, the reflected CRC-16-CCITT, 0x2189
+    Crc16Kermit,
+
+    /// CRC-16/MODBUS, 0x4B37
+    Crc16Modbus,
+
+    /// CRC-32/ISO-HDLC, as Ethernet and zlib compute it, 0xCBF43926
+    Crc32,
+}
+
+impl CrcPreset {
+    pub fn algorithm(&self) -> CrcAlgorithm {
+        let (width, polynomial, init, reflected, xor_out) = match self {
+            CrcPreset::Crc8 => (8, 0x07, 0x00, false, 0x00),
+            CrcPreset::Crc16Ccitt => (16, 0x1021, 0xFFFF, false, 0x0000),
+            CrcPreset::Crc16Kermit => (16, 0x1021, 0x0000, true, 0x0000),
+            CrcPreset::Crc16Modbus => (16, 0x8005, 0xFFFF, true, 0x0000),
+            CrcPreset::Crc32 => (32, 0x04C1_1DB7, 0xFFFF_FFFF, true, 0xFFFF_FFFF),
+        };
+
+        CrcAlgorithm {
+            width,
+            polynomial,
+            init,
+            reflected,
+            xor_out,
+        }
+    }
+}
+
+/// A CRC algorithm, as descriptions spell it
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum CrcAlgorithmDescription {
+    Preset(CrcPreset),
+    Parameters {
+        width: usize,
+        polynomial: u64,
+        init: u64,
+        reflected: bool,
+        xor_out: u64,
+    },
+}
+
+impl From<CrcAlgorithmDescription> for CrcAlgorithm {
+    fn from(description: CrcAlgorithmDescription) -> Self {
+        match description {
+            CrcAlgorithmDescription::Preset(preset) => preset.algorithm(),
+            CrcAlgorithmDescription::Parameters {
+                width,
+                polynomial,
+                init,
+                reflected,
+                xor_out,
+            } => CrcAlgorithm {
+                width,
+                polynomial,
+                init,
+                reflected,
+                xor_out,
+            },
+        }
+    }
+}
+
+impl CrcAlgorithm {
+    fn mask(&self) -> u64 {
+        if self.width >= 64 {
+            u64::MAX
+        } else {
+            (1u64 << self.width) - 1
+        }
+    }
+
+    /// Whether the same routine advances the registers of both algorithms,
+    /// i.e. they differ at most in the initial value and the final XOR
+    pub fn shares_routine(&self, other: &CrcAlgorithm) -> bool {
+        self.width == other.width
+            && self.polynomial == other.polynomial
+            && self.reflected == other.reflected
+    }
+
+    /// The polynomial in the bit order the register is shifted in
+    pub fn register_polynomial(&self) -> u64 {
+        match self.reflected {
+            true => self.polynomial.reverse_bits() >> (64 - self.width),
+            false => self.polynomial,
+        }
+    }
+
+    /// Advances the register by one byte, bit by bit
+    pub fn update(&self, crc: u64, byte: u8) -> u64 {
+        let polynomial = self.register_polynomial();
+        let mut crc = match self.reflected {
+            true => crc ^ u64::from(byte),
+            false => crc ^ (u64::from(byte) << (self.width - 8)),
+        };
+
+        for _ in 0..8 {
+            crc = match self.reflected {
+                true if crc & 1 != 0 => (crc >> 1) ^ polynomial,
+                true => crc >> 1,
+                false if crc & (1u64 << (self.width - 1)) != 0 => (crc << 1) ^ polynomial,
+                false => crc << 1,
+            } & self.mask();
+        }
+
+        crc
+    }
+
+    /// Register values for each byte fed into a zeroed register, the lookup
+    /// table of table-driven implementations
+    pub fn table(&self) -> std::vec::Vec<u64> {
+        (0..=255u8).map(|byte| self.update(0, byte)).collect()
+    }
+
+    /// CRC of a sequence of bytes, as transmitted
+    pub fn compute(&self, bytes: &[u8]) -> u64 {
+        bytes
+            .iter()
+            .fold(self.init & self.mask(), |crc, byte| self.update(crc, *byte))
+            ^ (self.xor_out & self.mask())
+    }
+}
+
+/// Turns an integer field into a checksum of the fields marked with the
+/// "start checksum" and "stop checksum" attributes of the same name. Frames
+/// whose checksum does not match are rejected
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ChecksumFieldAttribute {
+    /// Pairs the field with the covered range
+    pub name: std::string::String,
+    pub algorithm: CrcAlgorithm,
+}
+
+/// Turns an integer field into the byte length of the fields `first` through
+/// `last`, inclusive. Serializers fill it in, and parsers reject frames it
+/// does not match
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct LengthOfFieldAttribute {
+    pub first: std::string::String,
+    pub last: std::string::String,
+
+    /// The length in bytes, computed from the field's value, which the
+    /// expression refers to by the field's name, e.g. `len * 4` for a length
+    /// counted in words. If omitted, the value is the length
+    #[serde(default)]
+    pub length: Option<Expression>,
+}
+
+/// What a parser does with the bytes of an array field past its `MaxLength`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize)]
+pub enum OverflowPolicy {
+    /// The bytes are dropped, and the field keeps its first `MaxLength` bytes
+    #[default]
+    Truncate,
+
+    /// The frame is rejected
+    Error,
+
+    /// The frame is dropped, and the parser starts over on the byte which
+    /// overflowed the field, e.g. to look for the next sync sequence
+    Resync,
+}
+
 /// Every field is modified with a set of attributes, such as
 /// - length (if the field is of constant length);
 /// - accepted values;
-/// - hooks (for calculating checksums),
-/// etc.
-#[derive(Debug, Clone)]
+/// - hooks (for calculating checksums), etc.
+#[derive(Debug, Clone, serde::Deserialize)]
 pub enum FieldAttribute {
     MaxLength(MaxLengthFieldAttribute),
+    Scale(ScaleFieldAttribute),
+    Range(RangeFieldAttribute),
+    ConditionalOn(ConditionalOnFieldAttribute),
+
+    /// The field first appears in the given protocol version
+    SinceVersion(u32),
+
+    /// The field last appears in the given protocol version
+    UntilVersion(u32),
+
+    /// The named checksum covers bytes starting from this field
+    StartChecksum(std::string::String),
+
+    /// The named checksum covers bytes up to this field, inclusive
+    StopChecksum(std::string::String),
+
+    /// The field holds a checksum
+    Checksum(ChecksumFieldAttribute),
+
+    /// The field holds the length of other fields
+    LengthOf(LengthOfFieldAttribute),
+
+    /// Human-readable description, carried over into the generated code
+    Doc(std::string::String),
+
+    /// The frame is rejected, unless the expression holds once the field is
+    /// parsed. It may refer to the field itself, and to earlier unconditional
+    /// fields
+    Check(Expression),
+
+    /// Unit of measurement of the field's value, e.g. `mV`. For a scaled
+    /// field, the unit of the physical value
+    Unit(std::string::String),
+
+    /// The field is kept in the wire layout, but should no longer be used.
+    /// Holds the reason, carried over into the generated code
+    Deprecated(std::string::String),
+
+    /// Declares that the field may take values no alternative conditional on
+    /// it claims. Frames carrying such values are rejected. Without the
+    /// attribute, unclaimed values are linted as gaps in the alternatives
+    DefaultArm,
+
+    /// What the parser does with the bytes of the field past its
+    /// `MaxLength`. If omitted, `OverflowPolicy::Truncate` is assumed
+    Overflow(OverflowPolicy),
+
+    /// Silences the warnings of a lint rule about the field, e.g. `RB017`.
+    /// Errors can not be silenced
+    SuppressLint(std::string::String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Deserialize)]
 pub struct RegexFieldType {
+    /// Pattern the field's bytes match, see `crate::bpir::pattern` for the
+    /// syntax
     pub regex: std::string::String,
 }
 
-#[derive(Debug, Clone)]
+impl RegexFieldType {
+    /// The parsed pattern. A regex of unsupported syntax is linted, and taken
+    /// literally here, so code may still be generated from it
+    pub fn pattern(&self) -> crate::bpir::pattern::Pattern {
+        crate::bpir::pattern::Pattern::parse(&self.regex).unwrap_or_else(|_| {
+            crate::bpir::pattern::Pattern::from_bytes(&crate::utility::string::unescape_literal(&self.regex))
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct IntegerFieldType {
+    /// Width of the field in a byte (or bit) stream, in bits
+    pub bit_width: usize,
+    pub signed: bool,
+}
+
+impl IntegerFieldType {
+    /// Min value representable by the field's width
+    pub fn min_value(&self) -> i128 {
+        if self.signed {
+            -(1i128 << (self.bit_width - 1))
+        } else {
+            0i128
+        }
+    }
+
+    /// Max value representable by the field's width
+    pub fn max_value(&self) -> i128 {
+        if self.signed {
+            (1i128 << (self.bit_width - 1)) - 1
+        } else {
+            (1i128 << self.bit_width) - 1
+        }
+    }
+}
+
+/// A gap in a message. Its bytes are consumed, but not interpreted
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ReservedFieldType {
+    /// Length in bytes
+    pub length: usize,
+
+    /// Keep the bytes in a struct member anyway, e.g. for debugging
+    pub debug_member: bool,
+}
+
+/// A fixed number of repetitions of a group of fields, e.g. 8 channel records
+/// each consisting of an id and a value
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct GroupFieldType {
+    /// Number of repetitions
+    pub count: usize,
+
+    /// Fields of a single repetition. Only integer and reserved fields are
+    /// supported.
+    pub fields: std::vec::Vec<Field>,
+
+    /// Name of the protocol's group definition providing the fields, see
+    /// `Protocol::resolve_group_definitions()`. Groups made from the same
+    /// definition share a type
+    #[serde(default)]
+    pub definition: Option<std::string::String>,
+}
+
+/// A group of fields defined once, and referenced by group fields of several
+/// messages, e.g. a common header
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct GroupDefinition {
+    pub name: std::string::String,
+    pub fields: std::vec::Vec<Field>,
+}
+
+/// One of the record kinds a TLV field may contain
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct TlvRecordType {
+    /// Value of the "type" part identifying the record
+    pub tag: u64,
+
+    /// Describes the "value" part. Its name identifies the record kind
+    pub field: Field,
+}
+
+/// A sequence of type-length-value records. Tags and lengths are big-endian.
+/// The field's "max length" attribute limits the number of records.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct TlvFieldType {
+    /// Width of the "type" part, in bytes
+    pub tag_length: usize,
+
+    /// Width of the "length" part, in bytes
+    pub length_length: usize,
+    pub records: std::vec::Vec<TlvRecordType>,
+}
+
+/// Variable-length integer, LEB128-encoded as in protobuf: each byte carries
+/// 7 bits of the value, least significant first, and has its MSB set, if more
+/// bytes follow
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct VarintFieldType {
+    /// Width of the decoded value, in bits. The encoding takes at most
+    /// `ceil(bit_width / 7)` bytes
+    pub bit_width: usize,
+    pub signed: bool,
+
+    /// Signed values are zigzag-encoded (protobuf `sint`), so small negative
+    /// values take few bytes. Otherwise they are two's complement of
+    /// `bit_width` bits
+    pub zigzag: bool,
+}
+
+impl VarintFieldType {
+    /// Max number of bytes the encoding takes
+    pub fn max_length(&self) -> usize {
+        self.bit_width.div_ceil(7)
+    }
+
+    /// The fixed-width integer the value decodes into
+    pub fn decoded(&self) -> IntegerFieldType {
+        IntegerFieldType {
+            bit_width: self.bit_width,
+            signed: self.signed,
+        }
+    }
+}
+
+/// Named bit of a flags field
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct FlagBit {
+    pub name: std::string::String,
+
+    /// Position of the bit, 0 being the least significant one
+    pub position: usize,
+}
+
+/// Unsigned integer, each bit of which is a flag of its own
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct FlagsFieldType {
+    /// Width of the field in a byte (or bit) stream, in bits
+    pub bit_width: usize,
+    pub bits: std::vec::Vec<FlagBit>,
+}
+
+impl FlagsFieldType {
+    /// The integer holding the flags
+    pub fn integer(&self) -> IntegerFieldType {
+        IntegerFieldType {
+            bit_width: self.bit_width,
+            signed: false,
+        }
+    }
+}
+
+/// Unsigned count of ticks elapsed since an epoch
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct TimestampFieldType {
+    /// Width of the field in a byte (or bit) stream, in bits
+    pub bit_width: usize,
+
+    /// Instant the count starts at, in milliseconds since the Unix epoch
+    pub epoch_ms: i64,
+
+    /// Duration of a tick, in microseconds
+    pub tick_us: u64,
+}
+
+impl TimestampFieldType {
+    /// The integer holding the count
+    pub fn integer(&self) -> IntegerFieldType {
+        IntegerFieldType {
+            bit_width: self.bit_width,
+            signed: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
 pub enum FieldType {
     /// Expect a certain sequence of bytes
     Regex(RegexFieldType),
+
+    /// Signed or unsigned integer
+    Integer(IntegerFieldType),
+
+    /// Repeated type-length-value records
+    Tlv(TlvFieldType),
+
+    /// Bytes which are skipped
+    Reserved(ReservedFieldType),
+
+    /// Repeated group of fields
+    Group(GroupFieldType),
+
+    /// Integer of variable length
+    Varint(VarintFieldType),
+
+    /// Unsigned integer with named bits
+    Flags(FlagsFieldType),
+
+    /// Point in time, as a count of ticks
+    Timestamp(TimestampFieldType),
+}
+
+/// Defines how the fields of a message are laid out in a byte stream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum FieldOrder {
+    /// Fields follow each other in the same order they are defined in
+    Strict,
+
+    /// Fields may appear in any order, each one at most once (sets of TLV-like
+    /// parameters). The fields MUST be distinguishable by their content.
+    Unordered,
+}
+
+impl FieldOrder {
+    /// Max number of fields an unordered message may have. Each field occupies
+    /// one bit of the message's presence bitmap.
+    pub fn get_max_unordered_fields() -> usize {
+        32usize
+    }
+}
+
+/// Defines the granularity of a message's fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum MessageLayout {
+    /// Fields occupy whole bytes. Such messages are parsed with Ragel machines
+    Bytes,
+
+    /// Field widths are in bits, and fields need not be aligned to byte
+    /// boundaries (GNSS RTCM, radio PHY headers). Since Ragel operates on
+    /// bytes, such messages are parsed with a bit reader
+    Bits,
+}
+
+/// Order the bits of a bit-granular message are packed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum BitOrder {
+    /// Fields fill each byte from its most significant bit, and their values
+    /// are sent most significant bit first (RTCM, most radio PHY headers)
+    MsbFirst,
+
+    /// Fields fill each byte from its least significant bit, and their values
+    /// are sent least significant bit first (CAN signals in Intel byte order)
+    LsbFirst,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Deserialize)]
 pub enum MessageAttribute {
     /// This message is the core of the protocol, which nests every other one
     Root,
+
+    /// If omitted, `FieldOrder::Strict` is assumed
+    FieldOrder(FieldOrder),
+
+    /// If omitted, `MessageLayout::Bytes` is assumed
+    Layout(MessageLayout),
+
+    /// Total length of the message in bytes, before symbol encoding. Frames
+    /// of any other length are rejected
+    FixedLength(usize),
+
+    /// Value of the message's first field, which identifies the message. Every
+    /// message having an ID is parsed by one common dispatcher
+    Id(u64),
+
+    /// Bytes preceding every frame of the message, after symbol decoding. The
+    /// parser skips input until it finds the sequence, and resynchronizes on
+    /// it after an error, so buffers need not be aligned to frames
+    SyncSequence(std::vec::Vec<u8>),
+
+    /// Human-readable description, carried over into the generated code
+    Doc(std::string::String),
+
+    /// Bit order of a bit-granular message. If omitted, `BitOrder::MsbFirst`
+    /// is assumed
+    BitOrder(BitOrder),
+
+    /// Name of the base message, whose fields precede the message's own, see
+    /// `Protocol::resolve_message_extensions()`. Other attributes of the base
+    /// are not inherited
+    Extends(std::string::String),
+}
+
+/// Transforms applied to the byte stream before it reaches the parser's state
+/// machine, for protocols which require symbol decoding first
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub enum SymbolDecoding {
+    /// Swaps the high and the low nibbles of each byte
+    NibbleSwap,
+
+    /// Reverses the order of bits in each byte
+    BitReversal,
+
+    /// XORs each byte with a constant (data whitening)
+    Xor(u8),
+
+    /// 4B/5B line code. Every pair of 5-bit code groups decodes into one byte,
+    /// the first group carrying the high nibble. Code groups are read
+    /// MSB-first
+    FourBFiveB,
+
+    /// User-provided C function of the form `uint8_t hook(uint8_t aSymbol)`
+    /// applied to each byte
+    UserHook(std::string::String),
+}
+
+impl SymbolDecoding {
+    /// Whether each input byte decodes into exactly one output byte
+    pub fn is_bytewise(&self) -> bool {
+        !matches!(self, SymbolDecoding::FourBFiveB)
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Deserialize)]
 pub enum ProtocolAttribute {
+    /// Decoding applied to every byte-granular message of the protocol
+    SymbolDecoding(SymbolDecoding),
+
+    /// Generate a loopback self-test function, which feeds a canned instance of
+    /// each message into its parser, and checks that the decoded values match
+    SelfTest,
+
+    /// Generate a serializer for each byte-granular message, which encodes the
+    /// message struct into a frame. Messages whose frames can not be composed
+    /// of their structs (e.g. with non-literal regex fields) are skipped
+    Serializers,
+
+    /// Guarantee that the generated code allocates no memory dynamically, for
+    /// heapless firmware. Arrays are bounded by `MaxLength` anyway, so this
+    /// guards against overrides, rewrite rules, and user code calling
+    /// `malloc()` and its kin: generation fails, if any artifact does
+    StaticMemory,
+
+    /// Generate a parsing function for each byte-granular message, which
+    /// invokes a user-supplied callback on each frame as soon as it is
+    /// accepted, e.g. for event-driven firmware
+    Callbacks,
+
+    /// Represent the regex fields of byte-granular messages as an offset and a
+    /// length into the parser's input, rather than copying their bytes into
+    /// the message struct, for large payloads on RAM-constrained parts. The
+    /// input must outlive the struct. Decoded or destuffed input differs from
+    /// the frame, so its fields are still copied
+    ZeroCopy,
+
+    /// Generate a function for each message, printing the fields of its struct
+    /// through a `printf`-like function, e.g. for bring-up over a debug UART.
+    /// Flags are printed by name, and scaled values along with their physical
+    /// values
+    DebugDump,
+
+    /// Generate a function for each message initializing its struct, and one
+    /// comparing two of them, e.g. for tests, and for detecting changes in
+    /// application code
+    StructFunctions,
+
+    /// Count the bytes the parsers of messages with a sync sequence drop while
+    /// resynchronizing, i.e. the input preceding a sync sequence, and the
+    /// frames rejected, e.g. to monitor noisy serial links. The count is kept
+    /// in the parser state, and reset along with it. Counting takes an action
+    /// per input byte
+    DiscardCounting,
+
+    /// Keep counters of the frames parsers accept, and of the ones they reject
+    /// for a checksum mismatch, or an overflow, along with the number of
+    /// resynchronizations, so devices may report the quality of their links.
+    /// The counters survive restarts of the machine, only the parser state's
+    /// init function clears them
+    ParserStatistics,
+
+    /// Generate a table mapping parse error codes onto human-readable strings,
+    /// so devices may report errors without format strings
+    DiagnosticStrings(DiagnosticStringsAttribute),
+
+    /// Record the outcomes of the last frames of byte-granular messages in a
+    /// ring buffer, for post-mortem analysis
+    FrameTrace(FrameTraceAttribute),
+
+    /// Export the protocol's numeric constants (message IDs, error codes,
+    /// limits) into a standalone artifact in each of the listed languages
+    SharedConstants(std::vec::Vec<TargetLanguage>),
+
+    /// Escape sequences applied to the byte stream on the wire (HDLC-style).
+    /// The parsers of byte-granular messages remove them before symbol
+    /// decoding
+    ByteStuffing(ByteStuffingAttribute),
+
+    /// Split the generated code into one file pair per message, or per group
+    /// of messages, plus a pair of shared files and an umbrella header
+    OutputSplitting(OutputSplitting),
+
+    /// How the names of the generated types and functions are composed. If
+    /// omitted, `SymbolNaming::default()` is assumed
+    SymbolNaming(SymbolNaming),
+
+    /// How generated headers guard against being included twice. If omitted,
+    /// `IncludeGuard::Macro(None)` is assumed
+    IncludeGuard(IncludeGuard),
+
+    /// Version of the protocol the description is narrowed to. Fields absent
+    /// in other versions are marked with version attributes
+    Version(u32),
+
+    /// How the generated code computes checksums. If omitted,
+    /// `ChecksumImplementation::Table` is assumed
+    ChecksumImplementation(ChecksumImplementation),
+
+    /// How Ragel generates the state machines, trading flash for speed. If
+    /// omitted, `RagelOptions::default()` is assumed
+    Ragel(RagelOptions),
+
+    /// Revision of the C language the generated code must compile under. If
+    /// omitted, `CStandard::C99` is assumed
+    CStandard(CStandard),
+
+    /// Generate a C++17 header wrapping the C API in a namespace: a parser
+    /// class per byte-granular message, `enum class` counterparts of the C
+    /// enums, and entry points taking `std::byte` buffers, or `std::span`
+    /// ones under C++20. The C sources are compiled as they are
+    Cpp(CppOptions),
+
+    /// Generate a TypeScript module with an interface per byte-granular
+    /// message, and functions parsing frames off `Uint8Array`s and
+    /// serializing messages into them, e.g. for web dashboards talking to
+    /// devices over WebSerial. Messages whose frames can not be composed of
+    /// their fields (see `Serializers`), or which carry checks, are left out
+    TypeScript,
+
+    /// Write the parsers, serializers and helpers of byte-granular messages
+    /// avoiding constructs MISRA checkers commonly flag: each function returns
+    /// once, null pointers are spelled `NULL`, and conversions to Boolean and
+    /// integer types are explicit. The state machines Ragel generates are
+    /// outside the profile
+    MisraC,
+
+    /// Human-readable description, carried over into the generated code
+    Doc(std::string::String),
+
+    /// Generate a benchmark feeding the parsers of byte-granular messages with
+    /// inputs maximizing the work per byte, e.g. frames rejected at their last
+    /// byte, and near-miss sync sequences
+    Benchmark(BenchmarkAttribute),
+
+    /// Declares that frames may carry IDs no message claims. The dispatcher
+    /// drops such frames. Without the attribute, unclaimed IDs are linted as
+    /// gaps in the dispatch table
+    DispatchDefaultArm,
+
+    /// End each region of the generated files with a block delimited by
+    /// `ROBUSTO USER CODE` comments. What users put into the blocks may be
+    /// carried over into regenerated files
+    UserCodeAnchors,
+
+    /// Take the helpers which do not depend on the protocol (CRC routines)
+    /// from the runtime library, which is generated once for every protocol
+    /// of a project, instead of generating a copy along with the parsers
+    Runtime,
+
+    /// Defines a group of fields, which group fields may refer to by name
+    GroupDefinition(GroupDefinition),
+
+    /// Max number of bytes a frame may occupy on the wire, e.g. the size of a
+    /// receive buffer. Messages whose frames may exceed it are linted
+    FrameSizeBudget(usize),
+
+    /// The device the generated code runs on. The static buffers of the
+    /// message structs are linted against its RAM budget
+    Target(TargetProfile),
 }
 
-/// Represents a protocol's message as a sequence of fields
-#[derive(Debug)]
-pub struct Message {
+/// Memory constraints of a target device, e.g. a Cortex-M0 with 8 KiB of RAM
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct TargetProfile {
+    /// Human-readable name, e.g. `Cortex-M0`
     pub name: std::string::String,
-    pub fields: std::vec::Vec<Field>,
-    pub attributes: std::vec::Vec<MessageAttribute>,
+
+    /// Bytes of RAM the static buffers of all message structs combined may
+    /// take up
+    pub ram_budget: usize,
 }
 
-/// May be a regular field, such as byte sequence of fixed length, or u32, or a
-/// payload (nested message))
-#[derive(Debug, Clone)]
-pub struct Field {
-    pub name: std::string::String,
-    pub field_type: FieldType,
-    pub attributes: std::vec::Vec<FieldAttribute>,
+/// Trade-off between speed and flash usage of checksum routines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum ChecksumImplementation {
+    /// Looks each byte up in a 256-entry table: fast, but flash-hungry
+    Table,
+
+    /// Shifts each byte through the register bit by bit: slow, but tiny
+    Bitwise,
+
+    /// Only declares the routines, which are defined elsewhere, e.g. by the
+    /// code generated for another protocol employing the same CRCs
+    Extern,
 }
 
-/// Represents the entire protocol as a set of messages
-#[derive(Debug)]
-pub struct Protocol {
-    pub messages: std::vec::Vec<Message>,
-    pub attributes: std::vec::Vec<ProtocolAttribute>,
+/// Options of Ragel, and of the `write` statements of the generated machines
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct RagelOptions {
+    /// Form of the code Ragel generates. It is passed to Ragel on the command
+    /// line, see `RagelCodeStyle::flag()`
+    pub code_style: RagelCodeStyle,
+
+    /// Options of the `write data` statements, omitting constants of the
+    /// machines
+    pub write_data: std::vec::Vec<RagelWriteDataOption>,
+
+    /// Leave the current state out of `write init`, i.e. `write init nocs`.
+    /// The parser state init functions assign the start state in C instead,
+    /// where overrides and rewrite rules may change it
+    pub write_init_nocs: bool,
 }
 
-impl Protocol {
-    /// Gets the root message. If absent, the first message is considered root
-    pub fn root_message(&self) -> &Message {
-        if self.messages.len() == 0 {
-            log::error!("Empty messages list. Panicking");
-            panic!();
+/// Form of the code Ragel generates a machine in. Table-driven code is
+/// compact, goto-driven code is fast: `T0` is the smallest and slowest, `G2`
+/// the largest and fastest. `T0` suits parts short of flash, `F1` lowers the
+/// cost per byte while keeping to tables, and `G2` suits parsers on hot paths,
+/// if flash permits. Goto-driven code grows with the number of states, so
+/// large machines may not fit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+pub enum RagelCodeStyle {
+    /// Table-driven
+    #[default]
+    T0,
+
+    /// Table-driven, with expanded actions
+    T1,
+
+    /// Flat tables
+    F0,
+
+    /// Flat tables, with expanded actions
+    F1,
+
+    /// Goto-driven
+    G0,
+
+    /// Goto-driven, with expanded actions
+    G1,
+
+    /// Goto-driven, with in-place actions
+    G2,
+}
+
+impl RagelCodeStyle {
+    /// Ragel's command line flag selecting the style, e.g. `-G2`
+    pub fn flag(&self) -> &'static str {
+        match self {
+            RagelCodeStyle::T0 => "-T0",
+            RagelCodeStyle::T1 => "-T1",
+            RagelCodeStyle::F0 => "-F0",
+            RagelCodeStyle::F1 => "-F1",
+            RagelCodeStyle::G0 => "-G0",
+            RagelCodeStyle::G1 => "-G1",
+            RagelCodeStyle::G2 => "-G2",
         }
+    }
+}
 
-        for message in &self.messages {
-            for attribute in &message.attributes {
-                if let MessageAttribute::Root = attribute {
-                    return message;
-                }
-            }
+/// Option of Ragel's `write data` statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum RagelWriteDataOption {
+    /// Omit the error state, `noerror`
+    NoError,
+
+    /// Omit the first final state, `nofinal`
+    NoFinal,
+
+    /// Omit the entry points, `noentry`
+    NoEntry,
+
+    /// Leave the machine's name out of the constants' names, `noprefix`
+    NoPrefix,
+}
+
+impl RagelWriteDataOption {
+    /// The option, as Ragel spells it
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            RagelWriteDataOption::NoError => "noerror",
+            RagelWriteDataOption::NoFinal => "nofinal",
+            RagelWriteDataOption::NoEntry => "noentry",
+            RagelWriteDataOption::NoPrefix => "noprefix",
+        }
+    }
+
+    /// Whether the generated code may do without the constants the option
+    /// omits. The parsers compare their state against the first final state
+    /// by name, and the machines' constants share a file, so they keep their
+    /// prefixes. The error state is always 0, the parsers define it as a macro
+    /// in place of the constant
+    pub fn supported(&self) -> bool {
+        matches!(self, RagelWriteDataOption::NoEntry | RagelWriteDataOption::NoError)
+    }
+}
+
+/// Revision of the C language, as vendor toolchains support it. Ordered from
+/// the oldest one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize)]
+pub enum CStandard {
+    /// ANSI C: no `//` comments, no declarations after statements, and no
+    /// comma after the last enumerator
+    C89,
+    C99,
+    C11,
+}
+
+impl CStandard {
+    /// Whether comments may run to the end of the line, `//`-style
+    pub fn line_comments(&self) -> bool {
+        *self >= CStandard::C99
+    }
+
+    /// Whether declarations may follow statements in a block
+    pub fn mixed_declarations(&self) -> bool {
+        *self >= CStandard::C99
+    }
+
+    /// Whether the last enumerator of an enum may be followed by a comma
+    pub fn trailing_enumerator_comma(&self) -> bool {
+        *self >= CStandard::C99
+    }
+
+    /// Name of the standard, as compilers take it, e.g. `-std=c89`
+    pub fn name(&self) -> &'static str {
+        match self {
+            CStandard::C89 => "c89",
+            CStandard::C99 => "c99",
+            CStandard::C11 => "c11",
+        }
+    }
+}
+
+/// Options of the C++ header
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct CppOptions {
+    /// Namespace the wrappers are declared in, possibly nested, e.g.
+    /// `acme::sensors`
+    pub namespace: std::string::String,
+}
+
+impl Default for CppOptions {
+    fn default() -> Self {
+        CppOptions {
+            namespace: "robusto".to_string(),
         }
+    }
+}
+
+/// A language generated artifacts may be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum TargetLanguage {
+    C,
+    Rust,
+    TypeScript,
+}
 
-        &self.messages[0]
+/// Case style of generated symbols
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+pub enum CaseStyle {
+    /// `parse_status_message`
+    SnakeCase,
+
+    /// `parseStatusMessage`
+    CamelCase,
+
+    /// `ParseStatusMessage`
+    PascalCase,
+}
+
+impl CaseStyle {
+    /// Joins lowercase words into a symbol
+    pub fn join(&self, words: &[std::string::String]) -> std::string::String {
+        match self {
+            CaseStyle::SnakeCase => words.join("_"),
+            CaseStyle::CamelCase => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| match i {
+                    0 => word.clone(),
+                    _ => crate::utility::string::capitalize(word),
+                })
+                .collect(),
+            CaseStyle::PascalCase => words.iter().map(|word| crate::utility::string::capitalize(word)).collect(),
+        }
+    }
+}
+
+/// Naming rules of the types and functions generated for each message: the
+/// message struct, the parser state struct, and the functions parsing the
+/// message. Embedded codebases often mandate a case style, and a module
+/// prefix, e.g. `acme_status_parse_t`. The field accessors, and the
+/// dispatcher's types and functions, follow the same rules.
+///
+/// By default, the symbols are composed of the message's name, kept as is,
+/// e.g. `StatusMessage`, `StatusParserState`, `parseStatus`
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct SymbolNaming {
+    /// Case style of type names. The message's name is split into words,
+    /// see `utility::string::split_words()`
+    pub type_case: Option<CaseStyle>,
+    pub type_prefix: std::string::String,
+    pub type_suffix: std::string::String,
+
+    /// Case style of function names
+    pub function_case: Option<CaseStyle>,
+    pub function_prefix: std::string::String,
+    pub function_suffix: std::string::String,
+
+    /// Prepended to every symbol the generated code defines, the shared ones
+    /// and the Ragel machines included, so the code of several protocols may
+    /// be linked into one image, e.g. `acme_` for `acme_RobustoParseStatus`.
+    /// Macros and enumerators take it in upper case. The runtime library is
+    /// shared by the protocols, so its symbols are left as they are
+    pub symbol_prefix: std::string::String,
+}
+
+impl SymbolNaming {
+    /// Composes a symbol of the message's name, and the words around it
+    fn compose(case: Option<CaseStyle>, before: &str, message_name: &str, after: &str) -> std::string::String {
+        match case {
+            Some(case) => case.join(
+                &[before, message_name, after]
+                    .iter()
+                    .flat_map(|part| crate::utility::string::split_words(part))
+                    .collect::<std::vec::Vec<_>>(),
+            ),
+            None => format!("{0}{1}{2}", before, message_name, after),
+        }
+    }
+
+    fn type_name(&self, message_name: &str, kind: &str) -> std::string::String {
+        format!(
+            "{0}{1}{2}",
+            self.type_prefix,
+            SymbolNaming::compose(self.type_case, "", message_name, kind),
+            self.type_suffix
+        )
+    }
+
+    fn function_name(&self, verb: &str, message_name: &str, object: &str) -> std::string::String {
+        format!(
+            "{0}{1}{2}",
+            self.function_prefix,
+            SymbolNaming::compose(self.function_case, verb, message_name, object),
+            self.function_suffix
+        )
+    }
+
+    /// Type of the struct holding the message's fields, without the `struct`
+    /// keyword, e.g. `StatusMessage`
+    pub fn message_struct(&self, message_name: &str) -> std::string::String {
+        self.type_name(message_name, "Message")
+    }
+
+    /// Type of the struct holding the state of the message's parser, e.g.
+    /// `StatusParserState`
+    pub fn parser_state_struct(&self, message_name: &str) -> std::string::String {
+        self.type_name(message_name, "ParserState")
+    }
+
+    /// Type of the struct holding the callbacks of the message's parser, e.g.
+    /// `StatusCallbacks`
+    pub fn callbacks_struct(&self, message_name: &str) -> std::string::String {
+        self.type_name(message_name, "Callbacks")
+    }
+
+    /// e.g. `machineStatusParserStateInit`
+    pub fn parser_state_init_function(&self, message_name: &str) -> std::string::String {
+        self.function_name("machine", message_name, "ParserStateInit")
+    }
+
+    /// e.g. `parseStatus`
+    pub fn parsing_function(&self, message_name: &str) -> std::string::String {
+        self.function_name("parse", message_name, "")
+    }
+
+    /// e.g. `getStatusStatistics`
+    pub fn statistics_function(&self, message_name: &str) -> std::string::String {
+        self.function_name("get", message_name, "Statistics")
+    }
+
+    /// e.g. `isStatusAccepted`
+    pub fn acceptance_function(&self, message_name: &str) -> std::string::String {
+        self.function_name("is", message_name, "Accepted")
+    }
+
+    /// e.g. `parseStatusWithCallbacks`
+    pub fn callback_parsing_function(&self, message_name: &str) -> std::string::String {
+        self.function_name("parse", message_name, "WithCallbacks")
+    }
+
+    /// e.g. `serializeStatus`
+    pub fn serializing_function(&self, message_name: &str) -> std::string::String {
+        self.function_name("serialize", message_name, "")
+    }
+
+    /// e.g. `initStatus`
+    pub fn init_function(&self, message_name: &str) -> std::string::String {
+        self.function_name("init", message_name, "")
+    }
+
+    /// e.g. `equalsStatus`
+    pub fn equality_function(&self, message_name: &str) -> std::string::String {
+        self.function_name("equals", message_name, "")
+    }
+
+    /// e.g. `dumpStatus`
+    pub fn dump_function(&self, message_name: &str) -> std::string::String {
+        self.function_name("dump", message_name, "")
+    }
+
+    /// Accessor of one of the message's fields, e.g. `getStatusVoltageScaled`.
+    /// The unit, e.g. `_mV`, follows as it is spelled, since a case style
+    /// would blur `mV` and `MV`
+    pub fn field_accessor_function(
+        &self,
+        verb: &str,
+        message_name: &str,
+        object: &str,
+        unit: &str,
+    ) -> std::string::String {
+        format!(
+            "{0}{1}{2}{3}",
+            self.function_prefix,
+            SymbolNaming::compose(self.function_case, verb, message_name, object),
+            unit,
+            self.function_suffix
+        )
+    }
+
+    /// Union of every message's struct, e.g. `RobustoAnyMessage`
+    pub fn any_message_union(&self) -> std::string::String {
+        self.type_name("Robusto", "AnyMessage")
+    }
+
+    /// Type of the struct holding the callbacks of the dispatcher, e.g.
+    /// `RobustoDispatchCallbacks`
+    pub fn dispatch_callbacks_struct(&self) -> std::string::String {
+        self.type_name("Robusto", "DispatchCallbacks")
+    }
+
+    /// Union of the parser states of the dispatched messages, e.g.
+    /// `RobustoDispatchParserState`
+    pub fn dispatch_parser_state_union(&self) -> std::string::String {
+        self.type_name("Robusto", "DispatchParserState")
+    }
+
+    /// Type of the struct holding the state of the dispatcher, e.g.
+    /// `RobustoDispatcherState`
+    pub fn dispatcher_state_struct(&self) -> std::string::String {
+        self.type_name("Robusto", "DispatcherState")
+    }
+
+    /// e.g. `robustoDispatcherInit`
+    pub fn dispatcher_init_function(&self) -> std::string::String {
+        self.function_name("robusto", "Dispatcher", "Init")
+    }
+
+    /// e.g. `parseAny`
+    pub fn dispatching_function(&self) -> std::string::String {
+        self.function_name("parse", "Any", "")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub enum IncludeGuard {
+    /// `#ifndef` guard. The macro is named after the header's file, e.g.
+    /// `OUTPUT_H`, unless a name is given. The headers of split output append
+    /// their files' names to the given name, so their macros stay distinct
+    Macro(Option<std::string::String>),
+
+    /// `#pragma once`, which most compilers support, although the standard
+    /// does not define it
+    PragmaOnce,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub enum OutputSplitting {
+    /// One file pair per message
+    PerMessage,
+
+    /// One file pair per group. Every message must belong to exactly one group
+    Groups(std::vec::Vec<MessageGroup>),
+}
+
+/// Messages whose code is generated into the same file pair
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct MessageGroup {
+    /// Distinguishes the group's files
+    pub name: std::string::String,
+    pub messages: std::vec::Vec<std::string::String>,
+}
+
+/// An escape byte followed by a byte XOR-ed with a mask stands for the
+/// unmasked byte, e.g. `0x7D 0x5E` for `0x7E` in HDLC (escape 0x7D, mask 0x20)
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ByteStuffingAttribute {
+    pub escape: u8,
+    pub xor: u8,
+
+    /// Bytes which are escaped on the wire besides the escape byte itself,
+    /// e.g. the frame delimiter
+    pub escaped_bytes: std::vec::Vec<u8>,
+}
+
+impl ByteStuffingAttribute {
+    /// Whether the byte is transmitted as an escape sequence
+    pub fn is_escaped(&self, byte: u8) -> bool {
+        byte == self.escape || self.escaped_bytes.contains(&byte)
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct FrameTraceAttribute {
+    /// Number of the latest frame outcomes the ring buffer keeps
+    pub depth: usize,
+
+    /// User-provided C function of the form `uint32_t hook(void)` timestamping
+    /// the records. If absent, timestamps are zero
+    pub timestamp_hook: Option<std::string::String>,
+
+    /// Qualifier appended to the ring buffer's declaration, e.g. a section
+    /// attribute placing it into memory which survives a reset
+    pub storage_qualifier: Option<std::string::String>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct BenchmarkAttribute {
+    /// User-provided C function of the form `uint32_t hook(void)` reading a
+    /// free-running clock, e.g. a cycle counter
+    pub clock_hook: std::string::String,
+
+    /// Number of times each input pattern is fed, so the clock's resolution
+    /// does not dominate the measurement
+    pub repetitions: usize,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct DiagnosticStringsAttribute {
+    /// Qualifier appended to the declaration of each string, e.g. `PROGMEM` to
+    /// keep the strings in flash on AVR
+    pub storage_qualifier: Option<std::string::String>,
+}
+
+/// Represents a protocol's message as a sequence of fields
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Message {
+    pub name: std::string::String,
+    pub fields: std::vec::Vec<Field>,
+    pub attributes: std::vec::Vec<MessageAttribute>,
+}
+
+impl Message {
+    /// Gets the field order of the message. If absent, the order is considered
+    /// strict
+    pub fn field_order(&self) -> FieldOrder {
+        for attribute in &self.attributes {
+            if let MessageAttribute::FieldOrder(field_order) = attribute {
+                return *field_order;
+            }
+        }
+
+        FieldOrder::Strict
+    }
+
+    /// Gets the layout of the message. If absent, the message is considered
+    /// byte-granular
+    pub fn layout(&self) -> MessageLayout {
+        for attribute in &self.attributes {
+            if let MessageAttribute::Layout(layout) = attribute {
+                return *layout;
+            }
+        }
+
+        MessageLayout::Bytes
+    }
+
+    pub fn bit_order(&self) -> BitOrder {
+        for attribute in &self.attributes {
+            if let MessageAttribute::BitOrder(bit_order) = attribute {
+                return *bit_order;
+            }
+        }
+
+        BitOrder::MsbFirst
+    }
+
+    /// Gets the message's fixed length, if present
+    pub fn fixed_length(&self) -> Option<usize> {
+        for attribute in &self.attributes {
+            if let MessageAttribute::FixedLength(length) = attribute {
+                return Some(*length);
+            }
+        }
+
+        None
+    }
+
+    /// Gets the message's ID, if present
+    pub fn id(&self) -> Option<u64> {
+        for attribute in &self.attributes {
+            if let MessageAttribute::Id(id) = attribute {
+                return Some(*id);
+            }
+        }
+
+        None
+    }
+
+    /// Gets the message's sync sequence, if present
+    pub fn sync_sequence(&self) -> Option<&[u8]> {
+        for attribute in &self.attributes {
+            if let MessageAttribute::SyncSequence(sync_sequence) = attribute {
+                return Some(sync_sequence);
+            }
+        }
+
+        None
+    }
+
+    /// Gets the name of the message's base, if present
+    pub fn extends(&self) -> Option<&str> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let MessageAttribute::Extends(base) = attribute {
+                ret = Some(base.as_str());
+            }
+        }
+
+        ret
+    }
+
+    /// Gets the message's description, if present
+    pub fn doc(&self) -> Option<&str> {
+        for attribute in &self.attributes {
+            if let MessageAttribute::Doc(doc) = attribute {
+                return Some(doc);
+            }
+        }
+
+        None
+    }
+
+    /// Splits the fields into byte regions. Consecutive fields conditional on
+    /// the same field are alternatives sharing one region, every other field
+    /// occupies a region of its own
+    pub fn field_regions(&self) -> std::vec::Vec<&[Field]> {
+        let mut ret = std::vec::Vec::new();
+        let mut start = 0usize;
+
+        for end in 1..=self.fields.len() {
+            let selector = self.fields[start].conditional_on().map(|c| &c.field);
+            let is_same_region = end < self.fields.len()
+                && selector.is_some()
+                && self.fields[end].conditional_on().map(|c| &c.field) == selector;
+
+            if !is_same_region {
+                ret.push(&self.fields[start..end]);
+                start = end;
+            }
+        }
+
+        ret
+    }
+
+    /// Min number of bytes the message may occupy, before symbol encoding
+    pub fn min_encoded_length(&self) -> usize {
+        match self.layout() {
+            MessageLayout::Bytes => self
+                .field_regions()
+                .iter()
+                .map(|region| region.iter().map(Field::min_encoded_length).min().unwrap_or(0usize))
+                .sum(),
+            MessageLayout::Bits => self.bit_length().unwrap_or(0usize).div_ceil(8),
+        }
+    }
+
+    /// Max number of bytes the message may occupy, before symbol encoding
+    pub fn max_encoded_length(&self) -> usize {
+        match self.layout() {
+            MessageLayout::Bytes => self
+                .field_regions()
+                .iter()
+                .map(|region| region.iter().map(Field::max_encoded_length).max().unwrap_or(0usize))
+                .sum(),
+            MessageLayout::Bits => self.bit_length().unwrap_or(0usize).div_ceil(8),
+        }
+    }
+
+    /// Bytes of the arrays the message struct holds the fields' content in.
+    /// Alternatives share their storage
+    pub fn buffer_length(&self) -> usize {
+        self.field_regions()
+            .iter()
+            .map(|region| region.iter().map(Field::buffer_length).max().unwrap_or(0usize))
+            .sum()
+    }
+
+    /// Sum of widths of the message's fields, if every one of those has a
+    /// fixed width
+    pub fn bit_length(&self) -> Option<usize> {
+        self.fields.iter().map(Field::bit_width).sum()
+    }
+}
+
+/// May be a regular field, such as byte sequence of fixed length, or u32, or a
+/// payload (nested message))
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Field {
+    pub name: std::string::String,
+    pub field_type: FieldType,
+    pub attributes: std::vec::Vec<FieldAttribute>,
+}
+
+impl Field {
+    /// Gets the value of the field's "max length" attribute, if present
+    pub fn max_length(&self) -> Option<usize> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let FieldAttribute::MaxLength(max_length) = attribute {
+                ret = Some(max_length.value);
+            }
+        }
+
+        ret
+    }
+
+    /// Gets the field's "scale" attribute, if present
+    pub fn scale(&self) -> Option<&ScaleFieldAttribute> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let FieldAttribute::Scale(scale) = attribute {
+                ret = Some(scale);
+            }
+        }
+
+        ret
+    }
+
+    /// Gets the field's "range" attribute, if present
+    pub fn range(&self) -> Option<&RangeFieldAttribute> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let FieldAttribute::Range(range) = attribute {
+                ret = Some(range);
+            }
+        }
+
+        ret
+    }
+
+    /// Whether the field is present in the given protocol version
+    pub fn is_present_in(&self, version: u32) -> bool {
+        self.attributes.iter().all(|attribute| match attribute {
+            FieldAttribute::SinceVersion(since) => version >= *since,
+            FieldAttribute::UntilVersion(until) => version <= *until,
+            _ => true,
+        })
+    }
+
+    /// Whether the field is absent in some protocol versions
+    pub fn is_versioned(&self) -> bool {
+        self.attributes.iter().any(|attribute| {
+            matches!(
+                attribute,
+                FieldAttribute::SinceVersion(_) | FieldAttribute::UntilVersion(_)
+            )
+        })
+    }
+
+    /// Copy of the field as it is in the given protocol version: group members
+    /// absent in the version are dropped
+    fn revision(&self, version: u32) -> Field {
+        let mut ret = self.clone();
+
+        if let FieldType::Group(ref mut group) = ret.field_type {
+            group.fields = group
+                .fields
+                .iter()
+                .filter(|field| field.is_present_in(version))
+                .map(|field| field.revision(version))
+                .collect();
+        }
+
+        ret
+    }
+
+    /// Gets the field's "conditional on" attribute, if present
+    pub fn conditional_on(&self) -> Option<&ConditionalOnFieldAttribute> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let FieldAttribute::ConditionalOn(conditional_on) = attribute {
+                ret = Some(conditional_on);
+            }
+        }
+
+        ret
+    }
+
+    /// Whether values no alternative claims are declared to be expected
+    pub fn has_default_arm(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, FieldAttribute::DefaultArm))
+    }
+
+    /// Type of the field's value, once decoded. `None` for fields other than
+    /// integers, varints, flags and timestamps
+    pub fn integer_value_type(&self) -> Option<IntegerFieldType> {
+        match self.field_type {
+            FieldType::Integer(ref integer) => Some(integer.clone()),
+            FieldType::Varint(ref varint) => Some(varint.decoded()),
+            FieldType::Flags(ref flags) => Some(flags.integer()),
+            FieldType::Timestamp(ref timestamp) => Some(timestamp.integer()),
+            _ => None,
+        }
+    }
+
+    /// Values the field may take: its range, if present, or whatever its
+    /// width allows. `None` for fields other than integers
+    pub fn value_domain(&self) -> Option<(i128, i128)> {
+        let integer = self
+            .integer_value_type()
+            .filter(|integer| (1..=64).contains(&integer.bit_width))?;
+
+        match self.range() {
+            Some(range) => Some((
+                i128::from(range.min).max(integer.min_value()),
+                i128::from(range.max).min(integer.max_value()),
+            )),
+            None => Some((integer.min_value(), integer.max_value())),
+        }
+    }
+
+    /// Gets the field's "checksum" attribute, if present
+    pub fn checksum(&self) -> Option<&ChecksumFieldAttribute> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let FieldAttribute::Checksum(checksum) = attribute {
+                ret = Some(checksum);
+            }
+        }
+
+        ret
+    }
+
+    /// Gets the field's "length of" attribute, if present
+    pub fn length_of(&self) -> Option<&LengthOfFieldAttribute> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let FieldAttribute::LengthOf(length_of) = attribute {
+                ret = Some(length_of);
+            }
+        }
+
+        ret
+    }
+
+    /// Gets the field's description, if present
+    pub fn doc(&self) -> Option<&str> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let FieldAttribute::Doc(doc) = attribute {
+                ret = Some(doc.as_str());
+            }
+        }
+
+        ret
+    }
+
+    /// Gets the expressions the field's value is checked against
+    pub fn checks(&self) -> std::vec::Vec<&Expression> {
+        self.attributes
+            .iter()
+            .filter_map(|attribute| match attribute {
+                FieldAttribute::Check(expression) => Some(expression),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Gets the unit of the field's value, if present
+    pub fn unit(&self) -> Option<&str> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let FieldAttribute::Unit(unit) = attribute {
+                ret = Some(unit.as_str());
+            }
+        }
+
+        ret
+    }
+
+    /// Gets the field's "overflow" attribute, or the default policy
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        let mut ret = OverflowPolicy::default();
+
+        for attribute in &self.attributes {
+            if let FieldAttribute::Overflow(policy) = attribute {
+                ret = *policy;
+            }
+        }
+
+        ret
+    }
+
+    /// Gets the IDs of the lint rules whose warnings about the field are
+    /// silenced
+    pub fn suppressed_lints(&self) -> std::vec::Vec<&str> {
+        self.attributes
+            .iter()
+            .filter_map(|attribute| match attribute {
+                FieldAttribute::SuppressLint(id) => Some(id.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Gets the reason the field is deprecated for, if it is
+    pub fn deprecated(&self) -> Option<&str> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let FieldAttribute::Deprecated(reason) = attribute {
+                ret = Some(reason.as_str());
+            }
+        }
+
+        ret
+    }
+
+    /// Min number of bytes the field may occupy in a byte stream
+    pub fn min_encoded_length(&self) -> usize {
+        match self.field_type {
+            FieldType::Tlv(_) => 0usize,
+            FieldType::Varint(_) => 1usize,
+            FieldType::Regex(ref regex) => regex.pattern().min_length(),
+            FieldType::Group(ref group) => {
+                group.count * group.fields.iter().map(Field::min_encoded_length).sum::<usize>()
+            }
+            _ => self.max_encoded_length(),
+        }
+    }
+
+    /// Bytes of the arrays the message struct holds the field's content in,
+    /// as sized by "max length" attributes, padding aside. 0 for scalars
+    pub fn buffer_length(&self) -> usize {
+        match self.field_type {
+            FieldType::Regex(_) => self
+                .max_length()
+                .unwrap_or_else(MaxLengthFieldAttribute::get_default_value),
+            FieldType::Tlv(_) => self.max_encoded_length(),
+            FieldType::Group(ref group) => group.count * group.fields.iter().map(Field::buffer_length).sum::<usize>(),
+            FieldType::Integer(_)
+            | FieldType::Reserved(_)
+            | FieldType::Varint(_)
+            | FieldType::Flags(_)
+            | FieldType::Timestamp(_) => 0usize,
+        }
+    }
+
+    /// Max number of bytes the field may occupy in a byte stream. TLV records,
+    /// and regex fields matching runs of any length, are bounded by the
+    /// storage the message struct provides for them.
+    pub fn max_encoded_length(&self) -> usize {
+        match self.field_type {
+            FieldType::Regex(ref regex) => regex.pattern().max_length().unwrap_or_else(|| self.buffer_length()),
+            FieldType::Integer(ref integer) => integer.bit_width.div_ceil(8),
+            FieldType::Flags(ref flags) => flags.bit_width.div_ceil(8),
+            FieldType::Timestamp(ref timestamp) => timestamp.bit_width.div_ceil(8),
+            FieldType::Reserved(ref reserved) => reserved.length,
+            FieldType::Varint(ref varint) => varint.max_length(),
+            FieldType::Group(ref group) => {
+                group.count * group.fields.iter().map(Field::max_encoded_length).sum::<usize>()
+            }
+            FieldType::Tlv(ref tlv) => {
+                let max_record_length = tlv
+                    .records
+                    .iter()
+                    .map(|record| {
+                        // Non-integer values are stored as byte arrays, and
+                        // are not matched against the regex
+                        let value_length = match record.field.field_type {
+                            FieldType::Integer(_) => record.field.max_encoded_length(),
+                            _ => record
+                                .field
+                                .max_length()
+                                .unwrap_or_else(MaxLengthFieldAttribute::get_default_value),
+                        };
+
+                        tlv.tag_length + tlv.length_length + value_length
+                    })
+                    .max()
+                    .unwrap_or(0usize);
+                let max_records = self
+                    .max_length()
+                    .unwrap_or_else(MaxLengthFieldAttribute::get_default_value);
+
+                max_records * max_record_length
+            }
+        }
+    }
+
+    /// Width of the field in bits, if it is fixed
+    pub fn bit_width(&self) -> Option<usize> {
+        match self.field_type {
+            FieldType::Regex(_) => None,
+            FieldType::Integer(ref integer) => Some(integer.bit_width),
+            FieldType::Flags(ref flags) => Some(flags.bit_width),
+            FieldType::Timestamp(ref timestamp) => Some(timestamp.bit_width),
+            FieldType::Tlv(_) => None,
+            FieldType::Varint(_) => None,
+            FieldType::Reserved(ref reserved) => Some(reserved.length * 8),
+            FieldType::Group(ref group) => group
+                .fields
+                .iter()
+                .map(Field::bit_width)
+                .sum::<Option<usize>>()
+                .map(|bit_width| bit_width * group.count),
+        }
+    }
+}
+
+/// Bounds of the number of bytes a message's frame occupies on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameSize {
+    pub min: usize,
+    pub max: usize,
+}
+
+/// Represents the entire protocol as a set of messages
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct Protocol {
+    pub messages: std::vec::Vec<Message>,
+    pub attributes: std::vec::Vec<ProtocolAttribute>,
+}
+
+impl Protocol {
+    /// Loads a protocol description from JSON. Structures map onto objects,
+    /// enums take the externally tagged form, e.g. `{"Regex": {"regex":
+    /// "\\xfe"}}` for a field type, or just `"SelfTest"` for a variant
+    /// having no data
+    pub fn from_json(text: &str) -> Result<Protocol, std::string::String> {
+        serde_json::from_str(text).map_err(|error| error.to_string())
+    }
+
+    /// Gets the root message. If absent, the first message is considered root.
+    /// `None`, if the protocol defines no messages
+    pub fn root_message(&self) -> Option<&Message> {
+        for message in &self.messages {
+            for attribute in &message.attributes {
+                if let MessageAttribute::Root = attribute {
+                    return Some(message);
+                }
+            }
+        }
+
+        self.messages.first()
+    }
+
+    /// Messages having an ID, which are parsed by the common dispatcher
+    pub fn dispatched_messages(&self) -> std::vec::Vec<&Message> {
+        self.messages.iter().filter(|message| message.id().is_some()).collect()
+    }
+
+    /// Gets the symbol decoding transform, if the protocol employs one
+    pub fn symbol_decoding(&self) -> Option<&SymbolDecoding> {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::SymbolDecoding(symbol_decoding) = attribute {
+                return Some(symbol_decoding);
+            }
+        }
+
+        None
+    }
+
+    /// Whether a loopback self-test should be generated
+    pub fn self_test(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::SelfTest))
+    }
+
+    /// Whether serializers should be generated
+    pub fn serializers(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::Serializers))
+    }
+
+    /// Whether a TypeScript module should be generated
+    pub fn typescript(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::TypeScript))
+    }
+
+    /// Whether parsers should invoke callbacks on accepted frames
+    pub fn callbacks(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::Callbacks))
+    }
+
+    /// Whether the generated code should be written to the MISRA-C friendly
+    /// profile
+    pub fn misra_c(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::MisraC))
+    }
+
+    /// Whether the generated code must not allocate memory dynamically
+    pub fn static_memory(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::StaticMemory))
+    }
+
+    /// Whether the regex fields of the message refer into the parser's input,
+    /// rather than being copied into the message struct
+    pub fn zero_copy(&self, message: &Message) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::ZeroCopy))
+            && message.layout() == MessageLayout::Bytes
+            && self.symbol_decoding().is_none()
+            && self.byte_stuffing().is_none()
+    }
+
+    /// Whether a function printing the fields of each message struct should be
+    /// generated
+    pub fn debug_dump(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::DebugDump))
+    }
+
+    /// Whether functions initializing and comparing message structs should be
+    /// generated
+    pub fn struct_functions(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::StructFunctions))
+    }
+
+    /// Whether the message's parser counts the bytes it drops while
+    /// resynchronizing
+    pub fn discard_counting(&self, message: &Message) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::DiscardCounting))
+            && message.layout() == MessageLayout::Bytes
+            && message.sync_sequence().is_some()
+    }
+
+    /// Whether the message's parser keeps statistics. Bit-granular messages
+    /// have no parser state
+    pub fn parser_statistics(&self, message: &Message) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::ParserStatistics))
+            && message.layout() == MessageLayout::Bytes
+    }
+
+    /// Bytes of the arrays the message struct holds the field's content in,
+    /// see `Field::buffer_length`. Zero-copy fields take none
+    pub fn field_buffer_length(&self, message: &Message, field: &Field) -> usize {
+        match field.field_type {
+            FieldType::Regex(_) if self.zero_copy(message) => 0usize,
+            _ => field.buffer_length(),
+        }
+    }
+
+    /// Bytes of the arrays the message struct holds the fields' content in,
+    /// see `Message::buffer_length`
+    pub fn buffer_length(&self, message: &Message) -> usize {
+        message
+            .field_regions()
+            .iter()
+            .map(|region| {
+                region
+                    .iter()
+                    .map(|field| self.field_buffer_length(message, field))
+                    .max()
+                    .unwrap_or(0usize)
+            })
+            .sum()
+    }
+
+    /// Gets the protocol's "benchmark" attribute, if present
+    pub fn benchmark(&self) -> Option<&BenchmarkAttribute> {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::Benchmark(benchmark) = attribute {
+                return Some(benchmark);
+            }
+        }
+
+        None
+    }
+
+    /// Gets the protocol's "frame trace" attribute, if present
+    pub fn frame_trace(&self) -> Option<&FrameTraceAttribute> {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::FrameTrace(frame_trace) = attribute {
+                return Some(frame_trace);
+            }
+        }
+
+        None
+    }
+
+    /// Languages the shared constants should be exported into
+    pub fn shared_constants_languages(&self) -> &[TargetLanguage] {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::SharedConstants(languages) = attribute {
+                return languages;
+            }
+        }
+
+        &[]
+    }
+
+    /// Gets the naming rules of generated symbols. If absent, the default ones
+    pub fn symbol_naming(&self) -> SymbolNaming {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::SymbolNaming(symbol_naming) = attribute {
+                return symbol_naming.clone();
+            }
+        }
+
+        SymbolNaming::default()
+    }
+
+    /// Gets the output splitting strategy, if the generated code is split
+    pub fn output_splitting(&self) -> Option<&OutputSplitting> {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::OutputSplitting(output_splitting) = attribute {
+                return Some(output_splitting);
+            }
+        }
+
+        None
+    }
+
+    /// Gets how generated headers guard against being included twice
+    pub fn include_guard(&self) -> IncludeGuard {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::IncludeGuard(include_guard) = attribute {
+                return include_guard.clone();
+            }
+        }
+
+        IncludeGuard::Macro(None)
+    }
+
+    /// Gets the byte stuffing, if the protocol employs one
+    pub fn byte_stuffing(&self) -> Option<&ByteStuffingAttribute> {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::ByteStuffing(byte_stuffing) = attribute {
+                return Some(byte_stuffing);
+            }
+        }
+
+        None
+    }
+
+    /// Gets the version the description is narrowed to, if present
+    pub fn version(&self) -> Option<u32> {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::Version(version) = attribute {
+                return Some(*version);
+            }
+        }
+
+        None
+    }
+
+    /// Gets the group definitions
+    pub fn group_definitions(&self) -> std::vec::Vec<&GroupDefinition> {
+        self.attributes
+            .iter()
+            .filter_map(|attribute| match attribute {
+                ProtocolAttribute::GroupDefinition(definition) => Some(definition),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Gets the group definition of the given name, if present
+    pub fn group_definition(&self, name: &str) -> Option<&GroupDefinition> {
+        self.group_definitions()
+            .into_iter()
+            .find(|definition| definition.name == name)
+    }
+
+    /// Copies the fields of group definitions into the group fields which
+    /// refer to them, and have no fields of their own. Generators expect
+    /// references to be resolved
+    pub fn resolve_group_definitions(&self) -> Protocol {
+        let mut ret = self.clone();
+
+        for field in ret.messages.iter_mut().flat_map(|message| message.fields.iter_mut()) {
+            if let FieldType::Group(ref mut group) = field.field_type {
+                let definition = group
+                    .definition
+                    .as_ref()
+                    .and_then(|definition| self.group_definition(definition));
+
+                if let Some(definition) = definition.filter(|_| group.fields.is_empty()) {
+                    group.fields = definition.fields.clone();
+                }
+            }
+        }
+
+        ret
+    }
+
+    /// Gets the message of the given name, if present
+    pub fn message(&self, name: &str) -> Option<&Message> {
+        self.messages.iter().find(|message| message.name == name)
+    }
+
+    /// Fields the message inherits from its bases, the most basic base's
+    /// first. `None`, if a base is unknown, or the bases run in a cycle
+    pub fn inherited_fields(&self, message: &Message) -> Option<std::vec::Vec<Field>> {
+        let mut ret = std::vec::Vec::new();
+        let mut visited = vec![message.name.as_str()];
+        let mut current = message;
+
+        while let Some(base_name) = current.extends() {
+            if visited.contains(&base_name) {
+                return None;
+            }
+
+            current = self.message(base_name)?;
+            visited.push(base_name);
+            ret.splice(0..0, current.fields.iter().cloned());
+        }
+
+        Some(ret)
+    }
+
+    /// Flattens extensions: the fields of bases are prepended to the fields of
+    /// messages extending them, and `Extends` attributes are dropped. Messages
+    /// with an unknown base, or with bases running in a cycle, are left intact
+    /// for the validation to report. Generators expect extensions to be resolved
+    pub fn resolve_message_extensions(&self) -> Protocol {
+        let mut ret = self.clone();
+
+        for message in &mut ret.messages {
+            if let Some(mut fields) = self.inherited_fields(message) {
+                fields.append(&mut message.fields);
+                message.fields = fields;
+                message
+                    .attributes
+                    .retain(|attribute| !matches!(attribute, MessageAttribute::Extends(_)));
+            }
+        }
+
+        ret
+    }
+
+    /// Mangles the names the generated code makes identifiers of (messages,
+    /// fields, group definitions, TLV records, flag bits, and checksums) into
+    /// valid, distinct identifiers, and renames the references to them along,
+    /// see `Mangler`. Generators take names verbatim, so they expect mangled
+    /// ones. Validation expects the names of the description
+    pub fn mangle_identifiers(&self, mangler: &Mangler) -> Protocol {
+        let message_names = mangler.message_names(self.messages.iter().map(|message| message.name.as_str()));
+        let definition_names = mangler.member_names(
+            self.group_definitions()
+                .into_iter()
+                .map(|definition| definition.name.as_str()),
+        );
+        let mut ret = self.clone();
+
+        for message in &mut ret.messages {
+            message.name = message_names.get(&message.name);
+            mangle_fields(&mut message.fields, mangler, &definition_names);
+
+            for attribute in &mut message.attributes {
+                if let MessageAttribute::Extends(ref mut base) = attribute {
+                    *base = message_names.get(base);
+                }
+            }
+        }
+
+        for attribute in &mut ret.attributes {
+            match attribute {
+                ProtocolAttribute::GroupDefinition(ref mut definition) => {
+                    definition.name = definition_names.get(&definition.name);
+                    mangle_fields(&mut definition.fields, mangler, &definition_names);
+                }
+                ProtocolAttribute::OutputSplitting(OutputSplitting::Groups(ref mut groups)) => {
+                    for name in groups.iter_mut().flat_map(|group| group.messages.iter_mut()) {
+                        *name = message_names.get(name);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        ret
+    }
+
+    /// Narrows a description covering several protocol versions down to one
+    /// version: fields absent in the version, group definitions' included, are
+    /// dropped, and the version is
+    /// recorded in the protocol's attributes
+    pub fn revision(&self, version: u32) -> Protocol {
+        let mut attributes = self
+            .attributes
+            .iter()
+            .filter(|attribute| !matches!(attribute, ProtocolAttribute::Version(_)))
+            .map(|attribute| match attribute {
+                ProtocolAttribute::GroupDefinition(definition) => {
+                    ProtocolAttribute::GroupDefinition(GroupDefinition {
+                        name: definition.name.clone(),
+                        fields: definition
+                            .fields
+                            .iter()
+                            .filter(|field| field.is_present_in(version))
+                            .map(|field| field.revision(version))
+                            .collect(),
+                    })
+                }
+                other => other.clone(),
+            })
+            .collect::<std::vec::Vec<ProtocolAttribute>>();
+        attributes.push(ProtocolAttribute::Version(version));
+
+        Protocol {
+            messages: self
+                .messages
+                .iter()
+                .map(|message| Message {
+                    name: message.name.clone(),
+                    fields: message
+                        .fields
+                        .iter()
+                        .filter(|field| field.is_present_in(version))
+                        .map(|field| field.revision(version))
+                        .collect(),
+                    attributes: message.attributes.clone(),
+                })
+                .collect(),
+            attributes,
+        }
+    }
+
+    /// Gets the revision of C the generated code is written in. If absent, C99
+    /// is used
+    pub fn c_standard(&self) -> CStandard {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::CStandard(c_standard) = attribute {
+                return *c_standard;
+            }
+        }
+
+        CStandard::C99
+    }
+
+    /// Gets the options of the C++ header, if one is generated
+    pub fn cpp(&self) -> Option<&CppOptions> {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::Cpp(cpp_options) = attribute {
+                return Some(cpp_options);
+            }
+        }
+
+        None
+    }
+
+    /// Gets the options of Ragel. If absent, the defaults are used
+    pub fn ragel_options(&self) -> RagelOptions {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::Ragel(ragel_options) = attribute {
+                return ragel_options.clone();
+            }
+        }
+
+        RagelOptions::default()
+    }
+
+    /// Gets the way checksums are computed. If absent, lookup tables are used
+    pub fn checksum_implementation(&self) -> ChecksumImplementation {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::ChecksumImplementation(implementation) = attribute {
+                return *implementation;
+            }
+        }
+
+        ChecksumImplementation::Table
+    }
+
+    /// Distinct CRC algorithms the protocol's checksums employ. Algorithms
+    /// differing only in the initial value and the final XOR share routines,
+    /// so only one of them is listed
+    pub fn crc_algorithms(&self) -> std::vec::Vec<&CrcAlgorithm> {
+        let mut ret: std::vec::Vec<&CrcAlgorithm> = std::vec::Vec::new();
+
+        for field in self.messages.iter().flat_map(|message| message.fields.iter()) {
+            if let Some(checksum) = field.checksum() {
+                let algorithm = &checksum.algorithm;
+
+                if !ret.iter().any(|other| other.shares_routine(algorithm)) {
+                    ret.push(algorithm);
+                }
+            }
+        }
+
+        ret
+    }
+
+    /// Whether IDs no message claims are declared to be expected
+    pub fn has_dispatch_default_arm(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::DispatchDefaultArm))
+    }
+
+    /// Gets the protocol's frame size budget, if present
+    pub fn frame_size_budget(&self) -> Option<usize> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::FrameSizeBudget(budget) = attribute {
+                ret = Some(*budget);
+            }
+        }
+
+        ret
+    }
+
+    /// Gets the protocol's target profile, if present
+    pub fn target(&self) -> Option<&TargetProfile> {
+        let mut ret = None;
+
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::Target(target) = attribute {
+                ret = Some(target);
+            }
+        }
+
+        ret
+    }
+
+    /// Bounds of the size of a message's frame on the wire, i.e. after symbol
+    /// encoding and byte stuffing, including the sync sequence
+    pub fn frame_size(&self, message: &Message) -> FrameSize {
+        let sync_length = message.sync_sequence().map_or(0usize, <[u8]>::len);
+        let (min, max) = match message.fixed_length() {
+            Some(length) => (length, length),
+            None => (message.min_encoded_length(), message.max_encoded_length()),
+        };
+
+        FrameSize {
+            min: self.symbol_encoded_length(message, min + sync_length),
+            max: self.encoded_frame_length(message, max + sync_length),
+        }
+    }
+
+    /// Length of a message's frame on the wire, given its length before symbol
+    /// encoding. With byte stuffing, the worst case of every byte being escaped
+    /// is assumed
+    pub fn encoded_frame_length(&self, message: &Message, length: usize) -> usize {
+        let length = self.symbol_encoded_length(message, length);
+
+        match (message.layout(), self.byte_stuffing()) {
+            (MessageLayout::Bytes, Some(_)) => length * 2,
+            _ => length,
+        }
+    }
+
+    fn symbol_encoded_length(&self, message: &Message, length: usize) -> usize {
+        match (message.layout(), self.symbol_decoding()) {
+            (MessageLayout::Bytes, Some(SymbolDecoding::FourBFiveB)) => (length * 10).div_ceil(8),
+            _ => length,
+        }
+    }
+
+    /// Whether the protocol's helpers come from the runtime library
+    pub fn uses_runtime(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::Runtime))
+    }
+
+    /// Whether the generated files have blocks reserved for user code
+    pub fn user_code_anchors(&self) -> bool {
+        self.attributes
+            .iter()
+            .any(|attribute| matches!(attribute, ProtocolAttribute::UserCodeAnchors))
+    }
+
+    /// Gets the protocol's description, if present
+    pub fn doc(&self) -> Option<&str> {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::Doc(doc) = attribute {
+                return Some(doc);
+            }
+        }
+
+        None
+    }
+
+    /// Gets the protocol's "diagnostic strings" attribute, if present
+    pub fn diagnostic_strings(&self) -> Option<&DiagnosticStringsAttribute> {
+        for attribute in &self.attributes {
+            if let ProtocolAttribute::DiagnosticStrings(diagnostic_strings) = attribute {
+                return Some(diagnostic_strings);
+            }
+        }
+
+        None
+    }
+}
+
+/// Mangles the names of a scope of fields, and the names scoped by each field,
+/// see `Protocol::mangle_identifiers()`. Fields refer to the fields of the
+/// same scope, and to checksums of the same scope, by name
+fn mangle_fields(fields: &mut [Field], mangler: &Mangler, definition_names: &MangledNames) {
+    let field_names = mangler.member_names(fields.iter().map(|field| field.name.as_str()));
+    let checksum_names = mangler.member_names(fields.iter().flat_map(|field| {
+        field.attributes.iter().filter_map(|attribute| match attribute {
+            FieldAttribute::StartChecksum(ref name) | FieldAttribute::StopChecksum(ref name) => Some(name.as_str()),
+            FieldAttribute::Checksum(ref checksum) => Some(checksum.name.as_str()),
+            _ => None,
+        })
+    }));
+    let rename_field = |name: &str| field_names.get(name);
+
+    for field in fields.iter_mut() {
+        field.name = field_names.get(&field.name);
+
+        for attribute in &mut field.attributes {
+            match attribute {
+                FieldAttribute::ConditionalOn(ref mut conditional_on) => {
+                    conditional_on.field = field_names.get(&conditional_on.field);
+                }
+                FieldAttribute::LengthOf(ref mut length_of) => {
+                    length_of.first = field_names.get(&length_of.first);
+                    length_of.last = field_names.get(&length_of.last);
+
+                    if let Some(ref mut length) = length_of.length {
+                        length.rename_fields(&rename_field);
+                    }
+                }
+                FieldAttribute::Check(ref mut expression) => expression.rename_fields(&rename_field),
+                FieldAttribute::StartChecksum(ref mut name) | FieldAttribute::StopChecksum(ref mut name) => {
+                    *name = checksum_names.get(name);
+                }
+                FieldAttribute::Checksum(ref mut checksum) => checksum.name = checksum_names.get(&checksum.name),
+                _ => {}
+            }
+        }
+
+        match field.field_type {
+            FieldType::Group(ref mut group) => {
+                mangle_fields(&mut group.fields, mangler, definition_names);
+                group.definition = group.definition.as_ref().map(|definition| definition_names.get(definition));
+            }
+            FieldType::Tlv(ref mut tlv) => {
+                let record_names = mangler.member_names(tlv.records.iter().map(|record| record.field.name.as_str()));
+
+                for record in &mut tlv.records {
+                    record.field.name = record_names.get(&record.field.name);
+                }
+            }
+            FieldType::Flags(ref mut flags) => {
+                let bit_names = mangler.member_names(flags.bits.iter().map(|bit| bit.name.as_str()));
+
+                for bit in &mut flags.bits {
+                    bit.name = bit_names.get(&bit.name);
+                }
+            }
+            _ => {}
+        }
     }
 }
@@ -1,14 +1,13 @@
 //! Validates BPIR. Looks for common mistakes, and warns user of potential
 //! caveats, such as not specifying a field's max length.
 
+use crate::bpir::pattern;
 use crate::bpir::representation;
+use crate::utility::identifier::{is_identifier, name_reserved_by, Mangler, C_KEYWORDS, CPP_KEYWORDS};
 use std::boxed;
 use std::string;
 use std::vec;
 
-use super::representation::Message;
-use super::representation::Protocol;
-
 #[derive(Clone)]
 pub enum LintResult {
     /// A particular linter did not find errors in the message's structure.
@@ -21,25 +20,479 @@ pub enum LintResult {
     Error(string::String),
 }
 
-/// Aggregates the results of linting for each message of the protocol.
-/// Instances of `LintResult::Ok` are not included into the
-/// resulting report. If at least one instance of
-/// `LintResult::Error` is present, the protocol definition MUST be
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "Warning"),
+            Severity::Error => write!(f, "Error"),
+        }
+    }
+}
+
+/// Identifies a check, so its findings may be told apart and silenced
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct Rule {
+    /// Stable identifier, e.g. `RB022`. A retired rule's ID is not reused.
+    /// Rules of linters registered by users should not take the `RB` prefix
+    pub id: &'static str,
+
+    /// Short descriptive name, e.g. `checksum`
+    pub name: &'static str,
+}
+
+/// Every rule the linters report findings by
+pub mod rules {
+    use super::Rule;
+
+    pub const MOCK: Rule = Rule {
+        id: "RB000",
+        name: "mock",
+    };
+    pub const FIELD_NAME: Rule = Rule {
+        id: "RB001",
+        name: "field_name",
+    };
+    pub const REGEX_FIELD_MAX_LENGTH: Rule = Rule {
+        id: "RB002",
+        name: "regex_field_max_length",
+    };
+    pub const UNORDERED_FIELD_COUNT: Rule = Rule {
+        id: "RB003",
+        name: "unordered_field_count",
+    };
+    pub const INTEGER_BIT_WIDTH: Rule = Rule {
+        id: "RB004",
+        name: "integer_bit_width",
+    };
+    pub const BIT_LAYOUT: Rule = Rule {
+        id: "RB005",
+        name: "bit_layout",
+    };
+    pub const VARINT: Rule = Rule {
+        id: "RB006",
+        name: "varint",
+    };
+    pub const FLAGS: Rule = Rule {
+        id: "RB007",
+        name: "flags",
+    };
+    pub const TIMESTAMP: Rule = Rule {
+        id: "RB008",
+        name: "timestamp",
+    };
+    pub const TLV_FIELD: Rule = Rule {
+        id: "RB009",
+        name: "tlv_field",
+    };
+    pub const SCALE: Rule = Rule {
+        id: "RB010",
+        name: "scale",
+    };
+    pub const UNIT: Rule = Rule {
+        id: "RB011",
+        name: "unit",
+    };
+    pub const CHECK: Rule = Rule {
+        id: "RB012",
+        name: "check",
+    };
+    pub const RANGE: Rule = Rule {
+        id: "RB013",
+        name: "range",
+    };
+    pub const RESERVED_FIELD: Rule = Rule {
+        id: "RB014",
+        name: "reserved_field",
+    };
+    pub const GROUP_FIELD: Rule = Rule {
+        id: "RB015",
+        name: "group_field",
+    };
+    pub const CONDITIONAL_ON: Rule = Rule {
+        id: "RB016",
+        name: "conditional_on",
+    };
+    pub const DEPRECATED_FIELD: Rule = Rule {
+        id: "RB017",
+        name: "deprecated_field",
+    };
+    pub const SELECTOR_EXHAUSTIVENESS: Rule = Rule {
+        id: "RB018",
+        name: "selector_exhaustiveness",
+    };
+    pub const FIXED_LENGTH: Rule = Rule {
+        id: "RB019",
+        name: "fixed_length",
+    };
+    pub const ID: Rule = Rule {
+        id: "RB020",
+        name: "id",
+    };
+    pub const VERSION: Rule = Rule {
+        id: "RB021",
+        name: "version",
+    };
+    pub const CHECKSUM: Rule = Rule {
+        id: "RB022",
+        name: "checksum",
+    };
+    pub const LENGTH_OF: Rule = Rule {
+        id: "RB023",
+        name: "length_of",
+    };
+    pub const SYNC_SEQUENCE: Rule = Rule {
+        id: "RB024",
+        name: "sync_sequence",
+    };
+    pub const FRAME_TRACE: Rule = Rule {
+        id: "RB025",
+        name: "frame_trace",
+    };
+    pub const BENCHMARK: Rule = Rule {
+        id: "RB026",
+        name: "benchmark",
+    };
+    pub const OUTPUT_SPLITTING: Rule = Rule {
+        id: "RB027",
+        name: "output_splitting",
+    };
+    pub const BYTE_STUFFING: Rule = Rule {
+        id: "RB028",
+        name: "byte_stuffing",
+    };
+    pub const CHECKSUM_IMPLEMENTATION: Rule = Rule {
+        id: "RB029",
+        name: "checksum_implementation",
+    };
+    pub const REVISION: Rule = Rule {
+        id: "RB030",
+        name: "revision",
+    };
+    pub const DISPATCH: Rule = Rule {
+        id: "RB031",
+        name: "dispatch",
+    };
+    pub const START_PATTERN: Rule = Rule {
+        id: "RB032",
+        name: "start_pattern",
+    };
+    pub const GROUP_DEFINITION: Rule = Rule {
+        id: "RB033",
+        name: "group_definition",
+    };
+    pub const MESSAGE_EXTENSION: Rule = Rule {
+        id: "RB034",
+        name: "message_extension",
+    };
+    pub const SUPPRESS_LINT: Rule = Rule {
+        id: "RB035",
+        name: "suppress_lint",
+    };
+    pub const MESSAGE_NAME: Rule = Rule {
+        id: "RB036",
+        name: "message_name",
+    };
+    pub const ROOT_MESSAGE: Rule = Rule {
+        id: "RB037",
+        name: "root_message",
+    };
+    pub const RESERVED_NAME: Rule = Rule {
+        id: "RB038",
+        name: "reserved_name",
+    };
+    pub const FRAME_SIZE: Rule = Rule {
+        id: "RB039",
+        name: "frame_size",
+    };
+    pub const CHECKSUM_COVERAGE: Rule = Rule {
+        id: "RB040",
+        name: "checksum_coverage",
+    };
+    pub const REGEX_OVERLAP: Rule = Rule {
+        id: "RB041",
+        name: "regex_overlap",
+    };
+    pub const BUFFER_BUDGET: Rule = Rule {
+        id: "RB042",
+        name: "buffer_budget",
+    };
+    pub const OVERFLOW: Rule = Rule {
+        id: "RB043",
+        name: "overflow",
+    };
+    pub const CPP: Rule = Rule {
+        id: "RB044",
+        name: "cpp",
+    };
+    pub const REGEX_SYNTAX: Rule = Rule {
+        id: "RB045",
+        name: "regex_syntax",
+    };
+    pub const RAGEL: Rule = Rule {
+        id: "RB046",
+        name: "ragel",
+    };
+
+    pub const ALL: &[Rule] = &[
+        MOCK,
+        FIELD_NAME,
+        REGEX_FIELD_MAX_LENGTH,
+        UNORDERED_FIELD_COUNT,
+        INTEGER_BIT_WIDTH,
+        BIT_LAYOUT,
+        VARINT,
+        FLAGS,
+        TIMESTAMP,
+        TLV_FIELD,
+        SCALE,
+        UNIT,
+        CHECK,
+        RANGE,
+        RESERVED_FIELD,
+        GROUP_FIELD,
+        CONDITIONAL_ON,
+        DEPRECATED_FIELD,
+        SELECTOR_EXHAUSTIVENESS,
+        FIXED_LENGTH,
+        ID,
+        VERSION,
+        CHECKSUM,
+        LENGTH_OF,
+        SYNC_SEQUENCE,
+        FRAME_TRACE,
+        BENCHMARK,
+        OUTPUT_SPLITTING,
+        BYTE_STUFFING,
+        CHECKSUM_IMPLEMENTATION,
+        REVISION,
+        DISPATCH,
+        START_PATTERN,
+        GROUP_DEFINITION,
+        MESSAGE_EXTENSION,
+        SUPPRESS_LINT,
+        MESSAGE_NAME,
+        ROOT_MESSAGE,
+        RESERVED_NAME,
+        FRAME_SIZE,
+        CHECKSUM_COVERAGE,
+        REGEX_OVERLAP,
+        BUFFER_BUDGET,
+        OVERFLOW,
+        CPP,
+        REGEX_SYNTAX,
+        RAGEL,
+    ];
+}
+
+/// A finding of a linter, along with the part of the protocol it concerns
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+
+    /// The check which produced the finding
+    pub rule: Rule,
+
+    /// Message the finding concerns, if any particular one
+    pub message_name: Option<string::String>,
+
+    /// Field the finding concerns, if any particular one
+    pub field_name: Option<string::String>,
+    pub text: string::String,
+}
+
+impl Diagnostic {
+    /// The part of the protocol the finding concerns, e.g. `message Ping,
+    /// field id`. `None`, if the finding concerns the protocol as a whole
+    pub fn location(&self) -> Option<string::String> {
+        match (&self.message_name, &self.field_name) {
+            (Some(message_name), Some(field_name)) => Some(format!("message {0}, field {1}", message_name, field_name)),
+            (Some(message_name), None) => Some(format!("message {0}", message_name)),
+            (None, Some(field_name)) => Some(format!("field {0}", field_name)),
+            (None, None) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{0}: {1} [{2} {3}]",
+            self.severity, self.text, self.rule.id, self.rule.name
+        )
+    }
+}
+
+/// Aggregates the findings of linting the protocol and each of its messages.
+/// If at least one error is present, the protocol definition MUST be
 /// considered faulty.
 #[derive(Clone, Default)]
 pub struct ProtocolLintResult {
-    pub message_lint_results: vec::Vec<LintResult>,
+    pub diagnostics: vec::Vec<Diagnostic>,
+
+    /// Rules whose warnings are silenced, as the message, the field, and the
+    /// rule's ID
+    suppressed_lints: vec::Vec<(string::String, string::String, string::String)>,
 }
 
 impl ProtocolLintResult {
-    fn count_errors(&self) -> usize {
-        self.message_lint_results
+    /// Makes an empty result, which drops the warnings of any linter about
+    /// the fields suppressing their rules
+    pub fn new(protocol: &representation::Protocol) -> Self {
+        let suppressed_lints = protocol
+            .messages
+            .iter()
+            .flat_map(|message| message.fields.iter().map(move |field| (message, field)))
+            .flat_map(|(message, field)| {
+                field
+                    .suppressed_lints()
+                    .into_iter()
+                    .map(|id| (message.name.clone(), field.name.clone(), id.to_string()))
+            })
+            .collect();
+
+        ProtocolLintResult {
+            diagnostics: vec::Vec::new(),
+            suppressed_lints,
+        }
+    }
+
+    pub fn count_errors(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+            .count()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    pub fn has_warnings(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Warning)
+    }
+
+    pub fn count_warnings(&self) -> usize {
+        self.diagnostics
             .iter()
-            .map(|ref item| match item {
-                LintResult::Error(_) => 1usize,
-                _ => 0usize,
+            .filter(|diagnostic| diagnostic.severity == Severity::Warning)
+            .count()
+    }
+
+    /// Renders the findings for reading in a terminal, in the manner of
+    /// rustc: a heading with the severity and the rule's ID, the location, and
+    /// the rule's name, followed by a summary. With `color`, the report is
+    /// highlighted with ANSI escape sequences
+    pub fn render_report(&self, color: bool) -> string::String {
+        let paint = |style: &str, text: &str| match color {
+            true => format!("\x1b[{0}m{1}\x1b[0m", style, text),
+            false => text.to_string(),
+        };
+        let severity_style = |severity: Severity| match severity {
+            Severity::Error => "1;31",
+            Severity::Warning => "1;33",
+        };
+        let mut ret = string::String::new();
+
+        for diagnostic in &self.diagnostics {
+            let severity = diagnostic.severity.to_string().to_lowercase();
+            ret += &format!(
+                "{0}{1}\n",
+                paint(
+                    severity_style(diagnostic.severity),
+                    &format!("{0}[{1}]", severity, diagnostic.rule.id)
+                ),
+                paint("1", &format!(": {0}", diagnostic.text))
+            );
+
+            if let Some(location) = diagnostic.location() {
+                ret += &format!("  {0} {1}\n", paint("1;34", "-->"), location);
+            }
+
+            ret += &format!("  {0} rule: {1}\n\n", paint("1;34", "="), diagnostic.rule.name);
+        }
+
+        let (errors, warnings) = (self.count_errors(), self.count_warnings());
+        let summary = match (errors, warnings) {
+            (0, 0) => return ret,
+            (0, _) => (Severity::Warning, format!("{0} warning(s) emitted", warnings)),
+            _ => (
+                Severity::Error,
+                format!("the protocol is invalid due to {0} error(s); {1} warning(s) emitted", errors, warnings),
+            ),
+        };
+        ret += &format!(
+            "{0}{1}\n",
+            paint(severity_style(summary.0), &summary.0.to_string().to_lowercase()),
+            paint("1", &format!(": {0}", summary.1))
+        );
+
+        ret
+    }
+
+    /// Renders the findings as a JSON document for CI pipelines and editors:
+    /// an object with the `errors` and `warnings` counts, and the
+    /// `diagnostics`, each one with its `severity`, `rule`, `message_name`,
+    /// `field_name`, and `text`
+    pub fn render_json(&self) -> string::String {
+        #[derive(serde::Serialize)]
+        struct Report<'a> {
+            errors: usize,
+            warnings: usize,
+            diagnostics: &'a [Diagnostic],
+        }
+
+        let report = Report {
+            errors: self.count_errors(),
+            warnings: self.count_warnings(),
+            diagnostics: &self.diagnostics,
+        };
+
+        // Serializing plain structures does not fail
+        serde_json::to_string_pretty(&report).unwrap()
+    }
+
+    /// Records the result of a check, unless it is `LintResult::Ok`, or a
+    /// warning the field it concerns suppresses
+    pub fn record(
+        &mut self,
+        rule: Rule,
+        message_name: Option<&str>,
+        field_name: Option<&str>,
+        lint_result: LintResult,
+    ) {
+        let (severity, text) = match lint_result {
+            LintResult::Ok => return,
+            LintResult::Warning(text) => (Severity::Warning, text),
+            LintResult::Error(text) => (Severity::Error, text),
+        };
+
+        if severity == Severity::Warning
+            && self.suppressed_lints.iter().any(|(message, field, id)| {
+                Some(message.as_str()) == message_name && Some(field.as_str()) == field_name && id == rule.id
             })
-            .sum()
+        {
+            return;
+        }
+
+        self.diagnostics.push(Diagnostic {
+            severity,
+            rule,
+            message_name: message_name.map(str::to_string),
+            field_name: field_name.map(str::to_string),
+            text,
+        });
     }
 }
 
@@ -49,18 +502,24 @@ impl ProtocolLintResult {
 /// - The linter MAY be stateful;
 /// - The validation framework calls a set of linters on each one field;
 /// - The linter is GUARANTEED to be called in the same order as fields in a
-/// message are defined;
+///   message are defined;
 /// - On each field, a linter is GUARANTEED to be called once and only once;
 /// - Linters called on the same field MUST NOT make assumptions on the order of
-/// mutual execution;
+///   mutual execution;
 /// - Linters MUST BE functionally independent from each other;
 /// - The scope of the linter is limited by one message. The linter MAY perform
-/// composite (cross-field) checking, i.e. MAY make it so linting result depends
-/// on juxtaposition of 2 or more fields;
+///   composite (cross-field) checking, i.e. MAY make it so linting result depends
+///   on juxtaposition of 2 or more fields;
 /// - The scope of a field linter is limited by one message. If 2 or more
-/// messages are supported by the protocol, the linter MUST NOT implement
-/// cross-message checking.
-trait MessageFieldLint {
+///   messages are supported by the protocol, the linter MUST NOT implement
+///   cross-message checking.
+///
+/// Library users MAY implement the trait, and register their linters with a
+/// `LinterRegistry`.
+pub trait MessageFieldLint {
+    /// Identifies the linter's findings
+    fn rule(&self) -> Rule;
+
     fn lint_field(
         &mut self,
         message: &representation::Message,
@@ -72,34 +531,177 @@ trait MessageFieldLint {
 struct MockLinter {}
 
 impl MessageFieldLint for MockLinter {
+    fn rule(&self) -> Rule {
+        rules::MOCK
+    }
+
+    fn lint_field(
+        &mut self,
+        _message: &representation::Message,
+        _field: &representation::Field,
+    ) -> LintResult {
+        LintResult::Ok
+    }
+}
+
+/// Makes sure that field names are unique within a message, which fields
+/// inherited from a base may break
+#[derive(Default)]
+struct FieldNameLinter {}
+
+impl MessageFieldLint for FieldNameLinter {
+    fn rule(&self) -> Rule {
+        rules::FIELD_NAME
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let position = message
+            .fields
+            .iter()
+            .position(|f| std::ptr::eq(f, field))
+            .unwrap_or(0usize);
+
+        if message.fields[..position].iter().any(|f| f.name == field.name) {
+            return LintResult::Error(format!(
+                "in message {0} field {1} is defined more than once",
+                message.name, field.name
+            ));
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Makes sure that the expressions of "check" attributes refer to fields
+/// having a value by the time the field is parsed: the field itself, and
+/// earlier unconditional integer fields
+#[derive(Default)]
+struct CheckLinter {}
+
+impl MessageFieldLint for CheckLinter {
+    fn rule(&self) -> Rule {
+        rules::CHECK
+    }
+
     fn lint_field(
         &mut self,
         message: &representation::Message,
         field: &representation::Field,
     ) -> LintResult {
+        let position = message
+            .fields
+            .iter()
+            .position(|f| std::ptr::eq(f, field))
+            .unwrap_or(0usize);
+
+        for check in field.checks() {
+            for name in check.fields() {
+                let referred = match name == field.name {
+                    true => Some(field),
+                    false => message.fields[..position]
+                        .iter()
+                        .find(|f| f.name == name && f.conditional_on().is_none()),
+                };
+
+                if referred.is_none_or(|f| f.integer_value_type().is_none()) {
+                    return LintResult::Error(format!(
+                        "in message {0} check of field {1} refers to {2}, which is neither the field, nor an earlier unconditional integer field",
+                        message.name, field.name, name
+                    ));
+                }
+            }
+        }
+
         LintResult::Ok
     }
 }
 
+/// Makes sure that a scaled field's unit may be spelled in its accessor's name
+#[derive(Default)]
+struct UnitLinter {}
+
+impl MessageFieldLint for UnitLinter {
+    fn rule(&self) -> Rule {
+        rules::UNIT
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        match field.unit() {
+            Some(unit) if field.scale().is_some() && crate::utility::string::unit_to_identifier(unit).is_empty() => {
+                LintResult::Error(format!(
+                    "in message {0} field {1} has unit \"{2}\", which can not be spelled in its accessor's name",
+                    message.name, field.name, unit
+                ))
+            }
+            _ => LintResult::Ok,
+        }
+    }
+}
+
+/// Warns of conditions on deprecated fields, as the alternatives outlive the
+/// field selecting them
+#[derive(Default)]
+struct DeprecatedFieldLinter {}
+
+impl MessageFieldLint for DeprecatedFieldLinter {
+    fn rule(&self) -> Rule {
+        rules::DEPRECATED_FIELD
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let selector = field
+            .conditional_on()
+            .and_then(|conditional_on| message.fields.iter().find(|f| f.name == conditional_on.field));
+
+        match selector.and_then(|selector| selector.deprecated().map(|reason| (selector, reason))) {
+            Some((selector, reason)) => LintResult::Warning(format!(
+                "in message {0} field {1} is conditional on deprecated field {2} ({3})",
+                message.name, field.name, selector.name, reason
+            )),
+            None => LintResult::Ok,
+        }
+    }
+}
+
 /// Makes sure that a "regex" field has "max length" attribute
 #[derive(Default)]
 struct RegexFieldMaxLengthLinter {}
 
 impl MessageFieldLint for RegexFieldMaxLengthLinter {
+    fn rule(&self) -> Rule {
+        rules::REGEX_FIELD_MAX_LENGTH
+    }
+
     fn lint_field(
         &mut self,
         message: &representation::Message,
         field: &representation::Field,
     ) -> LintResult {
-        match field.field_type {
-            representation::FieldType::Regex(_) => {
-                for attribute in &field.attributes {
-                    if let representation::FieldAttribute::MaxLength(_) = attribute {
-                        return LintResult::Ok;
-                    }
-                }
-            }
-            _ => {}
+        let requires_max_length = match field.field_type {
+            representation::FieldType::Regex(_) => true,
+            representation::FieldType::Integer(_) => false,
+            representation::FieldType::Tlv(_) => true,
+            representation::FieldType::Reserved(_) => false,
+            representation::FieldType::Varint(_) => false,
+            representation::FieldType::Group(_) => false,
+            representation::FieldType::Flags(_) => false,
+            representation::FieldType::Timestamp(_) => false,
+        };
+
+        if !requires_max_length || field.max_length().is_some() {
+            return LintResult::Ok;
         }
 
         LintResult::Error(format!(
@@ -109,73 +711,2605 @@ impl MessageFieldLint for RegexFieldMaxLengthLinter {
     }
 }
 
-struct CompositeMessageLinter {
-    pending_linters: vec::Vec<boxed::Box<dyn MessageFieldLint>>,
-}
+/// Makes sure that the regex of a "regex" field is written in the supported
+/// dialect, see `bpir::pattern`
+#[derive(Default)]
+struct RegexSyntaxLinter {}
 
-impl CompositeMessageLinter {
-    pub fn new() -> Self {
-        let mut instance = CompositeMessageLinter {
-            pending_linters: vec::Vec::default(),
+impl MessageFieldLint for RegexSyntaxLinter {
+    fn rule(&self) -> Rule {
+        rules::REGEX_SYNTAX
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let representation::FieldType::Regex(ref regex) = field.field_type else {
+            return LintResult::Ok;
         };
-        instance
-            .pending_linters
-            .push(boxed::Box::new(MockLinter::default()));
-        instance
-            .pending_linters
-            .push(boxed::Box::new(RegexFieldMaxLengthLinter::default()));
 
-        instance
+        match pattern::Pattern::parse(&regex.regex) {
+            Ok(_) => LintResult::Ok,
+            Err(error) => LintResult::Error(format!(
+                "in message {0} field {1} has regex {2:?} of unsupported syntax: {3}",
+                message.name, field.name, regex.regex, error
+            )),
+        }
+    }
+}
+
+/// Makes sure that only fields stored into arrays bounded by "max length"
+/// have an overflow policy
+#[derive(Default)]
+struct OverflowLinter {}
+
+impl MessageFieldLint for OverflowLinter {
+    fn rule(&self) -> Rule {
+        rules::OVERFLOW
     }
 
-    pub fn lint_message(
+    fn lint_field(
         &mut self,
         message: &representation::Message,
-        protocol_lint_result: &mut ProtocolLintResult,
-    ) {
-        for field in &message.fields {
-            (self.lint_field(message, field, protocol_lint_result));
+        field: &representation::Field,
+    ) -> LintResult {
+        let has_overflow_policy = field
+            .attributes
+            .iter()
+            .any(|attribute| matches!(attribute, representation::FieldAttribute::Overflow(_)));
+
+        match field.field_type {
+            representation::FieldType::Regex(_) => LintResult::Ok,
+            _ if has_overflow_policy => LintResult::Error(format!(
+                "in message {0} field {1} has Overflow attribute, which only applies to regex fields",
+                message.name, field.name
+            )),
+            _ => LintResult::Ok,
         }
     }
+}
+
+/// Makes sure that every field of an unordered message fits into the message's
+/// presence bitmap
+#[derive(Default)]
+struct UnorderedFieldCountLinter {}
+
+impl MessageFieldLint for UnorderedFieldCountLinter {
+    fn rule(&self) -> Rule {
+        rules::UNORDERED_FIELD_COUNT
+    }
 
     fn lint_field(
         &mut self,
         message: &representation::Message,
         field: &representation::Field,
-        protocol_lint_result: &mut ProtocolLintResult,
-    ) {
-        for linter in &mut self.pending_linters {
-            protocol_lint_result
-                .message_lint_results
-                .push(linter.lint_field(message, field));
+    ) -> LintResult {
+        if message.field_order() != representation::FieldOrder::Unordered {
+            return LintResult::Ok;
+        }
+
+        let field_index = message
+            .fields
+            .iter()
+            .position(|item| std::ptr::eq(item, field))
+            .unwrap_or(0usize);
+
+        if field_index < representation::FieldOrder::get_max_unordered_fields() {
+            return LintResult::Ok;
         }
+
+        LintResult::Error(format!(
+            "in unordered message {0} field {1} exceeds the limit of {2} fields",
+            message.name,
+            field.name,
+            representation::FieldOrder::get_max_unordered_fields()
+        ))
     }
 }
 
-/// Invokes a series of linters on each message of the `protocol`. Produces a
-/// report consisting of Warnings and Errors that were found by the linters.
-pub fn validate_protocol(protocol: &representation::Protocol) -> ProtocolLintResult {
-    let mut linter = CompositeMessageLinter::new();
-    let mut protocol_lint_result = ProtocolLintResult::default();
+/// Looks for regex fields the parser can not tell apart from each other.
+/// Fields of an unordered message may follow each other in any order, so the
+/// parser tells them apart by their content: if a match of one field starts a
+/// match of the other, then having matched it, the parser can not tell,
+/// whether it is complete, or the other one is still underway. In a message
+/// of strict order, a regex field followed by another one can not tell where
+/// it ends, if it may go on with a byte the next one starts with
+#[derive(Default)]
+struct RegexOverlapLinter {}
 
-    for message in &protocol.messages {
-        linter.lint_message(message, &mut protocol_lint_result);
+impl MessageFieldLint for RegexOverlapLinter {
+    fn rule(&self) -> Rule {
+        rules::REGEX_OVERLAP
     }
 
-    for lint_result in &protocol_lint_result.message_lint_results {
-        match lint_result {
-            LintResult::Error(ref linting_message) => {
-                log::error!("Error: {}", linting_message);
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let field_pattern = |field: &representation::Field| match field.field_type {
+            representation::FieldType::Regex(ref regex) => Some(regex.pattern()),
+            _ => None,
+        };
+        let pattern = match field_pattern(field) {
+            Some(pattern) => pattern,
+            None => return LintResult::Ok,
+        };
+        // Each pair is reported once, on the later field
+        let preceding = message.fields.iter().take_while(|other| !std::ptr::eq(*other, field));
+
+        match message.field_order() {
+            representation::FieldOrder::Unordered => {
+                for other in preceding {
+                    let other_pattern = match field_pattern(other) {
+                        Some(other_pattern) => other_pattern,
+                        None => continue,
+                    };
+
+                    if pattern.is_prefix_of(&other_pattern) || other_pattern.is_prefix_of(&pattern) {
+                        return LintResult::Warning(format!(
+                            "in unordered message {0} fields {1} and {2} overlap, as one of them may start with the other",
+                            message.name, other.name, field.name
+                        ));
+                    }
+                }
+            }
+            representation::FieldOrder::Strict => {
+                let (previous, previous_pattern) = match preceding.last() {
+                    Some(previous) => match field_pattern(previous) {
+                        Some(previous_pattern) => (previous, previous_pattern),
+                        None => return LintResult::Ok,
+                    },
+                    None => return LintResult::Ok,
+                };
+                let ambiguous = previous_pattern.continuation_bytes().intersection(&pattern.first_bytes());
+
+                if let Some(byte) = ambiguous.first() {
+                    return LintResult::Warning(format!(
+                        "in message {0} fields {1} and {2} overlap, as {1} may go on with byte 0x{3:02X}, which {2} may start with",
+                        message.name, previous.name, field.name, byte
+                    ));
+                }
             }
-            LintResult::Warning(ref linting_message) => {
-                log::warn!("Warning: {}", linting_message)
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Makes sure that an integer field's width is supported by the message's
+/// layout
+#[derive(Default)]
+struct IntegerBitWidthLinter {}
+
+impl MessageFieldLint for IntegerBitWidthLinter {
+    fn rule(&self) -> Rule {
+        rules::INTEGER_BIT_WIDTH
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let bit_width = match field.integer_value_type() {
+            Some(integer) => integer.bit_width,
+            None => return LintResult::Ok,
+        };
+
+        if bit_width == 0 || bit_width > 64 {
+            return LintResult::Error(format!(
+                "in message {0} field {1} has unsupported width of {2} bits, expected 1..64",
+                message.name, field.name, bit_width
+            ));
+        }
+
+        // Odd widths (24, 48 bits) are held by the next wider type. Varints
+        // are not stored as is, so any width goes
+        if message.layout() == representation::MessageLayout::Bytes
+            && bit_width % 8 != 0
+            && matches!(
+                field.field_type,
+                representation::FieldType::Integer(_)
+                    | representation::FieldType::Flags(_)
+                    | representation::FieldType::Timestamp(_)
+            )
+        {
+            return LintResult::Error(format!(
+                "in byte-granular message {0} field {1} has width of {2} bits, which is not a whole number of bytes, consider using bit layout",
+                message.name, field.name, bit_width
+            ));
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Makes sure that a bit-granular message only consists of fields that a bit
+/// reader can handle
+#[derive(Default)]
+struct BitLayoutLinter {}
+
+impl MessageFieldLint for BitLayoutLinter {
+    fn rule(&self) -> Rule {
+        rules::BIT_LAYOUT
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        if message.layout() != representation::MessageLayout::Bits {
+            // Byte-granular fields are assembled from whole bytes
+            if message.bit_order() != representation::BitOrder::MsbFirst
+                && std::ptr::eq(field, &message.fields[0])
+            {
+                return LintResult::Warning(format!(
+                    "bit order of byte-granular message {0} is ignored, consider using bit layout",
+                    message.name
+                ));
             }
-            _ => {}
+
+            return LintResult::Ok;
+        }
+
+        if message.field_order() == representation::FieldOrder::Unordered
+            && std::ptr::eq(field, &message.fields[0])
+        {
+            return LintResult::Error(format!(
+                "bit-granular message {0} can not have unordered fields",
+                message.name
+            ));
+        }
+
+        if field.bit_width().is_none() {
+            return LintResult::Error(format!(
+                "in bit-granular message {0} field {1} does not have a fixed width",
+                message.name, field.name
+            ));
         }
+
+        LintResult::Ok
     }
+}
 
-    if protocol_lint_result.count_errors() > 0 {
-        panic!("Protocol description is invalid, panicking");
+/// Warns about a varint attribute which has no effect
+#[derive(Default)]
+struct VarintLinter {}
+
+impl MessageFieldLint for VarintLinter {
+    fn rule(&self) -> Rule {
+        rules::VARINT
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        match field.field_type {
+            representation::FieldType::Varint(ref varint) if varint.zigzag && !varint.signed => {
+                LintResult::Warning(format!(
+                    "in message {0} varint field {1} is unsigned, zigzag encoding is ignored",
+                    message.name, field.name
+                ))
+            }
+            _ => LintResult::Ok,
+        }
+    }
+}
+
+/// Makes sure that the named bits of a flags field fit into the field, and
+/// may be spelled in the names of their constants
+#[derive(Default)]
+struct FlagsLinter {}
+
+impl MessageFieldLint for FlagsLinter {
+    fn rule(&self) -> Rule {
+        rules::FLAGS
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let flags = match field.field_type {
+            representation::FieldType::Flags(ref flags) => flags,
+            _ => return LintResult::Ok,
+        };
+
+        if field.scale().is_some() || field.conditional_on().is_some() {
+            return LintResult::Error(format!(
+                "in message {0} flags field {1} can be neither scaled, nor conditional",
+                message.name, field.name
+            ));
+        }
+
+        for (index, bit) in flags.bits.iter().enumerate() {
+            if bit.name.is_empty() || !bit.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return LintResult::Error(format!(
+                    "in message {0} flags field {1} has bit \"{2}\", whose name is not an identifier",
+                    message.name, field.name, bit.name
+                ));
+            }
+
+            if bit.position >= flags.bit_width {
+                return LintResult::Error(format!(
+                    "in message {0} flags field {1} has bit {2} at position {3}, which exceeds the width of {4} bits",
+                    message.name, field.name, bit.name, bit.position, flags.bit_width
+                ));
+            }
+
+            if let Some(other) = flags.bits[..index]
+                .iter()
+                .find(|other| other.name == bit.name || other.position == bit.position)
+            {
+                return LintResult::Error(format!(
+                    "in message {0} flags field {1} has bits {2} and {3} with the same name or position",
+                    message.name, field.name, other.name, bit.name
+                ));
+            }
+        }
+
+        if flags.bits.is_empty() {
+            return LintResult::Warning(format!(
+                "in message {0} flags field {1} has no named bits, consider using an integer",
+                message.name, field.name
+            ));
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Makes sure that a timestamp field may be converted into milliseconds since
+/// the Unix epoch without overflowing 64 bits
+#[derive(Default)]
+struct TimestampLinter {}
+
+impl MessageFieldLint for TimestampLinter {
+    fn rule(&self) -> Rule {
+        rules::TIMESTAMP
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let timestamp = match field.field_type {
+            representation::FieldType::Timestamp(ref timestamp) => timestamp,
+            _ => return LintResult::Ok,
+        };
+
+        if field.scale().is_some() {
+            return LintResult::Error(format!(
+                "in message {0} timestamp field {1} can not be scaled",
+                message.name, field.name
+            ));
+        }
+
+        if timestamp.tick_us == 0 {
+            return LintResult::Error(format!(
+                "in message {0} timestamp field {1} has ticks of zero duration",
+                message.name, field.name
+            ));
+        }
+
+        // The conversion multiplies the ticks by their duration in
+        // microseconds, unless it is whole milliseconds
+        let max_ticks = timestamp.integer().max_value();
+        let max_product = match timestamp.tick_us % 1000 {
+            0 => max_ticks.checked_mul(i128::from(timestamp.tick_us / 1000)),
+            _ => max_ticks.checked_mul(i128::from(timestamp.tick_us)),
+        };
+        let max_ms = max_ticks
+            .checked_mul(i128::from(timestamp.tick_us))
+            .map(|us| us / 1000 + i128::from(timestamp.epoch_ms));
+
+        match (max_product, max_ms) {
+            (Some(product), Some(ms)) if product <= i128::from(i64::MAX) && ms <= i128::from(i64::MAX) => {
+                LintResult::Ok
+            }
+            _ => LintResult::Error(format!(
+                "in message {0} timestamp field {1} may exceed 64 bits, once converted into milliseconds",
+                message.name, field.name
+            )),
+        }
+    }
+}
+
+/// Warns about suppressing lint rules which do not exist, e.g. due to a typo
+struct SuppressLintLinter {
+    /// Built-in rules, and those of the registered linters
+    known_rules: vec::Vec<Rule>,
+}
+
+impl MessageFieldLint for SuppressLintLinter {
+    fn rule(&self) -> Rule {
+        rules::SUPPRESS_LINT
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        match field
+            .suppressed_lints()
+            .into_iter()
+            .find(|id| !self.known_rules.iter().any(|rule| rule.id == *id))
+        {
+            Some(id) => LintResult::Warning(format!(
+                "in message {0} field {1} suppresses unknown lint rule {2}",
+                message.name, field.name, id
+            )),
+            None => LintResult::Ok,
+        }
+    }
+}
+
+/// Looks for fields named after keywords of the target languages, or after
+/// identifiers the generated code uses. Struct members are named after
+/// fields, group members and TLV records, and Ragel machines after fields.
+/// The `Mangler` escapes the names which would break the generated code, so
+/// the findings are warnings: the code's names differ from the description's
+#[derive(Default)]
+struct ReservedFieldNameLinter {}
+
+impl MessageFieldLint for ReservedFieldNameLinter {
+    fn rule(&self) -> Rule {
+        rules::RESERVED_NAME
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let inner_names = match field.field_type {
+            representation::FieldType::Group(ref group) => group.fields.iter().map(|f| f.name.as_str()).collect(),
+            representation::FieldType::Tlv(ref tlv) => {
+                tlv.records.iter().map(|record| record.field.name.as_str()).collect()
+            }
+            _ => vec::Vec::new(),
+        };
+        let mut ret = LintResult::Ok;
+
+        for name in std::iter::once(field.name.as_str()).chain(inner_names) {
+            if let Some(language) = name_reserved_by(name) {
+                ret = LintResult::Warning(format!(
+                    "in message {0} field {1} uses name {2}, which is reserved by {3}{4}",
+                    message.name,
+                    field.name,
+                    name,
+                    language,
+                    match Mangler::escapes_member_name(name) {
+                        true => ", so the generated code escapes it",
+                        false => "",
+                    }
+                ));
+            }
+        }
+
+        ret
+    }
+}
+
+/// Makes sure that a TLV field's records are unambiguous, and may be stored
+/// in a tagged union
+#[derive(Default)]
+struct TlvFieldLinter {}
+
+impl MessageFieldLint for TlvFieldLinter {
+    fn rule(&self) -> Rule {
+        rules::TLV_FIELD
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let tlv = match field.field_type {
+            representation::FieldType::Tlv(ref tlv) => tlv,
+            _ => return LintResult::Ok,
+        };
+
+        if !(1..=4).contains(&tlv.tag_length) || !(1..=4).contains(&tlv.length_length) {
+            return LintResult::Error(format!(
+                "in message {0} TLV field {1} must have tag and length of 1..4 bytes",
+                message.name, field.name
+            ));
+        }
+
+        if tlv.records.is_empty() {
+            return LintResult::Warning(format!(
+                "in message {0} TLV field {1} does not define any records, every record will be skipped",
+                message.name, field.name
+            ));
+        }
+
+        for (i, record) in tlv.records.iter().enumerate() {
+            if record.tag >> (tlv.tag_length * 8) != 0 {
+                return LintResult::Error(format!(
+                    "in message {0} TLV field {1} record {2} tag {3} does not fit into {4} bytes",
+                    message.name, field.name, record.field.name, record.tag, tlv.tag_length
+                ));
+            }
+
+            if tlv.records[..i].iter().any(|other| other.tag == record.tag) {
+                return LintResult::Error(format!(
+                    "in message {0} TLV field {1} tag {2} is used more than once",
+                    message.name, field.name, record.tag
+                ));
+            }
+
+            match record.field.field_type {
+                representation::FieldType::Integer(ref integer)
+                    if [8usize, 16, 32, 64].contains(&integer.bit_width) => {}
+                representation::FieldType::Regex(_) if record.field.max_length().is_some() => {}
+                _ => {
+                    return LintResult::Error(format!(
+                        "in message {0} TLV field {1} record {2} must be either a byte-aligned integer, or a regex with MaxLength attribute",
+                        message.name, field.name, record.field.name
+                    ));
+                }
+            }
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Makes sure that a "scale" attribute is only applied to integer fields, and
+/// produces meaningful values
+#[derive(Default)]
+struct ScaleLinter {}
+
+impl MessageFieldLint for ScaleLinter {
+    fn rule(&self) -> Rule {
+        rules::SCALE
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let scale = match field.scale() {
+            Some(scale) => scale,
+            None => return LintResult::Ok,
+        };
+
+        if field.integer_value_type().is_none() {
+            return LintResult::Error(format!(
+                "in message {0} field {1} has Scale attribute, but is not an integer",
+                message.name, field.name
+            ));
+        }
+
+        if !scale.factor.is_finite() || !scale.offset.is_finite() {
+            return LintResult::Error(format!(
+                "in message {0} field {1} has non-finite Scale factor or offset",
+                message.name, field.name
+            ));
+        }
+
+        if scale.factor == 0.0 {
+            return LintResult::Warning(format!(
+                "in message {0} field {1} has Scale factor of 0, its physical value is constant",
+                message.name, field.name
+            ));
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Checks that "range" attributes are only applied to integer fields, and
+/// that the range is not empty
+#[derive(Default)]
+struct RangeLinter {}
+
+impl MessageFieldLint for RangeLinter {
+    fn rule(&self) -> Rule {
+        rules::RANGE
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let range = match field.range() {
+            Some(range) => range,
+            None => return LintResult::Ok,
+        };
+
+        let integer = match field.integer_value_type() {
+            Some(integer) => integer,
+            None => {
+                return LintResult::Error(format!(
+                    "in message {0} field {1} has Range attribute, but is not an integer",
+                    message.name, field.name
+                ))
+            }
+        };
+
+        // Reported by `IntegerBitWidthLinter`
+        if integer.bit_width == 0 || integer.bit_width > 64 {
+            return LintResult::Ok;
+        }
+
+        if range.min > range.max {
+            return LintResult::Error(format!(
+                "in message {0} field {1} has Range with min {2} greater than max {3}",
+                message.name, field.name, range.min, range.max
+            ));
+        }
+
+        if i128::from(range.max) < integer.min_value() || i128::from(range.min) > integer.max_value() {
+            return LintResult::Error(format!(
+                "in message {0} field {1} has Range [{2}, {3}] which does not fit into its width, no value would be accepted",
+                message.name, field.name, range.min, range.max
+            ));
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Reserved fields must occupy at least one byte
+#[derive(Default)]
+struct ReservedFieldLinter {}
+
+impl MessageFieldLint for ReservedFieldLinter {
+    fn rule(&self) -> Rule {
+        rules::RESERVED_FIELD
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        match field.field_type {
+            representation::FieldType::Reserved(ref reserved) if reserved.length == 0 => {
+                LintResult::Error(format!(
+                    "in message {0} reserved field {1} has zero length",
+                    message.name, field.name
+                ))
+            }
+            _ => LintResult::Ok,
+        }
+    }
+}
+
+/// Makes sure that a group is repeated at least once, and only consists of
+/// fields which a counted loop can store into a struct array
+#[derive(Default)]
+struct GroupFieldLinter {}
+
+impl MessageFieldLint for GroupFieldLinter {
+    fn rule(&self) -> Rule {
+        rules::GROUP_FIELD
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let group = match field.field_type {
+            representation::FieldType::Group(ref group) => group,
+            _ => return LintResult::Ok,
+        };
+
+        if message.layout() == representation::MessageLayout::Bits {
+            return LintResult::Error(format!(
+                "in message {0} group field {1} is not supported by the bit layout",
+                message.name, field.name
+            ));
+        }
+
+        // References to definitions are checked along with the definitions
+        if group.definition.is_some() && group.fields.is_empty() {
+            return LintResult::Ok;
+        }
+
+        if group.count == 0 || group.fields.is_empty() {
+            return LintResult::Error(format!(
+                "in message {0} group field {1} is empty",
+                message.name, field.name
+            ));
+        }
+
+        for member in &group.fields {
+            if member.range().is_some() || member.scale().is_some() || !member.checks().is_empty() {
+                return LintResult::Error(format!(
+                    "in message {0} group field {1} has member {2} with attributes which groups do not support",
+                    message.name, field.name, member.name
+                ));
+            }
+
+            match member.field_type {
+                representation::FieldType::Integer(ref integer)
+                    if (8..=64).contains(&integer.bit_width) && integer.bit_width % 8 == 0 => {}
+                representation::FieldType::Reserved(ref reserved)
+                    if reserved.length > 0 && !reserved.debug_member => {}
+                _ => {
+                    return LintResult::Error(format!(
+                        "in message {0} group field {1} has member {2} which is neither a byte-aligned integer, nor a reserved field",
+                        message.name, field.name, member.name
+                    ))
+                }
+            }
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Makes sure that a conditional field is selected by an earlier integer
+/// field, and that the alternatives sharing a region are distinguishable
+#[derive(Default)]
+struct ConditionalOnLinter {}
+
+impl MessageFieldLint for ConditionalOnLinter {
+    fn rule(&self) -> Rule {
+        rules::CONDITIONAL_ON
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let conditional_on = match field.conditional_on() {
+            Some(conditional_on) => conditional_on,
+            None => return LintResult::Ok,
+        };
+
+        if message.layout() != representation::MessageLayout::Bytes
+            || message.field_order() != representation::FieldOrder::Strict
+        {
+            return LintResult::Error(format!(
+                "in message {0} conditional field {1} requires byte layout and strict field order",
+                message.name, field.name
+            ));
+        }
+
+        if !matches!(
+            field.field_type,
+            representation::FieldType::Integer(_)
+                | representation::FieldType::Reserved(representation::ReservedFieldType {
+                    debug_member: false,
+                    ..
+                })
+        ) || field.range().is_some()
+            || field.scale().is_some()
+        {
+            return LintResult::Error(format!(
+                "in message {0} conditional field {1} must be a plain integer or a reserved field",
+                message.name, field.name
+            ));
+        }
+
+        let position = message
+            .fields
+            .iter()
+            .position(|f| std::ptr::eq(f, field))
+            .unwrap_or(0usize);
+        let selector = message.fields[..position]
+            .iter()
+            .find(|f| f.name == conditional_on.field && f.conditional_on().is_none());
+        let selector = match selector {
+            Some(selector) if selector.integer_value_type().is_some() => selector,
+            _ => {
+                return LintResult::Error(format!(
+                    "in message {0} field {1} is conditional on {2}, which is not an earlier unconditional integer field",
+                    message.name, field.name, conditional_on.field
+                ))
+            }
+        };
+
+        // Earlier alternatives of the same region
+        let is_duplicate = message.fields[..position]
+            .iter()
+            .rev()
+            .map_while(|f| f.conditional_on().filter(|c| c.field == conditional_on.field))
+            .any(|c| c.value == conditional_on.value);
+
+        if is_duplicate {
+            return LintResult::Error(format!(
+                "in message {0} field {1} shares the value {2} of {3} with another alternative",
+                message.name, field.name, conditional_on.value, conditional_on.field
+            ));
+        }
+
+        let value = i128::from(conditional_on.value);
+
+        if selector
+            .value_domain()
+            .is_some_and(|(min, max)| value < min || value > max)
+        {
+            return LintResult::Warning(format!(
+                "in message {0} field {1} is never present, as {2} can not take the value {3}",
+                message.name, field.name, conditional_on.field, conditional_on.value
+            ));
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// The smallest value of the domain no arm claims, if there is one
+fn first_unclaimed_value(domain: (i128, i128), claimed: &[i128]) -> Option<i128> {
+    let (min, max) = domain;
+
+    // Stops after at most `claimed.len() + 1` values
+    (min..=max).find(|value| !claimed.contains(value))
+}
+
+/// Makes sure that the alternatives conditional on a field claim each of its
+/// values, unless the field declares a default arm. Like non-exhaustive
+/// `match`es in Rust, gaps usually mean a forgotten alternative, and frames
+/// carrying the value would be rejected silently
+#[derive(Default)]
+struct SelectorExhaustivenessLinter {}
+
+impl MessageFieldLint for SelectorExhaustivenessLinter {
+    fn rule(&self) -> Rule {
+        rules::SELECTOR_EXHAUSTIVENESS
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let domain = match field.value_domain() {
+            Some(domain) if field.conditional_on().is_none() && !field.has_default_arm() => domain,
+            _ => return LintResult::Ok,
+        };
+
+        for region in message.field_regions() {
+            if region[0].conditional_on().map(|c| &c.field) != Some(&field.name) {
+                continue;
+            }
+
+            let claimed = region
+                .iter()
+                .filter_map(representation::Field::conditional_on)
+                .map(|conditional_on| i128::from(conditional_on.value))
+                .collect::<vec::Vec<i128>>();
+
+            if let Some(value) = first_unclaimed_value(domain, &claimed) {
+                return LintResult::Warning(format!(
+                    "in message {0} no alternative starting at field {1} claims the value {2} of {3}, consider adding the `DefaultArm` attribute to {3}",
+                    message.name, region[0].name, value, field.name
+                ));
+            }
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Cross-checks a message's fixed length against the lengths its fields may
+/// add up to
+#[derive(Default)]
+struct FixedLengthLinter {}
+
+impl MessageFieldLint for FixedLengthLinter {
+    fn rule(&self) -> Rule {
+        rules::FIXED_LENGTH
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let fixed_length = match message.fixed_length() {
+            Some(fixed_length) if std::ptr::eq(field, &message.fields[0]) => fixed_length,
+            _ => return LintResult::Ok,
+        };
+        let min_length = message.min_encoded_length();
+        let max_length = message.max_encoded_length();
+
+        if fixed_length < min_length || fixed_length > max_length {
+            return LintResult::Error(format!(
+                "message {0} has FixedLength of {1} bytes, but its fields add up to {2}..{3} bytes",
+                message.name, fixed_length, min_length, max_length
+            ));
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Checks that a message having an ID starts with a field the dispatcher can
+/// read the ID from
+#[derive(Default)]
+struct IdLinter {}
+
+impl MessageFieldLint for IdLinter {
+    fn rule(&self) -> Rule {
+        rules::ID
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let id = match message.id() {
+            Some(id) if std::ptr::eq(field, &message.fields[0]) => id,
+            _ => return LintResult::Ok,
+        };
+
+        if message.layout() != representation::MessageLayout::Bytes
+            || message.field_order() != representation::FieldOrder::Strict
+        {
+            return LintResult::Error(format!(
+                "message {0} has an ID, which is only supported for byte-granular messages with strict field order",
+                message.name
+            ));
+        }
+
+        let integer = match field.field_type {
+            representation::FieldType::Integer(ref integer)
+                if !integer.signed
+                    && integer.bit_width % 8 == 0
+                    && integer.bit_width <= 64
+                    && field.conditional_on().is_none() =>
+            {
+                integer
+            }
+            _ => {
+                return LintResult::Error(format!(
+                    "message {0} has an ID, but its first field {1} is not an unconditional unsigned byte-aligned integer of at most 64 bits",
+                    message.name, field.name
+                ));
+            }
+        };
+
+        if id as i128 > integer.max_value() {
+            return LintResult::Error(format!(
+                "ID {0} of message {1} does not fit into field {2}",
+                id, message.name, field.name
+            ));
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Checks that a message having a sync sequence can be scanned for it
+#[derive(Default)]
+struct SyncSequenceLinter {}
+
+impl MessageFieldLint for SyncSequenceLinter {
+    fn rule(&self) -> Rule {
+        rules::SYNC_SEQUENCE
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let sync_sequence = match message.sync_sequence() {
+            Some(sync_sequence) if std::ptr::eq(field, &message.fields[0]) => sync_sequence,
+            _ => return LintResult::Ok,
+        };
+
+        if sync_sequence.is_empty() {
+            return LintResult::Error(format!("message {0} has an empty sync sequence", message.name));
+        }
+
+        if message.layout() != representation::MessageLayout::Bytes {
+            return LintResult::Error(format!(
+                "message {0} has a sync sequence, which is only supported for byte-granular messages",
+                message.name
+            ));
+        }
+
+        // Skipped input counts towards the frame length
+        if message.fixed_length().is_some() {
+            return LintResult::Error(format!(
+                "message {0} has both a sync sequence and a fixed length, which are mutually exclusive",
+                message.name
+            ));
+        }
+
+        // The dispatcher expects the ID to be the first bytes of a frame
+        if message.id().is_some() {
+            return LintResult::Error(format!(
+                "message {0} has both a sync sequence and an ID, which are mutually exclusive",
+                message.name
+            ));
+        }
+
+        LintResult::Ok
+    }
+}
+
+/// Checks that the versions a field appears in form a non-empty range
+#[derive(Default)]
+struct VersionLinter {}
+
+impl MessageFieldLint for VersionLinter {
+    fn rule(&self) -> Rule {
+        rules::VERSION
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let mut since = None;
+        let mut until = None;
+
+        for attribute in &field.attributes {
+            match attribute {
+                representation::FieldAttribute::SinceVersion(version) => since = Some(*version),
+                representation::FieldAttribute::UntilVersion(version) => until = Some(*version),
+                _ => {}
+            }
+        }
+
+        match (since, until) {
+            (Some(since), Some(until)) if since > until => LintResult::Error(format!(
+                "field {0} of message {1} appears since version {2}, but only until version {3}",
+                field.name, message.name, since, until
+            )),
+            _ => LintResult::Ok,
+        }
+    }
+}
+
+/// Checks that a checksum field can hold the checksum, and that the parser
+/// can compute it
+#[derive(Default)]
+struct ChecksumLinter {}
+
+impl MessageFieldLint for ChecksumLinter {
+    fn rule(&self) -> Rule {
+        rules::CHECKSUM
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let checksum = match field.checksum() {
+            Some(checksum) => checksum,
+            None => return LintResult::Ok,
+        };
+
+        if message.layout() != representation::MessageLayout::Bytes
+            || message.field_order() != representation::FieldOrder::Strict
+        {
+            return LintResult::Error(format!(
+                "field {0} of message {1} is a checksum, which is only supported for byte-granular messages with strict field order",
+                field.name, message.name
+            ));
+        }
+
+        match field.field_type {
+            representation::FieldType::Integer(ref integer)
+                if !integer.signed
+                    && integer.bit_width == checksum.algorithm.width
+                    && field.conditional_on().is_none() =>
+            {
+                LintResult::Ok
+            }
+            _ => LintResult::Error(format!(
+                "checksum field {0} of message {1} is not an unconditional unsigned integer of {2} bits",
+                field.name, message.name, checksum.algorithm.width
+            )),
+        }
+    }
+}
+
+/// Checks that a length field refers to a range of fields the parser can
+/// count the bytes of, and is wide enough to hold the count
+#[derive(Default)]
+struct LengthOfLinter {}
+
+impl MessageFieldLint for LengthOfLinter {
+    fn rule(&self) -> Rule {
+        rules::LENGTH_OF
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+    ) -> LintResult {
+        let length_of = match field.length_of() {
+            Some(length_of) => length_of,
+            None => return LintResult::Ok,
+        };
+
+        if message.layout() != representation::MessageLayout::Bytes
+            || message.field_order() != representation::FieldOrder::Strict
+        {
+            return LintResult::Error(format!(
+                "field {0} of message {1} is a length, which is only supported for byte-granular messages with strict field order",
+                field.name, message.name
+            ));
+        }
+
+        let integer = match field.field_type {
+            representation::FieldType::Integer(ref integer)
+                if !integer.signed && field.conditional_on().is_none() && field.checksum().is_none() =>
+            {
+                integer
+            }
+            _ => {
+                return LintResult::Error(format!(
+                    "length field {0} of message {1} is not an unconditional unsigned integer",
+                    field.name, message.name
+                ))
+            }
+        };
+
+        if let Some(ref length) = length_of.length {
+            if length.fields() != [field.name.as_str()] {
+                return LintResult::Error(format!(
+                    "length expression of field {0} of message {1} must refer to the field, and only to it",
+                    field.name, message.name
+                ));
+            }
+        }
+
+        let position = |name: &str| {
+            message
+                .fields
+                .iter()
+                .position(|f| f.name == name && f.conditional_on().is_none())
+        };
+        let (first, last) = match (position(&length_of.first), position(&length_of.last)) {
+            (Some(first), Some(last)) if first <= last => (first, last),
+            _ => {
+                return LintResult::Error(format!(
+                    "length field {0} of message {1} covers fields {2} through {3}, which are not unconditional fields in this order",
+                    field.name, message.name, length_of.first, length_of.last
+                ))
+            }
+        };
+
+        // Alternatives share their bytes
+        let mut max_length = 0usize;
+        let mut index = 0usize;
+
+        for region in message.field_regions() {
+            if (first..=last).contains(&index) {
+                max_length += region
+                    .iter()
+                    .map(representation::Field::max_encoded_length)
+                    .max()
+                    .unwrap_or(0usize);
+            }
+
+            index += region.len();
+        }
+
+        // The length expression is presumed to grow with the value
+        let max_value = match length_of.length {
+            Some(ref length) => i128::from(
+                length
+                    .evaluate(&|_| Some(integer.max_value().min(i128::from(i64::MAX)) as i64))
+                    .unwrap_or(0i64),
+            ),
+            None => integer.max_value(),
+        };
+
+        if max_length as i128 > max_value {
+            return LintResult::Warning(format!(
+                "length field {0} of message {1} can not hold the length of up to {2} bytes of fields {3} through {4}",
+                field.name, message.name, max_length, length_of.first, length_of.last
+            ));
+        }
+
+        LintResult::Ok
+    }
+}
+
+struct CompositeMessageLinter {
+    pending_linters: vec::Vec<boxed::Box<dyn MessageFieldLint>>,
+}
+
+impl CompositeMessageLinter {
+    pub fn new(known_rules: vec::Vec<Rule>) -> Self {
+        let mut instance = CompositeMessageLinter {
+            pending_linters: vec::Vec::default(),
+        };
+        instance
+            .pending_linters
+            .push(boxed::Box::new(MockLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(FieldNameLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(ReservedFieldNameLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(RegexFieldMaxLengthLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(RegexSyntaxLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(OverflowLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(UnorderedFieldCountLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(RegexOverlapLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(IntegerBitWidthLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(BitLayoutLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(VarintLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(FlagsLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(TimestampLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(TlvFieldLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(ScaleLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(UnitLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(CheckLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(RangeLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(ReservedFieldLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(GroupFieldLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(ConditionalOnLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(DeprecatedFieldLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(SelectorExhaustivenessLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(FixedLengthLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(IdLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(VersionLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(ChecksumLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(LengthOfLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(SyncSequenceLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(SuppressLintLinter { known_rules }));
+
+        instance
+    }
+
+    /// Runs the built-in linters, followed by the `registered_linters`
+    pub fn lint_message(
+        &mut self,
+        message: &representation::Message,
+        registered_linters: &mut [boxed::Box<dyn MessageFieldLint>],
+        protocol_lint_result: &mut ProtocolLintResult,
+    ) {
+        for field in &message.fields {
+            self.lint_field(message, field, registered_linters, protocol_lint_result);
+        }
+    }
+
+    fn lint_field(
+        &mut self,
+        message: &representation::Message,
+        field: &representation::Field,
+        registered_linters: &mut [boxed::Box<dyn MessageFieldLint>],
+        protocol_lint_result: &mut ProtocolLintResult,
+    ) {
+        for linter in self.pending_linters.iter_mut().chain(registered_linters.iter_mut()) {
+            let lint_result = linter.lint_field(message, field);
+            protocol_lint_result.record(linter.rule(), Some(&message.name), Some(&field.name), lint_result);
+        }
+    }
+}
+
+/// Bytes every frame of a byte-granular message starts with, after symbol
+/// decoding: the sync sequence, the ID, and the literal fields preceding the
+/// first field which takes arbitrary values
+fn start_pattern(message: &representation::Message) -> vec::Vec<u8> {
+    let mut ret = message.sync_sequence().unwrap_or_default().to_vec();
+
+    for (field_index, field) in message.fields.iter().enumerate() {
+        if field.conditional_on().is_some() {
+            break;
+        }
+
+        match (&field.field_type, message.id()) {
+            (representation::FieldType::Regex(ref regex), _) => match regex.pattern().literal() {
+                Some(bytes) => ret.extend(bytes),
+                None => break,
+            },
+            // IDs are big-endian, like the rest of the integers
+            (representation::FieldType::Integer(ref integer), Some(id)) if field_index == 0 => {
+                let length = integer.bit_width.div_ceil(8);
+                ret.extend((0..length).rev().map(|i| (id >> (i * 8)) as u8));
+            }
+            _ => break,
+        }
+    }
+
+    ret
+}
+
+/// A linter implementing `ProtocolLint` checks the protocol as a whole.
+///
+/// - The linter MAY be stateful;
+/// - The validation framework calls each linter once per protocol, before
+///   linting the messages' fields;
+/// - Linters MUST BE functionally independent from each other;
+/// - Unlike `MessageFieldLint`, the linter MAY relate messages to each other,
+///   and MAY report any number of findings, each one with its location.
+///
+/// Library users MAY implement the trait, and register their linters with a
+/// `LinterRegistry`.
+pub trait ProtocolLint {
+    /// Identifies the linter's findings
+    fn rule(&self) -> Rule;
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult);
+}
+
+/// Makes sure that message names are unique, as they name the generated
+/// types and functions
+#[derive(Default)]
+struct MessageNameLinter {}
+
+impl ProtocolLint for MessageNameLinter {
+    fn rule(&self) -> Rule {
+        rules::MESSAGE_NAME
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        for (i, message) in protocol.messages.iter().enumerate() {
+            if protocol.messages[..i].iter().any(|other| other.name == message.name) {
+                protocol_lint_result.record(
+                    self.rule(),
+                    Some(&message.name),
+                    None,
+                    LintResult::Error(format!("message {0} is defined more than once", message.name)),
+                );
+            }
+        }
+    }
+}
+
+/// Makes sure the protocol has a root message: it defines at least one
+/// message, and at most one is marked as the root one
+#[derive(Default)]
+struct RootMessageLinter {}
+
+impl ProtocolLint for RootMessageLinter {
+    fn rule(&self) -> Rule {
+        rules::ROOT_MESSAGE
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        if protocol.messages.is_empty() {
+            protocol_lint_result.record(
+                self.rule(),
+                None,
+                None,
+                LintResult::Error("the protocol defines no messages".to_string()),
+            );
+        }
+
+        let roots = protocol
+            .messages
+            .iter()
+            .filter(|message| {
+                message
+                    .attributes
+                    .iter()
+                    .any(|attribute| matches!(attribute, representation::MessageAttribute::Root))
+            })
+            .collect::<vec::Vec<_>>();
+
+        for message in roots.iter().skip(1) {
+            protocol_lint_result.record(
+                self.rule(),
+                Some(&message.name),
+                None,
+                LintResult::Error(format!(
+                    "messages {0} and {1} are both marked as root",
+                    roots[0].name, message.name
+                )),
+            );
+        }
+    }
+}
+
+/// Looks for messages named after keywords of the target languages. Messages
+/// name Ragel machines and union members, the rest of the generated
+/// identifiers are prefixed, or suffixed. As with fields, the `Mangler`
+/// escapes the names which would break the generated code
+#[derive(Default)]
+struct ReservedMessageNameLinter {}
+
+impl ProtocolLint for ReservedMessageNameLinter {
+    fn rule(&self) -> Rule {
+        rules::RESERVED_NAME
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        for message in &protocol.messages {
+            if let Some(language) = name_reserved_by(&message.name) {
+                protocol_lint_result.record(
+                    self.rule(),
+                    Some(&message.name),
+                    None,
+                    LintResult::Warning(format!(
+                        "message {0} uses a name reserved by {1}{2}",
+                        message.name,
+                        language,
+                        match Mangler::escapes_message_name(&message.name) {
+                            true => ", so the generated code escapes it",
+                            false => "",
+                        }
+                    )),
+                );
+            }
+        }
+    }
+}
+
+/// Checks the frame sizes of the messages against the protocol's budget. A
+/// message whose frames never fit is an error
+#[derive(Default)]
+struct FrameSizeLinter {}
+
+impl ProtocolLint for FrameSizeLinter {
+    fn rule(&self) -> Rule {
+        rules::FRAME_SIZE
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        let budget = match protocol.frame_size_budget() {
+            Some(budget) => budget,
+            None => return,
+        };
+
+        for message in &protocol.messages {
+            let frame_size = protocol.frame_size(message);
+
+            if frame_size.max <= budget {
+                continue;
+            }
+
+            let text = format!(
+                "frames of message {0} take {1} to {2} bytes on the wire, exceeding the budget of {3} bytes",
+                message.name, frame_size.min, frame_size.max, budget
+            );
+            let lint_result = match frame_size.min > budget {
+                true => LintResult::Error(text),
+                false => LintResult::Warning(text),
+            };
+            protocol_lint_result.record(self.rule(), Some(&message.name), None, lint_result);
+        }
+    }
+}
+
+/// Adds up the static buffers of the message structs, which the "max length"
+/// attributes size, and checks them against the RAM budget of the target
+#[derive(Default)]
+struct BufferBudgetLinter {}
+
+impl ProtocolLint for BufferBudgetLinter {
+    fn rule(&self) -> Rule {
+        rules::BUFFER_BUDGET
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        let target = match protocol.target() {
+            Some(target) => target,
+            None => return,
+        };
+        let protocol = protocol.resolve_group_definitions();
+        let total: usize = protocol.messages.iter().map(|message| protocol.buffer_length(message)).sum();
+
+        if total <= target.ram_budget {
+            return;
+        }
+
+        // Points at the field to shrink first
+        let largest = protocol
+            .messages
+            .iter()
+            .flat_map(|message| message.fields.iter().map(move |field| (message, field)))
+            .max_by_key(|(message, field)| protocol.field_buffer_length(message, field));
+
+        if let Some((message, field)) = largest {
+            protocol_lint_result.record(
+                self.rule(),
+                Some(&message.name),
+                Some(&field.name),
+                LintResult::Warning(format!(
+                    "buffers of the message structs take {0} bytes, exceeding the {1} bytes of RAM budgeted on {2}; the largest one, of field {3} of message {4}, takes {5} bytes",
+                    total,
+                    target.ram_budget,
+                    target.name,
+                    field.name,
+                    message.name,
+                    protocol.field_buffer_length(message, field)
+                )),
+            );
+        }
+    }
+}
+
+/// Makes sure the frame trace can tell every message apart
+#[derive(Default)]
+struct FrameTraceLinter {}
+
+impl ProtocolLint for FrameTraceLinter {
+    fn rule(&self) -> Rule {
+        rules::FRAME_TRACE
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        let frame_trace = match protocol.frame_trace() {
+            Some(frame_trace) => frame_trace,
+            None => return,
+        };
+
+        if frame_trace.depth == 0 {
+            protocol_lint_result.record(
+                self.rule(),
+                None,
+                None,
+                LintResult::Error("frame trace has zero depth".to_string()),
+            );
+        }
+
+        // Trace records identify messages with a byte
+        if protocol.messages.len() > 256 {
+            protocol_lint_result.record(
+                self.rule(),
+                None,
+                None,
+                LintResult::Error("frame trace can not tell more than 256 messages apart".to_string()),
+            );
+        }
+    }
+}
+
+#[derive(Default)]
+struct BenchmarkLinter {}
+
+impl ProtocolLint for BenchmarkLinter {
+    fn rule(&self) -> Rule {
+        rules::BENCHMARK
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        if protocol.benchmark().is_some_and(|benchmark| benchmark.repetitions == 0) {
+            protocol_lint_result.record(
+                self.rule(),
+                None,
+                None,
+                LintResult::Error("benchmark has zero repetitions".to_string()),
+            );
+        }
+    }
+}
+
+/// Makes sure every message belongs to exactly one output group
+#[derive(Default)]
+struct OutputSplittingLinter {}
+
+impl ProtocolLint for OutputSplittingLinter {
+    fn rule(&self) -> Rule {
+        rules::OUTPUT_SPLITTING
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        let groups = match protocol.output_splitting() {
+            Some(representation::OutputSplitting::Groups(groups)) => groups,
+            _ => return,
+        };
+
+        for message in &protocol.messages {
+            let group_count = groups
+                .iter()
+                .filter(|group| group.messages.contains(&message.name))
+                .count();
+
+            if group_count != 1 {
+                protocol_lint_result.record(
+                    self.rule(),
+                    Some(&message.name),
+                    None,
+                    LintResult::Error(format!(
+                        "message {0} belongs to {1} output groups, instead of exactly one",
+                        message.name, group_count
+                    )),
+                );
+            }
+        }
+
+        for group in groups {
+            for message_name in &group.messages {
+                if protocol.message(message_name).is_none() {
+                    protocol_lint_result.record(
+                        self.rule(),
+                        None,
+                        None,
+                        LintResult::Error(format!(
+                            "output group {0} lists unknown message {1}",
+                            group.name, message_name
+                        )),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct ByteStuffingLinter {}
+
+impl ProtocolLint for ByteStuffingLinter {
+    fn rule(&self) -> Rule {
+        rules::BYTE_STUFFING
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        if protocol.byte_stuffing().is_some()
+            && !protocol
+                .symbol_decoding()
+                .map(representation::SymbolDecoding::is_bytewise)
+                .unwrap_or(true)
+        {
+            protocol_lint_result.record(
+                self.rule(),
+                None,
+                None,
+                LintResult::Error("byte stuffing requires bytewise symbol decoding".to_string()),
+            );
+        }
+    }
+}
+
+#[derive(Default)]
+struct ChecksumImplementationLinter {}
+
+impl ProtocolLint for ChecksumImplementationLinter {
+    fn rule(&self) -> Rule {
+        rules::CHECKSUM_IMPLEMENTATION
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        if protocol.uses_runtime()
+            && protocol.checksum_implementation() == representation::ChecksumImplementation::Extern
+        {
+            protocol_lint_result.record(
+                self.rule(),
+                None,
+                None,
+                LintResult::Warning(
+                    "checksum routines come from the runtime library, so the extern implementation is ignored"
+                        .to_string(),
+                ),
+            );
+        }
+    }
+}
+
+/// Checks that each checksum covers one range of fields, which ends before
+/// the checksum field, as the parser compares the checksum once the field is
+/// parsed. Ranges of different checksums should nest
+#[derive(Default)]
+struct ChecksumCoverageLinter {}
+
+impl ChecksumCoverageLinter {
+    /// Positions of the fields of a message carrying the checksum's markers,
+    /// and of its checksum fields
+    fn positions(
+        message: &representation::Message,
+        name: &str,
+    ) -> (vec::Vec<usize>, vec::Vec<usize>, vec::Vec<usize>) {
+        let mut starts = vec::Vec::new();
+        let mut stops = vec::Vec::new();
+        let mut checksums = vec::Vec::new();
+
+        for (index, field) in message.fields.iter().enumerate() {
+            for attribute in &field.attributes {
+                match attribute {
+                    representation::FieldAttribute::StartChecksum(other) if other == name => starts.push(index),
+                    representation::FieldAttribute::StopChecksum(other) if other == name => stops.push(index),
+                    _ => {}
+                }
+            }
+
+            if field.checksum().is_some_and(|checksum| checksum.name == name) {
+                checksums.push(index);
+            }
+        }
+
+        (starts, stops, checksums)
+    }
+
+    fn lint_algorithm(
+        &self,
+        message: &representation::Message,
+        field: &representation::Field,
+        algorithm: &representation::CrcAlgorithm,
+        protocol_lint_result: &mut ProtocolLintResult,
+    ) {
+        if !(8usize..=64).contains(&algorithm.width) {
+            protocol_lint_result.record(
+                self.rule(),
+                Some(&message.name),
+                Some(&field.name),
+                LintResult::Error(format!(
+                    "checksum field {0} of message {1} employs a CRC of {2} bits, while generators implement 8 to 64 bits",
+                    field.name, message.name, algorithm.width
+                )),
+            );
+
+            return;
+        }
+
+        let mask = u64::MAX >> (64 - algorithm.width);
+
+        for (parameter, value) in [
+            ("polynomial", algorithm.polynomial),
+            ("initial value", algorithm.init),
+            ("final XOR", algorithm.xor_out),
+        ] {
+            if value & !mask != 0 {
+                protocol_lint_result.record(
+                    self.rule(),
+                    Some(&message.name),
+                    Some(&field.name),
+                    LintResult::Error(format!(
+                        "CRC {0} 0x{1:X} of checksum field {2} of message {3} does not fit into {4} bits",
+                        parameter, value, field.name, message.name, algorithm.width
+                    )),
+                );
+            }
+        }
+    }
+}
+
+impl ProtocolLint for ChecksumCoverageLinter {
+    fn rule(&self) -> Rule {
+        rules::CHECKSUM_COVERAGE
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        for message in &protocol.messages {
+            let mut names = vec::Vec::<&str>::new();
+
+            for field in &message.fields {
+                for attribute in &field.attributes {
+                    let name = match attribute {
+                        representation::FieldAttribute::StartChecksum(name)
+                        | representation::FieldAttribute::StopChecksum(name) => name.as_str(),
+                        representation::FieldAttribute::Checksum(checksum) => checksum.name.as_str(),
+                        _ => continue,
+                    };
+
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+            }
+
+            // Covered ranges of the checksums, by name, for checking the nesting
+            let mut ranges = vec::Vec::<(&str, usize, usize)>::new();
+
+            for name in names {
+                let (starts, stops, checksums) = ChecksumCoverageLinter::positions(message, name);
+                let range = match (starts.as_slice(), stops.as_slice()) {
+                    ([start], [stop]) if start <= stop => Some((*start, *stop)),
+                    _ => {
+                        protocol_lint_result.record(
+                            self.rule(),
+                            Some(&message.name),
+                            None,
+                            LintResult::Error(format!(
+                                "checksum {0} of message {1} is started {2} times and stopped {3} times, rather than once each, in this order",
+                                name,
+                                message.name,
+                                starts.len(),
+                                stops.len()
+                            )),
+                        );
+
+                        None
+                    }
+                };
+
+                let checksum_index = match checksums.as_slice() {
+                    [checksum_index] => *checksum_index,
+                    [] => {
+                        protocol_lint_result.record(
+                            self.rule(),
+                            Some(&message.name),
+                            None,
+                            LintResult::Warning(format!(
+                                "checksum {0} of message {1} covers fields, but no field holds it",
+                                name, message.name
+                            )),
+                        );
+
+                        continue;
+                    }
+                    _ => {
+                        protocol_lint_result.record(
+                            self.rule(),
+                            Some(&message.name),
+                            None,
+                            LintResult::Error(format!(
+                                "checksum {0} of message {1} is held by {2} fields, rather than one",
+                                name,
+                                message.name,
+                                checksums.len()
+                            )),
+                        );
+
+                        continue;
+                    }
+                };
+                let field = &message.fields[checksum_index];
+                self.lint_algorithm(message, field, &field.checksum().unwrap().algorithm, protocol_lint_result);
+
+                let (start, stop) = match range {
+                    Some(range) => range,
+                    None => continue,
+                };
+
+                if checksum_index <= stop {
+                    protocol_lint_result.record(
+                        self.rule(),
+                        Some(&message.name),
+                        Some(&field.name),
+                        LintResult::Error(format!(
+                            "checksum field {0} of message {1} does not follow the fields {2} through {3} it covers",
+                            field.name, message.name, message.fields[start].name, message.fields[stop].name
+                        )),
+                    );
+                }
+
+                ranges.push((name, start, stop));
+            }
+
+            for (i, (name, start, stop)) in ranges.iter().enumerate() {
+                for (other_name, other_start, other_stop) in ranges.iter().skip(i + 1) {
+                    let overlaps = start <= other_stop && other_start <= stop;
+                    let nests = (start <= other_start && other_stop <= stop)
+                        || (other_start <= start && stop <= other_stop);
+
+                    if overlaps && !nests {
+                        protocol_lint_result.record(
+                            self.rule(),
+                            Some(&message.name),
+                            None,
+                            LintResult::Warning(format!(
+                                "ranges of checksums {0} and {1} of message {2} overlap, but do not nest",
+                                name, other_name, message.name
+                            )),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Generators do not tell versions apart, so the description must be narrowed
+/// to one version first
+#[derive(Default)]
+struct RevisionLinter {}
+
+impl ProtocolLint for RevisionLinter {
+    fn rule(&self) -> Rule {
+        rules::REVISION
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        for message in &protocol.messages {
+            let group_members = message.fields.iter().flat_map(|field| match field.field_type {
+                representation::FieldType::Group(ref group) => group.fields.iter(),
+                _ => [].iter(),
+            });
+
+            for field in message.fields.iter().chain(group_members) {
+                let is_present = match protocol.version() {
+                    Some(version) => field.is_present_in(version),
+                    None => !field.is_versioned(),
+                };
+
+                if !is_present {
+                    protocol_lint_result.record(
+                        self.rule(),
+                        Some(&message.name),
+                        Some(&field.name),
+                        LintResult::Error(format!(
+                            "field {0} of message {1} is absent in some protocol versions, the protocol must be narrowed to its version with `Protocol::revision()`",
+                            field.name, message.name
+                        )),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Makes sure the dispatcher can tell the dispatched messages apart by their
+/// IDs
+#[derive(Default)]
+struct DispatchLinter {}
+
+impl ProtocolLint for DispatchLinter {
+    fn rule(&self) -> Rule {
+        rules::DISPATCH
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        // The ID field linter is run on fields, so it never sees a message without any
+        for message in protocol.dispatched_messages() {
+            if message.fields.is_empty() {
+                protocol_lint_result.record(
+                    rules::ID,
+                    Some(&message.name),
+                    None,
+                    LintResult::Error(format!("message {0} has an ID but no fields", message.name)),
+                );
+            }
+        }
+
+        // Messages without an ID field are reported above
+        let dispatched_messages = protocol
+            .dispatched_messages()
+            .into_iter()
+            .filter_map(|message| Some((message, message.fields.first()?)))
+            .collect::<vec::Vec<_>>();
+        let (first, first_id_field) = match dispatched_messages.first() {
+            Some(first) => *first,
+            None => return,
+        };
+
+        // The dispatcher reads the same number of bytes before it knows which message it parses
+        for (message, id_field) in &dispatched_messages {
+            if id_field.bit_width() != first_id_field.bit_width() {
+                protocol_lint_result.record(
+                    self.rule(),
+                    Some(&message.name),
+                    None,
+                    LintResult::Error(format!(
+                        "ID fields of messages {0} and {1} differ in width",
+                        first.name, message.name
+                    )),
+                );
+            }
+        }
+
+        for (i, (message, _)) in dispatched_messages.iter().enumerate() {
+            if let Some((other, _)) = dispatched_messages[..i].iter().find(|(other, _)| other.id() == message.id()) {
+                protocol_lint_result.record(
+                    self.rule(),
+                    Some(&message.name),
+                    None,
+                    LintResult::Error(format!(
+                        "messages {0} and {1} share the same ID",
+                        other.name, message.name
+                    )),
+                );
+            }
+        }
+
+        let claimed = dispatched_messages
+            .iter()
+            .filter_map(|(message, _)| message.id())
+            .map(i128::from)
+            .collect::<vec::Vec<i128>>();
+
+        if let Some(domain) = first_id_field.value_domain() {
+            for (message, id_field) in &dispatched_messages {
+                if message.id().is_some_and(|id| i128::from(id) < domain.0 || i128::from(id) > domain.1) {
+                    protocol_lint_result.record(
+                        self.rule(),
+                        Some(&message.name),
+                        None,
+                        LintResult::Warning(format!(
+                            "message {0} is never dispatched, as its ID is out of the range of field {1}",
+                            message.name, id_field.name
+                        )),
+                    );
+                }
+            }
+
+            if let Some(id) = first_unclaimed_value(domain, &claimed)
+                .filter(|_| !protocol.has_dispatch_default_arm())
+            {
+                protocol_lint_result.record(
+                    self.rule(),
+                    None,
+                    None,
+                    LintResult::Warning(format!(
+                        "no message claims ID {0}, consider adding the `DispatchDefaultArm` attribute to the protocol",
+                        id
+                    )),
+                );
+            }
+        }
+
+        // The dispatcher tells messages apart with a byte, reserving two values
+        if dispatched_messages.len() > 254 {
+            protocol_lint_result.record(
+                self.rule(),
+                None,
+                None,
+                LintResult::Error("dispatcher can not tell more than 254 messages apart".to_string()),
+            );
+        }
+
+        if !protocol
+            .symbol_decoding()
+            .map(representation::SymbolDecoding::is_bytewise)
+            .unwrap_or(true)
+        {
+            protocol_lint_result.record(
+                self.rule(),
+                None,
+                None,
+                LintResult::Error("message dispatch requires bytewise symbol decoding".to_string()),
+            );
+        }
+    }
+}
+
+/// Looks for byte-granular messages whose frames may start with the same
+/// bytes, so a stream carrying both is parsed ambiguously. Messages without
+/// a constant start can not be told apart by it, and are skipped. Dispatched
+/// messages are told apart by their IDs, which are checked separately
+#[derive(Default)]
+struct StartPatternLinter {}
+
+impl ProtocolLint for StartPatternLinter {
+    fn rule(&self) -> Rule {
+        rules::START_PATTERN
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        let patterns = protocol
+            .messages
+            .iter()
+            .filter(|message| message.layout() == representation::MessageLayout::Bytes)
+            .map(|message| (message, start_pattern(message)))
+            .filter(|(_, pattern)| !pattern.is_empty())
+            .collect::<vec::Vec<_>>();
+
+        for (i, (message, pattern)) in patterns.iter().enumerate() {
+            for (other, other_pattern) in &patterns[..i] {
+                if message.id().is_some() && other.id().is_some() {
+                    continue;
+                }
+
+                let common_length = pattern.len().min(other_pattern.len());
+
+                if pattern[..common_length] != other_pattern[..common_length] {
+                    continue;
+                }
+
+                protocol_lint_result.record(
+                    self.rule(),
+                    Some(&message.name),
+                    None,
+                    LintResult::Warning(format!(
+                        "messages {0} and {1} may both start with bytes {2}, so a stream carrying both is parsed ambiguously",
+                        other.name,
+                        message.name,
+                        pattern[..common_length]
+                            .iter()
+                            .map(|byte| format!("0x{0:02X}", byte))
+                            .collect::<vec::Vec<string::String>>()
+                            .join(" ")
+                    )),
+                );
+            }
+        }
+    }
+}
+
+/// Makes sure extensions are resolved, which only fails for unknown bases and
+/// cycles
+#[derive(Default)]
+struct MessageExtensionLinter {}
+
+impl ProtocolLint for MessageExtensionLinter {
+    fn rule(&self) -> Rule {
+        rules::MESSAGE_EXTENSION
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        for message in &protocol.messages {
+            let base_name = match message.extends() {
+                Some(base_name) => base_name,
+                None => continue,
+            };
+            let mut visited = vec![message.name.as_str()];
+            let mut current = message;
+
+            let finding = loop {
+                let base_name = match current.extends() {
+                    Some(base_name) => base_name,
+                    None => {
+                        break format!(
+                            "message {0} extends {1}, the protocol must be resolved with `Protocol::resolve_message_extensions()`",
+                            message.name, base_name
+                        )
+                    }
+                };
+
+                if visited.contains(&base_name) {
+                    break format!(
+                        "bases of message {0} run in a cycle: {1} -> {2}",
+                        message.name,
+                        visited.join(" -> "),
+                        base_name
+                    );
+                }
+
+                current = match protocol.message(base_name) {
+                    Some(base) => base,
+                    None => break format!("message {0} extends unknown message {1}", message.name, base_name),
+                };
+                visited.push(base_name);
+            };
+
+            protocol_lint_result.record(
+                self.rule(),
+                Some(&message.name),
+                None,
+                LintResult::Error(finding),
+            );
+        }
+    }
+}
+
+/// Makes sure group definitions are unique, and that group fields made from
+/// a definition share its layout, as they share a type
+#[derive(Default)]
+struct GroupDefinitionLinter {}
+
+impl ProtocolLint for GroupDefinitionLinter {
+    fn rule(&self) -> Rule {
+        rules::GROUP_DEFINITION
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        let definitions = protocol.group_definitions();
+
+        for (i, definition) in definitions.iter().enumerate() {
+            if definitions[..i].iter().any(|other| other.name == definition.name) {
+                protocol_lint_result.record(
+                    self.rule(),
+                    None,
+                    None,
+                    LintResult::Error(format!(
+                        "group definition {0} is defined more than once",
+                        definition.name
+                    )),
+                );
+            }
+        }
+
+        for message in &protocol.messages {
+            for field in &message.fields {
+                let (group, definition_name) = match field.field_type {
+                    representation::FieldType::Group(ref group) => match group.definition {
+                        Some(ref definition_name) => (group, definition_name),
+                        None => continue,
+                    },
+                    _ => continue,
+                };
+
+                let definition = match protocol.group_definition(definition_name) {
+                    Some(definition) => definition,
+                    None => {
+                        protocol_lint_result.record(
+                            self.rule(),
+                            Some(&message.name),
+                            Some(&field.name),
+                            LintResult::Error(format!(
+                                "in message {0} group field {1} refers to unknown definition {2}",
+                                message.name, field.name, definition_name
+                            )),
+                        );
+                        continue;
+                    }
+                };
+
+                if group.fields.is_empty() {
+                    protocol_lint_result.record(
+                        self.rule(),
+                        Some(&message.name),
+                        Some(&field.name),
+                        LintResult::Error(format!(
+                            "in message {0} group field {1} refers to definition {2}, the protocol must be resolved with `Protocol::resolve_group_definitions()`",
+                            message.name, field.name, definition_name
+                        )),
+                    );
+                    continue;
+                }
+
+                let is_same_layout = group.fields.len() == definition.fields.len()
+                    && group.fields.iter().zip(definition.fields.iter()).all(|(member, defined)| {
+                        member.name == defined.name && member.bit_width() == defined.bit_width()
+                    });
+
+                if !is_same_layout {
+                    protocol_lint_result.record(
+                        self.rule(),
+                        Some(&message.name),
+                        Some(&field.name),
+                        LintResult::Error(format!(
+                            "in message {0} group field {1} differs from its definition {2}",
+                            message.name, field.name, definition_name
+                        )),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Makes sure the C++ header's namespace is a valid one, and that messages
+/// do not take the names the header declares in it
+#[derive(Default)]
+struct CppLinter {}
+
+impl ProtocolLint for CppLinter {
+    fn rule(&self) -> Rule {
+        rules::CPP
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        let Some(cpp_options) = protocol.cpp() else {
+            return;
+        };
+        let is_namespace = cpp_options.namespace.split("::").all(|part| {
+            is_identifier(part) && !C_KEYWORDS.contains(&part) && !CPP_KEYWORDS.contains(&part)
+        });
+
+        if !is_namespace {
+            protocol_lint_result.record(
+                self.rule(),
+                None,
+                None,
+                LintResult::Error(format!("C++ namespace {0} is not a valid name", cpp_options.namespace)),
+            );
+        }
+
+        for message in &protocol.messages {
+            if ["ParseStatus", "ParseError", "MessageId"].contains(&message.name.as_str()) {
+                protocol_lint_result.record(
+                    self.rule(),
+                    Some(&message.name),
+                    None,
+                    LintResult::Error(format!(
+                        "message {0} takes the name of an enum of the C++ header",
+                        message.name
+                    )),
+                );
+            }
+        }
+    }
+}
+
+/// Rejects the options of Ragel's `write data` statement which omit
+/// constants the parsers refer to, see `RagelWriteDataOption::supported()`
+#[derive(Default)]
+struct RagelLinter {}
+
+impl ProtocolLint for RagelLinter {
+    fn rule(&self) -> Rule {
+        rules::RAGEL
+    }
+
+    fn lint_protocol(&mut self, protocol: &representation::Protocol, protocol_lint_result: &mut ProtocolLintResult) {
+        for option in protocol.ragel_options().write_data.iter().filter(|option| !option.supported()) {
+            protocol_lint_result.record(
+                self.rule(),
+                None,
+                None,
+                LintResult::Error(format!(
+                    "\"write data {0}\" omits constants the parsers refer to",
+                    option.keyword()
+                )),
+            );
+        }
+    }
+}
+
+struct CompositeProtocolLinter {
+    pending_linters: vec::Vec<boxed::Box<dyn ProtocolLint>>,
+}
+
+impl CompositeProtocolLinter {
+    pub fn new() -> Self {
+        let mut instance = CompositeProtocolLinter {
+            pending_linters: vec::Vec::default(),
+        };
+        instance
+            .pending_linters
+            .push(boxed::Box::new(MessageNameLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(RootMessageLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(ReservedMessageNameLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(FrameTraceLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(BenchmarkLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(FrameSizeLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(BufferBudgetLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(OutputSplittingLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(ByteStuffingLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(ChecksumImplementationLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(ChecksumCoverageLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(RevisionLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(DispatchLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(StartPatternLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(GroupDefinitionLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(MessageExtensionLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(CppLinter::default()));
+        instance
+            .pending_linters
+            .push(boxed::Box::new(RagelLinter::default()));
+
+        instance
+    }
+
+    pub fn lint_protocol(
+        &mut self,
+        protocol: &representation::Protocol,
+        protocol_lint_result: &mut ProtocolLintResult,
+    ) {
+        for linter in &mut self.pending_linters {
+            linter.lint_protocol(protocol, protocol_lint_result);
+        }
+    }
+}
+
+/// Linters library users add to the built-in ones, e.g. to enforce a
+/// company's naming conventions. Registered linters run after the built-in
+/// ones, in the order of registration. Like those of the built-in linters,
+/// their warnings about a field are suppressed by its `SuppressLint`
+/// attributes
+#[derive(Default)]
+pub struct LinterRegistry {
+    message_field_linters: vec::Vec<boxed::Box<dyn MessageFieldLint>>,
+    protocol_linters: vec::Vec<boxed::Box<dyn ProtocolLint>>,
+}
+
+impl LinterRegistry {
+    pub fn new() -> Self {
+        LinterRegistry::default()
+    }
+
+    pub fn register_message_field_lint(&mut self, linter: boxed::Box<dyn MessageFieldLint>) {
+        self.message_field_linters.push(linter);
+    }
+
+    pub fn register_protocol_lint(&mut self, linter: boxed::Box<dyn ProtocolLint>) {
+        self.protocol_linters.push(linter);
+    }
+
+    /// Built-in rules, followed by those of the registered linters
+    pub fn rules(&self) -> vec::Vec<Rule> {
+        let mut ret = rules::ALL.to_vec();
+
+        for rule in self
+            .message_field_linters
+            .iter()
+            .map(|linter| linter.rule())
+            .chain(self.protocol_linters.iter().map(|linter| linter.rule()))
+        {
+            if !ret.contains(&rule) {
+                ret.push(rule);
+            }
+        }
+
+        ret
+    }
+
+    /// Invokes the protocol linters, then a series of linters on each message
+    /// of the `protocol`. Produces a report of the findings, leaving it to the
+    /// caller to act on them
+    pub fn lint_protocol(&mut self, protocol: &representation::Protocol) -> ProtocolLintResult {
+        let mut protocol_lint_result = ProtocolLintResult::new(protocol);
+        CompositeProtocolLinter::new().lint_protocol(protocol, &mut protocol_lint_result);
+
+        for linter in &mut self.protocol_linters {
+            linter.lint_protocol(protocol, &mut protocol_lint_result);
+        }
+
+        let mut linter = CompositeMessageLinter::new(self.rules());
+
+        for message in &protocol.messages {
+            linter.lint_message(message, &mut self.message_field_linters, &mut protocol_lint_result);
+        }
+
+        protocol_lint_result
+    }
+}
+
+/// Lints the `protocol` with the built-in linters only
+pub fn lint_protocol(protocol: &representation::Protocol) -> ProtocolLintResult {
+    LinterRegistry::new().lint_protocol(protocol)
+}
+
+/// How a rule's warnings are treated, after the fashion of rustc's lint
+/// levels
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LintLevel {
+    /// The warnings are dropped
+    Allow,
+
+    /// The warnings are kept, even if warnings are treated as errors
+    Warn,
+
+    /// The warnings are turned into errors
+    Deny,
+}
+
+/// Strictness of validation. Only warnings are affected: an error means the
+/// generated code would not work, so it is never relaxed
+#[derive(Clone, Debug, Default)]
+pub struct ValidationConfig {
+    /// Turns every warning into an error, so protocols with warnings never
+    /// reach code generation
+    pub warnings_as_errors: bool,
+
+    /// Levels of particular rules, by the rules' IDs or names, taking
+    /// precedence over `warnings_as_errors`
+    pub levels: vec::Vec<(string::String, LintLevel)>,
+}
+
+impl ValidationConfig {
+    /// Level of the rule's warnings. The last matching entry wins
+    pub fn level(&self, rule: &Rule) -> LintLevel {
+        let default = match self.warnings_as_errors {
+            true => LintLevel::Deny,
+            false => LintLevel::Warn,
+        };
+
+        self.levels
+            .iter()
+            .rev()
+            .find(|(name, _)| name == rule.id || name == rule.name)
+            .map_or(default, |(_, level)| *level)
+    }
+
+    /// Applies the levels to the findings
+    pub fn apply(&self, protocol_lint_result: ProtocolLintResult) -> ProtocolLintResult {
+        let diagnostics = protocol_lint_result
+            .diagnostics
+            .into_iter()
+            .filter_map(|mut diagnostic| {
+                if diagnostic.severity == Severity::Warning {
+                    match self.level(&diagnostic.rule) {
+                        LintLevel::Allow => return None,
+                        LintLevel::Warn => {}
+                        LintLevel::Deny => diagnostic.severity = Severity::Error,
+                    }
+                }
+
+                Some(diagnostic)
+            })
+            .collect();
+
+        ProtocolLintResult {
+            diagnostics,
+            ..protocol_lint_result
+        }
+    }
+}
+
+/// Invokes a series of linters on each message of the `protocol`. Produces a
+/// report consisting of Warnings and Errors that were found by the linters,
+/// at the levels of the `config`. If the report has errors, the protocol
+/// definition is faulty, and no code should be generated from it.
+pub fn validate_protocol(protocol: &representation::Protocol, config: &ValidationConfig) -> ProtocolLintResult {
+    validate_protocol_with(protocol, &mut LinterRegistry::new(), config)
+}
+
+/// Like `validate_protocol`, but also runs the linters of the `registry`
+pub fn validate_protocol_with(
+    protocol: &representation::Protocol,
+    registry: &mut LinterRegistry,
+    config: &ValidationConfig,
+) -> ProtocolLintResult {
+    let protocol_lint_result = config.apply(registry.lint_protocol(protocol));
+
+    for diagnostic in &protocol_lint_result.diagnostics {
+        match diagnostic.severity {
+            Severity::Error => log::error!("{}", diagnostic),
+            Severity::Warning => log::warn!("{}", diagnostic),
+        }
     }
 
     protocol_lint_result
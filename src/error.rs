@@ -0,0 +1,53 @@
+//! Errors the library reports to its callers, rather than panicking.
+
+/// Failure of generating or writing an artifact
+#[derive(Debug)]
+pub enum RobustoError {
+    /// Writing into the sink has failed
+    Io(std::io::Error),
+
+    /// Formatting into the sink has failed
+    Fmt(std::fmt::Error),
+
+    /// Generated code is not valid UTF-8, e.g. due to a non-ASCII regex
+    /// split in the middle of a character
+    Utf8(std::str::Utf8Error),
+}
+
+impl std::fmt::Display for RobustoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RobustoError::Io(error) => write!(f, "I/O error: {0}", error),
+            RobustoError::Fmt(error) => write!(f, "formatting error: {0}", error),
+            RobustoError::Utf8(error) => write!(f, "generated code is not valid UTF-8: {0}", error),
+        }
+    }
+}
+
+impl std::error::Error for RobustoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RobustoError::Io(error) => Some(error),
+            RobustoError::Fmt(error) => Some(error),
+            RobustoError::Utf8(error) => Some(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for RobustoError {
+    fn from(error: std::io::Error) -> Self {
+        RobustoError::Io(error)
+    }
+}
+
+impl From<std::fmt::Error> for RobustoError {
+    fn from(error: std::fmt::Error) -> Self {
+        RobustoError::Fmt(error)
+    }
+}
+
+impl From<std::str::Utf8Error> for RobustoError {
+    fn from(error: std::str::Utf8Error) -> Self {
+        RobustoError::Utf8(error)
+    }
+}
@@ -24,4 +24,5 @@
 
 pub mod parser_generation;
 pub mod bpir;
+pub mod error;
 pub mod utility;
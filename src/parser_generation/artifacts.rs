@@ -0,0 +1,345 @@
+//! The files generated for a protocol, as a set: backends render their
+//! artifacts into it under names derived from one stem, and the set is then
+//! written into a directory as a whole. A file which fails to render or to
+//! write leaves the directory as it was, rather than half-updated.
+
+use crate::bpir::representation::{CStandard, Protocol, TargetLanguage};
+use crate::error::RobustoError;
+use crate::parser_generation::overrides::{Overridden, Overrides};
+use crate::parser_generation::post_processing::{
+    block_comments, render, render_with_style, RewriteReport, RewriteRules, SymbolPrefix,
+};
+use crate::parser_generation::prologue::{Prologue, Prologued};
+use crate::parser_generation::user_code::UserCode;
+use crate::parser_generation::{constants, ragel::c, ragel::cpp, runtime, typescript};
+use crate::utility::codegen::{CodeGeneration, CodeStyle, SourceMap};
+use std::path::Path;
+use std::string::String;
+use std::vec::Vec;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArtifactKind {
+    Header,
+    Source,
+
+    /// Ragel source, compiled into a source by Ragel
+    RagelSource,
+
+    /// C++ header wrapping the C API. Its names are scoped by a namespace,
+    /// so symbol prefixes are not collected from it
+    CppHeader,
+
+    /// Reports for the user to review, which are not compiled. Neither the
+    /// prologue, nor rewrite rules apply to them
+    Documentation,
+}
+
+/// Names of the artifacts. Split output is named after a stem of its own, so
+/// it may sit beside the monolithic files
+#[derive(Debug, Clone)]
+pub struct ArtifactNaming {
+    pub stem: String,
+    pub split_stem: String,
+}
+
+impl Default for ArtifactNaming {
+    fn default() -> Self {
+        ArtifactNaming {
+            stem: "output".to_string(),
+            split_stem: "output".to_string(),
+        }
+    }
+}
+
+impl ArtifactNaming {
+    pub fn ragel_source(&self) -> String {
+        format!("{0}.c.rl", self.stem)
+    }
+
+    /// The source Ragel compiles the Ragel source into
+    pub fn source(&self) -> String {
+        format!("{0}.c", self.stem)
+    }
+
+    pub fn header(&self) -> String {
+        format!("{0}.h", self.stem)
+    }
+
+    /// The C++ header wrapping the header
+    pub fn cpp_header(&self) -> String {
+        format!("{0}.hpp", self.stem)
+    }
+
+    pub fn typescript(&self) -> String {
+        format!("{0}.ts", self.stem)
+    }
+
+    pub fn api_report(&self) -> String {
+        format!("{0}.api.txt", self.stem)
+    }
+
+    pub fn api_manifest(&self) -> String {
+        format!("{0}.api.manifest", self.stem)
+    }
+
+    pub fn constants(&self, language: TargetLanguage) -> String {
+        format!("{0}.constants.{1}", self.stem, constants::file_extension(language))
+    }
+
+    pub fn source_map(&self) -> String {
+        format!("{0}.map.json", self.ragel_source())
+    }
+
+    pub fn rewrite_report(&self) -> String {
+        format!("{0}.rewrite.txt", self.stem)
+    }
+}
+
+/// Generation settings every artifact is rendered with
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactSettings {
+    pub style: CodeStyle,
+    pub prologue: Prologue,
+    pub overrides: Overrides,
+}
+
+impl ArtifactSettings {
+    /// Renders the artifact headed by the prologue, with the overrides
+    pub fn render<T: CodeGeneration>(&self, artifact: &T) -> Result<String, RobustoError> {
+        let artifact = Prologued {
+            prologue: &self.prologue,
+            artifact,
+        };
+
+        render_with_style(
+            &Overridden {
+                overrides: &self.overrides,
+                artifact: &artifact,
+            },
+            &self.style,
+        )
+    }
+
+    /// Maps the artifact, as it is rendered
+    pub fn source_map<T: CodeGeneration>(&self, artifact: &T) -> SourceMap {
+        let artifact = Prologued {
+            prologue: &self.prologue,
+            artifact,
+        };
+
+        SourceMap::from_artifact(
+            &Overridden {
+                overrides: &self.overrides,
+                artifact: &artifact,
+            },
+            &self.style,
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneratedArtifact {
+    pub name: String,
+    pub kind: ArtifactKind,
+    pub text: String,
+}
+
+/// Rendered artifacts, in the order they have been added
+#[derive(Debug, Clone, Default)]
+pub struct GeneratedArtifactSet {
+    pub artifacts: Vec<GeneratedArtifact>,
+}
+
+impl GeneratedArtifactSet {
+    /// Renders every artifact the protocol asks for
+    pub fn from_protocol(
+        protocol: &Protocol,
+        naming: &ArtifactNaming,
+        settings: &ArtifactSettings,
+    ) -> Result<GeneratedArtifactSet, RobustoError> {
+        let mut ret = GeneratedArtifactSet::default();
+        c::add_artifacts(protocol, naming, settings, &mut ret)?;
+        cpp::add_artifacts(protocol, naming, settings, &mut ret)?;
+        typescript::add_artifacts(protocol, naming, settings, &mut ret)?;
+        constants::add_artifacts(protocol, naming, settings, &mut ret)?;
+
+        // The runtime library is shared by the protocols, so it is added once the symbols have been prefixed
+        let symbol_prefix = protocol.symbol_naming().symbol_prefix;
+
+        if !symbol_prefix.is_empty() {
+            ret.prefix_symbols(&symbol_prefix);
+        }
+
+        runtime::add_artifacts(&[protocol], settings, &mut ret)?;
+
+        Ok(ret)
+    }
+
+    /// Adds a piece of generated code, rendered with the settings
+    pub fn add_code<T: CodeGeneration>(
+        &mut self,
+        name: &str,
+        kind: ArtifactKind,
+        artifact: &T,
+        settings: &ArtifactSettings,
+    ) -> Result<(), RobustoError> {
+        let text = settings.render(artifact)?;
+        self.add(name, kind, text);
+
+        Ok(())
+    }
+
+    /// Adds a piece of generated C code, rendered with the settings, and
+    /// written in the given revision of C
+    pub fn add_c_code<T: CodeGeneration>(
+        &mut self,
+        name: &str,
+        kind: ArtifactKind,
+        artifact: &T,
+        settings: &ArtifactSettings,
+        c_standard: CStandard,
+    ) -> Result<(), RobustoError> {
+        let text = settings.render(artifact)?;
+
+        match c_standard.line_comments() {
+            true => self.add(name, kind, text),
+            false => self.add(name, kind, block_comments(&text)),
+        }
+
+        Ok(())
+    }
+
+    /// Prefixes the symbols the headers and the Ragel sources define,
+    /// throughout the artifacts, see `SymbolPrefix`
+    pub fn prefix_symbols(&mut self, prefix: &str) {
+        let symbol_prefix = SymbolPrefix::from_c_code(
+            prefix,
+            self.artifacts
+                .iter()
+                .filter(|artifact| matches!(artifact.kind, ArtifactKind::Header | ArtifactKind::RagelSource))
+                .map(|artifact| artifact.text.as_str()),
+        );
+
+        for artifact in &mut self.artifacts {
+            artifact.text = symbol_prefix.apply(&artifact.text);
+        }
+    }
+
+    /// Adds a report, rendered as is
+    pub fn add_report<T: CodeGeneration>(&mut self, name: &str, report: &T) -> Result<(), RobustoError> {
+        let text = render(report)?;
+        self.add(name, ArtifactKind::Documentation, text);
+
+        Ok(())
+    }
+
+    /// Adds an artifact, replacing the one of the same name, if any
+    pub fn add(&mut self, name: &str, kind: ArtifactKind, text: String) {
+        let artifact = GeneratedArtifact {
+            name: name.to_string(),
+            kind,
+            text,
+        };
+
+        match self.artifacts.iter_mut().find(|artifact| artifact.name == name) {
+            Some(existing) => *existing = artifact,
+            None => self.artifacts.push(artifact),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&GeneratedArtifact> {
+        self.artifacts.iter().find(|artifact| artifact.name == name)
+    }
+
+    /// Applies the rewrite rules to every artifact but documentation
+    pub fn rewrite(&mut self, rewrite_rules: &RewriteRules, rewrite_report: &mut RewriteReport) {
+        for artifact in self
+            .artifacts
+            .iter_mut()
+            .filter(|artifact| artifact.kind != ArtifactKind::Documentation)
+        {
+            let text = std::mem::take(&mut artifact.text);
+            artifact.text = rewrite_rules.apply(&artifact.name, text, rewrite_report);
+        }
+    }
+
+    /// Carries the user code blocks of the files in the directory over into
+    /// the artifacts of the same names, see `user_code`. Returns a warning per
+    /// non-empty block which has no counterpart in the artifact any longer
+    pub fn preserve_user_code(&mut self, directory: &Path) -> Result<Vec<String>, String> {
+        let mut ret = Vec::new();
+
+        for artifact in self
+            .artifacts
+            .iter_mut()
+            .filter(|artifact| artifact.kind != ArtifactKind::Documentation)
+        {
+            let path = directory.join(&artifact.name);
+            let previous = match std::fs::read_to_string(&path) {
+                Ok(previous) => previous,
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(error) => return Err(format!("unable to read {0}: {1}", path.display(), error)),
+            };
+            let user_code = UserCode::parse(&previous).map_err(|error| format!("{0}: {1}", path.display(), error))?;
+            let (text, lost) = user_code.restore(&artifact.text);
+            artifact.text = text;
+            ret.extend(lost.into_iter().map(|name| {
+                format!("{0}: user code block \"{1}\" is no longer generated, its code is dropped", artifact.name, name)
+            }));
+        }
+
+        Ok(ret)
+    }
+
+    /// Lists the calls of heap allocation routines the artifacts make, but
+    /// documentation, as `file:line: calls routine()`. Comment lines are skipped
+    pub fn heap_allocations(&self) -> Vec<String> {
+        let call = regex::Regex::new(r"\b(malloc|calloc|realloc|aligned_alloc|free)\s*\(").unwrap();
+        let mut ret = Vec::new();
+
+        for artifact in self
+            .artifacts
+            .iter()
+            .filter(|artifact| artifact.kind != ArtifactKind::Documentation)
+        {
+            for (line_number, line) in artifact.text.lines().enumerate() {
+                let code = line.trim_start();
+
+                if code.starts_with("//") || code.starts_with("/*") || code.starts_with('*') {
+                    continue;
+                }
+
+                ret.extend(
+                    call.captures_iter(code)
+                        .map(|captures| format!("{0}:{1}: calls {2}()", artifact.name, line_number + 1, &captures[1])),
+                );
+            }
+        }
+
+        ret
+    }
+
+    /// Writes the artifacts into the directory, creating it, if needed. Each
+    /// artifact is written into a temporary file first, and the files are
+    /// only renamed into place once all of them have been written
+    pub fn write_to_directory(&self, directory: &Path) -> Result<(), RobustoError> {
+        std::fs::create_dir_all(directory)?;
+        let temporary_path = |artifact: &GeneratedArtifact| directory.join(format!(".{0}.tmp", artifact.name));
+
+        for (i, artifact) in self.artifacts.iter().enumerate() {
+            if let Err(error) = std::fs::write(temporary_path(artifact), &artifact.text) {
+                for written in &self.artifacts[..=i] {
+                    let _ = std::fs::remove_file(temporary_path(written));
+                }
+
+                return Err(error.into());
+            }
+        }
+
+        for artifact in &self.artifacts {
+            std::fs::rename(temporary_path(artifact), directory.join(&artifact.name))?;
+        }
+
+        Ok(())
+    }
+}
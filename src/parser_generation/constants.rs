@@ -0,0 +1,267 @@
+//! Numeric constants of a protocol, exported into every target language from
+//! one table, so firmware and tooling written in different languages agree on
+//! message IDs, error codes, and limits.
+
+use crate::bpir::representation::{Protocol, TargetLanguage};
+use crate::error::RobustoError;
+use crate::parser_generation::artifacts::{ArtifactKind, ArtifactNaming, ArtifactSettings, GeneratedArtifactSet};
+use crate::parser_generation::ragel::c;
+use crate::parser_generation::ragel::common::{self, FieldBaseType};
+use crate::utility::codegen::{CodeChunk, CodeGeneration, CodeGenerationState};
+use crate::utility::string::to_upper_snake_case;
+use std::vec::Vec;
+
+/// Width of the constants which denote sizes and counts, in bits
+const SIZE_BIT_WIDTH: usize = 32;
+
+/// Prefix of every constant's name in languages lacking namespaces
+const C_NAME_PREFIX: &str = "ROBUSTO_";
+
+#[derive(Debug, Clone)]
+pub struct SharedConstant {
+    /// Language-neutral name, in upper snake case
+    pub name: String,
+    pub value: i128,
+
+    /// Width of the type the constant is represented with, in bits
+    pub bit_width: usize,
+    pub description: String,
+}
+
+/// The table every language's artifact is rendered from
+#[derive(Debug, Default)]
+pub struct SharedConstants {
+    pub constants: Vec<SharedConstant>,
+}
+
+impl SharedConstants {
+    fn push(&mut self, name: String, value: i128, bit_width: usize, description: String) {
+        self.constants.push(SharedConstant {
+            name,
+            value,
+            bit_width,
+            description,
+        });
+    }
+}
+
+impl From<&Protocol> for SharedConstants {
+    fn from(protocol: &Protocol) -> Self {
+        let mut ret = SharedConstants::default();
+
+        if let Some(version) = protocol.version() {
+            ret.push(
+                "PROTOCOL_VERSION".to_string(),
+                version as i128,
+                SIZE_BIT_WIDTH,
+                "Version of the protocol the code was generated for".to_string(),
+            );
+        }
+
+        if let Some(dispatcher) = common::Dispatcher::from_protocol(protocol) {
+            ret.push(
+                "DISPATCH_ID_LENGTH".to_string(),
+                dispatcher.id_length as i128,
+                SIZE_BIT_WIDTH,
+                "Length of the message ID field, in bytes".to_string(),
+            );
+
+            for message in &dispatcher.messages {
+                ret.push(
+                    format!("{0}_ID", to_upper_snake_case(&message.message_name)),
+                    message.id as i128,
+                    dispatcher.id_length * 8,
+                    format!("ID of message {0}", message.message_name),
+                );
+            }
+        }
+
+        for parse_error in common::ParseError::all() {
+            ret.push(
+                format!("ERROR_CODE_{0}", to_upper_snake_case(&format!("{:?}", parse_error))),
+                parse_error.code() as i128,
+                8,
+                format!("Parse error code: {0}", parse_error.description()),
+            );
+        }
+
+        for parse_status in common::ParseStatus::all() {
+            ret.push(
+                format!("PARSE_STATUS_{0}", to_upper_snake_case(&format!("{:?}", parse_status))),
+                parse_status.code() as i128,
+                8,
+                format!("Parse status: {0}", parse_status.description()),
+            );
+        }
+
+        for message in &protocol.messages {
+            ret.push(
+                format!("{0}_MIN_FRAME_SIZE", to_upper_snake_case(&message.name)),
+                protocol.frame_size(message).min as i128,
+                SIZE_BIT_WIDTH,
+                format!("Min length of a frame of message {0}, in bytes", message.name),
+            );
+            ret.push(
+                format!("{0}_MAX_FRAME_SIZE", to_upper_snake_case(&message.name)),
+                protocol.frame_size(message).max as i128,
+                SIZE_BIT_WIDTH,
+                format!("Max length of a frame of message {0}, in bytes", message.name),
+            );
+        }
+
+        ret.push(
+            "MIN_FRAME_SIZE".to_string(),
+            protocol
+                .messages
+                .iter()
+                .map(|message| protocol.frame_size(message).min)
+                .min()
+                .unwrap_or(0) as i128,
+            SIZE_BIT_WIDTH,
+            "Min length of a frame of any message, in bytes".to_string(),
+        );
+        ret.push(
+            "MAX_FRAME_SIZE".to_string(),
+            protocol
+                .messages
+                .iter()
+                .map(|message| protocol.frame_size(message).max)
+                .max()
+                .unwrap_or(0) as i128,
+            SIZE_BIT_WIDTH,
+            "Max length of a frame of any message, in bytes".to_string(),
+        );
+
+        if let Some(frame_trace) = protocol.frame_trace() {
+            ret.push(
+                "TRACE_DEPTH".to_string(),
+                frame_trace.depth as i128,
+                SIZE_BIT_WIDTH,
+                "Number of frame trace records".to_string(),
+            );
+
+            for (message_id, message) in protocol.messages.iter().enumerate() {
+                ret.push(
+                    format!("{0}_TRACE_ID", to_upper_snake_case(&message.name)),
+                    message_id as i128,
+                    8,
+                    format!("ID of message {0} in frame trace records", message.name),
+                );
+            }
+        }
+
+        ret
+    }
+}
+
+/// Standard file extension of the language's artifact
+pub fn file_extension(language: TargetLanguage) -> &'static str {
+    match language {
+        TargetLanguage::C => "h",
+        TargetLanguage::Rust => "rs",
+        TargetLanguage::TypeScript => "ts",
+    }
+}
+
+/// The shared constants, rendered in one language
+pub struct SharedConstantsArtifact<'a> {
+    pub constants: &'a SharedConstants,
+    pub language: TargetLanguage,
+}
+
+impl SharedConstantsArtifact<'_> {
+    fn constant_lines(&self, constant: &SharedConstant) -> [String; 2] {
+        match self.language {
+            // Same spelling as the parser header uses, so including both is a
+            // benign redefinition
+            TargetLanguage::C => [
+                format!("// {0}", constant.description),
+                format!(
+                    "#define {0}{1} {2}",
+                    C_NAME_PREFIX,
+                    constant.name,
+                    c::c_integer_literal(
+                        constant.value,
+                        &FieldBaseType::from_bit_width(constant.bit_width, constant.value < 0)
+                    )
+                ),
+            ],
+            TargetLanguage::Rust => [
+                format!("/// {0}", constant.description),
+                format!(
+                    "pub const {0}: {1} = {2};",
+                    constant.name,
+                    rust_type_name(constant),
+                    constant.value
+                ),
+            ],
+            // Numbers lose precision past 53 bits, so wide constants are bigints
+            TargetLanguage::TypeScript => [
+                format!("/** {0} */", constant.description),
+                format!(
+                    "export const {0} = {1}{2};",
+                    constant.name,
+                    constant.value,
+                    if constant.bit_width > 53 { "n" } else { "" }
+                ),
+            ],
+        }
+    }
+}
+
+fn rust_type_name(constant: &SharedConstant) -> &'static str {
+    match (constant.bit_width, constant.value < 0) {
+        (0..=8, false) => "u8",
+        (9..=16, false) => "u16",
+        (17..=32, false) => "u32",
+        (_, false) => "u64",
+        (0..=8, true) => "i8",
+        (9..=16, true) => "i16",
+        (17..=32, true) => "i32",
+        (_, true) => "i64",
+    }
+}
+
+impl CodeGeneration for SharedConstantsArtifact<'_> {
+    fn generate_code(&self, code_generation_state: &mut CodeGenerationState) -> Vec<CodeChunk> {
+        let mut ret = Vec::new();
+
+        for constant in &self.constants.constants {
+            for line in self.constant_lines(constant) {
+                ret.push(CodeChunk::new(line, code_generation_state.indent, 1usize));
+            }
+        }
+
+        ret
+    }
+}
+
+/// Renders the shared constants into the set, in each language the protocol
+/// asks for. C constants make a header, the rest sources
+pub fn add_artifacts(
+    protocol: &Protocol,
+    naming: &ArtifactNaming,
+    settings: &ArtifactSettings,
+    artifact_set: &mut GeneratedArtifactSet,
+) -> Result<(), RobustoError> {
+    let shared_constants = SharedConstants::from(protocol);
+
+    for language in protocol.shared_constants_languages() {
+        let artifact = SharedConstantsArtifact {
+            constants: &shared_constants,
+            language: *language,
+        };
+        match language {
+            TargetLanguage::C => artifact_set.add_c_code(
+                &naming.constants(*language),
+                ArtifactKind::Header,
+                &artifact,
+                settings,
+                protocol.c_standard(),
+            )?,
+            _ => artifact_set.add_code(&naming.constants(*language), ArtifactKind::Source, &artifact, settings)?,
+        }
+    }
+
+    Ok(())
+}
@@ -1,6 +1,25 @@
+pub mod artifacts;
+pub mod constants;
+pub mod overrides;
+pub mod post_processing;
+pub mod prologue;
 pub mod ragel;
+pub mod runtime;
+pub mod typescript;
+pub mod user_code;
 use std;
 
 pub trait Write {
-    fn write<W: std::io::Write>(&self, buf_writer: &mut std::io::BufWriter<W>);
+    /// Writes the artifact in the default style, see `write_with_style`
+    fn write<W: std::io::Write>(&self, buf_writer: &mut std::io::BufWriter<W>) -> Result<(), crate::error::RobustoError> {
+        self.write_with_style(buf_writer, &crate::utility::codegen::CodeStyle::default())
+    }
+
+    /// Writes the artifact, and flushes the writer, so failures are not lost
+    /// when it is dropped
+    fn write_with_style<W: std::io::Write>(
+        &self,
+        buf_writer: &mut std::io::BufWriter<W>,
+        style: &crate::utility::codegen::CodeStyle,
+    ) -> Result<(), crate::error::RobustoError>;
 }
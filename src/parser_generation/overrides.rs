@@ -0,0 +1,216 @@
+//! User overrides of generated fragments: the prologue, `#include`
+//! directives, parsing functions' signatures, and Ragel actions' bodies may be
+//! replaced with snippets of the user's own, without forking the backend.
+//!
+//! Unlike rewrite rules, which see an artifact as plain text, overrides are
+//! applied to the fragments generators mark, so a snippet follows the code's
+//! indentation, and does not depend on how the rest of the code looks.
+
+use crate::error::RobustoError;
+use crate::utility::codegen::{CodeChunk, CodeChunkSink, CodeGeneration, CodeGenerationState, Fragment};
+use std::string::String;
+use std::vec::Vec;
+
+/// Placeholder standing for the message the fragment belongs to
+const MESSAGE_PLACEHOLDER: &str = "{message}";
+
+/// Placeholder standing for the generated fragment, so a snippet may wrap it.
+/// On a line of its own, it keeps the fragment's indentation
+const ORIGINAL_PLACEHOLDER: &str = "{original}";
+
+#[derive(Debug, Clone)]
+pub struct Override {
+    pub fragment: Fragment,
+
+    /// Restricts the override to a message's code, unless `None`
+    pub message: Option<String>,
+
+    /// Snippet replacing the fragment, a chunk per line
+    pub template: String,
+}
+
+impl Override {
+    fn applies_to(&self, code_chunk: &CodeChunk) -> bool {
+        code_chunk.fragment() == Some(&self.fragment)
+            && match self.message {
+                Some(ref message) => code_chunk.origin().is_some_and(|origin| &origin.message == message),
+                None => true,
+            }
+    }
+}
+
+/// Overrides by fragment. If several apply to a fragment, the first one
+/// declared wins.
+///
+/// The text form has a header line followed by one tab-separated override per
+/// line. Lines starting with `#` are comments. `*` in place of a message
+/// applies to every message. Templates may use the escapes Ragel literals
+/// use, e.g. `\n`:
+///
+/// ```text
+/// robusto-overrides 1
+/// prologue<TAB>template
+/// includes<TAB>template
+/// signature<TAB>message<TAB>template
+/// action<TAB>message<TAB>action<TAB>template
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Overrides {
+    pub overrides: Vec<Override>,
+}
+
+const OVERRIDES_HEADER: &str = "robusto-overrides 1";
+
+impl Overrides {
+    /// Parses the text form of the overrides
+    pub fn parse(text: &str) -> Result<Overrides, String> {
+        let mut lines = text.lines().enumerate();
+
+        match lines.next() {
+            Some((_, OVERRIDES_HEADER)) => {}
+            _ => return Err(format!("expected \"{0}\" header", OVERRIDES_HEADER)),
+        }
+
+        let mut ret = Overrides::default();
+        let message = |message: &str| match message {
+            "*" => None,
+            message => Some(message.to_string()),
+        };
+
+        for (line_number, line) in lines {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let columns: Vec<&str> = line.split('\t').collect();
+            let (fragment, message, template) = match columns[..] {
+                ["prologue", template] => (Fragment::Prologue, None, template),
+                ["includes", template] => (Fragment::Includes, None, template),
+                ["signature", message_name, template] => {
+                    (Fragment::ParsingFunctionSignature, message(message_name), template)
+                }
+                ["action", message_name, action, template] => {
+                    (Fragment::ActionBody(action.to_string()), message(message_name), template)
+                }
+                _ => return Err(format!("line {0}: malformed override", line_number + 1)),
+            };
+            ret.register(Override {
+                fragment,
+                message,
+                template: String::from_utf8_lossy(&crate::utility::string::unescape_literal(template)).into_owned(),
+            });
+        }
+
+        Ok(ret)
+    }
+
+    pub fn register(&mut self, r#override: Override) {
+        self.overrides.push(r#override);
+    }
+
+    fn find(&self, code_chunk: &CodeChunk) -> Option<&Override> {
+        self.overrides.iter().find(|r#override| r#override.applies_to(code_chunk))
+    }
+}
+
+/// An artifact, having its fragments overridden
+pub struct Overridden<'a, T: CodeGeneration> {
+    pub overrides: &'a Overrides,
+    pub artifact: &'a T,
+}
+
+impl<T: CodeGeneration> CodeGeneration for Overridden<'_, T> {
+    fn generate_code(&self, code_generation_state: &mut CodeGenerationState) -> Vec<CodeChunk> {
+        let mut ret = Vec::new();
+
+        // Collecting into a buffer never fails
+        let _ = self.generate_code_into(code_generation_state, &mut ret);
+
+        ret
+    }
+
+    fn generate_code_into(
+        &self,
+        code_generation_state: &mut CodeGenerationState,
+        sink: &mut dyn CodeChunkSink,
+    ) -> Result<(), RobustoError> {
+        let mut overriding_sink = OverridingSink {
+            overrides: self.overrides,
+            sink,
+            fragment: Vec::new(),
+        };
+        self.artifact.generate_code_into(code_generation_state, &mut overriding_sink)?;
+
+        overriding_sink.flush()
+    }
+}
+
+/// Holds the chunks of an overridden fragment back until the fragment ends,
+/// and passes the snippet on instead
+struct OverridingSink<'a, 'b> {
+    overrides: &'a Overrides,
+    sink: &'b mut dyn CodeChunkSink,
+
+    /// Chunks of the fragment being overridden
+    fragment: Vec<CodeChunk>,
+}
+
+impl OverridingSink<'_, '_> {
+    fn flush(&mut self) -> Result<(), RobustoError> {
+        let fragment = std::mem::take(&mut self.fragment);
+        let (Some(first), Some(last)) = (fragment.first(), fragment.last()) else {
+            return Ok(());
+        };
+        let r#override = self.overrides.find(first).unwrap();
+        let message = first.origin().map(|origin| origin.message.clone()).unwrap_or_default();
+        let original = fragment.iter().map(CodeChunk::code).collect::<Vec<_>>().join("\n");
+        let template = r#override.template.replace(MESSAGE_PLACEHOLDER, &message);
+        let lines = match template.as_str() {
+            "" => Vec::new(),
+            template => template.split('\n').collect::<Vec<_>>(),
+        };
+
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim() == ORIGINAL_PLACEHOLDER {
+                for code_chunk in &fragment {
+                    self.sink.accept(code_chunk.clone())?;
+                }
+
+                continue;
+            }
+
+            // The fragment's spacing from the code following it is preserved
+            let newlines = if i + 1 == lines.len() { last.newlines().max(1usize) } else { 1usize };
+            self.sink.accept(
+                CodeChunk::new(line.replace(ORIGINAL_PLACEHOLDER, &original), first.indent(), newlines)
+                    .with_origin(first.origin()),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CodeChunkSink for OverridingSink<'_, '_> {
+    fn accept(&mut self, code_chunk: CodeChunk) -> Result<(), RobustoError> {
+        // A fragment lasts as long as its chunks come in a row
+        if let Some(first) = self.fragment.first() {
+            if first.fragment() == code_chunk.fragment() && first.origin() == code_chunk.origin() {
+                self.fragment.push(code_chunk);
+
+                return Ok(());
+            }
+
+            self.flush()?;
+        }
+
+        match self.overrides.find(&code_chunk) {
+            Some(_) => {
+                self.fragment.push(code_chunk);
+
+                Ok(())
+            }
+            None => self.sink.accept(code_chunk),
+        }
+    }
+}
@@ -0,0 +1,542 @@
+//! Post-processing of generated artifacts: declarative rewrite rules applied
+//! as the final stage of generation. Meant for site-specific quirks (a license
+//! banner, a vendor prefix on public symbols) which do not justify changes to
+//! the generators.
+//!
+//! Every applied rule is recorded, so a rewritten artifact may be traced back
+//! to the rules which shaped it.
+
+use crate::error::RobustoError;
+use crate::parser_generation::Write;
+use crate::utility::codegen::{CodeChunk, CodeGeneration, CodeGenerationState, CodeStyle};
+use crate::parser_generation::user_code;
+use crate::utility::identifier::is_identifier;
+use std::collections::HashSet;
+use std::vec::Vec;
+
+/// A single rewrite of an artifact's text
+#[derive(Debug, Clone)]
+pub enum RewriteAction {
+    /// Replaces every match of the regex. The replacement may refer to the
+    /// regex's capture groups as `$1`, or `${name}`
+    Replace {
+        pattern: regex::Regex,
+        replacement: String,
+    },
+
+    /// Prepends the text, followed by a newline
+    InjectHeader(String),
+
+    /// Renames identifiers. Only whole identifiers are matched, so renaming
+    /// `parse` leaves `parseMessage` intact
+    Rename(Vec<(String, String)>),
+}
+
+#[derive(Debug, Clone)]
+pub struct RewriteRule {
+    /// Artifacts the rule applies to: `*` for any, or a suffix of the
+    /// artifact's name, e.g. `.h`
+    pub artifacts: String,
+    pub action: RewriteAction,
+}
+
+impl RewriteRule {
+    fn applies_to(&self, artifact_name: &str) -> bool {
+        self.artifacts == "*" || artifact_name.ends_with(&self.artifacts)
+    }
+
+    /// Human-readable summary, for the report
+    fn description(&self) -> String {
+        match self.action {
+            RewriteAction::Replace { ref pattern, ref replacement } => {
+                format!("replace /{0}/ with \"{1}\"", pattern.as_str(), replacement)
+            }
+            RewriteAction::InjectHeader(_) => "inject header".to_string(),
+            RewriteAction::Rename(ref renames) => format!(
+                "rename {0}",
+                renames
+                    .iter()
+                    .map(|(from, to)| format!("{0} -> {1}", from, to))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// Rewrite rules, applied in the order of declaration.
+///
+/// The text form has a header line followed by one tab-separated rule per
+/// line. Lines starting with `#` are comments. Header text may use the
+/// escapes Ragel literals use, e.g. `\n`:
+///
+/// ```text
+/// robusto-rewrite-rules 1
+/// replace<TAB>artifacts<TAB>regex<TAB>replacement
+/// header<TAB>artifacts<TAB>text
+/// rename<TAB>artifacts<TAB>from=to[,from=to...]
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RewriteRules {
+    pub rules: Vec<RewriteRule>,
+}
+
+const REWRITE_RULES_HEADER: &str = "robusto-rewrite-rules 1";
+
+impl RewriteRules {
+    /// Parses the text form of the rules
+    pub fn parse(text: &str) -> Result<RewriteRules, String> {
+        let mut lines = text.lines().enumerate();
+
+        match lines.next() {
+            Some((_, REWRITE_RULES_HEADER)) => {}
+            _ => return Err(format!("expected \"{0}\" header", REWRITE_RULES_HEADER)),
+        }
+
+        let mut ret = RewriteRules::default();
+
+        for (line_number, line) in lines {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let columns: Vec<&str> = line.split('\t').collect();
+            let (artifacts, action) = match columns[..] {
+                ["replace", artifacts, pattern, replacement] => (
+                    artifacts,
+                    RewriteAction::Replace {
+                        pattern: regex::Regex::new(pattern)
+                            .map_err(|error| format!("line {0}: {1}", line_number + 1, error))?,
+                        replacement: replacement.to_string(),
+                    },
+                ),
+                ["header", artifacts, text] => (
+                    artifacts,
+                    RewriteAction::InjectHeader(
+                        String::from_utf8_lossy(&crate::utility::string::unescape_literal(text))
+                            .into_owned(),
+                    ),
+                ),
+                ["rename", artifacts, renames] => (
+                    artifacts,
+                    RewriteAction::Rename(
+                        renames
+                            .split(',')
+                            .map(|rename| match rename.split_once('=') {
+                                Some((from, to)) if is_identifier(from) && is_identifier(to) => {
+                                    Ok((from.to_string(), to.to_string()))
+                                }
+                                _ => Err(format!(
+                                    "line {0}: invalid rename \"{1}\"",
+                                    line_number + 1,
+                                    rename
+                                )),
+                            })
+                            .collect::<Result<Vec<(String, String)>, String>>()?,
+                    ),
+                ),
+                _ => return Err(format!("line {0}: malformed rule", line_number + 1)),
+            };
+            ret.rules.push(RewriteRule {
+                artifacts: artifacts.to_string(),
+                action,
+            });
+        }
+
+        Ok(ret)
+    }
+
+    /// Rewrites the artifact's text, recording every rule which applies to it
+    pub fn apply(&self, artifact_name: &str, text: String, report: &mut RewriteReport) -> String {
+        let mut ret = text;
+        let identifier_pattern = regex::Regex::new(r"[A-Za-z_][A-Za-z0-9_]*").unwrap();
+
+        for (rule_index, rule) in self.rules.iter().enumerate() {
+            if !rule.applies_to(artifact_name) {
+                continue;
+            }
+
+            let (text, rewrites) = match rule.action {
+                RewriteAction::Replace { ref pattern, ref replacement } => {
+                    let rewrites = pattern.find_iter(&ret).count();
+
+                    (pattern.replace_all(&ret, replacement.as_str()).into_owned(), rewrites)
+                }
+                RewriteAction::InjectHeader(ref header) => (format!("{0}\n{1}", header, ret), 1usize),
+                RewriteAction::Rename(ref renames) => {
+                    let mut rewrites = 0usize;
+                    let text = identifier_pattern
+                        .replace_all(&ret, |captures: &regex::Captures| {
+                            let identifier = &captures[0];
+
+                            match renames.iter().find(|(from, _)| from == identifier) {
+                                Some((_, to)) => {
+                                    rewrites += 1;
+                                    to.clone()
+                                }
+                                None => identifier.to_string(),
+                            }
+                        })
+                        .into_owned();
+
+                    (text, rewrites)
+                }
+            };
+
+            ret = text;
+            report.entries.push(RewriteReportEntry {
+                artifact_name: artifact_name.to_string(),
+                rule_index,
+                description: rule.description(),
+                rewrites,
+            });
+        }
+
+        ret
+    }
+}
+
+/// Renders an artifact into a string, so it may be rewritten
+pub fn render<T: Write>(artifact: &T) -> Result<String, RobustoError> {
+    render_with_style(artifact, &CodeStyle::default())
+}
+
+/// Renders an artifact into a string in the given style
+pub fn render_with_style<T: Write>(artifact: &T, style: &CodeStyle) -> Result<String, RobustoError> {
+    let mut buf_writer = std::io::BufWriter::new(Vec::new());
+    artifact.write_with_style(&mut buf_writer, style)?;
+    let bytes = buf_writer.into_inner().map_err(|error| error.into_error())?;
+
+    Ok(String::from_utf8(bytes).map_err(|error| error.utf8_error())?)
+}
+
+/// Turns the `//` comments of C code into `/* */` ones, for compilers which
+/// predate C99. Literals, and comments which already are block comments, are
+/// left as they are
+pub fn block_comments(text: &str) -> String {
+    let mut ret = String::with_capacity(text.len());
+    let mut in_block_comment = false;
+
+    for line in text.split_inclusive('\n') {
+        let code = line.trim_end_matches(['\r', '\n']);
+        let mut chars = code.char_indices().peekable();
+        let mut quote = None;
+        let mut line_comment = None;
+
+        while let Some((i, c)) = chars.next() {
+            let next = chars.peek().map(|(_, next)| *next);
+
+            match (in_block_comment, quote, c, next) {
+                (true, _, '*', Some('/')) => {
+                    in_block_comment = false;
+                    chars.next();
+                }
+                (true, _, _, _) => {}
+                (false, Some(_), '\\', _) => {
+                    chars.next();
+                }
+                (false, Some(opening), _, _) if c == opening => quote = None,
+                (false, Some(_), _, _) => {}
+                (false, None, '"' | '\'', _) => quote = Some(c),
+                (false, None, '/', Some('*')) => {
+                    in_block_comment = true;
+                    chars.next();
+                }
+                (false, None, '/', Some('/')) => {
+                    line_comment = Some(i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+
+        match line_comment {
+            Some(i) => {
+                // A comment ending the block early would expose the rest
+                ret.push_str(&code[..i]);
+                ret.push_str("/*");
+                ret.push_str(&code[i + 2..].replace("*/", "* /"));
+                ret.push_str(" */");
+                ret.push_str(&line[code.len()..]);
+            }
+            None => ret.push_str(line),
+        }
+    }
+
+    ret
+}
+
+/// Prepends a prefix to the symbols generated C code defines, see
+/// `SymbolNaming::symbol_prefix`. The symbols are collected from the top-level
+/// definitions of the code: functions, variables, struct, union and enum tags,
+/// enumerators, macros, and Ragel machines along with the constants Ragel
+/// names after them. Functions the code merely declares or calls, e.g. user
+/// hooks and the runtime library's, keep their names.
+///
+/// Only code is rewritten: string and character literals, comments and user
+/// code blocks are kept, and so are struct members, which may be named after
+/// a symbol, e.g. a field named `selfTest`
+#[derive(Debug, Clone, Default)]
+pub struct SymbolPrefix {
+    pub prefix: String,
+    pub symbols: HashSet<String>,
+    pub machines: Vec<String>,
+}
+
+impl SymbolPrefix {
+    /// Collects the symbols the texts of C code, and of Ragel sources, define
+    pub fn from_c_code<'a>(prefix: &str, texts: impl IntoIterator<Item = &'a str>) -> SymbolPrefix {
+        let define = regex::Regex::new(r"^#define\s+([A-Za-z_]\w*)").unwrap();
+        let tag = regex::Regex::new(r"^(?:typedef\s+)?(struct|union|enum)\s+([A-Za-z_]\w*)\s*\{?$").unwrap();
+        let machine = regex::Regex::new(r"^\s*machine\s+([A-Za-z_]\w*)\s*;").unwrap();
+        let leading_identifier = regex::Regex::new(r"^\s*([A-Za-z_]\w*)").unwrap();
+        let identifier = regex::Regex::new(r"[A-Za-z_]\w*").unwrap();
+        let last_identifier = |text: &str| identifier.find_iter(text).last().map(|found| found.as_str().to_string());
+        let mut ret = SymbolPrefix {
+            prefix: prefix.to_string(),
+            ..SymbolPrefix::default()
+        };
+
+        for text in texts {
+            let lines = text.lines().collect::<Vec<&str>>();
+            let mut in_enum = false;
+            let mut in_comment = false;
+
+            for (i, line) in lines.iter().enumerate() {
+                // Comments spanning lines, e.g. the ones of C89 code, may start with anything
+                if in_comment || line.trim_start().starts_with("/*") {
+                    in_comment = !line.contains("*/");
+                    continue;
+                }
+
+                // With own-line braces, the brace opening a definition follows its head
+                let opens_block = line.ends_with('{') || lines.get(i + 1).is_some_and(|next| next.trim() == "{");
+
+                if in_enum {
+                    if line.starts_with('}') {
+                        in_enum = false;
+                    } else if let Some(captures) = leading_identifier.captures(line) {
+                        ret.symbols.insert(captures[1].to_string());
+                    }
+
+                    continue;
+                }
+
+                if let Some(captures) = define.captures(line) {
+                    ret.symbols.insert(captures[1].to_string());
+                } else if let Some(captures) = tag.captures(line).filter(|_| opens_block) {
+                    ret.symbols.insert(captures[2].to_string());
+                    in_enum = &captures[1] == "enum";
+                } else if let Some(captures) = machine.captures(line) {
+                    if !ret.machines.iter().any(|name| name == &captures[1]) {
+                        ret.machines.push(captures[1].to_string());
+                    }
+                } else if !line.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+                    continue;
+                } else if let Some(parenthesis) = line.find('(') {
+                    // Functions are defined, rather than merely declared, where a body follows
+                    if opens_block {
+                        ret.symbols.extend(last_identifier(&line[..parenthesis]));
+                    }
+                } else if let Some(end) = line.find(['[', '=', ';']) {
+                    ret.symbols.extend(last_identifier(&line[..end]));
+                }
+            }
+        }
+
+        ret
+    }
+
+    /// The symbol with the prefix. Macros and enumerators, which are upper
+    /// case, take the prefix in upper case
+    fn prefixed(&self, symbol: &str) -> String {
+        match symbol.chars().any(|c| c.is_ascii_lowercase()) {
+            true => format!("{0}{1}", self.prefix, symbol),
+            false => format!("{0}{1}", self.prefix.to_uppercase(), symbol),
+        }
+    }
+
+    /// Whether Ragel names the identifier after one of the machines, e.g.
+    /// `Status_first_final`
+    fn is_machine_constant(&self, identifier: &str) -> bool {
+        self.machines.iter().any(|machine| {
+            identifier
+                .strip_prefix(machine.as_str())
+                .and_then(|rest| rest.strip_prefix('_'))
+                .is_some_and(|rest| matches!(rest, "first_final" | "error" | "start") || rest.starts_with("en_"))
+        })
+    }
+
+    fn rewrite_identifier(&self, identifier: &str) -> String {
+        if self.symbols.contains(identifier) {
+            self.prefixed(identifier)
+        } else if self.is_machine_constant(identifier) {
+            format!("{0}{1}", self.prefix, identifier)
+        } else {
+            identifier.to_string()
+        }
+    }
+
+    /// Rewrites the identifiers of a line of code. Members, accessed, or
+    /// declared in the body of a struct or a union, keep their names
+    fn apply_to_line(&self, line: &str, in_comment: &mut bool, declares_members: bool) -> String {
+        let characters = line.char_indices().collect::<Vec<(usize, char)>>();
+        let mut ret = String::with_capacity(line.len());
+        let mut i = 0usize;
+
+        while i < characters.len() {
+            let (start, c) = characters[i];
+            let next = characters.get(i + 1).map(|(_, c)| *c);
+
+            // The end of the comment, the literal, or the word, and the identifier's rewrite
+            let (end, rewrite) = if *in_comment {
+                match line[start..].find("*/") {
+                    Some(offset) => {
+                        *in_comment = false;
+                        (start + offset + 2, None)
+                    }
+                    None => (line.len(), None),
+                }
+            } else if c == '/' && next == Some('*') {
+                *in_comment = true;
+                (start + 2, None)
+            } else if c == '/' && next == Some('/') {
+                (line.len(), None)
+            } else if c == '"' || c == '\'' {
+                // Unterminated quotes, e.g. of Rust lifetimes, stand for themselves
+                let mut end = None;
+                let mut escaped = false;
+
+                for &(position, quoted) in &characters[i + 1..] {
+                    match quoted {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        _ if quoted == c => {
+                            end = Some(position + 1);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+
+                (end.unwrap_or(start + 1), None)
+            } else if c.is_ascii_digit() {
+                let end = characters[i..]
+                    .iter()
+                    .find(|(_, c)| !(c.is_ascii_alphanumeric() || *c == '_' || *c == '.'))
+                    .map_or(line.len(), |(position, _)| *position);
+                (end, None)
+            } else if c.is_ascii_alphabetic() || c == '_' {
+                let end = characters[i..]
+                    .iter()
+                    .find(|(_, c)| !(c.is_ascii_alphanumeric() || *c == '_'))
+                    .map_or(line.len(), |(position, _)| *position);
+                let before = line[..start].trim_end_matches(' ');
+                let after = line[end..].trim_start_matches(' ').chars().next();
+                let is_member = line[..start].ends_with('.')
+                    || line[..start].ends_with("->")
+                    || (declares_members && (matches!(after, Some(';' | '[' | ':' | ',')) || before.ends_with("(*")));
+                (end, (!is_member).then(|| self.rewrite_identifier(&line[start..end])))
+            } else {
+                (start + c.len_utf8(), None)
+            };
+
+            match rewrite {
+                Some(rewrite) => ret.push_str(&rewrite),
+                None => ret.push_str(&line[start..end]),
+            }
+
+            while i < characters.len() && characters[i].0 < end {
+                i += 1;
+            }
+        }
+
+        ret
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let machine = regex::Regex::new(r"(?m)^(\s*machine\s+)([A-Za-z_]\w*)(\s*;)").unwrap();
+        let aggregate = regex::Regex::new(r"^(?:typedef\s+)?(?:struct|union)(?:\s+[A-Za-z_]\w*)?\s*\{?$").unwrap();
+        let text = machine.replace_all(text, |captures: &regex::Captures| {
+            match self.machines.iter().any(|name| name == &captures[2]) {
+                true => format!("{0}{1}{2}{3}", &captures[1], self.prefix, &captures[2], &captures[3]),
+                false => captures[0].to_string(),
+            }
+        });
+        let lines = text.split_inclusive('\n').collect::<Vec<&str>>();
+        let mut ret = String::with_capacity(text.len());
+        let mut in_comment = false;
+        let mut in_user_code = false;
+
+        // Depth of the struct and union bodies the line is in, nested unions included
+        let mut aggregate_depth = 0usize;
+
+        for (i, line) in lines.iter().enumerate() {
+            let code = line.trim();
+
+            if user_code::is_begin_line(code) || user_code::is_end_line(code) {
+                in_user_code = user_code::is_begin_line(code);
+                ret.push_str(line);
+                continue;
+            }
+
+            if in_user_code {
+                ret.push_str(line);
+                continue;
+            }
+
+            let opens_aggregate = aggregate.is_match(code)
+                && (code.ends_with('{') || lines.get(i + 1).is_some_and(|next| next.trim() == "{"));
+            let closes_aggregate = aggregate_depth > 0 && code.starts_with('}');
+
+            // So do the struct member entries of API manifests, see `ApiManifest`
+            let declares_members = (aggregate_depth > 0 && !opens_aggregate) || code.starts_with("member\t");
+            ret.push_str(&self.apply_to_line(line, &mut in_comment, declares_members));
+
+            if opens_aggregate {
+                aggregate_depth += 1;
+            } else if closes_aggregate {
+                aggregate_depth -= 1;
+            }
+        }
+
+        ret
+    }
+}
+
+/// A rule applied to an artifact
+#[derive(Debug, Clone)]
+pub struct RewriteReportEntry {
+    pub artifact_name: String,
+
+    /// Position of the rule in the rule set, counting from 0
+    pub rule_index: usize,
+    pub description: String,
+
+    /// Number of places the rule has changed
+    pub rewrites: usize,
+}
+
+/// Rules applied in the course of generation, one entry per rule and artifact
+#[derive(Debug, Clone, Default)]
+pub struct RewriteReport {
+    pub entries: Vec<RewriteReportEntry>,
+}
+
+impl CodeGeneration for RewriteReport {
+    fn generate_code(&self, code_generation_state: &mut CodeGenerationState) -> Vec<CodeChunk> {
+        self.entries
+            .iter()
+            .map(|entry| {
+                CodeChunk::new(
+                    format!(
+                        "{0}: rule {1} ({2}), {3} rewrite(s)",
+                        entry.artifact_name, entry.rule_index, entry.description, entry.rewrites
+                    ),
+                    code_generation_state.indent,
+                    1,
+                )
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,136 @@
+//! The comment every generated file opens with: a warning against editing
+//! the file by hand, and, optionally, the license the code is distributed
+//! under.
+//!
+//! Every target language, and Ragel's host code, accept `//` comments, so
+//! the prologue reads the same in each artifact.
+
+use crate::error::RobustoError;
+use crate::utility::codegen::{CodeChunk, CodeChunkSink, CodeGeneration, CodeGenerationState, Fragment};
+use std::string::String;
+use std::vec::Vec;
+
+/// What goes atop the generated files. By default, the warning, and the time
+/// of generation
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct Prologue {
+    /// SPDX identifier of the license, e.g. "MIT"
+    pub spdx_license_identifier: Option<String>,
+
+    /// License text, which may span several lines
+    pub license: Option<String>,
+
+    /// Whether to record when the file has been generated. Reproducible
+    /// builds turn it off, so the output only depends on the input
+    pub timestamp: bool,
+}
+
+impl Default for Prologue {
+    fn default() -> Self {
+        Prologue {
+            spdx_license_identifier: None,
+            license: None,
+            timestamp: true,
+        }
+    }
+}
+
+impl Prologue {
+    pub fn lines(&self) -> Vec<String> {
+        let mut ret = Vec::new();
+
+        // The identifier is expected on the first line
+        if let Some(ref identifier) = self.spdx_license_identifier {
+            ret.push(format!("SPDX-License-Identifier: {0}", identifier));
+        }
+
+        ret.push(format!("GENERATED BY robusto v{0} - DO NOT EDIT", env!("CARGO_PKG_VERSION")));
+
+        if self.timestamp {
+            let seconds = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0u64);
+            ret.push(format!("Generated at {0}", format_utc(seconds)));
+        }
+
+        if let Some(ref license) = self.license {
+            ret.push(String::new());
+            ret.extend(license.lines().map(str::to_string));
+        }
+
+        ret
+    }
+}
+
+impl CodeGeneration for Prologue {
+    fn generate_code(&self, code_generation_state: &mut CodeGenerationState) -> Vec<CodeChunk> {
+        let mut ret = self
+            .lines()
+            .into_iter()
+            .map(|line| {
+                let comment = if line.is_empty() { "//".to_string() } else { format!("// {0}", line) };
+
+                CodeChunk::new(comment, code_generation_state.indent, 1usize).with_fragment(Some(&Fragment::Prologue))
+            })
+            .collect::<Vec<_>>();
+        ret.push(
+            CodeChunk::new(String::new(), code_generation_state.indent, 1usize).with_fragment(Some(&Fragment::Prologue)),
+        );
+
+        ret
+    }
+}
+
+/// An artifact headed by a prologue
+pub struct Prologued<'a, T: CodeGeneration> {
+    pub prologue: &'a Prologue,
+    pub artifact: &'a T,
+}
+
+impl<T: CodeGeneration> CodeGeneration for Prologued<'_, T> {
+    fn generate_code(&self, code_generation_state: &mut CodeGenerationState) -> Vec<CodeChunk> {
+        let mut ret = self.prologue.generate_code(code_generation_state);
+        ret.append(&mut self.artifact.generate_code(code_generation_state));
+
+        ret
+    }
+
+    fn generate_code_into(
+        &self,
+        code_generation_state: &mut CodeGenerationState,
+        sink: &mut dyn CodeChunkSink,
+    ) -> Result<(), RobustoError> {
+        self.prologue.generate_code_into(code_generation_state, sink)?;
+        self.artifact.generate_code_into(code_generation_state, sink)
+    }
+}
+
+/// Formats seconds since the Unix epoch as an ISO 8601 UTC time, e.g.
+/// "2023-07-14T09:30:00Z"
+fn format_utc(seconds: u64) -> String {
+    let days = (seconds / 86400u64) as i64;
+    let seconds_of_day = seconds % 86400u64;
+
+    // Civil date from the day number, see Howard Hinnant's `civil_from_days`
+    let shifted_days = days + 719468i64;
+    let era = shifted_days.div_euclid(146097i64);
+    let day_of_era = shifted_days.rem_euclid(146097i64);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{0:04}-{1:02}-{2:02}T{3:02}:{4:02}:{5:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60
+    )
+}
@@ -1,292 +1,6419 @@
-use crate::bpir::representation::{self, FieldAttribute, FieldType, Protocol};
-use crate::parser_generation;
+//! C backend. The generated code does not depend on the host's byte order
+//! or on the signedness of `char`, so the same sources run on little- and
+//! big-endian targets alike:
+//!
+//! - multi-byte values are assembled with shifts, never by copying memory;
+//! - unions are only used as tagged unions, never for type punning;
+//! - state machines operate on `unsigned char`.
+//!
+//! The generated self-test compares decoded values against canned ones
+//! numerically, and parses a frame per message whose multi-byte fields carry
+//! the bytes 0x01, 0x02, ... , so running it on a target verifies these
+//! properties there.
+
+use crate::bpir::expression::Expression;
+use crate::bpir::representation::{
+    self, FieldOrder, MessageLayout, OverflowPolicy, Protocol, SymbolDecoding, SymbolNaming,
+};
+use crate::bpir::validation::LintResult;
+use crate::error::RobustoError;
+use crate::parser_generation::artifacts;
 use crate::parser_generation::ragel::common;
 use crate::parser_generation::ragel::common::FieldBaseType;
+use crate::parser_generation::runtime;
+use crate::parser_generation::user_code;
 use crate::utility;
 use crate::utility::codegen::{self, RawCode};
 use crate::utility::codegen::{CodeChunk, CodeGeneration, SubnodeAccess, TreeBasedCodeGeneration};
 use log;
-use std::collections::LinkedList;
 use std::string::String;
 use std::vec::Vec;
 
 #[derive(Debug)]
-struct GenerationState {
-    // Current indent.
+struct ParsingFunction {
+    message_name: String,
+    symbol_naming: SymbolNaming,
+    symbol_decoding: Option<SymbolDecoding>,
+    byte_stuffing: Option<representation::ByteStuffingAttribute>,
+    max_frame_length: Option<usize>,
+    trace_message_id: Option<usize>,
+    resynchronize: bool,
+    discard_counting: bool,
+    statistics: bool,
+    callbacks: bool,
+    zero_copy: bool,
+    mixed_declarations: bool,
+    misra_c: bool,
+}
+
+impl From<&mut common::ParsingFunction> for ParsingFunction {
+    fn from(value: &mut common::ParsingFunction) -> Self {
+        ParsingFunction {
+            message_name: value.message_name.clone(),
+            symbol_naming: value.symbol_naming.clone(),
+            symbol_decoding: value.symbol_decoding.clone(),
+            byte_stuffing: value.byte_stuffing.clone(),
+            max_frame_length: value.max_frame_length,
+            trace_message_id: value.trace_message_id,
+            resynchronize: value.resynchronize,
+            discard_counting: value.discard_counting,
+            statistics: value.statistics,
+            callbacks: value.callbacks,
+            zero_copy: value.zero_copy,
+            mixed_declarations: value.mixed_declarations,
+            misra_c: value.misra_c,
+        }
+    }
+}
+
+impl ParsingFunction {
+    /// Runs the state machine. With resynchronization, the machine is
+    /// restarted on the input which caused an error, and scans it for the sync
+    /// sequence. Ragel emits labels into the function, so the machine is only
+    /// written once, and gets restarted in a loop. The bytes of the abandoned
+    /// attempt are counted as dropped, if discards are counted. Statistics
+    /// survive the restart
+    fn exec_lines(&self) -> Vec<(usize, String)> {
+        if !self.resynchronize {
+            return vec![(0, "%% write exec;".to_string())];
+        }
+
+        let mut ret = vec![(0, "for (;;) {".to_string())];
+
+        if self.zero_copy {
+            ret.push((
+                1,
+                format!("const uint32_t inputPosition = aParserState->{0};", common::INPUT_POSITION_MEMBER_NAME),
+            ));
+        }
+
+        if self.discard_counting {
+            ret.push((1, "uint32_t discardedLength;".to_string()));
+        }
+
+        if self.statistics {
+            ret.push((1, format!("struct {0} statistics;", common::STATISTICS_STRUCT_NAME)));
+        }
+
+        if self.zero_copy || self.discard_counting || self.statistics {
+            ret.push((0, String::new()));
+        }
+
+        ret.extend([
+            (1, "%% write exec;".to_string()),
+            (0, String::new()),
+            (
+                1,
+                format!(
+                    "if (aParserState->cs != {0}_error || p == pe) {{",
+                    self.message_name
+                ),
+            ),
+            (2, "break;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "// Resynchronize on the rest of the input".to_string()),
+        ]);
+        ret.extend(self.trace_call_lines().into_iter().map(|(indent, line)| (indent + 1, line)));
+
+        if self.discard_counting {
+            ret.push((
+                1,
+                format!(
+                    "discardedLength = aParserState->{0} + aParserState->{1};",
+                    common::DISCARDED_LENGTH_MEMBER_NAME,
+                    common::PENDING_LENGTH_MEMBER_NAME
+                ),
+            ));
+        }
+
+        if self.statistics {
+            ret.extend([
+                (1, format!("++aParserState->{0}.resyncs;", common::STATISTICS_MEMBER_NAME)),
+                (1, format!("statistics = aParserState->{0};", common::STATISTICS_MEMBER_NAME)),
+            ]);
+        }
+
+        ret.push((
+            1,
+            format!(
+                "{0}(aParserState);",
+                self.symbol_naming.parser_state_init_function(&self.message_name)
+            ),
+        ));
+
+        if self.discard_counting {
+            ret.push((1, format!("aParserState->{0} = discardedLength;", common::DISCARDED_LENGTH_MEMBER_NAME)));
+        }
+
+        if self.statistics {
+            ret.push((1, format!("aParserState->{0} = statistics;", common::STATISTICS_MEMBER_NAME)));
+        }
+
+        // Zero-copy offsets count from where the caller started, not from where the machine restarts
+        if self.zero_copy {
+            ret.push((1, format!("aParserState->{0} = inputPosition;", common::INPUT_POSITION_MEMBER_NAME)));
+        }
+
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+
+    fn trace_function_name(&self) -> String {
+        format!("robustoTrace{0}", self.message_name)
+    }
+
+    fn count_function_name(&self) -> String {
+        format!("robustoCount{0}", self.message_name)
+    }
+
+    fn status_function_name(&self) -> String {
+        format!("robustoParseStatus{0}", self.message_name)
+    }
+
+    /// Tells the outcome of parsing from the machine's state, and from the
+    /// error it has been rejected with
+    fn status_function_lines(&self) -> Vec<(usize, String)> {
+        if self.misra_c {
+            return self.single_exit_status_function_lines();
+        }
+
+        let mut ret = vec![
+            (
+                0,
+                format!(
+                    "static enum RobustoParseStatus {0}(const struct {1} *aParserState)",
+                    self.status_function_name(),
+                    self.symbol_naming.parser_state_struct(&self.message_name)
+                ),
+            ),
+            (0, "{".to_string()),
+            (1, format!("if (aParserState->cs >= {0}_first_final) {{", self.message_name)),
+            (2, format!("return {0};", c_parse_status_name(common::ParseStatus::Ok))),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, format!("if (aParserState->cs != {0}_error) {{", self.message_name)),
+            (2, format!("return {0};", c_parse_status_name(common::ParseStatus::Incomplete))),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, format!("switch (aParserState->{0}) {{", common::PARSE_ERROR_MEMBER_NAME)),
+        ];
+
+        for parse_error in common::ParseError::all() {
+            let parse_status = common::ParseStatus::from_error(parse_error);
+
+            if parse_status != common::ParseStatus::Error {
+                ret.push((2, format!("case {0}:", c_parse_error_name(parse_error))));
+                ret.push((3, format!("return {0};", c_parse_status_name(parse_status))));
+            }
+        }
+
+        ret.extend([
+            (2, "default:".to_string()),
+            (3, format!("return {0};", c_parse_status_name(common::ParseStatus::Error))),
+            (1, "}".to_string()),
+            (0, "}".to_string()),
+            (0, String::new()),
+        ]);
+
+        ret
+    }
+
+    /// Like `status_function_lines`, but the status is returned at the end
+    fn single_exit_status_function_lines(&self) -> Vec<(usize, String)> {
+        let assignment = |parse_status| format!("status = {0};", c_parse_status_name(parse_status));
+        let mut ret = vec![
+            (
+                0,
+                format!(
+                    "static enum RobustoParseStatus {0}(const struct {1} *aParserState)",
+                    self.status_function_name(),
+                    self.symbol_naming.parser_state_struct(&self.message_name)
+                ),
+            ),
+            (0, "{".to_string()),
+            (1, "enum RobustoParseStatus status;".to_string()),
+            (0, String::new()),
+            (1, format!("if (aParserState->cs >= {0}_first_final) {{", self.message_name)),
+            (2, assignment(common::ParseStatus::Ok)),
+            (1, format!("}} else if (aParserState->cs != {0}_error) {{", self.message_name)),
+            (2, assignment(common::ParseStatus::Incomplete)),
+            (1, "} else {".to_string()),
+            (2, format!("switch (aParserState->{0}) {{", common::PARSE_ERROR_MEMBER_NAME)),
+        ];
+
+        for parse_error in common::ParseError::all() {
+            let parse_status = common::ParseStatus::from_error(parse_error);
+
+            if parse_status != common::ParseStatus::Error {
+                ret.extend([
+                    (3, format!("case {0}:", c_parse_error_name(parse_error))),
+                    (4, assignment(parse_status)),
+                    (4, "break;".to_string()),
+                ]);
+            }
+        }
+
+        ret.extend([
+            (3, "default:".to_string()),
+            (4, assignment(common::ParseStatus::Error)),
+            (4, "break;".to_string()),
+            (2, "}".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "return status;".to_string()),
+            (0, "}".to_string()),
+            (0, String::new()),
+        ]);
+
+        ret
+    }
+
+    /// Reports the number of bytes the machine has taken, unless the caller
+    /// does not need it
+    fn consumed_length_lines(&self, value: &str) -> Vec<(usize, String)> {
+        vec![
+            (0, format!("if (aConsumedLength != {0}) {{", null_pointer(self.misra_c))),
+            (1, format!("*aConsumedLength = {0};", value)),
+            (0, "}".to_string()),
+        ]
+    }
+
+    fn return_line(&self) -> String {
+        format!("return {0}(aParserState);", self.status_function_name())
+    }
+
+    /// The function running the machine. With callbacks, the actions refer to
+    /// them, so the machine runs in the callback variant
+    fn api_function(&self) -> ApiFunction {
+        match self.callbacks {
+            true => ApiFunction::callback_parsing(&self.symbol_naming, &self.message_name),
+            false => ApiFunction::parsing(&self.symbol_naming, &self.message_name),
+        }
+    }
+
+    /// The plain parsing function, which runs the callback variant without
+    /// callbacks
+    fn callback_free_lines(&self) -> Vec<(usize, String)> {
+        let callback_parsing = ApiFunction::callback_parsing(&self.symbol_naming, &self.message_name);
+        let arguments = callback_parsing
+            .parameters
+            .iter()
+            .map(|parameter| match parameter.name.as_str() {
+                "aCallbacks" => null_pointer(self.misra_c),
+                name => name,
+            })
+            .collect::<Vec<&str>>()
+            .join(", ");
+
+        vec![
+            (0, ApiFunction::parsing(&self.symbol_naming, &self.message_name).signature()),
+            (0, "{".to_string()),
+            (1, format!("return {0}({1});", callback_parsing.name, arguments)),
+            (0, "}".to_string()),
+        ]
+    }
+
+    /// Records the frame's outcome, once the machine enters either a final or
+    /// the error state
+    fn trace_function_lines(&self, message_id: usize) -> Vec<(usize, String)> {
+        let record = |accepted: usize| {
+            format!(
+                "robustoTraceFrame({0}u, {1}u, aParserState->{2}, aParserState->{3});",
+                message_id,
+                accepted,
+                common::PARSE_ERROR_MEMBER_NAME,
+                common::TRACE_LENGTH_MEMBER_NAME
+            )
+        };
+
+        vec![
+            (
+                0,
+                format!(
+                    "static void {0}(int aPreviousCs, const struct {1} *aParserState)",
+                    self.trace_function_name(),
+                    self.symbol_naming.parser_state_struct(&self.message_name)
+                ),
+            ),
+            (0, "{".to_string()),
+            (
+                1,
+                format!(
+                    "if (aPreviousCs < {0}_first_final && aParserState->cs >= {0}_first_final) {{",
+                    self.message_name
+                ),
+            ),
+            (2, record(1)),
+            (
+                1,
+                format!(
+                    "}} else if (aPreviousCs != {0}_error && aParserState->cs == {0}_error) {{",
+                    self.message_name
+                ),
+            ),
+            (2, record(0)),
+            (1, "}".to_string()),
+            (0, "}".to_string()),
+            (0, String::new()),
+        ]
+    }
+
+    /// Counts the frame's outcome, once the machine enters either a final or
+    /// the error state. Rejections for other reasons are only seen through
+    /// `resyncs`
+    fn count_function_lines(&self) -> Vec<(usize, String)> {
+        let count = |counter: &str| format!("++aParserState->{0}.{1};", common::STATISTICS_MEMBER_NAME, counter);
+
+        vec![
+            (
+                0,
+                format!(
+                    "static void {0}(int aPreviousCs, struct {1} *aParserState)",
+                    self.count_function_name(),
+                    self.symbol_naming.parser_state_struct(&self.message_name)
+                ),
+            ),
+            (0, "{".to_string()),
+            (
+                1,
+                format!(
+                    "if (aPreviousCs < {0}_first_final && aParserState->cs >= {0}_first_final) {{",
+                    self.message_name
+                ),
+            ),
+            (2, count("framesOk")),
+            (
+                1,
+                format!(
+                    "}} else if (aPreviousCs != {0}_error && aParserState->cs == {0}_error) {{",
+                    self.message_name
+                ),
+            ),
+            (
+                2,
+                format!(
+                    "if (aParserState->{0} == {1}) {{",
+                    common::PARSE_ERROR_MEMBER_NAME,
+                    c_parse_error_name(common::ParseError::Checksum)
+                ),
+            ),
+            (3, count("crcErrors")),
+            (
+                2,
+                format!(
+                    "}} else if (aParserState->{0} == {1}) {{",
+                    common::PARSE_ERROR_MEMBER_NAME,
+                    c_parse_error_name(common::ParseError::Overflow)
+                ),
+            ),
+            (3, count("overflows")),
+            (2, "}".to_string()),
+            (1, "}".to_string()),
+            (0, "}".to_string()),
+            (0, String::new()),
+        ]
+    }
+
+    /// Records the outcome before the parsing function returns, if frames are
+    /// traced, and counts it, if the parser keeps statistics
+    fn trace_call_lines(&self) -> Vec<(usize, String)> {
+        let mut ret = Vec::new();
+
+        if self.trace_message_id.is_some() {
+            ret.push((
+                0,
+                format!("{0}(previousCs, aParserState);", self.trace_function_name()),
+            ));
+        }
+
+        if self.statistics {
+            ret.push((0, format!("{0}(previousCs, aParserState);", self.count_function_name())));
+        }
+
+        ret
+    }
+
+    /// Counts the bytes fed into the parser of a fixed-length message, and
+    /// rejects the frame as soon as it grows past the fixed length
+    fn frame_length_check_lines(&self, max_frame_length: usize) -> Vec<(usize, String)> {
+        let lines = vec![
+            (
+                0,
+                format!(
+                    "aParserState->{0} += (uint32_t)aInputBufferLength;",
+                    common::FRAME_LENGTH_MEMBER_NAME
+                ),
+            ),
+            (0, String::new()),
+            (
+                0,
+                format!(
+                    "if (aParserState->{0} > {1}u) {{",
+                    common::FRAME_LENGTH_MEMBER_NAME, max_frame_length
+                ),
+            ),
+            (
+                1,
+                format!(
+                    "aParserState->{0} = {1};",
+                    common::PARSE_ERROR_MEMBER_NAME,
+                    c_parse_error_name(common::ParseError::FrameLength)
+                ),
+            ),
+            (1, format!("aParserState->cs = {0}_error;", self.message_name)),
+        ];
+
+        // The single exit reports the length, once the input has been fed in the other branch
+        if self.misra_c {
+            return lines.into_iter().chain([(0, "} else {".to_string())]).collect();
+        }
+
+        lines
+            .into_iter()
+            .chain(self.consumed_length_lines("0").into_iter().map(|(indent, line)| (indent + 1, line)))
+        .chain(self.trace_call_lines().into_iter().map(|(indent, line)| (indent + 1, line)))
+        .chain([(1, self.return_line()), (0, "}".to_string()), (0, String::new())])
+        .collect()
+    }
+
+    /// Variables the feed of the state machine takes. The feeds decoding or
+    /// destuffing the input iterate over it, the others run the machine on it
+    fn declaration_lines(&self) -> Vec<(usize, String)> {
+        match (&self.symbol_decoding, &self.byte_stuffing) {
+            (Some(SymbolDecoding::FourBFiveB), _) if self.misra_c => vec![
+                (0, "int i;".to_string()),
+                (0, "int decodingFailed = 0;".to_string()),
+                (0, String::new()),
+            ],
+            (Some(SymbolDecoding::FourBFiveB), _) | (_, Some(_)) => vec![(0, "int i;".to_string()), (0, String::new())],
+            _ => vec![
+                (0, "const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator \"begin\" pointer -- Ragel-specific variable for C code generation".to_string()),
+                (0, "const unsigned char *pe = p + aInputBufferLength;  // Iterator \"end\" pointer -- Ragel-specific variable for C code generation".to_string()),
+            ],
+        }
+    }
+
+    /// Restarts the machine, if the caller has asked for it. Everything else
+    /// the machine needs between calls is kept in the parser state, so frames
+    /// may be fed in pieces of any length. Statistics survive the restart
+    fn guarded_init_lines(&self) -> Vec<(usize, String)> {
+        let init = (
+            1,
+            format!(
+                "{0}(aParserState);",
+                self.symbol_naming.parser_state_init_function(&self.message_name)
+            ),
+        );
+        let mut ret = vec![(0, "if (aParserState->machineInitRequired != 0) {".to_string())];
+
+        match self.statistics {
+            true => ret.extend([
+                (
+                    1,
+                    format!(
+                        "const struct {0} statistics = aParserState->{1};",
+                        common::STATISTICS_STRUCT_NAME,
+                        common::STATISTICS_MEMBER_NAME
+                    ),
+                ),
+                (0, String::new()),
+                init,
+                (1, format!("aParserState->{0} = statistics;", common::STATISTICS_MEMBER_NAME)),
+            ]),
+            false => ret.push(init),
+        }
+
+        ret.extend([(0, "}".to_string()), (0, String::new())]);
+
+        ret
+    }
+
+    /// Lines opening the function's body. Without mixed declarations and
+    /// statements, the feed's variables are declared first
+    fn opening_lines(&self) -> Vec<(usize, String)> {
+        let consumed_length = self.misra_c.then(|| (0, "int consumedLength = 0;".to_string()));
+
+        // The trace and the statistics compare against the state the machine starts from, which a pending restart resets
+        let previous_cs = (self.trace_message_id.is_some() || self.statistics).then(|| {
+            (
+                0,
+                format!(
+                    "const int previousCs = (aParserState->machineInitRequired != 0) ? {0}_start : aParserState->cs;",
+                    self.message_name
+                ),
+            )
+        });
+        let trace_length = self.trace_message_id.map(|_| {
+            (
+                0,
+                format!(
+                    "aParserState->{0} += (uint32_t)aInputBufferLength;",
+                    common::TRACE_LENGTH_MEMBER_NAME
+                ),
+            )
+        });
+        let mut ret = Vec::new();
+
+        ret.extend(consumed_length);
+        ret.extend(previous_cs);
+
+        if !self.mixed_declarations {
+            ret.extend(self.declaration_lines());
+        }
+
+        if ret.last().is_some_and(|(_, line)| !line.is_empty()) {
+            ret.push((0, String::new()));
+        }
+
+        ret.extend(self.guarded_init_lines());
+
+        if let Some(trace_length) = trace_length {
+            ret.extend([trace_length, (0, String::new())]);
+        }
+
+        ret
+    }
+
+    /// Decodes pairs of 4B/5B code groups, and feeds the state machine one
+    /// decoded byte at a time. Undecoded bits are kept in the parser state
+    /// between invocations. An invalid code group ends the loops, rather than
+    /// the function, in the MISRA-C profile
+    fn generate_four_b_five_b_feed(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<codegen::CodeChunk> {
+        let (for_condition, while_condition) = match self.misra_c {
+            true => (
+                "(i < aInputBufferLength) && (decodingFailed == 0)",
+                "(aParserState->symbolAccumulatorBits >= 10) && (decodingFailed == 0)",
+            ),
+            false => ("i < aInputBufferLength", "aParserState->symbolAccumulatorBits >= 10"),
+        };
+        let mut lines = vec![
+            (0, "// Decode 4B/5B code groups before feeding them to the state machine".to_string()),
+            (0, format!("for (i = 0; {0}; ++i) {{", for_condition)),
+            (1, "aParserState->symbolAccumulator = (aParserState->symbolAccumulator << 8) | (uint8_t)aInputBuffer[i];".to_string()),
+            (1, "aParserState->symbolAccumulatorBits += 8;".to_string()),
+            (0, String::new()),
+            (1, format!("while ({0}) {{", while_condition)),
+            (2, "uint8_t high;".to_string()),
+            (2, "uint8_t low;".to_string()),
+            (2, "unsigned char decoded;".to_string()),
+            (2, "const unsigned char *p = &decoded;  // Ragel-specific variable, points to the decoded byte".to_string()),
+            (2, "const unsigned char *pe = p + 1;  // Ragel-specific variable".to_string()),
+            (0, String::new()),
+            (2, "aParserState->symbolAccumulatorBits -= 10;".to_string()),
+            (2, "high = robustoDecode4b5b((uint8_t)(aParserState->symbolAccumulator >> (aParserState->symbolAccumulatorBits + 5)));".to_string()),
+            (2, "low = robustoDecode4b5b((uint8_t)(aParserState->symbolAccumulator >> aParserState->symbolAccumulatorBits));".to_string()),
+            (0, String::new()),
+            (2, "if (high > 0xF || low > 0xF) {".to_string()),
+            (3, "// Invalid code group".to_string()),
+            (3, format!("aParserState->cs = {0}_error;", self.message_name)),
+        ];
+
+        if self.misra_c {
+            lines.extend([
+                (3, "consumedLength = i;".to_string()),
+                (3, "decodingFailed = 1;".to_string()),
+                (2, "} else {".to_string()),
+                (3, "decoded = (unsigned char)((high << 4) | low);".to_string()),
+            ]);
+            lines.extend(self.exec_lines().into_iter().map(|(indent, line)| (indent + 3, line)));
+            lines.push((2, "}".to_string()));
+        } else {
+            lines.extend(self.consumed_length_lines("i").into_iter().map(|(indent, line)| (indent + 3, line)));
+            lines.extend(self.trace_call_lines().into_iter().map(|(indent, line)| (indent + 3, line)));
+            lines.extend([
+                (3, self.return_line()),
+                (2, "}".to_string()),
+                (0, String::new()),
+                (2, "decoded = (unsigned char)((high << 4) | low);".to_string()),
+            ]);
+            lines.extend(self.exec_lines().into_iter().map(|(indent, line)| (indent + 2, line)));
+        }
+
+        lines.extend([(1, "}".to_string()), (0, "}".to_string())]);
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+
+    /// Removes escape sequences, and feeds the state machine the restored
+    /// bytes one by one. An escape byte ending the buffer is remembered in the
+    /// parser state
+    fn generate_destuffing_feed(
+        &self,
+        byte_stuffing: &representation::ByteStuffingAttribute,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<codegen::CodeChunk> {
+        let escape_pending = format!("aParserState->{0}", common::ESCAPE_PENDING_MEMBER_NAME);
+        let mut lines = vec![
+            (0, "// Remove escape sequences before feeding bytes to the state machine".to_string()),
+            (0, "for (i = 0; i < aInputBufferLength; ++i) {".to_string()),
+            (1, "unsigned char destuffed = (unsigned char)aInputBuffer[i];".to_string()),
+            (1, "const unsigned char *p = &destuffed;  // Ragel-specific variable, points to the destuffed byte".to_string()),
+            (1, "const unsigned char *pe = p + 1;  // Ragel-specific variable".to_string()),
+            (0, String::new()),
+            (
+                1,
+                match self.misra_c {
+                    true => format!("if ({0} != 0) {{", escape_pending),
+                    false => format!("if ({0}) {{", escape_pending),
+                },
+            ),
+            (2, format!("{0} = 0;", escape_pending)),
+            (2, format!("destuffed = (unsigned char)(destuffed ^ 0x{0:02X}u);", byte_stuffing.xor)),
+            (1, format!("}} else if (destuffed == 0x{0:02X}u) {{", byte_stuffing.escape)),
+            (2, format!("{0} = 1;", escape_pending)),
+            (2, "continue;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+        ];
+        lines.extend(self.exec_lines().into_iter().map(|(indent, line)| (indent + 1, line)));
+        lines.push((0, "}".to_string()));
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Converts `(relative indent, line)` pairs into code chunks. Empty lines are
+/// not indented.
+pub fn indented_lines(
+    lines: impl Iterator<Item = (usize, String)>,
     indent: usize,
+) -> Vec<codegen::CodeChunk> {
+    lines
+        .map(|(line_indent, line)| {
+            let line_indent = if line.is_empty() { 0 } else { indent + line_indent };
+            codegen::CodeChunk::new(line, line_indent, 1usize)
+        })
+        .collect()
+}
+
+/// The null pointer constant, spelled `NULL` in the MISRA-C profile
+fn null_pointer(misra_c: bool) -> &'static str {
+    match misra_c {
+        true => "NULL",
+        false => "0",
+    }
+}
+
+/// Symbol decoding routines. Bytewise transforms are wrapped into
+/// `robustoDecodeSymbol()`, which the state machine invokes through `getkey`.
+#[derive(Debug)]
+struct SymbolDecoder {
+    symbol_decoding: SymbolDecoding,
+}
+
+impl SymbolDecoder {
+    /// Maps 5-bit code groups onto nibbles. Invalid code groups map onto 0xFF
+    fn four_b_five_b_decoding_table() -> [u8; 32] {
+        let mut ret = [0xFFu8; 32];
+
+        for (nibble, code_group) in common::FOUR_B_FIVE_B_CODE_GROUPS.iter().enumerate() {
+            ret[*code_group as usize] = nibble as u8;
+        }
+
+        ret
+    }
+
+    fn lines(&self) -> Vec<(usize, String)> {
+        let mut ret = Vec::new();
+
+        if let SymbolDecoding::FourBFiveB = self.symbol_decoding {
+            let table = SymbolDecoder::four_b_five_b_decoding_table()
+                .iter()
+                .map(|nibble| format!("0x{0:02X}", nibble))
+                .collect::<Vec<String>>();
+            ret.push((0, "static uint8_t robustoDecode4b5b(uint8_t aCodeGroup)".to_string()));
+            ret.push((0, "{".to_string()));
+            ret.push((1, "static const uint8_t kDecodingTable[32] = {".to_string()));
+
+            for chunk in table.chunks(8) {
+                ret.push((2, format!("{0},", chunk.join(", "))));
+            }
+
+            ret.push((1, "};".to_string()));
+            ret.push((0, String::new()));
+            ret.push((1, "return kDecodingTable[aCodeGroup & 0x1Fu];".to_string()));
+            ret.push((0, "}".to_string()));
+
+            return ret;
+        }
+
+        if let SymbolDecoding::UserHook(ref hook) = self.symbol_decoding {
+            ret.push((0, format!("uint8_t {0}(uint8_t aSymbol);", hook)));
+        }
+
+        ret.push((0, "static uint8_t robustoDecodeSymbol(uint8_t aSymbol)".to_string()));
+        ret.push((0, "{".to_string()));
+
+        match self.symbol_decoding {
+            SymbolDecoding::NibbleSwap => {
+                ret.push((1, "return (uint8_t)((aSymbol << 4) | (aSymbol >> 4));".to_string()));
+            }
+            SymbolDecoding::BitReversal => {
+                ret.push((1, "aSymbol = (uint8_t)(((aSymbol & 0xF0u) >> 4) | ((aSymbol & 0x0Fu) << 4));".to_string()));
+                ret.push((1, "aSymbol = (uint8_t)(((aSymbol & 0xCCu) >> 2) | ((aSymbol & 0x33u) << 2));".to_string()));
+                ret.push((1, "aSymbol = (uint8_t)(((aSymbol & 0xAAu) >> 1) | ((aSymbol & 0x55u) << 1));".to_string()));
+                ret.push((0, String::new()));
+                ret.push((1, "return aSymbol;".to_string()));
+            }
+            SymbolDecoding::Xor(mask) => {
+                ret.push((1, format!("return (uint8_t)(aSymbol ^ 0x{0:02X}u);", mask)));
+            }
+            SymbolDecoding::UserHook(ref hook) => {
+                ret.push((1, format!("return {0}(aSymbol);", hook)));
+            }
+            SymbolDecoding::FourBFiveB => {}
+        }
+
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for SymbolDecoder {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+#[derive(Debug)]
+pub struct MessageStruct {
+    pub message_name: std::string::String,
+    pub symbol_naming: SymbolNaming,
+}
+
+impl codegen::TreeBasedCodeGeneration for MessageStruct {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+
+        // Generate struct header
+        ret.push(CodeChunk::new(
+            format!("struct {0} {{", self.symbol_naming.message_struct(&self.message_name)),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        code_generation_state.indent += 1;
+
+        ret
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        code_generation_state.indent -= 1;
+
+        // Close the bracket
+        ret.push(CodeChunk::new(
+            "};".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+/// Doxygen comment preceding a declaration
+#[derive(Debug)]
+struct DocComment {
+    text: String,
+
+    /// Documents the whole file rather than the next declaration
+    is_file: bool,
+}
+
+impl DocComment {
+    fn lines(&self) -> Vec<(usize, String)> {
+        // A description must not terminate the comment
+        let text = self.text.trim().replace("*/", "* /");
+        let mut lines = text.lines().map(str::trim_end).collect::<Vec<&str>>();
+
+        if self.is_file {
+            lines.insert(0, "@file");
+        }
+
+        match lines.as_slice() {
+            [line] => vec![(0, format!("/** {0} */", line))],
+            _ => std::iter::once((0, "/**".to_string()))
+                .chain(lines.iter().map(|line| match line.is_empty() {
+                    true => (0, " *".to_string()),
+                    false => (0, format!(" * {0}", line)),
+                }))
+                .chain(std::iter::once((0, " */".to_string())))
+                .collect(),
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for DocComment {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Includes the runtime library's header, and checks that the library is of
+/// the version the code was generated against
+#[derive(Debug)]
+struct RuntimeInclude {}
+
+impl codegen::TreeBasedCodeGeneration for RuntimeInclude {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = vec![CodeChunk::new(
+            format!("#include \"{0}\"", runtime::HEADER_FILE_NAME),
+            code_generation_state.indent,
+            1usize,
+        )
+        .with_fragment(Some(&codegen::Fragment::Includes))];
+        let lines = vec![
+            (0, String::new()),
+            (0, format!("#if {0} != {1}", runtime::VERSION_MACRO_NAME, runtime::VERSION)),
+            (
+                0,
+                format!(
+                    "#error \"The generated code requires version {0} of the Robusto runtime library\"",
+                    runtime::VERSION
+                ),
+            ),
+            (0, "#endif".to_string()),
+        ];
+        ret.append(&mut indented_lines(lines.into_iter(), code_generation_state.indent));
+
+        ret
+    }
+}
+
+/// Includes the standard headers the generated types require
+#[derive(Debug)]
+struct StandardIncludes {
+    /// Serializers take the capacity of their buffers as `size_t`, and the
+    /// MISRA-C profile spells null pointers `NULL`
+    sizes: bool,
+
+    /// Structs are cleared, and compared, by `memset()` and `memcmp()`
+    memory: bool,
+}
+
+impl codegen::TreeBasedCodeGeneration for StandardIncludes {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut headers = vec!["stdint.h"];
+
+        if self.sizes {
+            headers.insert(0, "stddef.h");
+        }
+
+        if self.memory {
+            headers.push("string.h");
+        }
+
+        headers
+            .into_iter()
+            .map(|header| {
+                CodeChunk::new(format!("#include <{0}>", header), code_generation_state.indent, 1usize)
+                    .with_fragment(Some(&codegen::Fragment::Includes))
+            })
+            .collect()
+    }
+}
+
+/// Gives the declarations it wraps C linkage, so C++ code may include the
+/// header directly
+#[derive(Debug)]
+pub struct CppLinkage {}
+
+impl codegen::TreeBasedCodeGeneration for CppLinkage {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let lines = [
+            (0, String::new()),
+            (0, "#ifdef __cplusplus".to_string()),
+            (0, "extern \"C\" {".to_string()),
+            (0, "#endif".to_string()),
+        ];
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let lines = [
+            (0, String::new()),
+            (0, "#ifdef __cplusplus".to_string()),
+            (0, "}  // extern \"C\"".to_string()),
+            (0, "#endif".to_string()),
+        ];
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Guards a header against being included twice
+#[derive(Debug)]
+pub struct HeaderGuard {
+    include_guard: representation::IncludeGuard,
+    macro_name: String,
+}
+
+impl HeaderGuard {
+    pub fn new(include_guard: &representation::IncludeGuard, file_name: &str, is_split: bool) -> HeaderGuard {
+        let file_macro_name = file_name
+            .chars()
+            .map(|character| match character.is_ascii_alphanumeric() {
+                true => character.to_ascii_uppercase(),
+                false => '_',
+            })
+            .collect::<String>();
+        let macro_name = match include_guard {
+            representation::IncludeGuard::Macro(Some(name)) if is_split => format!("{0}_{1}", name, file_macro_name),
+            representation::IncludeGuard::Macro(Some(name)) => name.clone(),
+            _ => file_macro_name,
+        };
+
+        HeaderGuard {
+            include_guard: include_guard.clone(),
+            macro_name,
+        }
+    }
+
+    pub fn open(&self, sink: &mut dyn codegen::CodeChunkSink) -> Result<(), RobustoError> {
+        match self.include_guard {
+            representation::IncludeGuard::Macro(_) => {
+                sink.accept(CodeChunk::new(format!("#ifndef {0}", self.macro_name), 0, 1))?;
+                sink.accept(CodeChunk::new(format!("#define {0}", self.macro_name), 0, 2))
+            }
+            representation::IncludeGuard::PragmaOnce => sink.accept(CodeChunk::new("#pragma once".to_string(), 0, 2)),
+        }
+    }
+
+    pub fn close(&self, sink: &mut dyn codegen::CodeChunkSink) -> Result<(), RobustoError> {
+        match self.include_guard {
+            representation::IncludeGuard::Macro(_) => {
+                sink.accept(CodeChunk::new(String::new(), 0, 1))?;
+                sink.accept(CodeChunk::new(format!("#endif  // {0}", self.macro_name), 0, 1))
+            }
+            representation::IncludeGuard::PragmaOnce => Ok(()),
+        }
+    }
+}
+
+/// Maps a base type onto its `<stdint.h>` counterpart, or onto a
+/// floating-point type
+fn c_type_name(field_base_type: &FieldBaseType) -> String {
+    match field_base_type {
+        FieldBaseType::I8 => "int8_t".to_string(),
+        FieldBaseType::I16 => "int16_t".to_string(),
+        FieldBaseType::I32 => "int32_t".to_string(),
+        FieldBaseType::I64 => "int64_t".to_string(),
+        FieldBaseType::U8 => "uint8_t".to_string(),
+        FieldBaseType::U16 => "uint16_t".to_string(),
+        FieldBaseType::U32 => "uint32_t".to_string(),
+        FieldBaseType::U64 => "uint64_t".to_string(),
+        FieldBaseType::F32 => "float".to_string(),
+        FieldBaseType::F64 => "double".to_string(),
+        FieldBaseType::Struct(ref name) => format!("struct {0}", name),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MessageStructMember {
+    pub name: std::string::String,
+    pub field_base_type: FieldBaseType,
+
+    /// If 0, it is considered just a field
+    pub array_length: usize,
+}
+
+impl From<&mut common::MessageStructMember> for MessageStructMember {
+    fn from(value: &mut common::MessageStructMember) -> Self {
+        MessageStructMember {
+            name: value.name.clone(),
+            field_base_type: value.field_base_type.clone(),
+            array_length: value.array_length,
+        }
+    }
+}
+
+impl TreeBasedCodeGeneration for MessageStructMember {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        log::debug!("indent: {0}", code_generation_state.indent);
+
+        ret.push(CodeChunk::new(
+            self.declaration(),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+impl MessageStructMember {
+    pub fn is_array(&self) -> bool {
+        self.array_length > 0
+    }
+
+    /// Formatted C representation of the member
+    pub fn declaration(&self) -> String {
+        format!(
+            "{0} {1}{2};",
+            c_type_name(&self.field_base_type),
+            self.name,
+            {
+                if self.array_length == 0usize {
+                    std::string::String::from("")
+                } else {
+                    format!("[{}]", self.array_length)
+                }
+            }
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ParserStateStruct {
+    machine_name: String,
+    symbol_naming: SymbolNaming,
+}
+
+impl ParserStateStruct {
+    /// Members every parser state starts with, regardless of the message
+    fn ragel_member_declarations() -> [&'static str; 2] {
+        ["int machineInitRequired;", "int cs;"]
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for ParserStateStruct {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<codegen::CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        ret.push(CodeChunk::new(
+            format!("struct {0} {{", self.symbol_naming.parser_state_struct(&self.machine_name)),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret.push(CodeChunk::new(
+            "// Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off".to_string(),
+            code_generation_state.indent + 1,
+            1usize,
+        ));
+
+        for declaration in ParserStateStruct::ragel_member_declarations() {
+            ret.push(CodeChunk::new(
+                declaration.to_string(),
+                code_generation_state.indent + 1,
+                1usize,
+            ));
+        }
+
+        code_generation_state.indent += 1;
+
+        ret
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<codegen::CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        code_generation_state.indent -= 1;
+        ret.push(CodeChunk::new(
+            "};".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+/// Callbacks the message's parser invokes, along with a pointer of the caller's
+#[derive(Clone, Debug)]
+struct CallbacksStruct {
+    message_name: String,
+    symbol_naming: SymbolNaming,
+}
+
+impl CallbacksStruct {
+    fn callback_name(message_name: &str) -> String {
+        format!("on{0}Parsed", message_name)
+    }
+
+    fn members(&self) -> Vec<String> {
+        vec![
+            "void *context;".to_string(),
+            format!(
+                "void (*{0})(const struct {1} *a{2}, void *aContext);",
+                CallbacksStruct::callback_name(&self.message_name),
+                self.symbol_naming.message_struct(&self.message_name),
+                self.message_name
+            ),
+        ]
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for CallbacksStruct {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = vec![
+            (0, String::new()),
+            (0, "// The callback is invoked on each byte completing a frame, and may be NULL".to_string()),
+            (0, format!("struct {0} {{", self.symbol_naming.callbacks_struct(&self.message_name))),
+        ];
+        lines.extend(self.members().into_iter().map(|member| (1, member)));
+        lines.push((0, "};".to_string()));
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParserStateInitFunction {
+    pub machine_name: String,
+    pub symbol_naming: SymbolNaming,
+}
+
+impl codegen::TreeBasedCodeGeneration for ParserStateInitFunction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        ret.push(CodeChunk::new(
+            ApiFunction::parser_state_init(&self.symbol_naming, &self.machine_name).signature(),
+            code_generation_state.indent,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            "{".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+        code_generation_state.indent += 1usize;
+        ret.push(CodeChunk::new(
+            "aParserState->machineInitRequired = 0;".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            "aParserState->cs = 0;".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        code_generation_state.indent -= 1usize;
+        vec![CodeChunk::new(
+            "}".to_string(),
+            code_generation_state.indent,
+            1usize,
+        )]
+    }
+}
+
+impl From<&mut common::ParserStateInitFunction> for ParserStateInitFunction {
+    fn from(value: &mut common::ParserStateInitFunction) -> Self {
+        ParserStateInitFunction {
+            machine_name: value.machine_name.clone(),
+            symbol_naming: value.symbol_naming.clone(),
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for ParsingFunction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<codegen::CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+
+        if let Some(message_id) = self.trace_message_id {
+            ret.append(&mut indented_lines(
+                self.trace_function_lines(message_id).into_iter(),
+                code_generation_state.indent,
+            ));
+        }
+
+        if self.statistics {
+            ret.append(&mut indented_lines(
+                self.count_function_lines().into_iter(),
+                code_generation_state.indent,
+            ));
+        }
+
+        ret.append(&mut indented_lines(
+            self.status_function_lines().into_iter(),
+            code_generation_state.indent,
+        ));
+        ret.push(
+            codegen::CodeChunk::new(
+                self.api_function().signature(),
+                code_generation_state.indent,
+                1usize,
+            )
+            .with_fragment(Some(&codegen::Fragment::ParsingFunctionSignature)),
+        );
+        ret.push(codegen::CodeChunk::new(
+            "{".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+        code_generation_state.indent += 1usize;
+
+        ret.append(&mut indented_lines(
+            self.opening_lines().into_iter(),
+            code_generation_state.indent,
+        ));
+
+        if let Some(max_frame_length) = self.max_frame_length {
+            ret.append(&mut indented_lines(
+                self.frame_length_check_lines(max_frame_length).into_iter(),
+                code_generation_state.indent,
+            ));
+        }
+
+        // In the MISRA-C profile, the input is fed in the branch of the frame length check which accepts it
+        let feed_nested = self.misra_c && self.max_frame_length.is_some();
+
+        if feed_nested {
+            code_generation_state.indent += 1usize;
+        }
+
+        if self.mixed_declarations {
+            ret.append(&mut indented_lines(
+                self.declaration_lines().into_iter(),
+                code_generation_state.indent,
+            ));
+        }
+
+        // Decoded symbols and escape sequences are buffered in the parser state, so the feeds take the whole input
+        let consumed_length = if let Some(SymbolDecoding::FourBFiveB) = self.symbol_decoding {
+            ret.append(&mut self.generate_four_b_five_b_feed(code_generation_state));
+            "aInputBufferLength"
+        } else if let Some(ref byte_stuffing) = self.byte_stuffing {
+            ret.append(&mut self.generate_destuffing_feed(byte_stuffing, code_generation_state));
+            "aInputBufferLength"
+        } else {
+            ret.push(codegen::CodeChunk::new(
+                "// Parse starting from the state defined in `aParserState`".to_string(),
+                code_generation_state.indent,
+                1usize,
+            ));
+            ret.append(&mut indented_lines(
+                self.exec_lines().into_iter(),
+                code_generation_state.indent,
+            ));
+
+            if self.zero_copy {
+                ret.push(codegen::CodeChunk::new(String::new(), 0, 1usize));
+                ret.push(codegen::CodeChunk::new(
+                    format!(
+                        "aParserState->{0} += (uint32_t)(p - (const unsigned char *)aInputBuffer);",
+                        common::INPUT_POSITION_MEMBER_NAME
+                    ),
+                    code_generation_state.indent,
+                    1usize,
+                ));
+            }
+
+            "(int)(p - (const unsigned char *)aInputBuffer)"
+        };
+
+        // The length is kept until the single exit reports it. An invalid 4B/5B code group has set it already
+        let consumed_length = if self.misra_c {
+            let assignment = format!("consumedLength = {0};", consumed_length);
+            let lines = match self.symbol_decoding {
+                Some(SymbolDecoding::FourBFiveB) => vec![
+                    (0, "if (decodingFailed == 0) {".to_string()),
+                    (1, assignment),
+                    (0, "}".to_string()),
+                ],
+                _ => vec![(0, assignment)],
+            };
+            ret.push(codegen::CodeChunk::new(String::new(), 0, 1usize));
+            ret.append(&mut indented_lines(lines.into_iter(), code_generation_state.indent));
+
+            "consumedLength"
+        } else {
+            consumed_length
+        };
+
+        if feed_nested {
+            code_generation_state.indent -= 1usize;
+            ret.push(codegen::CodeChunk::new(
+                "}".to_string(),
+                code_generation_state.indent,
+                1usize,
+            ));
+        }
+
+        ret.push(codegen::CodeChunk::new(String::new(), 0, 1usize));
+        ret.append(&mut indented_lines(
+            self.consumed_length_lines(consumed_length)
+                .into_iter()
+                .chain([(0, String::new())])
+                .chain(self.trace_call_lines())
+                .chain([(0, self.return_line())]),
+            code_generation_state.indent,
+        ));
+
+        code_generation_state.indent -= 1usize;
+        ret.push(codegen::CodeChunk::new(
+            "}".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        if self.callbacks {
+            ret.append(&mut indented_lines(
+                self.callback_free_lines().into_iter(),
+                code_generation_state.indent,
+            ));
+        }
+
+        // Ragel's state constants are static, so other translation units check acceptance through a function
+        ret.append(&mut indented_lines(
+            [
+                (0, ApiFunction::acceptance(&self.symbol_naming, &self.message_name).signature()),
+                (0, "{".to_string()),
+                (
+                    1,
+                    match self.misra_c {
+                        true => format!("return (aParserState->cs >= {0}_first_final) ? 1 : 0;", self.message_name),
+                        false => format!("return aParserState->cs >= {0}_first_final;", self.message_name),
+                    },
+                ),
+                (0, "}".to_string()),
+            ]
+            .into_iter(),
+            code_generation_state.indent,
+        ));
+
+        if self.statistics {
+            ret.append(&mut indented_lines(
+                [
+                    (0, ApiFunction::statistics_accessor(&self.symbol_naming, &self.message_name).signature()),
+                    (0, "{".to_string()),
+                    (1, format!("return aParserState->{0};", common::STATISTICS_MEMBER_NAME)),
+                    (0, "}".to_string()),
+                ]
+                .into_iter(),
+                code_generation_state.indent,
+            ));
+        }
+
+        ret
+    }
+}
+
+/// Clears the presence bitmap of an unordered message
+#[derive(Debug)]
+struct PresenceBitmapReset {
+    message_name: String,
+}
+
+impl From<&mut common::PresenceBitmapReset> for PresenceBitmapReset {
+    fn from(value: &mut common::PresenceBitmapReset) -> Self {
+        PresenceBitmapReset {
+            message_name: value.message_name.clone(),
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for PresenceBitmapReset {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        ret.push(CodeChunk::new(
+            format!(
+                "a{0}->{1} = 0;",
+                self.message_name,
+                common::PRESENCE_BITMAP_MEMBER_NAME
+            ),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+/// Invokes the message's callback on an accepted frame, if the caller has set it
+#[derive(Debug)]
+struct ParsedCallback {
+    message_name: String,
+    misra_c: bool,
+}
+
+impl From<&mut common::ParsedCallback> for ParsedCallback {
+    fn from(value: &mut common::ParsedCallback) -> Self {
+        ParsedCallback {
+            message_name: value.message_name.clone(),
+            misra_c: value.misra_c,
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for ParsedCallback {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let callback = CallbacksStruct::callback_name(&self.message_name);
+        let lines = vec![
+            (
+                0,
+                format!(
+                    "if (aCallbacks != {0} && aCallbacks->{1} != {0}) {{",
+                    null_pointer(self.misra_c),
+                    callback
+                ),
+            ),
+            (1, format!("aCallbacks->{0}(a{1}, aCallbacks->context);", callback, self.message_name)),
+            (0, "}".to_string()),
+        ];
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Sets the field's bit in the presence bitmap of an unordered message, or
+/// switches the machine into the error state, if the field is duplicated
+#[derive(Debug)]
+struct PresenceBitmapUpdate {
+    message_name: String,
+    field_name: String,
+    field_index: usize,
+}
+
+impl From<&mut common::PresenceBitmapUpdate> for PresenceBitmapUpdate {
+    fn from(value: &mut common::PresenceBitmapUpdate) -> Self {
+        PresenceBitmapUpdate {
+            message_name: value.message_name.clone(),
+            field_name: value.field_name.clone(),
+            field_index: value.field_index,
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for PresenceBitmapUpdate {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        let bitmap = format!(
+            "a{0}->{1}",
+            self.message_name,
+            common::PRESENCE_BITMAP_MEMBER_NAME
+        );
+        ret.push(CodeChunk::new(
+            format!("if ({0} & (1UL << {1})) {{", bitmap, self.field_index),
+            code_generation_state.indent,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            format!("// Duplicate field \"{0}\"", self.field_name),
+            code_generation_state.indent + 1,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            format!("fgoto *{0}_error;", self.message_name),
+            code_generation_state.indent + 1,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            "}".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            format!("{0} |= (1UL << {1});", bitmap, self.field_index),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+/// Name of the routine reading a field of the given bit order
+fn bit_reader_name(bit_order: representation::BitOrder) -> &'static str {
+    match bit_order {
+        representation::BitOrder::MsbFirst => "robustoReadBits",
+        representation::BitOrder::LsbFirst => "robustoReadBitsLsbFirst",
+    }
+}
+
+/// Bit reader routines, one per bit order the parsed messages employ
+#[derive(Debug)]
+struct BitReader {
+    bit_orders: Vec<representation::BitOrder>,
+}
+
+impl codegen::TreeBasedCodeGeneration for BitReader {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        let indent = code_generation_state.indent;
+        let mut lines = Vec::new();
+
+        for bit_order in &self.bit_orders {
+            lines.push((
+                0,
+                format!(
+                    "static uint64_t {0}(const uint8_t *aBuffer, unsigned long aBitOffset, unsigned int aBitWidth)",
+                    bit_reader_name(*bit_order)
+                ),
+            ));
+            lines.extend([
+                (0, "{".to_string()),
+                (1, "uint64_t value = 0;".to_string()),
+                (1, "unsigned int i;".to_string()),
+                (0, String::new()),
+                (1, "for (i = 0; i < aBitWidth; ++i) {".to_string()),
+                (2, "unsigned long bit = aBitOffset + i;".to_string()),
+            ]);
+            lines.push((
+                2,
+                match bit_order {
+                    representation::BitOrder::MsbFirst => {
+                        "value = (value << 1) | ((aBuffer[bit / 8] >> (7 - (bit % 8))) & 1u);"
+                    }
+                    representation::BitOrder::LsbFirst => {
+                        "value |= (uint64_t)((aBuffer[bit / 8] >> (bit % 8)) & 1u) << i;"
+                    }
+                }
+                .to_string(),
+            ));
+            lines.extend([
+                (1, "}".to_string()),
+                (0, String::new()),
+                (1, "return value;".to_string()),
+                (0, "}".to_string()),
+            ]);
+        }
+
+        lines.extend([
+            (0, "static int64_t robustoSignExtend(uint64_t aValue, unsigned int aBitWidth)".to_string()),
+            (0, "{".to_string()),
+            (1, "uint64_t signBit = (uint64_t)1 << (aBitWidth - 1);".to_string()),
+            (0, String::new()),
+            (1, "return (int64_t)((aValue ^ signBit) - signBit);".to_string()),
+            (0, "}".to_string()),
+        ]);
+
+        for (line_indent, line) in lines {
+            ret.push(CodeChunk::new(line, indent + line_indent, 1usize));
+        }
+
+        ret
+    }
+}
+
+/// Composes a frame out of a message struct. Returns the length of the frame,
+/// or -1, if the buffer may be too short for the longest frame of the message
+#[derive(Debug)]
+struct SerializingFunction {
+    message_name: String,
+    symbol_naming: SymbolNaming,
+    max_length: usize,
+    marks: Vec<String>,
+    steps: Vec<common::SerializerStep>,
+    misra_c: bool,
+}
+
+impl From<&mut common::Serializer> for SerializingFunction {
+    fn from(value: &mut common::Serializer) -> Self {
+        SerializingFunction {
+            message_name: value.message_name.clone(),
+            symbol_naming: value.symbol_naming.clone(),
+            max_length: value.max_length,
+            marks: value.marks.clone(),
+            steps: value.steps.clone(),
+            misra_c: value.misra_c,
+        }
+    }
+}
+
+impl SerializingFunction {
+    /// The member a step reads. Group members are read from the repetition `i`
+    fn member(&self, member: &common::SerializedMember) -> String {
+        match member.container {
+            common::FieldContainer::Group(ref group_field_name) => format!(
+                "a{0}->{1}[i].{2}",
+                self.message_name, group_field_name, member.field_name
+            ),
+            _ => field_member(&self.message_name, &member.field_name, &member.container),
+        }
+    }
+
+    /// Writes the value big-endian, at the offset, or at the end of the frame
+    fn big_endian_lines(value: &str, bit_width: usize, offset: Option<&str>) -> Vec<(usize, String)> {
+        (0..bit_width.div_ceil(8))
+            .rev()
+            .enumerate()
+            .map(|(i, byte)| {
+                let target = match offset {
+                    Some(offset) if i == 0 => format!("aBuffer[{0}]", offset),
+                    Some(offset) => format!("aBuffer[{0} + {1}u]", offset, i),
+                    None => "aBuffer[length++]".to_string(),
+                };
+
+                match byte {
+                    0 => (0, format!("{0} = (uint8_t){1};", target, value)),
+                    _ => (0, format!("{0} = (uint8_t)({1} >> {2});", target, value, byte * 8)),
+                }
+            })
+            .collect()
+    }
+
+    fn varint_lines(&self, member: &common::SerializedMember, varint: &representation::VarintFieldType) -> Vec<(usize, String)> {
+        let member_name = self.member(member);
+        let unsigned_base_type = member.field_base_type.to_unsigned();
+        let unsigned_type = c_type_name(&unsigned_base_type);
+        let value = match (varint.signed, varint.zigzag) {
+            (true, true) => format!(
+                "{0} < 0 ? ~(({1}){0} << 1) : ({1}){0} << 1",
+                member_name, unsigned_type
+            ),
+            _ => format!("({0}){1}", unsigned_type, member_name),
+        };
+        let mut ret = vec![
+            (0, "{".to_string()),
+            (1, format!("{0} value = {1};", unsigned_type, value)),
+            (0, String::new()),
+        ];
+
+        if ![8usize, 16, 32, 64].contains(&varint.bit_width) {
+            ret.push((
+                1,
+                format!(
+                    "value &= {0};",
+                    c_hex_literal(u64::MAX >> (64 - varint.bit_width), &unsigned_base_type)
+                ),
+            ));
+            ret.push((0, String::new()));
+        }
+
+        ret.extend([
+            (1, "do {".to_string()),
+            (2, "aBuffer[length] = (uint8_t)(value & 0x7Fu);".to_string()),
+            (2, "value >>= 7;".to_string()),
+            (0, String::new()),
+            (2, "if (value != 0) {".to_string()),
+            (3, "aBuffer[length] |= 0x80u;".to_string()),
+            (2, "}".to_string()),
+            (0, String::new()),
+            (2, "++length;".to_string()),
+            (1, "} while (value != 0);".to_string()),
+            (0, "}".to_string()),
+        ]);
+
+        ret
+    }
+
+    fn checksum_lines(
+        start: &str,
+        stop: &str,
+        algorithm: &representation::CrcAlgorithm,
+        bit_width: usize,
+    ) -> Vec<(usize, String)> {
+        let register = match algorithm.xor_out {
+            0 => "crc".to_string(),
+            xor_out => crc_register_cast(algorithm, &format!("crc ^ {0}", crc_literal(algorithm, xor_out))),
+        };
+        let mut ret = vec![
+            (0, "{".to_string()),
+            (
+                1,
+                format!(
+                    "{0} crc = {1};",
+                    c_type_name(&crc_register_type(algorithm)),
+                    crc_literal(algorithm, algorithm.init)
+                ),
+            ),
+            (0, String::new()),
+            (1, format!("for (i = {0}; i < {1}; ++i) {{", start, stop)),
+            (2, format!("crc = {0}(crc, aBuffer[i]);", crc_routine_name(algorithm))),
+            (1, "}".to_string()),
+            (0, String::new()),
+        ];
+
+        if register != "crc" {
+            ret.push((1, format!("crc = {0};", register)));
+        }
+
+        ret.extend(
+            SerializingFunction::big_endian_lines("crc", bit_width, None)
+                .into_iter()
+                .map(|(indent, line)| (indent + 1, line)),
+        );
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+
+    fn step_lines(&self, step: &common::SerializerStep) -> Vec<(usize, String)> {
+        match step {
+            common::SerializerStep::Constant(bytes) => bytes
+                .iter()
+                .map(|byte| (0, format!("aBuffer[length++] = 0x{0:02X}u;", byte)))
+                .collect(),
+            common::SerializerStep::Zeros(length) => {
+                vec![(0, "aBuffer[length++] = 0x00u;".to_string()); *length]
+            }
+            common::SerializerStep::Integer { member, bit_width } => {
+                // A single byte is cast once
+                let value = match *bit_width > 8 {
+                    true => format!(
+                        "({0}){1}",
+                        c_type_name(&member.field_base_type.to_unsigned()),
+                        self.member(member)
+                    ),
+                    false => self.member(member),
+                };
+
+                SerializingFunction::big_endian_lines(&value, *bit_width, None)
+            }
+            common::SerializerStep::Varint { member, varint } => self.varint_lines(member, varint),
+            common::SerializerStep::Pattern {
+                member,
+                length,
+                max_length,
+            } => vec![
+                (0, "{".to_string()),
+                (1, "size_t j;".to_string()),
+                (0, String::new()),
+                (
+                    1,
+                    format!(
+                        "for (j = 0; j < {0} && j < {1}u; ++j) {{",
+                        self.member(length),
+                        max_length
+                    ),
+                ),
+                (2, format!("aBuffer[length++] = {0}[j];", self.member(member))),
+                (1, "}".to_string()),
+                (0, "}".to_string()),
+            ],
+            common::SerializerStep::Mark(name) => vec![(0, format!("{0} = length;", name))],
+            common::SerializerStep::Length {
+                offset,
+                start,
+                end,
+                bit_width,
+            } => SerializingFunction::big_endian_lines(&format!("({0} - {1})", end, start), *bit_width, Some(offset)),
+            common::SerializerStep::Checksum {
+                start,
+                stop,
+                algorithm,
+                bit_width,
+            } => SerializingFunction::checksum_lines(start, stop, algorithm, *bit_width),
+            common::SerializerStep::Conditional { selector, value, steps } => {
+                let mut ret = vec![(0, format!("if ({0} == {1}) {{", self.member(selector), value))];
+                ret.extend(self.nested_lines(steps));
+                ret.push((0, "}".to_string()));
+
+                ret
+            }
+            common::SerializerStep::Group { count, steps, .. } => {
+                let mut ret = vec![(0, format!("for (i = 0; i < {0}u; ++i) {{", count))];
+                ret.extend(self.nested_lines(steps));
+                ret.push((0, "}".to_string()));
+
+                ret
+            }
+        }
+    }
+
+    fn nested_lines(&self, steps: &[common::SerializerStep]) -> Vec<(usize, String)> {
+        steps
+            .iter()
+            .flat_map(|step| self.step_lines(step))
+            .map(|(indent, line)| (indent + 1, line))
+            .collect()
+    }
+
+    /// Whether the steps loop over an index
+    fn uses_index(steps: &[common::SerializerStep]) -> bool {
+        steps.iter().any(|step| match step {
+            common::SerializerStep::Checksum { .. } | common::SerializerStep::Group { .. } => true,
+            common::SerializerStep::Conditional { steps, .. } => SerializingFunction::uses_index(steps),
+            _ => false,
+        })
+    }
+
+    fn lines(&self) -> Vec<(usize, String)> {
+        let mut ret = vec![
+            (
+                0,
+                ApiFunction::serializing(&self.symbol_naming, &self.message_name).signature(),
+            ),
+            (0, "{".to_string()),
+            (1, "size_t length = 0;".to_string()),
+        ];
+
+        for mark in &self.marks {
+            ret.push((1, format!("size_t {0} = 0;", mark)));
+        }
+
+        if SerializingFunction::uses_index(&self.steps) {
+            ret.push((1, "size_t i;".to_string()));
+        }
+
+        if self.misra_c {
+            return self.single_exit_lines(ret);
+        }
+
+        ret.extend([
+            (0, String::new()),
+            (1, format!("if (aCapacity < {0}u) {{", self.max_length)),
+            (2, "return -1;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+        ]);
+
+        for step in &self.steps {
+            ret.extend(self.step_lines(step).into_iter().map(|(indent, line)| (indent + 1, line)));
+        }
+
+        ret.extend([
+            (0, String::new()),
+            (1, "return (int)length;".to_string()),
+            (0, "}".to_string()),
+        ]);
+
+        ret
+    }
+
+    /// Completes the declarations into a function which composes the frame
+    /// within the capacity check, and returns at the end
+    fn single_exit_lines(&self, declarations: Vec<(usize, String)>) -> Vec<(usize, String)> {
+        let mut ret = declarations;
+        ret.extend([
+            (1, "int result = -1;".to_string()),
+            (0, String::new()),
+            (1, format!("if (aCapacity >= {0}u) {{", self.max_length)),
+        ]);
+
+        for step in &self.steps {
+            ret.extend(self.step_lines(step).into_iter().map(|(indent, line)| (indent + 2, line)));
+        }
+
+        ret.extend([
+            (0, String::new()),
+            (2, "result = (int)length;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "return result;".to_string()),
+            (0, "}".to_string()),
+        ]);
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for SerializingFunction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Parses a bit-granular message from a buffer containing the entire frame.
+/// Returns the number of consumed bytes, -1, if the buffer is too short, -2,
+/// if a field's value is out of its range, or -3, if the buffer is longer
+/// than the message's fixed length.
+#[derive(Debug)]
+struct BitstreamParsingFunction {
+    message_name: String,
+    symbol_naming: SymbolNaming,
+    bit_length: usize,
+    fixed_length: Option<usize>,
+}
+
+impl From<&mut common::BitstreamParsingFunction> for BitstreamParsingFunction {
+    fn from(value: &mut common::BitstreamParsingFunction) -> Self {
+        BitstreamParsingFunction {
+            message_name: value.message_name.clone(),
+            symbol_naming: value.symbol_naming.clone(),
+            bit_length: value.bit_length,
+            fixed_length: value.fixed_length,
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for BitstreamParsingFunction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        ret.push(CodeChunk::new(
+            ApiFunction::bitstream_parsing(&self.symbol_naming, &self.message_name).signature(),
+            code_generation_state.indent,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            "{".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+        code_generation_state.indent += 1usize;
+        ret.push(CodeChunk::new(
+            "unsigned long bitOffset = 0;  // Position of the bit reader".to_string(),
+            code_generation_state.indent,
+            2usize,
+        ));
+        ret.push(CodeChunk::new(
+            format!(
+                "if ((unsigned long)aInputBufferLength * 8UL < {0}UL) {{",
+                self.bit_length
+            ),
+            code_generation_state.indent,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            "return -1;".to_string(),
+            code_generation_state.indent + 1,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            "}".to_string(),
+            code_generation_state.indent,
+            2usize,
+        ));
+
+        if let Some(fixed_length) = self.fixed_length {
+            ret.push(CodeChunk::new(
+                format!("if (aInputBufferLength > {0}) {{", fixed_length),
+                code_generation_state.indent,
+                1usize,
+            ));
+            ret.push(CodeChunk::new(
+                "return -3;".to_string(),
+                code_generation_state.indent + 1,
+                1usize,
+            ));
+            ret.push(CodeChunk::new(
+                "}".to_string(),
+                code_generation_state.indent,
+                2usize,
+            ));
+        }
+
+        ret
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        ret.push(CodeChunk::new(
+            "return (int)((bitOffset + 7UL) / 8UL);".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+        code_generation_state.indent -= 1usize;
+        ret.push(CodeChunk::new(
+            "}".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+/// Extracts a field from a bit stream, sign-extending it, if required
+#[derive(Debug)]
+struct BitstreamFieldRead {
+    message_name: String,
+    field_name: String,
+    field_base_type: FieldBaseType,
+    bit_width: usize,
+    signed: bool,
+    bit_order: representation::BitOrder,
+}
+
+impl From<&mut common::BitstreamFieldRead> for BitstreamFieldRead {
+    fn from(value: &mut common::BitstreamFieldRead) -> Self {
+        BitstreamFieldRead {
+            message_name: value.message_name.clone(),
+            field_name: value.field_name.clone(),
+            field_base_type: value.field_base_type.clone(),
+            bit_width: value.bit_width,
+            signed: value.signed,
+            bit_order: value.bit_order,
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for BitstreamFieldRead {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        let read = format!(
+            "{0}(aInputBuffer, bitOffset, {1}u)",
+            bit_reader_name(self.bit_order),
+            self.bit_width
+        );
+        let value = if self.signed {
+            format!("robustoSignExtend({0}, {1}u)", read, self.bit_width)
+        } else {
+            read
+        };
+        ret.push(CodeChunk::new(
+            format!(
+                "a{0}->{1} = ({2}){3};",
+                self.message_name,
+                self.field_name,
+                c_type_name(&self.field_base_type),
+                value
+            ),
+            code_generation_state.indent,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            format!("bitOffset += {0}u;", self.bit_width),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+/// Tagged union holding a single record of a TLV field
+#[derive(Debug)]
+struct TlvRecordStruct {
+    message_name: String,
+    field_name: String,
+    variants: Vec<common::TlvRecordVariant>,
+}
+
+impl From<&mut common::TlvRecordStruct> for TlvRecordStruct {
+    fn from(value: &mut common::TlvRecordStruct) -> Self {
+        TlvRecordStruct {
+            message_name: value.message_name.clone(),
+            field_name: value.field_name.clone(),
+            variants: value.variants.clone(),
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for TlvRecordStruct {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        let indent = code_generation_state.indent;
+        ret.push(CodeChunk::new(
+            format!(
+                "struct {0} {{",
+                common::tlv_record_struct_name(&self.message_name, &self.field_name)
+            ),
+            indent,
+            1usize,
+        ));
+        ret.push(CodeChunk::new("uint32_t tag;".to_string(), indent + 1, 1usize));
+        ret.push(CodeChunk::new("union {".to_string(), indent + 1, 1usize));
+
+        for variant in &self.variants {
+            ret.append(
+                &mut MessageStructMember {
+                    name: variant.name.clone(),
+                    field_base_type: variant.field_base_type.clone(),
+                    array_length: variant.array_length,
+                }
+                .generate_code_pre_traverse(&mut codegen::CodeGenerationState {
+                    indent: indent + 2,
+                    style: code_generation_state.style.clone(),
+                }),
+            );
+        }
+
+        ret.push(CodeChunk::new("} value;".to_string(), indent + 1, 1usize));
+        ret.push(CodeChunk::new("};".to_string(), indent, 1usize));
+
+        ret
+    }
+}
+
+/// Union of the message struct members which share the same bytes. The field
+/// selecting the interpretation serves as the union's tag
+#[derive(Debug)]
+struct MessageStructUnion {
+    name: String,
+}
+
+impl codegen::TreeBasedCodeGeneration for MessageStructUnion {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let ret = vec![CodeChunk::new(
+            "union {".to_string(),
+            code_generation_state.indent,
+            1usize,
+        )];
+        code_generation_state.indent += 1;
+
+        ret
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        code_generation_state.indent -= 1;
+        ret.push(CodeChunk::new(
+            format!("}} {0};", self.name),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+/// Condition of a conditional field, a C expression
+#[derive(Debug)]
+struct ConditionCheck {
+    condition: common::MessageExpression,
+}
+
+impl From<&mut common::ConditionCheck> for ConditionCheck {
+    fn from(value: &mut common::ConditionCheck) -> Self {
+        ConditionCheck {
+            condition: value.condition.clone(),
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for ConditionCheck {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let line = c_expression(&self.condition);
+
+        indented_lines(std::iter::once((0, line)), code_generation_state.indent)
+    }
+}
+
+/// Translates an expression into C. Operands are widened to `int64_t`, and
+/// every operation is parenthesized, so C precedence rules do not matter
+fn c_expression(message_expression: &common::MessageExpression) -> String {
+    fn translate(expression: &Expression, message_expression: &common::MessageExpression) -> String {
+        let (left, right) = match expression {
+            Expression::Field(ref name) => {
+                let container = message_expression
+                    .operands
+                    .iter()
+                    .find(|(operand, _)| operand == name)
+                    .map(|(_, container)| container.clone())
+                    .unwrap_or(common::FieldContainer::Message);
+
+                return format!(
+                    "(int64_t){0}",
+                    field_member(&message_expression.message_name, name, &container)
+                );
+            }
+            Expression::Constant(value) => return c_integer_literal(i128::from(*value), &FieldBaseType::I64),
+            Expression::Not(ref operand) => return format!("!{0}", translate(operand, message_expression)),
+            _ => {
+                let (left, right) = expression.operands().unwrap();
+
+                (translate(left, message_expression), translate(right, message_expression))
+            }
+        };
+
+        match expression {
+            // Division by zero yields 0, as it does at generation time
+            Expression::Div(_, _) => format!("({1} != 0 ? {0} / {1} : 0)", left, right),
+            _ => {
+                let operator = match expression {
+                    Expression::Add(_, _) => "+",
+                    Expression::Sub(_, _) => "-",
+                    Expression::Mul(_, _) => "*",
+                    Expression::Eq(_, _) => "==",
+                    Expression::Ne(_, _) => "!=",
+                    Expression::Lt(_, _) => "<",
+                    Expression::Le(_, _) => "<=",
+                    Expression::Gt(_, _) => ">",
+                    Expression::Ge(_, _) => ">=",
+                    Expression::And(_, _) => "&&",
+                    Expression::Or(_, _) => "||",
+                    _ => unreachable!(),
+                };
+
+                format!("({0} {1} {2})", left, operator, right)
+            }
+        }
+    }
+
+    translate(&message_expression.expression, message_expression)
+}
+
+/// Rejects the message, unless the expression of a "check" attribute holds
+#[derive(Debug)]
+struct ExpressionCheck {
+    check: common::MessageExpression,
+    layout: MessageLayout,
+}
+
+impl From<&mut common::ExpressionCheck> for ExpressionCheck {
+    fn from(value: &mut common::ExpressionCheck) -> Self {
+        ExpressionCheck {
+            check: value.check.clone(),
+            layout: value.layout,
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for ExpressionCheck {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = vec![(0, format!("if (!{0}) {{", c_expression(&self.check)))];
+
+        match self.layout {
+            MessageLayout::Bytes => {
+                lines.push((
+                    1,
+                    format!(
+                        "aParserState->{0} = {1};",
+                        common::PARSE_ERROR_MEMBER_NAME,
+                        c_parse_error_name(common::ParseError::Check)
+                    ),
+                ));
+                lines.push((1, format!("fgoto *{0}_error;", self.check.message_name)));
+            }
+            MessageLayout::Bits => lines.push((1, "return -2;".to_string())),
+        }
+
+        lines.push((0, "}".to_string()));
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Struct holding a single repetition of a group field
+#[derive(Debug)]
+struct GroupStruct {
+    name: String,
+    members: Vec<MessageStructMember>,
+}
+
+impl From<&mut common::GroupStruct> for GroupStruct {
+    fn from(value: &mut common::GroupStruct) -> Self {
+        GroupStruct {
+            name: value.name.clone(),
+            members: value.members.iter_mut().map(MessageStructMember::from).collect(),
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for GroupStruct {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = vec![(0, format!("struct {0} {{", self.name))];
+
+        for member in &self.members {
+            lines.push((1, member.declaration()));
+        }
+
+        lines.push((0, "};".to_string()));
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Body of a group action hook. The index of the current repetition is kept
+/// in the parser state.
+#[derive(Debug)]
+struct GroupAction {
+    kind: common::GroupActionKind,
+    field_name: String,
+}
+
+impl From<&mut common::GroupAction> for GroupAction {
+    fn from(value: &mut common::GroupAction) -> Self {
+        GroupAction {
+            kind: value.kind,
+            field_name: value.field_name.clone(),
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for GroupAction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let index = format!(
+            "aParserState->{0}",
+            common::group_index_member_name(&self.field_name)
+        );
+        let line = match self.kind {
+            common::GroupActionKind::Reset => format!("{0} = 0;", index),
+            common::GroupActionKind::Next => format!("++{0};", index),
+        };
+
+        indented_lines(std::iter::once((0, line)), code_generation_state.indent)
+    }
+}
+
+/// Body of a TLV action hook. Tags and lengths are accumulated in the parser
+/// state, values are stored directly into the message struct.
+#[derive(Debug)]
+struct TlvAction {
+    kind: common::TlvActionKind,
+    message_name: String,
+    field_name: String,
+    max_records: usize,
+    variants: Vec<common::TlvRecordVariant>,
+}
+
+impl From<&mut common::TlvAction> for TlvAction {
+    fn from(value: &mut common::TlvAction) -> Self {
+        TlvAction {
+            kind: value.kind,
+            message_name: value.message_name.clone(),
+            field_name: value.field_name.clone(),
+            max_records: value.max_records,
+            variants: value.variants.clone(),
+        }
+    }
+}
+
+impl TlvAction {
+    fn lines(&self) -> Vec<(usize, String)> {
+        let count = format!(
+            "a{0}->{1}",
+            self.message_name,
+            common::tlv_count_member_name(&self.field_name)
+        );
+        let record = format!("a{0}->{1}[{2}]", self.message_name, self.field_name, count);
+
+        match self.kind {
+            common::TlvActionKind::Reset => vec![(0, format!("{0} = 0;", count))],
+            common::TlvActionKind::RecordStart => vec![
+                (0, "aParserState->tlvTag = 0;".to_string()),
+                (0, "aParserState->tlvLength = 0;".to_string()),
+            ],
+            common::TlvActionKind::TagByte => vec![(
+                0,
+                "aParserState->tlvTag = (aParserState->tlvTag << 8) | (uint8_t)fc;".to_string(),
+            )],
+            common::TlvActionKind::LengthByte => vec![(
+                0,
+                "aParserState->tlvLength = (aParserState->tlvLength << 8) | (uint8_t)fc;"
+                    .to_string(),
+            )],
+            common::TlvActionKind::ValueStart => vec![
+                (0, format!("if ({0} >= {1}) {{", count, self.max_records)),
+                (1, "// Too many records".to_string()),
+                (1, format!("fgoto *{0}_error;", self.message_name)),
+                (0, "}".to_string()),
+                (0, format!("{0}.tag = aParserState->tlvTag;", record)),
+                (0, "aParserState->tlvValueIndex = 0;".to_string()),
+                (0, "if (aParserState->tlvLength == 0) {".to_string()),
+                (1, format!("{0}++;", count)),
+                (0, "} else {".to_string()),
+                (
+                    1,
+                    format!(
+                        "fcall {0};",
+                        common::TlvActionKind::ValueByte.hook_name(&self.field_name)
+                    ),
+                ),
+                (0, "}".to_string()),
+            ],
+            common::TlvActionKind::ValueByte => {
+                let mut ret = vec![(0, "switch (aParserState->tlvTag) {".to_string())];
+
+                for variant in &self.variants {
+                    let value = format!("{0}.value.{1}", record, variant.name);
+                    ret.push((1, format!("case {0}UL:", variant.tag)));
+
+                    if variant.array_length == 0 {
+                        ret.push((2, "if (aParserState->tlvValueIndex == 0) {".to_string()));
+                        ret.push((3, format!("{0} = 0;", value)));
+                        ret.push((2, "}".to_string()));
+                        ret.push((
+                            2,
+                            format!(
+                                "{0} = ({1})(({0} << 8) | (uint8_t)fc);",
+                                value,
+                                c_type_name(&variant.field_base_type)
+                            ),
+                        ));
+                    } else {
+                        ret.push((
+                            2,
+                            format!(
+                                "if (aParserState->tlvValueIndex < {0}) {{",
+                                variant.array_length
+                            ),
+                        ));
+                        ret.push((
+                            3,
+                            format!("{0}[aParserState->tlvValueIndex] = (uint8_t)fc;", value),
+                        ));
+                        ret.push((2, "}".to_string()));
+                    }
+
+                    ret.push((2, "break;".to_string()));
+                }
+
+                ret.push((1, "default:".to_string()));
+                ret.push((2, "// Unknown record, skip its value".to_string()));
+                ret.push((2, "break;".to_string()));
+                ret.push((0, "}".to_string()));
+                ret.push((0, "aParserState->tlvValueIndex++;".to_string()));
+                ret.push((
+                    0,
+                    "if (aParserState->tlvValueIndex == aParserState->tlvLength) {".to_string(),
+                ));
+                ret.push((1, format!("{0}++;", count)));
+                ret.push((1, "fret;".to_string()));
+                ret.push((0, "}".to_string()));
+
+                ret
+            }
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for TlvAction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        self.lines()
+            .into_iter()
+            .map(|(indent, line)| {
+                CodeChunk::new(line, code_generation_state.indent + indent, 1usize)
+            })
+            .collect()
+    }
+}
+
+/// Shifts a byte of a big-endian integer field into the message struct. The
+/// shift is done on the unsigned type, since shifting negative values is
+/// undefined.
+#[derive(Debug)]
+struct IntegerFieldByte {
+    message_name: String,
+    field_name: String,
+    field_base_type: FieldBaseType,
+    bit_width: usize,
+    signed: bool,
+    container: common::FieldContainer,
+}
+
+impl From<&mut common::IntegerFieldByte> for IntegerFieldByte {
+    fn from(value: &mut common::IntegerFieldByte) -> Self {
+        IntegerFieldByte {
+            message_name: value.message_name.clone(),
+            field_name: value.field_name.clone(),
+            field_base_type: value.field_base_type.clone(),
+            bit_width: value.bit_width,
+            signed: value.signed,
+            container: value.container.clone(),
+        }
+    }
+}
+
+impl IntegerFieldByte {
+    /// The accumulated value, with a byte shifted in. A field narrower than
+    /// its type is masked, and sign-extended, if signed, after every byte.
+    /// Intermediate values are meaningless, but the last byte leaves the
+    /// field's value
+    fn accumulation(&self, member: &str) -> String {
+        let unsigned_type = c_type_name(&self.field_base_type.to_unsigned());
+        let shifted = format!("(({0}){1} << 8) | (uint8_t)fc", unsigned_type, member);
+
+        if [8usize, 16, 32, 64].contains(&self.bit_width) {
+            return shifted;
+        }
+
+        let unsigned_base_type = self.field_base_type.to_unsigned();
+        let masked = format!(
+            "({0}) & {1}",
+            shifted,
+            c_hex_literal((1u64 << self.bit_width) - 1, &unsigned_base_type)
+        );
+
+        match self.signed {
+            true => {
+                let sign_bit = c_hex_literal(1u64 << (self.bit_width - 1), &unsigned_base_type);
+                format!("(({0}) ^ {1}) - {1}", masked, sign_bit)
+            }
+            false => masked,
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for IntegerFieldByte {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        let member = field_member(&self.message_name, &self.field_name, &self.container);
+        ret.push(CodeChunk::new(
+            format!(
+                "{0} = ({1})({2});",
+                member,
+                c_type_name(&self.field_base_type),
+                self.accumulation(&member)
+            ),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+/// The message struct member holding a field, as seen from an action
+fn field_member(message_name: &str, field_name: &str, container: &common::FieldContainer) -> String {
+    match container {
+        common::FieldContainer::Message => format!("a{0}->{1}", message_name, field_name),
+        common::FieldContainer::Group(ref group_field_name) => format!(
+            "a{0}->{1}[aParserState->{2}].{3}",
+            message_name,
+            group_field_name,
+            common::group_index_member_name(group_field_name),
+            field_name
+        ),
+        common::FieldContainer::Union(ref selector_field_name) => format!(
+            "a{0}->{1}.{2}",
+            message_name,
+            common::conditional_union_member_name(selector_field_name),
+            field_name
+        ),
+    }
+}
+
+/// Body of a varint action hook. The 7-bit groups are accumulated on the
+/// unsigned type, and the value is masked to the field's width, once
+/// complete, since the last group may carry excess bits.
+#[derive(Debug)]
+struct VarintAction {
+    kind: common::VarintActionKind,
+    member: String,
+    field_base_type: FieldBaseType,
+    varint: representation::VarintFieldType,
+}
+
+impl From<&mut common::VarintAction> for VarintAction {
+    fn from(value: &mut common::VarintAction) -> Self {
+        VarintAction {
+            kind: value.kind,
+            member: field_member(&value.message_name, &value.field_name, &value.container),
+            field_base_type: value.field_base_type.clone(),
+            varint: value.varint.clone(),
+        }
+    }
+}
+
+impl VarintAction {
+    fn lines(&self) -> Vec<(usize, String)> {
+        let type_name = c_type_name(&self.field_base_type);
+        let unsigned_base_type = self.field_base_type.to_unsigned();
+        let unsigned_type = c_type_name(&unsigned_base_type);
+        let shift = format!("aParserState->{0}", common::VARINT_SHIFT_MEMBER_NAME);
+
+        match self.kind {
+            common::VarintActionKind::Reset => vec![
+                (0, format!("{0} = 0;", self.member)),
+                (0, format!("{0} = 0;", shift)),
+            ],
+            common::VarintActionKind::Byte => vec![
+                (
+                    0,
+                    format!(
+                        "{0} = ({1})(({2}){0} | (({2})(fc & 0x7Fu) << {3}));",
+                        self.member, type_name, unsigned_type, shift
+                    ),
+                ),
+                (0, format!("{0} += 7;", shift)),
+            ],
+            common::VarintActionKind::Decode => {
+                let bit_width = self.varint.bit_width;
+                let full_width = [8usize, 16, 32, 64].contains(&bit_width);
+                let raw = match full_width {
+                    true => format!("({0}){1}", unsigned_type, self.member),
+                    false => format!(
+                        "(({0}){1} & {2})",
+                        unsigned_type,
+                        self.member,
+                        c_hex_literal(u64::MAX >> (64 - bit_width), &unsigned_base_type)
+                    ),
+                };
+                // The last group is already in place
+                if full_width && !(self.varint.signed && self.varint.zigzag) {
+                    return vec![];
+                }
+
+                let value = match (self.varint.signed, self.varint.zigzag) {
+                    (true, true) => format!("({0} >> 1) ^ (({1})0 - ({0} & 1u))", raw, unsigned_type),
+                    (true, false) if !full_width => {
+                        let sign_bit = c_hex_literal(1u64 << (bit_width - 1), &unsigned_base_type);
+                        format!("({0} ^ {1}) - {1}", raw, sign_bit)
+                    }
+                    _ => raw,
+                };
+
+                vec![(0, format!("{0} = ({1})({2});", self.member, type_name, value))]
+            }
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for VarintAction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Copies the bytes of a regex field into its member, as far as the member
+/// holds them
+#[derive(Debug)]
+struct RegexAction {
+    kind: common::RegexActionKind,
+    member: String,
+    message_name: String,
+    write_index: String,
+    capacity: usize,
+    overflow: OverflowPolicy,
+
+    /// Member holding the field's offset into the input, if the field is kept
+    /// as an offset and a length. The length then indexes the bytes
+    offset_member: Option<String>,
+}
+
+impl From<&mut common::RegexAction> for RegexAction {
+    fn from(value: &mut common::RegexAction) -> Self {
+        RegexAction {
+            kind: value.kind,
+            member: field_member(&value.message_name, &value.field_name, &value.container),
+            message_name: value.message_name.clone(),
+            write_index: match value.zero_copy {
+                true => format!("{0}.length", field_member(&value.message_name, &value.field_name, &value.container)),
+                false => field_member(
+                    &value.message_name,
+                    &common::regex_length_member_name(&value.field_name),
+                    &value.container,
+                ),
+            },
+            capacity: value.capacity,
+            overflow: value.overflow,
+            offset_member: Some(format!(
+                "{0}.offset",
+                field_member(&value.message_name, &value.field_name, &value.container)
+            ))
+            .filter(|_| value.zero_copy),
+        }
+    }
+}
+
+impl RegexAction {
+    /// Handles a byte which does not fit into the member
+    fn overflow_lines(&self) -> Vec<(usize, String)> {
+        match self.overflow {
+            OverflowPolicy::Truncate => vec![],
+            OverflowPolicy::Error => vec![
+                (0, "} else {".to_string()),
+                (
+                    1,
+                    format!(
+                        "aParserState->{0} = {1};",
+                        common::PARSE_ERROR_MEMBER_NAME,
+                        c_parse_error_name(common::ParseError::Overflow)
+                    ),
+                ),
+                (1, format!("fgoto *{0}_error;", self.message_name)),
+            ],
+            OverflowPolicy::Resync => vec![
+                (0, "} else {".to_string()),
+                (1, "// Drop the frame, and start over on this byte".to_string()),
+                (1, "fhold;".to_string()),
+                (1, "fgoto main;".to_string()),
+            ],
+        }
+    }
+
+    fn lines(&self) -> Vec<(usize, String)> {
+        let store = match self.offset_member {
+            Some(_) => format!("++{0};", self.write_index),
+            None => format!("{0}[{1}++] = (uint8_t)fc;", self.member, self.write_index),
+        };
+
+        match self.kind {
+            common::RegexActionKind::Reset => self
+                .offset_member
+                .iter()
+                .map(|offset_member| {
+                    (
+                        0,
+                        format!(
+                            "{0} = aParserState->{1} + (uint32_t)(p - (const unsigned char *)aInputBuffer);",
+                            offset_member,
+                            common::INPUT_POSITION_MEMBER_NAME
+                        ),
+                    )
+                })
+                .chain([(0, format!("{0} = 0;", self.write_index))])
+                .collect(),
+            common::RegexActionKind::Byte => [
+                (0, format!("if ({0} < {1}u) {{", self.write_index, self.capacity)),
+                (1, store),
+            ]
+            .into_iter()
+            .chain(self.overflow_lines())
+            .chain([(0, "}".to_string())])
+            .collect(),
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for RegexAction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Formats a mask as a hexadecimal constant of the given unsigned type
+fn c_hex_literal(value: u64, field_base_type: &FieldBaseType) -> String {
+    match field_base_type {
+        FieldBaseType::U64 => format!("UINT64_C(0x{0:X})", value),
+        _ => format!("0x{0:X}u", value),
+    }
+}
+
+/// Formats an integer constant, so it has the type of the member it gets
+/// compared against
+pub fn c_integer_literal(value: i128, field_base_type: &FieldBaseType) -> String {
+    c_typed_literal(value.to_string(), field_base_type)
+}
+
+/// Suffixes the literal, or wraps it into a macro, so it has the type
+fn c_typed_literal(literal: String, field_base_type: &FieldBaseType) -> String {
+    match field_base_type {
+        FieldBaseType::I64 => format!("INT64_C({0})", literal),
+        FieldBaseType::U64 => format!("UINT64_C({0})", literal),
+        FieldBaseType::U8 | FieldBaseType::U16 | FieldBaseType::U32 => format!("{0}u", literal),
+        FieldBaseType::F32 => format!("{0}.0f", literal),
+        FieldBaseType::F64 => format!("{0}.0", literal),
+        _ => literal,
+    }
+}
+
+/// Loopback self-test. Feeds each canned frame into its parser, and compares
+/// the decoded integer fields against the canned values. Byte-granular frames
+/// are then fed once more, one byte at a time. Byte-granular messages with
+/// multi-byte fields get a second frame, checking the byte order, see
+/// `common::CannedValues`. Returns 0 on success, or the 1-based index of the
+/// first failing frame.
+#[derive(Debug)]
+struct SelfTestFunction {
+    messages: Vec<common::SelfTestMessage>,
+    symbol_naming: SymbolNaming,
+}
+
+impl From<&mut common::SelfTestFunction> for SelfTestFunction {
+    fn from(value: &mut common::SelfTestFunction) -> Self {
+        SelfTestFunction {
+            messages: value.messages.clone(),
+            symbol_naming: value.symbol_naming.clone(),
+        }
+    }
+}
+
+impl SelfTestFunction {
+    /// Compares the decoded integer fields of the struct against the canned
+    /// values
+    fn field_check_lines(
+        message: &common::SelfTestMessage,
+        struct_name: &str,
+        failure_code: usize,
+    ) -> Vec<(usize, String)> {
+        let mut ret = Vec::new();
+
+        for field_value in &message.field_values {
+            // Byte patterns read better in hex
+            let hex = format!("{0:X}", field_value.value);
+            let is_byte_pattern = (2..=8).any(|byte_count| {
+                hex == (1..=byte_count).map(|byte| format!("{0:02X}", byte)).collect::<String>()[1..]
+            });
+            let literal = match message.canned_values {
+                common::CannedValues::AscendingBytes if is_byte_pattern => {
+                    c_typed_literal(format!("0x{0:X}", field_value.value), &field_value.field_base_type)
+                }
+                _ => c_integer_literal(field_value.value, &field_value.field_base_type),
+            };
+            ret.push((0, String::new()));
+            ret.push((
+                1,
+                format!("if ({0}.{1} != {2}) {{", struct_name, field_value.field_name, literal),
+            ));
+            ret.push((2, format!("return {0};", failure_code)));
+            ret.push((1, "}".to_string()));
+        }
+
+        ret
+    }
+
+    fn message_lines(&self, message: &common::SelfTestMessage, failure_code: usize) -> Vec<(usize, String)> {
+        let name = &message.message_name;
+        let naming = &self.symbol_naming;
+        let frame = message
+            .frame
+            .iter()
+            .map(|byte| format!("0x{0:02X}", byte))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let mut ret = vec![(0, "{".to_string())];
+
+        if message.canned_values == common::CannedValues::AscendingBytes {
+            ret.push((
+                1,
+                "// Byte order: multi-byte fields carry 0x01, 0x02, ... on the wire, and decode the same on any host"
+                    .to_string(),
+            ));
+        }
+
+        ret.push((1, format!("static const uint8_t kFrame[] = {{{0}}};", frame)));
+
+        match message.layout {
+            MessageLayout::Bytes => {
+                ret.push((1, format!("struct {0} parserState;", naming.parser_state_struct(name))));
+                ret.push((1, format!("struct {0} message = {{0}};", naming.message_struct(name))));
+                ret.push((1, format!("struct {0} splitMessage = {{0}};", naming.message_struct(name))));
+                ret.push((
+                    1,
+                    format!(
+                        "enum RobustoParseStatus splitStatus = {0};",
+                        c_parse_status_name(common::ParseStatus::Incomplete)
+                    ),
+                ));
+                ret.push((1, "int i;".to_string()));
+
+                if let Some(serializer_capacity) = message.serializer_capacity {
+                    ret.push((1, format!("uint8_t serialized[{0}u];", serializer_capacity)));
+                }
+
+                ret.push((0, String::new()));
+                ret.push((1, format!("{0}(&parserState);", naming.parser_state_init_function(name))));
+                ret.push((0, String::new()));
+                ret.push((
+                    1,
+                    format!(
+                        "if ({0}(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != {1}) {{",
+                        naming.parsing_function(name),
+                        c_parse_status_name(common::ParseStatus::Ok)
+                    ),
+                ));
+            }
+            MessageLayout::Bits => {
+                ret.push((1, format!("struct {0} message = {{0}};", naming.message_struct(name))));
+                ret.push((0, String::new()));
+                ret.push((
+                    1,
+                    format!(
+                        "if ({0}(kFrame, (int)sizeof(kFrame), &message) != (int)sizeof(kFrame)) {{",
+                        naming.parsing_function(name)
+                    ),
+                ));
+            }
+        }
+
+        ret.push((2, format!("return {0};", failure_code)));
+        ret.push((1, "}".to_string()));
+        ret.extend(SelfTestFunction::field_check_lines(message, "message", failure_code));
+
+        // The parser keeps what it needs between calls, so a frame fed in pieces decodes the same
+        if message.layout == MessageLayout::Bytes {
+            ret.extend([
+                (0, String::new()),
+                (1, "// Feed the frame once more, one byte at a time, into a restarted parser".to_string()),
+                (1, "parserState.machineInitRequired = 1;".to_string()),
+                (0, String::new()),
+                (1, "for (i = 0; i < (int)sizeof(kFrame); ++i) {".to_string()),
+                (
+                    2,
+                    format!(
+                        "splitStatus = {0}(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);",
+                        naming.parsing_function(name)
+                    ),
+                ),
+                (1, "}".to_string()),
+                (0, String::new()),
+                (
+                    1,
+                    format!("if (splitStatus != {0}) {{", c_parse_status_name(common::ParseStatus::Ok)),
+                ),
+                (2, format!("return {0};", failure_code)),
+                (1, "}".to_string()),
+            ]);
+            ret.extend(SelfTestFunction::field_check_lines(message, "splitMessage", failure_code));
+        }
+
+        if message.serializer_capacity.is_some() {
+            ret.extend([
+                (0, String::new()),
+                (1, "// Compose the parsed message back into the frame".to_string()),
+                (
+                    1,
+                    format!(
+                        "if ({0}(&message, serialized, sizeof(serialized)) != (int)sizeof(kFrame)) {{",
+                        naming.serializing_function(name)
+                    ),
+                ),
+                (2, format!("return {0};", failure_code)),
+                (1, "}".to_string()),
+                (0, String::new()),
+                (1, "for (i = 0; i < (int)sizeof(kFrame); ++i) {".to_string()),
+                (2, "if (serialized[i] != kFrame[i]) {".to_string()),
+                (3, format!("return {0};", failure_code)),
+                (2, "}".to_string()),
+                (1, "}".to_string()),
+            ]);
+        }
+
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+
+    fn lines(&self) -> Vec<(usize, String)> {
+        let mut ret = vec![
+            (0, ApiFunction::self_test().signature()),
+            (0, "{".to_string()),
+        ];
+
+        for (message_index, message) in self.messages.iter().enumerate() {
+            for (indent, line) in self.message_lines(message, message_index + 1) {
+                ret.push((indent + 1, line));
+            }
+        }
+
+        ret.push((0, String::new()));
+        ret.push((1, "return 0;".to_string()));
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for SelfTestFunction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Feeds each benchmark pattern into its parser the given number of times,
+/// and records the elapsed clock ticks. The parser is re-initialized before
+/// each repetition, as it would be before a frame
+#[derive(Debug)]
+struct BenchmarkFunction {
+    clock_hook: String,
+    repetitions: usize,
+    patterns: Vec<common::BenchmarkPattern>,
+    symbol_naming: SymbolNaming,
+}
+
+impl From<&mut common::BenchmarkFunction> for BenchmarkFunction {
+    fn from(value: &mut common::BenchmarkFunction) -> Self {
+        BenchmarkFunction {
+            clock_hook: value.attribute.clock_hook.clone(),
+            repetitions: value.attribute.repetitions,
+            patterns: value.patterns.clone(),
+            symbol_naming: value.symbol_naming.clone(),
+        }
+    }
+}
+
+impl BenchmarkFunction {
+    fn pattern_lines(&self, pattern: &common::BenchmarkPattern, result_index: usize) -> Vec<(usize, String)> {
+        let name = &pattern.message_name;
+        let naming = &self.symbol_naming;
+        let input = pattern
+            .input
+            .iter()
+            .map(|byte| format!("0x{0:02X}", byte))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        vec![
+            (0, "{".to_string()),
+            (1, format!("static const uint8_t kInput[] = {{{0}}};", input)),
+            (1, format!("struct {0} parserState;", naming.parser_state_struct(name))),
+            (1, format!("struct {0} message;", naming.message_struct(name))),
+            (1, "unsigned long i;".to_string()),
+            (1, format!("uint32_t start = {0}();", self.clock_hook)),
+            (0, String::new()),
+            (1, format!("for (i = 0; i < {0}ul; ++i) {{", self.repetitions)),
+            (2, format!("{0}(&parserState);", naming.parser_state_init_function(name))),
+            (
+                2,
+                format!(
+                    "{0}(&parserState, (const char *)kInput, (int)sizeof(kInput), &message, 0);",
+                    naming.parsing_function(name)
+                ),
+            ),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (
+                1,
+                format!(
+                    "aResults[{0}].ticks = (uint32_t)({1}() - start);",
+                    result_index, self.clock_hook
+                ),
+            ),
+            (
+                1,
+                format!(
+                    "aResults[{0}].bytes = (uint32_t)(sizeof(kInput) * {1}ul);",
+                    result_index, self.repetitions
+                ),
+            ),
+            (
+                1,
+                format!(
+                    "aResults[{0}].pattern = \"{1}: {2}\";",
+                    result_index, name, pattern.description
+                ),
+            ),
+            (0, "}".to_string()),
+        ]
+    }
+
+    fn lines(&self) -> Vec<(usize, String)> {
+        let mut ret = vec![
+            (0, ApiFunction::benchmark().signature()),
+            (0, "{".to_string()),
+        ];
+
+        for (pattern_index, pattern) in self.patterns.iter().enumerate() {
+            for (indent, line) in self.pattern_lines(pattern, pattern_index) {
+                ret.push((indent + 1, line));
+            }
+        }
+
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for BenchmarkFunction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Benchmark result type, the number of results the benchmark records, and
+/// the clock hook for the user to define
+#[derive(Debug)]
+struct BenchmarkDeclarations {
+    pattern_count: usize,
+    clock_hook: String,
+}
+
+impl codegen::TreeBasedCodeGeneration for BenchmarkDeclarations {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let lines = vec![
+            (0, format!("#define ROBUSTO_BENCHMARK_PATTERN_COUNT {0}u", self.pattern_count)),
+            (0, "struct RobustoBenchmarkResult {".to_string()),
+            (1, "const char *pattern;  // Message and input pattern".to_string()),
+            (1, "uint32_t bytes;  // Bytes fed over every repetition".to_string()),
+            (1, "uint32_t ticks;  // Clock ticks elapsed".to_string()),
+            (0, "};".to_string()),
+            (0, format!("uint32_t {0}(void);", self.clock_hook)),
+        ];
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Parse error descriptions. Each string is a separate array, so a storage
+/// qualifier such as `PROGMEM` may move it into flash. The table of pointers
+/// stays in RAM, so looking a string up does not require special read
+/// routines, yet the returned pointer refers to the qualified storage.
+#[derive(Debug)]
+struct DiagnosticStrings {
+    storage_qualifier: Option<String>,
+    misra_c: bool,
+}
+
+impl DiagnosticStrings {
+    fn string_name(suffix: &str) -> String {
+        format!("kRobustoParseErrorString{0}", suffix)
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for DiagnosticStrings {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let qualifier = match self.storage_qualifier {
+            Some(ref storage_qualifier) => format!(" {0}", storage_qualifier),
+            None => String::new(),
+        };
+        let unknown = DiagnosticStrings::string_name("Unknown");
+        let mut lines = Vec::new();
+
+        for parse_error in common::ParseError::all() {
+            lines.push((
+                0,
+                format!(
+                    "static const char {0}[]{1} = \"{2}\";",
+                    DiagnosticStrings::string_name(&parse_error.code().to_string()),
+                    qualifier,
+                    parse_error.description()
+                ),
+            ));
+        }
+
+        lines.push((
+            0,
+            format!("static const char {0}[]{1} = \"unknown error\";", unknown, qualifier),
+        ));
+        lines.push((0, String::new()));
+        lines.push((0, "static const char *const kRobustoParseErrorStrings[] = {".to_string()));
+
+        for parse_error in common::ParseError::all() {
+            lines.push((
+                1,
+                format!(
+                    "{0},  // {1}",
+                    DiagnosticStrings::string_name(&parse_error.code().to_string()),
+                    c_parse_error_name(parse_error)
+                ),
+            ));
+        }
+
+        lines.push((0, "};".to_string()));
+        lines.push((0, String::new()));
+        lines.push((0, ApiFunction::parse_error_string().signature()));
+        lines.push((0, "{".to_string()));
+
+        if self.misra_c {
+            lines.push((1, format!("const char *ret = {0};", unknown)));
+            lines.push((0, String::new()));
+            lines.push((1, "if (aError < sizeof(kRobustoParseErrorStrings) / sizeof(kRobustoParseErrorStrings[0])) {".to_string()));
+            lines.push((2, "ret = kRobustoParseErrorStrings[aError];".to_string()));
+            lines.push((1, "}".to_string()));
+            lines.push((0, String::new()));
+            lines.push((1, "return ret;".to_string()));
+        } else {
+            lines.push((1, "if (aError >= sizeof(kRobustoParseErrorStrings) / sizeof(kRobustoParseErrorStrings[0])) {".to_string()));
+            lines.push((2, format!("return {0};", unknown)));
+            lines.push((1, "}".to_string()));
+            lines.push((0, String::new()));
+            lines.push((1, "return kRobustoParseErrorStrings[aError];".to_string()));
+        }
+
+        lines.push((0, "}".to_string()));
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Fields of a frame trace record, in declaration order
+fn trace_record_members() -> [MessageStructMember; 5] {
+    let member = |name: &str, field_base_type| MessageStructMember {
+        name: name.to_string(),
+        field_base_type,
+        array_length: 0usize,
+    };
+
+    [
+        member("timestamp", FieldBaseType::U32),
+
+        // Bytes fed into the parser by the moment of the outcome
+        member("length", FieldBaseType::U32),
+        member("messageId", FieldBaseType::U8),
+        member("accepted", FieldBaseType::U8),
+        member("error", FieldBaseType::U8),
+    ]
+}
+
+/// Frame trace record type, and the constants needed to interpret records:
+/// the ring buffer's depth, and the ID of each message. The timestamp hook,
+/// if any, is declared here for the user to define
+#[derive(Debug)]
+struct FrameTraceDeclarations {
+    depth: usize,
+    message_names: Vec<String>,
+    timestamp_hook: Option<String>,
+}
+
+impl FrameTraceDeclarations {
+    fn record_struct_name() -> &'static str {
+        "struct RobustoTraceRecord"
+    }
+
+    fn message_id_macro_name(message_name: &str) -> String {
+        format!("ROBUSTO_{0}_TRACE_ID", utility::string::to_upper_snake_case(message_name))
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for FrameTraceDeclarations {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = vec![(0, format!("#define ROBUSTO_TRACE_DEPTH {0}u", self.depth))];
+
+        for (message_id, message_name) in self.message_names.iter().enumerate() {
+            lines.push((
+                0,
+                format!(
+                    "#define {0} {1}u",
+                    FrameTraceDeclarations::message_id_macro_name(message_name),
+                    message_id
+                ),
+            ));
+        }
+
+        lines.push((0, format!("{0} {{", FrameTraceDeclarations::record_struct_name())));
+
+        for member in trace_record_members() {
+            lines.push((1, member.declaration()));
+        }
+
+        lines.push((0, "};".to_string()));
+        lines.push((
+            0,
+            format!(
+                "extern {0} robustoTraceRecords[ROBUSTO_TRACE_DEPTH];",
+                FrameTraceDeclarations::record_struct_name()
+            ),
+        ));
+        lines.push((0, "extern uint32_t robustoTraceCount;".to_string()));
+
+        if let Some(ref timestamp_hook) = self.timestamp_hook {
+            lines.push((0, format!("uint32_t {0}(void);", timestamp_hook)));
+        }
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Ring buffer of the latest frame outcomes. The buffer and the counter are
+/// not static, so debuggers and post-mortem tools can locate them by name.
+/// When the output is split, the file holding the buffer and each file
+/// holding parsers get a part of the routines each
+#[derive(Debug)]
+struct FrameTrace {
+    timestamp_hook: Option<String>,
+    storage_qualifier: Option<String>,
+    storage: bool,
+    recorder: bool,
+}
+
+impl FrameTrace {
+    fn storage_lines(&self) -> Vec<(usize, String)> {
+        let qualifier = match self.storage_qualifier {
+            Some(ref storage_qualifier) => format!(" {0}", storage_qualifier),
+            None => String::new(),
+        };
+
+        vec![
+            (
+                0,
+                format!(
+                    "{0} robustoTraceRecords[ROBUSTO_TRACE_DEPTH]{1};",
+                    FrameTraceDeclarations::record_struct_name(),
+                    qualifier
+                ),
+            ),
+            (0, format!("uint32_t robustoTraceCount{0};  // Number of traced frames", qualifier)),
+            (0, String::new()),
+            (0, "// Copies the latest records, oldest first. Returns the number of copied records".to_string()),
+            (0, ApiFunction::trace_dump().signature()),
+            (0, "{".to_string()),
+            (1, "uint32_t n = robustoTraceCount < ROBUSTO_TRACE_DEPTH ? robustoTraceCount : ROBUSTO_TRACE_DEPTH;".to_string()),
+            (1, "uint32_t i;".to_string()),
+            (0, String::new()),
+            (1, "if (n > aCapacity) {".to_string()),
+            (2, "n = aCapacity;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "for (i = 0; i < n; ++i) {".to_string()),
+            (2, "aRecords[i] = robustoTraceRecords[(robustoTraceCount - n + i) % ROBUSTO_TRACE_DEPTH];".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "return (unsigned int)n;".to_string()),
+            (0, "}".to_string()),
+        ]
+    }
+
+    fn recorder_lines(&self) -> Vec<(usize, String)> {
+        let timestamp = match self.timestamp_hook {
+            Some(ref timestamp_hook) => format!("{0}()", timestamp_hook),
+            None => "0u".to_string(),
+        };
+
+        vec![
+            (0, "static void robustoTraceFrame(uint8_t aMessageId, uint8_t aAccepted, uint8_t aError, uint32_t aLength)".to_string()),
+            (0, "{".to_string()),
+            (
+                1,
+                format!(
+                    "{0} *record = &robustoTraceRecords[robustoTraceCount % ROBUSTO_TRACE_DEPTH];",
+                    FrameTraceDeclarations::record_struct_name()
+                ),
+            ),
+            (0, String::new()),
+            (1, format!("record->timestamp = {0};", timestamp)),
+            (1, "record->length = aLength;".to_string()),
+            (1, "record->messageId = aMessageId;".to_string()),
+            (1, "record->accepted = aAccepted;".to_string()),
+            (1, "record->error = aError;".to_string()),
+            (1, "++robustoTraceCount;".to_string()),
+            (0, "}".to_string()),
+        ]
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for FrameTrace {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = Vec::new();
+
+        if self.storage {
+            lines.extend(self.storage_lines());
+        }
+
+        if self.storage && self.recorder {
+            lines.push((0, String::new()));
+        }
+
+        if self.recorder {
+            lines.extend(self.recorder_lines());
+        }
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Banner and folding markers around a section of a generated file. The
+/// markers are comments, so compilers lacking `#pragma region` stay silent
+#[derive(Debug)]
+struct Region {
+    name: String,
+    user_code_anchor: bool,
+}
+
+impl Region {
+    const BANNER_RULE: &'static str =
+        "// =============================================================================";
+}
+
+impl codegen::TreeBasedCodeGeneration for Region {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let lines = [
+            (0, String::new()),
+            (0, Region::BANNER_RULE.to_string()),
+            (0, format!("// region {0}", self.name)),
+            (0, Region::BANNER_RULE.to_string()),
+            (0, String::new()),
+        ];
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = Vec::new();
+
+        if self.user_code_anchor {
+            lines.push((0, user_code::begin_line(&self.name)));
+            lines.push((0, user_code::end_line(&self.name)));
+        }
+
+        lines.push((0, format!("// endregion {0}", self.name)));
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Constants, the callback table, and the state of the dispatcher routing
+/// frames into parsers by message ID
+#[derive(Debug)]
+struct DispatcherDeclarations {
+    dispatcher: common::Dispatcher,
+
+    /// Whether the last enumerator is followed by a comma, as the others are
+    trailing_comma: bool,
+}
+
+impl DispatcherDeclarations {
+    fn callbacks_struct_name(&self) -> String {
+        format!("struct {0}", self.dispatcher.symbol_naming.dispatch_callbacks_struct())
+    }
+
+    fn state_struct_name(&self) -> String {
+        format!("struct {0}", self.dispatcher.symbol_naming.dispatcher_state_struct())
+    }
+
+    fn parser_state_union_name(&self) -> String {
+        format!("union {0}", self.dispatcher.symbol_naming.dispatch_parser_state_union())
+    }
+
+    fn callback_name(message_name: &str) -> String {
+        format!("on{0}", message_name)
+    }
+
+    /// Whether the IDs fit the enum. Enumerators are `int`, which may be as
+    /// narrow as 16 bits
+    fn has_message_id_enum(dispatcher: &common::Dispatcher) -> bool {
+        dispatcher.messages.iter().all(|message| message.id <= i16::MAX as u64)
+    }
+
+    /// Enumerator names of the message ID enum, and their values
+    fn message_id_enumerators(&self) -> Vec<(String, u64)> {
+        if !DispatcherDeclarations::has_message_id_enum(&self.dispatcher) {
+            return Vec::new();
+        }
+
+        self.dispatcher
+            .messages
+            .iter()
+            .map(|message| {
+                (
+                    format!(
+                        "ROBUSTO_MESSAGE_ID_{0}",
+                        utility::string::to_upper_snake_case(&message.message_name)
+                    ),
+                    message.id,
+                )
+            })
+            .collect()
+    }
+
+    /// The message IDs, as an enum, so dispatch tables may be indexed, and
+    /// switched over, with the compiler checking for missing cases
+    fn message_id_enum_lines(&self) -> Vec<(usize, String)> {
+        let enumerators = self.message_id_enumerators();
+
+        if enumerators.is_empty() {
+            return Vec::new();
+        }
+
+        let count = enumerators.len();
+        let mut ret = vec![(0, String::new()), (0, "enum RobustoMessageId {".to_string())];
+
+        for (i, ((name, _), message)) in enumerators.into_iter().zip(&self.dispatcher.messages).enumerate() {
+            let enumerator = format!(
+                "{0} = {1}",
+                name,
+                c_message_id_macro_name(&message.message_name)
+            );
+
+            ret.push((
+                1,
+                match i + 1 < count || self.trailing_comma {
+                    true => format!("{0},", enumerator),
+                    false => enumerator,
+                },
+            ));
+        }
+
+        ret.push((0, "};".to_string()));
+
+        ret
+    }
+
+    /// Macro names and values
+    fn constants(&self) -> Vec<(String, String)> {
+        let mut ret = vec![
+            (
+                "ROBUSTO_DISPATCH_ID_LENGTH".to_string(),
+                format!("{0}u", self.dispatcher.id_length),
+            ),
+            ("ROBUSTO_DISPATCH_PENDING".to_string(), "0u".to_string()),
+            ("ROBUSTO_DISPATCH_UNKNOWN".to_string(), "255u".to_string()),
+        ];
+
+        for message in &self.dispatcher.messages {
+            ret.push((
+                c_message_id_macro_name(&message.message_name),
+                c_integer_literal(message.id as i128, &self.dispatcher.id_base_type()),
+            ));
+        }
+
+        ret
+    }
+
+    fn callbacks_members(&self) -> Vec<String> {
+        let mut ret = vec!["void *context;".to_string()];
+
+        for message in &self.dispatcher.messages {
+            ret.push(format!(
+                "void (*{0})(const struct {1} *a{2}, void *aContext);",
+                DispatcherDeclarations::callback_name(&message.message_name),
+                self.dispatcher.symbol_naming.message_struct(&message.message_name),
+                message.message_name
+            ));
+        }
+
+        ret
+    }
+
+    fn parser_state_union_members(&self) -> Vec<String> {
+        self.dispatcher
+            .messages
+            .iter()
+            .map(|message| {
+                format!(
+                    "struct {0} {1};",
+                    self.dispatcher.symbol_naming.parser_state_struct(&message.message_name),
+                    message.message_name
+                )
+            })
+            .collect()
+    }
+
+    fn state_members(&self) -> Vec<String> {
+        vec![
+            MessageStructMember {
+                name: "id".to_string(),
+                field_base_type: self.dispatcher.id_base_type(),
+                array_length: 0,
+            }
+            .declaration(),
+            "char idBuffer[ROBUSTO_DISPATCH_ID_LENGTH];".to_string(),
+            "uint8_t idLength;".to_string(),
+            "uint8_t selected;".to_string(),
+            "uint8_t accepted;".to_string(),
+            format!("{0} parserState;", self.parser_state_union_name()),
+            format!("union {0} message;", self.dispatcher.symbol_naming.any_message_union()),
+        ]
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for DispatcherDeclarations {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = self
+            .constants()
+            .into_iter()
+            .map(|(name, value)| (0, format!("#define {0} {1}", name, value)))
+            .collect::<Vec<(usize, String)>>();
+
+        lines.extend(self.message_id_enum_lines());
+        lines.push((0, String::new()));
+        lines.push((0, "// Each callback is invoked once its message's frame is accepted, and may be NULL".to_string()));
+        lines.push((0, format!("{0} {{", self.callbacks_struct_name())));
+        lines.extend(self.callbacks_members().into_iter().map(|member| (1, member)));
+        lines.push((0, "};".to_string()));
+        lines.push((0, String::new()));
+        lines.push((0, format!("{0} {{", self.parser_state_union_name())));
+        lines.extend(self.parser_state_union_members().into_iter().map(|member| (1, member)));
+        lines.push((0, "};".to_string()));
+        lines.push((0, String::new()));
+        lines.push((0, "// `selected` is the 1-based index of the message being parsed, `ROBUSTO_DISPATCH_PENDING` while".to_string()));
+        lines.push((0, "// the ID is incomplete, or `ROBUSTO_DISPATCH_UNKNOWN` if no message has the ID".to_string()));
+        lines.push((0, format!("{0} {{", self.state_struct_name())));
+        lines.extend(self.state_members().into_iter().map(|member| (1, member)));
+        lines.push((0, "};".to_string()));
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Accumulates the ID, then feeds the ID bytes and the rest of the frame
+/// into the parser of the identified message. Handles one frame, the state
+/// must be re-initialized before the next one.
+#[derive(Debug)]
+struct Dispatcher {
+    dispatcher: common::Dispatcher,
+}
+
+impl Dispatcher {
+    fn init_lines(&self) -> Vec<(usize, String)> {
+        vec![
+            (0, ApiFunction::dispatcher_init(&self.dispatcher.symbol_naming).signature()),
+            (0, "{".to_string()),
+            (1, "aState->id = 0u;".to_string()),
+            (1, "aState->idLength = 0u;".to_string()),
+            (1, "aState->selected = ROBUSTO_DISPATCH_PENDING;".to_string()),
+            (1, "aState->accepted = 0u;".to_string()),
+            (0, "}".to_string()),
+            (0, String::new()),
+        ]
+    }
+
+    fn id_accumulation_lines(&self) -> Vec<(usize, String)> {
+        let byte = match self.dispatcher.symbol_decoding {
+            Some(_) => "robustoDecodeSymbol((uint8_t)*aInputBuffer)",
+            None => "(uint8_t)*aInputBuffer",
+        };
+        let mut ret = vec![
+            (0, "while (aState->selected == ROBUSTO_DISPATCH_PENDING && aInputBufferLength > 0) {".to_string()),
+            (1, "aState->idBuffer[aState->idLength] = *aInputBuffer;".to_string()),
+            (
+                1,
+                format!(
+                    "aState->id = ({0})((aState->id << 8) | {1});",
+                    c_type_name(&self.dispatcher.id_base_type()),
+                    byte
+                ),
+            ),
+            (1, "++aState->idLength;".to_string()),
+            (1, "++aInputBuffer;".to_string()),
+            (1, "--aInputBufferLength;".to_string()),
+            (0, String::new()),
+            (1, "if (aState->idLength < ROBUSTO_DISPATCH_ID_LENGTH) {".to_string()),
+            (2, "continue;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "switch (aState->id) {".to_string()),
+        ];
+
+        for (message_index, message) in self.dispatcher.messages.iter().enumerate() {
+            let name = &message.message_name;
+            ret.push((
+                2,
+                format!("case {0}:", c_message_id_macro_name(name)),
+            ));
+            ret.push((3, format!("aState->selected = {0}u;", message_index + 1)));
+            ret.push((
+                3,
+                format!(
+                    "{0}(&aState->parserState.{1});",
+                    self.dispatcher.symbol_naming.parser_state_init_function(name),
+                    name
+                ),
+            ));
+            ret.push((
+                3,
+                format!(
+                    "{0}(&aState->parserState.{1}, aState->idBuffer, ROBUSTO_DISPATCH_ID_LENGTH, &aState->message.{1}, 0);",
+                    self.dispatcher.symbol_naming.parsing_function(name),
+                    name
+                ),
+            ));
+            ret.push((3, "break;".to_string()));
+        }
+
+        ret.push((2, "default:".to_string()));
+        ret.push((3, "aState->selected = ROBUSTO_DISPATCH_UNKNOWN;".to_string()));
+        ret.push((3, "break;".to_string()));
+        ret.push((1, "}".to_string()));
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+
+    fn routing_lines(&self) -> Vec<(usize, String)> {
+        let mut ret = vec![(0, "switch (aState->selected) {".to_string())];
+
+        for (message_index, message) in self.dispatcher.messages.iter().enumerate() {
+            let name = &message.message_name;
+            let callback = DispatcherDeclarations::callback_name(name);
+            ret.extend([
+                (1, format!("case {0}u:", message_index + 1)),
+                (2, "if (aInputBufferLength > 0) {".to_string()),
+                (
+                    3,
+                    format!(
+                        "{0}(&aState->parserState.{1}, aInputBuffer, aInputBufferLength, &aState->message.{1}, 0);",
+                        self.dispatcher.symbol_naming.parsing_function(name),
+                        name
+                    ),
+                ),
+                (2, "}".to_string()),
+                (0, String::new()),
+                (
+                    2,
+                    format!(
+                        "if (!aState->accepted && {0}(&aState->parserState.{1})) {{",
+                        self.dispatcher.symbol_naming.acceptance_function(name),
+                        name
+                    ),
+                ),
+                (3, "aState->accepted = 1u;".to_string()),
+                (0, String::new()),
+                (3, format!("if (aCallbacks->{0} != 0) {{", callback)),
+                (
+                    4,
+                    format!("aCallbacks->{0}(&aState->message.{1}, aCallbacks->context);", callback, name),
+                ),
+                (3, "}".to_string()),
+                (2, "}".to_string()),
+                (2, "break;".to_string()),
+            ]);
+        }
+
+        ret.push((1, "default:".to_string()));
+        ret.push((2, "break;".to_string()));
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+
+    fn lines(&self) -> Vec<(usize, String)> {
+        let mut ret = self.init_lines();
+        ret.push((0, ApiFunction::parse_any(&self.dispatcher.symbol_naming).signature()));
+        ret.push((0, "{".to_string()));
+
+        for (indent, line) in self
+            .id_accumulation_lines()
+            .into_iter()
+            .chain([(0, String::new())])
+            .chain(self.routing_lines())
+        {
+            ret.push((indent + 1, line));
+        }
+
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for Dispatcher {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Name of the C enumerator representing the error
+pub fn c_parse_error_name(parse_error: common::ParseError) -> &'static str {
+    match parse_error {
+        common::ParseError::None => "ROBUSTO_PARSE_ERROR_NONE",
+        common::ParseError::ValueOutOfRange => "ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE",
+        common::ParseError::FrameLength => "ROBUSTO_PARSE_ERROR_FRAME_LENGTH",
+        common::ParseError::Checksum => "ROBUSTO_PARSE_ERROR_CHECKSUM",
+        common::ParseError::Length => "ROBUSTO_PARSE_ERROR_LENGTH",
+        common::ParseError::Check => "ROBUSTO_PARSE_ERROR_CHECK",
+        common::ParseError::Overflow => "ROBUSTO_PARSE_ERROR_OVERFLOW",
+    }
+}
+
+/// Name of the C macro holding the ID of the dispatched message
+pub fn c_message_id_macro_name(message_name: &str) -> String {
+    format!("ROBUSTO_{0}_ID", utility::string::to_upper_snake_case(message_name))
+}
+
+/// Name of the C enumerator representing the status
+pub fn c_parse_status_name(parse_status: common::ParseStatus) -> &'static str {
+    match parse_status {
+        common::ParseStatus::Ok => "ROBUSTO_PARSE_STATUS_OK",
+        common::ParseStatus::Incomplete => "ROBUSTO_PARSE_STATUS_INCOMPLETE",
+        common::ParseStatus::ErrorCrc => "ROBUSTO_PARSE_STATUS_ERROR_CRC",
+        common::ParseStatus::ErrorRange => "ROBUSTO_PARSE_STATUS_ERROR_RANGE",
+        common::ParseStatus::ErrorOverflow => "ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW",
+        common::ParseStatus::Error => "ROBUSTO_PARSE_STATUS_ERROR",
+    }
+}
+
+/// Codes stored in the `error` member of parser states, and the statuses
+/// parsing functions return
+#[derive(Debug)]
+struct ParseErrorEnum {
+    /// Whether the last enumerator is followed by a comma, as the others are
+    trailing_comma: bool,
+}
+
+impl ParseErrorEnum {
+    /// Separates the enumerators of an enum
+    fn enumerator_lines(&self, enumerators: Vec<String>, indent: usize) -> Vec<CodeChunk> {
+        let count = enumerators.len();
+
+        enumerators
+            .into_iter()
+            .enumerate()
+            .map(|(i, enumerator)| match i + 1 < count || self.trailing_comma {
+                true => CodeChunk::new(format!("{0},", enumerator), indent, 1usize),
+                false => CodeChunk::new(enumerator, indent, 1usize),
+            })
+            .collect()
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for ParseErrorEnum {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        ret.push(CodeChunk::new(
+            "enum RobustoParseError {".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret.append(&mut self.enumerator_lines(
+            common::ParseError::all()
+                .into_iter()
+                .map(|parse_error| format!("{0} = {1}", c_parse_error_name(parse_error), parse_error.code()))
+                .collect(),
+            code_generation_state.indent + 1,
+        ));
+
+        ret.push(CodeChunk::new(
+            "};".to_string(),
+            code_generation_state.indent,
+            2usize,
+        ));
+        ret.push(CodeChunk::new(
+            "enum RobustoParseStatus {".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret.append(&mut self.enumerator_lines(
+            common::ParseStatus::all()
+                .into_iter()
+                .map(|parse_status| format!("{0} = {1}", c_parse_status_name(parse_status), parse_status.code()))
+                .collect(),
+            code_generation_state.indent + 1,
+        ));
+
+        ret.push(CodeChunk::new(
+            "};".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+/// Type of the members of zero-copy fields
+#[derive(Debug)]
+struct SliceStruct {}
+
+impl codegen::TreeBasedCodeGeneration for SliceStruct {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = vec![
+            (0, "// Bytes of a field, left in the parser's input: the offset counts from the first byte".to_string()),
+            (0, "// passed since the parser state was initialized".to_string()),
+            (0, format!("struct {0} {{", common::SLICE_STRUCT_NAME)),
+        ];
+        lines.extend(
+            common::slice_struct_members()
+                .iter_mut()
+                .map(|member| (1, MessageStructMember::from(member).declaration())),
+        );
+        lines.push((0, "};".to_string()));
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Counters of the outcomes of a parser's frames
+#[derive(Debug)]
+struct StatisticsStruct {}
+
+impl codegen::TreeBasedCodeGeneration for StatisticsStruct {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = vec![
+            (0, "// Counters of a parser, which wrap around. `resyncs` counts the frames a parser has abandoned".to_string()),
+            (0, "// to scan for the sync sequence anew".to_string()),
+            (0, format!("struct {0} {{", common::STATISTICS_STRUCT_NAME)),
+        ];
+        lines.extend(
+            common::statistics_struct_members()
+                .iter_mut()
+                .map(|member| (1, MessageStructMember::from(member).declaration())),
+        );
+        lines.push((0, "};".to_string()));
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Rejects a message, if a field's value is out of its range
+#[derive(Debug)]
+struct RangeCheck {
+    message_name: String,
+    field_name: String,
+    field_base_type: FieldBaseType,
+    min: Option<i64>,
+    max: Option<i64>,
+    layout: MessageLayout,
+}
+
+impl From<&mut common::RangeCheck> for RangeCheck {
+    fn from(value: &mut common::RangeCheck) -> Self {
+        RangeCheck {
+            message_name: value.message_name.clone(),
+            field_name: value.field_name.clone(),
+            field_base_type: value.field_base_type.clone(),
+            min: value.min,
+            max: value.max,
+            layout: value.layout,
+        }
+    }
+}
+
+impl RangeCheck {
+    fn lines(&self) -> Vec<(usize, String)> {
+        let member = format!("a{0}->{1}", self.message_name, self.field_name);
+        let mut conditions = Vec::new();
+
+        if let Some(min) = self.min {
+            conditions.push(format!(
+                "{0} < {1}",
+                member,
+                c_integer_literal(i128::from(min), &self.field_base_type)
+            ));
+        }
+
+        if let Some(max) = self.max {
+            conditions.push(format!(
+                "{0} > {1}",
+                member,
+                c_integer_literal(i128::from(max), &self.field_base_type)
+            ));
+        }
+
+        let mut ret = vec![(0, format!("if ({0}) {{", conditions.join(" || ")))];
+
+        match self.layout {
+            MessageLayout::Bytes => {
+                ret.push((
+                    1,
+                    format!(
+                        "aParserState->{0} = {1};",
+                        common::PARSE_ERROR_MEMBER_NAME,
+                        c_parse_error_name(common::ParseError::ValueOutOfRange)
+                    ),
+                ));
+                ret.push((1, format!("fgoto *{0}_error;", self.message_name)));
+            }
+            MessageLayout::Bits => ret.push((1, "return -2;".to_string())),
+        }
+
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for RangeCheck {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Name of the routine advancing a register of the CRC by one byte. The
+/// initial value and the final XOR are applied by the caller, so algorithms
+/// differing only in those share the routine
+fn crc_routine_name(algorithm: &representation::CrcAlgorithm) -> String {
+    format!(
+        "robustoCrc{0}_{1:02$X}{3}",
+        algorithm.width,
+        algorithm.polynomial,
+        algorithm.width.div_ceil(4),
+        if algorithm.reflected { "Reflected" } else { "" }
+    )
+}
+
+/// Type of a CRC register
+fn crc_register_type(algorithm: &representation::CrcAlgorithm) -> FieldBaseType {
+    FieldBaseType::from_bit_width(algorithm.width, false)
+}
+
+/// Formats a CRC register value as a hexadecimal constant of the register's
+/// type
+fn crc_literal(algorithm: &representation::CrcAlgorithm, value: u64) -> String {
+    let literal = format!("0x{0:01$X}", value, algorithm.width.div_ceil(4));
+
+    match crc_register_type(algorithm) {
+        FieldBaseType::U64 => format!("UINT64_C({0})", literal),
+        _ => format!("{0}u", literal),
+    }
+}
+
+/// Truncates an expression to the register's width. Registers narrower than
+/// their type are masked
+fn crc_register_cast(algorithm: &representation::CrcAlgorithm, expression: &str) -> String {
+    let register_type = crc_register_type(algorithm);
+
+    match register_type {
+        FieldBaseType::U8 | FieldBaseType::U16 | FieldBaseType::U32 | FieldBaseType::U64
+            if [8usize, 16, 32, 64].contains(&algorithm.width) =>
+        {
+            format!("({0})({1})", c_type_name(&register_type), expression)
+        }
+        _ => format!(
+            "({0})(({1}) & {2})",
+            c_type_name(&register_type),
+            expression,
+            crc_literal(algorithm, (1u64 << algorithm.width) - 1)
+        ),
+    }
+}
+
+/// Definitions of the CRC routines the protocol's checksums employ
+#[derive(Debug)]
+pub(crate) struct ChecksumRoutines {
+    pub(crate) algorithms: Vec<representation::CrcAlgorithm>,
+    pub(crate) implementation: representation::ChecksumImplementation,
+}
+
+impl ChecksumRoutines {
+    fn table_name(algorithm: &representation::CrcAlgorithm) -> String {
+        format!("k{0}Table", utility::string::capitalize(&crc_routine_name(algorithm)))
+    }
+
+    /// Each byte is looked up in a table of precomputed register values
+    fn table_lines(algorithm: &representation::CrcAlgorithm) -> Vec<(usize, String)> {
+        let table_name = ChecksumRoutines::table_name(algorithm);
+        let values_per_line = match algorithm.width {
+            0..=16 => 8usize,
+            17..=32 => 4usize,
+            _ => 2usize,
+        };
+        let mut ret = vec![(
+            0,
+            format!(
+                "static const {0} {1}[256] = {{",
+                c_type_name(&crc_register_type(algorithm)),
+                table_name
+            ),
+        )];
+
+        for chunk in algorithm.table().chunks(values_per_line) {
+            ret.push((
+                1,
+                format!(
+                    "{0},",
+                    chunk
+                        .iter()
+                        .map(|value| crc_literal(algorithm, *value))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            ));
+        }
+
+        ret.push((0, "};".to_string()));
+        ret.push((0, String::new()));
+        ret.push((0, ApiFunction::crc_update(algorithm).signature()));
+        ret.push((0, "{".to_string()));
+
+        let expression = match (algorithm.width, algorithm.reflected) {
+            // The register is shifted out entirely
+            (8, _) => format!("{0}[aCrc ^ aByte]", table_name),
+            (_, true) => crc_register_cast(
+                algorithm,
+                &format!("(aCrc >> 8) ^ {0}[(aCrc ^ aByte) & 0xFFu]", table_name),
+            ),
+            (width, false) => crc_register_cast(
+                algorithm,
+                &format!(
+                    "(aCrc << 8) ^ {0}[((aCrc >> {1}) ^ aByte) & 0xFFu]",
+                    table_name,
+                    width - 8
+                ),
+            ),
+        };
+        ret.push((1, format!("return {0};", expression)));
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+
+    /// Each byte is shifted through the register bit by bit
+    fn bitwise_lines(algorithm: &representation::CrcAlgorithm) -> Vec<(usize, String)> {
+        let register_type = c_type_name(&crc_register_type(algorithm));
+        let polynomial = crc_literal(algorithm, algorithm.register_polynomial());
+        let mut ret = vec![
+            (0, ApiFunction::crc_update(algorithm).signature()),
+            (0, "{".to_string()),
+            (1, "unsigned int i;".to_string()),
+            (0, String::new()),
+        ];
+
+        let (byte, top_bit, shift) = match algorithm.reflected {
+            true => ("aByte".to_string(), "1u".to_string(), "aCrc >> 1"),
+            false if algorithm.width == 8 => (
+                "aByte".to_string(),
+                crc_literal(algorithm, 0x80u64),
+                "aCrc << 1",
+            ),
+            false => (
+                format!("(({0})aByte << {1})", register_type, algorithm.width - 8),
+                crc_literal(algorithm, 1u64 << (algorithm.width - 1)),
+                "aCrc << 1",
+            ),
+        };
+        ret.push((1, format!("aCrc ^= {0};", byte)));
+        ret.push((0, String::new()));
+        ret.push((1, "for (i = 0; i < 8u; ++i) {".to_string()));
+        ret.push((
+            2,
+            format!(
+                "aCrc = (aCrc & {0}) ? {1} : {2};",
+                top_bit,
+                crc_register_cast(algorithm, &format!("({0}) ^ {1}", shift, polynomial)),
+                crc_register_cast(algorithm, shift)
+            ),
+        ));
+        ret.push((1, "}".to_string()));
+        ret.push((0, String::new()));
+        ret.push((1, "return aCrc;".to_string()));
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for ChecksumRoutines {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = Vec::new();
+
+        for algorithm in &self.algorithms {
+            if !lines.is_empty() {
+                lines.push((0, String::new()));
+            }
+
+            match self.implementation {
+                representation::ChecksumImplementation::Table => {
+                    lines.extend(ChecksumRoutines::table_lines(algorithm))
+                }
+                representation::ChecksumImplementation::Bitwise => {
+                    lines.extend(ChecksumRoutines::bitwise_lines(algorithm))
+                }
+                representation::ChecksumImplementation::Extern => {}
+            }
+        }
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Maintains a checksum register in the parser state, and rejects the frame,
+/// if the checksum field does not match it
+#[derive(Debug)]
+struct ChecksumAction {
+    kind: common::ChecksumActionKind,
+    message_name: String,
+    field_name: String,
+    checksum_name: String,
+    algorithm: representation::CrcAlgorithm,
+}
+
+impl From<&mut common::ChecksumAction> for ChecksumAction {
+    fn from(value: &mut common::ChecksumAction) -> Self {
+        ChecksumAction {
+            kind: value.kind,
+            message_name: value.message_name.clone(),
+            field_name: value.field_name.clone(),
+            checksum_name: value.checksum_name.clone(),
+            algorithm: value.algorithm.clone(),
+        }
+    }
+}
+
+impl ChecksumAction {
+    fn lines(&self) -> Vec<(usize, String)> {
+        let register = format!(
+            "aParserState->{0}",
+            common::checksum_member_name(&self.checksum_name)
+        );
+
+        match self.kind {
+            common::ChecksumActionKind::Reset => vec![(
+                0,
+                format!("{0} = {1};", register, crc_literal(&self.algorithm, self.algorithm.init)),
+            )],
+            common::ChecksumActionKind::Update => vec![(
+                0,
+                format!(
+                    "{0} = {1}({0}, (uint8_t)fc);",
+                    register,
+                    crc_routine_name(&self.algorithm)
+                ),
+            )],
+            common::ChecksumActionKind::Check => {
+                let expected = match self.algorithm.xor_out {
+                    0 => register,
+                    xor_out => crc_register_cast(
+                        &self.algorithm,
+                        &format!("{0} ^ {1}", register, crc_literal(&self.algorithm, xor_out)),
+                    ),
+                };
+
+                vec![
+                    (
+                        0,
+                        format!(
+                            "if (a{0}->{1} != {2}) {{",
+                            self.message_name, self.field_name, expected
+                        ),
+                    ),
+                    (
+                        1,
+                        format!(
+                            "aParserState->{0} = {1};",
+                            common::PARSE_ERROR_MEMBER_NAME,
+                            c_parse_error_name(common::ParseError::Checksum)
+                        ),
+                    ),
+                    (1, format!("fgoto *{0}_error;", self.message_name)),
+                    (0, "}".to_string()),
+                ]
+            }
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for ChecksumAction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Counts the bytes a length field covers in the parser state, and rejects
+/// the frame, if the length field does not match the count
+#[derive(Debug)]
+struct LengthAction {
+    kind: common::LengthActionKind,
+    message_name: String,
+    field_name: String,
+    length: Option<common::MessageExpression>,
+}
+
+impl From<&mut common::LengthAction> for LengthAction {
+    fn from(value: &mut common::LengthAction) -> Self {
+        LengthAction {
+            kind: value.kind,
+            message_name: value.message_name.clone(),
+            field_name: value.field_name.clone(),
+            length: value.length.clone(),
+        }
+    }
+}
+
+impl LengthAction {
+    fn lines(&self) -> Vec<(usize, String)> {
+        let counter = format!(
+            "aParserState->{0}",
+            common::covered_length_member_name(&self.field_name)
+        );
+        let length = match self.length {
+            Some(ref length) => c_expression(length),
+            None => format!("a{0}->{1}", self.message_name, self.field_name),
+        };
+
+        match self.kind {
+            common::LengthActionKind::Reset => vec![(0, format!("{0} = 0;", counter))],
+            common::LengthActionKind::Count => vec![(0, format!("++{0};", counter))],
+            common::LengthActionKind::Check => vec![
+                (
+                    0,
+                    format!("if ({0} != {1}) {{", length, counter),
+                ),
+                (
+                    1,
+                    format!(
+                        "aParserState->{0} = {1};",
+                        common::PARSE_ERROR_MEMBER_NAME,
+                        c_parse_error_name(common::ParseError::Length)
+                    ),
+                ),
+                (1, format!("fgoto *{0}_error;", self.message_name)),
+                (0, "}".to_string()),
+            ],
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for LengthAction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Constants for sizing RTOS queues and buffers: the largest message struct,
+/// and the bounds of each message's frame length
+#[derive(Debug)]
+struct SizingConstants {
+    message_names: Vec<String>,
+    symbol_naming: SymbolNaming,
+
+    /// Max frame length of each message, in bytes
+    frame_lengths: Vec<usize>,
+
+    /// Min frame length of each message, in bytes
+    min_frame_lengths: Vec<usize>,
+}
+
+impl From<&Protocol> for SizingConstants {
+    fn from(protocol: &Protocol) -> Self {
+        SizingConstants {
+            message_names: protocol.messages.iter().map(|m| m.name.clone()).collect(),
+            symbol_naming: protocol.symbol_naming(),
+            frame_lengths: protocol
+                .messages
+                .iter()
+                .map(|message| protocol.frame_size(message).max)
+                .collect(),
+            min_frame_lengths: protocol
+                .messages
+                .iter()
+                .map(|message| protocol.frame_size(message).min)
+                .collect(),
+        }
+    }
+}
+
+impl SizingConstants {
+    /// Macro names and values of the frame length bounds
+    fn frame_size_constants(&self) -> Vec<(String, String)> {
+        let mut ret = Vec::new();
+
+        for ((message_name, min_frame_length), frame_length) in
+            self.message_names.iter().zip(&self.min_frame_lengths).zip(&self.frame_lengths)
+        {
+            let message_name = utility::string::to_upper_snake_case(message_name);
+            ret.push((format!("ROBUSTO_{0}_MIN_FRAME_SIZE", message_name), format!("{0}u", min_frame_length)));
+            ret.push((format!("ROBUSTO_{0}_MAX_FRAME_SIZE", message_name), format!("{0}u", frame_length)));
+        }
+
+        ret.push((
+            "ROBUSTO_MIN_FRAME_SIZE".to_string(),
+            format!("{0}u", self.min_frame_lengths.iter().min().unwrap_or(&0usize)),
+        ));
+        ret.push((
+            "ROBUSTO_MAX_FRAME_SIZE".to_string(),
+            format!("{0}u", self.frame_lengths.iter().max().unwrap_or(&0usize)),
+        ));
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for SizingConstants {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let any_message_union = self.symbol_naming.any_message_union();
+        let mut lines = vec![(0, format!("union {0} {{", any_message_union))];
+
+        for message_name in &self.message_names {
+            lines.push((
+                1,
+                format!("struct {0} {1};", self.symbol_naming.message_struct(message_name), message_name),
+            ));
+        }
+
+        lines.push((0, "};".to_string()));
+        lines.push((
+            0,
+            format!("#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union {0}))", any_message_union),
+        ));
+
+        for (name, value) in self.frame_size_constants() {
+            lines.push((0, format!("#define {0} {1}", name, value)));
+        }
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Shifts a byte of a reserved field into its debug member
+#[derive(Debug)]
+struct ReservedFieldByte {
+    message_name: String,
+    field_name: String,
+    length: usize,
+}
+
+impl From<&mut common::ReservedFieldByte> for ReservedFieldByte {
+    fn from(value: &mut common::ReservedFieldByte) -> Self {
+        ReservedFieldByte {
+            message_name: value.message_name.clone(),
+            field_name: value.field_name.clone(),
+            length: value.length,
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for ReservedFieldByte {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let member = format!("a{0}->{1}", self.message_name, self.field_name);
+        let lines = vec![
+            (0, "unsigned int i;".to_string()),
+            (0, String::new()),
+            (0, format!("for (i = 1; i < {0}u; ++i) {{", self.length)),
+            (1, format!("{0}[i - 1] = {0}[i];", member)),
+            (0, "}".to_string()),
+            (0, format!("{0}[{1}] = (uint8_t)fc;", member, self.length - 1)),
+        ];
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Skips a reserved field of a bit-granular message, copying its bytes into
+/// the debug member, if there is one
+#[derive(Debug)]
+struct BitstreamReservedField {
+    message_name: String,
+    field_name: String,
+    length: usize,
+    debug_member: bool,
+    bit_order: representation::BitOrder,
+}
+
+impl From<&mut common::BitstreamReservedField> for BitstreamReservedField {
+    fn from(value: &mut common::BitstreamReservedField) -> Self {
+        BitstreamReservedField {
+            message_name: value.message_name.clone(),
+            field_name: value.field_name.clone(),
+            length: value.length,
+            debug_member: value.debug_member,
+            bit_order: value.bit_order,
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for BitstreamReservedField {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let lines = if self.debug_member {
+            vec![
+                (0, "{".to_string()),
+                (1, "unsigned int i;".to_string()),
+                (0, String::new()),
+                (1, format!("for (i = 0; i < {0}u; ++i) {{", self.length)),
+                (
+                    2,
+                    format!(
+                        "a{0}->{1}[i] = (uint8_t){2}(aInputBuffer, bitOffset, 8u);",
+                        self.message_name,
+                        self.field_name,
+                        bit_reader_name(self.bit_order)
+                    ),
+                ),
+                (2, "bitOffset += 8u;".to_string()),
+                (1, "}".to_string()),
+                (0, "}".to_string()),
+            ]
+        } else {
+            vec![(0, format!("bitOffset += {0}u;  // Reserved", self.length * 8))]
+        };
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// How a function treats the memory a pointer parameter refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerAccess {
+    Read,
+    ReadWrite,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiPointer {
+    pub is_const: bool,
+    pub access: PointerAccess,
+    pub nullable: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiParameter {
+    pub name: String,
+
+    /// Type of the parameter, or of the pointee, if it is a pointer
+    pub type_name: String,
+
+    /// `None` for parameters passed by value
+    pub pointer: Option<ApiPointer>,
+}
+
+impl ApiParameter {
+    fn by_value(name: &str, type_name: &str) -> ApiParameter {
+        ApiParameter {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            pointer: None,
+        }
+    }
+
+    fn by_pointer(name: &str, type_name: &str, access: PointerAccess) -> ApiParameter {
+        ApiParameter {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            pointer: Some(ApiPointer {
+                is_const: access == PointerAccess::Read,
+                access,
+                nullable: false,
+            }),
+        }
+    }
+
+    /// Lets the pointer be NULL
+    fn nullable(mut self) -> ApiParameter {
+        if let Some(ref mut pointer) = self.pointer {
+            pointer.nullable = true;
+        }
+
+        self
+    }
+
+    /// A pointer to a function, passed by value, e.g. `int (*aPrint)(const
+    /// char *aFormat, ...)`
+    fn function_pointer(name: &str, return_type: &str, parameters: &str) -> ApiParameter {
+        ApiParameter {
+            name: name.to_string(),
+            type_name: format!("{0} (*)({1})", return_type, parameters),
+            pointer: None,
+        }
+    }
+
+    fn declaration(&self) -> String {
+        match self.pointer {
+            None if self.type_name.contains("(*)") => {
+                self.type_name.replacen("(*)", &format!("(*{0})", self.name), 1)
+            }
+            Some(ref pointer) if pointer.is_const => {
+                format!("const {0} *{1}", self.type_name, self.name)
+            }
+            Some(_) => format!("{0} *{1}", self.type_name, self.name),
+            None => format!("{0} {1}", self.type_name, self.name),
+        }
+    }
+}
+
+/// A function the generated code exposes to its users
+#[derive(Debug, Clone)]
+pub struct ApiFunction {
+    pub name: String,
+    pub return_type: String,
+    pub parameters: Vec<ApiParameter>,
+}
+
+impl ApiFunction {
+    pub fn signature(&self) -> String {
+        let parameters = if self.parameters.is_empty() {
+            "void".to_string()
+        } else {
+            self.parameters
+                .iter()
+                .map(ApiParameter::declaration)
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        // Pointer return types keep the asterisk next to the name
+        let separator = if self.return_type.ends_with('*') { "" } else { " " };
+
+        format!("{0}{1}{2}({3})", self.return_type, separator, self.name, parameters)
+    }
+
+    /// Pointer parameters which must not be NULL
+    pub fn non_null_parameters(&self) -> Vec<&str> {
+        self.parameters
+            .iter()
+            .filter(|parameter| matches!(parameter.pointer, Some(ref pointer) if !pointer.nullable))
+            .map(|parameter| parameter.name.as_str())
+            .collect()
+    }
+
+    fn parser_state_init(symbol_naming: &SymbolNaming, message_name: &str) -> ApiFunction {
+        ApiFunction {
+            name: symbol_naming.parser_state_init_function(message_name),
+            return_type: "void".to_string(),
+            parameters: vec![ApiParameter::by_pointer(
+                "aParserState",
+                &format!("struct {0}", symbol_naming.parser_state_struct(message_name)),
+                PointerAccess::ReadWrite,
+            )],
+        }
+    }
+
+    /// Returns the counters of the parser, by value
+    fn statistics_accessor(symbol_naming: &SymbolNaming, message_name: &str) -> ApiFunction {
+        ApiFunction {
+            name: symbol_naming.statistics_function(message_name),
+            return_type: format!("struct {0}", common::STATISTICS_STRUCT_NAME),
+            parameters: vec![ApiParameter::by_pointer(
+                "aParserState",
+                &format!("struct {0}", symbol_naming.parser_state_struct(message_name)),
+                PointerAccess::Read,
+            )],
+        }
+    }
+
+    /// Returns a `RobustoParseStatus`, and tells how many bytes of the input
+    /// the machine has taken through `aConsumedLength`
+    fn parsing(symbol_naming: &SymbolNaming, message_name: &str) -> ApiFunction {
+        ApiFunction {
+            name: symbol_naming.parsing_function(message_name),
+            return_type: "enum RobustoParseStatus".to_string(),
+            parameters: vec![
+                ApiParameter::by_pointer(
+                    "aParserState",
+                    &format!("struct {0}", symbol_naming.parser_state_struct(message_name)),
+                    PointerAccess::ReadWrite,
+                ),
+                ApiParameter::by_pointer("aInputBuffer", "char", PointerAccess::Read),
+                ApiParameter::by_value("aInputBufferLength", "int"),
+                ApiParameter::by_pointer(
+                    &format!("a{0}", message_name),
+                    &format!("struct {0}", symbol_naming.message_struct(message_name)),
+                    PointerAccess::ReadWrite,
+                ),
+                ApiParameter::by_pointer("aConsumedLength", "int", PointerAccess::ReadWrite).nullable(),
+            ],
+        }
+    }
+
+    /// Like `parsing`, but invokes the callback on each frame the parser accepts
+    fn callback_parsing(symbol_naming: &SymbolNaming, message_name: &str) -> ApiFunction {
+        let mut ret = ApiFunction::parsing(symbol_naming, message_name);
+        ret.name = symbol_naming.callback_parsing_function(message_name);
+        ret.parameters.push(
+            ApiParameter::by_pointer(
+                "aCallbacks",
+                &format!("struct {0}", symbol_naming.callbacks_struct(message_name)),
+                PointerAccess::Read,
+            )
+            .nullable(),
+        );
+
+        ret
+    }
+
+    /// Returns the length of the frame written into the buffer, or -1, if the
+    /// buffer may be too short for it
+    fn serializing(symbol_naming: &SymbolNaming, message_name: &str) -> ApiFunction {
+        ApiFunction {
+            name: symbol_naming.serializing_function(message_name),
+            return_type: "int".to_string(),
+            parameters: vec![
+                ApiParameter::by_pointer(
+                    &format!("a{0}", message_name),
+                    &format!("struct {0}", symbol_naming.message_struct(message_name)),
+                    PointerAccess::Read,
+                ),
+                ApiParameter::by_pointer("aBuffer", "uint8_t", PointerAccess::ReadWrite),
+                ApiParameter::by_value("aCapacity", "size_t"),
+            ],
+        }
+    }
+
+    /// Clears the message struct, and sets the fields of constant values
+    fn init(symbol_naming: &SymbolNaming, message_name: &str) -> ApiFunction {
+        ApiFunction {
+            name: symbol_naming.init_function(message_name),
+            return_type: "void".to_string(),
+            parameters: vec![ApiParameter::by_pointer(
+                &format!("a{0}", message_name),
+                &format!("struct {0}", symbol_naming.message_struct(message_name)),
+                PointerAccess::ReadWrite,
+            )],
+        }
+    }
+
+    /// Returns 1, if the messages' fields are equal, 0 otherwise
+    fn equality(symbol_naming: &SymbolNaming, message_name: &str) -> ApiFunction {
+        let message_struct = format!("struct {0}", symbol_naming.message_struct(message_name));
+
+        ApiFunction {
+            name: symbol_naming.equality_function(message_name),
+            return_type: "int".to_string(),
+            parameters: vec![
+                ApiParameter::by_pointer("aLeft", &message_struct, PointerAccess::Read),
+                ApiParameter::by_pointer("aRight", &message_struct, PointerAccess::Read),
+            ],
+        }
+    }
+
+    /// Prints the message's fields through a `printf`-like function
+    fn dump(symbol_naming: &SymbolNaming, message_name: &str) -> ApiFunction {
+        ApiFunction {
+            name: symbol_naming.dump_function(message_name),
+            return_type: "void".to_string(),
+            parameters: vec![
+                ApiParameter::by_pointer(
+                    &format!("a{0}", message_name),
+                    &format!("struct {0}", symbol_naming.message_struct(message_name)),
+                    PointerAccess::Read,
+                ),
+                ApiParameter::function_pointer("aPrint", "int", "const char *aFormat, ..."),
+            ],
+        }
+    }
+
+    fn acceptance(symbol_naming: &SymbolNaming, message_name: &str) -> ApiFunction {
+        ApiFunction {
+            name: symbol_naming.acceptance_function(message_name),
+            return_type: "int".to_string(),
+            parameters: vec![ApiParameter::by_pointer(
+                "aParserState",
+                &format!("struct {0}", symbol_naming.parser_state_struct(message_name)),
+                PointerAccess::Read,
+            )],
+        }
+    }
+
+    fn bitstream_parsing(symbol_naming: &SymbolNaming, message_name: &str) -> ApiFunction {
+        ApiFunction {
+            name: symbol_naming.parsing_function(message_name),
+            return_type: "int".to_string(),
+            parameters: vec![
+                ApiParameter::by_pointer("aInputBuffer", "uint8_t", PointerAccess::Read),
+                ApiParameter::by_value("aInputBufferLength", "int"),
+                ApiParameter::by_pointer(
+                    &format!("a{0}", message_name),
+                    &format!("struct {0}", symbol_naming.message_struct(message_name)),
+                    PointerAccess::ReadWrite,
+                ),
+            ],
+        }
+    }
+
+    /// The name carries the unit of the physical value, if known
+    fn scaled_value_accessor(
+        symbol_naming: &SymbolNaming,
+        message_name: &str,
+        field_name: &str,
+        unit: Option<&str>,
+    ) -> ApiFunction {
+        let field_name = utility::string::capitalize(field_name);
+        let (object, unit) = match unit {
+            Some(unit) => (field_name, format!("_{0}", utility::string::unit_to_identifier(unit))),
+            None => (format!("{0}Scaled", field_name), String::new()),
+        };
+
+        ApiFunction {
+            name: symbol_naming.field_accessor_function("get", message_name, &object, &unit),
+            return_type: c_type_name(&FieldBaseType::F32),
+            parameters: vec![ApiParameter::by_pointer(
+                &format!("a{0}", message_name),
+                &format!("struct {0}", symbol_naming.message_struct(message_name)),
+                PointerAccess::Read,
+            )],
+        }
+    }
+
+    fn flags_accessor(
+        symbol_naming: &SymbolNaming,
+        kind: common::FlagsAccessorKind,
+        message_name: &str,
+        field_name: &str,
+        field_base_type: &FieldBaseType,
+    ) -> ApiFunction {
+        let (prefix, return_type, access) = match kind {
+            common::FlagsAccessorKind::Test => ("test", "int", PointerAccess::Read),
+            common::FlagsAccessorKind::Set => ("set", "void", PointerAccess::ReadWrite),
+        };
+        let mut parameters = vec![
+            ApiParameter::by_pointer(
+                &format!("a{0}", message_name),
+                &format!("struct {0}", symbol_naming.message_struct(message_name)),
+                access,
+            ),
+            ApiParameter::by_value("aFlags", &c_type_name(field_base_type)),
+        ];
+
+        if kind == common::FlagsAccessorKind::Set {
+            parameters.push(ApiParameter::by_value("aValue", "int"));
+        }
+
+        ApiFunction {
+            name: symbol_naming.field_accessor_function(
+                prefix,
+                message_name,
+                &utility::string::capitalize(field_name),
+                "",
+            ),
+            return_type: return_type.to_string(),
+            parameters,
+        }
+    }
+
+    /// Milliseconds are spelled in the name, as scaled accessors spell their
+    /// units
+    fn timestamp_accessor(
+        symbol_naming: &SymbolNaming,
+        kind: common::TimestampAccessorKind,
+        message_name: &str,
+        field_name: &str,
+    ) -> ApiFunction {
+        let message_parameter = |access| {
+            ApiParameter::by_pointer(
+                &format!("a{0}", message_name),
+                &format!("struct {0}", symbol_naming.message_struct(message_name)),
+                access,
+            )
+        };
+        let (prefix, return_type, parameters) = match kind {
+            common::TimestampAccessorKind::Get => ("get", "int64_t", vec![message_parameter(PointerAccess::Read)]),
+            common::TimestampAccessorKind::Set => (
+                "set",
+                "void",
+                vec![
+                    message_parameter(PointerAccess::ReadWrite),
+                    ApiParameter::by_value("aMilliseconds", "int64_t"),
+                ],
+            ),
+        };
+
+        ApiFunction {
+            name: symbol_naming.field_accessor_function(
+                prefix,
+                message_name,
+                &utility::string::capitalize(field_name),
+                "_ms",
+            ),
+            return_type: return_type.to_string(),
+            parameters,
+        }
+    }
+
+    fn self_test() -> ApiFunction {
+        ApiFunction {
+            name: "selfTest".to_string(),
+            return_type: "int".to_string(),
+            parameters: Vec::new(),
+        }
+    }
+
+    fn benchmark() -> ApiFunction {
+        ApiFunction {
+            name: "robustoBenchmark".to_string(),
+            return_type: "void".to_string(),
+            parameters: vec![ApiParameter::by_pointer(
+                "aResults",
+                "struct RobustoBenchmarkResult",
+                PointerAccess::ReadWrite,
+            )],
+        }
+    }
+
+    fn trace_dump() -> ApiFunction {
+        ApiFunction {
+            name: "robustoTraceDump".to_string(),
+            return_type: "unsigned int".to_string(),
+            parameters: vec![
+                ApiParameter::by_pointer(
+                    "aRecords",
+                    "struct RobustoTraceRecord",
+                    PointerAccess::ReadWrite,
+                ),
+                ApiParameter::by_value("aCapacity", "unsigned int"),
+            ],
+        }
+    }
+
+    fn dispatcher_init(symbol_naming: &SymbolNaming) -> ApiFunction {
+        ApiFunction {
+            name: symbol_naming.dispatcher_init_function(),
+            return_type: "void".to_string(),
+            parameters: vec![ApiParameter::by_pointer(
+                "aState",
+                &format!("struct {0}", symbol_naming.dispatcher_state_struct()),
+                PointerAccess::ReadWrite,
+            )],
+        }
+    }
+
+    fn parse_any(symbol_naming: &SymbolNaming) -> ApiFunction {
+        ApiFunction {
+            name: symbol_naming.dispatching_function(),
+            return_type: "void".to_string(),
+            parameters: vec![
+                ApiParameter::by_pointer(
+                    "aState",
+                    &format!("struct {0}", symbol_naming.dispatcher_state_struct()),
+                    PointerAccess::ReadWrite,
+                ),
+                ApiParameter::by_pointer("aInputBuffer", "char", PointerAccess::Read),
+                ApiParameter::by_value("aInputBufferLength", "int"),
+                ApiParameter::by_pointer(
+                    "aCallbacks",
+                    &format!("struct {0}", symbol_naming.dispatch_callbacks_struct()),
+                    PointerAccess::Read,
+                ),
+            ],
+        }
+    }
+
+    pub(crate) fn crc_update(algorithm: &representation::CrcAlgorithm) -> ApiFunction {
+        let register_type = c_type_name(&crc_register_type(algorithm));
+
+        ApiFunction {
+            name: crc_routine_name(algorithm),
+            return_type: register_type.clone(),
+            parameters: vec![
+                ApiParameter::by_value("aCrc", &register_type),
+                ApiParameter::by_value("aByte", "uint8_t"),
+            ],
+        }
+    }
+
+    fn parse_error_string() -> ApiFunction {
+        ApiFunction {
+            name: "robustoParseErrorString".to_string(),
+            return_type: "const char *".to_string(),
+            parameters: vec![ApiParameter::by_value("aError", "uint8_t")],
+        }
+    }
+}
+
+/// Enumerates the functions the generated code exposes for the protocol
+pub fn api_functions(protocol: &Protocol) -> Vec<ApiFunction> {
+    api_functions_of_part(protocol, &common::OutputPart::Whole)
+}
+
+/// Enumerates the functions the given part of the generated code exposes
+fn api_functions_of_part(protocol: &Protocol, part: &common::OutputPart) -> Vec<ApiFunction> {
+    let mut ret = Vec::new();
+    let symbol_naming = protocol.symbol_naming();
+
+    for message in protocol
+        .messages
+        .iter()
+        .filter(|message| part.includes_message(&message.name))
+    {
+        match message.layout() {
+            MessageLayout::Bytes => {
+                ret.push(ApiFunction::parser_state_init(&symbol_naming, &message.name));
+                ret.push(ApiFunction::parsing(&symbol_naming, &message.name));
+
+                if protocol.callbacks() {
+                    ret.push(ApiFunction::callback_parsing(&symbol_naming, &message.name));
+                }
+
+                ret.push(ApiFunction::acceptance(&symbol_naming, &message.name));
+
+                if protocol.parser_statistics(message) {
+                    ret.push(ApiFunction::statistics_accessor(&symbol_naming, &message.name));
+                }
+
+                if protocol.serializers() && common::Serializer::from_message(protocol, message).is_ok() {
+                    ret.push(ApiFunction::serializing(&symbol_naming, &message.name));
+                }
+            }
+            MessageLayout::Bits => ret.push(ApiFunction::bitstream_parsing(&symbol_naming, &message.name)),
+        }
+
+        for accessor in common::ScaledValueAccessor::from_message(&symbol_naming, message) {
+            ret.push(ApiFunction::scaled_value_accessor(
+                &symbol_naming,
+                &accessor.message_name,
+                &accessor.field_name,
+                accessor.unit.as_deref(),
+            ));
+        }
+
+        for accessor in common::FlagsAccessor::from_message(&symbol_naming, message) {
+            ret.push(ApiFunction::flags_accessor(
+                &symbol_naming,
+                accessor.kind,
+                &accessor.message_name,
+                &accessor.field_name,
+                &accessor.field_base_type,
+            ));
+        }
+
+        for accessor in common::TimestampAccessor::from_message(&symbol_naming, message) {
+            ret.push(ApiFunction::timestamp_accessor(
+                &symbol_naming,
+                accessor.kind,
+                &accessor.message_name,
+                &accessor.field_name,
+            ));
+        }
+
+        if protocol.struct_functions() {
+            ret.push(ApiFunction::init(&symbol_naming, &message.name));
+            ret.push(ApiFunction::equality(&symbol_naming, &message.name));
+        }
+
+        if protocol.debug_dump() {
+            ret.push(ApiFunction::dump(&symbol_naming, &message.name));
+        }
+    }
+
+    // The runtime library exposes the routines of protocols relying on it
+    if part.includes_shared() && !protocol.uses_runtime() {
+        for algorithm in protocol.crc_algorithms() {
+            ret.push(ApiFunction::crc_update(algorithm));
+        }
+    }
+
+    if protocol.self_test() && part.includes_shared() {
+        ret.push(ApiFunction::self_test());
+    }
+
+    if protocol.benchmark().is_some() && part.includes_shared() {
+        ret.push(ApiFunction::benchmark());
+    }
+
+    if protocol.diagnostic_strings().is_some() && part.includes_shared() {
+        ret.push(ApiFunction::parse_error_string());
+    }
+
+    if protocol.frame_trace().is_some() && part.includes_shared() {
+        ret.push(ApiFunction::trace_dump());
+    }
+
+    if !protocol.dispatched_messages().is_empty() && part.includes_umbrella() {
+        ret.push(ApiFunction::dispatcher_init(&symbol_naming));
+        ret.push(ApiFunction::parse_any(&symbol_naming));
+    }
+
+    ret
+}
+
+/// Reviews the generated API for const-correctness and nullability. Read-only
+/// pointers must be `const`, and nullable pointers must be documented as such.
+pub struct ApiReport {
+    pub functions: Vec<(ApiFunction, Vec<LintResult>)>,
+}
+
+impl ApiReport {
+    fn review(function: &ApiFunction) -> Vec<LintResult> {
+        let mut ret = Vec::new();
+
+        for parameter in &function.parameters {
+            let pointer = match parameter.pointer {
+                Some(ref pointer) => pointer,
+                None => continue,
+            };
+
+            match (pointer.access, pointer.is_const) {
+                (PointerAccess::Read, false) => ret.push(LintResult::Warning(format!(
+                    "{0}: {1} is only read, but is not const",
+                    function.name, parameter.name
+                ))),
+                (PointerAccess::ReadWrite, true) => ret.push(LintResult::Error(format!(
+                    "{0}: {1} is written, but is const",
+                    function.name, parameter.name
+                ))),
+                _ => {}
+            }
+        }
+
+        ret
+    }
+
+    pub fn count_findings(&self) -> usize {
+        self.functions.iter().map(|(_, findings)| findings.len()).sum()
+    }
+}
+
+impl From<&Protocol> for ApiReport {
+    fn from(protocol: &Protocol) -> Self {
+        ApiReport {
+            functions: api_functions(protocol)
+                .into_iter()
+                .map(|function| {
+                    let findings = ApiReport::review(&function);
+                    (function, findings)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl CodeGeneration for ApiReport {
+    fn generate_code(
+        &self,
+        _code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = Vec::new();
+
+        for (function, findings) in &self.functions {
+            lines.push((0, function.signature()));
+
+            for parameter in &function.parameters {
+                let description = match parameter.pointer {
+                    Some(ref pointer) => format!(
+                        "{0}: {1}, {2}",
+                        parameter.name,
+                        match pointer.access {
+                            PointerAccess::Read => "read-only",
+                            PointerAccess::ReadWrite => "read-write",
+                        },
+                        if pointer.nullable { "nullable" } else { "non-null" }
+                    ),
+                    None => format!("{0}: by value", parameter.name),
+                };
+                lines.push((1, description));
+            }
+
+            for finding in findings {
+                match finding {
+                    LintResult::Warning(ref text) => lines.push((1, format!("Warning: {0}", text))),
+                    LintResult::Error(ref text) => lines.push((1, format!("Error: {0}", text))),
+                    LintResult::Ok => {}
+                }
+            }
+        }
+
+        lines.push((0, format!("{0} finding(s)", self.count_findings())));
+
+        indented_lines(lines.into_iter(), 0)
+    }
+}
+
+/// A public symbol of the generated library, or a part of its layout
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManifestEntry {
+    Function {
+        name: String,
+        signature: String,
+    },
+
+    /// Member of a struct, identified by its position
+    StructMember {
+        struct_name: String,
+        position: usize,
+        declaration: String,
+    },
+
+    /// Enumerator or macro with a literal value
+    Constant {
+        name: String,
+        value: String,
+    },
+}
+
+impl ManifestEntry {
+    /// Identity of the entry, shared by its versions across generations
+    fn key(&self) -> String {
+        match self {
+            ManifestEntry::Function { ref name, .. } => format!("function {0}", name),
+            ManifestEntry::StructMember {
+                ref struct_name,
+                position,
+                ..
+            } => format!("{0}, member #{1}", struct_name, position),
+            ManifestEntry::Constant { ref name, .. } => format!("constant {0}", name),
+        }
+    }
+
+    /// The part of the entry which must stay intact across generations
+    fn value(&self) -> &str {
+        match self {
+            ManifestEntry::Function { ref signature, .. } => signature,
+            ManifestEntry::StructMember { ref declaration, .. } => declaration,
+            ManifestEntry::Constant { ref value, .. } => value,
+        }
+    }
+}
+
+/// Machine-readable list of the generated library's public symbols and struct
+/// layouts. Manifests of two schema revisions may be diffed to find out,
+/// whether prebuilt libraries remain compatible with already compiled users.
+///
+/// The text form has a header line followed by one tab-separated entry per
+/// line:
+///
+/// ```text
+/// robusto-api-manifest 1
+/// function<TAB>name<TAB>signature
+/// member<TAB>struct name<TAB>position<TAB>declaration
+/// constant<TAB>name<TAB>value
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ApiManifest {
+    pub entries: Vec<ManifestEntry>,
+}
+
+const API_MANIFEST_HEADER: &str = "robusto-api-manifest 1";
+
+impl ApiManifest {
+    /// Parses the text form produced by the manifest's code generation
+    pub fn parse(text: &str) -> Result<ApiManifest, String> {
+        let mut lines = text.lines().enumerate();
+
+        match lines.next() {
+            Some((_, API_MANIFEST_HEADER)) => {}
+            _ => return Err(format!("expected \"{0}\" header", API_MANIFEST_HEADER)),
+        }
+
+        let mut ret = ApiManifest::default();
+
+        for (line_number, line) in lines {
+            if line.is_empty() {
+                continue;
+            }
+
+            let columns: Vec<&str> = line.split('\t').collect();
+            let entry = match columns[..] {
+                ["function", name, signature] => ManifestEntry::Function {
+                    name: name.to_string(),
+                    signature: signature.to_string(),
+                },
+                ["member", struct_name, position, declaration] => ManifestEntry::StructMember {
+                    struct_name: struct_name.to_string(),
+                    position: position.parse().map_err(|_| {
+                        format!("line {0}: invalid member position", line_number + 1)
+                    })?,
+                    declaration: declaration.to_string(),
+                },
+                ["constant", name, value] => ManifestEntry::Constant {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                },
+                _ => return Err(format!("line {0}: malformed entry", line_number + 1)),
+            };
+            ret.entries.push(entry);
+        }
+
+        Ok(ret)
+    }
+
+    /// Flags changes breaking binary compatibility between the library
+    /// generated from `old`, and users compiled against it. Removed or
+    /// changed entries are errors, and so are members added to an existing
+    /// struct, as they change its size. New functions, structs and constants
+    /// are compatible.
+    pub fn diff(old: &ApiManifest, new: &ApiManifest) -> Vec<LintResult> {
+        let mut ret = Vec::new();
+
+        for old_entry in &old.entries {
+            match new.entries.iter().find(|entry| entry.key() == old_entry.key()) {
+                None => ret.push(LintResult::Error(format!("{0} was removed", old_entry.key()))),
+                Some(new_entry) if new_entry.value() != old_entry.value() => {
+                    ret.push(LintResult::Error(format!(
+                        "{0} changed from \"{1}\" to \"{2}\"",
+                        old_entry.key(),
+                        old_entry.value(),
+                        new_entry.value()
+                    )))
+                }
+                Some(_) => {}
+            }
+        }
+
+        for new_entry in &new.entries {
+            if let ManifestEntry::StructMember { ref struct_name, .. } = new_entry {
+                let is_new_member = !old.entries.iter().any(|entry| entry.key() == new_entry.key());
+                let is_old_struct = old.entries.iter().any(|entry| {
+                    matches!(entry, ManifestEntry::StructMember { struct_name: ref name, .. } if name == struct_name)
+                });
+
+                if is_new_member && is_old_struct {
+                    ret.push(LintResult::Error(format!("{0} was added", new_entry.key())));
+                }
+            }
+        }
+
+        ret
+    }
+
+    fn add_struct_members<I: Iterator<Item = String>>(&mut self, struct_name: &str, declarations: I) {
+        for (position, declaration) in declarations.enumerate() {
+            self.entries.push(ManifestEntry::StructMember {
+                struct_name: struct_name.to_string(),
+                position,
+                declaration,
+            });
+        }
+    }
+
+    /// Collects struct layouts and constants from the header tree
+    fn add_header_node(&mut self, node: &AstNode) {
+        let member_declarations = || {
+            node.children.iter().flat_map(|child| match child.ast_node_type {
+                AstNodeType::MessageStructMember(ref member) => vec![member.declaration()],
+                AstNodeType::MessageStructUnion(ref union) => child
+                    .children
+                    .iter()
+                    .filter_map(|union_child| match union_child.ast_node_type {
+                        AstNodeType::MessageStructMember(ref member) => {
+                            Some(format!("union {0}: {1}", union.name, member.declaration()))
+                        }
+                        _ => None,
+                    })
+                    .collect(),
+                _ => vec![],
+            })
+        };
+
+        match node.ast_node_type {
+            AstNodeType::MessageStruct(ref message_struct) => self.add_struct_members(
+                &format!(
+                    "struct {0}",
+                    message_struct.symbol_naming.message_struct(&message_struct.message_name)
+                ),
+                member_declarations(),
+            ),
+            AstNodeType::ParserStateStruct(ref parser_state_struct) => self.add_struct_members(
+                &format!(
+                    "struct {0}",
+                    parser_state_struct.symbol_naming.parser_state_struct(&parser_state_struct.machine_name)
+                ),
+                ParserStateStruct::ragel_member_declarations()
+                    .iter()
+                    .map(|declaration| declaration.to_string())
+                    .chain(member_declarations()),
+            ),
+            AstNodeType::CallbacksStruct(ref callbacks_struct) => self.add_struct_members(
+                &format!(
+                    "struct {0}",
+                    callbacks_struct.symbol_naming.callbacks_struct(&callbacks_struct.message_name)
+                ),
+                callbacks_struct.members().into_iter(),
+            ),
+            AstNodeType::TlvRecordStruct(ref record_struct) => self.add_struct_members(
+                &format!(
+                    "struct {0}",
+                    common::tlv_record_struct_name(&record_struct.message_name, &record_struct.field_name)
+                ),
+                std::iter::once("uint32_t tag;".to_string()).chain(record_struct.variants.iter().map(
+                    |variant| {
+                        format!(
+                            "union value: {0}",
+                            MessageStructMember {
+                                name: variant.name.clone(),
+                                field_base_type: variant.field_base_type.clone(),
+                                array_length: variant.array_length,
+                            }
+                            .declaration()
+                        )
+                    },
+                )),
+            ),
+            AstNodeType::GroupStruct(ref group_struct) => self.add_struct_members(
+                &format!("struct {0}", group_struct.name),
+                group_struct.members.iter().map(MessageStructMember::declaration),
+            ),
+            AstNodeType::SliceStruct(_) => self.add_struct_members(
+                &format!("struct {0}", common::SLICE_STRUCT_NAME),
+                common::slice_struct_members()
+                    .iter_mut()
+                    .map(|member| MessageStructMember::from(member).declaration()),
+            ),
+            AstNodeType::StatisticsStruct(_) => self.add_struct_members(
+                &format!("struct {0}", common::STATISTICS_STRUCT_NAME),
+                common::statistics_struct_members()
+                    .iter_mut()
+                    .map(|member| MessageStructMember::from(member).declaration()),
+            ),
+            AstNodeType::ParseErrorEnum(_) => {
+                for parse_error in common::ParseError::all() {
+                    self.entries.push(ManifestEntry::Constant {
+                        name: c_parse_error_name(parse_error).to_string(),
+                        value: parse_error.code().to_string(),
+                    });
+                }
+
+                for parse_status in common::ParseStatus::all() {
+                    self.entries.push(ManifestEntry::Constant {
+                        name: c_parse_status_name(parse_status).to_string(),
+                        value: parse_status.code().to_string(),
+                    });
+                }
+            }
+            AstNodeType::SizingConstants(ref sizing_constants) => {
+                for (name, value) in sizing_constants.frame_size_constants() {
+                    self.entries.push(ManifestEntry::Constant { name, value });
+                }
+            }
+            AstNodeType::FlagConstants(ref flag_constants) => {
+                for (name, value) in &flag_constants.constants {
+                    self.entries.push(ManifestEntry::Constant {
+                        name: name.clone(),
+                        value: value.clone(),
+                    });
+                }
+            }
+            AstNodeType::FrameTraceDeclarations(ref declarations) => {
+                self.entries.push(ManifestEntry::Constant {
+                    name: "ROBUSTO_TRACE_DEPTH".to_string(),
+                    value: format!("{0}u", declarations.depth),
+                });
+
+                for (message_id, message_name) in declarations.message_names.iter().enumerate() {
+                    self.entries.push(ManifestEntry::Constant {
+                        name: FrameTraceDeclarations::message_id_macro_name(message_name),
+                        value: format!("{0}u", message_id),
+                    });
+                }
+
+                self.add_struct_members(
+                    FrameTraceDeclarations::record_struct_name(),
+                    trace_record_members().iter().map(MessageStructMember::declaration),
+                );
+            }
+            AstNodeType::DispatcherDeclarations(ref declarations) => {
+                for (name, value) in declarations.constants() {
+                    self.entries.push(ManifestEntry::Constant { name, value });
+                }
+
+                for (name, id) in declarations.message_id_enumerators() {
+                    self.entries.push(ManifestEntry::Constant {
+                        name,
+                        value: id.to_string(),
+                    });
+                }
+
+                self.add_struct_members(
+                    &declarations.callbacks_struct_name(),
+                    declarations.callbacks_members().into_iter(),
+                );
+                self.add_struct_members(
+                    &declarations.parser_state_union_name(),
+                    declarations.parser_state_union_members().into_iter(),
+                );
+                self.add_struct_members(
+                    &declarations.state_struct_name(),
+                    declarations.state_members().into_iter(),
+                );
+            }
+            AstNodeType::ApiPrototypes(ref prototypes) => {
+                for function in &prototypes.functions {
+                    self.entries.push(ManifestEntry::Function {
+                        name: function.name.clone(),
+                        signature: function.signature(),
+                    });
+                }
+            }
+            _ => {}
+        }
+
+        for child in &node.children {
+            self.add_header_node(child);
+        }
+    }
+}
+
+impl From<&Protocol> for ApiManifest {
+    fn from(protocol: &Protocol) -> Self {
+        let mut ret = ApiManifest::default();
+        ret.add_header_node(&HeaderAstNode::from(protocol).ast_node);
+
+        ret
+    }
+}
+
+impl CodeGeneration for ApiManifest {
+    fn generate_code(
+        &self,
+        _code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = vec![(0, API_MANIFEST_HEADER.to_string())];
+
+        for entry in &self.entries {
+            lines.push((
+                0,
+                match entry {
+                    ManifestEntry::Function {
+                        ref name,
+                        ref signature,
+                    } => format!("function\t{0}\t{1}", name, signature),
+                    ManifestEntry::StructMember {
+                        ref struct_name,
+                        position,
+                        ref declaration,
+                    } => format!("member\t{0}\t{1}\t{2}", struct_name, position, declaration),
+                    ManifestEntry::Constant { ref name, ref value } => {
+                        format!("constant\t{0}\t{1}", name, value)
+                    }
+                },
+            ));
+        }
+
+        indented_lines(lines.into_iter(), 0)
+    }
+}
+
+/// Prototypes of the functions the generated code exposes. Pointer
+/// parameters which must not be NULL are listed in a comment.
+#[derive(Debug)]
+struct ApiPrototypes {
+    functions: Vec<ApiFunction>,
+}
+
+impl codegen::TreeBasedCodeGeneration for ApiPrototypes {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = Vec::new();
+
+        for function in &self.functions {
+            let non_null_parameters = function.non_null_parameters();
+
+            if !non_null_parameters.is_empty() {
+                lines.push((0, format!("// Non-null: {0}", non_null_parameters.join(", "))));
+            }
+
+            lines.push((0, format!("{0};", function.signature())));
+        }
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Formats a floating point constant, so it is a valid C `float` literal
+fn c_float_literal(value: f64) -> String {
+    // `Debug` keeps either a fractional part, or an exponent
+    format!("{0:?}f", value)
+}
+
+/// Masks of the named bits of a flags field
+#[derive(Debug)]
+struct FlagConstants {
+    /// Macro names along with their values
+    constants: Vec<(String, String)>,
+}
+
+impl FlagConstants {
+    fn from_field(message_name: &str, field_name: &str, flags: &representation::FlagsFieldType) -> FlagConstants {
+        FlagConstants {
+            constants: flags
+                .bits
+                .iter()
+                .map(|bit| {
+                    let name = format!(
+                        "{0}_{1}_FLAG_{2}",
+                        utility::string::to_upper_snake_case(message_name),
+                        utility::string::to_upper_snake_case(field_name),
+                        utility::string::to_upper_snake_case(&bit.name)
+                    );
+                    // `1u` is at least 16 bits wide, which may be short of the field
+                    let value = match bit.position {
+                        0..=15 => format!("(1u << {0})", bit.position),
+                        16..=31 => format!("(UINT32_C(1) << {0})", bit.position),
+                        _ => format!("(UINT64_C(1) << {0})", bit.position),
+                    };
+
+                    (name, value)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for FlagConstants {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(
+            self.constants
+                .iter()
+                .map(|(name, value)| (0, format!("#define {0} {1}", name, value))),
+            code_generation_state.indent,
+        )
+    }
+}
+
+/// Tests or sets the bits of a flags field
+#[derive(Debug)]
+struct FlagsAccessor {
+    kind: common::FlagsAccessorKind,
+    message_name: String,
+    symbol_naming: SymbolNaming,
+    field_name: String,
+    field_base_type: FieldBaseType,
+}
+
+impl From<&mut common::FlagsAccessor> for FlagsAccessor {
+    fn from(value: &mut common::FlagsAccessor) -> Self {
+        FlagsAccessor {
+            kind: value.kind,
+            message_name: value.message_name.clone(),
+            symbol_naming: value.symbol_naming.clone(),
+            field_name: value.field_name.clone(),
+            field_base_type: value.field_base_type.clone(),
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for FlagsAccessor {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let function = ApiFunction::flags_accessor(
+            &self.symbol_naming,
+            self.kind,
+            &self.message_name,
+            &self.field_name,
+            &self.field_base_type,
+        );
+        let member = format!("a{0}->{1}", self.message_name, self.field_name);
+        let mut lines = vec![(0, function.signature()), (0, "{".to_string())];
+
+        match self.kind {
+            common::FlagsAccessorKind::Test => {
+                lines.push((1, format!("return ({0} & aFlags) == aFlags;", member)));
+            }
+            common::FlagsAccessorKind::Set => {
+                let c_type = c_type_name(&self.field_base_type);
+                lines.push((1, "if (aValue) {".to_string()));
+                lines.push((2, format!("{0} = ({1})({0} | aFlags);", member, c_type)));
+                lines.push((1, "} else {".to_string()));
+                lines.push((2, format!("{0} = ({1})({0} & ~aFlags);", member, c_type)));
+                lines.push((1, "}".to_string()));
+            }
+        }
+
+        lines.push((0, "}".to_string()));
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Converts a timestamp field from and to milliseconds since the Unix epoch.
+/// Whole milliseconds per tick spare the division
+#[derive(Debug)]
+struct TimestampAccessor {
+    kind: common::TimestampAccessorKind,
+    message_name: String,
+    symbol_naming: SymbolNaming,
+    field_name: String,
+    field_base_type: FieldBaseType,
+    epoch_ms: i64,
+    tick_us: u64,
+}
+
+impl From<&mut common::TimestampAccessor> for TimestampAccessor {
+    fn from(value: &mut common::TimestampAccessor) -> Self {
+        TimestampAccessor {
+            kind: value.kind,
+            message_name: value.message_name.clone(),
+            symbol_naming: value.symbol_naming.clone(),
+            field_name: value.field_name.clone(),
+            field_base_type: value.field_base_type.clone(),
+            epoch_ms: value.epoch_ms,
+            tick_us: value.tick_us,
+        }
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for TimestampAccessor {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let function =
+            ApiFunction::timestamp_accessor(&self.symbol_naming, self.kind, &self.message_name, &self.field_name);
+        let member = format!("a{0}->{1}", self.message_name, self.field_name);
+        let epoch = format!("INT64_C({0})", self.epoch_ms);
+        let statement = match (self.kind, self.tick_us % 1000) {
+            (common::TimestampAccessorKind::Get, 0) => {
+                format!("return {0} + (int64_t){1} * INT64_C({2});", epoch, member, self.tick_us / 1000)
+            }
+            (common::TimestampAccessorKind::Get, _) => format!(
+                "return {0} + (int64_t){1} * INT64_C({2}) / INT64_C(1000);",
+                epoch, member, self.tick_us
+            ),
+            (common::TimestampAccessorKind::Set, 0) => format!(
+                "{0} = ({1})((aMilliseconds - {2}) / INT64_C({3}));",
+                member,
+                c_type_name(&self.field_base_type),
+                epoch,
+                self.tick_us / 1000
+            ),
+            (common::TimestampAccessorKind::Set, _) => format!(
+                "{0} = ({1})((aMilliseconds - {2}) * INT64_C(1000) / INT64_C({3}));",
+                member,
+                c_type_name(&self.field_base_type),
+                epoch,
+                self.tick_us
+            ),
+        };
+        let lines = [
+            (0, function.signature()),
+            (0, "{".to_string()),
+            (1, statement),
+            (0, "}".to_string()),
+        ];
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Prints the fields of a message struct, one per line, through a
+/// `printf`-like function. Only the C90 conversions are used, since the
+/// `printf()` of small C libraries often lacks the others: 64-bit values are
+/// printed in hexadecimal, as two halves. Conditional fields are printed, if
+/// they are present
+#[derive(Debug)]
+struct DumpFunction {
+    message: representation::Message,
+    symbol_naming: SymbolNaming,
+    zero_copy: bool,
+}
+
+impl From<&mut common::DumpFunction> for DumpFunction {
+    fn from(value: &mut common::DumpFunction) -> Self {
+        DumpFunction {
+            message: value.message.clone(),
+            symbol_naming: value.symbol_naming.clone(),
+            zero_copy: value.zero_copy,
+        }
+    }
+}
+
+impl DumpFunction {
+    /// Escapes text for a format string literal
+    fn format_literal(text: &str) -> String {
+        text.replace('\\', "\\\\").replace('"', "\\\"").replace('%', "%%")
+    }
+
+    fn print_line(format: &str, arguments: &[String]) -> String {
+        match arguments.is_empty() {
+            true => format!("aPrint(\"{0}\");", format),
+            false => format!("aPrint(\"{0}\", {1});", format, arguments.join(", ")),
+        }
+    }
+
+    /// Conversion of an integer, and its arguments
+    fn integer_conversion(member: &str, field_base_type: &FieldBaseType, hexadecimal: bool) -> (&'static str, Vec<String>) {
+        match field_base_type {
+            FieldBaseType::U64 | FieldBaseType::I64 => (
+                "0x%08lX%08lX",
+                vec![
+                    format!("(unsigned long)((uint64_t){0} >> 32)", member),
+                    format!("(unsigned long)((uint64_t){0} & 0xFFFFFFFFu)", member),
+                ],
+            ),
+            FieldBaseType::I8 | FieldBaseType::I16 | FieldBaseType::I32 if !hexadecimal => {
+                ("%ld", vec![format!("(long){0}", member)])
+            }
+            _ if hexadecimal => ("0x%lX", vec![format!("(unsigned long){0}", member)]),
+            _ => ("%lu", vec![format!("(unsigned long){0}", member)]),
+        }
+    }
+
+    /// Whether printing the fields takes a loop. Groups hold integers only,
+    /// so loops are not nested
+    fn has_loop(&self) -> bool {
+        self.message
+            .fields
+            .iter()
+            .filter(|field| common::has_struct_member(field))
+            .any(|field| matches!(field.field_type, representation::FieldType::Reserved(_) | representation::FieldType::Group(_)))
+    }
+
+    /// Lines printing a field stored in the member, labeled by the format and
+    /// its arguments. Groups are printed repetition by repetition
+    fn field_lines(&self, field: &representation::Field, member: &str, label: (&str, &[String])) -> Vec<(usize, String)> {
+        let (label_format, label_arguments) = label;
+        let line = |format: &str, arguments: Vec<String>| {
+            DumpFunction::print_line(
+                &format!("{0}{1}", label_format, format),
+                &label_arguments.iter().cloned().chain(arguments).collect::<Vec<String>>(),
+            )
+        };
+        let field_base_type = FieldBaseType::from_field(&self.message.name, field);
+
+        match field.field_type {
+            representation::FieldType::Integer(_)
+            | representation::FieldType::Varint(_)
+            | representation::FieldType::Timestamp(_) => {
+                let (conversion, mut arguments) = DumpFunction::integer_conversion(member, &field_base_type, false);
+                let mut format = format!(": {0}", conversion);
+
+                // Members of groups are not scaled
+                if field.scale().is_some() {
+                    let accessor =
+                        ApiFunction::scaled_value_accessor(&self.symbol_naming, &self.message.name, &field.name, field.unit());
+                    format += &match field.unit() {
+                        Some(unit) => format!(" (%g {0})", DumpFunction::format_literal(unit)),
+                        None => " (%g)".to_string(),
+                    };
+                    arguments.push(format!("(double){0}(a{1})", accessor.name, self.message.name));
+                }
+
+                vec![(0, line(&format!("{0}\\n", format), arguments))]
+            }
+            representation::FieldType::Flags(ref flags) => {
+                let (conversion, arguments) = DumpFunction::integer_conversion(member, &field_base_type, true);
+                let constants = FlagConstants::from_field(&self.message.name, &field.name, flags).constants;
+                let mut ret = vec![(0, line(&format!(": {0}", conversion), arguments))];
+
+                for ((constant, _), bit) in constants.iter().zip(&flags.bits) {
+                    ret.push((0, format!("if (({0} & {1}) != 0) {{", member, constant)));
+                    ret.push((
+                        1,
+                        DumpFunction::print_line(&format!(" {0}", DumpFunction::format_literal(&bit.name)), &[]),
+                    ));
+                    ret.push((0, "}".to_string()));
+                }
+
+                ret.push((0, DumpFunction::print_line("\\n", &[])));
+
+                ret
+            }
+            representation::FieldType::Regex(_) if self.zero_copy => vec![(
+                0,
+                line(
+                    ": offset %lu, length %lu\\n",
+                    vec![
+                        format!("(unsigned long){0}.offset", member),
+                        format!("(unsigned long){0}.length", member),
+                    ],
+                ),
+            )],
+            representation::FieldType::Regex(_) => {
+                let length = format!(
+                    "{0}{1}",
+                    member.strip_suffix(field.name.as_str()).unwrap_or_default(),
+                    common::regex_length_member_name(&field.name)
+                );
+
+                vec![(
+                    0,
+                    line(
+                        ": \\\"%.*s\\\"\\n",
+                        vec![format!("(int){0}", length), format!("(const char *){0}", member)],
+                    ),
+                )]
+            }
+            representation::FieldType::Reserved(ref reserved) => {
+                vec![
+                    (0, line(":", Vec::new())),
+                    (0, format!("for (i = 0; i < {0}u; ++i) {{", reserved.length)),
+                    (1, DumpFunction::print_line(" %02X", &[format!("(unsigned int){0}[i]", member)])),
+                    (0, "}".to_string()),
+                    (0, DumpFunction::print_line("\\n", &[])),
+                ]
+            }
+            representation::FieldType::Tlv(_) => {
+                let count = format!(
+                    "{0}{1}",
+                    member.strip_suffix(field.name.as_str()).unwrap_or_default(),
+                    common::tlv_count_member_name(&field.name)
+                );
+
+                vec![(0, line(": %lu record(s)\\n", vec![format!("(unsigned long){0}", count)]))]
+            }
+            representation::FieldType::Group(ref group) => {
+                let mut ret = vec![(0, format!("for (i = 0; i < {0}u; ++i) {{", group.count))];
+                let label_arguments = label_arguments
+                    .iter()
+                    .cloned()
+                    .chain(std::iter::once("i".to_string()))
+                    .collect::<Vec<String>>();
+
+                for subfield in group.fields.iter().filter(|subfield| common::has_struct_member(subfield)) {
+                    let subfield_label = format!("{0}[%u].{1}", label_format, subfield.name);
+                    ret.extend(
+                        self.field_lines(
+                            subfield,
+                            &format!("{0}[i].{1}", member, subfield.name),
+                            (&subfield_label, &label_arguments),
+                        )
+                        .into_iter()
+                        .map(|(indent, line)| (indent + 1, line)),
+                    );
+                }
+
+                ret.push((0, "}".to_string()));
+
+                ret
+            }
+        }
+    }
+
+    fn lines(&self) -> Vec<(usize, String)> {
+        let name = &self.message.name;
+        let mut ret = vec![
+            (0, ApiFunction::dump(&self.symbol_naming, name).signature()),
+            (0, "{".to_string()),
+        ];
+
+        // The index is printed as unsigned
+        if self.has_loop() {
+            ret.extend([(1, "unsigned int i;".to_string()), (0, String::new())]);
+        }
+
+        ret.push((1, DumpFunction::print_line(&format!("{0}:\\n", name), &[])));
+
+        for field in self.message.fields.iter().filter(|field| common::has_struct_member(field)) {
+            let member = field_member(name, &field.name, &common::FieldContainer::from_field(field));
+            let label = format!("  {0}", field.name);
+            let lines = self.field_lines(field, &member, (&label, &[]));
+
+            match field.conditional_on() {
+                Some(conditional_on) => {
+                    let selector_type = self
+                        .message
+                        .fields
+                        .iter()
+                        .find(|selector| selector.name == conditional_on.field)
+                        .map(|selector| FieldBaseType::from_field(name, selector))
+                        .unwrap_or(FieldBaseType::I64);
+
+                    ret.push((
+                        1,
+                        format!(
+                            "if (a{0}->{1} == {2}) {{",
+                            name,
+                            conditional_on.field,
+                            c_integer_literal(i128::from(conditional_on.value), &selector_type)
+                        ),
+                    ));
+                    ret.extend(lines.into_iter().map(|(indent, line)| (indent + 2, line)));
+                    ret.push((1, "}".to_string()));
+                }
+                None => ret.extend(lines.into_iter().map(|(indent, line)| (indent + 1, line))),
+            }
+        }
+
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
 }
 
-impl GenerationState {
-    fn new() -> GenerationState {
-        GenerationState { indent: 0 }
+impl codegen::TreeBasedCodeGeneration for DumpFunction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
     }
 }
 
+/// Initializes a message struct, and compares two of them. Arrays are compared
+/// as a whole, so the bytes past the parsed ones count as well. Structs set up
+/// by the init function have them cleared. Reserved fields are skipped, and
+/// conditional fields are compared, if they are present
 #[derive(Debug)]
-struct ParsingFunction {
-    message_name: String,
+struct StructFunctions {
+    message: representation::Message,
+    symbol_naming: SymbolNaming,
+    zero_copy: bool,
 }
 
-impl From<&mut common::ParsingFunction> for ParsingFunction {
-    fn from(value: &mut common::ParsingFunction) -> Self {
-        ParsingFunction {
-            message_name: value.message_name.clone(),
+impl From<&mut common::StructFunctions> for StructFunctions {
+    fn from(value: &mut common::StructFunctions) -> Self {
+        StructFunctions {
+            message: value.message.clone(),
+            symbol_naming: value.symbol_naming.clone(),
+            zero_copy: value.zero_copy,
         }
     }
 }
 
-#[derive(Debug)]
-pub struct MessageStruct {
-    pub message_name: std::string::String,
-}
-
-impl From<&mut common::MessageStruct> for MessageStruct {
-    fn from(value: &mut common::MessageStruct) -> Self {
-        MessageStruct {
-            message_name: value.message_name.clone(),
+impl StructFunctions {
+    /// The struct member holding a field, in the message pointed to
+    fn member(pointer: &str, field: &representation::Field) -> String {
+        match field.conditional_on() {
+            Some(conditional_on) => format!(
+                "{0}->{1}.{2}",
+                pointer,
+                common::conditional_union_member_name(&conditional_on.field),
+                field.name
+            ),
+            None => format!("{0}->{1}", pointer, field.name),
         }
     }
-}
 
-impl codegen::TreeBasedCodeGeneration for MessageStruct {
-    fn generate_code_pre_traverse(
-        &self,
-        code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        let mut ret = LinkedList::<codegen::CodeChunk>::new();
+    fn unequal_lines(condition: &str) -> Vec<(usize, String)> {
+        vec![
+            (0, format!("if ({0}) {{", condition)),
+            (1, "equal = 0;".to_string()),
+            (0, "}".to_string()),
+        ]
+    }
 
-        // Generate struct header
-        ret.push_back(CodeChunk::new(
-            format!("struct {0}Message {{", self.message_name),
-            code_generation_state.indent,
-            1usize,
-        ));
+    fn init_lines(&self) -> Vec<(usize, String)> {
+        let name = &self.message.name;
+        let mut ret = vec![
+            (0, ApiFunction::init(&self.symbol_naming, name).signature()),
+            (0, "{".to_string()),
+            (1, format!("memset(a{0}, 0, sizeof(*a{0}));", name)),
+        ];
 
-        code_generation_state.indent += 1;
+        for (i, field) in self.message.fields.iter().enumerate() {
+            match field.field_type {
+                representation::FieldType::Integer(_) if i == 0 && self.message.id().is_some() => {
+                    ret.push((
+                        1,
+                        format!(
+                            "a{0}->{1} = {2};",
+                            name,
+                            field.name,
+                            c_integer_literal(
+                                i128::from(self.message.id().unwrap_or_default()),
+                                &FieldBaseType::from_field(name, field)
+                            )
+                        ),
+                    ));
+                }
+                representation::FieldType::Regex(ref regex) if !self.zero_copy && field.conditional_on().is_none() => {
+                    let Some(bytes) = regex.pattern().literal() else {
+                        continue;
+                    };
+                    let length = bytes.len().min(common::array_capacity(field));
 
-        ret
-    }
+                    for (j, byte) in bytes.iter().enumerate().take(length) {
+                        ret.push((1, format!("a{0}->{1}[{2}] = 0x{3:02X}u;", name, field.name, j, byte)));
+                    }
 
-    fn generate_code_post_traverse(
-        &self,
-        code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        let mut ret = LinkedList::<codegen::CodeChunk>::new();
-        code_generation_state.indent -= 1;
+                    ret.push((
+                        1,
+                        format!("a{0}->{1} = {2}u;", name, common::regex_length_member_name(&field.name), length),
+                    ));
+                }
+                _ => {}
+            }
+        }
 
-        // Close the bracket
-        ret.push_back(CodeChunk::new(
-            "};".to_string(),
-            code_generation_state.indent,
-            1usize,
-        ));
+        ret.push((0, "}".to_string()));
 
         ret
     }
-}
-
-#[derive(Clone, Debug)]
-pub struct MessageStructMember {
-    pub name: std::string::String,
-    pub field_base_type: FieldBaseType,
 
-    /// If 0, it is considered just a field
-    pub array_length: usize,
-}
+    /// Lines comparing a field stored in the members
+    fn field_lines(&self, field: &representation::Field, left: &str, right: &str) -> Vec<(usize, String)> {
+        match field.field_type {
+            representation::FieldType::Integer(_)
+            | representation::FieldType::Varint(_)
+            | representation::FieldType::Flags(_)
+            | representation::FieldType::Timestamp(_) => {
+                StructFunctions::unequal_lines(&format!("{0} != {1}", left, right))
+            }
+            representation::FieldType::Regex(_) if self.zero_copy => StructFunctions::unequal_lines(&format!(
+                "{0}.offset != {1}.offset || {0}.length != {1}.length",
+                left, right
+            )),
+            representation::FieldType::Regex(_) => {
+                let length_member = common::regex_length_member_name(&field.name);
+                let left_length =
+                    format!("{0}{1}", left.strip_suffix(field.name.as_str()).unwrap_or_default(), length_member);
+                let right_length =
+                    format!("{0}{1}", right.strip_suffix(field.name.as_str()).unwrap_or_default(), length_member);
 
-impl From<&mut common::MessageStructMember> for MessageStructMember {
-    fn from(value: &mut common::MessageStructMember) -> Self {
-        MessageStructMember {
-            name: value.name.clone(),
-            field_base_type: value.field_base_type.clone(),
-            array_length: value.array_length,
-        }
-    }
-}
+                StructFunctions::unequal_lines(&format!(
+                    "{0} != {1} || memcmp({2}, {3}, (size_t){0}) != 0",
+                    left_length, right_length, left, right
+                ))
+            }
+            representation::FieldType::Reserved(_) => Vec::new(),
+            representation::FieldType::Tlv(ref tlv) => {
+                let count_member = common::tlv_count_member_name(&field.name);
+                let left_count = format!("{0}{1}", left.strip_suffix(field.name.as_str()).unwrap_or_default(), count_member);
+                let right_count = format!("{0}{1}", right.strip_suffix(field.name.as_str()).unwrap_or_default(), count_member);
+                let mut ret = vec![
+                    (0, format!("if ({0} != {1}) {{", left_count, right_count)),
+                    (1, "equal = 0;".to_string()),
+                    (0, "} else {".to_string()),
+                    (1, format!("for (i = 0; i < {0}; ++i) {{", left_count)),
+                    (2, format!("if ({0}[i].tag != {1}[i].tag) {{", left, right)),
+                    (3, "equal = 0;".to_string()),
+                ];
 
-impl TreeBasedCodeGeneration for MessageStructMember {
-    fn generate_code_pre_traverse(
-        &self,
-        code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        let mut ret = LinkedList::<codegen::CodeChunk>::new();
-        log::debug!("indent: {0}", code_generation_state.indent);
+                for variant in common::TlvRecordVariant::from_tlv(tlv) {
+                    let left_value = format!("{0}[i].value.{1}", left, variant.name);
+                    let right_value = format!("{0}[i].value.{1}", right, variant.name);
+                    let condition = match variant.array_length {
+                        0 => format!("{0} != {1}", left_value, right_value),
+                        _ => format!("memcmp({0}, {1}, sizeof({0})) != 0", left_value, right_value),
+                    };
 
-        // Get a formatted C representation
-        let formatted = format!(
-            "{0} {1}{2};",
-            match self.field_base_type {
-                FieldBaseType::I8 => {
-                    "uint8_t"
-                }
-                _ => {
-                    panic!("Unsupported type {:?}", self.field_base_type)
-                }
-            },
-            self.name,
-            {
-                if self.array_length == 0usize {
-                    std::string::String::from("")
-                } else {
-                    format!("[{}]", self.array_length)
+                    ret.push((2, format!("}} else if ({0}[i].tag == {1}u) {{", left, variant.tag)));
+                    ret.extend(
+                        StructFunctions::unequal_lines(&condition)
+                            .into_iter()
+                            .map(|(indent, line)| (indent + 3, line)),
+                    );
                 }
+
+                ret.extend([(2, "}".to_string()), (1, "}".to_string()), (0, "}".to_string())]);
+
+                ret
             }
-        );
+            representation::FieldType::Group(ref group) => {
+                let mut ret = vec![(0, format!("for (i = 0; i < {0}u; ++i) {{", group.count))];
 
-        ret.push_back(CodeChunk::new(
-            formatted,
-            code_generation_state.indent,
-            1usize,
-        ));
+                for subfield in group.fields.iter().filter(|subfield| common::has_struct_member(subfield)) {
+                    ret.extend(
+                        self.field_lines(
+                            subfield,
+                            &format!("{0}[i].{1}", left, subfield.name),
+                            &format!("{0}[i].{1}", right, subfield.name),
+                        )
+                        .into_iter()
+                        .map(|(indent, line)| (indent + 1, line)),
+                    );
+                }
 
-        ret
-    }
-}
+                ret.push((0, "}".to_string()));
 
-impl MessageStructMember {
-    pub fn is_array(&self) -> bool {
-        self.array_length > 0
+                ret
+            }
+        }
     }
-}
 
-#[derive(Clone, Debug)]
-struct ParserStateStruct {
-    machine_name: String,
-}
+    fn equality_lines(&self) -> Vec<(usize, String)> {
+        let name = &self.message.name;
+        let fields = self
+            .message
+            .fields
+            .iter()
+            .filter(|field| common::has_struct_member(field))
+            .filter(|field| !matches!(field.field_type, representation::FieldType::Reserved(_)));
+        let mut ret = vec![
+            (0, ApiFunction::equality(&self.symbol_naming, name).signature()),
+            (0, "{".to_string()),
+            (1, "int equal = 1;".to_string()),
+        ];
 
-impl codegen::TreeBasedCodeGeneration for ParserStateStruct {
-    fn generate_code_pre_traverse(
-        &self,
-        code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<codegen::CodeChunk> {
-        let mut ret = LinkedList::<codegen::CodeChunk>::new();
-        ret.push_back(CodeChunk::new(
-            format!("struct {0}ParserState {{", self.machine_name),
-            code_generation_state.indent,
-            1usize,
-        ));
-        ret.push_back(CodeChunk::new(
-            "int machineInitRequired;".to_string(),
-            code_generation_state.indent + 1,
-            1usize,
-        ));
-        ret.push_back(CodeChunk::new(
-            "int cs;".to_string(),
-            code_generation_state.indent + 1,
-            1usize,
-        ));
-        ret.push_back(CodeChunk::new(
-            "};".to_string(),
-            code_generation_state.indent,
-            1usize,
-        ));
+        if fields
+            .clone()
+            .any(|field| matches!(field.field_type, representation::FieldType::Group(_) | representation::FieldType::Tlv(_)))
+        {
+            ret.push((1, "unsigned int i;".to_string()));
+        }
 
-        ret
-    }
-}
+        ret.push((0, String::new()));
 
-#[derive(Debug)]
-pub struct ParserStateInitFunction {
-    pub machine_name: String,
-}
+        for field in fields {
+            let lines = self.field_lines(
+                field,
+                &StructFunctions::member("aLeft", field),
+                &StructFunctions::member("aRight", field),
+            );
 
-impl codegen::TreeBasedCodeGeneration for ParserStateInitFunction {
-    fn generate_code_pre_traverse(
-        &self,
-        code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        let mut ret = LinkedList::<codegen::CodeChunk>::new();
-        ret.push_back(CodeChunk::new(
-            format!(
-                "void machine{0}ParserStateInit(struct {0}ParserState *aParserState)",
-                self.machine_name
-            ),
-            code_generation_state.indent,
-            1usize,
-        ));
-        ret.push_back(CodeChunk::new(
-            "{".to_string(),
-            code_generation_state.indent,
-            1usize,
-        ));
-        code_generation_state.indent += 1usize;
-        ret.push_back(CodeChunk::new(
-            "aParserState->machineInitRequired = 0;".to_string(),
-            code_generation_state.indent,
-            1usize,
-        ));
-        ret.push_back(CodeChunk::new(
-            "aParserState->cs = 0;".to_string(),
-            code_generation_state.indent,
-            1usize,
-        ));
+            match field.conditional_on() {
+                Some(conditional_on) => {
+                    let selector_type = self
+                        .message
+                        .fields
+                        .iter()
+                        .find(|selector| selector.name == conditional_on.field)
+                        .map(|selector| FieldBaseType::from_field(name, selector))
+                        .unwrap_or(FieldBaseType::I64);
+
+                    // The selectors have been compared already
+                    ret.push((
+                        1,
+                        format!(
+                            "if (aLeft->{0} == {1}) {{",
+                            conditional_on.field,
+                            c_integer_literal(i128::from(conditional_on.value), &selector_type)
+                        ),
+                    ));
+                    ret.extend(lines.into_iter().map(|(indent, line)| (indent + 2, line)));
+                    ret.push((1, "}".to_string()));
+                }
+                None => ret.extend(lines.into_iter().map(|(indent, line)| (indent + 1, line))),
+            }
+        }
+
+        if self.message.field_order() == FieldOrder::Unordered {
+            ret.extend(
+                StructFunctions::unequal_lines(&format!(
+                    "aLeft->{0} != aRight->{0}",
+                    common::PRESENCE_BITMAP_MEMBER_NAME
+                ))
+                .into_iter()
+                .map(|(indent, line)| (indent + 1, line)),
+            );
+        }
+
+        ret.extend([(0, String::new()), (1, "return equal;".to_string()), (0, "}".to_string())]);
 
         ret
     }
+}
 
-    fn generate_code_post_traverse(
+impl codegen::TreeBasedCodeGeneration for StructFunctions {
+    fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        code_generation_state.indent -= 1usize;
-        let mut ret = LinkedList::<codegen::CodeChunk>::new();
-        ret.push_back(CodeChunk::new(
-            "}".to_string(),
-            code_generation_state.indent,
-            1usize,
-        ));
+    ) -> Vec<CodeChunk> {
+        let mut lines = self.init_lines();
+        lines.extend(self.equality_lines());
 
-        ret
+        indented_lines(lines.into_iter(), code_generation_state.indent)
     }
 }
 
-impl From<&mut common::ParserStateInitFunction> for ParserStateInitFunction {
-    fn from(value: &mut common::ParserStateInitFunction) -> Self {
-        ParserStateInitFunction {
-            machine_name: value.machine_name.clone(),
+/// Converts a scaled integer field into its physical value
+#[derive(Debug)]
+struct ScaledValueAccessor {
+    message_name: String,
+    symbol_naming: SymbolNaming,
+    field_name: String,
+    factor: f64,
+    offset: f64,
+    unit: Option<String>,
+}
+
+impl From<&mut common::ScaledValueAccessor> for ScaledValueAccessor {
+    fn from(value: &mut common::ScaledValueAccessor) -> Self {
+        ScaledValueAccessor {
+            message_name: value.message_name.clone(),
+            symbol_naming: value.symbol_naming.clone(),
+            field_name: value.field_name.clone(),
+            factor: value.factor,
+            offset: value.offset,
+            unit: value.unit.clone(),
         }
     }
 }
 
-impl codegen::TreeBasedCodeGeneration for ParsingFunction {
+impl codegen::TreeBasedCodeGeneration for ScaledValueAccessor {
     fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<codegen::CodeChunk> {
-        let mut ret = LinkedList::<codegen::CodeChunk>::new();
-        ret.push_back(codegen::CodeChunk::new(
-            format!("void parse{0}(struct {0}ParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct {0} *a{0})", self.message_name),
-            code_generation_state.indent,
-            1usize
-        ));
-        ret.push_back(codegen::CodeChunk::new(
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<codegen::CodeChunk>::new();
+        let signature = ApiFunction::scaled_value_accessor(
+            &self.symbol_naming,
+            &self.message_name,
+            &self.field_name,
+            self.unit.as_deref(),
+        )
+        .signature();
+
+        if let Some(ref unit) = self.unit {
+            ret.push(CodeChunk::new(
+                format!("// Physical value of `{0}`, in {1}", self.field_name, unit),
+                code_generation_state.indent,
+                1usize,
+            ));
+        }
+
+        ret.push(CodeChunk::new(signature, code_generation_state.indent, 1usize));
+        ret.push(CodeChunk::new(
             "{".to_string(),
             code_generation_state.indent,
             1usize,
         ));
-        code_generation_state.indent += 1usize;
-        ret.push_back(codegen::CodeChunk::new(
-            "const char *p = aInputBuffer;  // Iterator \"begin\" pointer -- Ragel-specific variable for C code generation".to_string(),
-            code_generation_state.indent,
-            1usize,
-        ));
-        ret.push_back(codegen::CodeChunk::new(
-            "const char *pe = aInputBuffer + aInputBufferLength;  // Iterator \"end\" pointer -- Ragel-specific variable for C code generation".to_string(),
-            code_generation_state.indent,
-            1usize,
-        ));
-        ret.push_back(codegen::CodeChunk::new(
-            "// Parse starting from the state defined in `aParserState`".to_string(),
-            code_generation_state.indent,
-            1usize,
-        ));
-        ret.push_back(codegen::CodeChunk::new(
-            "%% write exec;".to_string(),
-            code_generation_state.indent,
+        ret.push(CodeChunk::new(
+            format!(
+                "return (float)a{0}->{1} * {2} + {3};",
+                self.message_name,
+                self.field_name,
+                c_float_literal(self.factor),
+                c_float_literal(self.offset)
+            ),
+            code_generation_state.indent + 1,
             1usize,
         ));
-        code_generation_state.indent -= 1usize;
-        ret.push_back(codegen::CodeChunk::new(
+        ret.push(CodeChunk::new(
             "}".to_string(),
             code_generation_state.indent,
             1usize,
@@ -299,11 +6426,27 @@ impl codegen::TreeBasedCodeGeneration for ParsingFunction {
 #[derive(Debug)]
 enum AstNodeType {
     Root,
-    ParsingFunction(ParsingFunction),
+    TlvRecordStruct(TlvRecordStruct),
+    GroupStruct(GroupStruct),
+    ApiPrototypes(ApiPrototypes),
+    ParseErrorEnum(ParseErrorEnum),
+    SliceStruct(SliceStruct),
+    StatisticsStruct(StatisticsStruct),
+    SizingConstants(SizingConstants),
+    FlagConstants(FlagConstants),
+    FrameTraceDeclarations(FrameTraceDeclarations),
+    DispatcherDeclarations(DispatcherDeclarations),
+    Region(Region),
     ParserStateStruct(ParserStateStruct),
-    ParserStateInitFunction(ParserStateInitFunction),
+    CallbacksStruct(CallbacksStruct),
     MessageStruct(MessageStruct),
+    MessageStructUnion(MessageStructUnion),
     MessageStructMember(MessageStructMember),
+    DocComment(DocComment),
+    RuntimeInclude(RuntimeInclude),
+    StandardIncludes(StandardIncludes),
+    CppLinkage(CppLinkage),
+    BenchmarkDeclarations(BenchmarkDeclarations),
     Common(common::AstNode),
 }
 
@@ -340,7 +6483,7 @@ impl TreeBasedCodeGeneration for AstNode {
     fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
+    ) -> Vec<CodeChunk> {
         self.ast_node_type
             .generate_code_pre_traverse(code_generation_state)
     }
@@ -348,7 +6491,7 @@ impl TreeBasedCodeGeneration for AstNode {
     fn generate_code_post_traverse(
         &self,
         code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
+    ) -> Vec<CodeChunk> {
         self.ast_node_type
             .generate_code_post_traverse(code_generation_state)
     }
@@ -358,61 +6501,147 @@ impl TreeBasedCodeGeneration for AstNodeType {
     fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
+    ) -> Vec<CodeChunk> {
         match self {
-            AstNodeType::ParsingFunction(ref node) => {
+            AstNodeType::ParserStateStruct(ref node) => {
                 node.generate_code_pre_traverse(code_generation_state)
             }
-            AstNodeType::ParserStateStruct(ref node) => {
+            AstNodeType::CallbacksStruct(ref node) => {
                 node.generate_code_pre_traverse(code_generation_state)
             }
-            AstNodeType::ParserStateInitFunction(ref node) => {
+            AstNodeType::TlvRecordStruct(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::GroupStruct(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::ApiPrototypes(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::ParseErrorEnum(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::SliceStruct(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::StatisticsStruct(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::SizingConstants(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::FlagConstants(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::FrameTraceDeclarations(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::DispatcherDeclarations(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::Region(ref node) => {
                 node.generate_code_pre_traverse(code_generation_state)
             }
             AstNodeType::MessageStruct(ref node) => {
                 node.generate_code_pre_traverse(code_generation_state)
             }
+            AstNodeType::MessageStructUnion(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
             AstNodeType::MessageStructMember(ref node) => {
                 node.generate_code_pre_traverse(code_generation_state)
             }
+            AstNodeType::DocComment(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::RuntimeInclude(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::StandardIncludes(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::CppLinkage(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::BenchmarkDeclarations(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
             // Delegate further generation to common
             AstNodeType::Common(ref node) => node.generate_code(code_generation_state),
-            AstNodeType::Root => LinkedList::new(),
-            n => {
-                log::warn!("Unhandled node {:?}, skipping", n);
-
-                LinkedList::new()
-            }
+            AstNodeType::Root => Vec::new(),
         }
     }
 
     fn generate_code_post_traverse(
         &self,
         code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
+    ) -> Vec<CodeChunk> {
         match self {
-            AstNodeType::ParsingFunction(ref node) => {
+            AstNodeType::ParserStateStruct(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::CallbacksStruct(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::TlvRecordStruct(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::GroupStruct(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::ApiPrototypes(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::ParseErrorEnum(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::SliceStruct(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::StatisticsStruct(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::SizingConstants(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::FlagConstants(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::FrameTraceDeclarations(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::DispatcherDeclarations(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::Region(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::MessageStruct(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::MessageStructUnion(ref node) => {
                 node.generate_code_post_traverse(code_generation_state)
             }
-            AstNodeType::ParserStateStruct(ref node) => {
+            AstNodeType::MessageStructMember(ref node) => {
                 node.generate_code_post_traverse(code_generation_state)
             }
-            AstNodeType::ParserStateInitFunction(ref node) => {
-                node.generate_code_pre_traverse(code_generation_state)
+            AstNodeType::DocComment(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
             }
-            AstNodeType::MessageStruct(ref node) => {
+            AstNodeType::RuntimeInclude(ref node) => {
                 node.generate_code_post_traverse(code_generation_state)
             }
-            AstNodeType::MessageStructMember(ref node) => {
+            AstNodeType::StandardIncludes(ref node) => {
                 node.generate_code_post_traverse(code_generation_state)
             }
-            AstNodeType::Common(ref node) => LinkedList::new(),
-            AstNodeType::Root => LinkedList::new(),
-            n => {
-                log::warn!("Unhandled node {:?}, skipping", n);
-
-                LinkedList::new()
+            AstNodeType::CppLinkage(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
             }
+            AstNodeType::BenchmarkDeclarations(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::Common(_) => Vec::new(),
+            AstNodeType::Root => Vec::new(),
         }
     }
 }
@@ -420,35 +6649,75 @@ impl TreeBasedCodeGeneration for AstNodeType {
 /// AST tree for generating C source files
 pub struct SourceAstNode {
     ast_node: AstNode,
+
+    /// The header declaring the types, unless the source is a part of split
+    /// output, whose files include their headers by `OutputFile`
+    header_name: Option<String>,
 }
 
 impl CodeGeneration for SourceAstNode {
     fn generate_code(
         &self,
         code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        self.ast_node.generate_code(code_generation_state)
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::new();
+
+        // Collecting into a buffer never fails
+        let _ = self.generate_code_into(code_generation_state, &mut ret);
+
+        ret
+    }
+
+    fn generate_code_into(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+        sink: &mut dyn codegen::CodeChunkSink,
+    ) -> Result<(), RobustoError> {
+        if let Some(ref header_name) = self.header_name {
+            sink.accept(
+                CodeChunk::new(format!("#include \"{0}\"", header_name), 0, 1)
+                    .with_fragment(Some(&codegen::Fragment::Includes)),
+            )?;
+        }
+
+        self.ast_node.generate_code_into(code_generation_state, sink)
     }
 }
 
 impl From<&Protocol> for SourceAstNode {
     fn from(protocol: &Protocol) -> Self {
-        let mut ret = AstNode {
-            ast_node_type: AstNodeType::Root,
-            children: vec![],
-        };
-        let mut common = common::AstNode::from(protocol);
+        SourceAstNode::from_part(protocol, &common::OutputPart::Whole)
+    }
+}
+
+impl SourceAstNode {
+    /// Builds the source of the given part of the generated code
+    pub fn from_part(protocol: &Protocol, part: &common::OutputPart) -> SourceAstNode {
+        let mut ret = AstNode::new();
+        let mut common = common::AstNode::from_part(protocol, part);
 
         // Traverse over the tree and replace generic platform dependent definitions w/ concrete ones
         common.apply_replacement_recursive(SourceAstNode::preprocess_common);
 
         ret.add_child(AstNodeType::Common(common));
+        let header_name = match part {
+            common::OutputPart::Whole => Some(artifacts::ArtifactNaming::default().header()),
+            _ => None,
+        };
 
-        SourceAstNode { ast_node: ret }
+        SourceAstNode {
+            ast_node: ret,
+            header_name,
+        }
+    }
+
+    /// Includes the header of the given name
+    pub fn with_header_name(mut self, header_name: &str) -> SourceAstNode {
+        self.header_name = self.header_name.map(|_| header_name.to_string());
+
+        self
     }
-}
 
-impl SourceAstNode {
     /// Replaces platform-dependent code chunks
     fn preprocess_common(common: &mut common::AstNode) {
         match common.ast_node_type {
@@ -456,10 +6725,6 @@ impl SourceAstNode {
                 common.ast_node_type =
                     common::AstNodeType::RawCode(RawCode::from(&ParsingFunction::from(node)));
             }
-            common::AstNodeType::MessageStruct(ref mut node) => {
-                common.ast_node_type =
-                    common::AstNodeType::RawCode(RawCode::from(&MessageStruct::from(node)));
-            }
             common::AstNodeType::MessageStructMember(ref mut node) => {
                 common.ast_node_type =
                     common::AstNodeType::RawCode(RawCode::from(&MessageStructMember::from(node)));
@@ -469,6 +6734,160 @@ impl SourceAstNode {
                     &ParserStateInitFunction::from(node),
                 ));
             }
+            common::AstNodeType::PresenceBitmapReset(ref mut node) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(
+                    &PresenceBitmapReset::from(node),
+                ));
+            }
+            common::AstNodeType::ParsedCallback(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&ParsedCallback::from(node)));
+            }
+            common::AstNodeType::PresenceBitmapUpdate(ref mut node) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(
+                    &PresenceBitmapUpdate::from(node),
+                ));
+            }
+            common::AstNodeType::BitReader(ref node) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(&BitReader {
+                    bit_orders: node.bit_orders.clone(),
+                }));
+            }
+            common::AstNodeType::BitstreamParsingFunction(ref mut node) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(
+                    &BitstreamParsingFunction::from(node),
+                ));
+            }
+            common::AstNodeType::BitstreamFieldRead(ref mut node) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(
+                    &BitstreamFieldRead::from(node),
+                ));
+            }
+            common::AstNodeType::TlvAction(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&TlvAction::from(node)));
+            }
+            common::AstNodeType::GroupAction(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&GroupAction::from(node)));
+            }
+            common::AstNodeType::VarintAction(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&VarintAction::from(node)));
+            }
+            common::AstNodeType::RegexAction(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&RegexAction::from(node)));
+            }
+            common::AstNodeType::ConditionCheck(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&ConditionCheck::from(node)));
+            }
+            common::AstNodeType::ScaledValueAccessor(ref mut node) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(
+                    &ScaledValueAccessor::from(node),
+                ));
+            }
+            common::AstNodeType::FlagsAccessor(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&FlagsAccessor::from(node)));
+            }
+            common::AstNodeType::TimestampAccessor(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&TimestampAccessor::from(node)));
+            }
+            common::AstNodeType::StructFunctions(ref mut node) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(&StructFunctions::from(node)));
+            }
+            common::AstNodeType::DumpFunction(ref mut node) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(&DumpFunction::from(node)));
+            }
+            common::AstNodeType::SymbolDecoder(ref symbol_decoding) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(&SymbolDecoder {
+                    symbol_decoding: symbol_decoding.clone(),
+                }));
+            }
+            common::AstNodeType::SymbolDecodingGetKey(_) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode("getkey robustoDecodeSymbol(*p);".into());
+            }
+            common::AstNodeType::AlphTypeSequence => {
+                // Plain `char` is signed on some hosts, and unsigned on others
+                common.ast_node_type =
+                    common::AstNodeType::RawCode("alphtype unsigned char;".into());
+            }
+            common::AstNodeType::IntegerFieldByte(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&IntegerFieldByte::from(node)));
+            }
+            common::AstNodeType::SelfTestFunction(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&SelfTestFunction::from(node)));
+            }
+            common::AstNodeType::BenchmarkFunction(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&BenchmarkFunction::from(node)));
+            }
+            common::AstNodeType::SerializingFunction(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&SerializingFunction::from(node)));
+            }
+            common::AstNodeType::RangeCheck(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&RangeCheck::from(node)));
+            }
+            common::AstNodeType::ExpressionCheck(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&ExpressionCheck::from(node)));
+            }
+            common::AstNodeType::ReservedFieldByte(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&ReservedFieldByte::from(node)));
+            }
+            common::AstNodeType::BitstreamReservedField(ref mut node) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(
+                    &BitstreamReservedField::from(node),
+                ));
+            }
+            common::AstNodeType::FrameTrace(ref frame_trace) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(&FrameTrace {
+                    timestamp_hook: frame_trace.attribute.timestamp_hook.clone(),
+                    storage_qualifier: frame_trace.attribute.storage_qualifier.clone(),
+                    storage: frame_trace.storage,
+                    recorder: frame_trace.recorder,
+                }));
+            }
+            common::AstNodeType::Region(ref region) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(&Region {
+                    name: region.name.clone(),
+                    user_code_anchor: region.user_code_anchor,
+                }));
+            }
+            common::AstNodeType::Dispatcher(ref dispatcher) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(&Dispatcher {
+                    dispatcher: dispatcher.clone(),
+                }));
+            }
+            common::AstNodeType::DiagnosticStrings(ref diagnostic_strings) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(&DiagnosticStrings {
+                    storage_qualifier: diagnostic_strings.attribute.storage_qualifier.clone(),
+                    misra_c: diagnostic_strings.misra_c,
+                }));
+            }
+            common::AstNodeType::ChecksumAction(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&ChecksumAction::from(node)));
+            }
+            common::AstNodeType::LengthAction(ref mut node) => {
+                common.ast_node_type =
+                    common::AstNodeType::RawCode(RawCode::from(&LengthAction::from(node)));
+            }
+            common::AstNodeType::ChecksumRoutines(ref routines) => {
+                common.ast_node_type = common::AstNodeType::RawCode(RawCode::from(&ChecksumRoutines {
+                    algorithms: routines.algorithms.clone(),
+                    implementation: routines.implementation,
+                }));
+            }
             common::AstNodeType::AccessSequence => {
                 common.ast_node_type =
                     common::AstNodeType::RawCode("access aParserState->;".into());
@@ -480,56 +6899,388 @@ impl SourceAstNode {
 
 pub struct HeaderAstNode {
     ast_node: AstNode,
+
+    /// Unless the header is a part of split output, whose files are guarded
+    /// by `OutputFile`
+    guard: Option<HeaderGuard>,
 }
 
 impl From<&Protocol> for HeaderAstNode {
     fn from(protocol: &Protocol) -> Self {
-        let mut ret = AstNode {
-            ast_node_type: AstNodeType::Root,
-            children: vec![],
-        };
+        HeaderAstNode::from_part(protocol, &common::OutputPart::Whole)
+    }
+}
 
-        // Generate message structs
-        // TODO: move it into header
-        // TODO: use the code from `common.rs`
-        for message in &protocol.messages {
-            let mut message_struct = ret.add_child(AstNodeType::MessageStruct(MessageStruct {
-                message_name: message.name.clone(),
+impl HeaderAstNode {
+    /// Builds the header of the given part of the generated code
+    pub fn from_part(protocol: &Protocol, part: &common::OutputPart) -> HeaderAstNode {
+        let mut ret = AstNode::new();
+
+        if let Some(doc) = protocol.doc().filter(|_| part.includes_umbrella()) {
+            ret.add_child(AstNodeType::DocComment(DocComment {
+                text: doc.to_string(),
+                is_file: true,
+            }));
+        }
+
+        if part.includes_shared() {
+            ret.add_child(AstNodeType::StandardIncludes(StandardIncludes {
+                sizes: protocol.serializers() || protocol.misra_c(),
+                memory: protocol.struct_functions(),
+            }));
+        }
+
+        if protocol.uses_runtime() && part.includes_shared() {
+            ret.add_child(AstNodeType::Region(Region {
+                name: "Runtime".to_string(),
+                user_code_anchor: protocol.user_code_anchors(),
+            }))
+            .add_child(AstNodeType::RuntimeInclude(RuntimeInclude {}));
+        }
+
+        // Declarations take C linkage in C++, the includes are left as they are
+        let mut declarations = AstNode::new();
+
+        if part.includes_shared() {
+            declarations.add_child(AstNodeType::Region(Region {
+                name: "Parse errors".to_string(),
+                user_code_anchor: protocol.user_code_anchors(),
+            }))
+            .add_child(AstNodeType::ParseErrorEnum(ParseErrorEnum {
+                trailing_comma: protocol.c_standard().trailing_enumerator_comma(),
+            }));
+        }
+
+        if part.includes_shared() && protocol.messages.iter().any(|message| protocol.zero_copy(message)) {
+            declarations.add_child(AstNodeType::Region(Region {
+                name: "Slices".to_string(),
+                user_code_anchor: protocol.user_code_anchors(),
+            }))
+            .add_child(AstNodeType::SliceStruct(SliceStruct {}));
+        }
+
+        if part.includes_shared() && protocol.messages.iter().any(|message| protocol.parser_statistics(message)) {
+            declarations.add_child(AstNodeType::Region(Region {
+                name: "Statistics".to_string(),
+                user_code_anchor: protocol.user_code_anchors(),
+            }))
+            .add_child(AstNodeType::StatisticsStruct(StatisticsStruct {}));
+        }
+
+        let mut group_definition_structs = common::group_definition_structs(protocol);
+
+        if part.includes_shared() && !group_definition_structs.is_empty() {
+            let region = declarations.add_child(AstNodeType::Region(Region {
+                name: "Group definitions".to_string(),
+                user_code_anchor: protocol.user_code_anchors(),
+            }));
+
+            for group_struct in &mut group_definition_structs {
+                region.add_child(AstNodeType::GroupStruct(GroupStruct::from(group_struct)));
+            }
+        }
+
+        // The header alone declares the messages' types, the sources include it
+        for message in protocol
+            .messages
+            .iter()
+            .filter(|message| part.includes_message(&message.name))
+        {
+            let region = declarations.add_child(AstNodeType::Region(Region {
+                name: format!("{0} types", message.name),
+                user_code_anchor: protocol.user_code_anchors(),
             }));
 
             for field in &message.fields {
-                message_struct.add_child(AstNodeType::MessageStructMember(MessageStructMember {
-                    name: field.name.clone(),
-                    field_base_type: match field.field_type {
-                        representation::FieldType::Regex(ref regex) => FieldBaseType::I8,
-                        _ => {
-                            log::error!("Unhandled field type, panicking!");
-                            panic!();
-                        }
-                    },
-                    array_length: {
-                        let mut length = 1usize;
-
-                        for attribute in &field.attributes {
-                            if let representation::FieldAttribute::MaxLength(ref max_length) =
-                                attribute
-                            {
-                                length = max_length.value;
+                match field.field_type {
+                    representation::FieldType::Tlv(ref tlv) => {
+                        let mut record_struct = common::TlvRecordStruct {
+                            message_name: message.name.clone(),
+                            field_name: field.name.clone(),
+                            variants: common::TlvRecordVariant::from_tlv(tlv),
+                        };
+                        region.add_child(AstNodeType::TlvRecordStruct(TlvRecordStruct::from(
+                            &mut record_struct,
+                        )));
+                    }
+                    // Shared types are declared along with the rest of the
+                    // shared part
+                    representation::FieldType::Group(ref group) if group.definition.is_none() => {
+                        let mut group_struct = common::GroupStruct {
+                            name: common::group_struct_name(&message.name, &field.name, group),
+                            members: common::group_struct_members(&message.name, group),
+                        };
+                        region.add_child(AstNodeType::GroupStruct(GroupStruct::from(
+                            &mut group_struct,
+                        )));
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(doc) = message.doc() {
+                region.add_child(AstNodeType::DocComment(DocComment {
+                    text: doc.to_string(),
+                    is_file: false,
+                }));
+            }
+
+            for field in &message.fields {
+                if let representation::FieldType::Flags(ref flags) = field.field_type {
+                    region.add_child(AstNodeType::FlagConstants(FlagConstants::from_field(
+                        &message.name,
+                        &field.name,
+                        flags,
+                    )));
+                }
+            }
+
+            let message_struct = region.add_child(AstNodeType::MessageStruct(MessageStruct {
+                message_name: message.name.clone(),
+                symbol_naming: protocol.symbol_naming(),
+            }));
+
+            for region in message.field_regions() {
+                let parent = match region[0].conditional_on() {
+                    Some(conditional_on) if region.iter().any(common::has_struct_member) => {
+                        message_struct.add_child(AstNodeType::MessageStructUnion(MessageStructUnion {
+                            name: common::conditional_union_member_name(&conditional_on.field),
+                        }))
+                    }
+                    _ => &mut *message_struct,
+                };
+
+                for field in region.iter().filter(|field| common::has_struct_member(field)) {
+                    // Members of scaled fields hold raw values
+                    let unit = field.unit().map(|unit| match field.scale() {
+                        Some(_) => format!("Unit: {0}, once scaled", unit),
+                        None => format!("Unit: {0}", unit),
+                    });
+
+                    // Doxygen-style, so IDEs flag the member. A compiler attribute
+                    // would flag the parser's own stores as well
+                    let deprecation = field
+                        .deprecated()
+                        .map(|reason| format!("@deprecated {0}", reason).trim_end().to_string());
+                    let doc = field
+                        .doc()
+                        .map(|doc| doc.trim().to_string())
+                        .into_iter()
+                        .chain(unit)
+                        .chain(deprecation)
+                        .collect::<Vec<String>>();
+
+                    if !doc.is_empty() {
+                        parent.add_child(AstNodeType::DocComment(DocComment {
+                            text: doc.join("\n\n"),
+                            is_file: false,
+                        }));
+                    }
+
+                    if let (representation::FieldType::Regex(_), true) = (&field.field_type, protocol.zero_copy(message)) {
+                        parent.add_child(AstNodeType::MessageStructMember(MessageStructMember {
+                            name: field.name.clone(),
+                            field_base_type: FieldBaseType::Struct(common::SLICE_STRUCT_NAME.to_string()),
+                            array_length: 0usize,
+                        }));
+
+                        continue;
+                    }
+
+                    parent.add_child(AstNodeType::MessageStructMember(MessageStructMember {
+                        name: field.name.clone(),
+                        field_base_type: FieldBaseType::from_field(&message.name, field),
+                        array_length: match field.field_type {
+                            representation::FieldType::Regex(_) | representation::FieldType::Tlv(_) => {
+                                common::array_capacity(field)
                             }
-                        }
+                            representation::FieldType::Integer(_)
+                            | representation::FieldType::Varint(_)
+                            | representation::FieldType::Flags(_)
+                            | representation::FieldType::Timestamp(_) => 0usize,
+                            representation::FieldType::Reserved(ref reserved) => reserved.length,
+                            representation::FieldType::Group(ref group) => group.count,
+                        },
+                    }));
+
+                    let count_member_name = match field.field_type {
+                        representation::FieldType::Regex(_) => Some(common::regex_length_member_name(&field.name)),
+                        representation::FieldType::Tlv(_) => Some(common::tlv_count_member_name(&field.name)),
+                        _ => None,
+                    };
 
-                        length
+                    if let Some(count_member_name) = count_member_name {
+                        parent.add_child(AstNodeType::MessageStructMember(
+                            MessageStructMember {
+                                name: count_member_name,
+                                field_base_type: FieldBaseType::U32,
+                                array_length: 0usize,
+                            },
+                        ));
+                    }
+                }
+            }
+
+            if message.field_order() == FieldOrder::Unordered {
+                message_struct.add_child(AstNodeType::MessageStructMember(MessageStructMember {
+                    name: common::PRESENCE_BITMAP_MEMBER_NAME.to_string(),
+                    field_base_type: FieldBaseType::U32,
+                    array_length: 0usize,
+                }));
+            }
+
+            // Bit-granular messages are parsed without Ragel, and have no state
+            if message.layout() == MessageLayout::Bits {
+                continue;
+            }
+
+            let parser_state_struct =
+                region.add_child(AstNodeType::ParserStateStruct(ParserStateStruct {
+                    machine_name: message.name.clone(),
+                    symbol_naming: protocol.symbol_naming(),
+                }));
+
+            for member in common::parse_error_parser_state_members()
+                .into_iter()
+                .chain(common::fixed_length_parser_state_members(message))
+                .chain(common::group_parser_state_members(message))
+                .chain(common::varint_parser_state_members(message))
+                .chain(common::frame_trace_parser_state_members(protocol))
+                .chain(common::byte_stuffing_parser_state_members(protocol))
+                .chain(common::checksum_parser_state_members(message))
+                .chain(common::length_parser_state_members(message))
+                .chain(common::zero_copy_parser_state_members(protocol, message))
+                .chain(common::discard_counting_parser_state_members(protocol, message))
+                .chain(common::statistics_parser_state_members(protocol, message))
+            {
+                parser_state_struct.add_child(AstNodeType::MessageStructMember(
+                    MessageStructMember {
+                        name: member.name,
+                        field_base_type: member.field_base_type,
+                        array_length: member.array_length,
                     },
+                ));
+            }
+
+            if let Some(SymbolDecoding::FourBFiveB) = protocol.symbol_decoding() {
+                for member in common::four_b_five_b_parser_state_members() {
+                    parser_state_struct.add_child(AstNodeType::MessageStructMember(
+                        MessageStructMember {
+                            name: member.name,
+                            field_base_type: member.field_base_type,
+                            array_length: member.array_length,
+                        },
+                    ));
+                }
+            }
+
+            if message
+                .fields
+                .iter()
+                .any(|field| matches!(field.field_type, representation::FieldType::Tlv(_)))
+            {
+                for member in common::tlv_parser_state_members() {
+                    parser_state_struct.add_child(AstNodeType::MessageStructMember(
+                        MessageStructMember {
+                            name: member.name,
+                            field_base_type: member.field_base_type,
+                            array_length: member.array_length,
+                        },
+                    ));
+                }
+            }
+
+            if protocol.callbacks() {
+                region.add_child(AstNodeType::CallbacksStruct(CallbacksStruct {
+                    message_name: message.name.clone(),
+                    symbol_naming: protocol.symbol_naming(),
                 }));
             }
+        }
+
+        if !protocol.messages.is_empty() && part.includes_umbrella() {
+            declarations.add_child(AstNodeType::Region(Region {
+                name: "Sizing constants".to_string(),
+                user_code_anchor: protocol.user_code_anchors(),
+            }))
+            .add_child(AstNodeType::SizingConstants(SizingConstants::from(protocol)));
+        }
+
+        if let Some(frame_trace) = protocol.frame_trace().filter(|_| part.includes_shared()) {
+            declarations.add_child(AstNodeType::Region(Region {
+                name: "Frame trace".to_string(),
+                user_code_anchor: protocol.user_code_anchors(),
+            }))
+            .add_child(AstNodeType::FrameTraceDeclarations(FrameTraceDeclarations {
+                depth: frame_trace.depth,
+                message_names: protocol.messages.iter().map(|m| m.name.clone()).collect(),
+                timestamp_hook: frame_trace.timestamp_hook.clone(),
+            }));
+        }
+
+        if let Some(benchmark_function) =
+            common::BenchmarkFunction::from_protocol(protocol).filter(|_| part.includes_shared())
+        {
+            declarations.add_child(AstNodeType::Region(Region {
+                name: "Benchmark".to_string(),
+                user_code_anchor: protocol.user_code_anchors(),
+            }))
+            .add_child(AstNodeType::BenchmarkDeclarations(BenchmarkDeclarations {
+                pattern_count: benchmark_function.patterns.len(),
+                clock_hook: benchmark_function.attribute.clock_hook.clone(),
+            }));
+        }
+
+        if let Some(dispatcher) =
+            common::Dispatcher::from_protocol(protocol).filter(|_| part.includes_umbrella())
+        {
+            if !DispatcherDeclarations::has_message_id_enum(&dispatcher) {
+                log::warn!("message IDs exceed the range of an enum, only their macros are generated");
+            }
 
-            // TODO: move it into header
-            ret.add_child(AstNodeType::ParserStateStruct(ParserStateStruct {
-                machine_name: message.name.clone(),
+            declarations.add_child(AstNodeType::Region(Region {
+                name: "Dispatcher".to_string(),
+                user_code_anchor: protocol.user_code_anchors(),
+            }))
+            .add_child(AstNodeType::DispatcherDeclarations(DispatcherDeclarations {
+                dispatcher,
+                trailing_comma: protocol.c_standard().trailing_enumerator_comma(),
             }));
         }
 
-        HeaderAstNode { ast_node: ret }
+        let functions = api_functions_of_part(protocol, part);
+
+        if !functions.is_empty() {
+            declarations.add_child(AstNodeType::Region(Region {
+                name: "API".to_string(),
+                user_code_anchor: protocol.user_code_anchors(),
+            }))
+            .add_child(AstNodeType::ApiPrototypes(ApiPrototypes { functions }));
+        }
+
+        if !declarations.children.is_empty() {
+            ret.add_child(AstNodeType::CppLinkage(CppLinkage {})).children = declarations.children;
+        }
+
+        let guard = match part {
+            common::OutputPart::Whole => Some(HeaderGuard::new(
+                &protocol.include_guard(),
+                &artifacts::ArtifactNaming::default().header(),
+                false,
+            )),
+            _ => None,
+        };
+
+        HeaderAstNode { ast_node: ret, guard }
+    }
+
+    /// Names the include guard's macro after the header's file
+    pub fn with_file_name(mut self, file_name: &str) -> HeaderAstNode {
+        self.guard = self
+            .guard
+            .map(|guard| HeaderGuard::new(&guard.include_guard, file_name, false));
+
+        self
     }
 }
 
@@ -537,7 +7288,204 @@ impl CodeGeneration for HeaderAstNode {
     fn generate_code(
         &self,
         code_generation_state: &mut codegen::CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        self.ast_node.generate_code(code_generation_state)
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::new();
+
+        // Collecting into a buffer never fails
+        let _ = self.generate_code_into(code_generation_state, &mut ret);
+
+        ret
+    }
+
+    fn generate_code_into(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+        sink: &mut dyn codegen::CodeChunkSink,
+    ) -> Result<(), RobustoError> {
+        if let Some(ref guard) = self.guard {
+            guard.open(sink)?;
+        }
+
+        self.ast_node.generate_code_into(code_generation_state, sink)?;
+
+        match self.guard {
+            Some(ref guard) => guard.close(sink),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A file of split output
+pub struct OutputFile {
+    pub name: String,
+    includes: Vec<String>,
+
+    /// Headers only
+    guard: Option<HeaderGuard>,
+    code: Box<dyn CodeGeneration>,
+}
+
+impl OutputFile {
+    fn new(name: String, includes: &[String], guard: Option<HeaderGuard>, code: Box<dyn CodeGeneration>) -> OutputFile {
+        OutputFile {
+            name,
+            includes: includes.to_vec(),
+            guard,
+            code,
+        }
+    }
+
+    pub fn is_header(&self) -> bool {
+        self.guard.is_some()
+    }
+}
+
+impl CodeGeneration for OutputFile {
+    fn generate_code(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::new();
+
+        // Collecting into a buffer never fails
+        let _ = self.generate_code_into(code_generation_state, &mut ret);
+
+        ret
+    }
+
+    fn generate_code_into(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+        sink: &mut dyn codegen::CodeChunkSink,
+    ) -> Result<(), RobustoError> {
+        if let Some(ref guard) = self.guard {
+            guard.open(sink)?;
+        }
+
+        for include in &self.includes {
+            sink.accept(
+                CodeChunk::new(format!("#include \"{0}\"", include), 0, 1)
+                    .with_fragment(Some(&codegen::Fragment::Includes)),
+            )?;
+        }
+
+        self.code.generate_code_into(code_generation_state, sink)?;
+
+        match self.guard {
+            Some(ref guard) => guard.close(sink),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Splits the generated code into a header and a source per message group,
+/// a header and a source of the code shared by every message, and an
+/// umbrella header including the rest of the headers. A group's source only
+/// includes its own header, so touching a message rebuilds one source.
+pub fn split_output(
+    protocol: &Protocol,
+    splitting: &representation::OutputSplitting,
+    file_stem: &str,
+) -> Vec<OutputFile> {
+    let groups = match splitting {
+        representation::OutputSplitting::PerMessage => protocol
+            .messages
+            .iter()
+            .map(|message| {
+                (
+                    utility::string::to_upper_snake_case(&message.name).to_lowercase(),
+                    vec![message.name.clone()],
+                )
+            })
+            .collect::<Vec<(String, Vec<String>)>>(),
+        representation::OutputSplitting::Groups(groups) => groups
+            .iter()
+            .map(|group| (group.name.clone(), group.messages.clone()))
+            .collect(),
+    };
+    let include_guard = protocol.include_guard();
+    let guard = |name: &String| Some(HeaderGuard::new(&include_guard, name, true));
+    let umbrella_header_name = format!("{0}.h", file_stem);
+    let shared_header_name = format!("{0}_shared.h", file_stem);
+    let mut ret = vec![
+        OutputFile::new(
+            shared_header_name.clone(),
+            &[],
+            guard(&shared_header_name),
+            Box::new(HeaderAstNode::from_part(protocol, &common::OutputPart::Shared)),
+        ),
+        OutputFile::new(
+            format!("{0}_shared.c.rl", file_stem),
+            std::slice::from_ref(&umbrella_header_name),
+            None,
+            Box::new(SourceAstNode::from_part(protocol, &common::OutputPart::Shared)),
+        ),
+    ];
+    let mut umbrella_includes = vec![shared_header_name.clone()];
+
+    for (group_name, message_names) in groups {
+        let part = common::OutputPart::Messages(message_names);
+        let header_name = format!("{0}_{1}.h", file_stem, group_name);
+        ret.push(OutputFile::new(
+            header_name.clone(),
+            std::slice::from_ref(&shared_header_name),
+            guard(&header_name),
+            Box::new(HeaderAstNode::from_part(protocol, &part)),
+        ));
+        ret.push(OutputFile::new(
+            format!("{0}_{1}.c.rl", file_stem, group_name),
+            std::slice::from_ref(&header_name),
+            None,
+            Box::new(SourceAstNode::from_part(protocol, &part)),
+        ));
+        umbrella_includes.push(header_name);
     }
+
+    ret.push(OutputFile::new(
+        umbrella_header_name.clone(),
+        &umbrella_includes,
+        guard(&umbrella_header_name),
+        Box::new(HeaderAstNode::from_part(protocol, &common::OutputPart::Umbrella)),
+    ));
+
+    ret
+}
+
+/// Renders the C backend's artifacts into the set: the Ragel source, the
+/// header, the API report and manifest, and, if the protocol asks for it, the
+/// split output
+pub fn add_artifacts(
+    protocol: &Protocol,
+    naming: &artifacts::ArtifactNaming,
+    settings: &artifacts::ArtifactSettings,
+    artifact_set: &mut artifacts::GeneratedArtifactSet,
+) -> Result<(), RobustoError> {
+    artifact_set.add_c_code(
+        &naming.ragel_source(),
+        artifacts::ArtifactKind::RagelSource,
+        &SourceAstNode::from(protocol).with_header_name(&naming.header()),
+        settings,
+        protocol.c_standard(),
+    )?;
+    artifact_set.add_c_code(
+        &naming.header(),
+        artifacts::ArtifactKind::Header,
+        &HeaderAstNode::from(protocol).with_file_name(&naming.header()),
+        settings,
+        protocol.c_standard(),
+    )?;
+    artifact_set.add_report(&naming.api_report(), &ApiReport::from(protocol))?;
+    artifact_set.add_report(&naming.api_manifest(), &ApiManifest::from(protocol))?;
+
+    if let Some(output_splitting) = protocol.output_splitting() {
+        for output_file in split_output(protocol, output_splitting, &naming.split_stem) {
+            let kind = match output_file.is_header() {
+                true => artifacts::ArtifactKind::Header,
+                false => artifacts::ArtifactKind::RagelSource,
+            };
+            artifact_set.add_c_code(&output_file.name, kind, &output_file, settings, protocol.c_standard())?;
+        }
+    }
+
+    Ok(())
 }
@@ -1,7 +1,10 @@
 use crate::bpir;
-use crate::bpir::representation::{FieldAttribute, FieldType};
+use crate::bpir::representation::{
+    BitOrder, FieldOrder, FieldType, MessageLayout, OverflowPolicy, SymbolDecoding, SymbolNaming,
+};
+use crate::utility::string::capitalize;
 use crate::utility::codegen::{
-    CodeChunk, CodeGenerationState, RawCode, SubnodeAccess, TreeBasedCodeGeneration,
+    CodeChunk, CodeGenerationState, Fragment, Origin, RawCode, SubnodeAccess, TreeBasedCodeGeneration,
 };
 use log;
 
@@ -15,8 +18,8 @@ use log;
 /// - Support for functions;
 /// - Support for mutable pointers or similar entities;
 pub use std;
-use std::collections::LinkedList;
 use std::string::String;
+use std::vec::Vec;
 
 /// Represents an abstract syntactic tree for Ragel code, with the difference
 /// that its leaves mostly consist of snippets rather than atomic language
@@ -26,34 +29,164 @@ use std::string::String;
 pub struct ParsingFunction {
     /// Each parsing function is supposed to be associated w/ a particular message
     pub message_name: std::string::String,
+    pub symbol_naming: SymbolNaming,
+
+    /// Decoding placed between the input buffer and the state machine
+    pub symbol_decoding: Option<SymbolDecoding>,
+
+    /// Escape sequences removed before symbol decoding
+    pub byte_stuffing: Option<bpir::representation::ByteStuffingAttribute>,
+
+    /// Number of input bytes after which the parser rejects the frame, if the
+    /// message has a fixed length
+    pub max_frame_length: Option<usize>,
+
+    /// Identifies the message in frame trace records, if frames are traced
+    pub trace_message_id: Option<usize>,
+
+    /// Restart the machine after an error, so it scans for the sync sequence
+    pub resynchronize: bool,
+
+    /// Whether the bytes of the attempts resynchronization abandons are
+    /// counted as dropped
+    pub discard_counting: bool,
+
+    /// Whether the outcomes of frames are counted, see
+    /// `statistics_parser_state_members()`
+    pub statistics: bool,
+
+    /// The machine invokes the message's callback on each accepted frame
+    pub callbacks: bool,
+
+    /// Regex fields refer into the input, whose position the parser tracks
+    pub zero_copy: bool,
+
+    /// Declarations may follow statements, see `CStandard`
+    pub mixed_declarations: bool,
+
+    /// Each function returns once, see `ProtocolAttribute::MisraC`
+    pub misra_c: bool,
 }
 
 #[derive(Debug)]
 pub struct RegexMachineField {
-    /// Ragel machine string sequence (definition)
+    /// Ragel machine expression matching the field, see
+    /// `bpir::pattern::Pattern::ragel`
     pub string_sequence: std::string::String,
     pub name: std::string::String,
+
+    /// Whether the matched bytes are copied into the field's member
+    pub capture: bool,
+}
+
+/// A field of a known length which does not impose any restrictions on its
+/// content
+#[derive(Debug)]
+pub struct FixedLengthMachineField {
+    pub name: std::string::String,
+
+    /// Length in bytes
+    pub length: usize,
+
+    /// Action hook invoked on every byte of the field, if any
+    pub byte_action: Option<String>,
+
+    /// Condition hook which has to hold for the field to be present, if any
+    pub condition: Option<String>,
 }
 
 #[derive(Debug)]
 pub struct MachineHeader {
     pub machine_name: std::string::String,
+
+    /// Options of the `write data` statement
+    pub write_data_options: std::vec::Vec<bpir::representation::RagelWriteDataOption>,
 }
 
 #[derive(Debug)]
 pub struct MachineDefinition {
     pub machine_name: std::string::String,
     pub fields: std::vec::Vec<String>,
-}
+    pub field_order: FieldOrder,
 
-#[derive(Debug)]
-pub struct MessageStruct {
-    pub message_name: std::string::String,
+    /// Input up to the first occurrence of the sequence is skipped
+    pub sync_sequence: Option<std::vec::Vec<u8>>,
+
+    /// Action hook invoked once the machine accepts a frame
+    pub acceptance_hook: Option<String>,
+
+    /// Whether the bytes dropped while resynchronizing are counted, see
+    /// `discard_counting_parser_state_members()`
+    pub discard_counting: bool,
 }
 
 #[derive(Clone, Debug)]
 pub enum FieldBaseType {
     I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+
+    /// IEEE 754 single precision
+    F32,
+
+    /// IEEE 754 double precision
+    F64,
+
+    /// A composite type defined by the generated code
+    Struct(String),
+}
+
+impl FieldBaseType {
+    /// Picks the narrowest integer type capable of holding `bit_width` bits
+    pub fn from_bit_width(bit_width: usize, signed: bool) -> FieldBaseType {
+        match (bit_width, signed) {
+            (0..=8, true) => FieldBaseType::I8,
+            (9..=16, true) => FieldBaseType::I16,
+            (17..=32, true) => FieldBaseType::I32,
+            (_, true) => FieldBaseType::I64,
+            (0..=8, false) => FieldBaseType::U8,
+            (9..=16, false) => FieldBaseType::U16,
+            (17..=32, false) => FieldBaseType::U32,
+            (_, false) => FieldBaseType::U64,
+        }
+    }
+
+    /// Gets the unsigned type of the same width. Non-integer types are left
+    /// intact
+    pub fn to_unsigned(&self) -> FieldBaseType {
+        match self {
+            FieldBaseType::I8 => FieldBaseType::U8,
+            FieldBaseType::I16 => FieldBaseType::U16,
+            FieldBaseType::I32 => FieldBaseType::U32,
+            FieldBaseType::I64 => FieldBaseType::U64,
+            other => other.clone(),
+        }
+    }
+
+    /// Picks a type for the struct member representing the field
+    pub fn from_field(message_name: &str, field: &bpir::representation::Field) -> FieldBaseType {
+        match field.field_type {
+            FieldType::Regex(_) | FieldType::Reserved(_) => FieldBaseType::U8,
+            FieldType::Integer(ref integer) => {
+                FieldBaseType::from_bit_width(integer.bit_width, integer.signed)
+            }
+            FieldType::Varint(ref varint) => FieldBaseType::from_bit_width(varint.bit_width, varint.signed),
+            FieldType::Flags(_) | FieldType::Timestamp(_) => {
+                FieldBaseType::from_bit_width(field.bit_width().unwrap_or(0usize), false)
+            }
+            FieldType::Tlv(_) => {
+                FieldBaseType::Struct(tlv_record_struct_name(message_name, &field.name))
+            }
+            FieldType::Group(ref group) => {
+                FieldBaseType::Struct(group_struct_name(message_name, &field.name, group))
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -80,84 +213,2717 @@ pub struct MachineActionHook {
 #[derive(Debug)]
 pub struct ParserStateInitFunction {
     pub machine_name: String,
+    pub symbol_naming: SymbolNaming,
+}
+
+/// Name of the message struct member tracking which fields of an unordered
+/// message have been parsed
+pub const PRESENCE_BITMAP_MEMBER_NAME: &str = "presenceBitmap";
+
+/// Name of the action hook which clears the presence bitmap of an unordered
+/// message
+pub const PRESENCE_BITMAP_RESET_HOOK_NAME: &str = "resetPresenceBitmap";
+
+/// Name of the action hook which invokes the callback on an accepted frame
+pub const MESSAGE_PARSED_HOOK_NAME: &str = "messageParsed";
+
+/// Invokes the message's callback, if any, on an accepted frame
+#[derive(Debug, Clone)]
+pub struct ParsedCallback {
+    pub message_name: String,
+
+    /// Null pointers are spelled `NULL`
+    pub misra_c: bool,
 }
 
+/// Clears the presence bitmap before parsing an unordered message
 #[derive(Debug)]
-pub enum AstNodeType {
-    /// An empty representation for a subtre
-    Root,
+pub struct PresenceBitmapReset {
+    pub message_name: String,
+}
 
-    // C-specific elements (TBD)
+/// Marks a field of an unordered message as parsed. Rejects the message, if
+/// the field has already been encountered.
+#[derive(Debug)]
+pub struct PresenceBitmapUpdate {
+    pub message_name: String,
+    pub field_name: String,
 
-    // Language-agnostic elements
-    /// Just treat it as a mere sequence
-    MessageStructMember(MessageStructMember),
-    MessageStruct(MessageStruct),
-    ParsingFunction(ParsingFunction),
+    /// Position of the field's bit in the presence bitmap
+    pub field_index: usize,
+}
 
-    /// Ragel-specific machine header
-    MachineHeader(MachineHeader),
-    MachineActionHook(MachineActionHook),
-    MachineDefinition(MachineDefinition),
-    RegexMachineField(RegexMachineField),
-    RawCode(RawCode),
-    ParserStateInitFunction(ParserStateInitFunction),
-    AccessSequence,
+/// Name of the tagged union struct holding a single record of a TLV field
+pub fn tlv_record_struct_name(message_name: &str, field_name: &str) -> String {
+    format!("{0}{1}Record", message_name, capitalize(field_name))
 }
 
-impl TreeBasedCodeGeneration for MachineHeader {
-    fn generate_code_pre_traverse(
-        &self,
-        generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        let mut ret = LinkedList::<CodeChunk>::new();
-        ret.push_back(CodeChunk::new(
-            "%%{".to_string(),
-            generation_state.indent,
-            1usize,
-        ));
-        ret.push_back(CodeChunk::new(
-            format!("machine {0};", self.machine_name),
-            generation_state.indent + 1,
-            1usize,
-        ));
-        ret.push_back(CodeChunk::new(
-            "write data;".to_string(),
-            generation_state.indent + 1,
-            1usize,
-        ));
+/// Name of the message struct member holding the number of parsed records of a
+/// TLV field
+pub fn tlv_count_member_name(field_name: &str) -> String {
+    format!("{0}Count", field_name)
+}
 
-        ret
+/// Errors the generated parsers report on top of rejecting the input
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    None,
+
+    /// A field's value violates its "range" attribute
+    ValueOutOfRange,
+
+    /// The frame is longer than the message's fixed length
+    FrameLength,
+
+    /// A checksum field does not match the checksum of the bytes it covers
+    Checksum,
+
+    /// A length field does not match the length of the fields it covers
+    Length,
+
+    /// A field's value violates one of its "check" attributes
+    Check,
+
+    /// An array field is longer than its max length
+    Overflow,
+}
+
+impl ParseError {
+    pub fn all() -> [ParseError; 7] {
+        [
+            ParseError::None,
+            ParseError::ValueOutOfRange,
+            ParseError::FrameLength,
+            ParseError::Checksum,
+            ParseError::Length,
+            ParseError::Check,
+            ParseError::Overflow,
+        ]
     }
 
-    fn generate_code_post_traverse(
-        &self,
-        generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        let mut ret = LinkedList::<CodeChunk>::new();
-        ret.push_back(CodeChunk::new(
-            "}%%".to_string(),
-            generation_state.indent,
-            1usize,
-        ));
+    /// Numeric code of the error, as stored in the parser state
+    pub fn code(&self) -> usize {
+        *self as usize
+    }
+
+    /// Human-readable description of the error
+    pub fn description(&self) -> &'static str {
+        match self {
+            ParseError::None => "no error",
+            ParseError::ValueOutOfRange => "field value out of range",
+            ParseError::FrameLength => "frame longer than the message's fixed length",
+            ParseError::Checksum => "checksum mismatch",
+            ParseError::Length => "length field mismatch",
+            ParseError::Check => "field check failed",
+            ParseError::Overflow => "field longer than its max length",
+        }
+    }
+}
+
+/// Outcomes the parsing functions of byte-granular messages report
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseStatus {
+    /// The frame is complete
+    Ok,
+
+    /// The frame is neither complete, nor rejected yet
+    Incomplete,
+
+    /// The frame is rejected on a checksum mismatch
+    ErrorCrc,
+
+    /// The frame is rejected on a field's value out of its range
+    ErrorRange,
+
+    /// The frame is rejected on an array field longer than its max length
+    ErrorOverflow,
+
+    /// The frame is rejected for any other reason, see the parser state's
+    /// error member
+    Error,
+}
+
+impl ParseStatus {
+    pub fn all() -> [ParseStatus; 6] {
+        [
+            ParseStatus::Ok,
+            ParseStatus::Incomplete,
+            ParseStatus::ErrorCrc,
+            ParseStatus::ErrorRange,
+            ParseStatus::ErrorOverflow,
+            ParseStatus::Error,
+        ]
+    }
+
+    /// Numeric code of the status, as returned by the parsing functions
+    pub fn code(&self) -> usize {
+        *self as usize
+    }
+
+    /// Human-readable description of the status
+    pub fn description(&self) -> &'static str {
+        match self {
+            ParseStatus::Ok => "frame complete",
+            ParseStatus::Incomplete => "frame incomplete",
+            ParseStatus::ErrorCrc => "frame rejected on a checksum mismatch",
+            ParseStatus::ErrorRange => "frame rejected on a field value out of range",
+            ParseStatus::ErrorOverflow => "frame rejected on a field longer than its max length",
+            ParseStatus::Error => "frame rejected",
+        }
+    }
+
+    /// Status of a frame rejected with the error
+    pub fn from_error(parse_error: ParseError) -> ParseStatus {
+        match parse_error {
+            ParseError::Checksum => ParseStatus::ErrorCrc,
+            ParseError::ValueOutOfRange => ParseStatus::ErrorRange,
+            ParseError::Overflow => ParseStatus::ErrorOverflow,
+            _ => ParseStatus::Error,
+        }
+    }
+}
+
+/// Name of the parser state member holding the last `ParseError`
+pub const PARSE_ERROR_MEMBER_NAME: &str = "error";
+
+/// Name of the parser state member counting the bytes fed into the parser of
+/// a fixed-length message
+pub const FRAME_LENGTH_MEMBER_NAME: &str = "frameLength";
+
+/// Name of the parser state member counting the bytes fed into the parser,
+/// for frame trace records
+pub const TRACE_LENGTH_MEMBER_NAME: &str = "traceLength";
+
+/// Parser state members of byte-granular parsers, if frames are traced
+pub fn frame_trace_parser_state_members(
+    protocol: &bpir::representation::Protocol,
+) -> std::vec::Vec<MessageStructMember> {
+    match protocol.frame_trace() {
+        Some(_) => vec![MessageStructMember {
+            name: TRACE_LENGTH_MEMBER_NAME.to_string(),
+            field_base_type: FieldBaseType::U32,
+            array_length: 0usize,
+        }],
+        None => vec![],
+    }
+}
+
+/// Name of the parser state member telling that the last input byte was an
+/// escape byte
+pub const ESCAPE_PENDING_MEMBER_NAME: &str = "escapePending";
+
+/// Parser state members of byte-granular parsers, if the protocol employs
+/// byte stuffing
+pub fn byte_stuffing_parser_state_members(
+    protocol: &bpir::representation::Protocol,
+) -> std::vec::Vec<MessageStructMember> {
+    match protocol.byte_stuffing() {
+        Some(_) => vec![MessageStructMember {
+            name: ESCAPE_PENDING_MEMBER_NAME.to_string(),
+            field_base_type: FieldBaseType::U8,
+            array_length: 0usize,
+        }],
+        None => vec![],
+    }
+}
+
+/// Name of the parser state member counting the bytes dropped while
+/// resynchronizing
+pub const DISCARDED_LENGTH_MEMBER_NAME: &str = "discardedLength";
+
+/// Name of the parser state member counting the bytes fed into the machine
+/// since it has started, which are neither known to be dropped, nor part of an
+/// accepted frame yet
+pub const PENDING_LENGTH_MEMBER_NAME: &str = "pendingLength";
+
+/// Names of the action hooks counting dropped bytes: each byte preceding the
+/// sync sequence, the completion of the sync sequence, each byte following it,
+/// and the acceptance of the frame
+pub const HUNTED_BYTE_HOOK_NAME: &str = "huntedByte";
+pub const SYNCHRONIZED_HOOK_NAME: &str = "synchronized";
+pub const FRAMED_BYTE_HOOK_NAME: &str = "framedByte";
+pub const FRAME_SETTLED_HOOK_NAME: &str = "frameSettled";
+
+/// Parser state members of byte-granular parsers, if they count the bytes
+/// they drop while resynchronizing
+pub fn discard_counting_parser_state_members(
+    protocol: &bpir::representation::Protocol,
+    message: &bpir::representation::Message,
+) -> std::vec::Vec<MessageStructMember> {
+    match protocol.discard_counting(message) {
+        true => [DISCARDED_LENGTH_MEMBER_NAME, PENDING_LENGTH_MEMBER_NAME]
+            .into_iter()
+            .map(|name| MessageStructMember {
+                name: name.to_string(),
+                field_base_type: FieldBaseType::U32,
+                array_length: 0usize,
+            })
+            .collect(),
+        false => vec![],
+    }
+}
+
+/// Name of the parser state member holding the statistics of the parser
+pub const STATISTICS_MEMBER_NAME: &str = "statistics";
+
+/// Name of the struct type holding the statistics of a parser
+pub const STATISTICS_STRUCT_NAME: &str = "RobustoParserStatistics";
+
+/// Counters of the statistics struct
+pub fn statistics_struct_members() -> std::vec::Vec<MessageStructMember> {
+    ["framesOk", "crcErrors", "resyncs", "overflows"]
+        .into_iter()
+        .map(|name| MessageStructMember {
+            name: name.to_string(),
+            field_base_type: FieldBaseType::U32,
+            array_length: 0usize,
+        })
+        .collect()
+}
+
+/// Parser state members of byte-granular parsers, if they keep statistics
+pub fn statistics_parser_state_members(
+    protocol: &bpir::representation::Protocol,
+    message: &bpir::representation::Message,
+) -> std::vec::Vec<MessageStructMember> {
+    match protocol.parser_statistics(message) {
+        true => vec![MessageStructMember {
+            name: STATISTICS_MEMBER_NAME.to_string(),
+            field_base_type: FieldBaseType::Struct(STATISTICS_STRUCT_NAME.to_string()),
+            array_length: 0usize,
+        }],
+        false => vec![],
+    }
+}
+
+/// Parser state members every byte-granular parser has
+pub fn parse_error_parser_state_members() -> std::vec::Vec<MessageStructMember> {
+    vec![MessageStructMember {
+        name: PARSE_ERROR_MEMBER_NAME.to_string(),
+        field_base_type: FieldBaseType::U8,
+        array_length: 0usize,
+    }]
+}
+
+/// Parser state members of fixed-length messages
+pub fn fixed_length_parser_state_members(
+    message: &bpir::representation::Message,
+) -> std::vec::Vec<MessageStructMember> {
+    match message.fixed_length() {
+        Some(_) => vec![MessageStructMember {
+            name: FRAME_LENGTH_MEMBER_NAME.to_string(),
+            field_base_type: FieldBaseType::U32,
+            array_length: 0usize,
+        }],
+        None => vec![],
+    }
+}
+
+/// Name of the parser state member accumulating the named checksum
+pub fn checksum_member_name(checksum_name: &str) -> String {
+    format!("{0}Checksum", checksum_name)
+}
+
+/// Name of the action hook feeding a byte into the named checksum
+pub fn checksum_hook_name(checksum_name: &str) -> String {
+    format!("{0}_checksum", checksum_name)
+}
+
+/// Parser state members accumulating the checksums of a message
+pub fn checksum_parser_state_members(
+    message: &bpir::representation::Message,
+) -> std::vec::Vec<MessageStructMember> {
+    message
+        .fields
+        .iter()
+        .filter_map(|field| field.checksum())
+        .map(|checksum| MessageStructMember {
+            name: checksum_member_name(&checksum.name),
+            field_base_type: FieldBaseType::from_bit_width(checksum.algorithm.width, false),
+            array_length: 0usize,
+        })
+        .collect()
+}
+
+/// Names of the checksums covering each field of a message, in the order of
+/// the fields
+pub fn checksum_coverage(message: &bpir::representation::Message) -> std::vec::Vec<std::vec::Vec<String>> {
+    let mut active = std::vec::Vec::<String>::new();
+    let mut ret = std::vec::Vec::new();
+
+    for field in &message.fields {
+        for attribute in &field.attributes {
+            if let bpir::representation::FieldAttribute::StartChecksum(name) = attribute {
+                active.push(name.clone());
+            }
+        }
+
+        ret.push(active.clone());
+
+        for attribute in &field.attributes {
+            if let bpir::representation::FieldAttribute::StopChecksum(name) = attribute {
+                active.retain(|other| other != name);
+            }
+        }
+    }
+
+    ret
+}
+
+/// Name of the parser state member counting the bytes a length field covers
+pub fn covered_length_member_name(field_name: &str) -> String {
+    format!("{0}Covered", field_name)
+}
+
+/// Parser state members counting the bytes the length fields of a message
+/// cover
+pub fn length_parser_state_members(
+    message: &bpir::representation::Message,
+) -> std::vec::Vec<MessageStructMember> {
+    message
+        .fields
+        .iter()
+        .filter(|field| field.length_of().is_some())
+        .map(|field| MessageStructMember {
+            name: covered_length_member_name(&field.name),
+            field_base_type: FieldBaseType::U32,
+            array_length: 0usize,
+        })
+        .collect()
+}
+
+/// Names of the length fields covering each field of a message, in the order
+/// of the fields
+pub fn length_coverage(message: &bpir::representation::Message) -> std::vec::Vec<std::vec::Vec<String>> {
+    let position = |name: &str| message.fields.iter().position(|field| field.name == name);
+    let mut ret = vec![std::vec::Vec::new(); message.fields.len()];
+
+    for field in &message.fields {
+        let Some(length_of) = field.length_of() else {
+            continue;
+        };
+
+        if let (Some(first), Some(last)) = (position(&length_of.first), position(&length_of.last)) {
+            for covered in ret.iter_mut().take(last + 1).skip(first) {
+                covered.push(field.name.clone());
+            }
+        }
+    }
+
+    ret
+}
+
+/// Index of the field after which a length field gets checked: both the
+/// length field, and the fields it covers have to be complete by then
+pub fn length_check_position(
+    message: &bpir::representation::Message,
+    field: &bpir::representation::Field,
+) -> Option<usize> {
+    let length_of = field.length_of()?;
+    let position = |name: &str| message.fields.iter().position(|f| f.name == name);
+
+    Some(position(&field.name)?.max(position(&length_of.last)?))
+}
+
+/// Stages of length field verification
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthActionKind {
+    /// Zeroes the byte counter, when the parser is initialized
+    Reset,
+
+    /// Counts a covered byte
+    Count,
+
+    /// Compares the length field against the counter
+    Check,
+}
+
+impl LengthActionKind {
+    /// Name of the action hook, unique within a length field
+    pub fn hook_name(&self, field_name: &str) -> String {
+        let suffix = match self {
+            LengthActionKind::Reset => "reset",
+            LengthActionKind::Count => "count",
+            LengthActionKind::Check => "check",
+        };
+
+        format!("{0}_{1}", field_name, suffix)
+    }
+}
+
+/// Body of a length action hook, or a statement of the parser state
+/// initialization
+#[derive(Debug)]
+pub struct LengthAction {
+    pub kind: LengthActionKind,
+    pub message_name: String,
+
+    /// Name of the field holding the length
+    pub field_name: String,
+
+    /// The length computed from the field's value, unless it is the value
+    pub length: Option<MessageExpression>,
+}
+
+/// Stages of checksum verification
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumActionKind {
+    /// Loads the initial value into the register, when the parser is
+    /// initialized
+    Reset,
+
+    /// Feeds a covered byte into the register
+    Update,
+
+    /// Compares the checksum field against the register, once the field is
+    /// complete
+    Check,
+}
+
+/// Body of a checksum action hook, or a statement of the parser state
+/// initialization
+#[derive(Debug)]
+pub struct ChecksumAction {
+    pub kind: ChecksumActionKind,
+    pub message_name: String,
+
+    /// Name of the field holding the checksum
+    pub field_name: String,
+    pub checksum_name: String,
+    pub algorithm: bpir::representation::CrcAlgorithm,
+}
+
+/// Routines advancing CRC registers by one byte, one per algorithm
+#[derive(Debug)]
+pub struct ChecksumRoutines {
+    pub algorithms: std::vec::Vec<bpir::representation::CrcAlgorithm>,
+    pub implementation: bpir::representation::ChecksumImplementation,
+}
+
+/// Rejects the message, if a field's value falls out of its range. Bounds
+/// which the field's width satisfies anyway are omitted.
+#[derive(Debug)]
+pub struct RangeCheck {
+    pub message_name: String,
+    pub field_name: String,
+    pub field_base_type: FieldBaseType,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+
+    /// Byte-granular parsers report errors through the parser state, and
+    /// bit-granular ones through the return value
+    pub layout: MessageLayout,
+}
+
+impl RangeCheck {
+    pub fn from_field(
+        message: &bpir::representation::Message,
+        field: &bpir::representation::Field,
+    ) -> Option<RangeCheck> {
+        let integer = field.integer_value_type()?;
+        let range = field.range()?;
+        let min = Some(range.min).filter(|min| i128::from(*min) > integer.min_value());
+        let max = Some(range.max).filter(|max| i128::from(*max) < integer.max_value());
+
+        if min.is_none() && max.is_none() {
+            return None;
+        }
+
+        Some(RangeCheck {
+            message_name: message.name.clone(),
+            field_name: field.name.clone(),
+            field_base_type: FieldBaseType::from_field(&message.name, field),
+            min,
+            max,
+            layout: message.layout(),
+        })
+    }
+}
+
+/// Whether the field is represented in the message struct. Reserved fields
+/// only are, if requested for debugging
+pub fn has_struct_member(field: &bpir::representation::Field) -> bool {
+    match field.field_type {
+        FieldType::Reserved(ref reserved) => reserved.debug_member,
+        _ => true,
+    }
+}
+
+/// Keeps the bytes of a reserved field for debugging. Bytes are shifted
+/// through the member, so no index has to be tracked
+#[derive(Debug)]
+pub struct ReservedFieldByte {
+    pub message_name: String,
+    pub field_name: String,
+
+    /// Length in bytes
+    pub length: usize,
+}
+
+/// Skips a reserved field of a bit-granular message, or copies its bytes into
+/// the debug member
+#[derive(Debug)]
+pub struct BitstreamReservedField {
+    pub message_name: String,
+    pub field_name: String,
+
+    /// Length in bytes
+    pub length: usize,
+    pub debug_member: bool,
+    pub bit_order: BitOrder,
+}
+
+/// Name of the action hook storing a byte of a fixed-length field
+pub fn field_byte_hook_name(field_name: &str) -> String {
+    format!("{0}_byte", field_name)
+}
+
+/// Shifts a byte of a big-endian integer field into the message struct
+#[derive(Debug)]
+pub struct IntegerFieldByte {
+    pub message_name: String,
+    pub field_name: String,
+    pub field_base_type: FieldBaseType,
+
+    /// Width of the field, which may be narrower than its type (e.g. 24 bits)
+    pub bit_width: usize,
+    pub signed: bool,
+
+    pub container: FieldContainer,
+}
+
+/// Where a field's value is stored within the message struct
+#[derive(Clone, Debug)]
+pub enum FieldContainer {
+    /// Directly in the message struct
+    Message,
+
+    /// In the current repetition of the named group field
+    Group(String),
+
+    /// In the union shared by the fields conditional on the named field
+    Union(String),
+}
+
+impl FieldContainer {
+    pub fn from_field(field: &bpir::representation::Field) -> FieldContainer {
+        match field.conditional_on() {
+            Some(conditional_on) => FieldContainer::Union(conditional_on.field.clone()),
+            None => FieldContainer::Message,
+        }
+    }
+}
+
+/// Name of the message struct member holding the union of the fields
+/// conditional on `field_name`
+pub fn conditional_union_member_name(field_name: &str) -> String {
+    format!("{0}Variant", field_name)
+}
+
+/// Name of the action hook checking, whether a conditional field is present
+pub fn condition_hook_name(field_name: &str) -> String {
+    format!("{0}_when", field_name)
+}
+
+/// Checks whether the field which selects a conditional field's
+/// interpretation has the required value
+#[derive(Debug)]
+pub struct ConditionCheck {
+    pub condition: MessageExpression,
+}
+
+/// An expression over the fields of a message, which knows where the fields
+/// are stored
+#[derive(Clone, Debug)]
+pub struct MessageExpression {
+    pub message_name: String,
+    pub expression: bpir::expression::Expression,
+
+    /// Containers of the fields the expression refers to
+    pub operands: std::vec::Vec<(String, FieldContainer)>,
+}
+
+impl MessageExpression {
+    pub fn new(
+        message: &bpir::representation::Message,
+        expression: &bpir::expression::Expression,
+    ) -> MessageExpression {
+        MessageExpression {
+            message_name: message.name.clone(),
+            expression: expression.clone(),
+            operands: expression
+                .fields()
+                .into_iter()
+                .map(|name| {
+                    let container = message
+                        .fields
+                        .iter()
+                        .find(|field| field.name == name)
+                        .map(FieldContainer::from_field)
+                        .unwrap_or(FieldContainer::Message);
+
+                    (name.to_string(), container)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Rejects the message, unless an expression of a "check" attribute holds
+#[derive(Debug)]
+pub struct ExpressionCheck {
+    pub check: MessageExpression,
+
+    /// Byte-granular parsers report errors through the parser state, and
+    /// bit-granular ones through the return value
+    pub layout: MessageLayout,
+}
+
+/// 4B/5B code groups indexed by the nibble they encode
+pub const FOUR_B_FIVE_B_CODE_GROUPS: [u8; 16] = [
+    0b11110, 0b01001, 0b10100, 0b10101, 0b01010, 0b01011, 0b01110, 0b01111, 0b10010, 0b10011,
+    0b10110, 0b10111, 0b11010, 0b11011, 0b11100, 0b11101,
+];
+
+/// Parser state members which the 4B/5B decoder uses for storing code group
+/// bits which have not been decoded yet
+pub fn four_b_five_b_parser_state_members() -> std::vec::Vec<MessageStructMember> {
+    vec![
+        MessageStructMember {
+            name: "symbolAccumulator".to_string(),
+            field_base_type: FieldBaseType::U32,
+            array_length: 0usize,
+        },
+        MessageStructMember {
+            name: "symbolAccumulatorBits".to_string(),
+            field_base_type: FieldBaseType::U8,
+            array_length: 0usize,
+        },
+    ]
+}
+
+/// Parser state members which TLV machines use for storing partially parsed
+/// records. The stack enables calling into a machine parsing a record's value.
+pub fn tlv_parser_state_members() -> std::vec::Vec<MessageStructMember> {
+    vec![
+        MessageStructMember {
+            name: "tlvTag".to_string(),
+            field_base_type: FieldBaseType::U32,
+            array_length: 0usize,
+        },
+        MessageStructMember {
+            name: "tlvLength".to_string(),
+            field_base_type: FieldBaseType::U32,
+            array_length: 0usize,
+        },
+        MessageStructMember {
+            name: "tlvValueIndex".to_string(),
+            field_base_type: FieldBaseType::U32,
+            array_length: 0usize,
+        },
+        MessageStructMember {
+            name: "stack".to_string(),
+            field_base_type: FieldBaseType::I32,
+            array_length: 1usize,
+        },
+        MessageStructMember {
+            name: "top".to_string(),
+            field_base_type: FieldBaseType::I32,
+            array_length: 0usize,
+        },
+    ]
+}
+
+/// A kind of record a TLV field may contain
+#[derive(Clone, Debug)]
+pub struct TlvRecordVariant {
+    pub tag: u64,
+    pub name: String,
+    pub field_base_type: FieldBaseType,
+
+    /// If 0, the value is an integer, and is accumulated byte by byte
+    /// (big-endian). Otherwise, the value is a byte array
+    pub array_length: usize,
+}
+
+impl TlvRecordVariant {
+    pub fn from_tlv(tlv: &bpir::representation::TlvFieldType) -> std::vec::Vec<TlvRecordVariant> {
+        tlv.records
+            .iter()
+            .map(|record| TlvRecordVariant {
+                tag: record.tag,
+                name: record.field.name.clone(),
+                field_base_type: FieldBaseType::from_field("", &record.field),
+                array_length: match record.field.field_type {
+                    FieldType::Integer(_) => 0usize,
+                    _ => record.field.max_length().unwrap_or(1usize),
+                },
+            })
+            .collect()
+    }
+}
+
+/// A tagged union type holding a single record of a TLV field
+#[derive(Debug)]
+pub struct TlvRecordStruct {
+    pub message_name: String,
+    pub field_name: String,
+    pub variants: std::vec::Vec<TlvRecordVariant>,
+}
+
+/// Name of the struct type holding a single repetition of a group field.
+/// Groups made from the same definition share the type
+pub fn group_struct_name(
+    message_name: &str,
+    field_name: &str,
+    group: &bpir::representation::GroupFieldType,
+) -> String {
+    match group.definition {
+        Some(ref definition) => format!("{0}Group", capitalize(definition)),
+        None => format!("{0}{1}Group", message_name, capitalize(field_name)),
+    }
+}
+
+/// Name of the parser state member holding the index of the group field's
+/// current repetition
+pub fn group_index_member_name(field_name: &str) -> String {
+    format!("{0}Index", field_name)
+}
+
+/// Name of the action hook storing a byte of a group member
+pub fn group_member_byte_hook_name(field_name: &str, member_name: &str) -> String {
+    format!("{0}_{1}_byte", field_name, member_name)
+}
+
+/// Parser state members which group fields use for tracking the current
+/// repetition
+pub fn group_parser_state_members(
+    message: &bpir::representation::Message,
+) -> std::vec::Vec<MessageStructMember> {
+    message
+        .fields
+        .iter()
+        .filter(|field| matches!(field.field_type, FieldType::Group(_)))
+        .map(|field| MessageStructMember {
+            name: group_index_member_name(&field.name),
+            field_base_type: FieldBaseType::U32,
+            array_length: 0usize,
+        })
+        .collect()
+}
+
+/// Struct members of a single repetition of a group field
+pub fn group_struct_members(
+    message_name: &str,
+    group: &bpir::representation::GroupFieldType,
+) -> std::vec::Vec<MessageStructMember> {
+    group
+        .fields
+        .iter()
+        .filter(|member| has_struct_member(member))
+        .map(|member| MessageStructMember {
+            name: member.name.clone(),
+            field_base_type: FieldBaseType::from_field(message_name, member),
+            array_length: 0usize,
+        })
+        .collect()
+}
+
+/// A struct type holding a single repetition of a group field
+#[derive(Debug)]
+pub struct GroupStruct {
+    pub name: String,
+    pub members: std::vec::Vec<MessageStructMember>,
+}
+
+/// Struct types of the group fields made from a definition, one per
+/// definition. Members are taken from the first group referring to the
+/// definition, as a narrowed protocol may have dropped some
+pub fn group_definition_structs(protocol: &bpir::representation::Protocol) -> std::vec::Vec<GroupStruct> {
+    let mut ret: std::vec::Vec<GroupStruct> = std::vec::Vec::new();
+
+    for message in &protocol.messages {
+        for field in &message.fields {
+            if let FieldType::Group(ref group) = field.field_type {
+                let name = group_struct_name(&message.name, &field.name, group);
+
+                if group.definition.is_some() && !ret.iter().any(|group_struct| group_struct.name == name) {
+                    ret.push(GroupStruct {
+                        name,
+                        members: group_struct_members(&message.name, group),
+                    });
+                }
+            }
+        }
+    }
+
+    ret
+}
+
+/// A member of a group, as seen by the state machine
+#[derive(Debug)]
+pub struct GroupMachineMember {
+    /// Length in bytes
+    pub length: usize,
+
+    /// Action hook invoked on every byte of the member, if any
+    pub byte_action: Option<String>,
+}
+
+/// A loop over a fixed number of repetitions of a group field
+#[derive(Debug)]
+pub struct GroupMachineField {
+    pub name: String,
+    pub count: usize,
+    pub members: std::vec::Vec<GroupMachineMember>,
+}
+
+/// Stages of group parsing, each one gets its own action hook
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupActionKind {
+    /// Points the index to the first repetition
+    Reset,
+
+    /// Advances the index once a repetition is complete
+    Next,
+}
+
+impl GroupActionKind {
+    pub fn all() -> [GroupActionKind; 2] {
+        [GroupActionKind::Reset, GroupActionKind::Next]
+    }
+
+    /// Name of the action hook, unique within a group field
+    pub fn hook_name(&self, field_name: &str) -> String {
+        let suffix = match self {
+            GroupActionKind::Reset => "reset",
+            GroupActionKind::Next => "next",
+        };
+
+        format!("{0}_{1}", field_name, suffix)
+    }
+}
+
+/// Body of a group action hook
+#[derive(Debug)]
+pub struct GroupAction {
+    pub kind: GroupActionKind,
+    pub field_name: String,
+}
+
+/// Name of the parser state member holding the bit offset of the next 7-bit
+/// group of the varint being parsed. Varints are parsed one at a time, so a
+/// message needs a single one
+pub const VARINT_SHIFT_MEMBER_NAME: &str = "varintShift";
+
+/// Parser state members of messages with varint fields
+pub fn varint_parser_state_members(
+    message: &bpir::representation::Message,
+) -> std::vec::Vec<MessageStructMember> {
+    match message
+        .fields
+        .iter()
+        .any(|field| matches!(field.field_type, FieldType::Varint(_)))
+    {
+        true => vec![MessageStructMember {
+            name: VARINT_SHIFT_MEMBER_NAME.to_string(),
+            field_base_type: FieldBaseType::U8,
+            array_length: 0usize,
+        }],
+        false => vec![],
+    }
+}
+
+/// Encodes a value as a LEB128 varint, as the field's parser expects it
+pub fn encode_varint(value: i128, varint: &bpir::representation::VarintFieldType) -> std::vec::Vec<u8> {
+    let mask = u64::MAX >> (64 - varint.bit_width.clamp(1, 64));
+    let mut raw = match varint.signed && varint.zigzag {
+        true => ((value << 1) ^ (value >> 127)) as u64 & mask,
+        false => value as u64 & mask,
+    };
+    let mut ret = std::vec::Vec::new();
+
+    loop {
+        let byte = (raw & 0x7F) as u8;
+        raw >>= 7;
+
+        if raw == 0 {
+            ret.push(byte);
+
+            return ret;
+        }
+
+        ret.push(byte | 0x80);
+    }
+}
+
+/// A varint: bytes with the MSB set, followed by the byte with the MSB clear
+#[derive(Debug)]
+pub struct VarintMachineField {
+    pub name: String,
+
+    /// Max length of the encoding, in bytes
+    pub max_length: usize,
+
+    /// Condition hook which has to hold for the field to be present, if any
+    pub condition: Option<String>,
+}
+
+/// Stages of varint parsing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VarintActionKind {
+    /// Clears the value before the first byte
+    Reset,
+
+    /// Accumulates the 7 bits of a byte
+    Byte,
+
+    /// Restores a signed value, once the last byte is in
+    Decode,
+}
+
+impl VarintActionKind {
+    /// Name of the action hook. Decoding shares the field's hook, so it
+    /// precedes the range check
+    pub fn hook_name(&self, field_name: &str) -> String {
+        match self {
+            VarintActionKind::Reset => format!("{0}_reset", field_name),
+            VarintActionKind::Byte => field_byte_hook_name(field_name),
+            VarintActionKind::Decode => field_name.to_string(),
+        }
+    }
+}
+
+/// Body of a varint action hook
+#[derive(Debug)]
+pub struct VarintAction {
+    pub kind: VarintActionKind,
+    pub message_name: String,
+    pub field_name: String,
+    pub field_base_type: FieldBaseType,
+    pub varint: bpir::representation::VarintFieldType,
+    pub container: FieldContainer,
+}
+
+/// Name of the message struct member holding the number of bytes of a regex
+/// field's member. The parser appends the field's bytes at it
+pub fn regex_length_member_name(field_name: &str) -> String {
+    format!("{0}Length", field_name)
+}
+
+/// Elements of the member array of a regex or TLV field
+pub fn array_capacity(field: &bpir::representation::Field) -> usize {
+    field.max_length().unwrap_or(1usize)
+}
+
+/// Type of the members of zero-copy fields, which hold the position of the
+/// field's first byte in the parser's input, and the number of its bytes
+pub const SLICE_STRUCT_NAME: &str = "RobustoSlice";
+
+/// Members of the slice struct
+pub fn slice_struct_members() -> std::vec::Vec<MessageStructMember> {
+    ["offset", "length"]
+        .into_iter()
+        .map(|name| MessageStructMember {
+            name: name.to_string(),
+            field_base_type: FieldBaseType::U32,
+            array_length: 0usize,
+        })
+        .collect()
+}
+
+/// Name of the parser state member counting the input bytes the parser has
+/// taken in previous invocations, which zero-copy offsets are relative to
+pub const INPUT_POSITION_MEMBER_NAME: &str = "inputPosition";
+
+/// Parser state members of byte-granular parsers, if the message's regex
+/// fields refer into the input
+pub fn zero_copy_parser_state_members(
+    protocol: &bpir::representation::Protocol,
+    message: &bpir::representation::Message,
+) -> std::vec::Vec<MessageStructMember> {
+    match protocol.zero_copy(message) {
+        true => vec![MessageStructMember {
+            name: INPUT_POSITION_MEMBER_NAME.to_string(),
+            field_base_type: FieldBaseType::U32,
+            array_length: 0usize,
+        }],
+        false => vec![],
+    }
+}
+
+/// Stages of copying a regex field into its member
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegexActionKind {
+    /// Rewinds the write index before the first byte
+    Reset,
+
+    /// Stores a byte, or applies the field's overflow policy, if the member
+    /// is full
+    Byte,
+}
+
+impl RegexActionKind {
+    pub fn hook_name(&self, field_name: &str) -> String {
+        match self {
+            RegexActionKind::Reset => format!("{0}_reset", field_name),
+            RegexActionKind::Byte => field_byte_hook_name(field_name),
+        }
+    }
+}
+
+/// Body of a regex field's action hook
+#[derive(Debug)]
+pub struct RegexAction {
+    pub kind: RegexActionKind,
+    pub message_name: String,
+    pub field_name: String,
+
+    /// Elements of the member array
+    pub capacity: usize,
+    pub overflow: OverflowPolicy,
+    pub container: FieldContainer,
+
+    /// The field is kept as an offset and a length into the input
+    pub zero_copy: bool,
+}
+
+/// A loop of TLV records. Each record's value is parsed by a separate machine
+/// which gets called once the record's length is known
+#[derive(Debug)]
+pub struct TlvMachineField {
+    pub name: String,
+
+    /// Width of the "type" part, in bytes
+    pub tag_length: usize,
+
+    /// Width of the "length" part, in bytes
+    pub length_length: usize,
+}
+
+/// Stages of TLV record parsing, each one gets its own action hook
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TlvActionKind {
+    /// Clears the record counter before the first record
+    Reset,
+
+    /// Starts a new record, clears the tag and length accumulators
+    RecordStart,
+    TagByte,
+    LengthByte,
+
+    /// Stores the record's tag, and calls into the value machine
+    ValueStart,
+    ValueByte,
+}
+
+impl TlvActionKind {
+    pub fn all() -> [TlvActionKind; 6] {
+        [
+            TlvActionKind::Reset,
+            TlvActionKind::RecordStart,
+            TlvActionKind::TagByte,
+            TlvActionKind::LengthByte,
+            TlvActionKind::ValueStart,
+            TlvActionKind::ValueByte,
+        ]
+    }
+
+    /// Name of the action hook, unique within a TLV field
+    pub fn hook_name(&self, field_name: &str) -> String {
+        let suffix = match self {
+            TlvActionKind::Reset => "reset",
+            TlvActionKind::RecordStart => "record",
+            TlvActionKind::TagByte => "tag",
+            TlvActionKind::LengthByte => "length",
+            TlvActionKind::ValueStart => "valueStart",
+            TlvActionKind::ValueByte => "value",
+        };
+
+        format!("{0}_{1}", field_name, suffix)
+    }
+}
+
+/// Body of a TLV action hook
+#[derive(Debug)]
+pub struct TlvAction {
+    pub kind: TlvActionKind,
+    pub message_name: String,
+    pub field_name: String,
+
+    /// Max number of records the message struct can store
+    pub max_records: usize,
+    pub variants: std::vec::Vec<TlvRecordVariant>,
+}
+
+/// Converts a scaled integer field into its physical value
+#[derive(Debug)]
+pub struct ScaledValueAccessor {
+    pub message_name: String,
+    pub symbol_naming: SymbolNaming,
+    pub field_name: String,
+    pub factor: f64,
+    pub offset: f64,
+
+    /// Unit of the physical value, if known
+    pub unit: Option<String>,
+}
+
+impl ScaledValueAccessor {
+    pub fn from_message(
+        symbol_naming: &SymbolNaming,
+        message: &bpir::representation::Message,
+    ) -> std::vec::Vec<ScaledValueAccessor> {
+        message
+            .fields
+            .iter()
+            .filter_map(|field| {
+                field.scale().map(|scale| ScaledValueAccessor {
+                    message_name: message.name.clone(),
+                    symbol_naming: symbol_naming.clone(),
+                    field_name: field.name.clone(),
+                    factor: scale.factor,
+                    offset: scale.offset,
+                    unit: field.unit().map(str::to_string),
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagsAccessorKind {
+    /// Whether every given flag is set
+    Test,
+
+    /// Sets or clears the given flags
+    Set,
+}
+
+/// Tests or sets the bits of a flags field
+#[derive(Debug)]
+pub struct FlagsAccessor {
+    pub kind: FlagsAccessorKind,
+    pub message_name: String,
+    pub symbol_naming: SymbolNaming,
+    pub field_name: String,
+    pub field_base_type: FieldBaseType,
+}
+
+impl FlagsAccessor {
+    pub fn from_message(
+        symbol_naming: &SymbolNaming,
+        message: &bpir::representation::Message,
+    ) -> std::vec::Vec<FlagsAccessor> {
+        message
+            .fields
+            .iter()
+            .filter(|field| matches!(field.field_type, FieldType::Flags(_)))
+            .flat_map(|field| {
+                [FlagsAccessorKind::Test, FlagsAccessorKind::Set].map(|kind| FlagsAccessor {
+                    kind,
+                    message_name: message.name.clone(),
+                    symbol_naming: symbol_naming.clone(),
+                    field_name: field.name.clone(),
+                    field_base_type: FieldBaseType::from_field(&message.name, field),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Prints the fields of a message struct through a `printf`-like function
+#[derive(Debug)]
+pub struct DumpFunction {
+    pub message: bpir::representation::Message,
+    pub symbol_naming: SymbolNaming,
+
+    /// Regex fields refer into the input, see `ProtocolAttribute::ZeroCopy`
+    pub zero_copy: bool,
+}
+
+/// Initializes a message struct, and compares two of them
+#[derive(Debug)]
+pub struct StructFunctions {
+    pub message: bpir::representation::Message,
+    pub symbol_naming: SymbolNaming,
+
+    /// Regex fields refer into the input, see `ProtocolAttribute::ZeroCopy`
+    pub zero_copy: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimestampAccessorKind {
+    /// Converts ticks into milliseconds since the Unix epoch
+    Get,
+
+    /// Converts milliseconds since the Unix epoch into ticks
+    Set,
+}
+
+/// Converts a timestamp field from and to milliseconds since the Unix epoch
+#[derive(Debug)]
+pub struct TimestampAccessor {
+    pub kind: TimestampAccessorKind,
+    pub message_name: String,
+    pub symbol_naming: SymbolNaming,
+    pub field_name: String,
+    pub field_base_type: FieldBaseType,
+    pub epoch_ms: i64,
+    pub tick_us: u64,
+}
+
+impl TimestampAccessor {
+    pub fn from_message(
+        symbol_naming: &SymbolNaming,
+        message: &bpir::representation::Message,
+    ) -> std::vec::Vec<TimestampAccessor> {
+        message
+            .fields
+            .iter()
+            .filter_map(|field| match field.field_type {
+                FieldType::Timestamp(ref timestamp) => Some((field, timestamp)),
+                _ => None,
+            })
+            .flat_map(|(field, timestamp)| {
+                [TimestampAccessorKind::Get, TimestampAccessorKind::Set].map(|kind| TimestampAccessor {
+                    kind,
+                    message_name: message.name.clone(),
+                    symbol_naming: symbol_naming.clone(),
+                    field_name: field.name.clone(),
+                    field_base_type: FieldBaseType::from_field(&message.name, field),
+                    epoch_ms: timestamp.epoch_ms,
+                    tick_us: timestamp.tick_us,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Parsing function for a bit-granular message. Ragel is not used, since it
+/// operates on bytes. Instead, the fields are extracted by a bit reader one by
+/// one.
+#[derive(Debug)]
+pub struct BitstreamParsingFunction {
+    pub message_name: String,
+    pub symbol_naming: SymbolNaming,
+
+    /// Sum of widths of the message's fields
+    pub bit_length: usize,
+
+    /// Exact frame length, in bytes, if the message has a fixed length
+    pub fixed_length: Option<usize>,
+}
+
+/// Extracts a field from a bit stream
+#[derive(Debug)]
+pub struct BitstreamFieldRead {
+    pub message_name: String,
+    pub field_name: String,
+    pub field_base_type: FieldBaseType,
+    pub bit_width: usize,
+    pub signed: bool,
+    pub bit_order: BitOrder,
+}
+
+/// Bit reader routines of the bit orders the parsed messages employ
+#[derive(Debug)]
+pub struct BitReader {
+    pub bit_orders: std::vec::Vec<BitOrder>,
+}
+
+/// Value an integer field of a canned message instance is expected to be
+/// decoded into
+#[derive(Clone, Debug)]
+pub struct SelfTestFieldValue {
+    pub field_name: String,
+    pub field_base_type: FieldBaseType,
+
+    /// Wide enough for both signed and unsigned 64-bit fields
+    pub value: i128,
+}
+
+/// Values the integer fields of a canned instance take
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CannedValues {
+    /// A pattern depending on the field's position, so swapped fields get
+    /// detected
+    Distinct,
+
+    /// Byte-aligned multi-byte fields carry the bytes 0x01, 0x02, ... on the
+    /// wire, which decode into different values on hosts of either byte
+    /// order, unless the value is assembled in the wire's order. The rest
+    /// take distinct values
+    AscendingBytes,
+}
+
+/// A canned instance of a message, serialized at generation time
+#[derive(Clone, Debug)]
+pub struct SelfTestMessage {
+    pub message_name: String,
+    pub layout: MessageLayout,
+    pub canned_values: CannedValues,
+
+    /// Encoded message, as it appears on the wire
+    pub frame: std::vec::Vec<u8>,
+    pub field_values: std::vec::Vec<SelfTestFieldValue>,
+
+    /// Bytes the longest frame of the message takes, if it has a serializer,
+    /// which should compose the parsed message back into the frame
+    pub serializer_capacity: Option<usize>,
+}
+
+impl SelfTestMessage {
+    /// Makes a canned value for an integer field, see `CannedValues`. The
+    /// sign bit is only set, if the field's range requires so.
+    fn canned_integer_value(
+        canned_values: CannedValues,
+        field_index: usize,
+        field: &bpir::representation::Field,
+        integer: &bpir::representation::IntegerFieldType,
+    ) -> i128 {
+        if canned_values == CannedValues::AscendingBytes && SelfTestMessage::is_multi_byte(integer) {
+            let value = (1..=integer.bit_width / 8).fold(0i128, |value, byte| (value << 8) | byte as i128);
+
+            return match field.range() {
+                Some(range) => value.clamp(i128::from(range.min), i128::from(range.max)),
+                None => value,
+            };
+        }
+
+        let value_bits = if integer.signed {
+            integer.bit_width - 1
+        } else {
+            integer.bit_width
+        };
+        let mask = if value_bits >= 64 {
+            u64::MAX
+        } else {
+            (1u64 << value_bits) - 1
+        };
+
+        let value = (0x0123_4567_89AB_CDEFu64.rotate_right(8 * (field_index % 8) as u32) & mask) as i128;
+
+        match field.range() {
+            Some(range) => value.clamp(i128::from(range.min), i128::from(range.max)),
+            None => value,
+        }
+    }
+
+    /// Whether the field spans several whole bytes, so its value depends on
+    /// their order
+    fn is_multi_byte(integer: &bpir::representation::IntegerFieldType) -> bool {
+        integer.bit_width > 8 && integer.bit_width.is_multiple_of(8)
+    }
+
+    /// Whether a field of the message carries a canned value spanning several
+    /// whole bytes. Varints are encoded least significant group first
+    /// regardless, and are not counted
+    pub fn has_multi_byte_values(message: &bpir::representation::Message) -> bool {
+        message
+            .fields
+            .iter()
+            .flat_map(|field| match field.field_type {
+                FieldType::Group(ref group) => group.fields.iter().collect::<std::vec::Vec<_>>(),
+                _ => vec![field],
+            })
+            .filter(|field| !matches!(field.field_type, FieldType::Varint(_)))
+            .filter(|field| field.checksum().is_none() && field.length_of().is_none())
+            .filter_map(|field| field.integer_value_type())
+            .any(|integer| SelfTestMessage::is_multi_byte(&integer))
+    }
+
+    /// Serializes a canned instance of the message. Returns `None`, if the
+    /// instance cannot be encoded at generation time (e.g. a user-provided
+    /// symbol decoding hook is used), or the message is empty.
+    pub fn from_message(
+        message: &bpir::representation::Message,
+        symbol_decoding: Option<&SymbolDecoding>,
+        byte_stuffing: Option<&bpir::representation::ByteStuffingAttribute>,
+        canned_values: CannedValues,
+    ) -> Option<SelfTestMessage> {
+        let mut frame = BitWriter::with_bit_order(match message.layout() {
+            MessageLayout::Bits => message.bit_order(),
+            MessageLayout::Bytes => BitOrder::MsbFirst,
+        });
+        let mut field_values = std::vec::Vec::new();
+
+        for byte in message.sync_sequence().unwrap_or_default() {
+            frame.push(u64::from(*byte), 8usize);
+        }
+
+        // Selectors take the value of their first alternative. Every other
+        // region depending on them must have a matching alternative too
+        let mut selector_values = std::collections::HashMap::new();
+
+        for region in message.field_regions() {
+            if let Some(conditional_on) = region[0].conditional_on() {
+                let value = *selector_values
+                    .entry(conditional_on.field.as_str())
+                    .or_insert(conditional_on.value);
+
+                if !region.iter().any(|field| field.conditional_on().is_some_and(|c| c.value == value)) {
+                    return None;
+                }
+            }
+        }
+
+        // Byte offsets the checksums' coverage starts and stops at
+        let mut checksum_starts = std::collections::HashMap::new();
+        let mut checksum_stops = std::collections::HashMap::new();
+
+        // Byte offsets the fields start and stop at. Length fields preceding
+        // the fields they cover are patched, once those are encoded
+        let mut field_spans = std::collections::HashMap::new();
+        let mut pending_lengths = std::vec::Vec::new();
+        let mut is_encodable = true;
+
+        // Values of the integer fields present in the frame, by name, for
+        // evaluating the checks
+        let mut field_value_indices = std::collections::HashMap::new();
+        let mut present_fields = std::vec::Vec::new();
+        let covered_length = |field_spans: &std::collections::HashMap<&str, (usize, usize)>,
+                              length_of: &bpir::representation::LengthOfFieldAttribute| {
+            Some(field_spans.get(length_of.last.as_str())?.1 - field_spans.get(length_of.first.as_str())?.0)
+        };
+
+        for (field_index, field) in message.fields.iter().enumerate() {
+            let container = FieldContainer::from_field(field);
+            let field_start = frame.bytes.len();
+
+            if let Some(previous) = field_index.checked_sub(1).map(|i| &message.fields[i]) {
+                for attribute in &previous.attributes {
+                    if let bpir::representation::FieldAttribute::StopChecksum(name) = attribute {
+                        checksum_stops.insert(name.as_str(), frame.bytes.len());
+                    }
+                }
+            }
+
+            for attribute in &field.attributes {
+                if let bpir::representation::FieldAttribute::StartChecksum(name) = attribute {
+                    checksum_starts.insert(name.as_str(), frame.bytes.len());
+                }
+            }
+
+            if let Some(conditional_on) = field.conditional_on() {
+                if selector_values.get(conditional_on.field.as_str()) != Some(&conditional_on.value) {
+                    continue;
+                }
+            }
+
+            present_fields.push(field);
+
+            match field.field_type {
+                FieldType::Regex(ref regex) => {
+                    for byte in regex.pattern().sample() {
+                        frame.push(byte as u64, 8usize);
+                    }
+                }
+                FieldType::Integer(ref integer) => {
+                    let value = match (selector_values.get(field.name.as_str()), field.checksum(), field.length_of()) {
+                        (Some(value), _, _) => i128::from(*value),
+                        (None, Some(checksum), _) => {
+                            let stop = checksum_stops
+                                .get(checksum.name.as_str())
+                                .copied()
+                                .unwrap_or(frame.bytes.len());
+                            let start = checksum_starts.get(checksum.name.as_str()).copied().unwrap_or(stop);
+
+                            i128::from(checksum.algorithm.compute(
+                                frame.bytes.get(start..stop).unwrap_or_default(),
+                            ))
+                        }
+                        (None, None, Some(length_of)) => match covered_length(&field_spans, length_of) {
+                            Some(length) => length_field_value(field, length_of, integer, length)?,
+                            None => {
+                                pending_lengths.push((field, length_of, integer, frame.bytes.len(), field_values.len()));
+
+                                0i128
+                            }
+                        },
+                        (None, None, None) => {
+                            SelfTestMessage::canned_integer_value(canned_values, field_index, field, integer)
+                        }
+                    };
+                    // Two's complement is truncated to the field's width
+                    frame.push(value as u64, integer.bit_width);
+                    field_value_indices.insert(field.name.as_str(), field_values.len());
+                    field_values.push(SelfTestFieldValue {
+                        field_name: match container {
+                            FieldContainer::Union(ref selector) => format!(
+                                "{0}.{1}",
+                                conditional_union_member_name(selector),
+                                field.name
+                            ),
+                            _ => field.name.clone(),
+                        },
+                        field_base_type: FieldBaseType::from_field(&message.name, field),
+                        value,
+                    });
+                }
+                // Flags and timestamps are never conditional
+                FieldType::Flags(_) | FieldType::Timestamp(_) => {
+                    let integer = field.integer_value_type().unwrap();
+                    let value = match selector_values.get(field.name.as_str()) {
+                        Some(value) => i128::from(*value),
+                        None => SelfTestMessage::canned_integer_value(canned_values, field_index, field, &integer),
+                    };
+                    frame.push(value as u64, integer.bit_width);
+                    field_value_indices.insert(field.name.as_str(), field_values.len());
+                    field_values.push(SelfTestFieldValue {
+                        field_name: field.name.clone(),
+                        field_base_type: FieldBaseType::from_field(&message.name, field),
+                        value,
+                    });
+                }
+                FieldType::Varint(ref varint) => {
+                    let value = match selector_values.get(field.name.as_str()) {
+                        Some(value) => i128::from(*value),
+                        None => SelfTestMessage::canned_integer_value(
+                            CannedValues::Distinct,
+                            field_index,
+                            field,
+                            &varint.decoded(),
+                        ),
+                    };
+
+                    for byte in encode_varint(value, varint) {
+                        frame.push(byte as u64, 8usize);
+                    }
+
+                    field_value_indices.insert(field.name.as_str(), field_values.len());
+                    field_values.push(SelfTestFieldValue {
+                        field_name: match container {
+                            FieldContainer::Union(ref selector) => format!(
+                                "{0}.{1}",
+                                conditional_union_member_name(selector),
+                                field.name
+                            ),
+                            _ => field.name.clone(),
+                        },
+                        field_base_type: FieldBaseType::from_field(&message.name, field),
+                        value,
+                    });
+                }
+                // The canned instance has no records
+                FieldType::Tlv(_) => {}
+                FieldType::Group(ref group) => {
+                    for repetition in 0..group.count {
+                        for (member_index, member) in group.fields.iter().enumerate() {
+                            match member.field_type {
+                                FieldType::Integer(ref integer) => {
+                                    let value = SelfTestMessage::canned_integer_value(
+                                        canned_values,
+                                        field_index + repetition + member_index,
+                                        member,
+                                        integer,
+                                    );
+                                    frame.push(value as u64, integer.bit_width);
+                                    field_values.push(SelfTestFieldValue {
+                                        field_name: format!(
+                                            "{0}[{1}].{2}",
+                                            field.name, repetition, member.name
+                                        ),
+                                        field_base_type: FieldBaseType::from_field(
+                                            &message.name,
+                                            member,
+                                        ),
+                                        value,
+                                    });
+                                }
+                                _ => frame.push(0u64, member.bit_width().unwrap_or(0usize)),
+                            }
+                        }
+                    }
+                }
+                FieldType::Reserved(ref reserved) => {
+                    for _ in 0..reserved.length {
+                        frame.push(0u64, 8usize);
+                    }
+                }
+            }
+
+            field_spans.insert(field.name.as_str(), (field_start, frame.bytes.len()));
+            pending_lengths.retain(|&(field, length_of, integer, offset, value_index)| {
+                let Some(length) = covered_length(&field_spans, length_of) else {
+                    return true;
+                };
+                let Some(length) = length_field_value(field, length_of, integer, length) else {
+                    is_encodable = false;
+
+                    return false;
+                };
+                let byte_count = integer.bit_width / 8;
+
+                // Length fields are byte-aligned, and big-endian
+                for i in 0..byte_count {
+                    frame.bytes[offset + i] = (length >> (8 * (byte_count - 1 - i))) as u8;
+                }
+
+                field_values[value_index].value = length;
+
+                false
+            });
+        }
+
+        // Canned values may violate the checks
+        let value_of = |name: &str| {
+            field_value_indices
+                .get(name)
+                .map(|&index| field_values[index].value as i64)
+        };
+
+        if !is_encodable
+            || present_fields
+                .iter()
+                .flat_map(|field| field.checks())
+                .any(|check| check.evaluate(&value_of).unwrap_or(0) == 0)
+        {
+            return None;
+        }
+
+        let frame = encode_frame(message, symbol_decoding, byte_stuffing, frame.into_bytes())?;
+
+        if frame.is_empty() {
+            return None;
+        }
+
+        Some(SelfTestMessage {
+            message_name: message.name.clone(),
+            layout: message.layout(),
+            canned_values,
+            frame,
+            field_values,
+            serializer_capacity: None,
+        })
+    }
+}
+
+/// Value a length field takes for the given covered length. A length
+/// expression is inverted by trying values in turn, up to 16 bits wide.
+/// `None`, if no value matches
+fn length_field_value(
+    field: &bpir::representation::Field,
+    length_of: &bpir::representation::LengthOfFieldAttribute,
+    integer: &bpir::representation::IntegerFieldType,
+    covered_length: usize,
+) -> Option<i128> {
+    let Some(ref length) = length_of.length else {
+        return Some(covered_length as i128);
+    };
+
+    (0..=integer.max_value().min(0xFFFF)).find(|value| {
+        length.evaluate(&|name| (name == field.name).then_some(*value as i64)) == Some(covered_length as i64)
+    })
+}
+
+/// Encodes a frame of the message for the wire. Returns `None`, if the
+/// encoding is not known at generation time
+fn encode_frame(
+    message: &bpir::representation::Message,
+    symbol_decoding: Option<&SymbolDecoding>,
+    byte_stuffing: Option<&bpir::representation::ByteStuffingAttribute>,
+    mut frame: std::vec::Vec<u8>,
+) -> Option<std::vec::Vec<u8>> {
+    // Bit-granular messages are not subject to symbol decoding
+    if let (Some(symbol_decoding), MessageLayout::Bytes) = (symbol_decoding, message.layout()) {
+        frame = encode_symbols(symbol_decoding, &frame)?;
+    }
+
+    if let (Some(byte_stuffing), MessageLayout::Bytes) = (byte_stuffing, message.layout()) {
+        frame = stuff_bytes(byte_stuffing, &frame);
+    }
+
+    Some(frame)
+}
+
+/// Packs values the way the bit reader expects them, MSB-first, unless told
+/// otherwise
+#[derive(Default)]
+struct BitWriter {
+    bytes: std::vec::Vec<u8>,
+    bit_length: usize,
+    lsb_first: bool,
+}
+
+impl BitWriter {
+    fn with_bit_order(bit_order: BitOrder) -> BitWriter {
+        BitWriter {
+            lsb_first: bit_order == BitOrder::LsbFirst,
+            ..BitWriter::default()
+        }
+    }
+
+    fn push(&mut self, value: u64, bit_width: usize) {
+        for i in 0..bit_width {
+            if self.bit_length.is_multiple_of(8) {
+                self.bytes.push(0u8);
+            }
+
+            let (bit, position) = match self.lsb_first {
+                true => ((value >> i) & 1, self.bit_length % 8),
+                false => ((value >> (bit_width - 1 - i)) & 1, 7 - self.bit_length % 8),
+            };
+            *self.bytes.last_mut().unwrap() |= (bit as u8) << position;
+            self.bit_length += 1;
+        }
+    }
+
+    fn into_bytes(self) -> std::vec::Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Applies the inverse of the symbol decoding. Returns `None`, if the decoding
+/// is not known at generation time
+fn encode_symbols(symbol_decoding: &SymbolDecoding, frame: &[u8]) -> Option<std::vec::Vec<u8>> {
+    // Bytewise transforms are involutions
+    match symbol_decoding {
+        SymbolDecoding::NibbleSwap => Some(frame.iter().map(|byte| byte.rotate_left(4)).collect()),
+        SymbolDecoding::BitReversal => Some(frame.iter().map(|byte| byte.reverse_bits()).collect()),
+        SymbolDecoding::Xor(mask) => Some(frame.iter().map(|byte| byte ^ mask).collect()),
+        SymbolDecoding::FourBFiveB => {
+            let mut writer = BitWriter::default();
+
+            for byte in frame {
+                writer.push(FOUR_B_FIVE_B_CODE_GROUPS[(byte >> 4) as usize] as u64, 5usize);
+                writer.push(FOUR_B_FIVE_B_CODE_GROUPS[(byte & 0xF) as usize] as u64, 5usize);
+            }
+
+            Some(writer.into_bytes())
+        }
+        SymbolDecoding::UserHook(_) => None,
+    }
+}
+
+/// Replaces escaped bytes with escape sequences, the inverse of what the
+/// parsers do
+fn stuff_bytes(
+    byte_stuffing: &bpir::representation::ByteStuffingAttribute,
+    frame: &[u8],
+) -> std::vec::Vec<u8> {
+    let mut ret = std::vec::Vec::with_capacity(frame.len());
+
+    for byte in frame {
+        if byte_stuffing.is_escaped(*byte) {
+            ret.push(byte_stuffing.escape);
+            ret.push(byte ^ byte_stuffing.xor);
+        } else {
+            ret.push(*byte);
+        }
+    }
+
+    ret
+}
+
+/// Runs every canned message through its parser
+#[derive(Debug)]
+pub struct SelfTestFunction {
+    pub messages: std::vec::Vec<SelfTestMessage>,
+    pub symbol_naming: SymbolNaming,
+}
+
+impl From<&bpir::representation::Protocol> for SelfTestFunction {
+    fn from(protocol: &bpir::representation::Protocol) -> Self {
+        let mut messages = std::vec::Vec::new();
+
+        for message in &protocol.messages {
+            let first_index = messages.len();
+
+            match SelfTestMessage::from_message(
+                message,
+                protocol.symbol_decoding(),
+                protocol.byte_stuffing(),
+                CannedValues::Distinct,
+            ) {
+                Some(self_test_message) => messages.push(self_test_message),
+                None => {
+                    log::warn!(
+                        "Could not make a canned instance of message \"{}\", it will not be self-tested",
+                        message.name
+                    );
+
+                    continue;
+                }
+            }
+
+            // Bit-granular messages are packed by the bit reader, which knows no byte order
+            if message.layout() == MessageLayout::Bytes && SelfTestMessage::has_multi_byte_values(message) {
+                messages.extend(SelfTestMessage::from_message(
+                    message,
+                    protocol.symbol_decoding(),
+                    protocol.byte_stuffing(),
+                    CannedValues::AscendingBytes,
+                ));
+            }
+
+            let serializer_capacity = match protocol.serializers() {
+                true => Serializer::from_message(protocol, message).ok().map(|serializer| serializer.max_length),
+                false => None,
+            };
+
+            for self_test_message in &mut messages[first_index..] {
+                self_test_message.serializer_capacity = serializer_capacity;
+            }
+        }
+
+        SelfTestFunction {
+            messages,
+            symbol_naming: protocol.symbol_naming(),
+        }
+    }
+}
+
+/// Input crafted to make a parser do the most work per byte
+#[derive(Clone, Debug)]
+pub struct BenchmarkPattern {
+    pub message_name: String,
+
+    /// What the input exercises
+    pub description: String,
+
+    /// Input as it appears on the wire
+    pub input: std::vec::Vec<u8>,
+}
+
+impl BenchmarkPattern {
+    /// Derives the patterns from the structure of the message's machine, on
+    /// top of its canned instance:
+    ///
+    /// - a valid frame, the nominal case;
+    /// - a frame rejected at its last byte, since every byte before it runs
+    ///   the field actions and checksum updates, and the rejection restarts
+    ///   the parser. Frames are rejected by a checksum mismatch, or by an
+    ///   extra byte past the fixed length;
+    /// - near-miss sync sequences lacking their last byte, repeated up to the
+    ///   max frame length, which keep the parser hunting for a frame.
+    pub fn from_message(
+        protocol: &bpir::representation::Protocol,
+        message: &bpir::representation::Message,
+    ) -> std::vec::Vec<BenchmarkPattern> {
+        let mut ret = std::vec::Vec::new();
+
+        if message.layout() != MessageLayout::Bytes {
+            return ret;
+        }
+
+        let frame = match SelfTestMessage::from_message(message, None, None, CannedValues::Distinct) {
+            Some(self_test_message) => self_test_message.frame,
+            None => return ret,
+        };
+        let mut push = |description: &str, input: std::vec::Vec<u8>| {
+            if let Some(input) = encode_frame(message, protocol.symbol_decoding(), protocol.byte_stuffing(), input) {
+                ret.push(BenchmarkPattern {
+                    message_name: message.name.clone(),
+                    description: description.to_string(),
+                    input,
+                });
+            }
+        };
+
+        push("valid frame", frame.clone());
+
+        let ends_with_checksum = message.fields.last().is_some_and(|field| field.checksum().is_some());
+
+        if ends_with_checksum {
+            let mut rejected = frame.clone();
+
+            if let Some(last) = rejected.last_mut() {
+                *last ^= 0xFF;
+            }
+
+            push("checksum mismatch at the last byte", rejected);
+        } else if message.fixed_length().is_some() {
+            let mut rejected = frame.clone();
+            rejected.push(0u8);
+            push("extra byte past the fixed length", rejected);
+        }
+
+        if let Some(sync_sequence) = message.sync_sequence().filter(|sync_sequence| sync_sequence.len() > 1) {
+            let mut near_miss = sync_sequence.to_vec();
+            *near_miss.last_mut().unwrap() ^= 0xFF;
+
+            let length = message.max_encoded_length() + sync_sequence.len();
+            push(
+                "near-miss sync sequences",
+                near_miss.iter().copied().cycle().take(length).collect(),
+            );
+        }
+
+        ret
+    }
+}
+
+/// Times the parsers on the benchmark patterns of every message
+#[derive(Debug)]
+pub struct BenchmarkFunction {
+    pub attribute: bpir::representation::BenchmarkAttribute,
+    pub patterns: std::vec::Vec<BenchmarkPattern>,
+    pub symbol_naming: SymbolNaming,
+}
+
+impl BenchmarkFunction {
+    pub fn from_protocol(protocol: &bpir::representation::Protocol) -> Option<BenchmarkFunction> {
+        Some(BenchmarkFunction {
+            attribute: protocol.benchmark()?.clone(),
+            patterns: protocol
+                .messages
+                .iter()
+                .flat_map(|message| BenchmarkPattern::from_message(protocol, message))
+                .collect(),
+            symbol_naming: protocol.symbol_naming(),
+        })
+    }
+}
+
+/// A field member a serializer reads
+#[derive(Clone, Debug)]
+pub struct SerializedMember {
+    pub field_name: String,
+    pub container: FieldContainer,
+    pub field_base_type: FieldBaseType,
+}
+
+impl SerializedMember {
+    fn new(message: &bpir::representation::Message, field: &bpir::representation::Field, container: FieldContainer) -> Self {
+        SerializedMember {
+            field_name: field.name.clone(),
+            container,
+            field_base_type: FieldBaseType::from_field(&message.name, field),
+        }
+    }
+}
+
+/// A step of composing a frame out of a message struct
+#[derive(Clone, Debug)]
+pub enum SerializerStep {
+    /// Bytes every frame carries: the sync sequence, the ID, literal fields
+    Constant(std::vec::Vec<u8>),
+
+    /// Zero bytes of reserved fields, and placeholders of length fields
+    Zeros(usize),
+
+    /// A big-endian integer, flags, or timestamp field
+    Integer {
+        member: SerializedMember,
+        bit_width: usize,
+    },
+
+    /// A LEB128 varint field
+    Varint {
+        member: SerializedMember,
+        varint: bpir::representation::VarintFieldType,
+    },
+
+    /// The bytes of a regex field, which is not a literal: as many as its
+    /// length member holds, up to `max_length`
+    Pattern {
+        member: SerializedMember,
+        length: SerializedMember,
+        max_length: usize,
+    },
+
+    /// Remembers the length of the frame so far under the name
+    Mark(String),
+
+    /// Fills a length field in at the first mark with the number of bytes
+    /// between the other two
+    Length {
+        offset: String,
+        start: String,
+        end: String,
+        bit_width: usize,
+    },
+
+    /// A checksum of the bytes between the marks
+    Checksum {
+        start: String,
+        stop: String,
+        algorithm: bpir::representation::CrcAlgorithm,
+        bit_width: usize,
+    },
+
+    /// Steps of a field, which is only present if the selector has the value
+    Conditional {
+        selector: SerializedMember,
+        value: i64,
+        steps: std::vec::Vec<SerializerStep>,
+    },
+
+    /// Steps of each repetition of a group field
+    Group {
+        field_name: String,
+        count: usize,
+        steps: std::vec::Vec<SerializerStep>,
+    },
+}
+
+/// Name of the mark the serializer remembers the start of a field under
+fn field_start_mark(field_name: &str) -> String {
+    format!("{0}Start", field_name)
+}
+
+/// Name of the mark the serializer remembers the end of a field under
+fn field_end_mark(field_name: &str) -> String {
+    format!("{0}End", field_name)
+}
+
+/// Encodes a message struct into a frame, as its parser expects it before
+/// symbol encoding and byte stuffing
+#[derive(Debug)]
+pub struct Serializer {
+    pub message_name: String,
+    pub symbol_naming: SymbolNaming,
+
+    /// Bytes the longest frame takes
+    pub max_length: usize,
+    pub marks: std::vec::Vec<String>,
+    pub steps: std::vec::Vec<SerializerStep>,
+
+    /// The function returns once, see `ProtocolAttribute::MisraC`
+    pub misra_c: bool,
+}
+
+impl Serializer {
+    /// Plans the serializer of a message. Fails with the reason, if the
+    /// message's frames can not be composed of its struct
+    pub fn from_message(
+        protocol: &bpir::representation::Protocol,
+        message: &bpir::representation::Message,
+    ) -> Result<Serializer, String> {
+        if message.layout() != MessageLayout::Bytes {
+            return Err("its layout is bit-granular".to_string());
+        }
+
+        if protocol.symbol_decoding().is_some() || protocol.byte_stuffing().is_some() {
+            return Err("the protocol encodes symbols, or stuffs bytes".to_string());
+        }
+
+        // Literal regex fields are written as constants, the others are
+        // copied out of their members
+        let has_patterns = message.fields.iter().any(|field| {
+            matches!(field.field_type, FieldType::Regex(ref regex) if regex.pattern().literal().is_none())
+        });
+
+        if protocol.zero_copy(message) && has_patterns {
+            return Err("its regex fields refer into the parser's input".to_string());
+        }
+
+        let mut ret = Serializer {
+            message_name: message.name.clone(),
+            symbol_naming: protocol.symbol_naming(),
+            max_length: message.sync_sequence().map_or(0usize, <[u8]>::len) + message.max_encoded_length(),
+            marks: std::vec::Vec::new(),
+            steps: std::vec::Vec::new(),
+            misra_c: protocol.misra_c(),
+        };
+
+        if let Some(sync_sequence) = message.sync_sequence() {
+            ret.steps.push(SerializerStep::Constant(sync_sequence.to_vec()));
+        }
+
+        // Length fields are filled in, once both they and the fields they
+        // cover have been written
+        let mut pending_lengths = std::vec::Vec::new();
+        let mut written_fields = std::vec::Vec::new();
+
+        for (field_index, field) in message.fields.iter().enumerate() {
+            let mut steps = std::vec::Vec::new();
+            let covers_first = message
+                .fields
+                .iter()
+                .filter_map(|other| other.length_of())
+                .any(|length_of| length_of.first == field.name);
+
+            if covers_first {
+                steps.push(ret.mark(field_start_mark(&field.name)));
+            }
+
+            for attribute in &field.attributes {
+                if let bpir::representation::FieldAttribute::StartChecksum(name) = attribute {
+                    steps.push(ret.mark(field_start_mark(&checksum_member_name(name))));
+                }
+            }
+
+            match (&field.field_type, field.checksum(), field.length_of()) {
+                (FieldType::Integer(ref integer), _, _) if field_index == 0 && message.id().is_some() => {
+                    let id = message.id().unwrap_or_default();
+                    let byte_count = integer.bit_width.div_ceil(8);
+                    steps.push(SerializerStep::Constant(
+                        (0..byte_count).rev().map(|i| (id >> (8 * i)) as u8).collect(),
+                    ));
+                }
+                (FieldType::Integer(ref integer), Some(checksum), _) => {
+                    let stop = field_end_mark(&checksum_member_name(&checksum.name));
+
+                    if !ret.marks.contains(&stop) {
+                        return Err(format!("checksum field {0} precedes the end of its coverage", field.name));
+                    }
+
+                    steps.push(SerializerStep::Checksum {
+                        start: field_start_mark(&checksum_member_name(&checksum.name)),
+                        stop,
+                        algorithm: checksum.algorithm.clone(),
+                        bit_width: integer.bit_width,
+                    });
+                }
+                (FieldType::Integer(ref integer), None, Some(length_of)) => {
+                    if length_of.length.is_some() {
+                        return Err(format!("length field {0} has a length expression", field.name));
+                    }
+
+                    steps.push(ret.mark(field_start_mark(&field.name)));
+                    steps.push(SerializerStep::Zeros(integer.bit_width.div_ceil(8)));
+                    pending_lengths.push((field, length_of, integer.bit_width));
+                }
+                _ => steps.extend(Serializer::field_steps(message, field, FieldContainer::from_field(field))?),
+            }
+
+            let ends_checksum = field.attributes.iter().filter_map(|attribute| match attribute {
+                bpir::representation::FieldAttribute::StopChecksum(name) => Some(name),
+                _ => None,
+            });
+
+            for name in ends_checksum {
+                steps.push(ret.mark(field_end_mark(&checksum_member_name(name))));
+            }
+
+            let ends_coverage = message
+                .fields
+                .iter()
+                .filter_map(|other| other.length_of())
+                .any(|length_of| length_of.last == field.name);
+
+            if ends_coverage {
+                steps.push(ret.mark(field_end_mark(&field.name)));
+            }
+
+            written_fields.push(field.name.as_str());
+            pending_lengths.retain(|&(length_field, length_of, bit_width)| {
+                let is_complete = [length_field.name.as_str(), length_of.last.as_str()]
+                    .iter()
+                    .all(|name| written_fields.contains(name));
+
+                if is_complete {
+                    steps.push(SerializerStep::Length {
+                        offset: field_start_mark(&length_field.name),
+                        start: field_start_mark(&length_of.first),
+                        end: field_end_mark(&length_of.last),
+                        bit_width,
+                    });
+                }
+
+                !is_complete
+            });
+
+            match field.conditional_on() {
+                Some(conditional_on) => {
+                    let selector = message
+                        .fields
+                        .iter()
+                        .find(|other| other.name == conditional_on.field)
+                        .ok_or_else(|| format!("selector {0} is unknown", conditional_on.field))?;
+                    ret.steps.push(SerializerStep::Conditional {
+                        selector: SerializedMember::new(message, selector, FieldContainer::from_field(selector)),
+                        value: conditional_on.value,
+                        steps,
+                    });
+                }
+                None => ret.steps.extend(steps),
+            }
+        }
+
+        Ok(ret)
+    }
+
+    fn mark(&mut self, name: String) -> SerializerStep {
+        if !self.marks.contains(&name) {
+            self.marks.push(name.clone());
+        }
+
+        SerializerStep::Mark(name)
+    }
+
+    /// Steps writing the value of a field, as stored in the container
+    fn field_steps(
+        message: &bpir::representation::Message,
+        field: &bpir::representation::Field,
+        container: FieldContainer,
+    ) -> Result<std::vec::Vec<SerializerStep>, String> {
+        let ret = match field.field_type {
+            FieldType::Regex(ref regex) => match regex.pattern().literal() {
+                Some(bytes) => vec![SerializerStep::Constant(bytes)],
+                None => vec![SerializerStep::Pattern {
+                    member: SerializedMember::new(message, field, container.clone()),
+                    length: SerializedMember {
+                        field_name: regex_length_member_name(&field.name),
+                        container,
+                        field_base_type: FieldBaseType::U32,
+                    },
+                    max_length: field.max_encoded_length().min(array_capacity(field)),
+                }],
+            },
+            FieldType::Integer(ref integer) => vec![SerializerStep::Integer {
+                member: SerializedMember::new(message, field, container),
+                bit_width: integer.bit_width,
+            }],
+            FieldType::Flags(_) | FieldType::Timestamp(_) => vec![SerializerStep::Integer {
+                member: SerializedMember::new(message, field, container),
+                bit_width: field.integer_value_type().map_or(0usize, |integer| integer.bit_width),
+            }],
+            FieldType::Varint(ref varint) => vec![SerializerStep::Varint {
+                member: SerializedMember::new(message, field, container),
+                varint: varint.clone(),
+            }],
+            FieldType::Reserved(ref reserved) => vec![SerializerStep::Zeros(reserved.length)],
+            FieldType::Group(ref group) => {
+                let mut steps = std::vec::Vec::new();
+
+                for member in &group.fields {
+                    steps.extend(Serializer::field_steps(
+                        message,
+                        member,
+                        FieldContainer::Group(field.name.clone()),
+                    )?);
+                }
+
+                vec![SerializerStep::Group {
+                    field_name: field.name.clone(),
+                    count: group.count,
+                    steps,
+                }]
+            }
+            FieldType::Tlv(_) => return Err(format!("TLV field {0} is not supported", field.name)),
+        };
+
+        Ok(ret)
+    }
+}
+
+/// Part of the generated code a file holds
+#[derive(Debug, Clone)]
+pub enum OutputPart {
+    /// All of the code, when the output is not split
+    Whole,
+
+    /// Code shared by every message: parse errors, frame trace storage, the
+    /// self-test, the benchmark, diagnostic strings
+    Shared,
+
+    /// Types and parsers of the listed messages
+    Messages(std::vec::Vec<String>),
+
+    /// Declarations depending on every message: sizing constants and the
+    /// dispatcher. Only the umbrella header holds them
+    Umbrella,
+}
+
+impl OutputPart {
+    pub fn includes_message(&self, message_name: &str) -> bool {
+        match self {
+            OutputPart::Whole => true,
+            OutputPart::Messages(message_names) => message_names.iter().any(|name| name == message_name),
+            _ => false,
+        }
+    }
+
+    pub fn includes_shared(&self) -> bool {
+        matches!(self, OutputPart::Whole | OutputPart::Shared)
+    }
+
+    pub fn includes_umbrella(&self) -> bool {
+        matches!(self, OutputPart::Whole | OutputPart::Umbrella)
+    }
+}
+
+/// Frame trace routines. The storage is defined once, while the routine
+/// recording frames is static, so each file holding parsers has a copy
+#[derive(Debug, Clone)]
+pub struct FrameTrace {
+    pub attribute: bpir::representation::FrameTraceAttribute,
+
+    /// The ring buffer, and the function dumping it
+    pub storage: bool,
+
+    /// The function recording a frame
+    pub recorder: bool,
+}
+
+/// Table of parse error descriptions, and the function looking them up
+#[derive(Debug, Clone)]
+pub struct DiagnosticStrings {
+    pub attribute: bpir::representation::DiagnosticStringsAttribute,
+
+    /// The lookup returns once, see `ProtocolAttribute::MisraC`
+    pub misra_c: bool,
+}
+
+/// A section of a generated file. Its children are marked as a foldable
+/// region, so IDEs can collapse the section
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub name: std::string::String,
+
+    /// Whether the region ends with a block of user code, named after the
+    /// region, see `parser_generation::user_code`
+    pub user_code_anchor: bool,
+}
+
+impl Region {
+    pub fn new(name: &str, protocol: &bpir::representation::Protocol) -> Region {
+        Region {
+            name: name.to_string(),
+            user_code_anchor: protocol.user_code_anchors(),
+        }
+    }
+}
+
+/// A message the dispatcher routes frames into
+#[derive(Debug, Clone)]
+pub struct DispatchedMessage {
+    pub message_name: std::string::String,
+    pub id: u64,
+}
+
+/// Reads the ID field shared by messages having an ID, and routes the frame
+/// into the parser of the message it identifies
+#[derive(Debug, Clone)]
+pub struct Dispatcher {
+    pub messages: std::vec::Vec<DispatchedMessage>,
+
+    /// Length of the ID field, in bytes
+    pub id_length: usize,
+
+    /// Decoding the dispatcher applies to the ID bytes before comparing them
+    pub symbol_decoding: Option<SymbolDecoding>,
+    pub symbol_naming: SymbolNaming,
+}
+
+impl Dispatcher {
+    /// Makes a dispatcher, if any message of the protocol has an ID
+    pub fn from_protocol(protocol: &bpir::representation::Protocol) -> Option<Dispatcher> {
+        let dispatched_messages = protocol.dispatched_messages();
+        let id_length = dispatched_messages.first()?.fields.first()?.max_encoded_length();
+
+        Some(Dispatcher {
+            messages: dispatched_messages
+                .iter()
+                .map(|message| DispatchedMessage {
+                    message_name: message.name.clone(),
+                    id: message.id().unwrap_or(0),
+                })
+                .collect(),
+            id_length,
+            symbol_decoding: protocol.symbol_decoding().cloned(),
+            symbol_naming: protocol.symbol_naming(),
+        })
+    }
+
+    /// Type of the accumulated ID
+    pub fn id_base_type(&self) -> FieldBaseType {
+        FieldBaseType::from_bit_width(self.id_length * 8, false)
+    }
+}
+
+#[derive(Debug)]
+pub enum AstNodeType {
+    /// An empty representation for a subtre
+    Root,
+
+    // C-specific elements (TBD)
+
+    // Language-agnostic elements
+    /// Just treat it as a mere sequence
+    MessageStructMember(MessageStructMember),
+    ParsingFunction(ParsingFunction),
+
+    /// Ragel-specific machine header
+    MachineHeader(MachineHeader),
+    MachineActionHook(MachineActionHook),
+    MachineDefinition(MachineDefinition),
+    RegexMachineField(RegexMachineField),
+    RawCode(RawCode),
+    ParserStateInitFunction(ParserStateInitFunction),
+    AccessSequence,
+
+    /// Declares the type of the machine's input symbols
+    AlphTypeSequence,
+    PresenceBitmapReset(PresenceBitmapReset),
+    PresenceBitmapUpdate(PresenceBitmapUpdate),
+    FixedLengthMachineField(FixedLengthMachineField),
+
+    /// Bit reader routines, shared by every bit-granular message
+    BitReader(BitReader),
+    BitstreamParsingFunction(BitstreamParsingFunction),
+    BitstreamFieldRead(BitstreamFieldRead),
+    TlvMachineField(TlvMachineField),
+    TlvAction(TlvAction),
+    GroupMachineField(GroupMachineField),
+    GroupAction(GroupAction),
+    VarintMachineField(VarintMachineField),
+    VarintAction(VarintAction),
+    RegexAction(RegexAction),
+    ConditionCheck(ConditionCheck),
+    ScaledValueAccessor(ScaledValueAccessor),
+    FlagsAccessor(FlagsAccessor),
+    TimestampAccessor(TimestampAccessor),
+    DumpFunction(DumpFunction),
+    StructFunctions(StructFunctions),
+
+    /// Symbol decoding routines, shared by every message
+    SymbolDecoder(SymbolDecoding),
+
+    /// Makes the state machine retrieve bytes through a bytewise symbol
+    /// decoder
+    SymbolDecodingGetKey(SymbolDecoding),
+    IntegerFieldByte(IntegerFieldByte),
+    RangeCheck(RangeCheck),
+    ExpressionCheck(ExpressionCheck),
+    ReservedFieldByte(ReservedFieldByte),
+    BitstreamReservedField(BitstreamReservedField),
+
+    /// Loopback self-test, covers every message of the protocol
+    SelfTestFunction(SelfTestFunction),
+    BenchmarkFunction(BenchmarkFunction),
+
+    /// Composes a frame out of a message struct
+    SerializingFunction(Serializer),
+    ParsedCallback(ParsedCallback),
+
+    /// Table of parse error descriptions, and a function looking them up
+    DiagnosticStrings(DiagnosticStrings),
+
+    /// Ring buffer of frame outcomes, shared by every message
+    FrameTrace(FrameTrace),
+
+    /// Routes frames into parsers by message ID
+    Dispatcher(Dispatcher),
+    ChecksumAction(ChecksumAction),
+    LengthAction(LengthAction),
+
+    /// CRC routines, shared by every message
+    ChecksumRoutines(ChecksumRoutines),
+
+    /// Foldable section of the generated file
+    Region(Region),
+}
+
+impl TreeBasedCodeGeneration for MachineHeader {
+    fn generate_code_pre_traverse(
+        &self,
+        generation_state: &mut CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<CodeChunk>::new();
+        ret.push(CodeChunk::new(
+            "%%{".to_string(),
+            generation_state.indent,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            format!("machine {0};", self.machine_name),
+            generation_state.indent + 1,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            std::iter::once("write data")
+                .chain(self.write_data_options.iter().map(|option| option.keyword()))
+                .collect::<std::vec::Vec<_>>()
+                .join(" ")
+                + ";",
+            generation_state.indent + 1,
+            1usize,
+        ));
+
+        ret
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        generation_state: &mut CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = vec![CodeChunk::new(
+            "}%%".to_string(),
+            generation_state.indent,
+            1usize,
+        )];
+
+        // Costs no storage, unlike the constant
+        if self.write_data_options.contains(&bpir::representation::RagelWriteDataOption::NoError) {
+            ret.push(CodeChunk::new(
+                format!("#define {0}_error 0  // Omitted by \"write data noerror\"", self.machine_name),
+                generation_state.indent,
+                1usize,
+            ));
+        }
+
+        ret
+    }
+}
+
+impl TreeBasedCodeGeneration for MachineActionHook {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<CodeChunk>::new();
+        ret.push(CodeChunk::new(
+            format!("action {0} {{", self.name),
+            code_generation_state.indent,
+            1usize,
+        ));
+        code_generation_state.indent += 1;
+
+        // Leaves a place for overriding the body, even if it is empty
+        ret.push(
+            CodeChunk::new(String::new(), code_generation_state.indent, 0usize)
+                .with_fragment(Some(&Fragment::ActionBody(self.name.clone()))),
+        );
+
+        ret
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<CodeChunk>::new();
+        code_generation_state.indent -= 1;
+        ret.push(CodeChunk::new(
+            "}".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+impl TreeBasedCodeGeneration for MachineDefinition {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<CodeChunk>::new();
+        ret.push(CodeChunk::new(
+            "%%{".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+        ret.push(CodeChunk::new(
+            format!("machine {0};", self.machine_name),
+            code_generation_state.indent + 1,
+            1usize,
+        ));
+
+        code_generation_state.indent += 1;
+
+        ret
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<CodeChunk>::new();
+        let fields = match self.field_order {
+            FieldOrder::Strict => self.fields.join(" "),
+            FieldOrder::Unordered => format!(
+                "( {0} )* >{1}",
+                self.fields.join(" | "),
+                PRESENCE_BITMAP_RESET_HOOK_NAME
+            ),
+        };
+        let frame = match self.sync_sequence {
+            // The hunt's bytes, but the last one of the sync sequence, are counted as they are dropped. Actions of
+            // inner machines run first, so a frame is settled once its last byte has been counted
+            Some(ref sync_sequence) if self.discard_counting => format!(
+                "( ( ( any ${0} )* :>> ( {1} ) ) @{2} ( {3} ) ${4} ) @{5}",
+                HUNTED_BYTE_HOOK_NAME,
+                sync_sequence
+                    .iter()
+                    .map(|byte| format!("0x{0:02X}", byte))
+                    .collect::<std::vec::Vec<String>>()
+                    .join(" "),
+                SYNCHRONIZED_HOOK_NAME,
+                fields,
+                FRAMED_BYTE_HOOK_NAME,
+                FRAME_SETTLED_HOOK_NAME
+            ),
+            Some(ref sync_sequence) => format!(
+                "( any* :>> ( {0} ) ) {1}",
+                sync_sequence
+                    .iter()
+                    .map(|byte| format!("0x{0:02X}", byte))
+                    .collect::<std::vec::Vec<String>>()
+                    .join(" "),
+                fields
+            ),
+            None => fields,
+        };
+        let main = match self.acceptance_hook {
+            Some(ref hook) => format!("main := ( {0} ) @{1};", frame, hook),
+            None => format!("main := {0};", frame),
+        };
+        ret.push(CodeChunk::new(main, code_generation_state.indent, 1usize));
+
+        code_generation_state.indent -= 1;
+        ret.push(CodeChunk::new(
+            "}%%".to_string(),
+            code_generation_state.indent,
+            1usize,
+        ));
+
+        ret
+    }
+}
+
+impl TreeBasedCodeGeneration for FixedLengthMachineField {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<CodeChunk>::new();
+        let machine = match self.byte_action {
+            Some(ref byte_action) => format!("any{{{0}}} ${1} @{2}", self.length, byte_action, self.name),
+            None => format!("any{{{0}}} @{1}", self.length, self.name),
+        };
+        let line = match self.condition {
+            Some(ref condition) => format!("{0} = ( {1} ) when {2}; ", self.name, machine, condition),
+            None => format!("{0} = {1}; ", self.name, machine),
+        };
+        ret.push(CodeChunk::new(line, code_generation_state.indent, 1usize));
 
         ret
     }
 }
 
-impl TreeBasedCodeGeneration for MachineActionHook {
+impl TreeBasedCodeGeneration for TlvMachineField {
     fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        let mut ret = LinkedList::<CodeChunk>::new();
-        ret.push_back(CodeChunk::new(
-            format!("action {0} {{", self.name),
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<CodeChunk>::new();
+        ret.push(CodeChunk::new(
+            format!(
+                "{0} := ( any @{1} )*;",
+                TlvActionKind::ValueByte.hook_name(&self.name),
+                TlvActionKind::ValueByte.hook_name(&self.name)
+            ),
             code_generation_state.indent,
             1usize,
         ));
-        ret.push_back(CodeChunk::new(
-            "}".to_string(),
+        ret.push(CodeChunk::new(
+            format!(
+                "{0} = ( any{{{1}}} >{2} ${3} any{{{4}}} ${5} @{6} )* >{7};",
+                self.name,
+                self.tag_length,
+                TlvActionKind::RecordStart.hook_name(&self.name),
+                TlvActionKind::TagByte.hook_name(&self.name),
+                self.length_length,
+                TlvActionKind::LengthByte.hook_name(&self.name),
+                TlvActionKind::ValueStart.hook_name(&self.name),
+                TlvActionKind::Reset.hook_name(&self.name)
+            ),
             code_generation_state.indent,
             1usize,
         ));
@@ -166,47 +2932,60 @@ impl TreeBasedCodeGeneration for MachineActionHook {
     }
 }
 
-impl TreeBasedCodeGeneration for MachineDefinition {
+impl TreeBasedCodeGeneration for GroupMachineField {
     fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        let mut ret = LinkedList::<CodeChunk>::new();
-        ret.push_back(CodeChunk::new(
-            "%%{".to_string(),
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<CodeChunk>::new();
+        let members = self
+            .members
+            .iter()
+            .map(|member| match member.byte_action {
+                Some(ref byte_action) => format!("any{{{0}}} ${1}", member.length, byte_action),
+                None => format!("any{{{0}}}", member.length),
+            })
+            .collect::<std::vec::Vec<String>>()
+            .join(" ");
+        ret.push(CodeChunk::new(
+            format!(
+                "{0} = ( {1} @{2} ){{{3}}} >{4} @{0};",
+                self.name,
+                members,
+                GroupActionKind::Next.hook_name(&self.name),
+                self.count,
+                GroupActionKind::Reset.hook_name(&self.name)
+            ),
             code_generation_state.indent,
             1usize,
         ));
-        ret.push_back(CodeChunk::new(
-            format!("machine {0};", self.machine_name),
-            code_generation_state.indent + 1,
-            1usize,
-        ));
-
-        code_generation_state.indent += 1;
 
         ret
     }
+}
 
-    fn generate_code_post_traverse(
+impl TreeBasedCodeGeneration for VarintMachineField {
+    fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-
-        let mut ret = LinkedList::<CodeChunk>::new();
-        // TODO: `main := <fields>`
-        ret.push_back(CodeChunk::new(
-            format!("main := {0};", self.fields.join(" ")),
-            code_generation_state.indent,
-            1usize
-        ));
-
-        code_generation_state.indent -= 1;
-        ret.push_back(CodeChunk::new(
-            "}%%".to_string(),
-            code_generation_state.indent,
-            1usize,
-        ));
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<CodeChunk>::new();
+        let bytes = match self.max_length {
+            0 | 1 => "0x00..0x7F".to_string(),
+            max_length => format!("( 0x80..0xFF ){{,{0}}} 0x00..0x7F", max_length - 1),
+        };
+        let machine = format!(
+            "( {0} ) >{1} ${2} @{3}",
+            bytes,
+            VarintActionKind::Reset.hook_name(&self.name),
+            VarintActionKind::Byte.hook_name(&self.name),
+            VarintActionKind::Decode.hook_name(&self.name)
+        );
+        let line = match self.condition {
+            Some(ref condition) => format!("{0} = ( {1} ) when {2}; ", self.name, machine, condition),
+            None => format!("{0} = {1}; ", self.name, machine),
+        };
+        ret.push(CodeChunk::new(line, code_generation_state.indent, 1usize));
 
         ret
     }
@@ -216,10 +2995,18 @@ impl TreeBasedCodeGeneration for RegexMachineField {
     fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        let mut ret = LinkedList::<CodeChunk>::new();
-        ret.push_back(CodeChunk::new(
-            format!("{0} = '{1}' @{0}; ", self.name, self.string_sequence),
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::<CodeChunk>::new();
+        let capture = match self.capture {
+            true => format!(
+                " >{0} ${1}",
+                RegexActionKind::Reset.hook_name(&self.name),
+                RegexActionKind::Byte.hook_name(&self.name)
+            ),
+            false => String::new(),
+        };
+        ret.push(CodeChunk::new(
+            format!("{0} = {1}{2} @{0}; ", self.name, self.string_sequence, capture),
             code_generation_state.indent,
             1usize,
         ));
@@ -232,17 +3019,141 @@ impl TreeBasedCodeGeneration for RegexMachineField {
 pub struct AstNode {
     pub ast_node_type: AstNodeType,
     pub children: std::vec::Vec<AstNode>,
+
+    /// Element of the protocol the node's code is attributed to, see
+    /// `codegen::SourceMap`
+    pub origin: Option<Origin>,
+
+    /// Overridable fragment the node's code is a part of
+    pub fragment: Option<Fragment>,
 }
 
 impl From<&bpir::representation::Protocol> for AstNode {
     fn from(protocol: &bpir::representation::Protocol) -> Self {
+        AstNode::from_part(protocol, &OutputPart::Whole)
+    }
+}
+
+impl AstNode {
+    /// Builds the tree of the given part of the generated code
+    pub fn from_part(protocol: &bpir::representation::Protocol, part: &OutputPart) -> AstNode {
         let mut root = AstNode {
             ast_node_type: AstNodeType::Root,
             children: vec![],
+            origin: None,
+            fragment: None,
+        };
+        let messages = protocol
+            .messages
+            .iter()
+            .filter(|message| part.includes_message(&message.name))
+            .collect::<std::vec::Vec<_>>();
+        let has_byte_parsers = messages
+            .iter()
+            .any(|message| message.layout() == MessageLayout::Bytes);
+        let dispatcher = match part.includes_shared() {
+            true => Dispatcher::from_protocol(protocol),
+            false => None,
         };
 
-        for message in &protocol.messages {
-            root.add_message_parser(message);
+        if part.includes_shared() {
+            for option in protocol.ragel_options().write_data.iter().filter(|option| !option.supported()) {
+                log::warn!(
+                    "The parsers refer to the constants \"write data {}\" omits, the option is dropped",
+                    option.keyword()
+                );
+            }
+        }
+
+        let mut bit_orders = std::vec::Vec::new();
+
+        for message in messages.iter().filter(|message| message.layout() == MessageLayout::Bits) {
+            if !bit_orders.contains(&message.bit_order()) {
+                bit_orders.push(message.bit_order());
+            }
+        }
+
+        if !bit_orders.is_empty() {
+            root.add_child(AstNodeType::Region(Region::new("Bit reader", protocol)))
+                .add_child(AstNodeType::BitReader(BitReader { bit_orders }));
+        }
+
+        // The shared part only decodes symbols for the dispatcher
+        if let Some(symbol_decoding) = protocol.symbol_decoding() {
+            if matches!(part, OutputPart::Whole) || has_byte_parsers || dispatcher.is_some() {
+                root.add_child(AstNodeType::Region(Region::new("Symbol decoding", protocol)))
+                    .add_child(AstNodeType::SymbolDecoder(symbol_decoding.clone()));
+            }
+        }
+
+        if let Some(frame_trace) = protocol.frame_trace() {
+            let frame_trace = FrameTrace {
+                attribute: frame_trace.clone(),
+                storage: part.includes_shared(),
+                recorder: has_byte_parsers,
+            };
+
+            if frame_trace.storage || frame_trace.recorder {
+                root.add_child(AstNodeType::Region(Region::new("Frame trace", protocol)))
+                    .add_child(AstNodeType::FrameTrace(frame_trace));
+            }
+        }
+
+        let crc_algorithms = protocol.crc_algorithms();
+
+        // The runtime library defines the routines once for every protocol
+        if !crc_algorithms.is_empty() && part.includes_shared() && !protocol.uses_runtime() {
+            let implementation = protocol.checksum_implementation();
+
+            // Extern routines are only declared in the header
+            if implementation != bpir::representation::ChecksumImplementation::Extern {
+                root.add_child(AstNodeType::Region(Region::new("Checksums", protocol)))
+                    .add_child(AstNodeType::ChecksumRoutines(ChecksumRoutines {
+                        algorithms: crc_algorithms.into_iter().cloned().collect(),
+                        implementation,
+                    }));
+            }
+        }
+
+        for message in &messages {
+            let region = root.add_child(AstNodeType::Region(Region::new(
+                &format!("{0} parser", message.name),
+                protocol,
+            )));
+
+            match message.layout() {
+                MessageLayout::Bytes => region.add_message_parser(protocol, message),
+                MessageLayout::Bits => region.add_bitstream_message_parser(protocol, message),
+            }
+
+            region.assign_origin(&Origin::message(&message.name));
+        }
+
+        if let Some(dispatcher) = dispatcher {
+            root.add_child(AstNodeType::Region(Region::new("Dispatcher", protocol)))
+                .add_child(AstNodeType::Dispatcher(dispatcher));
+        }
+
+        if protocol.self_test() && part.includes_shared() {
+            root.add_child(AstNodeType::Region(Region::new("Self-test", protocol)))
+                .add_child(AstNodeType::SelfTestFunction(SelfTestFunction::from(protocol)));
+        }
+
+        if let Some(benchmark_function) =
+            BenchmarkFunction::from_protocol(protocol).filter(|_| part.includes_shared())
+        {
+            root.add_child(AstNodeType::Region(Region::new("Benchmark", protocol)))
+                .add_child(AstNodeType::BenchmarkFunction(benchmark_function));
+        }
+
+        if let Some(diagnostic_strings) = protocol.diagnostic_strings() {
+            if part.includes_shared() {
+                root.add_child(AstNodeType::Region(Region::new("Diagnostic strings", protocol)))
+                    .add_child(AstNodeType::DiagnosticStrings(DiagnosticStrings {
+                        attribute: diagnostic_strings.clone(),
+                        misra_c: protocol.misra_c(),
+                    }));
+            }
         }
 
         root
@@ -253,7 +3164,7 @@ impl TreeBasedCodeGeneration for AstNodeType {
     fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
+    ) -> Vec<CodeChunk> {
         match self {
             AstNodeType::MachineHeader(ref node) => {
                 node.generate_code_pre_traverse(code_generation_state)
@@ -267,21 +3178,33 @@ impl TreeBasedCodeGeneration for AstNodeType {
             AstNodeType::RegexMachineField(ref node) => {
                 node.generate_code_pre_traverse(code_generation_state)
             }
+            AstNodeType::FixedLengthMachineField(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::TlvMachineField(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::GroupMachineField(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+            AstNodeType::VarintMachineField(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
             AstNodeType::RawCode(ref node) => {
                 node.generate_code_pre_traverse(code_generation_state)
             }
-            AstNodeType::Root => LinkedList::new(),
+            AstNodeType::Root => Vec::new(),
             n => {
                 log::warn!("Unhandled node {:?}, skipping", n);
 
-                LinkedList::new()
+                Vec::new()
             }
         }
     }
     fn generate_code_post_traverse(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
+    ) -> Vec<CodeChunk> {
         match self {
             AstNodeType::MachineHeader(ref node) => {
                 node.generate_code_post_traverse(code_generation_state)
@@ -295,14 +3218,26 @@ impl TreeBasedCodeGeneration for AstNodeType {
             AstNodeType::RegexMachineField(ref node) => {
                 node.generate_code_post_traverse(code_generation_state)
             }
+            AstNodeType::FixedLengthMachineField(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::TlvMachineField(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::GroupMachineField(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
+            AstNodeType::VarintMachineField(ref node) => {
+                node.generate_code_post_traverse(code_generation_state)
+            }
             AstNodeType::RawCode(ref node) => {
                 node.generate_code_post_traverse(code_generation_state)
             }
-            AstNodeType::Root => LinkedList::new(),
+            AstNodeType::Root => Vec::new(),
             n => {
                 log::warn!("Unhandled node {:?}, skipping", n);
 
-                LinkedList::new()
+                Vec::new()
             }
         }
     }
@@ -312,17 +3247,31 @@ impl TreeBasedCodeGeneration for AstNode {
     fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
+    ) -> Vec<CodeChunk> {
         self.ast_node_type
             .generate_code_pre_traverse(code_generation_state)
+            .into_iter()
+            .map(|code_chunk| {
+                code_chunk
+                    .with_origin(self.origin.as_ref())
+                    .with_fragment(self.fragment.as_ref())
+            })
+            .collect()
     }
 
     fn generate_code_post_traverse(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
+    ) -> Vec<CodeChunk> {
         self.ast_node_type
             .generate_code_post_traverse(code_generation_state)
+            .into_iter()
+            .map(|code_chunk| {
+                code_chunk
+                    .with_origin(self.origin.as_ref())
+                    .with_fragment(self.fragment.as_ref())
+            })
+            .collect()
     }
 }
 
@@ -340,11 +3289,50 @@ impl AstNode {
         let child = AstNode {
             ast_node_type,
             children: vec![],
+            origin: None,
+            fragment: None,
         };
         self.children.push(child);
         self.children.last_mut().unwrap()
     }
 
+    /// Attributes the subtree to an element of the protocol, except for the
+    /// nodes already attributed to another one
+    fn assign_origin(&mut self, origin: &Origin) {
+        if self.origin.is_none() {
+            self.origin = Some(origin.clone());
+        }
+
+        for subnode in &mut self.children {
+            subnode.assign_origin(origin);
+        }
+    }
+
+    /// Marks the subtree as a part of an overridable fragment
+    fn assign_fragment(&mut self, fragment: &Fragment) {
+        if self.fragment.is_none() {
+            self.fragment = Some(fragment.clone());
+        }
+
+        for subnode in &mut self.children {
+            subnode.assign_fragment(fragment);
+        }
+    }
+
+    /// Attributes the children from the given index on to a field
+    fn assign_field_origin(
+        &mut self,
+        first_child: usize,
+        message: &bpir::representation::Message,
+        field: &bpir::representation::Field,
+    ) {
+        let origin = Origin::field(&message.name, &field.name);
+
+        for subnode in &mut self.children[first_child..] {
+            subnode.assign_origin(&origin);
+        }
+    }
+
     /// Visitor.
     ///
     /// Changes nodes of the tree recursively
@@ -356,95 +3344,666 @@ impl AstNode {
         }
     }
 
-    fn add_message_parser(&mut self, message: &bpir::representation::Message) {
+    fn add_message_parser(
+        &mut self,
+        protocol: &bpir::representation::Protocol,
+        message: &bpir::representation::Message,
+    ) {
+        let ragel_options = protocol.ragel_options();
+
         self.add_child(AstNodeType::MachineHeader(MachineHeader {
             machine_name: message.name.clone(),
+            write_data_options: ragel_options
+                .write_data
+                .iter()
+                .filter(|option| option.supported())
+                .copied()
+                .collect(),
         }));
 
-        let mut message_struct = self.add_child(AstNodeType::MessageStruct(MessageStruct {
-            message_name: message.name.clone(),
-        }));
+        self.add_accessors(protocol, message);
 
-        for field in &message.fields {
-            message_struct.add_child(AstNodeType::MessageStructMember(MessageStructMember {
-                name: field.name.clone(),
-                field_base_type: match field.field_type {
-                    FieldType::Regex(_) => FieldBaseType::I8,
-                },
-                array_length: {
-                    let mut value = 0;
-
-                    match field.field_type {
-                        FieldType::Regex(_) => {
-                            for attribute in &field.attributes {
-                                if let FieldAttribute::MaxLength(ref max_length) = attribute {
-                                    value = max_length.value
-                                }
-                            }
-                        }
-                    }
+        let parser_struct_init_function = self.add_child(AstNodeType::ParserStateInitFunction(
+            ParserStateInitFunction {
+                machine_name: message.name.clone(),
+                symbol_naming: protocol.symbol_naming(),
+            },
+        ));
 
-                    if value == 0usize {
-                        value = bpir::representation::MaxLengthFieldAttribute::get_default_value();
+        if let Some(SymbolDecoding::FourBFiveB) = protocol.symbol_decoding() {
+            for member in four_b_five_b_parser_state_members() {
+                parser_struct_init_function.add_child(AstNodeType::RawCode(RawCode::from(
+                    format!("aParserState->{0} = 0;", member.name).as_str(),
+                )));
+            }
+        }
 
-                        log::warn!(
-                            "Did not get \"MaxLength\" attribute for field \"{}\" in message \"{}\", using default \"{}\"",
-                            field.name,
-                            message.name,
-                            value,
-                        );
-                    }
+        for member in fixed_length_parser_state_members(message)
+            .into_iter()
+            .chain(frame_trace_parser_state_members(protocol))
+            .chain(byte_stuffing_parser_state_members(protocol))
+            .chain(zero_copy_parser_state_members(protocol, message))
+            .chain(discard_counting_parser_state_members(protocol, message))
+        {
+            parser_struct_init_function.add_child(AstNodeType::RawCode(RawCode::from(
+                format!("aParserState->{0} = 0;", member.name).as_str(),
+            )));
+        }
 
-                    value
-                }
+        if protocol.parser_statistics(message) {
+            for member in statistics_struct_members() {
+                parser_struct_init_function.add_child(AstNodeType::RawCode(RawCode::from(
+                    format!("aParserState->{0}.{1} = 0;", STATISTICS_MEMBER_NAME, member.name).as_str(),
+                )));
+            }
+        }
+
+        for field in message.fields.iter().filter(|field| field.length_of().is_some()) {
+            parser_struct_init_function.add_child(AstNodeType::LengthAction(LengthAction {
+                kind: LengthActionKind::Reset,
+                message_name: message.name.clone(),
+                field_name: field.name.clone(),
+                length: None,
             }));
         }
 
-        let mut parser_struct_init_function = self.add_child(AstNodeType::ParserStateInitFunction(
-            ParserStateInitFunction {
-                machine_name: message.name.clone(),
-            },
-        ));
+        for field in &message.fields {
+            if let Some(checksum) = field.checksum() {
+                parser_struct_init_function.add_child(AstNodeType::ChecksumAction(ChecksumAction {
+                    kind: ChecksumActionKind::Reset,
+                    message_name: message.name.clone(),
+                    field_name: field.name.clone(),
+                    checksum_name: checksum.name.clone(),
+                    algorithm: checksum.algorithm.clone(),
+                }));
+            }
+        }
 
-        parser_struct_init_function.add_child(AstNodeType::RawCode(RawCode::from("%% write init;")));
+        parser_struct_init_function.add_child(AstNodeType::RawCode(RawCode::from(
+            format!("aParserState->{0} = {1};", PARSE_ERROR_MEMBER_NAME, ParseError::None.code())
+                .as_str(),
+        )));
 
-        let mut machine_definition_node =
+        match ragel_options.write_init_nocs {
+            true => {
+                parser_struct_init_function.add_child(AstNodeType::RawCode(RawCode::from("%% write init nocs;")));
+                parser_struct_init_function.add_child(AstNodeType::RawCode(RawCode::from(
+                    format!("aParserState->cs = {0}_start;", message.name).as_str(),
+                )));
+            }
+            false => {
+                parser_struct_init_function.add_child(AstNodeType::RawCode(RawCode::from("%% write init;")));
+            }
+        }
+
+        // Covered fields feed each of their bytes into the checksums and the
+        // length counters. Lengths are checked once the later of the length
+        // field and the covered fields is complete
+        let checksum_coverage = checksum_coverage(message);
+        let length_coverage = length_coverage(message);
+        let field_machine = |field: &bpir::representation::Field| {
+            let field_index = message
+                .fields
+                .iter()
+                .position(|f| std::ptr::eq(f, field))
+                .unwrap_or(0usize);
+            let hooks = checksum_coverage[field_index]
+                .iter()
+                .map(|name| format!("${0}", checksum_hook_name(name)))
+                .chain(
+                    length_coverage[field_index]
+                        .iter()
+                        .map(|name| format!("${0}", LengthActionKind::Count.hook_name(name))),
+                )
+                .chain(
+                    message
+                        .fields
+                        .iter()
+                        .filter(|f| length_check_position(message, f) == Some(field_index))
+                        .map(|f| format!("@{0}", LengthActionKind::Check.hook_name(&f.name))),
+                )
+                .collect::<std::vec::Vec<String>>();
+
+            match hooks.as_slice() {
+                [] => field.name.clone(),
+                hooks => format!("( {0} {1} )", field.name, hooks.join(" ")),
+            }
+        };
+        let machine_definition_node =
             self.add_child(AstNodeType::MachineDefinition(MachineDefinition {
                 machine_name: message.name.clone(),
-                fields: message.fields.iter().map(|f| f.name.clone()).collect(),
+                fields: message
+                    .field_regions()
+                    .iter()
+                    .map(|region| match region {
+                        [field] => field_machine(field),
+                        alternatives => format!(
+                            "( {0} )",
+                            alternatives
+                                .iter()
+                                .map(field_machine)
+                                .collect::<std::vec::Vec<String>>()
+                                .join(" | ")
+                        ),
+                    })
+                    .collect(),
+                field_order: message.field_order(),
+                sync_sequence: message.sync_sequence().map(<[u8]>::to_vec),
+                acceptance_hook: Some(MESSAGE_PARSED_HOOK_NAME.to_string()).filter(|_| protocol.callbacks()),
+                discard_counting: protocol.discard_counting(message),
             }));
         machine_definition_node.add_child(AstNodeType::AccessSequence);
+        machine_definition_node.add_child(AstNodeType::AlphTypeSequence);
 
-        for field in &message.fields {
-            machine_definition_node.add_machine_action_hook(field);
+        if let Some(symbol_decoding) = protocol.symbol_decoding() {
+            if symbol_decoding.is_bytewise() {
+                machine_definition_node
+                    .add_child(AstNodeType::SymbolDecodingGetKey(symbol_decoding.clone()));
+            }
+        }
+
+        if protocol.callbacks() {
+            machine_definition_node
+                .add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                    name: MESSAGE_PARSED_HOOK_NAME.to_string(),
+                }))
+                .add_child(AstNodeType::ParsedCallback(ParsedCallback {
+                    message_name: message.name.clone(),
+                    misra_c: protocol.misra_c(),
+                }));
+        }
+
+        if let (true, Some(sync_sequence)) = (protocol.discard_counting(message), message.sync_sequence()) {
+            let discarded = format!("aParserState->{0}", DISCARDED_LENGTH_MEMBER_NAME);
+            let pending = format!("aParserState->{0}", PENDING_LENGTH_MEMBER_NAME);
+
+            // The last byte of the sync sequence is not counted as hunted
+            for (name, lines) in [
+                (HUNTED_BYTE_HOOK_NAME, vec![format!("{0}++;", pending)]),
+                (
+                    SYNCHRONIZED_HOOK_NAME,
+                    vec![
+                        format!("{0} += {1} - {2}u;", discarded, pending, sync_sequence.len() - 1),
+                        format!("{0} = {1}u;", pending, sync_sequence.len()),
+                    ],
+                ),
+                (FRAMED_BYTE_HOOK_NAME, vec![format!("{0}++;", pending)]),
+                (FRAME_SETTLED_HOOK_NAME, vec![format!("{0} = 0;", pending)]),
+            ] {
+                let action_hook = machine_definition_node.add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                    name: name.to_string(),
+                }));
+
+                for line in lines {
+                    action_hook.add_child(AstNodeType::RawCode(RawCode::from(line.as_str())));
+                }
+            }
+        }
+
+        if message.field_order() == FieldOrder::Unordered {
+            machine_definition_node
+                .add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                    name: PRESENCE_BITMAP_RESET_HOOK_NAME.to_string(),
+                }))
+                .add_child(AstNodeType::PresenceBitmapReset(PresenceBitmapReset {
+                    message_name: message.name.clone(),
+                }));
+        }
+
+        for (field_index, field) in message.fields.iter().enumerate() {
+            let first_child = machine_definition_node.children.len();
+            let action_hook = machine_definition_node.add_machine_action_hook(field);
+
+            if let FieldType::Varint(ref varint) = field.field_type {
+                action_hook.add_child(AstNodeType::VarintAction(VarintAction {
+                    kind: VarintActionKind::Decode,
+                    message_name: message.name.clone(),
+                    field_name: field.name.clone(),
+                    field_base_type: FieldBaseType::from_field(&message.name, field),
+                    varint: varint.clone(),
+                    container: FieldContainer::from_field(field),
+                }));
+            }
+
+            if message.field_order() == FieldOrder::Unordered {
+                action_hook.add_child(AstNodeType::PresenceBitmapUpdate(PresenceBitmapUpdate {
+                    message_name: message.name.clone(),
+                    field_name: field.name.clone(),
+                    field_index,
+                }));
+            }
+
+            if let Some(range_check) = RangeCheck::from_field(message, field) {
+                action_hook.add_child(AstNodeType::RangeCheck(range_check));
+            }
+
+            for check in field.checks() {
+                action_hook.add_child(AstNodeType::ExpressionCheck(ExpressionCheck {
+                    check: MessageExpression::new(message, check),
+                    layout: message.layout(),
+                }));
+            }
+
+            if let Some(checksum) = field.checksum() {
+                action_hook.add_child(AstNodeType::ChecksumAction(ChecksumAction {
+                    kind: ChecksumActionKind::Check,
+                    message_name: message.name.clone(),
+                    field_name: field.name.clone(),
+                    checksum_name: checksum.name.clone(),
+                    algorithm: checksum.algorithm.clone(),
+                }));
+                machine_definition_node
+                    .add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                        name: checksum_hook_name(&checksum.name),
+                    }))
+                    .add_child(AstNodeType::ChecksumAction(ChecksumAction {
+                        kind: ChecksumActionKind::Update,
+                        message_name: message.name.clone(),
+                        field_name: field.name.clone(),
+                        checksum_name: checksum.name.clone(),
+                        algorithm: checksum.algorithm.clone(),
+                    }));
+            }
+
+            if let Some(length_of) = field.length_of() {
+                for kind in [LengthActionKind::Count, LengthActionKind::Check] {
+                    machine_definition_node
+                        .add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                            name: kind.hook_name(&field.name),
+                        }))
+                        .add_child(AstNodeType::LengthAction(LengthAction {
+                            kind,
+                            message_name: message.name.clone(),
+                            field_name: field.name.clone(),
+                            length: length_of
+                                .length
+                                .as_ref()
+                                .filter(|_| kind == LengthActionKind::Check)
+                                .map(|length| MessageExpression::new(message, length)),
+                        }));
+                }
+            }
+
+            if let Some(conditional_on) = field.conditional_on() {
+                let condition =
+                    bpir::expression::Expression::field_equals(&conditional_on.field, conditional_on.value);
+                machine_definition_node
+                    .add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                        name: condition_hook_name(&field.name),
+                    }))
+                    .add_child(AstNodeType::ConditionCheck(ConditionCheck {
+                        condition: MessageExpression::new(message, &condition),
+                    }));
+            }
+
+            match field.field_type {
+                FieldType::Integer(_) | FieldType::Flags(_) | FieldType::Timestamp(_) => {
+                    let integer = field.integer_value_type().unwrap();
+                    machine_definition_node
+                        .add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                            name: field_byte_hook_name(&field.name),
+                        }))
+                        .add_child(AstNodeType::IntegerFieldByte(IntegerFieldByte {
+                            message_name: message.name.clone(),
+                            field_name: field.name.clone(),
+                            field_base_type: FieldBaseType::from_field(&message.name, field),
+                            bit_width: integer.bit_width,
+                            signed: integer.signed,
+                            container: FieldContainer::from_field(field),
+                        }));
+                }
+                FieldType::Varint(ref varint) => {
+                    for kind in [VarintActionKind::Reset, VarintActionKind::Byte] {
+                        machine_definition_node
+                            .add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                                name: kind.hook_name(&field.name),
+                            }))
+                            .add_child(AstNodeType::VarintAction(VarintAction {
+                                kind,
+                                message_name: message.name.clone(),
+                                field_name: field.name.clone(),
+                                field_base_type: FieldBaseType::from_field(&message.name, field),
+                                varint: varint.clone(),
+                                container: FieldContainer::from_field(field),
+                            }));
+                    }
+                }
+                FieldType::Tlv(ref tlv) => {
+                    machine_definition_node.add_tlv_action_hooks(message, field, tlv);
+                }
+                FieldType::Group(ref group) => {
+                    machine_definition_node.add_group_action_hooks(message, field, group);
+                }
+                FieldType::Reserved(ref reserved) if reserved.debug_member => {
+                    machine_definition_node
+                        .add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                            name: field_byte_hook_name(&field.name),
+                        }))
+                        .add_child(AstNodeType::ReservedFieldByte(ReservedFieldByte {
+                            message_name: message.name.clone(),
+                            field_name: field.name.clone(),
+                            length: reserved.length,
+                        }));
+                }
+                FieldType::Regex(_) => {
+                    for kind in [RegexActionKind::Reset, RegexActionKind::Byte] {
+                        machine_definition_node
+                            .add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                                name: kind.hook_name(&field.name),
+                            }))
+                            .add_child(AstNodeType::RegexAction(RegexAction {
+                                kind,
+                                message_name: message.name.clone(),
+                                field_name: field.name.clone(),
+                                capacity: array_capacity(field),
+                                overflow: field.overflow_policy(),
+                                container: FieldContainer::from_field(field),
+                                zero_copy: protocol.zero_copy(message),
+                            }));
+                    }
+                }
+                FieldType::Reserved(_) => {}
+            }
+
+            machine_definition_node.assign_field_origin(first_child, message, field);
         }
 
         for field in &message.fields {
+            let first_child = machine_definition_node.children.len();
             machine_definition_node.add_machine_field_parser(field);
+            machine_definition_node.assign_field_origin(first_child, message, field);
+        }
+
+        for node in &mut machine_definition_node.children {
+            if let AstNodeType::MachineActionHook(ref action_hook) = node.ast_node_type {
+                let fragment = Fragment::ActionBody(action_hook.name.clone());
+
+                for subnode in &mut node.children {
+                    subnode.assign_fragment(&fragment);
+                }
+            }
         }
 
-        let mut parsing_function = self.add_child(AstNodeType::ParsingFunction(ParsingFunction {
+        self.add_child(AstNodeType::ParsingFunction(ParsingFunction {
             message_name: message.name.clone(),
+            symbol_naming: protocol.symbol_naming(),
+            symbol_decoding: protocol.symbol_decoding().cloned(),
+            byte_stuffing: protocol.byte_stuffing().cloned(),
+            max_frame_length: message
+                .fixed_length()
+                .map(|length| protocol.encoded_frame_length(message, length)),
+            trace_message_id: protocol.frame_trace().and_then(|_| {
+                protocol
+                    .messages
+                    .iter()
+                    .position(|m| std::ptr::eq(m, message))
+            }),
+            resynchronize: message.sync_sequence().is_some(),
+            discard_counting: protocol.discard_counting(message),
+            statistics: protocol.parser_statistics(message),
+            callbacks: protocol.callbacks(),
+            zero_copy: protocol.zero_copy(message),
+            mixed_declarations: protocol.c_standard().mixed_declarations(),
+            misra_c: protocol.misra_c(),
         }));
 
-        for field in &message.fields {}
+        if protocol.serializers() {
+            match Serializer::from_message(protocol, message) {
+                Ok(serializer) => {
+                    self.add_child(AstNodeType::SerializingFunction(serializer));
+                }
+                Err(reason) => log::warn!(
+                    "Could not compose frames of message \"{}\", it will not be serialized: {}",
+                    message.name,
+                    reason
+                ),
+            }
+        }
+    }
+
+    /// Adds the message's accessors, and the functions initializing, comparing
+    /// and dumping its struct. Its types are declared by the backend's header
+    fn add_accessors(
+        &mut self,
+        protocol: &bpir::representation::Protocol,
+        message: &bpir::representation::Message,
+    ) {
+        let symbol_naming = protocol.symbol_naming();
+
+        for accessor in ScaledValueAccessor::from_message(&symbol_naming, message) {
+            self.add_child(AstNodeType::ScaledValueAccessor(accessor));
+        }
+
+        for accessor in FlagsAccessor::from_message(&symbol_naming, message) {
+            self.add_child(AstNodeType::FlagsAccessor(accessor));
+        }
+
+        for accessor in TimestampAccessor::from_message(&symbol_naming, message) {
+            self.add_child(AstNodeType::TimestampAccessor(accessor));
+        }
+
+        if protocol.struct_functions() {
+            self.add_child(AstNodeType::StructFunctions(StructFunctions {
+                message: message.clone(),
+                symbol_naming: symbol_naming.clone(),
+                zero_copy: protocol.zero_copy(message),
+            }));
+        }
+
+        if protocol.debug_dump() {
+            if protocol.misra_c() {
+                log::warn!(
+                    "The dump function of message \"{}\" calls a variadic function, it is not written to the MISRA-C profile",
+                    message.name
+                );
+            }
+
+            self.add_child(AstNodeType::DumpFunction(DumpFunction {
+                message: message.clone(),
+                symbol_naming,
+                zero_copy: protocol.zero_copy(message),
+            }));
+        }
+    }
+
+    fn add_bitstream_message_parser(
+        &mut self,
+        protocol: &bpir::representation::Protocol,
+        message: &bpir::representation::Message,
+    ) {
+        self.add_accessors(protocol, message);
+
+        if protocol.misra_c() {
+            log::warn!(
+                "The parser of bit-granular message \"{}\" returns on each error, it is not written to the MISRA-C profile",
+                message.name
+            );
+        }
+
+        let parsing_function =
+            self.add_child(AstNodeType::BitstreamParsingFunction(BitstreamParsingFunction {
+                message_name: message.name.clone(),
+                symbol_naming: protocol.symbol_naming(),
+                bit_length: message.bit_length().unwrap_or(0usize),
+                fixed_length: message.fixed_length(),
+            }));
+
+        for field in &message.fields {
+            let first_child = parsing_function.children.len();
+
+            match field.field_type {
+                FieldType::Integer(_) | FieldType::Flags(_) | FieldType::Timestamp(_) => {
+                    let integer = field.integer_value_type().unwrap();
+                    parsing_function.add_child(AstNodeType::BitstreamFieldRead(
+                        BitstreamFieldRead {
+                            message_name: message.name.clone(),
+                            field_name: field.name.clone(),
+                            field_base_type: FieldBaseType::from_field(&message.name, field),
+                            bit_width: integer.bit_width,
+                            signed: integer.signed,
+                            bit_order: message.bit_order(),
+                        },
+                    ));
+
+                    if let Some(range_check) = RangeCheck::from_field(message, field) {
+                        parsing_function.add_child(AstNodeType::RangeCheck(range_check));
+                    }
+
+                    for check in field.checks() {
+                        parsing_function.add_child(AstNodeType::ExpressionCheck(ExpressionCheck {
+                            check: MessageExpression::new(message, check),
+                            layout: message.layout(),
+                        }));
+                    }
+                }
+                FieldType::Reserved(ref reserved) => {
+                    parsing_function.add_child(AstNodeType::BitstreamReservedField(
+                        BitstreamReservedField {
+                            message_name: message.name.clone(),
+                            field_name: field.name.clone(),
+                            length: reserved.length,
+                            debug_member: reserved.debug_member,
+                            bit_order: message.bit_order(),
+                        },
+                    ));
+                }
+                FieldType::Regex(_) | FieldType::Tlv(_) | FieldType::Group(_) | FieldType::Varint(_) => {}
+            }
+
+            parsing_function.assign_field_origin(first_child, message, field);
+        }
+    }
+
+    fn add_tlv_action_hooks(
+        &mut self,
+        message: &bpir::representation::Message,
+        field: &bpir::representation::Field,
+        tlv: &bpir::representation::TlvFieldType,
+    ) {
+        let variants = TlvRecordVariant::from_tlv(tlv);
+        let max_records = field
+            .max_length()
+            .unwrap_or_else(bpir::representation::MaxLengthFieldAttribute::get_default_value);
+
+        for kind in TlvActionKind::all() {
+            self.add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                name: kind.hook_name(&field.name),
+            }))
+            .add_child(AstNodeType::TlvAction(TlvAction {
+                kind,
+                message_name: message.name.clone(),
+                field_name: field.name.clone(),
+                max_records,
+                variants: variants.clone(),
+            }));
+        }
+    }
+
+    fn add_group_action_hooks(
+        &mut self,
+        message: &bpir::representation::Message,
+        field: &bpir::representation::Field,
+        group: &bpir::representation::GroupFieldType,
+    ) {
+        for kind in GroupActionKind::all() {
+            self.add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                name: kind.hook_name(&field.name),
+            }))
+            .add_child(AstNodeType::GroupAction(GroupAction {
+                kind,
+                field_name: field.name.clone(),
+            }));
+        }
+
+        for member in &group.fields {
+            if let FieldType::Integer(ref integer) = member.field_type {
+                self.add_child(AstNodeType::MachineActionHook(MachineActionHook {
+                    name: group_member_byte_hook_name(&field.name, &member.name),
+                }))
+                .add_child(AstNodeType::IntegerFieldByte(IntegerFieldByte {
+                    message_name: message.name.clone(),
+                    field_name: member.name.clone(),
+                    field_base_type: FieldBaseType::from_field(&message.name, member),
+                    bit_width: integer.bit_width,
+                    signed: integer.signed,
+                    container: FieldContainer::Group(field.name.clone()),
+                }));
+            }
+        }
     }
 
-    fn add_machine_action_hook(&mut self, field: &bpir::representation::Field) {
+    /// Adds an action hook for the field. Returns reference to the hook, so the
+    /// action's body can be populated
+    fn add_machine_action_hook(&mut self, field: &bpir::representation::Field) -> &mut AstNode {
         self.add_child(AstNodeType::MachineActionHook(MachineActionHook {
             name: field.name.clone(),
-        }));
+        }))
     }
 
     fn add_machine_field_parser(&mut self, field: &bpir::representation::Field) {
-        use std::fmt;
-
         match field.field_type {
             bpir::representation::FieldType::Regex(ref node) => {
                 self.add_regex_machine_field_parser(field, node)
             }
+            bpir::representation::FieldType::Integer(ref node) => {
+                self.add_child(AstNodeType::FixedLengthMachineField(FixedLengthMachineField {
+                    name: field.name.clone(),
+                    length: node.bit_width / 8usize,
+                    byte_action: Some(field_byte_hook_name(&field.name)),
+                    condition: field.conditional_on().map(|_| condition_hook_name(&field.name)),
+                }));
+            }
+            bpir::representation::FieldType::Flags(_) | bpir::representation::FieldType::Timestamp(_) => {
+                self.add_child(AstNodeType::FixedLengthMachineField(FixedLengthMachineField {
+                    name: field.name.clone(),
+                    length: field.max_encoded_length(),
+                    byte_action: Some(field_byte_hook_name(&field.name)),
+                    condition: None,
+                }));
+            }
+            bpir::representation::FieldType::Varint(ref node) => {
+                self.add_child(AstNodeType::VarintMachineField(VarintMachineField {
+                    name: field.name.clone(),
+                    max_length: node.max_length(),
+                    condition: field.conditional_on().map(|_| condition_hook_name(&field.name)),
+                }));
+            }
+            bpir::representation::FieldType::Tlv(ref node) => {
+                self.add_child(AstNodeType::TlvMachineField(TlvMachineField {
+                    name: field.name.clone(),
+                    tag_length: node.tag_length,
+                    length_length: node.length_length,
+                }));
+            }
+            bpir::representation::FieldType::Group(ref node) => {
+                self.add_child(AstNodeType::GroupMachineField(GroupMachineField {
+                    name: field.name.clone(),
+                    count: node.count,
+                    members: node
+                        .fields
+                        .iter()
+                        .map(|member| GroupMachineMember {
+                            length: member.max_encoded_length(),
+                            byte_action: match member.field_type {
+                                FieldType::Integer(_) => {
+                                    Some(group_member_byte_hook_name(&field.name, &member.name))
+                                }
+                                _ => None,
+                            },
+                        })
+                        .collect(),
+                }));
+            }
+            bpir::representation::FieldType::Reserved(ref node) => {
+                self.add_child(AstNodeType::FixedLengthMachineField(FixedLengthMachineField {
+                    name: field.name.clone(),
+                    length: node.length,
+                    byte_action: Some(field_byte_hook_name(&field.name))
+                        .filter(|_| node.debug_member),
+                    condition: field.conditional_on().map(|_| condition_hook_name(&field.name)),
+                }));
+            }
         }
-        // Get field type
     }
 
     fn add_regex_machine_field_parser(
@@ -453,8 +4012,9 @@ impl AstNode {
         regex: &bpir::representation::RegexFieldType,
     ) {
         self.add_child(AstNodeType::RegexMachineField(RegexMachineField {
-            string_sequence: regex.regex.clone(),
+            string_sequence: regex.pattern().ragel(),
             name: field.name.clone(),
+            capture: has_struct_member(field),
         }));
     }
 }
@@ -0,0 +1,663 @@
+//! C++ backend. The header wraps the C API of the `c` backend, so the parsers
+//! are generated, reviewed and compiled once, whatever language calls them:
+//!
+//! - the C enums have `enum class` counterparts, whose enumerators are
+//!   defined by the C ones, so the two never drift apart;
+//! - each byte-granular message gets a parser class owning its parser state;
+//! - buffers are passed as `std::byte` pointers and lengths, and as
+//!   `std::span` under C++20.
+//!
+//! Everything is declared in the namespace `CppOptions` name, and `inline`, so
+//! the header needs no source of its own.
+
+use crate::bpir::representation::{CppOptions, MessageLayout, Protocol, SymbolNaming};
+use crate::error::RobustoError;
+use crate::parser_generation::artifacts;
+use crate::parser_generation::ragel::c;
+use crate::parser_generation::ragel::common;
+use crate::utility::codegen::{self, CodeChunk, CodeGeneration, SubnodeAccess, TreeBasedCodeGeneration};
+use crate::utility::string::to_upper_snake_case;
+use std::string::String;
+use std::vec::Vec;
+
+/// Guards the parts of the header which take C++20
+const CPP20_CONDITION: &str = "#if __cplusplus >= 202002L";
+
+/// Name of the C++ fixed-width type holding an unsigned value of the given
+/// length, in bytes
+fn cpp_unsigned_type(length: usize) -> &'static str {
+    match length {
+        0..=1 => "std::uint8_t",
+        2 => "std::uint16_t",
+        3..=4 => "std::uint32_t",
+        _ => "std::uint64_t",
+    }
+}
+
+/// Lines guarded by `CPP20_CONDITION`
+fn cpp20_lines(lines: Vec<(usize, String)>) -> Vec<(usize, String)> {
+    let mut ret = vec![(0, CPP20_CONDITION.to_string())];
+    ret.extend(lines);
+    ret.push((0, "#endif".to_string()));
+
+    ret
+}
+
+/// Standard headers, and the C header the wrappers call into
+#[derive(Debug)]
+struct Includes {
+    c_header_name: String,
+}
+
+impl codegen::TreeBasedCodeGeneration for Includes {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut lines = vec![
+            (0, "#include <array>".to_string()),
+            (0, "#include <climits>".to_string()),
+            (0, "#include <cstddef>".to_string()),
+            (0, "#include <cstdint>".to_string()),
+        ];
+        lines.extend(cpp20_lines(vec![(0, "#include <span>".to_string())]));
+        lines.push((0, String::new()));
+        lines.push((0, format!("#include \"{0}\"", self.c_header_name)));
+
+        c::indented_lines(lines.into_iter(), code_generation_state.indent)
+            .into_iter()
+            .map(|code_chunk| code_chunk.with_fragment(Some(&codegen::Fragment::Includes)))
+            .collect()
+    }
+}
+
+/// Encloses its children. Their lines are not indented
+#[derive(Debug)]
+struct Namespace {
+    name: String,
+}
+
+impl codegen::TreeBasedCodeGeneration for Namespace {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let lines = [(0, String::new()), (0, format!("namespace {0} {{", self.name))];
+
+        c::indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let lines = [(0, String::new()), (0, format!("}}  // namespace {0}", self.name))];
+
+        c::indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Scoped counterparts of `RobustoParseStatus`, of the codes of the parser
+/// states' `error` member, and of the dispatched messages' IDs
+#[derive(Debug)]
+struct EnumClasses {
+    dispatcher: Option<common::Dispatcher>,
+}
+
+impl EnumClasses {
+    fn enum_lines(name: &str, underlying_type: &str, enumerators: Vec<(String, String)>) -> Vec<(usize, String)> {
+        let mut ret = vec![(0, String::new()), (0, format!("enum class {0} : {1} {{", name, underlying_type))];
+        ret.extend(
+            enumerators
+                .into_iter()
+                .map(|(enumerator, value)| (1, format!("{0} = {1},", enumerator, value))),
+        );
+        ret.push((0, "};".to_string()));
+
+        ret
+    }
+
+    fn lines(&self) -> Vec<(usize, String)> {
+        let mut ret = EnumClasses::enum_lines(
+            "ParseStatus",
+            "int",
+            common::ParseStatus::all()
+                .into_iter()
+                .map(|parse_status| (format!("{:?}", parse_status), c::c_parse_status_name(parse_status).to_string()))
+                .collect(),
+        );
+        ret.extend(EnumClasses::enum_lines(
+            "ParseError",
+            "std::uint8_t",
+            common::ParseError::all()
+                .into_iter()
+                .map(|parse_error| (format!("{:?}", parse_error), c::c_parse_error_name(parse_error).to_string()))
+                .collect(),
+        ));
+
+        if let Some(ref dispatcher) = self.dispatcher {
+            ret.extend(EnumClasses::enum_lines(
+                "MessageId",
+                cpp_unsigned_type(dispatcher.id_length),
+                dispatcher
+                    .messages
+                    .iter()
+                    .map(|message| {
+                        (
+                            message.message_name.clone(),
+                            c::c_message_id_macro_name(&message.message_name),
+                        )
+                    })
+                    .collect(),
+            ));
+        }
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for EnumClasses {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        c::indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Names the message struct in the namespace, along with a buffer fitting
+/// any of the message's frames
+#[derive(Debug)]
+struct MessageTypes {
+    message_name: String,
+    symbol_naming: SymbolNaming,
+}
+
+impl MessageTypes {
+    fn lines(&self) -> Vec<(usize, String)> {
+        vec![
+            (0, String::new()),
+            (
+                0,
+                format!(
+                    "using {0} = ::{1};",
+                    self.message_name,
+                    self.symbol_naming.message_struct(&self.message_name)
+                ),
+            ),
+            (
+                0,
+                format!(
+                    "using {0}Frame = std::array<std::byte, ROBUSTO_{1}_MAX_FRAME_SIZE>;",
+                    self.message_name,
+                    to_upper_snake_case(&self.message_name)
+                ),
+            ),
+        ]
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for MessageTypes {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        c::indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Parses frames of a byte-granular message, keeping the parser state in
+/// between calls. Constructing the parser initializes the state
+#[derive(Debug)]
+struct ParserClass {
+    message_name: String,
+    symbol_naming: SymbolNaming,
+    statistics: bool,
+}
+
+impl ParserClass {
+    fn lines(&self) -> Vec<(usize, String)> {
+        let class_name = format!("{0}Parser", self.message_name);
+        let mut ret = vec![
+            (0, String::new()),
+            (0, format!("class {0} {{", class_name)),
+            (0, "public:".to_string()),
+            (1, format!("{0}() noexcept {{", class_name)),
+            (2, "reset();".to_string()),
+            (1, "}".to_string()),
+            (1, "// Restarts the machine, discarding the frame parsed so far".to_string()),
+            (1, "void reset() noexcept {".to_string()),
+            (
+                2,
+                format!(
+                    "{0}(&mState);",
+                    self.symbol_naming.parser_state_init_function(&self.message_name)
+                ),
+            ),
+            (1, "}".to_string()),
+            (1, "// Tells how many bytes of the input the machine has taken through aConsumedLength".to_string()),
+            (
+                1,
+                format!(
+                    "ParseStatus parse(const std::byte *aInput, std::size_t aInputLength, {0} &aMessage, \
+                     std::size_t &aConsumedLength) noexcept {{",
+                    self.message_name
+                ),
+            ),
+            (2, "// Input past INT_MAX is left for the next call".to_string()),
+            (
+                2,
+                "const int inputLength = aInputLength > INT_MAX ? INT_MAX : static_cast<int>(aInputLength);"
+                    .to_string(),
+            ),
+            (2, "int consumedLength = 0;".to_string()),
+            (
+                2,
+                format!(
+                    "const auto status = {0}(&mState, reinterpret_cast<const char *>(aInput), inputLength, \
+                     &aMessage, &consumedLength);",
+                    self.symbol_naming.parsing_function(&self.message_name)
+                ),
+            ),
+            (2, "aConsumedLength = static_cast<std::size_t>(consumedLength);".to_string()),
+            (2, "return static_cast<ParseStatus>(status);".to_string()),
+            (1, "}".to_string()),
+        ];
+        ret.extend(cpp20_lines(vec![
+            (
+                1,
+                format!(
+                    "ParseStatus parse(std::span<const std::byte> aInput, {0} &aMessage, \
+                     std::size_t &aConsumedLength) noexcept {{",
+                    self.message_name
+                ),
+            ),
+            (2, "return parse(aInput.data(), aInput.size(), aMessage, aConsumedLength);".to_string()),
+            (1, "}".to_string()),
+        ]));
+        ret.extend([
+            (1, "bool isAccepted() const noexcept {".to_string()),
+            (
+                2,
+                format!(
+                    "return {0}(&mState) != 0;",
+                    self.symbol_naming.acceptance_function(&self.message_name)
+                ),
+            ),
+            (1, "}".to_string()),
+            (1, "// Why the last frame has been rejected".to_string()),
+            (1, "ParseError error() const noexcept {".to_string()),
+            (
+                2,
+                format!(
+                    "return static_cast<ParseError>(mState.{0});",
+                    common::PARSE_ERROR_MEMBER_NAME
+                ),
+            ),
+            (1, "}".to_string()),
+        ]);
+
+        if self.statistics {
+            ret.extend([
+                (
+                    1,
+                    format!("::{0} statistics() const noexcept {{", common::STATISTICS_STRUCT_NAME),
+                ),
+                (
+                    2,
+                    format!(
+                        "return {0}(&mState);",
+                        self.symbol_naming.statistics_function(&self.message_name)
+                    ),
+                ),
+                (1, "}".to_string()),
+            ]);
+        }
+
+        ret.extend([
+            (1, format!("const {0} &state() const noexcept {{", self.parser_state_struct())),
+            (2, "return mState;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (0, "private:".to_string()),
+            (1, format!("{0} mState;", self.parser_state_struct())),
+            (0, "};".to_string()),
+        ]);
+
+        ret
+    }
+
+    fn parser_state_struct(&self) -> String {
+        format!("::{0}", self.symbol_naming.parser_state_struct(&self.message_name))
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for ParserClass {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        c::indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Overloads of `serialize`, one per kind of buffer. Each returns the length
+/// of the frame written into the buffer, or -1, if the buffer may be too
+/// short for it
+#[derive(Debug)]
+struct SerializingFunctions {
+    message_name: String,
+    symbol_naming: SymbolNaming,
+}
+
+impl SerializingFunctions {
+    fn lines(&self) -> Vec<(usize, String)> {
+        let mut ret = vec![
+            (0, String::new()),
+            (
+                0,
+                format!(
+                    "inline int serialize(const {0} &aMessage, std::byte *aBuffer, std::size_t aCapacity) noexcept {{",
+                    self.message_name
+                ),
+            ),
+            (
+                1,
+                format!(
+                    "return {0}(&aMessage, reinterpret_cast<std::uint8_t *>(aBuffer), aCapacity);",
+                    self.symbol_naming.serializing_function(&self.message_name)
+                ),
+            ),
+            (0, "}".to_string()),
+            (
+                0,
+                format!(
+                    "inline int serialize(const {0} &aMessage, {0}Frame &aFrame) noexcept {{",
+                    self.message_name
+                ),
+            ),
+            (1, "return serialize(aMessage, aFrame.data(), aFrame.size());".to_string()),
+            (0, "}".to_string()),
+        ];
+        ret.extend(cpp20_lines(vec![
+            (
+                0,
+                format!(
+                    "inline int serialize(const {0} &aMessage, std::span<std::byte> aBuffer) noexcept {{",
+                    self.message_name
+                ),
+            ),
+            (1, "return serialize(aMessage, aBuffer.data(), aBuffer.size());".to_string()),
+            (0, "}".to_string()),
+        ]));
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for SerializingFunctions {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        c::indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Overloads of `parse` for a bit-granular message, whose frames are parsed
+/// whole. Each returns the number of consumed bytes, or a negative value on
+/// failure, as the C function does
+#[derive(Debug)]
+struct BitstreamParsingFunctions {
+    message_name: String,
+    symbol_naming: SymbolNaming,
+}
+
+impl BitstreamParsingFunctions {
+    fn lines(&self) -> Vec<(usize, String)> {
+        let mut ret = vec![
+            (0, String::new()),
+            (
+                0,
+                format!(
+                    "inline int parse(const std::byte *aInput, std::size_t aInputLength, {0} &aMessage) noexcept {{",
+                    self.message_name
+                ),
+            ),
+            (1, "// A frame longer than INT_MAX is longer than the message's length anyway".to_string()),
+            (
+                1,
+                "const int inputLength = aInputLength > INT_MAX ? INT_MAX : static_cast<int>(aInputLength);"
+                    .to_string(),
+            ),
+            (
+                1,
+                format!(
+                    "return {0}(reinterpret_cast<const std::uint8_t *>(aInput), inputLength, &aMessage);",
+                    self.symbol_naming.parsing_function(&self.message_name)
+                ),
+            ),
+            (0, "}".to_string()),
+        ];
+        ret.extend(cpp20_lines(vec![
+            (
+                0,
+                format!(
+                    "inline int parse(std::span<const std::byte> aInput, {0} &aMessage) noexcept {{",
+                    self.message_name
+                ),
+            ),
+            (1, "return parse(aInput.data(), aInput.size(), aMessage);".to_string()),
+            (0, "}".to_string()),
+        ]));
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for BitstreamParsingFunctions {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        c::indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+#[derive(Debug)]
+enum AstNodeType {
+    Root,
+    Includes(Includes),
+    Namespace(Namespace),
+    EnumClasses(EnumClasses),
+    MessageTypes(MessageTypes),
+    ParserClass(ParserClass),
+    SerializingFunctions(SerializingFunctions),
+    BitstreamParsingFunctions(BitstreamParsingFunctions),
+}
+
+#[derive(Debug)]
+struct AstNode {
+    ast_node_type: AstNodeType,
+    children: Vec<AstNode>,
+}
+
+impl AstNode {
+    fn new() -> AstNode {
+        AstNode {
+            ast_node_type: AstNodeType::Root,
+            children: Vec::new(),
+        }
+    }
+
+    fn add_child(&mut self, ast_node_type: AstNodeType) -> &mut AstNode {
+        self.children.push(AstNode {
+            ast_node_type,
+            children: Vec::new(),
+        });
+
+        self.children.last_mut().unwrap()
+    }
+}
+
+impl SubnodeAccess<AstNode> for AstNode {
+    fn iter(&self) -> std::slice::Iter<'_, AstNode> {
+        self.children.iter()
+    }
+}
+
+impl TreeBasedCodeGeneration for AstNode {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        self.ast_node_type
+            .generate_code_pre_traverse(code_generation_state)
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        self.ast_node_type
+            .generate_code_post_traverse(code_generation_state)
+    }
+}
+
+impl TreeBasedCodeGeneration for AstNodeType {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        match self {
+            AstNodeType::Root => Vec::new(),
+            AstNodeType::Includes(ref node) => node.generate_code_pre_traverse(code_generation_state),
+            AstNodeType::Namespace(ref node) => node.generate_code_pre_traverse(code_generation_state),
+            AstNodeType::EnumClasses(ref node) => node.generate_code_pre_traverse(code_generation_state),
+            AstNodeType::MessageTypes(ref node) => node.generate_code_pre_traverse(code_generation_state),
+            AstNodeType::ParserClass(ref node) => node.generate_code_pre_traverse(code_generation_state),
+            AstNodeType::SerializingFunctions(ref node) => node.generate_code_pre_traverse(code_generation_state),
+            AstNodeType::BitstreamParsingFunctions(ref node) => {
+                node.generate_code_pre_traverse(code_generation_state)
+            }
+        }
+    }
+
+    fn generate_code_post_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        match self {
+            AstNodeType::Namespace(ref node) => node.generate_code_post_traverse(code_generation_state),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// AST tree for generating the C++ header
+pub struct CppHeaderAstNode {
+    ast_node: AstNode,
+    guard: c::HeaderGuard,
+}
+
+impl CppHeaderAstNode {
+    /// Builds the header wrapping the C header of the given name, which
+    /// itself is guarded as a file of the given name
+    pub fn from_protocol(
+        protocol: &Protocol,
+        cpp_options: &CppOptions,
+        file_name: &str,
+        c_header_name: &str,
+    ) -> CppHeaderAstNode {
+        let mut ret = AstNode::new();
+        let symbol_naming = protocol.symbol_naming();
+
+        ret.add_child(AstNodeType::Includes(Includes {
+            c_header_name: c_header_name.to_string(),
+        }));
+        let namespace = ret.add_child(AstNodeType::Namespace(Namespace {
+            name: cpp_options.namespace.clone(),
+        }));
+        namespace.add_child(AstNodeType::EnumClasses(EnumClasses {
+            dispatcher: common::Dispatcher::from_protocol(protocol),
+        }));
+
+        for message in &protocol.messages {
+            namespace.add_child(AstNodeType::MessageTypes(MessageTypes {
+                message_name: message.name.clone(),
+                symbol_naming: symbol_naming.clone(),
+            }));
+
+            match message.layout() {
+                MessageLayout::Bytes => {
+                    namespace.add_child(AstNodeType::ParserClass(ParserClass {
+                        message_name: message.name.clone(),
+                        symbol_naming: symbol_naming.clone(),
+                        statistics: protocol.parser_statistics(message),
+                    }));
+
+                    if protocol.serializers() && common::Serializer::from_message(protocol, message).is_ok() {
+                        namespace.add_child(AstNodeType::SerializingFunctions(SerializingFunctions {
+                            message_name: message.name.clone(),
+                            symbol_naming: symbol_naming.clone(),
+                        }));
+                    }
+                }
+                MessageLayout::Bits => {
+                    namespace.add_child(AstNodeType::BitstreamParsingFunctions(BitstreamParsingFunctions {
+                        message_name: message.name.clone(),
+                        symbol_naming: symbol_naming.clone(),
+                    }));
+                }
+            }
+        }
+
+        CppHeaderAstNode {
+            ast_node: ret,
+            guard: c::HeaderGuard::new(&protocol.include_guard(), file_name, true),
+        }
+    }
+}
+
+impl CodeGeneration for CppHeaderAstNode {
+    fn generate_code(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::new();
+
+        // Collecting into a buffer never fails
+        let _ = self.generate_code_into(code_generation_state, &mut ret);
+
+        ret
+    }
+
+    fn generate_code_into(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+        sink: &mut dyn codegen::CodeChunkSink,
+    ) -> Result<(), RobustoError> {
+        self.guard.open(sink)?;
+        self.ast_node.generate_code_into(code_generation_state, sink)?;
+        self.guard.close(sink)
+    }
+}
+
+/// Renders the C++ header into the set, if the protocol asks for one
+pub fn add_artifacts(
+    protocol: &Protocol,
+    naming: &artifacts::ArtifactNaming,
+    settings: &artifacts::ArtifactSettings,
+    artifact_set: &mut artifacts::GeneratedArtifactSet,
+) -> Result<(), RobustoError> {
+    let Some(cpp_options) = protocol.cpp() else {
+        return Ok(());
+    };
+
+    artifact_set.add_code(
+        &naming.cpp_header(),
+        artifacts::ArtifactKind::CppHeader,
+        &CppHeaderAstNode::from_protocol(protocol, cpp_options, &naming.cpp_header(), &naming.header()),
+        settings,
+    )
+}
@@ -1,2 +1,3 @@
 pub mod c;
 pub mod common;
+pub mod cpp;
@@ -0,0 +1,157 @@
+//! Runtime library: the helpers which do not depend on a protocol, generated
+//! once for every protocol of a project. Firmware including the parsers of
+//! several protocols then links one copy of each CRC routine, rather than one
+//! per protocol.
+//!
+//! The library is versioned. Code generated for a protocol relying on it
+//! refuses to compile against a library of another version.
+
+use crate::bpir::representation::{CStandard, ChecksumImplementation, CrcAlgorithm, Protocol};
+use crate::error::RobustoError;
+use crate::parser_generation::artifacts::{ArtifactKind, ArtifactSettings, GeneratedArtifactSet};
+use crate::parser_generation::ragel::c;
+use crate::utility::codegen::{CodeChunk, CodeGeneration, CodeGenerationState, TreeBasedCodeGeneration};
+use std::vec::Vec;
+
+/// Version of the library's interface. Bumped whenever a helper changes in
+/// a way the generated code depends on
+pub const VERSION: u32 = 1;
+
+/// The macro the library's header defines to its version
+pub const VERSION_MACRO_NAME: &str = "ROBUSTO_RT_VERSION";
+
+pub const HEADER_FILE_NAME: &str = "robusto_rt.h";
+pub const SOURCE_FILE_NAME: &str = "robusto_rt.c";
+
+/// Helpers the protocols relying on the library employ
+#[derive(Debug)]
+pub struct RuntimeLibrary {
+    pub crc_algorithms: Vec<CrcAlgorithm>,
+
+    /// Tables, unless every protocol opts for bitwise routines
+    pub implementation: ChecksumImplementation,
+}
+
+impl RuntimeLibrary {
+    /// Collects the helpers of the protocols carrying the "runtime" attribute.
+    /// The rest of the protocols generate their own helpers
+    pub fn from_protocols(protocols: &[&Protocol]) -> RuntimeLibrary {
+        let protocols = protocols
+            .iter()
+            .filter(|protocol| protocol.uses_runtime())
+            .collect::<Vec<_>>();
+        let mut crc_algorithms: Vec<CrcAlgorithm> = Vec::new();
+
+        for algorithm in protocols.iter().flat_map(|protocol| protocol.crc_algorithms()) {
+            if !crc_algorithms.iter().any(|other| other.shares_routine(algorithm)) {
+                crc_algorithms.push(algorithm.clone());
+            }
+        }
+
+        let implementation = match protocols
+            .iter()
+            .all(|protocol| protocol.checksum_implementation() == ChecksumImplementation::Bitwise)
+        {
+            true if !protocols.is_empty() => ChecksumImplementation::Bitwise,
+            _ => ChecksumImplementation::Table,
+        };
+
+        RuntimeLibrary {
+            crc_algorithms,
+            implementation,
+        }
+    }
+}
+
+/// Declarations of the library's helpers
+pub struct RuntimeHeader<'a> {
+    pub library: &'a RuntimeLibrary,
+}
+
+impl CodeGeneration for RuntimeHeader<'_> {
+    fn generate_code(&self, code_generation_state: &mut CodeGenerationState) -> Vec<CodeChunk> {
+        let indent = code_generation_state.indent;
+        let mut ret = Vec::new();
+        ret.push(CodeChunk::new("#ifndef ROBUSTO_RT_H".to_string(), indent, 1));
+        ret.push(CodeChunk::new("#define ROBUSTO_RT_H".to_string(), indent, 2));
+        ret.push(CodeChunk::new("#include <stdint.h>".to_string(), indent, 2));
+        ret.push(CodeChunk::new(
+            format!("#define {0} {1}", VERSION_MACRO_NAME, VERSION),
+            indent,
+            1,
+        ));
+
+        let linkage = c::CppLinkage {};
+        ret.append(&mut linkage.generate_code_pre_traverse(code_generation_state));
+        ret.push(CodeChunk::new(String::new(), indent, 1));
+
+        for algorithm in &self.library.crc_algorithms {
+            ret.push(CodeChunk::new(
+                format!("{0};", c::ApiFunction::crc_update(algorithm).signature()),
+                indent,
+                1,
+            ));
+        }
+
+        ret.append(&mut linkage.generate_code_post_traverse(code_generation_state));
+        ret.push(CodeChunk::new(String::new(), indent, 1));
+        ret.push(CodeChunk::new("#endif  // ROBUSTO_RT_H".to_string(), indent, 1));
+
+        ret
+    }
+}
+
+/// Definitions of the library's helpers
+pub struct RuntimeSource<'a> {
+    pub library: &'a RuntimeLibrary,
+}
+
+impl CodeGeneration for RuntimeSource<'_> {
+    fn generate_code(&self, code_generation_state: &mut CodeGenerationState) -> Vec<CodeChunk> {
+        let indent = code_generation_state.indent;
+        let mut ret = Vec::new();
+        ret.push(CodeChunk::new(format!("#include \"{0}\"", HEADER_FILE_NAME), indent, 2));
+
+        let routines = c::ChecksumRoutines {
+            algorithms: self.library.crc_algorithms.clone(),
+            implementation: self.library.implementation,
+        };
+        ret.append(&mut routines.generate_code_pre_traverse(code_generation_state));
+
+        ret
+    }
+}
+
+/// Renders the library into the set, if any of the protocols relies on it
+pub fn add_artifacts(
+    protocols: &[&Protocol],
+    settings: &ArtifactSettings,
+    artifact_set: &mut GeneratedArtifactSet,
+) -> Result<(), RobustoError> {
+    if !protocols.iter().any(|protocol| protocol.uses_runtime()) {
+        return Ok(());
+    }
+
+    let library = RuntimeLibrary::from_protocols(protocols);
+
+    // Every protocol's toolchain builds the library
+    let c_standard = protocols
+        .iter()
+        .map(|protocol| protocol.c_standard())
+        .min()
+        .unwrap_or(CStandard::C99);
+    artifact_set.add_c_code(
+        HEADER_FILE_NAME,
+        ArtifactKind::Header,
+        &RuntimeHeader { library: &library },
+        settings,
+        c_standard,
+    )?;
+    artifact_set.add_c_code(
+        SOURCE_FILE_NAME,
+        ArtifactKind::Source,
+        &RuntimeSource { library: &library },
+        settings,
+        c_standard,
+    )
+}
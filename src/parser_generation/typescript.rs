@@ -0,0 +1,1179 @@
+//! TypeScript backend, for tooling running in browsers and Node.js, e.g.
+//! dashboards reading devices over WebSerial. Frames are parsed and
+//! composed along the plan the C serializers follow, see
+//! `common::Serializer`, so both sides agree on the wire format:
+//!
+//! - each message gets an interface, named as its C struct is, whose members
+//!   are the fields the frame carries values of. Constants, lengths and
+//!   checksums are derived, and left out;
+//! - parsing functions take the first frame off a `Uint8Array`, skipping the
+//!   input preceding the sync sequence, if any, and report the outcome with
+//!   the statuses of the C parsers;
+//! - serializing functions return the frame as a `Uint8Array`.
+//!
+//! Values of 64-bit fields are `bigint`s, the rest `number`s.
+
+use crate::bpir::representation::{Field, FieldType, Message, OverflowPolicy, Protocol, SymbolNaming};
+use crate::error::RobustoError;
+use crate::parser_generation::artifacts::{ArtifactKind, ArtifactNaming, ArtifactSettings, GeneratedArtifactSet};
+use crate::parser_generation::ragel::c::indented_lines;
+use crate::parser_generation::ragel::common::{self, FieldBaseType, SerializerStep};
+use crate::utility::codegen::{self, CodeChunk, CodeGeneration, SubnodeAccess, TreeBasedCodeGeneration};
+use log;
+use std::string::String;
+use std::vec::Vec;
+
+/// Whether values of the type are `bigint`s
+fn is_bigint(field_base_type: &FieldBaseType) -> bool {
+    matches!(field_base_type, FieldBaseType::I64 | FieldBaseType::U64)
+}
+
+fn ts_type_name(field_base_type: &FieldBaseType) -> &'static str {
+    match is_bigint(field_base_type) {
+        true => "bigint",
+        false => "number",
+    }
+}
+
+fn ts_literal(value: i64, field_base_type: &FieldBaseType) -> String {
+    match is_bigint(field_base_type) {
+        true => format!("{0}n", value),
+        false => value.to_string(),
+    }
+}
+
+/// Converts a `bigint` expression into a value of the type
+fn from_bigint(expression: String, field_base_type: &FieldBaseType) -> String {
+    match is_bigint(field_base_type) {
+        true => expression,
+        false => format!("Number({0})", expression),
+    }
+}
+
+/// Converts a value of the type into a `bigint` expression
+fn to_bigint(expression: String, field_base_type: &FieldBaseType) -> String {
+    match is_bigint(field_base_type) {
+        true => expression,
+        false => format!("BigInt({0})", expression),
+    }
+}
+
+fn byte_array_literal(bytes: &[u8]) -> String {
+    format!(
+        "[{0}]",
+        bytes
+            .iter()
+            .map(|byte| format!("0x{0:02X}", byte))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
+}
+
+fn doc_lines(doc: &str) -> Vec<(usize, String)> {
+    match doc.lines().count() {
+        0..=1 => vec![(0, format!("/** {0} */", doc.trim()))],
+        _ => {
+            let mut ret = vec![(0, "/**".to_string())];
+            ret.extend(doc.lines().map(|line| (0, format!(" * {0}", line.trim_end()).trim_end().to_string())));
+            ret.push((0, " */".to_string()));
+
+            ret
+        }
+    }
+}
+
+/// Why the module leaves the message out, if it does
+fn unsupported_reason(protocol: &Protocol, message: &Message) -> Option<String> {
+    if let Err(reason) = common::Serializer::from_message(protocol, message) {
+        return Some(reason);
+    }
+
+    message
+        .fields
+        .iter()
+        .flat_map(|field| match field.field_type {
+            FieldType::Group(ref group) => group.fields.iter().collect::<Vec<&Field>>(),
+            _ => vec![field],
+        })
+        .find(|field| !field.checks().is_empty())
+        .map(|field| format!("field {0} carries checks", field.name))
+}
+
+/// The statuses parsing reports, and the classes reading and writing frames
+#[derive(Debug)]
+struct Runtime {}
+
+impl Runtime {
+    fn lines(&self) -> Vec<(usize, String)> {
+        let mut ret = vec![
+            (0, "/** Outcome of parsing a frame, as the C parsers report it */".to_string()),
+            (0, "export enum RobustoParseStatus {".to_string()),
+        ];
+
+        for parse_status in common::ParseStatus::all() {
+            ret.extend(
+                doc_lines(parse_status.description())
+                    .into_iter()
+                    .map(|(indent, line)| (indent + 1, line)),
+            );
+            ret.push((1, format!("{0:?} = {1},", parse_status, parse_status.code())));
+        }
+
+        ret.extend([
+            (0, "}".to_string()),
+            (0, String::new()),
+            (0, "export interface RobustoParseResult<T> {".to_string()),
+            (1, "status: RobustoParseStatus;".to_string()),
+            (0, String::new()),
+            (1, "/** The message, if the status is `Ok` */".to_string()),
+            (1, "message?: T;".to_string()),
+            (0, String::new()),
+            (1, "/** Bytes of the input up to the end of the frame, if the status is `Ok` */".to_string()),
+            (1, "length: number;".to_string()),
+            (0, "}".to_string()),
+            (0, String::new()),
+            (0, "/** Ends parsing with the status */".to_string()),
+            (0, "class RobustoParseFailure {".to_string()),
+            (1, "readonly status: RobustoParseStatus;".to_string()),
+            (0, String::new()),
+            (1, "constructor(status: RobustoParseStatus) {".to_string()),
+            (2, "this.status = status;".to_string()),
+            (1, "}".to_string()),
+            (0, "}".to_string()),
+            (0, String::new()),
+            (0, "/** CRC of the bytes, computed as the C routines compute it */".to_string()),
+            (0, "function robustoCrc(".to_string()),
+            (1, "data: ArrayLike<number>,".to_string()),
+            (1, "start: number,".to_string()),
+            (1, "end: number,".to_string()),
+            (1, "width: number,".to_string()),
+            (1, "polynomial: bigint,".to_string()),
+            (1, "init: bigint,".to_string()),
+            (1, "reflected: boolean,".to_string()),
+            (1, "xorOut: bigint,".to_string()),
+            (0, "): bigint {".to_string()),
+            (1, "let crc = init;".to_string()),
+            (1, "let reflectedPolynomial = 0n;".to_string()),
+            (0, String::new()),
+            (1, "for (let bit = 0; bit < width; ++bit) {".to_string()),
+            (2, "reflectedPolynomial |= ((polynomial >> BigInt(bit)) & 1n) << BigInt(width - 1 - bit);".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "for (let i = start; i < end; ++i) {".to_string()),
+            (2, "if (reflected) {".to_string()),
+            (3, "crc ^= BigInt(data[i]);".to_string()),
+            (0, String::new()),
+            (3, "for (let bit = 0; bit < 8; ++bit) {".to_string()),
+            (4, "crc = (crc & 1n) !== 0n ? (crc >> 1n) ^ reflectedPolynomial : crc >> 1n;".to_string()),
+            (3, "}".to_string()),
+            (2, "} else {".to_string()),
+            (3, "crc ^= BigInt(data[i]) << BigInt(width - 8);".to_string()),
+            (0, String::new()),
+            (3, "for (let bit = 0; bit < 8; ++bit) {".to_string()),
+            (4, "const msb = crc >> BigInt(width - 1);".to_string()),
+            (4, "crc = BigInt.asUintN(width, msb !== 0n ? (crc << 1n) ^ polynomial : crc << 1n);".to_string()),
+            (3, "}".to_string()),
+            (2, "}".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "return crc ^ xorOut;".to_string()),
+            (0, "}".to_string()),
+            (0, String::new()),
+            (0, "/** Zigzag encoding of a signed varint's value */".to_string()),
+            (0, "function robustoZigzag(value: bigint): bigint {".to_string()),
+            (1, "return value < 0n ? ~(value << 1n) : value << 1n;".to_string()),
+            (0, "}".to_string()),
+            (0, String::new()),
+            (0, "/** Reads the fields of a frame off the input, throwing `RobustoParseFailure` */".to_string()),
+            (0, "class RobustoFrameReader {".to_string()),
+            (1, "readonly input: Uint8Array;".to_string()),
+            (1, "offset = 0;".to_string()),
+            (0, String::new()),
+            (1, "/** The input as Latin-1 characters, which regex fields are matched against */".to_string()),
+            (1, "private text?: string;".to_string()),
+            (0, String::new()),
+            (1, "constructor(input: Uint8Array) {".to_string()),
+            (2, "this.input = input;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "/** Skips the input preceding the first occurrence of the sequence */".to_string()),
+            (1, "synchronize(sequence: number[]): void {".to_string()),
+            (2, "for (let start = 0; start + sequence.length <= this.input.length; ++start) {".to_string()),
+            (3, "if (sequence.every((byte, i) => this.input[start + i] === byte)) {".to_string()),
+            (4, "this.offset = start;".to_string()),
+            (4, "return;".to_string()),
+            (3, "}".to_string()),
+            (2, "}".to_string()),
+            (0, String::new()),
+            (2, "throw new RobustoParseFailure(RobustoParseStatus.Incomplete);".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "/** Takes the bytes, returning the offset of the first one */".to_string()),
+            (1, "skip(length: number): number {".to_string()),
+            (2, "if (this.offset + length > this.input.length) {".to_string()),
+            (3, "throw new RobustoParseFailure(RobustoParseStatus.Incomplete);".to_string()),
+            (2, "}".to_string()),
+            (0, String::new()),
+            (2, "this.offset += length;".to_string()),
+            (0, String::new()),
+            (2, "return this.offset - length;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "constant(bytes: number[]): void {".to_string()),
+            (2, "const start = this.skip(bytes.length);".to_string()),
+            (0, String::new()),
+            (2, "if (!bytes.every((byte, i) => this.input[start + i] === byte)) {".to_string()),
+            (3, "throw new RobustoParseFailure(RobustoParseStatus.Error);".to_string()),
+            (2, "}".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "/** Reads a big-endian unsigned integer */".to_string()),
+            (1, "integer(length: number, offset = this.skip(length)): bigint {".to_string()),
+            (2, "let value = 0n;".to_string()),
+            (0, String::new()),
+            (2, "for (let i = 0; i < length; ++i) {".to_string()),
+            (3, "value = (value << 8n) | BigInt(this.input[offset + i]);".to_string()),
+            (2, "}".to_string()),
+            (0, String::new()),
+            (2, "return value;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "/** Reads a LEB128 varint of at most the given length */".to_string()),
+            (1, "varint(maxLength: number): bigint {".to_string()),
+            (2, "let value = 0n;".to_string()),
+            (0, String::new()),
+            (2, "for (let i = 0; i < maxLength; ++i) {".to_string()),
+            (3, "const byte = this.input[this.skip(1)];".to_string()),
+            (3, "value |= BigInt(byte & 0x7F) << BigInt(7 * i);".to_string()),
+            (0, String::new()),
+            (3, "if ((byte & 0x80) === 0) {".to_string()),
+            (4, "return value;".to_string()),
+            (3, "}".to_string()),
+            (2, "}".to_string()),
+            (0, String::new()),
+            (2, "throw new RobustoParseFailure(RobustoParseStatus.Error);".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "range(value: bigint, min: bigint, max: bigint): void {".to_string()),
+            (2, "if (value < min || value > max) {".to_string()),
+            (3, "throw new RobustoParseFailure(RobustoParseStatus.ErrorRange);".to_string()),
+            (2, "}".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "/** Compares the length field at the offset with the bytes between the marks */".to_string()),
+            (1, "length(offset: number, length: number, start: number, end: number): void {".to_string()),
+            (2, "if (this.integer(length, offset) !== BigInt(end - start)) {".to_string()),
+            (3, "throw new RobustoParseFailure(RobustoParseStatus.Error);".to_string()),
+            (2, "}".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "checksum(length: number, crc: bigint): void {".to_string()),
+            (2, "if (this.integer(length) !== crc) {".to_string()),
+            (3, "throw new RobustoParseFailure(RobustoParseStatus.ErrorCrc);".to_string()),
+            (2, "}".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (
+                1,
+                "/** Reads a regex field, keeping `maxLength` bytes of it. Input matching `prefixes` is cut off */"
+                    .to_string(),
+            ),
+            (
+                1,
+                "pattern(expression: RegExp, prefixes: RegExp, maxLength: number, truncate: boolean): Uint8Array {"
+                    .to_string(),
+            ),
+            (2, "if (this.text === undefined) {".to_string()),
+            (3, "this.text = Array.from(this.input, (byte) => String.fromCharCode(byte)).join(\"\");".to_string()),
+            (2, "}".to_string()),
+            (0, String::new()),
+            (2, "expression.lastIndex = this.offset;".to_string()),
+            (2, "prefixes.lastIndex = this.offset;".to_string()),
+            (0, String::new()),
+            (2, "const match = expression.exec(this.text);".to_string()),
+            (0, String::new()),
+            (2, "if (match === null) {".to_string()),
+            (3, "throw new RobustoParseFailure(".to_string()),
+            (4, "prefixes.test(this.text) ? RobustoParseStatus.Incomplete : RobustoParseStatus.Error,".to_string()),
+            (3, ");".to_string()),
+            (2, "}".to_string()),
+            (0, String::new()),
+            (2, "if (match[0].length > maxLength && !truncate) {".to_string()),
+            (3, "throw new RobustoParseFailure(RobustoParseStatus.ErrorOverflow);".to_string()),
+            (2, "}".to_string()),
+            (0, String::new()),
+            (2, "const start = this.skip(match[0].length);".to_string()),
+            (0, String::new()),
+            (2, "return this.input.slice(start, start + Math.min(match[0].length, maxLength));".to_string()),
+            (1, "}".to_string()),
+            (0, "}".to_string()),
+            (0, String::new()),
+            (0, "/** Composes a frame */".to_string()),
+            (0, "class RobustoFrameWriter {".to_string()),
+            (1, "readonly bytes: number[] = [];".to_string()),
+            (0, String::new()),
+            (1, "get offset(): number {".to_string()),
+            (2, "return this.bytes.length;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "constant(bytes: number[]): void {".to_string()),
+            (2, "this.bytes.push(...bytes);".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "zeros(length: number): void {".to_string()),
+            (2, "for (let i = 0; i < length; ++i) {".to_string()),
+            (3, "this.bytes.push(0);".to_string()),
+            (2, "}".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "/** Writes the value big-endian, at the offset, or at the end of the frame */".to_string()),
+            (1, "integer(value: bigint, length: number, offset = this.bytes.length): void {".to_string()),
+            (2, "for (let i = 0; i < length; ++i) {".to_string()),
+            (3, "const shift = BigInt(8 * (length - 1 - i));".to_string()),
+            (3, "this.bytes[offset + i] = Number(BigInt.asUintN(8, value >> shift));".to_string()),
+            (2, "}".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "varint(value: bigint): void {".to_string()),
+            (2, "do {".to_string()),
+            (3, "const byte = Number(value & 0x7Fn);".to_string()),
+            (3, "value >>= 7n;".to_string()),
+            (3, "this.bytes.push(value !== 0n ? byte | 0x80 : byte);".to_string()),
+            (2, "} while (value !== 0n);".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "pattern(bytes: Uint8Array, maxLength: number): void {".to_string()),
+            (2, "this.bytes.push(...bytes.subarray(0, maxLength));".to_string()),
+            (1, "}".to_string()),
+            (0, "}".to_string()),
+        ]);
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for Runtime {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Fields of the frame a step reads or writes belong to the variable. Group
+/// repetitions are variables of their own
+struct Scope<'a> {
+    variable: String,
+    fields: &'a [Field],
+}
+
+impl Scope<'_> {
+    fn member(&self, member: &common::SerializedMember) -> String {
+        format!("{0}.{1}", self.variable, member.field_name)
+    }
+
+    fn field(&self, field_name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == field_name)
+    }
+}
+
+/// Splits the steps into runs, so the alternatives conditional on the same
+/// selector are taken together
+fn step_runs(steps: &[SerializerStep]) -> Vec<&[SerializerStep]> {
+    let mut ret: Vec<&[SerializerStep]> = Vec::new();
+    let mut start = 0usize;
+
+    for i in 1..=steps.len() {
+        let continues = match (steps.get(i - 1), steps.get(i)) {
+            (
+                Some(SerializerStep::Conditional { selector: previous, .. }),
+                Some(SerializerStep::Conditional { selector, .. }),
+            ) => previous.field_name == selector.field_name,
+            _ => false,
+        };
+
+        if !continues {
+            ret.push(&steps[start..i]);
+            start = i;
+        }
+    }
+
+    ret
+}
+
+fn crc_call(data: &str, start: &str, end: &str, algorithm: &crate::bpir::representation::CrcAlgorithm) -> String {
+    format!(
+        "robustoCrc({0}, {1}, {2}, {3}, 0x{4:X}n, 0x{5:X}n, {6}, 0x{7:X}n)",
+        data, start, end, algorithm.width, algorithm.polynomial, algorithm.init, algorithm.reflected, algorithm.xor_out
+    )
+}
+
+/// The interface of a message, preceded by the ones of its group fields
+#[derive(Debug)]
+struct MessageInterface {
+    name: String,
+    doc: Option<String>,
+    members: Vec<(usize, String)>,
+
+    /// Interfaces of group repetitions, which are not declared by another
+    /// message already
+    groups: Vec<(String, Vec<(usize, String)>)>,
+}
+
+impl MessageInterface {
+    fn from_message(message: &Message, symbol_naming: &SymbolNaming, serializer: &common::Serializer) -> Self {
+        let scope = Scope {
+            variable: "message".to_string(),
+            fields: &message.fields,
+        };
+        let mut ret = MessageInterface {
+            name: symbol_naming.message_struct(&message.name),
+            doc: message.doc().map(str::to_string),
+            members: Vec::new(),
+            groups: Vec::new(),
+        };
+        ret.members = ret.member_lines(message, &scope, &serializer.steps, false);
+
+        ret
+    }
+
+    fn member_lines(
+        &mut self,
+        message: &Message,
+        scope: &Scope,
+        steps: &[SerializerStep],
+        optional: bool,
+    ) -> Vec<(usize, String)> {
+        let mut ret = Vec::new();
+
+        for step in steps {
+            match step {
+                SerializerStep::Integer { member, .. }
+                | SerializerStep::Varint { member, .. }
+                | SerializerStep::Pattern { member, .. } => {
+                    if let Some(doc) = scope.field(&member.field_name).and_then(Field::doc) {
+                        ret.extend(doc_lines(doc));
+                    }
+
+                    let type_name = match step {
+                        SerializerStep::Pattern { .. } => "Uint8Array",
+                        _ => ts_type_name(&member.field_base_type),
+                    };
+
+                    ret.push((
+                        0,
+                        format!("{0}{1}: {2};", member.field_name, if optional { "?" } else { "" }, type_name),
+                    ));
+                }
+                SerializerStep::Conditional { steps, .. } => ret.extend(self.member_lines(message, scope, steps, true)),
+                SerializerStep::Group { field_name, steps, .. } => {
+                    let Some(field) = scope.field(field_name) else {
+                        continue;
+                    };
+                    let FieldType::Group(ref group) = field.field_type else {
+                        continue;
+                    };
+                    let group_name = common::group_struct_name(&message.name, field_name, group);
+                    let group_scope = Scope {
+                        variable: format!("{0}Item", field_name),
+                        fields: &group.fields,
+                    };
+                    let group_members = self.member_lines(message, &group_scope, steps, false);
+
+                    if let Some(doc) = field.doc() {
+                        ret.extend(doc_lines(doc));
+                    }
+
+                    ret.push((0, format!("{0}{1}: {2}[];", field_name, if optional { "?" } else { "" }, group_name)));
+                    self.groups.push((group_name, group_members));
+                }
+                _ => {}
+            }
+        }
+
+        ret
+    }
+
+    fn lines(&self) -> Vec<(usize, String)> {
+        let mut ret = Vec::new();
+
+        for (group_name, members) in &self.groups {
+            ret.push((0, String::new()));
+            ret.push((0, format!("export interface {0} {{", group_name)));
+            ret.extend(members.iter().map(|(indent, line)| (indent + 1, line.clone())));
+            ret.push((0, "}".to_string()));
+        }
+
+        ret.push((0, String::new()));
+
+        if let Some(ref doc) = self.doc {
+            ret.extend(doc_lines(doc));
+        }
+
+        ret.push((0, format!("export interface {0} {{", self.name)));
+        ret.extend(self.members.iter().map(|(indent, line)| (indent + 1, line.clone())));
+        ret.push((0, "}".to_string()));
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for MessageInterface {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Parses the first frame of a message off the input
+#[derive(Debug)]
+struct ParsingFunction {
+    message_name: String,
+    symbol_naming: SymbolNaming,
+    sync_sequence: Option<Vec<u8>>,
+    marks: Vec<String>,
+    lines: Vec<(usize, String)>,
+}
+
+impl ParsingFunction {
+    fn from_message(message: &Message, symbol_naming: &SymbolNaming, serializer: &common::Serializer) -> Self {
+        let scope = Scope {
+            variable: "message".to_string(),
+            fields: &message.fields,
+        };
+
+        ParsingFunction {
+            message_name: message.name.clone(),
+            symbol_naming: symbol_naming.clone(),
+            sync_sequence: message.sync_sequence().map(<[u8]>::to_vec),
+            marks: serializer.marks.clone(),
+            lines: ParsingFunction::steps_lines(message, &scope, &serializer.steps),
+        }
+    }
+
+    fn steps_lines(message: &Message, scope: &Scope, steps: &[SerializerStep]) -> Vec<(usize, String)> {
+        let mut ret = Vec::new();
+
+        for run in step_runs(steps) {
+            match run {
+                [SerializerStep::Conditional { selector, .. }, ..] => {
+                    for (i, step) in run.iter().enumerate() {
+                        let SerializerStep::Conditional { value, steps, .. } = step else {
+                            continue;
+                        };
+                        let condition = format!(
+                            "{0} === {1}",
+                            scope.member(selector),
+                            ts_literal(*value, &selector.field_base_type)
+                        );
+                        ret.push((
+                            0,
+                            match i {
+                                0 => format!("if ({0}) {{", condition),
+                                _ => format!("}} else if ({0}) {{", condition),
+                            },
+                        ));
+                        ret.extend(
+                            ParsingFunction::steps_lines(message, scope, steps)
+                                .into_iter()
+                                .map(|(indent, line)| (indent + 1, line)),
+                        );
+                    }
+
+                    ret.push((0, "}".to_string()));
+                }
+                _ => {
+                    for step in run {
+                        ret.extend(ParsingFunction::step_lines(message, scope, step));
+                    }
+                }
+            }
+        }
+
+        ret
+    }
+
+    fn step_lines(message: &Message, scope: &Scope, step: &SerializerStep) -> Vec<(usize, String)> {
+        match step {
+            SerializerStep::Constant(bytes) => vec![(0, format!("reader.constant({0});", byte_array_literal(bytes)))],
+            SerializerStep::Zeros(length) => vec![(0, format!("reader.skip({0});", length))],
+            SerializerStep::Integer { member, bit_width } => {
+                let mut value = format!("reader.integer({0})", bit_width.div_ceil(8));
+
+                if matches!(
+                    member.field_base_type,
+                    FieldBaseType::I8 | FieldBaseType::I16 | FieldBaseType::I32 | FieldBaseType::I64
+                ) {
+                    value = format!("BigInt.asIntN({0}, {1})", bit_width, value);
+                }
+
+                ParsingFunction::value_lines(message, scope, member, value)
+            }
+            SerializerStep::Varint { member, varint } => {
+                let value = match (varint.signed, varint.zigzag) {
+                    (true, true) => format!("BigInt.asIntN({0}, (varint >> 1n) ^ -(varint & 1n))", varint.bit_width),
+                    (true, false) => format!("BigInt.asIntN({0}, varint)", varint.bit_width),
+                    (false, _) => format!("BigInt.asUintN({0}, varint)", varint.bit_width),
+                };
+                let mut ret = vec![
+                    (0, "{".to_string()),
+                    (1, format!("const varint = reader.varint({0});", varint.bit_width.div_ceil(7))),
+                ];
+                ret.extend(
+                    ParsingFunction::value_lines(message, scope, member, value)
+                        .into_iter()
+                        .map(|(indent, line)| (indent + 1, line)),
+                );
+                ret.push((0, "}".to_string()));
+
+                ret
+            }
+            SerializerStep::Pattern { member, max_length, .. } => {
+                let Some(field) = scope.field(&member.field_name) else {
+                    return Vec::new();
+                };
+                let FieldType::Regex(ref regex) = field.field_type else {
+                    return Vec::new();
+                };
+                let pattern = regex.pattern();
+
+                vec![(
+                    0,
+                    format!(
+                        "{0} = reader.pattern(/{1}/y, /(?:{2})$/y, {3}, {4});",
+                        scope.member(member),
+                        pattern.ecmascript(),
+                        pattern.prefixes().ecmascript(),
+                        max_length,
+                        field.overflow_policy() == OverflowPolicy::Truncate
+                    ),
+                )]
+            }
+            SerializerStep::Mark(name) => vec![(0, format!("{0} = reader.offset;", name))],
+            SerializerStep::Length {
+                offset,
+                start,
+                end,
+                bit_width,
+            } => vec![(
+                0,
+                format!("reader.length({0}, {1}, {2}, {3});", offset, bit_width.div_ceil(8), start, end),
+            )],
+            SerializerStep::Checksum {
+                start,
+                stop,
+                algorithm,
+                bit_width,
+            } => vec![(
+                0,
+                format!(
+                    "reader.checksum({0}, {1});",
+                    bit_width.div_ceil(8),
+                    crc_call("input", start, stop, algorithm)
+                ),
+            )],
+            SerializerStep::Conditional { .. } => {
+                ParsingFunction::steps_lines(message, scope, std::slice::from_ref(step))
+            }
+            SerializerStep::Group {
+                field_name,
+                count,
+                steps,
+            } => {
+                let Some(FieldType::Group(ref group)) = scope.field(field_name).map(|field| &field.field_type) else {
+                    return Vec::new();
+                };
+                let group_name = common::group_struct_name(&message.name, field_name, group);
+                let group_scope = Scope {
+                    variable: format!("{0}Item", field_name),
+                    fields: &group.fields,
+                };
+                let mut ret = vec![
+                    (0, format!("{0}.{1} = [];", scope.variable, field_name)),
+                    (0, String::new()),
+                    (0, format!("for (let i = 0; i < {0}; ++i) {{", count)),
+                    (1, format!("const {0} = {{}} as {1};", group_scope.variable, group_name)),
+                ];
+                ret.extend(
+                    ParsingFunction::steps_lines(message, &group_scope, steps)
+                        .into_iter()
+                        .map(|(indent, line)| (indent + 1, line)),
+                );
+                ret.extend([
+                    (1, format!("{0}.{1}.push({2});", scope.variable, field_name, group_scope.variable)),
+                    (0, "}".to_string()),
+                ]);
+
+                ret
+            }
+        }
+    }
+
+    /// Checks the range of the field's value, if it has one, and stores it
+    fn value_lines(
+        message: &Message,
+        scope: &Scope,
+        member: &common::SerializedMember,
+        value: String,
+    ) -> Vec<(usize, String)> {
+        let range_check = scope
+            .field(&member.field_name)
+            .and_then(|field| common::RangeCheck::from_field(message, field));
+
+        match range_check {
+            Some(range_check) => vec![
+                (0, format!("const {0}Value = {1};", member.field_name, value)),
+                (
+                    0,
+                    format!(
+                        "reader.range({0}Value, {1}, {2});",
+                        member.field_name,
+                        range_check.min.map_or(format!("{0}Value", member.field_name), |min| format!("{0}n", min)),
+                        range_check.max.map_or(format!("{0}Value", member.field_name), |max| format!("{0}n", max)),
+                    ),
+                ),
+                (
+                    0,
+                    format!(
+                        "{0} = {1};",
+                        scope.member(member),
+                        from_bigint(format!("{0}Value", member.field_name), &member.field_base_type)
+                    ),
+                ),
+            ],
+            None => vec![(
+                0,
+                format!("{0} = {1};", scope.member(member), from_bigint(value, &member.field_base_type)),
+            )],
+        }
+    }
+
+    fn lines(&self) -> Vec<(usize, String)> {
+        let message_struct = self.symbol_naming.message_struct(&self.message_name);
+        let mut ret = vec![
+            (0, String::new()),
+            (
+                0,
+                format!(
+                    "export function {0}(input: Uint8Array): RobustoParseResult<{1}> {{",
+                    self.symbol_naming.parsing_function(&self.message_name),
+                    message_struct
+                ),
+            ),
+            (1, "const reader = new RobustoFrameReader(input);".to_string()),
+            (1, format!("const message = {{}} as {0};", message_struct)),
+        ];
+
+        for mark in &self.marks {
+            ret.push((1, format!("let {0} = 0;", mark)));
+        }
+
+        ret.extend([(0, String::new()), (1, "try {".to_string())]);
+
+        if let Some(ref sync_sequence) = self.sync_sequence {
+            ret.push((2, format!("reader.synchronize({0});", byte_array_literal(sync_sequence))));
+        }
+
+        ret.extend(self.lines.iter().map(|(indent, line)| (indent + 2, line.clone())));
+        ret.extend([
+            (1, "} catch (error) {".to_string()),
+            (2, "if (error instanceof RobustoParseFailure) {".to_string()),
+            (3, "return { status: error.status, length: 0 };".to_string()),
+            (2, "}".to_string()),
+            (0, String::new()),
+            (2, "throw error;".to_string()),
+            (1, "}".to_string()),
+            (0, String::new()),
+            (1, "return { status: RobustoParseStatus.Ok, message, length: reader.offset };".to_string()),
+            (0, "}".to_string()),
+        ]);
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for ParsingFunction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Composes the frame of a message. Members of alternatives the selector
+/// does not pick are ignored, and absent ones are written as zeros
+#[derive(Debug)]
+struct SerializingFunction {
+    message_name: String,
+    symbol_naming: SymbolNaming,
+    marks: Vec<String>,
+    lines: Vec<(usize, String)>,
+}
+
+impl SerializingFunction {
+    fn from_message(message: &Message, symbol_naming: &SymbolNaming, serializer: &common::Serializer) -> Self {
+        let scope = Scope {
+            variable: "message".to_string(),
+            fields: &message.fields,
+        };
+
+        SerializingFunction {
+            message_name: message.name.clone(),
+            symbol_naming: symbol_naming.clone(),
+            marks: serializer.marks.clone(),
+            lines: SerializingFunction::steps_lines(&scope, &serializer.steps, false),
+        }
+    }
+
+    fn steps_lines(scope: &Scope, steps: &[SerializerStep], optional: bool) -> Vec<(usize, String)> {
+        let mut ret = Vec::new();
+
+        for run in step_runs(steps) {
+            for (i, step) in run.iter().enumerate() {
+                let SerializerStep::Conditional { selector, value, steps } = step else {
+                    ret.extend(SerializingFunction::step_lines(scope, step, optional));
+                    continue;
+                };
+                let condition = format!(
+                    "{0} === {1}",
+                    scope.member(selector),
+                    ts_literal(*value, &selector.field_base_type)
+                );
+                ret.push((
+                    0,
+                    match i {
+                        0 => format!("if ({0}) {{", condition),
+                        _ => format!("}} else if ({0}) {{", condition),
+                    },
+                ));
+                ret.extend(
+                    SerializingFunction::steps_lines(scope, steps, true)
+                        .into_iter()
+                        .map(|(indent, line)| (indent + 1, line)),
+                );
+
+                if i + 1 == run.len() {
+                    ret.push((0, "}".to_string()));
+                }
+            }
+        }
+
+        ret
+    }
+
+    /// The member's value, as a `bigint`
+    fn value(scope: &Scope, member: &common::SerializedMember, optional: bool) -> String {
+        match (optional, is_bigint(&member.field_base_type)) {
+            (true, true) => format!("({0} ?? 0n)", scope.member(member)),
+            (true, false) => format!("BigInt({0} ?? 0)", scope.member(member)),
+            (false, _) => to_bigint(scope.member(member), &member.field_base_type),
+        }
+    }
+
+    fn step_lines(scope: &Scope, step: &SerializerStep, optional: bool) -> Vec<(usize, String)> {
+        match step {
+            SerializerStep::Constant(bytes) => vec![(0, format!("writer.constant({0});", byte_array_literal(bytes)))],
+            SerializerStep::Zeros(length) => vec![(0, format!("writer.zeros({0});", length))],
+            SerializerStep::Integer { member, bit_width } => vec![(
+                0,
+                format!(
+                    "writer.integer({0}, {1});",
+                    SerializingFunction::value(scope, member, optional),
+                    bit_width.div_ceil(8)
+                ),
+            )],
+            SerializerStep::Varint { member, varint } => {
+                let value = SerializingFunction::value(scope, member, optional);
+                let value = match (varint.signed, varint.zigzag) {
+                    (true, true) => format!("robustoZigzag({0})", value),
+                    _ => value,
+                };
+
+                vec![(0, format!("writer.varint(BigInt.asUintN({0}, {1}));", varint.bit_width, value))]
+            }
+            SerializerStep::Pattern { member, max_length, .. } => {
+                let value = match optional {
+                    true => format!("({0} ?? new Uint8Array())", scope.member(member)),
+                    false => scope.member(member),
+                };
+
+                vec![(0, format!("writer.pattern({0}, {1});", value, max_length))]
+            }
+            SerializerStep::Mark(name) => vec![(0, format!("{0} = writer.offset;", name))],
+            SerializerStep::Length {
+                offset,
+                start,
+                end,
+                bit_width,
+            } => vec![(
+                0,
+                format!(
+                    "writer.integer(BigInt({0} - {1}), {2}, {3});",
+                    end,
+                    start,
+                    bit_width.div_ceil(8),
+                    offset
+                ),
+            )],
+            SerializerStep::Checksum {
+                start,
+                stop,
+                algorithm,
+                bit_width,
+            } => vec![(
+                0,
+                format!(
+                    "writer.integer({0}, {1});",
+                    crc_call("writer.bytes", start, stop, algorithm),
+                    bit_width.div_ceil(8)
+                ),
+            )],
+            SerializerStep::Conditional { .. } => {
+                SerializingFunction::steps_lines(scope, std::slice::from_ref(step), optional)
+            }
+            SerializerStep::Group {
+                field_name,
+                count,
+                steps,
+            } => {
+                let Some(FieldType::Group(ref group)) = scope.field(field_name).map(|field| &field.field_type) else {
+                    return Vec::new();
+                };
+                let group_scope = Scope {
+                    variable: format!("{0}Item", field_name),
+                    fields: &group.fields,
+                };
+                let repetitions = match optional {
+                    true => format!("({0}.{1} ?? [])", scope.variable, field_name),
+                    false => format!("{0}.{1}", scope.variable, field_name),
+                };
+                let mut ret = vec![
+                    (0, format!("if ({0}.length !== {1}) {{", repetitions, count)),
+                    (
+                        1,
+                        format!(
+                            "throw new RangeError(\"{0} takes {1} repetitions\");",
+                            field_name, count
+                        ),
+                    ),
+                    (0, "}".to_string()),
+                    (0, String::new()),
+                    (0, format!("for (const {0} of {1}) {{", group_scope.variable, repetitions)),
+                ];
+                ret.extend(
+                    SerializingFunction::steps_lines(&group_scope, steps, false)
+                        .into_iter()
+                        .map(|(indent, line)| (indent + 1, line)),
+                );
+                ret.push((0, "}".to_string()));
+
+                ret
+            }
+        }
+    }
+
+    fn lines(&self) -> Vec<(usize, String)> {
+        let mut ret = vec![
+            (0, String::new()),
+            (
+                0,
+                format!(
+                    "export function {0}(message: {1}): Uint8Array {{",
+                    self.symbol_naming.serializing_function(&self.message_name),
+                    self.symbol_naming.message_struct(&self.message_name)
+                ),
+            ),
+            (1, "const writer = new RobustoFrameWriter();".to_string()),
+        ];
+
+        for mark in &self.marks {
+            ret.push((1, format!("let {0} = 0;", mark)));
+        }
+
+        ret.push((0, String::new()));
+        ret.extend(self.lines.iter().map(|(indent, line)| (indent + 1, line.clone())));
+        ret.extend([
+            (0, String::new()),
+            (1, "return Uint8Array.from(writer.bytes);".to_string()),
+            (0, "}".to_string()),
+        ]);
+
+        ret
+    }
+}
+
+impl codegen::TreeBasedCodeGeneration for SerializingFunction {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        indented_lines(self.lines().into_iter(), code_generation_state.indent)
+    }
+}
+
+/// Notes a message the module leaves out
+#[derive(Debug)]
+struct UnsupportedMessage {
+    message_name: String,
+    reason: String,
+}
+
+impl codegen::TreeBasedCodeGeneration for UnsupportedMessage {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        let lines = [
+            (0, String::new()),
+            (
+                0,
+                format!("// Message {0} is left out: {1}", self.message_name, self.reason),
+            ),
+        ];
+
+        indented_lines(lines.into_iter(), code_generation_state.indent)
+    }
+}
+
+#[derive(Debug)]
+enum AstNodeType {
+    Root,
+    Runtime(Runtime),
+    MessageInterface(MessageInterface),
+    ParsingFunction(ParsingFunction),
+    SerializingFunction(SerializingFunction),
+    UnsupportedMessage(UnsupportedMessage),
+}
+
+#[derive(Debug)]
+struct AstNode {
+    ast_node_type: AstNodeType,
+    children: Vec<AstNode>,
+}
+
+impl AstNode {
+    fn new() -> AstNode {
+        AstNode {
+            ast_node_type: AstNodeType::Root,
+            children: Vec::new(),
+        }
+    }
+
+    fn add_child(&mut self, ast_node_type: AstNodeType) -> &mut AstNode {
+        self.children.push(AstNode {
+            ast_node_type,
+            children: Vec::new(),
+        });
+
+        self.children.last_mut().unwrap()
+    }
+}
+
+impl SubnodeAccess<AstNode> for AstNode {
+    fn iter(&self) -> std::slice::Iter<'_, AstNode> {
+        self.children.iter()
+    }
+}
+
+impl TreeBasedCodeGeneration for AstNode {
+    fn generate_code_pre_traverse(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        match self.ast_node_type {
+            AstNodeType::Root => Vec::new(),
+            AstNodeType::Runtime(ref node) => node.generate_code_pre_traverse(code_generation_state),
+            AstNodeType::MessageInterface(ref node) => node.generate_code_pre_traverse(code_generation_state),
+            AstNodeType::ParsingFunction(ref node) => node.generate_code_pre_traverse(code_generation_state),
+            AstNodeType::SerializingFunction(ref node) => node.generate_code_pre_traverse(code_generation_state),
+            AstNodeType::UnsupportedMessage(ref node) => node.generate_code_pre_traverse(code_generation_state),
+        }
+    }
+}
+
+/// AST tree for generating the TypeScript module
+pub struct ModuleAstNode {
+    ast_node: AstNode,
+}
+
+impl From<&Protocol> for ModuleAstNode {
+    fn from(protocol: &Protocol) -> Self {
+        let mut ret = AstNode::new();
+        let symbol_naming = protocol.symbol_naming();
+        let mut group_names = Vec::new();
+
+        ret.add_child(AstNodeType::Runtime(Runtime {}));
+
+        for message in &protocol.messages {
+            let serializer = match unsupported_reason(protocol, message) {
+                Some(reason) => {
+                    log::warn!(
+                        "Message \"{}\" is left out of the TypeScript module: {}",
+                        message.name,
+                        reason
+                    );
+                    ret.add_child(AstNodeType::UnsupportedMessage(UnsupportedMessage {
+                        message_name: message.name.clone(),
+                        reason,
+                    }));
+                    continue;
+                }
+                None => match common::Serializer::from_message(protocol, message) {
+                    Ok(serializer) => serializer,
+                    Err(_) => continue,
+                },
+            };
+            let mut message_interface = MessageInterface::from_message(message, &symbol_naming, &serializer);
+
+            // Groups made from the same definition share the interface
+            message_interface.groups.retain(|(group_name, _)| !group_names.contains(group_name));
+            group_names.extend(message_interface.groups.iter().map(|(group_name, _)| group_name.clone()));
+
+            ret.add_child(AstNodeType::MessageInterface(message_interface));
+            ret.add_child(AstNodeType::ParsingFunction(ParsingFunction::from_message(
+                message,
+                &symbol_naming,
+                &serializer,
+            )));
+            ret.add_child(AstNodeType::SerializingFunction(SerializingFunction::from_message(
+                message,
+                &symbol_naming,
+                &serializer,
+            )));
+        }
+
+        ModuleAstNode { ast_node: ret }
+    }
+}
+
+impl CodeGeneration for ModuleAstNode {
+    fn generate_code(
+        &self,
+        code_generation_state: &mut codegen::CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        self.ast_node.generate_code(code_generation_state)
+    }
+}
+
+/// Renders the TypeScript module into the set, if the protocol asks for one
+pub fn add_artifacts(
+    protocol: &Protocol,
+    naming: &ArtifactNaming,
+    settings: &ArtifactSettings,
+    artifact_set: &mut GeneratedArtifactSet,
+) -> Result<(), RobustoError> {
+    if !protocol.typescript() {
+        return Ok(());
+    }
+
+    artifact_set.add_code(
+        &naming.typescript(),
+        ArtifactKind::Source,
+        &ModuleAstNode::from(protocol),
+        settings,
+    )
+}
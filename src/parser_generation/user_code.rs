@@ -0,0 +1,148 @@
+//! Blocks of generated files reserved for user code. Each block is delimited
+//! by a pair of comments naming it:
+//!
+//! ```text
+//! /* ROBUSTO USER CODE BEGIN SensorReport parser */
+//! ...
+//! /* ROBUSTO USER CODE END SensorReport parser */
+//! ```
+//!
+//! When a file is regenerated, the lines users have put between the comments
+//! of the previous file are carried over into the block of the same name.
+
+use std::string::String;
+use std::vec::Vec;
+
+const BEGIN_MARKER: &str = "/* ROBUSTO USER CODE BEGIN ";
+const END_MARKER: &str = "/* ROBUSTO USER CODE END ";
+const COMMENT_END: &str = " */";
+
+pub fn begin_line(tag: &str) -> String {
+    format!("{0}{1}{2}", BEGIN_MARKER, tag, COMMENT_END)
+}
+
+pub fn end_line(tag: &str) -> String {
+    format!("{0}{1}{2}", END_MARKER, tag, COMMENT_END)
+}
+
+/// Whether the line opens a block
+pub fn is_begin_line(line: &str) -> bool {
+    tag(line, BEGIN_MARKER).is_some()
+}
+
+/// Whether the line closes a block
+pub fn is_end_line(line: &str) -> bool {
+    tag(line, END_MARKER).is_some()
+}
+
+/// Name of the block the line opens or closes, if it is a marker
+fn tag<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    line.trim()
+        .strip_prefix(marker)
+        .and_then(|rest| rest.strip_suffix(COMMENT_END))
+}
+
+/// Contents of the user code blocks of a file
+#[derive(Debug, Clone, Default)]
+pub struct UserCode {
+    /// Lines of each block, by the block's name, in the order of the file
+    pub blocks: Vec<(String, Vec<String>)>,
+}
+
+impl UserCode {
+    /// Collects the blocks of a file. Fails on a block left open, and on two
+    /// blocks of the same name
+    pub fn parse(text: &str) -> Result<UserCode, String> {
+        let mut ret = UserCode::default();
+        let mut block: Option<(usize, &str, Vec<String>)> = None;
+
+        for (line_number, line) in text.lines().enumerate() {
+            match (block.take(), tag(line, BEGIN_MARKER), tag(line, END_MARKER)) {
+                (None, Some(name), _) => {
+                    if ret.get(name).is_some() {
+                        return Err(format!("line {0}: user code block \"{1}\" repeats", line_number + 1, name));
+                    }
+
+                    block = Some((line_number, name, Vec::new()));
+                }
+                (None, None, _) => {}
+                (Some((_, name, lines)), _, Some(end_name)) if end_name == name => {
+                    ret.blocks.push((name.to_string(), lines));
+                }
+                (Some((begin_line_number, name, _)), Some(_), _) | (Some((begin_line_number, name, _)), _, Some(_)) => {
+                    return Err(format!(
+                        "line {0}: user code block \"{1}\" is not closed",
+                        begin_line_number + 1,
+                        name
+                    ))
+                }
+                (Some((begin_line_number, name, mut lines)), None, None) => {
+                    lines.push(line.to_string());
+                    block = Some((begin_line_number, name, lines));
+                }
+            }
+        }
+
+        match block {
+            Some((begin_line_number, name, _)) => Err(format!(
+                "line {0}: user code block \"{1}\" is not closed",
+                begin_line_number + 1,
+                name
+            )),
+            None => Ok(ret),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Vec<String>> {
+        self.blocks
+            .iter()
+            .find(|(block_name, _)| block_name == name)
+            .map(|(_, lines)| lines)
+    }
+
+    /// Fills the blocks of a freshly generated file. Returns the names of the
+    /// non-empty blocks the file has no counterpart for, whose code is lost
+    pub fn restore(&self, text: &str) -> (String, Vec<String>) {
+        let mut ret = String::with_capacity(text.len());
+        let mut restored = Vec::new();
+        let mut skipping = false;
+
+        for line in text.split_inclusive('\n') {
+            if skipping {
+                if tag(line, END_MARKER).is_none() {
+                    continue;
+                }
+
+                skipping = false;
+            }
+
+            ret.push_str(line);
+
+            let Some(lines) = tag(line, BEGIN_MARKER).and_then(|name| {
+                restored.push(name.to_string());
+                self.get(name)
+            }) else {
+                continue;
+            };
+
+            // The block's lines take the newline of the marker
+            let newline = &line[line.trim_end_matches(['\r', '\n']).len()..];
+
+            for user_line in lines {
+                ret.push_str(user_line);
+                ret.push_str(newline);
+            }
+
+            skipping = true;
+        }
+
+        let lost = self
+            .blocks
+            .iter()
+            .filter(|(name, lines)| !lines.is_empty() && !restored.contains(name))
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        (ret, lost)
+    }
+}
@@ -1,19 +1,14 @@
 use crate::parser_generation;
-use crate::utility::string::write_newlines_or_panic;
-use std::alloc::handle_alloc_error;
-use std::array::IntoIter;
-use std::collections::{linked_list, LinkedList};
-use std::io::{BufWriter, Write};
+use crate::error::RobustoError;
 use std::iter::Iterator;
-use std::path::Iter;
-use std::str::FromStr;
 use std::string::String;
+use std::vec::Vec;
 
 /// Precompiled code
 #[derive(Clone, Debug)]
 pub struct RawCode {
-    code_chunk_pre_traverse: LinkedList<CodeChunk>,
-    code_chunk_post_traverse: LinkedList<CodeChunk>,
+    code_chunk_pre_traverse: Vec<CodeChunk>,
+    code_chunk_post_traverse: Vec<CodeChunk>,
 
     /// A diff for further increment.
     ///
@@ -25,16 +20,15 @@ pub struct RawCode {
 
 impl From<&str> for RawCode {
     fn from(value: &str) -> Self {
-        let mut ret = LinkedList::new();
-        ret.push_back(CodeChunk {
-            code: value.into(),
-            indent: 0usize,
-            newlines: 1usize,
-        });
-
         RawCode {
-            code_chunk_pre_traverse: ret,
-            code_chunk_post_traverse: LinkedList::new(),
+            code_chunk_pre_traverse: vec![CodeChunk {
+                code: value.into(),
+                indent: 0usize,
+                newlines: 1usize,
+                origin: None,
+                fragment: None,
+            }],
+            code_chunk_post_traverse: Vec::new(),
             indent_increment: 0isize,
         }
     }
@@ -60,15 +54,17 @@ impl TreeBasedCodeGeneration for RawCode {
     fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
+    ) -> Vec<CodeChunk> {
         // Heuristic: apply whatever indent was used during creation of the object + the current indent
         // TODO: won't fit for the negative indents
-        let mut ret = self.code_chunk_pre_traverse
+        let ret = self.code_chunk_pre_traverse
             .iter()
             .map(|chunk| CodeChunk {
                 code: chunk.code.clone(),
                 indent: chunk.indent + code_generation_state.indent,
                 newlines: chunk.newlines,
+                origin: chunk.origin.clone(),
+                fragment: chunk.fragment.clone(),
             })
             .collect();
         code_generation_state.increment_indent(self.indent_increment);
@@ -79,7 +75,7 @@ impl TreeBasedCodeGeneration for RawCode {
     fn generate_code_post_traverse(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
+    ) -> Vec<CodeChunk> {
         code_generation_state.increment_indent(-self.indent_increment);
         self.code_chunk_post_traverse
             .iter()
@@ -87,23 +83,152 @@ impl TreeBasedCodeGeneration for RawCode {
                 code: chunk.code.clone(),
                 indent: chunk.indent + code_generation_state.indent,
                 newlines: chunk.newlines,
+                origin: chunk.origin.clone(),
+                fragment: chunk.fragment.clone(),
             })
             .collect()
     }
 }
 
+/// Characters an indent level consists of
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+pub enum IndentStyle {
+    Spaces,
+    Tabs,
+}
+
+/// Line terminators
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+pub enum NewlineStyle {
+    Lf,
+    CrLf,
+}
+
+/// Placement of the braces opening blocks
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize)]
+pub enum BraceStyle {
+    /// The brace ends the line opening the block, as generated (K&R)
+    Attached,
+
+    /// The brace takes a line of its own (Allman). Braces of initializers
+    /// stay attached
+    OwnLine,
+}
+
+/// Formatting of the generated code, so it matches the coding standard of the
+/// project it is generated for. Defaults to 4-space indents, LF, and K&R
+/// braces
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct CodeStyle {
+    pub indent_style: IndentStyle,
+
+    /// Spaces per indent level, unless indenting with tabs
+    pub indent_width: usize,
+    pub newline_style: NewlineStyle,
+    pub brace_style: BraceStyle,
+}
+
+impl Default for CodeStyle {
+    fn default() -> Self {
+        CodeStyle {
+            indent_style: IndentStyle::Spaces,
+            indent_width: 4usize,
+            newline_style: NewlineStyle::Lf,
+            brace_style: BraceStyle::Attached,
+        }
+    }
+}
+
+impl CodeStyle {
+    pub fn indent(&self, indent: usize) -> String {
+        match self.indent_style {
+            IndentStyle::Spaces => " ".repeat(indent * self.indent_width),
+            IndentStyle::Tabs => "\t".repeat(indent),
+        }
+    }
+
+    pub fn newline(&self) -> &'static str {
+        match self.newline_style {
+            NewlineStyle::Lf => "\n",
+            NewlineStyle::CrLf => "\r\n",
+        }
+    }
+
+    /// Lines of a chunk's line in the style's brace placement. With braces
+    /// on lines of their own, `} else {` spans three lines
+    fn place_braces<'a>(&self, line: &'a str) -> std::vec::Vec<&'a str> {
+        let head = match line.strip_suffix(" {") {
+            Some(head) if self.brace_style == BraceStyle::OwnLine && !head.ends_with('=') => head,
+            _ => return vec![line],
+        };
+
+        match head.strip_prefix("} ") {
+            Some(head) => vec!["}", head, "{"],
+            None => vec![head, "{"],
+        }
+    }
+
+    /// Formats a chunk. Every line of the chunk is indented, except for the
+    /// empty ones following the first one
+    fn format_chunk(&self, code_chunk: &CodeChunk) -> String {
+        let indent = self.indent(code_chunk.indent);
+        let newline = self.newline();
+        let mut ret = String::new();
+
+        // Marks a place in the code, e.g. where an empty fragment is
+        if code_chunk.code.is_empty() && code_chunk.newlines == 0 {
+            return ret;
+        }
+
+        for (i, line) in code_chunk.code.split('\n').enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+
+            if i > 0 {
+                ret.push_str(newline);
+            }
+
+            if i > 0 && line.is_empty() {
+                continue;
+            }
+
+            for (j, line) in self.place_braces(line).into_iter().enumerate() {
+                if j > 0 {
+                    ret.push_str(newline);
+                }
+
+                ret.push_str(&indent);
+                ret.push_str(line);
+            }
+        }
+
+        for _ in 0..code_chunk.newlines {
+            ret.push_str(newline);
+        }
+
+        ret
+    }
+}
+
 pub struct CodeGenerationState {
     // Current indent.
     pub indent: usize,
+
+    /// Formatting of the generated code
+    pub style: CodeStyle,
 }
 
 impl CodeGenerationState {
     fn new() -> CodeGenerationState {
-        CodeGenerationState { indent: 0 }
+        CodeGenerationState::with_style(CodeStyle::default())
+    }
+
+    pub fn with_style(style: CodeStyle) -> CodeGenerationState {
+        CodeGenerationState { indent: 0, style }
     }
 
     fn increment_indent(&mut self, increment: isize) {
-        if increment < 0isize && self.indent < increment.abs() as usize {
+        if increment < 0isize && self.indent < increment.unsigned_abs() {
             log::warn!(
                 "Indent value is less than 0, current indent: {0}, increment: {1}",
                 self.indent,
@@ -125,6 +250,12 @@ pub struct CodeChunk {
 
     /// Number of new lines to add after the chunk
     newlines: usize,
+
+    /// Element of the protocol the chunk has been generated from, if any
+    origin: Option<Origin>,
+
+    /// Fragment users may override the chunk as a part of, if any
+    fragment: Option<Fragment>,
 }
 
 impl CodeChunk {
@@ -133,15 +264,184 @@ impl CodeChunk {
             code,
             indent,
             newlines,
+            origin: None,
+            fragment: None,
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn indent(&self) -> usize {
+        self.indent
+    }
+
+    pub fn newlines(&self) -> usize {
+        self.newlines
+    }
+
+    pub fn fragment(&self) -> Option<&Fragment> {
+        self.fragment.as_ref()
+    }
+
+    /// Marks the chunk as a part of a fragment, unless it already is a part
+    /// of another one
+    pub fn with_fragment(mut self, fragment: Option<&Fragment>) -> CodeChunk {
+        if self.fragment.is_none() {
+            self.fragment = fragment.cloned();
+        }
+
+        self
+    }
+
+    pub fn origin(&self) -> Option<&Origin> {
+        self.origin.as_ref()
+    }
+
+    /// Attributes the chunk to an element of the protocol, unless it already
+    /// is attributed to a more specific one
+    pub fn with_origin(mut self, origin: Option<&Origin>) -> CodeChunk {
+        if self.origin.is_none() {
+            self.origin = origin.cloned();
+        }
+
+        self
+    }
+}
+
+/// A piece of generated code users may replace with their own, see
+/// `parser_generation::overrides`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fragment {
+    /// The comment atop every generated file
+    Prologue,
+
+    /// `#include` directives
+    Includes,
+
+    /// First line of a message's parsing function
+    ParsingFunctionSignature,
+
+    /// Body of the Ragel action of the given name
+    ActionBody(String),
+}
+
+/// Element of the protocol a piece of generated code stems from: a message,
+/// or one of its fields
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct Origin {
+    pub message: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+}
+
+impl Origin {
+    pub fn message(message_name: &str) -> Origin {
+        Origin {
+            message: message_name.to_string(),
+            field: None,
+        }
+    }
+
+    pub fn field(message_name: &str, field_name: &str) -> Origin {
+        Origin {
+            message: message_name.to_string(),
+            field: Some(field_name.to_string()),
         }
     }
 }
 
+/// Lines of a generated file, numbered from 1, which stem from an element of
+/// the protocol
+#[derive(Debug, serde::Serialize)]
+pub struct SourceMapEntry {
+    pub first_line: usize,
+    pub last_line: usize,
+
+    #[serde(flatten)]
+    pub origin: Origin,
+}
+
+/// Traces the lines of a generated file back to the protocol's messages and
+/// fields, so a misbehaving Ragel action can be looked up in the description.
+/// Adjacent lines of the same origin share an entry
+#[derive(Debug, Default, serde::Serialize)]
+pub struct SourceMap {
+    pub entries: Vec<SourceMapEntry>,
+}
+
+impl SourceMap {
+    /// Maps the artifact as it is written in the given style
+    pub fn from_artifact<T: CodeGeneration>(artifact: &T, style: &CodeStyle) -> SourceMap {
+        let mut builder = SourceMapBuilder {
+            style,
+            line: 1usize,
+            extends_last_entry: false,
+            source_map: SourceMap::default(),
+        };
+
+        // Building a map never fails
+        let _ = artifact.generate_code_into(&mut CodeGenerationState::with_style(style.clone()), &mut builder);
+
+        builder.source_map
+    }
+
+    pub fn render_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+/// Follows the lines of the chunks as they are written
+struct SourceMapBuilder<'a> {
+    style: &'a CodeStyle,
+
+    /// Line the next chunk starts at
+    line: usize,
+
+    /// Whether nothing but blank lines has followed the last entry
+    extends_last_entry: bool,
+    source_map: SourceMap,
+}
+
+impl CodeChunkSink for SourceMapBuilder<'_> {
+    fn accept(&mut self, code_chunk: CodeChunk) -> Result<(), RobustoError> {
+        let text = self.style.format_chunk(&code_chunk);
+        let first_line = self.line;
+        self.line += text.matches('\n').count();
+
+        if code_chunk.code.trim().is_empty() {
+            return Ok(());
+        }
+
+        let Some(origin) = code_chunk.origin else {
+            self.extends_last_entry = false;
+
+            return Ok(());
+        };
+        let last_line = first_line + text.trim_end_matches(['\r', '\n']).matches('\n').count();
+
+        match self.source_map.entries.last_mut() {
+            Some(entry) if self.extends_last_entry && entry.origin == origin => entry.last_line = last_line,
+            _ => self.source_map.entries.push(SourceMapEntry {
+                first_line,
+                last_line,
+                origin,
+            }),
+        }
+
+        self.extends_last_entry = true;
+
+        Ok(())
+    }
+}
+
 pub trait TreeBasedCodeGeneration {
     fn generate_code_pre_traverse(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk>;
+    ) -> Vec<CodeChunk>;
 
     /// A hook which gets invoked after the AST's children have been traversed.
     /// Usually it is used for generating content nested in brackets of some
@@ -149,9 +449,9 @@ pub trait TreeBasedCodeGeneration {
     /// node is only supposed to be used as a leaf.
     fn generate_code_post_traverse(
         &self,
-        code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        LinkedList::<CodeChunk>::new()
+        _code_generation_state: &mut CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        Vec::<CodeChunk>::new()
     }
 }
 
@@ -159,11 +459,54 @@ pub trait SubnodeAccess<T: CodeGeneration> {
     fn iter(&self) -> std::slice::Iter<'_, T>;
 }
 
+/// Receives code chunks as they are generated, so the whole output need not
+/// be held in memory
+pub trait CodeChunkSink {
+    fn accept(&mut self, code_chunk: CodeChunk) -> Result<(), RobustoError>;
+}
+
+impl CodeChunkSink for Vec<CodeChunk> {
+    fn accept(&mut self, code_chunk: CodeChunk) -> Result<(), RobustoError> {
+        self.push(code_chunk);
+
+        Ok(())
+    }
+}
+
+/// Formats chunks in a style, and writes them as they come
+pub struct StyledWriter<'a, W: std::io::Write> {
+    pub buf_writer: &'a mut std::io::BufWriter<W>,
+    pub style: &'a CodeStyle,
+}
+
+impl<W: std::io::Write> CodeChunkSink for StyledWriter<'_, W> {
+    fn accept(&mut self, code_chunk: CodeChunk) -> Result<(), RobustoError> {
+        std::io::Write::write_all(self.buf_writer, self.style.format_chunk(&code_chunk).as_bytes())?;
+
+        Ok(())
+    }
+}
+
 pub trait CodeGeneration {
     fn generate_code(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk>;
+    ) -> Vec<CodeChunk>;
+
+    /// Streams the chunks into the sink. Stops at the first chunk the sink
+    /// fails to accept. Unless overridden, the chunks are generated at once,
+    /// and then passed on
+    fn generate_code_into(
+        &self,
+        code_generation_state: &mut CodeGenerationState,
+        sink: &mut dyn CodeChunkSink,
+    ) -> Result<(), RobustoError> {
+        for code_chunk in self.generate_code(code_generation_state) {
+            sink.accept(code_chunk)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<T> CodeGeneration for T
@@ -173,29 +516,48 @@ where
     fn generate_code(
         &self,
         code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        let mut ret = LinkedList::new();
-        ret.append(&mut self.generate_code_pre_traverse(code_generation_state));
+    ) -> Vec<CodeChunk> {
+        let mut ret = Vec::new();
+
+        // Collecting into a buffer never fails
+        let _ = self.generate_code_into(code_generation_state, &mut ret);
+
+        ret
+    }
+
+    /// Passes each node's chunks on as soon as the node is traversed
+    fn generate_code_into(
+        &self,
+        code_generation_state: &mut CodeGenerationState,
+        sink: &mut dyn CodeChunkSink,
+    ) -> Result<(), RobustoError> {
+        for code_chunk in self.generate_code_pre_traverse(code_generation_state) {
+            sink.accept(code_chunk)?;
+        }
 
         for subnode in self.iter() {
-            ret.append(&mut subnode.generate_code(code_generation_state));
+            subnode.generate_code_into(code_generation_state, sink)?;
         }
 
-        ret.append(&mut self.generate_code_post_traverse(code_generation_state));
+        for code_chunk in self.generate_code_post_traverse(code_generation_state) {
+            sink.accept(code_chunk)?;
+        }
 
-        ret
+        Ok(())
     }
 }
 
 impl<T: CodeGeneration> parser_generation::Write for T {
-    fn write<W: std::io::Write>(&self, buf_writer: &mut std::io::BufWriter<W>) {
-        use crate::utility::string::write_with_indent_or_panic;
-        let mut code_generation_state = CodeGenerationState::new();
+    fn write_with_style<W: std::io::Write>(
+        &self,
+        buf_writer: &mut std::io::BufWriter<W>,
+        style: &CodeStyle,
+    ) -> Result<(), RobustoError> {
+        let mut code_generation_state = CodeGenerationState::with_style(style.clone());
+        self.generate_code_into(&mut code_generation_state, &mut StyledWriter { buf_writer, style })?;
+        std::io::Write::flush(buf_writer)?;
 
-        for code_chunk in self.generate_code(&mut code_generation_state).iter() {
-            write_with_indent_or_panic(buf_writer, code_chunk.indent, code_chunk.code.as_bytes());
-            write_newlines_or_panic(buf_writer, code_chunk.newlines);
-        }
+        Ok(())
     }
 }
 
@@ -204,8 +566,8 @@ pub struct MockCodeGenerator {}
 impl TreeBasedCodeGeneration for MockCodeGenerator {
     fn generate_code_pre_traverse(
         &self,
-        code_generation_state: &mut CodeGenerationState,
-    ) -> LinkedList<CodeChunk> {
-        LinkedList::new()
+        _code_generation_state: &mut CodeGenerationState,
+    ) -> Vec<CodeChunk> {
+        Vec::new()
     }
 }
@@ -0,0 +1,194 @@
+//! Identifiers of the generated code are made from the names of a protocol
+//! description: messages, fields, flag bits, checksums. The description
+//! allows any string, the target languages do not, so names are mangled into
+//! valid identifiers once, before any backend sees them, see
+//! `Protocol::mangle_identifiers()`.
+
+use std::collections::{HashMap, HashSet};
+use std::string::String;
+use std::vec::Vec;
+
+pub const C_KEYWORDS: &[&str] = &[
+    "auto", "break", "case", "char", "const", "continue", "default", "do", "double", "else", "enum", "extern",
+    "float", "for", "goto", "if", "inline", "int", "long", "register", "restrict", "return", "short", "signed",
+    "sizeof", "static", "struct", "switch", "typedef", "union", "unsigned", "void", "volatile", "while", "_Alignas",
+    "_Alignof", "_Atomic", "_Bool", "_Complex", "_Generic", "_Imaginary", "_Noreturn", "_Static_assert",
+    "_Thread_local", "alignas", "alignof", "bool", "constexpr", "false", "nullptr", "static_assert",
+    "thread_local", "true", "typeof", "typeof_unqual",
+];
+
+pub const CPP_KEYWORDS: &[&str] = &[
+    "and", "and_eq", "asm", "bitand", "bitor", "catch", "char8_t", "char16_t", "char32_t", "class", "compl",
+    "concept", "consteval", "constinit", "const_cast", "co_await", "co_return", "co_yield", "decltype", "delete",
+    "dynamic_cast", "explicit", "export", "friend", "mutable", "namespace", "new", "noexcept", "not", "not_eq",
+    "operator", "or", "or_eq", "private", "protected", "public", "reinterpret_cast", "requires", "static_cast",
+    "template", "this", "throw", "try", "typeid", "typename", "using", "virtual", "wchar_t", "xor", "xor_eq",
+];
+
+pub const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self",
+    "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while", "abstract",
+    "become", "box", "do", "final", "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Keywords, and the machines every Ragel program has
+pub const RAGEL_KEYWORDS: &[&str] = &[
+    "machine", "action", "alphtype", "access", "variable", "write", "include", "import", "export", "when",
+    "inwhen", "outwhen", "err", "lerr", "eof", "to", "from", "getkey", "prepush", "postpop", "nfaprepush",
+    "nfapostpop", "main", "any", "ascii", "extend", "alpha", "digit", "alnum", "lower", "upper", "xdigit", "cntrl",
+    "graph", "print", "punct", "space", "zlen", "empty", "null",
+];
+
+/// Variables of the Ragel-generated C code
+pub const BACKEND_IDENTIFIERS: &[&str] = &["p", "pe", "cs", "fc", "fpc", "ts", "te", "act", "stack", "top"];
+
+/// Names the `Mangler` escapes in messages' names. Messages name Ragel
+/// machines, and the members of the unions holding any message
+const RESERVED_MESSAGE_NAMES: &[&[&str]] = &[C_KEYWORDS, RAGEL_KEYWORDS];
+
+/// Names the `Mangler` escapes in members' names
+const RESERVED_MEMBER_NAMES: &[&[&str]] = &[C_KEYWORDS, RAGEL_KEYWORDS, BACKEND_IDENTIFIERS];
+
+/// Language which reserves a name, if any. Names reserved by C or Ragel would
+/// break the generated code, unless the `Mangler` escapes them, the rest may
+/// break the code of other backends, or the code using the generated one
+pub fn name_reserved_by(name: &str) -> Option<&'static str> {
+    [
+        ("C", C_KEYWORDS),
+        ("Ragel", RAGEL_KEYWORDS),
+        ("C++", CPP_KEYWORDS),
+        ("Rust", RUST_KEYWORDS),
+        ("the generated parsers", BACKEND_IDENTIFIERS),
+    ]
+    .into_iter()
+    .find(|(_, names)| names.contains(&name))
+    .map(|(language, _)| language)
+}
+
+/// Whether the text is an identifier in C, Rust, and Ragel alike
+pub fn is_identifier(text: &str) -> bool {
+    let mut characters = text.chars();
+
+    matches!(characters.next(), Some(first) if first.is_ascii_alphabetic() || first == '_')
+        && characters.all(|character| character.is_ascii_alphanumeric() || character == '_')
+}
+
+/// Escapes the characters an identifier may not have. Separators turn into
+/// underscores, the rest into their code points, e.g. `rate-%` into
+/// `rate__x25_`. A leading digit is preceded by an underscore
+pub fn escape(name: &str) -> String {
+    let mut ret = String::with_capacity(name.len() + 1);
+
+    if !name.starts_with(|character: char| character.is_ascii_alphabetic() || character == '_') {
+        ret.push('_');
+    }
+
+    for character in name.chars() {
+        match character {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '_' => ret.push(character),
+            ' ' | '-' | '.' | '/' => ret.push('_'),
+            _ => ret.push_str(&format!("_x{0:x}_", u32::from(character))),
+        }
+    }
+
+    ret
+}
+
+/// Turns the names of a description into identifiers. Names which are
+/// identifiers already, and are reserved by no target, are kept verbatim,
+/// unless a prefix is configured
+#[derive(Debug, Clone, Default)]
+pub struct Mangler {
+    /// Prepended to the name of every message, e.g. a vendor prefix keeping
+    /// the generated types apart from the ones of another protocol
+    pub prefix: String,
+}
+
+impl Mangler {
+    /// Mangles the names of messages, which make types, union members, and
+    /// Ragel machines
+    pub fn message_names<'a>(&self, names: impl IntoIterator<Item = &'a str>) -> MangledNames {
+        MangledNames::new(names, &self.prefix, RESERVED_MESSAGE_NAMES)
+    }
+
+    /// Mangles the names of a single scope of members, e.g. the fields of a
+    /// message, which make struct members, variables, and Ragel machines
+    pub fn member_names<'a>(&self, names: impl IntoIterator<Item = &'a str>) -> MangledNames {
+        MangledNames::new(names, "", RESERVED_MEMBER_NAMES)
+    }
+
+    /// Whether a message's name is escaped for being reserved, unprefixed
+    pub fn escapes_message_name(name: &str) -> bool {
+        RESERVED_MESSAGE_NAMES.iter().any(|names| names.contains(&name))
+    }
+
+    /// Whether a member's name is escaped for being reserved
+    pub fn escapes_member_name(name: &str) -> bool {
+        RESERVED_MEMBER_NAMES.iter().any(|names| names.contains(&name))
+    }
+}
+
+/// Identifiers the names of a scope map onto. Escaped names which collide
+/// with another name of the scope are suffixed with a number, `_2` onwards,
+/// in the order of declaration, so the same description always yields the
+/// same identifiers
+#[derive(Debug, Clone, Default)]
+pub struct MangledNames {
+    identifiers: HashMap<String, String>,
+}
+
+impl MangledNames {
+    fn new<'a>(names: impl IntoIterator<Item = &'a str>, prefix: &str, reserved: &[&[&str]]) -> MangledNames {
+        let is_reserved = |identifier: &str| reserved.iter().any(|names| names.contains(&identifier));
+        let mut unique_names = Vec::new();
+
+        for name in names {
+            if !unique_names.contains(&name) {
+                unique_names.push(name);
+            }
+        }
+
+        // Names kept verbatim take precedence over escaped ones
+        let mut taken = unique_names
+            .iter()
+            .map(|name| format!("{0}{1}", prefix, name))
+            .filter(|identifier| is_identifier(identifier) && !is_reserved(identifier))
+            .collect::<HashSet<_>>();
+        let mut identifiers = HashMap::new();
+
+        for name in unique_names {
+            let identifier = format!("{0}{1}", prefix, name);
+
+            if taken.contains(&identifier) {
+                identifiers.insert(name.to_string(), identifier);
+                continue;
+            }
+
+            let mut escaped = escape(&identifier);
+
+            if is_reserved(&escaped) {
+                escaped.push('_');
+            }
+
+            let mut candidate = escaped.clone();
+            let mut suffix = 2usize;
+
+            while taken.contains(&candidate) {
+                candidate = format!("{0}_{1}", escaped, suffix);
+                suffix += 1;
+            }
+
+            taken.insert(candidate.clone());
+            identifiers.insert(name.to_string(), candidate);
+        }
+
+        MangledNames { identifiers }
+    }
+
+    /// The identifier of a name. Names from outside of the scope, e.g. a
+    /// reference to an unknown field, are kept
+    pub fn get(&self, name: &str) -> String {
+        self.identifiers.get(name).cloned().unwrap_or_else(|| name.to_string())
+    }
+}
@@ -1,164 +1,271 @@
+use crate::error::RobustoError;
 use std::io::Write;
 use std::string::String;
 
-pub const NEWLINE: &'static str = "\n";
-pub const INDENT: &'static str = "    ";
+pub const NEWLINE: &str = "\n";
+pub const INDENT: &str = "    ";
 
 pub fn make_indent(indent: usize) -> String {
-    use std::fmt::Write;
     let mut ret = String::with_capacity(INDENT.len());
 
-    for i in 0..indent {
-        write!(&mut ret, "{}", INDENT);
+    for _ in 0..indent {
+        ret.push_str(INDENT);
     }
 
     ret
 }
 
-pub fn append_indent_or_panic<T: std::fmt::Write>(
+/// Makes the first character of an identifier uppercase, e.g. for composing
+/// type names of the form `MessageFieldRecord`
+pub fn capitalize(identifier: &str) -> String {
+    let mut chars = identifier.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Converts a camel case identifier into upper snake case, e.g. for composing
+/// macro names of the form `ROBUSTO_MESSAGE_NAME`
+pub fn to_upper_snake_case(identifier: &str) -> String {
+    let mut ret = String::with_capacity(identifier.len() + 4);
+    let mut previous: Option<char> = None;
+
+    for character in identifier.chars() {
+        if let Some(previous) = previous {
+            if character.is_uppercase() && (previous.is_lowercase() || previous.is_ascii_digit()) {
+                ret.push('_');
+            }
+        }
+
+        ret.extend(character.to_uppercase());
+        previous = Some(character);
+    }
+
+    ret
+}
+
+/// Splits an identifier into lowercase words, at underscores, and where the
+/// case changes, e.g. `HTTPStatus_v2` into `http`, `status`, `v2`
+pub fn split_words(identifier: &str) -> std::vec::Vec<String> {
+    let characters = identifier.chars().collect::<std::vec::Vec<char>>();
+    let mut ret = std::vec::Vec::new();
+    let mut word = String::new();
+
+    for (i, character) in characters.iter().enumerate() {
+        if *character == '_' {
+            if !word.is_empty() {
+                ret.push(std::mem::take(&mut word));
+            }
+
+            continue;
+        }
+
+        if let Some(previous) = word.chars().last() {
+            let next_is_lowercase = characters.get(i + 1).is_some_and(|next| next.is_lowercase());
+            let boundary = character.is_uppercase()
+                && (previous.is_lowercase()
+                    || previous.is_ascii_digit()
+                    || (previous.is_uppercase() && next_is_lowercase));
+
+            if boundary {
+                ret.push(std::mem::take(&mut word));
+            }
+        }
+
+        word.extend(character.to_lowercase());
+    }
+
+    if !word.is_empty() {
+        ret.push(word);
+    }
+
+    ret
+}
+
+/// Converts a unit of measurement into an identifier suffix, e.g. `km/h` into
+/// `km_per_h`. Case is kept, as it tells `mV` and `MV` apart. Characters
+/// having no spelling are dropped
+pub fn unit_to_identifier(unit: &str) -> String {
+    let mut ret = String::with_capacity(unit.len());
+
+    for character in unit.chars() {
+        match character {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => ret.push(character),
+            '/' => ret.push_str("_per_"),
+            '%' => ret.push_str("percent"),
+            '\u{b0}' => ret.push_str("deg"),
+            '\u{b5}' => ret.push('u'),
+            '*' | '.' | ' ' => ret.push('_'),
+            _ => {}
+        }
+    }
+
+    ret.trim_matches('_').to_string()
+}
+
+/// Converts a literal string of the form used in Ragel machines (e.g. `\xfe`)
+/// into the sequence of bytes it matches
+pub fn unescape_literal(literal: &str) -> std::vec::Vec<u8> {
+    let mut ret = std::vec::Vec::new();
+    let bytes = literal.as_bytes();
+    let mut position = 0usize;
+
+    while position < bytes.len() {
+        if bytes[position] != b'\\' || position + 1 == bytes.len() {
+            ret.push(bytes[position]);
+            position += 1;
+
+            continue;
+        }
+
+        let escaped = bytes[position + 1];
+        position += 2;
+
+        match escaped {
+            b'x' => {
+                let end = (position + 2).min(bytes.len());
+                let digits = std::str::from_utf8(&bytes[position..end]).unwrap_or("");
+
+                match u8::from_str_radix(digits, 16) {
+                    Ok(value) => {
+                        ret.push(value);
+                        position = end;
+                    }
+                    Err(_) => ret.push(b'x'),
+                }
+            }
+            b'0' => ret.push(0x00),
+            b'a' => ret.push(0x07),
+            b'b' => ret.push(0x08),
+            b't' => ret.push(b'\t'),
+            b'n' => ret.push(b'\n'),
+            b'v' => ret.push(0x0B),
+            b'f' => ret.push(0x0C),
+            b'r' => ret.push(b'\r'),
+            other => ret.push(other),
+        }
+    }
+
+    ret
+}
+
+pub fn append_indent<T: std::fmt::Write>(
     sink: &mut T,
     indent: usize,
-) {
-    for i in 0..indent {
-        if let Err(_) = write!(sink, "    ") {
-            log::error!("Failed to write into file, panicking!");
-            panic!();
-        }
+) -> Result<(), RobustoError> {
+    for _ in 0..indent {
+        sink.write_str(INDENT)?;
     }
+
+    Ok(())
 }
 
-/// Boilerplate reducer. Writes a line with indent. Panics when unable.
+/// Boilerplate reducer. Writes a line with indent.
 /// It is important to note that it will not seek for newline characters.
-pub fn append_line_with_indent_or_panic<W: std::fmt::Write>(
+pub fn append_line_with_indent<W: std::fmt::Write>(
     sink: &mut W,
     indent: usize,
     line: &[u8],
-) {
-    append_indent_or_panic(sink, indent);
+) -> Result<(), RobustoError> {
+    append_indent(sink, indent)?;
+    sink.write_str(std::str::from_utf8(line)?)?;
+    sink.write_str(NEWLINE)?;
 
-    if let Err(_) = write!(sink, "{}", std::str::from_utf8(line).unwrap()) {
-        log::error!("Failed to write into file, panicking!");
-        panic!();
-    }
-
-    if let Err(_) = write!(sink, "{}", NEWLINE) {
-        log::error!("Failed to write into file, panicking!");
-        panic!();
-    }
+    Ok(())
 }
 
-pub fn append_with_indent_or_panic<W: std::fmt::Write>(
+pub fn append_with_indent<W: std::fmt::Write>(
     sink: &mut W,
     indent: usize,
     lines: &[u8],
-) {
+) -> Result<(), RobustoError> {
     let mut start_position = 0usize;
-    let mut position = 0usize;
     let mut n_newlines = 0usize;
 
-    append_indent_or_panic(sink, indent);
+    append_indent(sink, indent)?;
 
-    for position in 0..lines.len() {
-        if lines[position] == b'\r' || lines[position] == b'\n' {
+    for (position, byte) in lines.iter().enumerate() {
+        if *byte == b'\r' || *byte == b'\n' {
             n_newlines += 1;
         } else if n_newlines > 0 {
-
-            if let Err(_) = write!(sink, "{}", std::str::from_utf8(&lines[start_position..position]).unwrap()) {
-                log::error!("Failed to write into file, panicking!");
-                panic!();
-            }
-
-            append_indent_or_panic(sink, indent);
+            sink.write_str(std::str::from_utf8(&lines[start_position..position])?)?;
+            append_indent(sink, indent)?;
 
             start_position = position;
             n_newlines = 0;
         }
     }
 
-    if (start_position < lines.len()) {
-        if let Err(_) = write!(sink, "{}", std::str::from_utf8(&lines[start_position..lines.len()]).unwrap()) {
-            log::error!("Failed to write into file, panicking!");
-            panic!();
-        }
+    if start_position < lines.len() {
+        sink.write_str(std::str::from_utf8(&lines[start_position..lines.len()])?)?;
     }
+
+    Ok(())
 }
 
-pub fn write_indent_or_panic<W: std::io::Write>(
+pub fn write_indent<W: std::io::Write>(
     buf_writer: &mut std::io::BufWriter<W>,
     indent: usize,
-) {
-    for i in 0..indent {
-        if let Err(_) = buf_writer.write(INDENT.as_bytes()) {
-            log::error!("Failed to write into file, panicking!");
-            panic!();
-        }
+) -> Result<(), RobustoError> {
+    for _ in 0..indent {
+        buf_writer.write_all(INDENT.as_bytes())?;
     }
+
+    Ok(())
 }
 
-/// Boilerplate reducer. Writes a line with indent. Panics when unable.
+/// Boilerplate reducer. Writes a line with indent.
 /// It is important to note that it will not seek for newline characters.
-pub fn write_line_with_indent_or_panic<W: std::io::Write>(
+pub fn write_line_with_indent<W: std::io::Write>(
     buf_writer: &mut std::io::BufWriter<W>,
     indent: usize,
     line: &[u8],
-) {
-    write_indent_or_panic(buf_writer, indent);
-
-    if let Err(_) = buf_writer.write(line) {
-        log::error!("Failed to write into file, panicking!");
-        panic!();
-    }
+) -> Result<(), RobustoError> {
+    write_indent(buf_writer, indent)?;
+    buf_writer.write_all(line)?;
+    buf_writer.write_all(NEWLINE.as_bytes())?;
 
-    if let Err(_) = buf_writer.write(NEWLINE.as_bytes()) {
-        log::error!("Failed to write into file, panicking!");
-        panic!();
-    }
+    Ok(())
 }
 
-pub fn write_with_indent_or_panic<W: std::io::Write>(
+pub fn write_with_indent<W: std::io::Write>(
     buf_writer: &mut std::io::BufWriter<W>,
     indent: usize,
     lines: &[u8],
-) {
+) -> Result<(), RobustoError> {
     let mut start_position = 0usize;
-    let mut position = 0usize;
     let mut n_newlines = 0usize;
 
-    write_indent_or_panic(buf_writer, indent);
+    write_indent(buf_writer, indent)?;
 
-    for position in 0..lines.len() {
-        if lines[position] == b'\r' || lines[position] == b'\n' {
+    for (position, byte) in lines.iter().enumerate() {
+        if *byte == b'\r' || *byte == b'\n' {
             n_newlines += 1;
         } else if n_newlines > 0 {
-
-            if let Err(_) = buf_writer.write(&lines[start_position..position]) {
-                log::error!("Failed to write into file, panicking!");
-                panic!();
-            }
-
-            write_indent_or_panic(buf_writer, indent);
+            buf_writer.write_all(&lines[start_position..position])?;
+            write_indent(buf_writer, indent)?;
 
             start_position = position;
             n_newlines = 0;
         }
     }
 
-    if (start_position < lines.len()) {
-        if let Err(_) = buf_writer.write(&lines[start_position..lines.len()]) {
-            log::error!("Failed to write into file, panicking!");
-            panic!();
-        }
+    if start_position < lines.len() {
+        buf_writer.write_all(&lines[start_position..lines.len()])?;
     }
+
+    Ok(())
 }
 
-pub fn write_newlines_or_panic<W: std::io::Write>(
+pub fn write_newlines<W: std::io::Write>(
     buf_writer: &mut std::io::BufWriter<W>,
     newlines: usize,
-) {
-    for i in 0..newlines {
-        if let Err(_) = buf_writer.write(NEWLINE.as_bytes()) {
-            log::error!("Failed to write into file, panicking!");
-            panic!();
-        }
+) -> Result<(), RobustoError> {
+    for _ in 0..newlines {
+        buf_writer.write_all(NEWLINE.as_bytes())?;
     }
+
+    Ok(())
 }
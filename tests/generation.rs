@@ -0,0 +1,181 @@
+//! Code generation tests: properties of the generated code, and of the data
+//! it is derived from, which the golden files do not make evident.
+
+use robusto::bpir::representation::{Protocol, RagelCodeStyle};
+use robusto::parser_generation::artifacts::{ArtifactNaming, ArtifactSettings, GeneratedArtifactSet};
+use robusto::parser_generation::ragel::common::{
+    BenchmarkPattern, CannedValues, Dispatcher, SelfTestFunction, SelfTestMessage,
+};
+
+fn protocol(json: &str) -> Protocol {
+    Protocol::from_json(json).unwrap().resolve_message_extensions().resolve_group_definitions()
+}
+
+fn benchmark_patterns(protocol: &Protocol, message_name: &str) -> Vec<BenchmarkPattern> {
+    let message = protocol.messages.iter().find(|message| message.name == message_name).unwrap();
+    BenchmarkPattern::from_message(protocol, message)
+}
+
+#[test]
+fn benchmark_patterns_of_a_checksummed_frame() {
+    let protocol = protocol(include_str!("golden/protocols/benchmark.json"));
+    let patterns = benchmark_patterns(&protocol, "Reading");
+    let descriptions = patterns.iter().map(|pattern| pattern.description.as_str()).collect::<Vec<_>>();
+
+    assert_eq!(
+        descriptions,
+        ["valid frame", "checksum mismatch at the last byte", "near-miss sync sequences"]
+    );
+
+    // Only the checksum's last byte differs, so every other byte runs the field actions
+    let (valid, rejected) = (&patterns[0].input, &patterns[1].input);
+    assert_eq!(valid, &[0xAA, 0x55, 0xCD, 0xEF, 0x71, 0xC6]);
+    assert_eq!(valid.len(), rejected.len());
+    assert_eq!(valid[..valid.len() - 1], rejected[..rejected.len() - 1]);
+    assert_eq!(valid.last().unwrap() ^ 0xFF, *rejected.last().unwrap());
+
+    // The sync sequence never completes, over the longest frame and the sequence
+    let near_miss = &patterns[2].input;
+    let message = protocol.messages.iter().find(|message| message.name == "Reading").unwrap();
+    assert_eq!(near_miss.len(), message.max_encoded_length() + 2);
+    assert!(!near_miss.windows(2).any(|window| window == [0xAA, 0x55]));
+}
+
+#[test]
+fn benchmark_patterns_of_a_fixed_length_frame() {
+    let protocol = protocol(include_str!("golden/protocols/benchmark.json"));
+    let patterns = benchmark_patterns(&protocol, "Ping");
+    let descriptions = patterns.iter().map(|pattern| pattern.description.as_str()).collect::<Vec<_>>();
+
+    // A single-byte sync sequence has no near miss
+    assert_eq!(descriptions, ["valid frame", "extra byte past the fixed length"]);
+
+    let mut overlong = patterns[0].input.clone();
+    overlong.push(0u8);
+    assert_eq!(patterns[1].input, overlong);
+}
+
+#[test]
+fn byte_stuffing_round_trip() {
+    let protocol = protocol(include_str!("golden/protocols/byte_stuffing.json"));
+    let byte_stuffing = protocol.byte_stuffing().unwrap();
+    let message = &protocol.messages[0];
+    let frame = SelfTestMessage::from_message(message, None, None, CannedValues::Distinct).unwrap().frame;
+    let wire = &SelfTestFunction::from(&protocol).messages[0].frame;
+
+    // The payload carries both an escaped byte and the escape byte itself
+    assert_eq!(frame, [0x7E, 0xEF, 0xCD, 0xAB]);
+    assert_eq!(wire, &[0x7E, 0xEF, 0xAB, 0xED, 0xAB, 0x8B]);
+
+    // Destuffed the way the generated parsers do, see the golden source
+    let mut destuffed = Vec::new();
+    let mut escape_pending = false;
+
+    for byte in wire {
+        if escape_pending {
+            escape_pending = false;
+            destuffed.push(byte ^ byte_stuffing.xor);
+        } else if *byte == byte_stuffing.escape {
+            escape_pending = true;
+        } else {
+            destuffed.push(*byte);
+        }
+    }
+
+    assert_eq!(destuffed, frame);
+
+    let naming = ArtifactNaming::default();
+    let artifact_set = GeneratedArtifactSet::from_protocol(&protocol, &naming, &ArtifactSettings::default()).unwrap();
+    let source = &artifact_set.get(&naming.ragel_source()).unwrap().text;
+
+    assert!(source.contains("destuffed = (unsigned char)(destuffed ^ 0x20u);"));
+    assert!(source.contains("} else if (destuffed == 0xABu) {"));
+}
+
+#[test]
+fn dispatch_follows_the_protocol_version() {
+    let protocol = protocol(include_str!("golden/protocols/versions.json"));
+
+    for (version, status_length, status_members, absent_member) in [
+        (1, 3, ["battery", "legacyFlags"], "temperature"),
+        (2, 4, ["battery", "temperature"], "legacyFlags"),
+    ] {
+        let revision = protocol.revision(version);
+        let dispatcher = Dispatcher::from_protocol(&revision).unwrap();
+        let dispatched = dispatcher
+            .messages
+            .iter()
+            .map(|message| (message.message_name.as_str(), message.id))
+            .collect::<Vec<_>>();
+
+        // The IDs are shared by the versions, the frames they select differ
+        assert_eq!(dispatched, [("Status", 1), ("Command", 2)]);
+        assert_eq!(dispatcher.id_length, 1);
+        assert_eq!(revision.messages[0].max_encoded_length(), status_length);
+
+        let naming = ArtifactNaming::default();
+        let artifact_set =
+            GeneratedArtifactSet::from_protocol(&revision, &naming, &ArtifactSettings::default()).unwrap();
+        let header = &artifact_set.get(&naming.header()).unwrap().text;
+        let status = &header[header.find("struct StatusMessage {").unwrap()..];
+        let status = &status[..status.find("};").unwrap()];
+
+        for member in status_members {
+            assert!(status.contains(&format!(" {member};")), "version {version} lacks {member}");
+        }
+
+        assert!(!status.contains(absent_member), "version {version} has {absent_member}");
+    }
+}
+
+#[test]
+fn ragel_directives_follow_the_options() {
+    let protocol = protocol(include_str!("golden/protocols/ragel.json"));
+    let naming = ArtifactNaming::default();
+    let artifact_set = GeneratedArtifactSet::from_protocol(&protocol, &naming, &ArtifactSettings::default()).unwrap();
+    let source = &artifact_set.get(&naming.ragel_source()).unwrap().text;
+
+    assert!(source.contains("write data noentry noerror;"));
+    assert!(source.contains("#define Reading_error 0"));
+    assert!(source.contains("%% write init nocs;"));
+    assert!(!source.contains("%% write init;"));
+
+    // The styles the documentation weighs against each other
+    assert_eq!(RagelCodeStyle::T0.flag(), "-T0");
+    assert_eq!(RagelCodeStyle::F1.flag(), "-F1");
+    assert_eq!(RagelCodeStyle::G2.flag(), "-G2");
+}
+
+#[test]
+fn symbol_prefix_spares_members_literals_and_comments() {
+    // The field, and so the struct member, is named after the self-test routine
+    let protocol = protocol(
+        r#"{
+            "messages": [
+                {
+                    "name": "Log",
+                    "fields": [
+                        {
+                            "name": "selfTest",
+                            "field_type": {"Integer": {"bit_width": 8, "signed": false}},
+                            "attributes": [{"Doc": "Outcome of the last selfTest() run"}]
+                        }
+                    ],
+                    "attributes": []
+                }
+            ],
+            "attributes": [{"SymbolNaming": {"symbol_prefix": "acme_"}}, "SelfTest", "DebugDump"]
+        }"#,
+    );
+    let naming = ArtifactNaming::default();
+    let artifact_set = GeneratedArtifactSet::from_protocol(&protocol, &naming, &ArtifactSettings::default()).unwrap();
+    let header = &artifact_set.get(&naming.header()).unwrap().text;
+    let source = &artifact_set.get(&naming.ragel_source()).unwrap().text;
+
+    assert!(header.contains("int acme_selfTest(void);"));
+    assert!(header.contains("    uint8_t selfTest;"));
+    assert!(header.contains("Outcome of the last selfTest() run"));
+    assert!(source.contains("int acme_selfTest(void)\n"));
+    assert!(source.contains("\"  selfTest: %lu\\n\""));
+    assert!(!source.contains(".acme_selfTest") && !source.contains("->acme_selfTest"));
+}
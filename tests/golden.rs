@@ -0,0 +1,190 @@
+//! Golden-output tests: every reference protocol in `tests/golden/protocols`
+//! is rendered through each backend, and the artifacts are compared against
+//! the files checked in under `tests/golden/<protocol>`.
+//!
+//! After an intended change to the generated code, run the tests with
+//! `ROBUSTO_BLESS=1` to rewrite the golden files, and review the diff.
+
+use robusto::bpir::representation::{Protocol, TargetLanguage};
+use robusto::parser_generation::post_processing::{block_comments, render, SymbolPrefix};
+use robusto::parser_generation::{constants, ragel::c, ragel::cpp, runtime, typescript};
+use robusto::utility::identifier::Mangler;
+use std::path::{Path, PathBuf};
+
+/// Setting it to anything but "0" rewrites the golden files
+const BLESS_VARIABLE: &str = "ROBUSTO_BLESS";
+
+fn golden_directory() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+fn bless() -> bool {
+    std::env::var(BLESS_VARIABLE).is_ok_and(|value| value != "0")
+}
+
+/// Renders every artifact the protocol asks for, by file name
+fn render_artifacts(protocol: &Protocol) -> Vec<(String, String)> {
+    // C code is written in the protocol's revision of C, as `GeneratedArtifactSet` writes it
+    let c_code = |text: String| match protocol.c_standard().line_comments() {
+        true => text,
+        false => block_comments(&text),
+    };
+    let mut ret = vec![
+        ("output.c.rl".to_string(), c_code(render(&c::SourceAstNode::from(protocol)).unwrap())),
+        ("output.h".to_string(), c_code(render(&c::HeaderAstNode::from(protocol)).unwrap())),
+        ("output.api.txt".to_string(), render(&c::ApiReport::from(protocol)).unwrap()),
+        ("output.api.manifest".to_string(), render(&c::ApiManifest::from(protocol)).unwrap()),
+    ];
+
+    if let Some(cpp_options) = protocol.cpp() {
+        ret.push((
+            "output.hpp".to_string(),
+            render(&cpp::CppHeaderAstNode::from_protocol(protocol, cpp_options, "output.hpp", "output.h")).unwrap(),
+        ));
+    }
+
+    if protocol.typescript() {
+        ret.push((
+            "output.ts".to_string(),
+            render(&typescript::ModuleAstNode::from(protocol)).unwrap(),
+        ));
+    }
+
+    let shared_constants = constants::SharedConstants::from(protocol);
+
+    for language in protocol.shared_constants_languages() {
+        let artifact = constants::SharedConstantsArtifact {
+            constants: &shared_constants,
+            language: *language,
+        };
+        let text = render(&artifact).unwrap();
+        ret.push((
+            format!("output.constants.{0}", constants::file_extension(*language)),
+            match language {
+                TargetLanguage::C => c_code(text),
+                _ => text,
+            },
+        ));
+    }
+
+    if let Some(output_splitting) = protocol.output_splitting() {
+        for output_file in c::split_output(protocol, output_splitting, "split") {
+            let text = c_code(render(&output_file).unwrap());
+            ret.push((output_file.name, text));
+        }
+    }
+
+    // As `GeneratedArtifactSet` does, symbols are prefixed before the runtime library is added
+    let symbol_prefix = protocol.symbol_naming().symbol_prefix;
+
+    if !symbol_prefix.is_empty() {
+        let symbol_prefix = SymbolPrefix::from_c_code(
+            &symbol_prefix,
+            ret.iter()
+                .filter(|(file_name, _)| file_name.ends_with(".h") || file_name.ends_with(".rl"))
+                .map(|(_, text)| text.as_str()),
+        );
+
+        for (_, text) in &mut ret {
+            *text = symbol_prefix.apply(text);
+        }
+    }
+
+    if protocol.uses_runtime() {
+        let library = runtime::RuntimeLibrary::from_protocols(&[protocol]);
+        ret.push((
+            runtime::HEADER_FILE_NAME.to_string(),
+            c_code(render(&runtime::RuntimeHeader { library: &library }).unwrap()),
+        ));
+        ret.push((
+            runtime::SOURCE_FILE_NAME.to_string(),
+            c_code(render(&runtime::RuntimeSource { library: &library }).unwrap()),
+        ));
+    }
+
+    ret
+}
+
+/// Describes the first line the texts differ in
+fn first_difference(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+    let mut line = 1usize;
+
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(expected_line), Some(actual_line)) if expected_line == actual_line => line += 1,
+            (expected_line, actual_line) => {
+                return format!(
+                    "line {0}:\n    expected: {1:?}\n    actual:   {2:?}",
+                    line, expected_line, actual_line
+                )
+            }
+        }
+    }
+}
+
+/// Compares the protocol's artifacts against the golden files, or rewrites
+/// them. Returns the mismatches
+fn check_protocol(protocol_file: &Path) -> Vec<String> {
+    let name = protocol_file.file_stem().unwrap().to_string_lossy().to_string();
+    let protocol = Protocol::from_json(&std::fs::read_to_string(protocol_file).unwrap())
+        .unwrap()
+        .resolve_message_extensions()
+        .resolve_group_definitions();
+
+    // Protocols covering several versions are narrowed to the one they record
+    let protocol = match protocol.version() {
+        Some(version) => protocol.revision(version),
+        None => protocol,
+    }
+    .mangle_identifiers(&Mangler::default());
+    let directory = golden_directory().join(&name);
+    let mut ret = Vec::new();
+
+    if bless() {
+        std::fs::create_dir_all(&directory).unwrap();
+    }
+
+    for (file_name, actual) in render_artifacts(&protocol) {
+        let path = directory.join(&file_name);
+
+        if bless() {
+            std::fs::write(&path, actual).unwrap();
+            continue;
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => {
+                ret.push(format!("{0}/{1} differs, {2}", name, file_name, first_difference(&expected, &actual)))
+            }
+            Err(error) => ret.push(format!("{0}/{1} is unreadable: {2}", name, file_name, error)),
+        }
+    }
+
+    ret
+}
+
+#[test]
+fn golden_outputs() {
+    let mut protocol_files = std::fs::read_dir(golden_directory().join("protocols"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .collect::<Vec<_>>();
+    protocol_files.sort();
+    assert!(!protocol_files.is_empty());
+
+    let mismatches = protocol_files
+        .iter()
+        .flat_map(|protocol_file| check_protocol(protocol_file))
+        .collect::<Vec<_>>();
+
+    assert!(
+        mismatches.is_empty(),
+        "The generated code differs from the golden files. If it is intended, rerun with {0}=1\n\n{1}",
+        BLESS_VARIABLE,
+        mismatches.join("\n\n")
+    );
+}
@@ -0,0 +1,162 @@
+#include "output.h"
+
+// =============================================================================
+// region TestMessage parser
+// =============================================================================
+
+%%{
+    machine TestMessage;
+    write data;
+}%%
+void machineTestMessageParserStateInit(struct TestMessageParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine TestMessage;
+    access aParserState->;
+    alphtype unsigned char;
+    action preamble {
+    }
+    action preamble_reset {
+        aTestMessage->preambleLength = 0;
+    }
+    action preamble_byte {
+        if (aTestMessage->preambleLength < 64u) {
+            aTestMessage->preamble[aTestMessage->preambleLength++] = (uint8_t)fc;
+        }
+    }
+    action value {
+    }
+    action value_byte {
+        aTestMessage->value = (uint16_t)(((uint16_t)aTestMessage->value << 8) | (uint8_t)fc);
+    }
+    preamble = 0xFE >preamble_reset $preamble_byte @preamble; 
+    value = any{2} $value_byte @value; 
+    main := preamble value;
+}%%
+static enum RobustoParseStatus robustoParseStatusTestMessage(const struct TestMessageParserState *aParserState)
+{
+    if (aParserState->cs >= TestMessage_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != TestMessage_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseTestMessage(struct TestMessageParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct TestMessageMessage *aTestMessage, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineTestMessageParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusTestMessage(aParserState);
+}
+int isTestMessageAccepted(const struct TestMessageParserState *aParserState)
+{
+    return aParserState->cs >= TestMessage_first_final;
+}
+// endregion TestMessage parser
+
+// =============================================================================
+// region Self-test
+// =============================================================================
+
+int selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0xFE, 0xAB, 0xCD};
+        struct TestMessageParserState parserState;
+        struct TestMessageMessage message = {0};
+        struct TestMessageMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineTestMessageParserStateInit(&parserState);
+
+        if (parseTestMessage(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.value != 43981u) {
+            return 1;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseTestMessage(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.value != 43981u) {
+            return 1;
+        }
+    }
+    {
+        // Byte order: multi-byte fields carry 0x01, 0x02, ... on the wire, and decode the same on any host
+        static const uint8_t kFrame[] = {0xFE, 0x01, 0x02};
+        struct TestMessageParserState parserState;
+        struct TestMessageMessage message = {0};
+        struct TestMessageMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineTestMessageParserStateInit(&parserState);
+
+        if (parseTestMessage(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (message.value != 0x102u) {
+            return 2;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseTestMessage(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (splitMessage.value != 0x102u) {
+            return 2;
+        }
+    }
+
+    return 0;
+}
+// endregion Self-test
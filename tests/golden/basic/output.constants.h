@@ -0,0 +1,34 @@
+// Parse error code: no error
+#define ROBUSTO_ERROR_CODE_NONE 0u
+// Parse error code: field value out of range
+#define ROBUSTO_ERROR_CODE_VALUE_OUT_OF_RANGE 1u
+// Parse error code: frame longer than the message's fixed length
+#define ROBUSTO_ERROR_CODE_FRAME_LENGTH 2u
+// Parse error code: checksum mismatch
+#define ROBUSTO_ERROR_CODE_CHECKSUM 3u
+// Parse error code: length field mismatch
+#define ROBUSTO_ERROR_CODE_LENGTH 4u
+// Parse error code: field check failed
+#define ROBUSTO_ERROR_CODE_CHECK 5u
+// Parse error code: field longer than its max length
+#define ROBUSTO_ERROR_CODE_OVERFLOW 6u
+// Parse status: frame complete
+#define ROBUSTO_PARSE_STATUS_OK 0u
+// Parse status: frame incomplete
+#define ROBUSTO_PARSE_STATUS_INCOMPLETE 1u
+// Parse status: frame rejected on a checksum mismatch
+#define ROBUSTO_PARSE_STATUS_ERROR_CRC 2u
+// Parse status: frame rejected on a field value out of range
+#define ROBUSTO_PARSE_STATUS_ERROR_RANGE 3u
+// Parse status: frame rejected on a field longer than its max length
+#define ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW 4u
+// Parse status: frame rejected
+#define ROBUSTO_PARSE_STATUS_ERROR 5u
+// Min length of a frame of message TestMessage, in bytes
+#define ROBUSTO_TEST_MESSAGE_MIN_FRAME_SIZE 3u
+// Max length of a frame of message TestMessage, in bytes
+#define ROBUSTO_TEST_MESSAGE_MAX_FRAME_SIZE 3u
+// Min length of a frame of any message, in bytes
+#define ROBUSTO_MIN_FRAME_SIZE 3u
+// Max length of a frame of any message, in bytes
+#define ROBUSTO_MAX_FRAME_SIZE 3u
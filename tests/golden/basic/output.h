@@ -0,0 +1,82 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region TestMessage types
+// =============================================================================
+
+struct TestMessageMessage {
+    uint8_t preamble[64];
+    uint32_t preambleLength;
+    uint16_t value;
+};
+struct TestMessageParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+// endregion TestMessage types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct TestMessageMessage TestMessage;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_TEST_MESSAGE_MIN_FRAME_SIZE 3u
+#define ROBUSTO_TEST_MESSAGE_MAX_FRAME_SIZE 3u
+#define ROBUSTO_MIN_FRAME_SIZE 3u
+#define ROBUSTO_MAX_FRAME_SIZE 3u
+// endregion Sizing constants
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineTestMessageParserStateInit(struct TestMessageParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aTestMessage
+enum RobustoParseStatus parseTestMessage(struct TestMessageParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct TestMessageMessage *aTestMessage, int *aConsumedLength);
+// Non-null: aParserState
+int isTestMessageAccepted(const struct TestMessageParserState *aParserState);
+int selfTest(void);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
@@ -0,0 +1,323 @@
+#include "output.h"
+
+// =============================================================================
+// region Checksums
+// =============================================================================
+
+static const uint16_t kRobustoCrc16_1021Table[256] = {
+    0x0000u, 0x1021u, 0x2042u, 0x3063u, 0x4084u, 0x50A5u, 0x60C6u, 0x70E7u,
+    0x8108u, 0x9129u, 0xA14Au, 0xB16Bu, 0xC18Cu, 0xD1ADu, 0xE1CEu, 0xF1EFu,
+    0x1231u, 0x0210u, 0x3273u, 0x2252u, 0x52B5u, 0x4294u, 0x72F7u, 0x62D6u,
+    0x9339u, 0x8318u, 0xB37Bu, 0xA35Au, 0xD3BDu, 0xC39Cu, 0xF3FFu, 0xE3DEu,
+    0x2462u, 0x3443u, 0x0420u, 0x1401u, 0x64E6u, 0x74C7u, 0x44A4u, 0x5485u,
+    0xA56Au, 0xB54Bu, 0x8528u, 0x9509u, 0xE5EEu, 0xF5CFu, 0xC5ACu, 0xD58Du,
+    0x3653u, 0x2672u, 0x1611u, 0x0630u, 0x76D7u, 0x66F6u, 0x5695u, 0x46B4u,
+    0xB75Bu, 0xA77Au, 0x9719u, 0x8738u, 0xF7DFu, 0xE7FEu, 0xD79Du, 0xC7BCu,
+    0x48C4u, 0x58E5u, 0x6886u, 0x78A7u, 0x0840u, 0x1861u, 0x2802u, 0x3823u,
+    0xC9CCu, 0xD9EDu, 0xE98Eu, 0xF9AFu, 0x8948u, 0x9969u, 0xA90Au, 0xB92Bu,
+    0x5AF5u, 0x4AD4u, 0x7AB7u, 0x6A96u, 0x1A71u, 0x0A50u, 0x3A33u, 0x2A12u,
+    0xDBFDu, 0xCBDCu, 0xFBBFu, 0xEB9Eu, 0x9B79u, 0x8B58u, 0xBB3Bu, 0xAB1Au,
+    0x6CA6u, 0x7C87u, 0x4CE4u, 0x5CC5u, 0x2C22u, 0x3C03u, 0x0C60u, 0x1C41u,
+    0xEDAEu, 0xFD8Fu, 0xCDECu, 0xDDCDu, 0xAD2Au, 0xBD0Bu, 0x8D68u, 0x9D49u,
+    0x7E97u, 0x6EB6u, 0x5ED5u, 0x4EF4u, 0x3E13u, 0x2E32u, 0x1E51u, 0x0E70u,
+    0xFF9Fu, 0xEFBEu, 0xDFDDu, 0xCFFCu, 0xBF1Bu, 0xAF3Au, 0x9F59u, 0x8F78u,
+    0x9188u, 0x81A9u, 0xB1CAu, 0xA1EBu, 0xD10Cu, 0xC12Du, 0xF14Eu, 0xE16Fu,
+    0x1080u, 0x00A1u, 0x30C2u, 0x20E3u, 0x5004u, 0x4025u, 0x7046u, 0x6067u,
+    0x83B9u, 0x9398u, 0xA3FBu, 0xB3DAu, 0xC33Du, 0xD31Cu, 0xE37Fu, 0xF35Eu,
+    0x02B1u, 0x1290u, 0x22F3u, 0x32D2u, 0x4235u, 0x5214u, 0x6277u, 0x7256u,
+    0xB5EAu, 0xA5CBu, 0x95A8u, 0x8589u, 0xF56Eu, 0xE54Fu, 0xD52Cu, 0xC50Du,
+    0x34E2u, 0x24C3u, 0x14A0u, 0x0481u, 0x7466u, 0x6447u, 0x5424u, 0x4405u,
+    0xA7DBu, 0xB7FAu, 0x8799u, 0x97B8u, 0xE75Fu, 0xF77Eu, 0xC71Du, 0xD73Cu,
+    0x26D3u, 0x36F2u, 0x0691u, 0x16B0u, 0x6657u, 0x7676u, 0x4615u, 0x5634u,
+    0xD94Cu, 0xC96Du, 0xF90Eu, 0xE92Fu, 0x99C8u, 0x89E9u, 0xB98Au, 0xA9ABu,
+    0x5844u, 0x4865u, 0x7806u, 0x6827u, 0x18C0u, 0x08E1u, 0x3882u, 0x28A3u,
+    0xCB7Du, 0xDB5Cu, 0xEB3Fu, 0xFB1Eu, 0x8BF9u, 0x9BD8u, 0xABBBu, 0xBB9Au,
+    0x4A75u, 0x5A54u, 0x6A37u, 0x7A16u, 0x0AF1u, 0x1AD0u, 0x2AB3u, 0x3A92u,
+    0xFD2Eu, 0xED0Fu, 0xDD6Cu, 0xCD4Du, 0xBDAAu, 0xAD8Bu, 0x9DE8u, 0x8DC9u,
+    0x7C26u, 0x6C07u, 0x5C64u, 0x4C45u, 0x3CA2u, 0x2C83u, 0x1CE0u, 0x0CC1u,
+    0xEF1Fu, 0xFF3Eu, 0xCF5Du, 0xDF7Cu, 0xAF9Bu, 0xBFBAu, 0x8FD9u, 0x9FF8u,
+    0x6E17u, 0x7E36u, 0x4E55u, 0x5E74u, 0x2E93u, 0x3EB2u, 0x0ED1u, 0x1EF0u,
+};
+
+uint16_t robustoCrc16_1021(uint16_t aCrc, uint8_t aByte)
+{
+    return (uint16_t)((aCrc << 8) ^ kRobustoCrc16_1021Table[((aCrc >> 8) ^ aByte) & 0xFFu]);
+}
+// endregion Checksums
+
+// =============================================================================
+// region Reading parser
+// =============================================================================
+
+%%{
+    machine Reading;
+    write data;
+}%%
+void machineReadingParserStateInit(struct ReadingParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->frameChecksum = 0xFFFFu;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Reading;
+    access aParserState->;
+    alphtype unsigned char;
+    action value {
+    }
+    action value_byte {
+        aReading->value = (uint16_t)(((uint16_t)aReading->value << 8) | (uint8_t)fc);
+    }
+    action crc {
+        if (aReading->crc != aParserState->frameChecksum) {
+            aParserState->error = ROBUSTO_PARSE_ERROR_CHECKSUM;
+            fgoto *Reading_error;
+        }
+    }
+    action frame_checksum {
+        aParserState->frameChecksum = robustoCrc16_1021(aParserState->frameChecksum, (uint8_t)fc);
+    }
+    action crc_byte {
+        aReading->crc = (uint16_t)(((uint16_t)aReading->crc << 8) | (uint8_t)fc);
+    }
+    value = any{2} $value_byte @value; 
+    crc = any{2} $crc_byte @crc; 
+    main := ( any* :>> ( 0xAA 0x55 ) ) ( value $frame_checksum ) crc;
+}%%
+static enum RobustoParseStatus robustoParseStatusReading(const struct ReadingParserState *aParserState)
+{
+    if (aParserState->cs >= Reading_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Reading_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseReading(struct ReadingParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct ReadingMessage *aReading, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineReadingParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    for (;;) {
+        %% write exec;
+
+        if (aParserState->cs != Reading_error || p == pe) {
+            break;
+        }
+
+        // Resynchronize on the rest of the input
+        machineReadingParserStateInit(aParserState);
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusReading(aParserState);
+}
+int isReadingAccepted(const struct ReadingParserState *aParserState)
+{
+    return aParserState->cs >= Reading_first_final;
+}
+// endregion Reading parser
+
+// =============================================================================
+// region Ping parser
+// =============================================================================
+
+%%{
+    machine Ping;
+    write data;
+}%%
+void machinePingParserStateInit(struct PingParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->frameLength = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Ping;
+    access aParserState->;
+    alphtype unsigned char;
+    action sequence {
+    }
+    action sequence_byte {
+        aPing->sequence = (uint8_t)(((uint8_t)aPing->sequence << 8) | (uint8_t)fc);
+    }
+    sequence = any{1} $sequence_byte @sequence; 
+    main := ( any* :>> ( 0x70 ) ) sequence;
+}%%
+static enum RobustoParseStatus robustoParseStatusPing(const struct PingParserState *aParserState)
+{
+    if (aParserState->cs >= Ping_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Ping_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parsePing(struct PingParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct PingMessage *aPing, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machinePingParserStateInit(aParserState);
+    }
+
+    aParserState->frameLength += (uint32_t)aInputBufferLength;
+
+    if (aParserState->frameLength > 2u) {
+        aParserState->error = ROBUSTO_PARSE_ERROR_FRAME_LENGTH;
+        aParserState->cs = Ping_error;
+        if (aConsumedLength != 0) {
+            *aConsumedLength = 0;
+        }
+        return robustoParseStatusPing(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    for (;;) {
+        %% write exec;
+
+        if (aParserState->cs != Ping_error || p == pe) {
+            break;
+        }
+
+        // Resynchronize on the rest of the input
+        machinePingParserStateInit(aParserState);
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusPing(aParserState);
+}
+int isPingAccepted(const struct PingParserState *aParserState)
+{
+    return aParserState->cs >= Ping_first_final;
+}
+// endregion Ping parser
+
+// =============================================================================
+// region Benchmark
+// =============================================================================
+
+void robustoBenchmark(struct RobustoBenchmarkResult *aResults)
+{
+    {
+        static const uint8_t kInput[] = {0xAA, 0x55, 0xCD, 0xEF, 0x71, 0xC6};
+        struct ReadingParserState parserState;
+        struct ReadingMessage message;
+        unsigned long i;
+        uint32_t start = cycleCount();
+
+        for (i = 0; i < 1000ul; ++i) {
+            machineReadingParserStateInit(&parserState);
+            parseReading(&parserState, (const char *)kInput, (int)sizeof(kInput), &message, 0);
+        }
+
+        aResults[0].ticks = (uint32_t)(cycleCount() - start);
+        aResults[0].bytes = (uint32_t)(sizeof(kInput) * 1000ul);
+        aResults[0].pattern = "Reading: valid frame";
+    }
+    {
+        static const uint8_t kInput[] = {0xAA, 0x55, 0xCD, 0xEF, 0x71, 0x39};
+        struct ReadingParserState parserState;
+        struct ReadingMessage message;
+        unsigned long i;
+        uint32_t start = cycleCount();
+
+        for (i = 0; i < 1000ul; ++i) {
+            machineReadingParserStateInit(&parserState);
+            parseReading(&parserState, (const char *)kInput, (int)sizeof(kInput), &message, 0);
+        }
+
+        aResults[1].ticks = (uint32_t)(cycleCount() - start);
+        aResults[1].bytes = (uint32_t)(sizeof(kInput) * 1000ul);
+        aResults[1].pattern = "Reading: checksum mismatch at the last byte";
+    }
+    {
+        static const uint8_t kInput[] = {0xAA, 0xAA, 0xAA, 0xAA, 0xAA, 0xAA};
+        struct ReadingParserState parserState;
+        struct ReadingMessage message;
+        unsigned long i;
+        uint32_t start = cycleCount();
+
+        for (i = 0; i < 1000ul; ++i) {
+            machineReadingParserStateInit(&parserState);
+            parseReading(&parserState, (const char *)kInput, (int)sizeof(kInput), &message, 0);
+        }
+
+        aResults[2].ticks = (uint32_t)(cycleCount() - start);
+        aResults[2].bytes = (uint32_t)(sizeof(kInput) * 1000ul);
+        aResults[2].pattern = "Reading: near-miss sync sequences";
+    }
+    {
+        static const uint8_t kInput[] = {0x70, 0xEF};
+        struct PingParserState parserState;
+        struct PingMessage message;
+        unsigned long i;
+        uint32_t start = cycleCount();
+
+        for (i = 0; i < 1000ul; ++i) {
+            machinePingParserStateInit(&parserState);
+            parsePing(&parserState, (const char *)kInput, (int)sizeof(kInput), &message, 0);
+        }
+
+        aResults[3].ticks = (uint32_t)(cycleCount() - start);
+        aResults[3].bytes = (uint32_t)(sizeof(kInput) * 1000ul);
+        aResults[3].pattern = "Ping: valid frame";
+    }
+    {
+        static const uint8_t kInput[] = {0x70, 0xEF, 0x00};
+        struct PingParserState parserState;
+        struct PingMessage message;
+        unsigned long i;
+        uint32_t start = cycleCount();
+
+        for (i = 0; i < 1000ul; ++i) {
+            machinePingParserStateInit(&parserState);
+            parsePing(&parserState, (const char *)kInput, (int)sizeof(kInput), &message, 0);
+        }
+
+        aResults[4].ticks = (uint32_t)(cycleCount() - start);
+        aResults[4].bytes = (uint32_t)(sizeof(kInput) * 1000ul);
+        aResults[4].pattern = "Ping: extra byte past the fixed length";
+    }
+}
+// endregion Benchmark
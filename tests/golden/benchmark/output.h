@@ -0,0 +1,122 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region Reading types
+// =============================================================================
+
+struct ReadingMessage {
+    uint16_t value;
+    uint16_t crc;
+};
+struct ReadingParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint16_t frameChecksum;
+};
+// endregion Reading types
+
+// =============================================================================
+// region Ping types
+// =============================================================================
+
+struct PingMessage {
+    uint8_t sequence;
+};
+struct PingParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint32_t frameLength;
+};
+// endregion Ping types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct ReadingMessage Reading;
+    struct PingMessage Ping;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_READING_MIN_FRAME_SIZE 6u
+#define ROBUSTO_READING_MAX_FRAME_SIZE 6u
+#define ROBUSTO_PING_MIN_FRAME_SIZE 3u
+#define ROBUSTO_PING_MAX_FRAME_SIZE 3u
+#define ROBUSTO_MIN_FRAME_SIZE 3u
+#define ROBUSTO_MAX_FRAME_SIZE 6u
+// endregion Sizing constants
+
+// =============================================================================
+// region Benchmark
+// =============================================================================
+
+#define ROBUSTO_BENCHMARK_PATTERN_COUNT 5u
+struct RobustoBenchmarkResult {
+    const char *pattern;  // Message and input pattern
+    uint32_t bytes;  // Bytes fed over every repetition
+    uint32_t ticks;  // Clock ticks elapsed
+};
+uint32_t cycleCount(void);
+// endregion Benchmark
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineReadingParserStateInit(struct ReadingParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aReading
+enum RobustoParseStatus parseReading(struct ReadingParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct ReadingMessage *aReading, int *aConsumedLength);
+// Non-null: aParserState
+int isReadingAccepted(const struct ReadingParserState *aParserState);
+// Non-null: aParserState
+void machinePingParserStateInit(struct PingParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aPing
+enum RobustoParseStatus parsePing(struct PingParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct PingMessage *aPing, int *aConsumedLength);
+// Non-null: aParserState
+int isPingAccepted(const struct PingParserState *aParserState);
+uint16_t robustoCrc16_1021(uint16_t aCrc, uint8_t aByte);
+// Non-null: aResults
+void robustoBenchmark(struct RobustoBenchmarkResult *aResults);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
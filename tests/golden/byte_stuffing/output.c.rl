@@ -0,0 +1,169 @@
+#include "output.h"
+
+// =============================================================================
+// region Sample parser
+// =============================================================================
+
+%%{
+    machine Sample;
+    write data;
+}%%
+void machineSampleParserStateInit(struct SampleParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->escapePending = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Sample;
+    access aParserState->;
+    alphtype unsigned char;
+    action channel {
+    }
+    action channel_byte {
+        aSample->channel = (uint8_t)(((uint8_t)aSample->channel << 8) | (uint8_t)fc);
+    }
+    action value {
+    }
+    action value_byte {
+        aSample->value = (uint8_t)(((uint8_t)aSample->value << 8) | (uint8_t)fc);
+    }
+    action flags {
+    }
+    action flags_byte {
+        aSample->flags = (uint8_t)(((uint8_t)aSample->flags << 8) | (uint8_t)fc);
+    }
+    channel = any{1} $channel_byte @channel; 
+    value = any{1} $value_byte @value; 
+    flags = any{1} $flags_byte @flags; 
+    main := ( any* :>> ( 0x7E ) ) channel value flags;
+}%%
+static enum RobustoParseStatus robustoParseStatusSample(const struct SampleParserState *aParserState)
+{
+    if (aParserState->cs >= Sample_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Sample_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseSample(struct SampleParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct SampleMessage *aSample, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineSampleParserStateInit(aParserState);
+    }
+
+    int i;
+
+    // Remove escape sequences before feeding bytes to the state machine
+    for (i = 0; i < aInputBufferLength; ++i) {
+        unsigned char destuffed = (unsigned char)aInputBuffer[i];
+        const unsigned char *p = &destuffed;  // Ragel-specific variable, points to the destuffed byte
+        const unsigned char *pe = p + 1;  // Ragel-specific variable
+
+        if (aParserState->escapePending) {
+            aParserState->escapePending = 0;
+            destuffed = (unsigned char)(destuffed ^ 0x20u);
+        } else if (destuffed == 0xABu) {
+            aParserState->escapePending = 1;
+            continue;
+        }
+
+        for (;;) {
+            %% write exec;
+
+            if (aParserState->cs != Sample_error || p == pe) {
+                break;
+            }
+
+            // Resynchronize on the rest of the input
+            machineSampleParserStateInit(aParserState);
+        }
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = aInputBufferLength;
+    }
+
+    return robustoParseStatusSample(aParserState);
+}
+int isSampleAccepted(const struct SampleParserState *aParserState)
+{
+    return aParserState->cs >= Sample_first_final;
+}
+// endregion Sample parser
+
+// =============================================================================
+// region Self-test
+// =============================================================================
+
+int selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0x7E, 0xEF, 0xAB, 0xED, 0xAB, 0x8B};
+        struct SampleParserState parserState;
+        struct SampleMessage message = {0};
+        struct SampleMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineSampleParserStateInit(&parserState);
+
+        if (parseSample(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.channel != 239u) {
+            return 1;
+        }
+
+        if (message.value != 205u) {
+            return 1;
+        }
+
+        if (message.flags != 171u) {
+            return 1;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseSample(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.channel != 239u) {
+            return 1;
+        }
+
+        if (splitMessage.value != 205u) {
+            return 1;
+        }
+
+        if (splitMessage.flags != 171u) {
+            return 1;
+        }
+    }
+
+    return 0;
+}
+// endregion Self-test
@@ -0,0 +1,146 @@
+#include "output.h"
+
+/* ============================================================================= */
+/* region Frame trace */
+/* ============================================================================= */
+
+struct RobustoTraceRecord robustoTraceRecords[ROBUSTO_TRACE_DEPTH];
+uint32_t robustoTraceCount;  /* Number of traced frames */
+
+/* Copies the latest records, oldest first. Returns the number of copied records */
+unsigned int robustoTraceDump(struct RobustoTraceRecord *aRecords, unsigned int aCapacity)
+{
+    uint32_t n = robustoTraceCount < ROBUSTO_TRACE_DEPTH ? robustoTraceCount : ROBUSTO_TRACE_DEPTH;
+    uint32_t i;
+
+    if (n > aCapacity) {
+        n = aCapacity;
+    }
+
+    for (i = 0; i < n; ++i) {
+        aRecords[i] = robustoTraceRecords[(robustoTraceCount - n + i) % ROBUSTO_TRACE_DEPTH];
+    }
+
+    return (unsigned int)n;
+}
+
+static void robustoTraceFrame(uint8_t aMessageId, uint8_t aAccepted, uint8_t aError, uint32_t aLength)
+{
+    struct RobustoTraceRecord *record = &robustoTraceRecords[robustoTraceCount % ROBUSTO_TRACE_DEPTH];
+
+    record->timestamp = 0u;
+    record->length = aLength;
+    record->messageId = aMessageId;
+    record->accepted = aAccepted;
+    record->error = aError;
+    ++robustoTraceCount;
+}
+/* endregion Frame trace */
+
+/* ============================================================================= */
+/* region Telemetry parser */
+/* ============================================================================= */
+
+%%{
+    machine Telemetry;
+    write data;
+}%%
+void machineTelemetryParserStateInit(struct TelemetryParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->traceLength = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Telemetry;
+    access aParserState->;
+    alphtype unsigned char;
+    action voltage {
+    }
+    action voltage_byte {
+        aTelemetry->voltage = (uint16_t)(((uint16_t)aTelemetry->voltage << 8) | (uint8_t)fc);
+    }
+    action label {
+    }
+    action label_reset {
+        aTelemetry->labelLength = 0;
+    }
+    action label_byte {
+        if (aTelemetry->labelLength < 8u) {
+            aTelemetry->label[aTelemetry->labelLength++] = (uint8_t)fc;
+        }
+    }
+    voltage = any{2} $voltage_byte @voltage; 
+    label = ( 'A'..'Z' )+ >label_reset $label_byte @label; 
+    main := ( any* :>> ( 0x7E ) ) voltage label;
+}%%
+static void robustoTraceTelemetry(int aPreviousCs, const struct TelemetryParserState *aParserState)
+{
+    if (aPreviousCs < Telemetry_first_final && aParserState->cs >= Telemetry_first_final) {
+        robustoTraceFrame(0u, 1u, aParserState->error, aParserState->traceLength);
+    } else if (aPreviousCs != Telemetry_error && aParserState->cs == Telemetry_error) {
+        robustoTraceFrame(0u, 0u, aParserState->error, aParserState->traceLength);
+    }
+}
+
+static enum RobustoParseStatus robustoParseStatusTelemetry(const struct TelemetryParserState *aParserState)
+{
+    if (aParserState->cs >= Telemetry_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Telemetry_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseTelemetry(struct TelemetryParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct TelemetryMessage *aTelemetry, int *aConsumedLength)
+{
+    const int previousCs = (aParserState->machineInitRequired != 0) ? Telemetry_start : aParserState->cs;
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  /* Iterator "begin" pointer -- Ragel-specific variable for C code generation */
+    const unsigned char *pe = p + aInputBufferLength;  /* Iterator "end" pointer -- Ragel-specific variable for C code generation */
+
+    if (aParserState->machineInitRequired != 0) {
+        machineTelemetryParserStateInit(aParserState);
+    }
+
+    aParserState->traceLength += (uint32_t)aInputBufferLength;
+
+    /* Parse starting from the state defined in `aParserState` */
+    for (;;) {
+        %% write exec;
+
+        if (aParserState->cs != Telemetry_error || p == pe) {
+            break;
+        }
+
+        /* Resynchronize on the rest of the input */
+        robustoTraceTelemetry(previousCs, aParserState);
+        machineTelemetryParserStateInit(aParserState);
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    robustoTraceTelemetry(previousCs, aParserState);
+    return robustoParseStatusTelemetry(aParserState);
+}
+int isTelemetryAccepted(const struct TelemetryParserState *aParserState)
+{
+    return aParserState->cs >= Telemetry_first_final;
+}
+/* endregion Telemetry parser */
@@ -0,0 +1,101 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* ============================================================================= */
+/* region Parse errors */
+/* ============================================================================= */
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5
+};
+/* endregion Parse errors */
+
+/* ============================================================================= */
+/* region Telemetry types */
+/* ============================================================================= */
+
+struct TelemetryMessage {
+    uint16_t voltage;
+    uint8_t label[8];
+    uint32_t labelLength;
+};
+struct TelemetryParserState {
+    /* Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off */
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint32_t traceLength;
+};
+/* endregion Telemetry types */
+
+/* ============================================================================= */
+/* region Sizing constants */
+/* ============================================================================= */
+
+union RobustoAnyMessage {
+    struct TelemetryMessage Telemetry;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_TELEMETRY_MIN_FRAME_SIZE 4u
+#define ROBUSTO_TELEMETRY_MAX_FRAME_SIZE 11u
+#define ROBUSTO_MIN_FRAME_SIZE 4u
+#define ROBUSTO_MAX_FRAME_SIZE 11u
+/* endregion Sizing constants */
+
+/* ============================================================================= */
+/* region Frame trace */
+/* ============================================================================= */
+
+#define ROBUSTO_TRACE_DEPTH 4u
+#define ROBUSTO_TELEMETRY_TRACE_ID 0u
+struct RobustoTraceRecord {
+    uint32_t timestamp;
+    uint32_t length;
+    uint8_t messageId;
+    uint8_t accepted;
+    uint8_t error;
+};
+extern struct RobustoTraceRecord robustoTraceRecords[ROBUSTO_TRACE_DEPTH];
+extern uint32_t robustoTraceCount;
+/* endregion Frame trace */
+
+/* ============================================================================= */
+/* region API */
+/* ============================================================================= */
+
+/* Non-null: aParserState */
+void machineTelemetryParserStateInit(struct TelemetryParserState *aParserState);
+/* Non-null: aParserState, aInputBuffer, aTelemetry */
+enum RobustoParseStatus parseTelemetry(struct TelemetryParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct TelemetryMessage *aTelemetry, int *aConsumedLength);
+/* Non-null: aParserState */
+int isTelemetryAccepted(const struct TelemetryParserState *aParserState);
+/* Non-null: aRecords */
+unsigned int robustoTraceDump(struct RobustoTraceRecord *aRecords, unsigned int aCapacity);
+/* endregion API */
+
+#ifdef __cplusplus
+}  /* extern "C" */
+#endif
+
+#endif  /* OUTPUT_H */
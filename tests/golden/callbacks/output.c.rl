@@ -0,0 +1,183 @@
+#include "output.h"
+
+// =============================================================================
+// region Heartbeat parser
+// =============================================================================
+
+%%{
+    machine Heartbeat;
+    write data;
+}%%
+void machineHeartbeatParserStateInit(struct HeartbeatParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Heartbeat;
+    access aParserState->;
+    alphtype unsigned char;
+    action messageParsed {
+        if (aCallbacks != 0 && aCallbacks->onHeartbeatParsed != 0) {
+            aCallbacks->onHeartbeatParsed(aHeartbeat, aCallbacks->context);
+        }
+    }
+    action sequence {
+    }
+    action sequence_byte {
+        aHeartbeat->sequence = (uint16_t)(((uint16_t)aHeartbeat->sequence << 8) | (uint8_t)fc);
+    }
+    action uptime {
+    }
+    action uptime_byte {
+        aHeartbeat->uptime = (uint32_t)(((uint32_t)aHeartbeat->uptime << 8) | (uint8_t)fc);
+    }
+    sequence = any{2} $sequence_byte @sequence; 
+    uptime = any{4} $uptime_byte @uptime; 
+    main := ( ( any* :>> ( 0xAA 0x55 ) ) sequence uptime ) @messageParsed;
+}%%
+static enum RobustoParseStatus robustoParseStatusHeartbeat(const struct HeartbeatParserState *aParserState)
+{
+    if (aParserState->cs >= Heartbeat_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Heartbeat_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseHeartbeatWithCallbacks(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength, const struct HeartbeatCallbacks *aCallbacks)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineHeartbeatParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    for (;;) {
+        %% write exec;
+
+        if (aParserState->cs != Heartbeat_error || p == pe) {
+            break;
+        }
+
+        // Resynchronize on the rest of the input
+        machineHeartbeatParserStateInit(aParserState);
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusHeartbeat(aParserState);
+}
+enum RobustoParseStatus parseHeartbeat(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength)
+{
+    return parseHeartbeatWithCallbacks(aParserState, aInputBuffer, aInputBufferLength, aHeartbeat, aConsumedLength, 0);
+}
+int isHeartbeatAccepted(const struct HeartbeatParserState *aParserState)
+{
+    return aParserState->cs >= Heartbeat_first_final;
+}
+// endregion Heartbeat parser
+
+// =============================================================================
+// region Log parser
+// =============================================================================
+
+%%{
+    machine Log;
+    write data;
+}%%
+void machineLogParserStateInit(struct LogParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Log;
+    access aParserState->;
+    alphtype unsigned char;
+    action messageParsed {
+        if (aCallbacks != 0 && aCallbacks->onLogParsed != 0) {
+            aCallbacks->onLogParsed(aLog, aCallbacks->context);
+        }
+    }
+    action text {
+    }
+    action text_reset {
+        aLog->textLength = 0;
+    }
+    action text_byte {
+        if (aLog->textLength < 16u) {
+            aLog->text[aLog->textLength++] = (uint8_t)fc;
+        }
+    }
+    text = ( 'a'..'z' )+ >text_reset $text_byte @text; 
+    main := ( text ) @messageParsed;
+}%%
+static enum RobustoParseStatus robustoParseStatusLog(const struct LogParserState *aParserState)
+{
+    if (aParserState->cs >= Log_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Log_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseLogWithCallbacks(struct LogParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct LogMessage *aLog, int *aConsumedLength, const struct LogCallbacks *aCallbacks)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineLogParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusLog(aParserState);
+}
+enum RobustoParseStatus parseLog(struct LogParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct LogMessage *aLog, int *aConsumedLength)
+{
+    return parseLogWithCallbacks(aParserState, aInputBuffer, aInputBufferLength, aLog, aConsumedLength, 0);
+}
+int isLogAccepted(const struct LogParserState *aParserState)
+{
+    return aParserState->cs >= Log_first_final;
+}
+// endregion Log parser
@@ -0,0 +1,121 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region Heartbeat types
+// =============================================================================
+
+struct HeartbeatMessage {
+    uint16_t sequence;
+    uint32_t uptime;
+};
+struct HeartbeatParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+
+// The callback is invoked on each byte completing a frame, and may be NULL
+struct HeartbeatCallbacks {
+    void *context;
+    void (*onHeartbeatParsed)(const struct HeartbeatMessage *aHeartbeat, void *aContext);
+};
+// endregion Heartbeat types
+
+// =============================================================================
+// region Log types
+// =============================================================================
+
+struct LogMessage {
+    uint8_t text[16];
+    uint32_t textLength;
+};
+struct LogParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+
+// The callback is invoked on each byte completing a frame, and may be NULL
+struct LogCallbacks {
+    void *context;
+    void (*onLogParsed)(const struct LogMessage *aLog, void *aContext);
+};
+// endregion Log types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct HeartbeatMessage Heartbeat;
+    struct LogMessage Log;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_HEARTBEAT_MIN_FRAME_SIZE 8u
+#define ROBUSTO_HEARTBEAT_MAX_FRAME_SIZE 8u
+#define ROBUSTO_LOG_MIN_FRAME_SIZE 1u
+#define ROBUSTO_LOG_MAX_FRAME_SIZE 16u
+#define ROBUSTO_MIN_FRAME_SIZE 1u
+#define ROBUSTO_MAX_FRAME_SIZE 16u
+// endregion Sizing constants
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineHeartbeatParserStateInit(struct HeartbeatParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aHeartbeat
+enum RobustoParseStatus parseHeartbeat(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength);
+// Non-null: aParserState, aInputBuffer, aHeartbeat
+enum RobustoParseStatus parseHeartbeatWithCallbacks(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength, const struct HeartbeatCallbacks *aCallbacks);
+// Non-null: aParserState
+int isHeartbeatAccepted(const struct HeartbeatParserState *aParserState);
+// Non-null: aParserState
+void machineLogParserStateInit(struct LogParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aLog
+enum RobustoParseStatus parseLog(struct LogParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct LogMessage *aLog, int *aConsumedLength);
+// Non-null: aParserState, aInputBuffer, aLog
+enum RobustoParseStatus parseLogWithCallbacks(struct LogParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct LogMessage *aLog, int *aConsumedLength, const struct LogCallbacks *aCallbacks);
+// Non-null: aParserState
+int isLogAccepted(const struct LogParserState *aParserState);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
@@ -0,0 +1,200 @@
+#include "output.h"
+
+// =============================================================================
+// region Good parser
+// =============================================================================
+
+%%{
+    machine Good;
+    write data;
+}%%
+void machineGoodParserStateInit(struct GoodParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->innerChecksum = 0xFFFFu;
+    aParserState->outerChecksum = 0xFFFFu;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Good;
+    access aParserState->;
+    alphtype unsigned char;
+    action sync {
+    }
+    action sync_reset {
+        aGood->syncLength = 0;
+    }
+    action sync_byte {
+        if (aGood->syncLength < 1u) {
+            aGood->sync[aGood->syncLength++] = (uint8_t)fc;
+        }
+    }
+    action a {
+    }
+    action a_byte {
+        aGood->a = (uint8_t)(((uint8_t)aGood->a << 8) | (uint8_t)fc);
+    }
+    action b {
+    }
+    action b_byte {
+        aGood->b = (uint8_t)(((uint8_t)aGood->b << 8) | (uint8_t)fc);
+    }
+    action crc {
+        if (aGood->crc != aParserState->innerChecksum) {
+            aParserState->error = ROBUSTO_PARSE_ERROR_CHECKSUM;
+            fgoto *Good_error;
+        }
+    }
+    action inner_checksum {
+        aParserState->innerChecksum = robustoCrc16_1021(aParserState->innerChecksum, (uint8_t)fc);
+    }
+    action crc_byte {
+        aGood->crc = (uint16_t)(((uint16_t)aGood->crc << 8) | (uint8_t)fc);
+    }
+    action c {
+    }
+    action c_byte {
+        aGood->c = (uint8_t)(((uint8_t)aGood->c << 8) | (uint8_t)fc);
+    }
+    action crc2 {
+        if (aGood->crc2 != aParserState->outerChecksum) {
+            aParserState->error = ROBUSTO_PARSE_ERROR_CHECKSUM;
+            fgoto *Good_error;
+        }
+    }
+    action outer_checksum {
+        aParserState->outerChecksum = robustoCrc16_1021(aParserState->outerChecksum, (uint8_t)fc);
+    }
+    action crc2_byte {
+        aGood->crc2 = (uint16_t)(((uint16_t)aGood->crc2 << 8) | (uint8_t)fc);
+    }
+    sync = 0xAA >sync_reset $sync_byte @sync; 
+    a = any{1} $a_byte @a; 
+    b = any{1} $b_byte @b; 
+    crc = any{2} $crc_byte @crc; 
+    c = any{1} $c_byte @c; 
+    crc2 = any{2} $crc2_byte @crc2; 
+    main := sync ( a $outer_checksum $inner_checksum ) ( b $outer_checksum $inner_checksum ) ( crc $outer_checksum ) ( c $outer_checksum ) crc2;
+}%%
+static enum RobustoParseStatus robustoParseStatusGood(const struct GoodParserState *aParserState)
+{
+    if (aParserState->cs >= Good_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Good_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseGood(struct GoodParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct GoodMessage *aGood, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineGoodParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusGood(aParserState);
+}
+int isGoodAccepted(const struct GoodParserState *aParserState)
+{
+    return aParserState->cs >= Good_first_final;
+}
+// endregion Good parser
+
+// =============================================================================
+// region Self-test
+// =============================================================================
+
+int selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0xAA, 0xCD, 0xAB, 0x79, 0x86, 0x67, 0x1C, 0x41};
+        struct GoodParserState parserState;
+        struct GoodMessage message = {0};
+        struct GoodMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineGoodParserStateInit(&parserState);
+
+        if (parseGood(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.a != 205u) {
+            return 1;
+        }
+
+        if (message.b != 171u) {
+            return 1;
+        }
+
+        if (message.crc != 31110u) {
+            return 1;
+        }
+
+        if (message.c != 103u) {
+            return 1;
+        }
+
+        if (message.crc2 != 7233u) {
+            return 1;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseGood(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.a != 205u) {
+            return 1;
+        }
+
+        if (splitMessage.b != 171u) {
+            return 1;
+        }
+
+        if (splitMessage.crc != 31110u) {
+            return 1;
+        }
+
+        if (splitMessage.c != 103u) {
+            return 1;
+        }
+
+        if (splitMessage.crc2 != 7233u) {
+            return 1;
+        }
+    }
+
+    return 0;
+}
+// endregion Self-test
@@ -0,0 +1,34 @@
+/// Parse error code: no error
+pub const ERROR_CODE_NONE: u8 = 0;
+/// Parse error code: field value out of range
+pub const ERROR_CODE_VALUE_OUT_OF_RANGE: u8 = 1;
+/// Parse error code: frame longer than the message's fixed length
+pub const ERROR_CODE_FRAME_LENGTH: u8 = 2;
+/// Parse error code: checksum mismatch
+pub const ERROR_CODE_CHECKSUM: u8 = 3;
+/// Parse error code: length field mismatch
+pub const ERROR_CODE_LENGTH: u8 = 4;
+/// Parse error code: field check failed
+pub const ERROR_CODE_CHECK: u8 = 5;
+/// Parse error code: field longer than its max length
+pub const ERROR_CODE_OVERFLOW: u8 = 6;
+/// Parse status: frame complete
+pub const PARSE_STATUS_OK: u8 = 0;
+/// Parse status: frame incomplete
+pub const PARSE_STATUS_INCOMPLETE: u8 = 1;
+/// Parse status: frame rejected on a checksum mismatch
+pub const PARSE_STATUS_ERROR_CRC: u8 = 2;
+/// Parse status: frame rejected on a field value out of range
+pub const PARSE_STATUS_ERROR_RANGE: u8 = 3;
+/// Parse status: frame rejected on a field longer than its max length
+pub const PARSE_STATUS_ERROR_OVERFLOW: u8 = 4;
+/// Parse status: frame rejected
+pub const PARSE_STATUS_ERROR: u8 = 5;
+/// Min length of a frame of message Good, in bytes
+pub const GOOD_MIN_FRAME_SIZE: u32 = 8;
+/// Max length of a frame of message Good, in bytes
+pub const GOOD_MAX_FRAME_SIZE: u32 = 8;
+/// Min length of a frame of any message, in bytes
+pub const MIN_FRAME_SIZE: u32 = 8;
+/// Max length of a frame of any message, in bytes
+pub const MAX_FRAME_SIZE: u32 = 8;
@@ -0,0 +1,34 @@
+/** Parse error code: no error */
+export const ERROR_CODE_NONE = 0;
+/** Parse error code: field value out of range */
+export const ERROR_CODE_VALUE_OUT_OF_RANGE = 1;
+/** Parse error code: frame longer than the message's fixed length */
+export const ERROR_CODE_FRAME_LENGTH = 2;
+/** Parse error code: checksum mismatch */
+export const ERROR_CODE_CHECKSUM = 3;
+/** Parse error code: length field mismatch */
+export const ERROR_CODE_LENGTH = 4;
+/** Parse error code: field check failed */
+export const ERROR_CODE_CHECK = 5;
+/** Parse error code: field longer than its max length */
+export const ERROR_CODE_OVERFLOW = 6;
+/** Parse status: frame complete */
+export const PARSE_STATUS_OK = 0;
+/** Parse status: frame incomplete */
+export const PARSE_STATUS_INCOMPLETE = 1;
+/** Parse status: frame rejected on a checksum mismatch */
+export const PARSE_STATUS_ERROR_CRC = 2;
+/** Parse status: frame rejected on a field value out of range */
+export const PARSE_STATUS_ERROR_RANGE = 3;
+/** Parse status: frame rejected on a field longer than its max length */
+export const PARSE_STATUS_ERROR_OVERFLOW = 4;
+/** Parse status: frame rejected */
+export const PARSE_STATUS_ERROR = 5;
+/** Min length of a frame of message Good, in bytes */
+export const GOOD_MIN_FRAME_SIZE = 8;
+/** Max length of a frame of message Good, in bytes */
+export const GOOD_MAX_FRAME_SIZE = 8;
+/** Min length of a frame of any message, in bytes */
+export const MIN_FRAME_SIZE = 8;
+/** Max length of a frame of any message, in bytes */
+export const MAX_FRAME_SIZE = 8;
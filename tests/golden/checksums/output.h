@@ -0,0 +1,99 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+// =============================================================================
+// region Runtime
+// =============================================================================
+
+#include "robusto_rt.h"
+
+#if ROBUSTO_RT_VERSION != 1
+#error "The generated code requires version 1 of the Robusto runtime library"
+#endif
+// endregion Runtime
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region Good types
+// =============================================================================
+
+struct GoodMessage {
+    uint8_t sync[1];
+    uint32_t syncLength;
+    uint8_t a;
+    uint8_t b;
+    uint16_t crc;
+    uint8_t c;
+    uint16_t crc2;
+};
+struct GoodParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint16_t innerChecksum;
+    uint16_t outerChecksum;
+};
+// endregion Good types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct GoodMessage Good;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_GOOD_MIN_FRAME_SIZE 8u
+#define ROBUSTO_GOOD_MAX_FRAME_SIZE 8u
+#define ROBUSTO_MIN_FRAME_SIZE 8u
+#define ROBUSTO_MAX_FRAME_SIZE 8u
+// endregion Sizing constants
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineGoodParserStateInit(struct GoodParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aGood
+enum RobustoParseStatus parseGood(struct GoodParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct GoodMessage *aGood, int *aConsumedLength);
+// Non-null: aParserState
+int isGoodAccepted(const struct GoodParserState *aParserState);
+int selfTest(void);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
@@ -0,0 +1,378 @@
+#include "output.h"
+
+// =============================================================================
+// region Bit reader
+// =============================================================================
+
+static uint64_t robustoReadBits(const uint8_t *aBuffer, unsigned long aBitOffset, unsigned int aBitWidth)
+{
+    uint64_t value = 0;
+    unsigned int i;
+
+    for (i = 0; i < aBitWidth; ++i) {
+        unsigned long bit = aBitOffset + i;
+        value = (value << 1) | ((aBuffer[bit / 8] >> (7 - (bit % 8))) & 1u);
+    }
+
+    return value;
+}
+static int64_t robustoSignExtend(uint64_t aValue, unsigned int aBitWidth)
+{
+    uint64_t signBit = (uint64_t)1 << (aBitWidth - 1);
+
+    return (int64_t)((aValue ^ signBit) - signBit);
+}
+// endregion Bit reader
+
+// =============================================================================
+// region SensorReport parser
+// =============================================================================
+
+%%{
+    machine SensorReport;
+    write data;
+}%%
+void machineSensorReportParserStateInit(struct SensorReportParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->statistics.framesOk = 0;
+    aParserState->statistics.crcErrors = 0;
+    aParserState->statistics.resyncs = 0;
+    aParserState->statistics.overflows = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine SensorReport;
+    access aParserState->;
+    alphtype unsigned char;
+    action id {
+    }
+    action id_byte {
+        aSensorReport->id = (uint8_t)(((uint8_t)aSensorReport->id << 8) | (uint8_t)fc);
+    }
+    action voltage {
+    }
+    action voltage_byte {
+        aSensorReport->voltage = (uint16_t)(((uint16_t)aSensorReport->voltage << 8) | (uint8_t)fc);
+    }
+    action label {
+    }
+    action label_reset {
+        aSensorReport->labelLength = 0;
+    }
+    action label_byte {
+        if (aSensorReport->labelLength < 8u) {
+            aSensorReport->label[aSensorReport->labelLength++] = (uint8_t)fc;
+        }
+    }
+    id = any{1} $id_byte @id; 
+    voltage = any{2} $voltage_byte @voltage; 
+    label = ( ( 'a'..'z' )+ ';' ) >label_reset $label_byte @label; 
+    main := id voltage label;
+}%%
+static void robustoCountSensorReport(int aPreviousCs, struct SensorReportParserState *aParserState)
+{
+    if (aPreviousCs < SensorReport_first_final && aParserState->cs >= SensorReport_first_final) {
+        ++aParserState->statistics.framesOk;
+    } else if (aPreviousCs != SensorReport_error && aParserState->cs == SensorReport_error) {
+        if (aParserState->error == ROBUSTO_PARSE_ERROR_CHECKSUM) {
+            ++aParserState->statistics.crcErrors;
+        } else if (aParserState->error == ROBUSTO_PARSE_ERROR_OVERFLOW) {
+            ++aParserState->statistics.overflows;
+        }
+    }
+}
+
+static enum RobustoParseStatus robustoParseStatusSensorReport(const struct SensorReportParserState *aParserState)
+{
+    if (aParserState->cs >= SensorReport_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != SensorReport_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseSensorReport(struct SensorReportParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct SensorReportMessage *aSensorReport, int *aConsumedLength)
+{
+    const int previousCs = (aParserState->machineInitRequired != 0) ? SensorReport_start : aParserState->cs;
+
+    if (aParserState->machineInitRequired != 0) {
+        const struct RobustoParserStatistics statistics = aParserState->statistics;
+
+        machineSensorReportParserStateInit(aParserState);
+        aParserState->statistics = statistics;
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    robustoCountSensorReport(previousCs, aParserState);
+    return robustoParseStatusSensorReport(aParserState);
+}
+int isSensorReportAccepted(const struct SensorReportParserState *aParserState)
+{
+    return aParserState->cs >= SensorReport_first_final;
+}
+struct RobustoParserStatistics getSensorReportStatistics(const struct SensorReportParserState *aParserState)
+{
+    return aParserState->statistics;
+}
+int serializeSensorReport(const struct SensorReportMessage *aSensorReport, uint8_t *aBuffer, size_t aCapacity)
+{
+    size_t length = 0;
+
+    if (aCapacity < 11u) {
+        return -1;
+    }
+
+    aBuffer[length++] = 0x01u;
+    aBuffer[length++] = (uint8_t)((uint16_t)aSensorReport->voltage >> 8);
+    aBuffer[length++] = (uint8_t)(uint16_t)aSensorReport->voltage;
+    {
+        size_t j;
+
+        for (j = 0; j < aSensorReport->labelLength && j < 8u; ++j) {
+            aBuffer[length++] = aSensorReport->label[j];
+        }
+    }
+
+    return (int)length;
+}
+// endregion SensorReport parser
+
+// =============================================================================
+// region Heartbeat parser
+// =============================================================================
+
+%%{
+    machine Heartbeat;
+    write data;
+}%%
+void machineHeartbeatParserStateInit(struct HeartbeatParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->statistics.framesOk = 0;
+    aParserState->statistics.crcErrors = 0;
+    aParserState->statistics.resyncs = 0;
+    aParserState->statistics.overflows = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Heartbeat;
+    access aParserState->;
+    alphtype unsigned char;
+    action id {
+    }
+    action id_byte {
+        aHeartbeat->id = (uint8_t)(((uint8_t)aHeartbeat->id << 8) | (uint8_t)fc);
+    }
+    action uptime {
+    }
+    action uptime_byte {
+        aHeartbeat->uptime = (uint32_t)(((uint32_t)aHeartbeat->uptime << 8) | (uint8_t)fc);
+    }
+    id = any{1} $id_byte @id; 
+    uptime = any{4} $uptime_byte @uptime; 
+    main := id uptime;
+}%%
+static void robustoCountHeartbeat(int aPreviousCs, struct HeartbeatParserState *aParserState)
+{
+    if (aPreviousCs < Heartbeat_first_final && aParserState->cs >= Heartbeat_first_final) {
+        ++aParserState->statistics.framesOk;
+    } else if (aPreviousCs != Heartbeat_error && aParserState->cs == Heartbeat_error) {
+        if (aParserState->error == ROBUSTO_PARSE_ERROR_CHECKSUM) {
+            ++aParserState->statistics.crcErrors;
+        } else if (aParserState->error == ROBUSTO_PARSE_ERROR_OVERFLOW) {
+            ++aParserState->statistics.overflows;
+        }
+    }
+}
+
+static enum RobustoParseStatus robustoParseStatusHeartbeat(const struct HeartbeatParserState *aParserState)
+{
+    if (aParserState->cs >= Heartbeat_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Heartbeat_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseHeartbeat(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength)
+{
+    const int previousCs = (aParserState->machineInitRequired != 0) ? Heartbeat_start : aParserState->cs;
+
+    if (aParserState->machineInitRequired != 0) {
+        const struct RobustoParserStatistics statistics = aParserState->statistics;
+
+        machineHeartbeatParserStateInit(aParserState);
+        aParserState->statistics = statistics;
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    robustoCountHeartbeat(previousCs, aParserState);
+    return robustoParseStatusHeartbeat(aParserState);
+}
+int isHeartbeatAccepted(const struct HeartbeatParserState *aParserState)
+{
+    return aParserState->cs >= Heartbeat_first_final;
+}
+struct RobustoParserStatistics getHeartbeatStatistics(const struct HeartbeatParserState *aParserState)
+{
+    return aParserState->statistics;
+}
+int serializeHeartbeat(const struct HeartbeatMessage *aHeartbeat, uint8_t *aBuffer, size_t aCapacity)
+{
+    size_t length = 0;
+
+    if (aCapacity < 5u) {
+        return -1;
+    }
+
+    aBuffer[length++] = 0x02u;
+    aBuffer[length++] = (uint8_t)((uint32_t)aHeartbeat->uptime >> 24);
+    aBuffer[length++] = (uint8_t)((uint32_t)aHeartbeat->uptime >> 16);
+    aBuffer[length++] = (uint8_t)((uint32_t)aHeartbeat->uptime >> 8);
+    aBuffer[length++] = (uint8_t)(uint32_t)aHeartbeat->uptime;
+
+    return (int)length;
+}
+// endregion Heartbeat parser
+
+// =============================================================================
+// region Status parser
+// =============================================================================
+
+int parseStatus(const uint8_t *aInputBuffer, int aInputBufferLength, struct StatusMessage *aStatus)
+{
+    unsigned long bitOffset = 0;  // Position of the bit reader
+
+    if ((unsigned long)aInputBufferLength * 8UL < 8UL) {
+        return -1;
+    }
+
+    aStatus->mode = (uint8_t)robustoReadBits(aInputBuffer, bitOffset, 3u);
+    bitOffset += 3u;
+    aStatus->level = (uint8_t)robustoReadBits(aInputBuffer, bitOffset, 5u);
+    bitOffset += 5u;
+    return (int)((bitOffset + 7UL) / 8UL);
+}
+// endregion Status parser
+
+// =============================================================================
+// region Dispatcher
+// =============================================================================
+
+void robustoDispatcherInit(struct RobustoDispatcherState *aState)
+{
+    aState->id = 0u;
+    aState->idLength = 0u;
+    aState->selected = ROBUSTO_DISPATCH_PENDING;
+    aState->accepted = 0u;
+}
+
+void parseAny(struct RobustoDispatcherState *aState, const char *aInputBuffer, int aInputBufferLength, const struct RobustoDispatchCallbacks *aCallbacks)
+{
+    while (aState->selected == ROBUSTO_DISPATCH_PENDING && aInputBufferLength > 0) {
+        aState->idBuffer[aState->idLength] = *aInputBuffer;
+        aState->id = (uint8_t)((aState->id << 8) | (uint8_t)*aInputBuffer);
+        ++aState->idLength;
+        ++aInputBuffer;
+        --aInputBufferLength;
+
+        if (aState->idLength < ROBUSTO_DISPATCH_ID_LENGTH) {
+            continue;
+        }
+
+        switch (aState->id) {
+            case ROBUSTO_SENSOR_REPORT_ID:
+                aState->selected = 1u;
+                machineSensorReportParserStateInit(&aState->parserState.SensorReport);
+                parseSensorReport(&aState->parserState.SensorReport, aState->idBuffer, ROBUSTO_DISPATCH_ID_LENGTH, &aState->message.SensorReport, 0);
+                break;
+            case ROBUSTO_HEARTBEAT_ID:
+                aState->selected = 2u;
+                machineHeartbeatParserStateInit(&aState->parserState.Heartbeat);
+                parseHeartbeat(&aState->parserState.Heartbeat, aState->idBuffer, ROBUSTO_DISPATCH_ID_LENGTH, &aState->message.Heartbeat, 0);
+                break;
+            default:
+                aState->selected = ROBUSTO_DISPATCH_UNKNOWN;
+                break;
+        }
+    }
+
+    switch (aState->selected) {
+        case 1u:
+            if (aInputBufferLength > 0) {
+                parseSensorReport(&aState->parserState.SensorReport, aInputBuffer, aInputBufferLength, &aState->message.SensorReport, 0);
+            }
+
+            if (!aState->accepted && isSensorReportAccepted(&aState->parserState.SensorReport)) {
+                aState->accepted = 1u;
+
+                if (aCallbacks->onSensorReport != 0) {
+                    aCallbacks->onSensorReport(&aState->message.SensorReport, aCallbacks->context);
+                }
+            }
+            break;
+        case 2u:
+            if (aInputBufferLength > 0) {
+                parseHeartbeat(&aState->parserState.Heartbeat, aInputBuffer, aInputBufferLength, &aState->message.Heartbeat, 0);
+            }
+
+            if (!aState->accepted && isHeartbeatAccepted(&aState->parserState.Heartbeat)) {
+                aState->accepted = 1u;
+
+                if (aCallbacks->onHeartbeat != 0) {
+                    aCallbacks->onHeartbeat(&aState->message.Heartbeat, aCallbacks->context);
+                }
+            }
+            break;
+        default:
+            break;
+    }
+}
+// endregion Dispatcher
@@ -0,0 +1,191 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region Statistics
+// =============================================================================
+
+// Counters of a parser, which wrap around. `resyncs` counts the frames a parser has abandoned
+// to scan for the sync sequence anew
+struct RobustoParserStatistics {
+    uint32_t framesOk;
+    uint32_t crcErrors;
+    uint32_t resyncs;
+    uint32_t overflows;
+};
+// endregion Statistics
+
+// =============================================================================
+// region SensorReport types
+// =============================================================================
+
+struct SensorReportMessage {
+    uint8_t id;
+    uint16_t voltage;
+    uint8_t label[8];
+    uint32_t labelLength;
+};
+struct SensorReportParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    struct RobustoParserStatistics statistics;
+};
+// endregion SensorReport types
+
+// =============================================================================
+// region Heartbeat types
+// =============================================================================
+
+struct HeartbeatMessage {
+    uint8_t id;
+    uint32_t uptime;
+};
+struct HeartbeatParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    struct RobustoParserStatistics statistics;
+};
+// endregion Heartbeat types
+
+// =============================================================================
+// region Status types
+// =============================================================================
+
+struct StatusMessage {
+    uint8_t mode;
+    uint8_t level;
+};
+// endregion Status types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct SensorReportMessage SensorReport;
+    struct HeartbeatMessage Heartbeat;
+    struct StatusMessage Status;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_SENSOR_REPORT_MIN_FRAME_SIZE 5u
+#define ROBUSTO_SENSOR_REPORT_MAX_FRAME_SIZE 11u
+#define ROBUSTO_HEARTBEAT_MIN_FRAME_SIZE 5u
+#define ROBUSTO_HEARTBEAT_MAX_FRAME_SIZE 5u
+#define ROBUSTO_STATUS_MIN_FRAME_SIZE 1u
+#define ROBUSTO_STATUS_MAX_FRAME_SIZE 1u
+#define ROBUSTO_MIN_FRAME_SIZE 1u
+#define ROBUSTO_MAX_FRAME_SIZE 11u
+// endregion Sizing constants
+
+// =============================================================================
+// region Dispatcher
+// =============================================================================
+
+#define ROBUSTO_DISPATCH_ID_LENGTH 1u
+#define ROBUSTO_DISPATCH_PENDING 0u
+#define ROBUSTO_DISPATCH_UNKNOWN 255u
+#define ROBUSTO_SENSOR_REPORT_ID 1u
+#define ROBUSTO_HEARTBEAT_ID 2u
+
+enum RobustoMessageId {
+    ROBUSTO_MESSAGE_ID_SENSOR_REPORT = ROBUSTO_SENSOR_REPORT_ID,
+    ROBUSTO_MESSAGE_ID_HEARTBEAT = ROBUSTO_HEARTBEAT_ID,
+};
+
+// Each callback is invoked once its message's frame is accepted, and may be NULL
+struct RobustoDispatchCallbacks {
+    void *context;
+    void (*onSensorReport)(const struct SensorReportMessage *aSensorReport, void *aContext);
+    void (*onHeartbeat)(const struct HeartbeatMessage *aHeartbeat, void *aContext);
+};
+
+union RobustoDispatchParserState {
+    struct SensorReportParserState SensorReport;
+    struct HeartbeatParserState Heartbeat;
+};
+
+// `selected` is the 1-based index of the message being parsed, `ROBUSTO_DISPATCH_PENDING` while
+// the ID is incomplete, or `ROBUSTO_DISPATCH_UNKNOWN` if no message has the ID
+struct RobustoDispatcherState {
+    uint8_t id;
+    char idBuffer[ROBUSTO_DISPATCH_ID_LENGTH];
+    uint8_t idLength;
+    uint8_t selected;
+    uint8_t accepted;
+    union RobustoDispatchParserState parserState;
+    union RobustoAnyMessage message;
+};
+// endregion Dispatcher
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineSensorReportParserStateInit(struct SensorReportParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aSensorReport
+enum RobustoParseStatus parseSensorReport(struct SensorReportParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct SensorReportMessage *aSensorReport, int *aConsumedLength);
+// Non-null: aParserState
+int isSensorReportAccepted(const struct SensorReportParserState *aParserState);
+// Non-null: aParserState
+struct RobustoParserStatistics getSensorReportStatistics(const struct SensorReportParserState *aParserState);
+// Non-null: aSensorReport, aBuffer
+int serializeSensorReport(const struct SensorReportMessage *aSensorReport, uint8_t *aBuffer, size_t aCapacity);
+// Non-null: aParserState
+void machineHeartbeatParserStateInit(struct HeartbeatParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aHeartbeat
+enum RobustoParseStatus parseHeartbeat(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength);
+// Non-null: aParserState
+int isHeartbeatAccepted(const struct HeartbeatParserState *aParserState);
+// Non-null: aParserState
+struct RobustoParserStatistics getHeartbeatStatistics(const struct HeartbeatParserState *aParserState);
+// Non-null: aHeartbeat, aBuffer
+int serializeHeartbeat(const struct HeartbeatMessage *aHeartbeat, uint8_t *aBuffer, size_t aCapacity);
+// Non-null: aInputBuffer, aStatus
+int parseStatus(const uint8_t *aInputBuffer, int aInputBufferLength, struct StatusMessage *aStatus);
+// Non-null: aState
+void robustoDispatcherInit(struct RobustoDispatcherState *aState);
+// Non-null: aState, aInputBuffer, aCallbacks
+void parseAny(struct RobustoDispatcherState *aState, const char *aInputBuffer, int aInputBufferLength, const struct RobustoDispatchCallbacks *aCallbacks);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
@@ -0,0 +1,168 @@
+#ifndef OUTPUT_HPP
+#define OUTPUT_HPP
+
+#include <array>
+#include <climits>
+#include <cstddef>
+#include <cstdint>
+#if __cplusplus >= 202002L
+#include <span>
+#endif
+
+#include "output.h"
+
+namespace acme::sensors {
+
+enum class ParseStatus : int {
+    Ok = ROBUSTO_PARSE_STATUS_OK,
+    Incomplete = ROBUSTO_PARSE_STATUS_INCOMPLETE,
+    ErrorCrc = ROBUSTO_PARSE_STATUS_ERROR_CRC,
+    ErrorRange = ROBUSTO_PARSE_STATUS_ERROR_RANGE,
+    ErrorOverflow = ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW,
+    Error = ROBUSTO_PARSE_STATUS_ERROR,
+};
+
+enum class ParseError : std::uint8_t {
+    None = ROBUSTO_PARSE_ERROR_NONE,
+    ValueOutOfRange = ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE,
+    FrameLength = ROBUSTO_PARSE_ERROR_FRAME_LENGTH,
+    Checksum = ROBUSTO_PARSE_ERROR_CHECKSUM,
+    Length = ROBUSTO_PARSE_ERROR_LENGTH,
+    Check = ROBUSTO_PARSE_ERROR_CHECK,
+    Overflow = ROBUSTO_PARSE_ERROR_OVERFLOW,
+};
+
+enum class MessageId : std::uint8_t {
+    SensorReport = ROBUSTO_SENSOR_REPORT_ID,
+    Heartbeat = ROBUSTO_HEARTBEAT_ID,
+};
+
+using SensorReport = ::SensorReportMessage;
+using SensorReportFrame = std::array<std::byte, ROBUSTO_SENSOR_REPORT_MAX_FRAME_SIZE>;
+
+class SensorReportParser {
+public:
+    SensorReportParser() noexcept {
+        reset();
+    }
+    // Restarts the machine, discarding the frame parsed so far
+    void reset() noexcept {
+        machineSensorReportParserStateInit(&mState);
+    }
+    // Tells how many bytes of the input the machine has taken through aConsumedLength
+    ParseStatus parse(const std::byte *aInput, std::size_t aInputLength, SensorReport &aMessage, std::size_t &aConsumedLength) noexcept {
+        // Input past INT_MAX is left for the next call
+        const int inputLength = aInputLength > INT_MAX ? INT_MAX : static_cast<int>(aInputLength);
+        int consumedLength = 0;
+        const auto status = parseSensorReport(&mState, reinterpret_cast<const char *>(aInput), inputLength, &aMessage, &consumedLength);
+        aConsumedLength = static_cast<std::size_t>(consumedLength);
+        return static_cast<ParseStatus>(status);
+    }
+#if __cplusplus >= 202002L
+    ParseStatus parse(std::span<const std::byte> aInput, SensorReport &aMessage, std::size_t &aConsumedLength) noexcept {
+        return parse(aInput.data(), aInput.size(), aMessage, aConsumedLength);
+    }
+#endif
+    bool isAccepted() const noexcept {
+        return isSensorReportAccepted(&mState) != 0;
+    }
+    // Why the last frame has been rejected
+    ParseError error() const noexcept {
+        return static_cast<ParseError>(mState.error);
+    }
+    ::RobustoParserStatistics statistics() const noexcept {
+        return getSensorReportStatistics(&mState);
+    }
+    const ::SensorReportParserState &state() const noexcept {
+        return mState;
+    }
+
+private:
+    ::SensorReportParserState mState;
+};
+
+inline int serialize(const SensorReport &aMessage, std::byte *aBuffer, std::size_t aCapacity) noexcept {
+    return serializeSensorReport(&aMessage, reinterpret_cast<std::uint8_t *>(aBuffer), aCapacity);
+}
+inline int serialize(const SensorReport &aMessage, SensorReportFrame &aFrame) noexcept {
+    return serialize(aMessage, aFrame.data(), aFrame.size());
+}
+#if __cplusplus >= 202002L
+inline int serialize(const SensorReport &aMessage, std::span<std::byte> aBuffer) noexcept {
+    return serialize(aMessage, aBuffer.data(), aBuffer.size());
+}
+#endif
+
+using Heartbeat = ::HeartbeatMessage;
+using HeartbeatFrame = std::array<std::byte, ROBUSTO_HEARTBEAT_MAX_FRAME_SIZE>;
+
+class HeartbeatParser {
+public:
+    HeartbeatParser() noexcept {
+        reset();
+    }
+    // Restarts the machine, discarding the frame parsed so far
+    void reset() noexcept {
+        machineHeartbeatParserStateInit(&mState);
+    }
+    // Tells how many bytes of the input the machine has taken through aConsumedLength
+    ParseStatus parse(const std::byte *aInput, std::size_t aInputLength, Heartbeat &aMessage, std::size_t &aConsumedLength) noexcept {
+        // Input past INT_MAX is left for the next call
+        const int inputLength = aInputLength > INT_MAX ? INT_MAX : static_cast<int>(aInputLength);
+        int consumedLength = 0;
+        const auto status = parseHeartbeat(&mState, reinterpret_cast<const char *>(aInput), inputLength, &aMessage, &consumedLength);
+        aConsumedLength = static_cast<std::size_t>(consumedLength);
+        return static_cast<ParseStatus>(status);
+    }
+#if __cplusplus >= 202002L
+    ParseStatus parse(std::span<const std::byte> aInput, Heartbeat &aMessage, std::size_t &aConsumedLength) noexcept {
+        return parse(aInput.data(), aInput.size(), aMessage, aConsumedLength);
+    }
+#endif
+    bool isAccepted() const noexcept {
+        return isHeartbeatAccepted(&mState) != 0;
+    }
+    // Why the last frame has been rejected
+    ParseError error() const noexcept {
+        return static_cast<ParseError>(mState.error);
+    }
+    ::RobustoParserStatistics statistics() const noexcept {
+        return getHeartbeatStatistics(&mState);
+    }
+    const ::HeartbeatParserState &state() const noexcept {
+        return mState;
+    }
+
+private:
+    ::HeartbeatParserState mState;
+};
+
+inline int serialize(const Heartbeat &aMessage, std::byte *aBuffer, std::size_t aCapacity) noexcept {
+    return serializeHeartbeat(&aMessage, reinterpret_cast<std::uint8_t *>(aBuffer), aCapacity);
+}
+inline int serialize(const Heartbeat &aMessage, HeartbeatFrame &aFrame) noexcept {
+    return serialize(aMessage, aFrame.data(), aFrame.size());
+}
+#if __cplusplus >= 202002L
+inline int serialize(const Heartbeat &aMessage, std::span<std::byte> aBuffer) noexcept {
+    return serialize(aMessage, aBuffer.data(), aBuffer.size());
+}
+#endif
+
+using Status = ::StatusMessage;
+using StatusFrame = std::array<std::byte, ROBUSTO_STATUS_MAX_FRAME_SIZE>;
+
+inline int parse(const std::byte *aInput, std::size_t aInputLength, Status &aMessage) noexcept {
+    // A frame longer than INT_MAX is longer than the message's length anyway
+    const int inputLength = aInputLength > INT_MAX ? INT_MAX : static_cast<int>(aInputLength);
+    return parseStatus(reinterpret_cast<const std::uint8_t *>(aInput), inputLength, &aMessage);
+}
+#if __cplusplus >= 202002L
+inline int parse(std::span<const std::byte> aInput, Status &aMessage) noexcept {
+    return parse(aInput.data(), aInput.size(), aMessage);
+}
+#endif
+
+}  // namespace acme::sensors
+
+#endif  // OUTPUT_HPP
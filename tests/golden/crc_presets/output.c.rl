@@ -0,0 +1,273 @@
+#include "output.h"
+
+// =============================================================================
+// region Checksums
+// =============================================================================
+
+uint8_t robustoCrc8_07(uint8_t aCrc, uint8_t aByte)
+{
+    unsigned int i;
+
+    aCrc ^= aByte;
+
+    for (i = 0; i < 8u; ++i) {
+        aCrc = (aCrc & 0x80u) ? (uint8_t)((aCrc << 1) ^ 0x07u) : (uint8_t)(aCrc << 1);
+    }
+
+    return aCrc;
+}
+
+uint32_t robustoCrc32_04C11DB7Reflected(uint32_t aCrc, uint8_t aByte)
+{
+    unsigned int i;
+
+    aCrc ^= aByte;
+
+    for (i = 0; i < 8u; ++i) {
+        aCrc = (aCrc & 1u) ? (uint32_t)((aCrc >> 1) ^ 0xEDB88320u) : (uint32_t)(aCrc >> 1);
+    }
+
+    return aCrc;
+}
+// endregion Checksums
+
+// =============================================================================
+// region Sample parser
+// =============================================================================
+
+%%{
+    machine Sample;
+    write data;
+}%%
+void machineSampleParserStateInit(struct SampleParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->short_Checksum = 0x00u;
+    aParserState->long_Checksum = 0xFFFFFFFFu;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Sample;
+    access aParserState->;
+    alphtype unsigned char;
+    action a {
+    }
+    action a_byte {
+        aSample->a = (uint8_t)(((uint8_t)aSample->a << 8) | (uint8_t)fc);
+    }
+    action b {
+    }
+    action b_byte {
+        aSample->b = (uint16_t)(((uint16_t)aSample->b << 8) | (uint8_t)fc);
+    }
+    action crc8 {
+        if (aSample->crc8 != aParserState->short_Checksum) {
+            aParserState->error = ROBUSTO_PARSE_ERROR_CHECKSUM;
+            fgoto *Sample_error;
+        }
+    }
+    action short__checksum {
+        aParserState->short_Checksum = robustoCrc8_07(aParserState->short_Checksum, (uint8_t)fc);
+    }
+    action crc8_byte {
+        aSample->crc8 = (uint8_t)(((uint8_t)aSample->crc8 << 8) | (uint8_t)fc);
+    }
+    action crc32 {
+        if (aSample->crc32 != (uint32_t)(aParserState->long_Checksum ^ 0xFFFFFFFFu)) {
+            aParserState->error = ROBUSTO_PARSE_ERROR_CHECKSUM;
+            fgoto *Sample_error;
+        }
+    }
+    action long__checksum {
+        aParserState->long_Checksum = robustoCrc32_04C11DB7Reflected(aParserState->long_Checksum, (uint8_t)fc);
+    }
+    action crc32_byte {
+        aSample->crc32 = (uint32_t)(((uint32_t)aSample->crc32 << 8) | (uint8_t)fc);
+    }
+    a = any{1} $a_byte @a; 
+    b = any{2} $b_byte @b; 
+    crc8 = any{1} $crc8_byte @crc8; 
+    crc32 = any{4} $crc32_byte @crc32; 
+    main := ( any* :>> ( 0xAA ) ) ( a $short__checksum $long__checksum ) ( b $short__checksum $long__checksum ) ( crc8 $long__checksum ) crc32;
+}%%
+static enum RobustoParseStatus robustoParseStatusSample(const struct SampleParserState *aParserState)
+{
+    if (aParserState->cs >= Sample_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Sample_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseSample(struct SampleParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct SampleMessage *aSample, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineSampleParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    for (;;) {
+        %% write exec;
+
+        if (aParserState->cs != Sample_error || p == pe) {
+            break;
+        }
+
+        // Resynchronize on the rest of the input
+        machineSampleParserStateInit(aParserState);
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusSample(aParserState);
+}
+int isSampleAccepted(const struct SampleParserState *aParserState)
+{
+    return aParserState->cs >= Sample_first_final;
+}
+// endregion Sample parser
+
+// =============================================================================
+// region Self-test
+// =============================================================================
+
+int selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0xAA, 0xEF, 0xAB, 0xCD, 0x6B, 0xDE, 0x41, 0x2D, 0x20};
+        struct SampleParserState parserState;
+        struct SampleMessage message = {0};
+        struct SampleMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineSampleParserStateInit(&parserState);
+
+        if (parseSample(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.a != 239u) {
+            return 1;
+        }
+
+        if (message.b != 43981u) {
+            return 1;
+        }
+
+        if (message.crc8 != 107u) {
+            return 1;
+        }
+
+        if (message.crc32 != 3728813344u) {
+            return 1;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseSample(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.a != 239u) {
+            return 1;
+        }
+
+        if (splitMessage.b != 43981u) {
+            return 1;
+        }
+
+        if (splitMessage.crc8 != 107u) {
+            return 1;
+        }
+
+        if (splitMessage.crc32 != 3728813344u) {
+            return 1;
+        }
+    }
+    {
+        // Byte order: multi-byte fields carry 0x01, 0x02, ... on the wire, and decode the same on any host
+        static const uint8_t kFrame[] = {0xAA, 0xEF, 0x01, 0x02, 0x92, 0x82, 0xAD, 0x74, 0xAF};
+        struct SampleParserState parserState;
+        struct SampleMessage message = {0};
+        struct SampleMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineSampleParserStateInit(&parserState);
+
+        if (parseSample(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (message.a != 239u) {
+            return 2;
+        }
+
+        if (message.b != 0x102u) {
+            return 2;
+        }
+
+        if (message.crc8 != 146u) {
+            return 2;
+        }
+
+        if (message.crc32 != 2192405679u) {
+            return 2;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseSample(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (splitMessage.a != 239u) {
+            return 2;
+        }
+
+        if (splitMessage.b != 0x102u) {
+            return 2;
+        }
+
+        if (splitMessage.crc8 != 146u) {
+            return 2;
+        }
+
+        if (splitMessage.crc32 != 2192405679u) {
+            return 2;
+        }
+    }
+
+    return 0;
+}
+// endregion Self-test
@@ -0,0 +1,164 @@
+#include "output.h"
+
+/* ============================================================================= */
+/* region Beacon parser */
+/* ============================================================================= */
+
+%%{
+    machine Beacon;
+    write data;
+}%%
+void machineBeaconParserStateInit(struct BeaconParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->discardedLength = 0;
+    aParserState->pendingLength = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Beacon;
+    access aParserState->;
+    alphtype unsigned char;
+    action huntedByte {
+        aParserState->pendingLength++;
+    }
+    action synchronized {
+        aParserState->discardedLength += aParserState->pendingLength - 1u;
+        aParserState->pendingLength = 2u;
+    }
+    action framedByte {
+        aParserState->pendingLength++;
+    }
+    action frameSettled {
+        aParserState->pendingLength = 0;
+    }
+    action node {
+        if (aBeacon->node < 1u || aBeacon->node > 100u) {
+            aParserState->error = ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE;
+            fgoto *Beacon_error;
+        }
+    }
+    action node_byte {
+        aBeacon->node = (uint8_t)(((uint8_t)aBeacon->node << 8) | (uint8_t)fc);
+    }
+    action rssi {
+    }
+    action rssi_byte {
+        aBeacon->rssi = (int8_t)(((uint8_t)aBeacon->rssi << 8) | (uint8_t)fc);
+    }
+    node = any{1} $node_byte @node; 
+    rssi = any{1} $rssi_byte @rssi; 
+    main := ( ( ( any $huntedByte )* :>> ( 0xAA 0x55 ) ) @synchronized ( node rssi ) $framedByte ) @frameSettled;
+}%%
+static enum RobustoParseStatus robustoParseStatusBeacon(const struct BeaconParserState *aParserState)
+{
+    if (aParserState->cs >= Beacon_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Beacon_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseBeacon(struct BeaconParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct BeaconMessage *aBeacon, int *aConsumedLength)
+{
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  /* Iterator "begin" pointer -- Ragel-specific variable for C code generation */
+    const unsigned char *pe = p + aInputBufferLength;  /* Iterator "end" pointer -- Ragel-specific variable for C code generation */
+
+    if (aParserState->machineInitRequired != 0) {
+        machineBeaconParserStateInit(aParserState);
+    }
+
+    /* Parse starting from the state defined in `aParserState` */
+    for (;;) {
+        uint32_t discardedLength;
+
+        %% write exec;
+
+        if (aParserState->cs != Beacon_error || p == pe) {
+            break;
+        }
+
+        /* Resynchronize on the rest of the input */
+        discardedLength = aParserState->discardedLength + aParserState->pendingLength;
+        machineBeaconParserStateInit(aParserState);
+        aParserState->discardedLength = discardedLength;
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusBeacon(aParserState);
+}
+int isBeaconAccepted(const struct BeaconParserState *aParserState)
+{
+    return aParserState->cs >= Beacon_first_final;
+}
+/* endregion Beacon parser */
+
+/* ============================================================================= */
+/* region Self-test */
+/* ============================================================================= */
+
+int selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0xAA, 0x55, 0x64, 0x4D};
+        struct BeaconParserState parserState;
+        struct BeaconMessage message = {0};
+        struct BeaconMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineBeaconParserStateInit(&parserState);
+
+        if (parseBeacon(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.node != 100u) {
+            return 1;
+        }
+
+        if (message.rssi != 77) {
+            return 1;
+        }
+
+        /* Feed the frame once more, one byte at a time, into a restarted parser */
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseBeacon(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.node != 100u) {
+            return 1;
+        }
+
+        if (splitMessage.rssi != 77) {
+            return 1;
+        }
+    }
+
+    return 0;
+}
+/* endregion Self-test */
@@ -0,0 +1,83 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* ============================================================================= */
+/* region Parse errors */
+/* ============================================================================= */
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5
+};
+/* endregion Parse errors */
+
+/* ============================================================================= */
+/* region Beacon types */
+/* ============================================================================= */
+
+struct BeaconMessage {
+    uint8_t node;
+    int8_t rssi;
+};
+struct BeaconParserState {
+    /* Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off */
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint32_t discardedLength;
+    uint32_t pendingLength;
+};
+/* endregion Beacon types */
+
+/* ============================================================================= */
+/* region Sizing constants */
+/* ============================================================================= */
+
+union RobustoAnyMessage {
+    struct BeaconMessage Beacon;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_BEACON_MIN_FRAME_SIZE 4u
+#define ROBUSTO_BEACON_MAX_FRAME_SIZE 4u
+#define ROBUSTO_MIN_FRAME_SIZE 4u
+#define ROBUSTO_MAX_FRAME_SIZE 4u
+/* endregion Sizing constants */
+
+/* ============================================================================= */
+/* region API */
+/* ============================================================================= */
+
+/* Non-null: aParserState */
+void machineBeaconParserStateInit(struct BeaconParserState *aParserState);
+/* Non-null: aParserState, aInputBuffer, aBeacon */
+enum RobustoParseStatus parseBeacon(struct BeaconParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct BeaconMessage *aBeacon, int *aConsumedLength);
+/* Non-null: aParserState */
+int isBeaconAccepted(const struct BeaconParserState *aParserState);
+int selfTest(void);
+/* endregion API */
+
+#ifdef __cplusplus
+}  /* extern "C" */
+#endif
+
+#endif  /* OUTPUT_H */
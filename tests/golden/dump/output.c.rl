@@ -0,0 +1,194 @@
+#include "output.h"
+
+/* ============================================================================= */
+/* region Sensor parser */
+/* ============================================================================= */
+
+%%{
+    machine Sensor;
+    write data;
+}%%
+/* Physical value of `humidity`, in %RH */
+float getSensorHumidity_percentRH(const struct SensorMessage *aSensor)
+{
+    return (float)aSensor->humidity * 0.1f + 0.0f;
+}
+int testSensorStatus(const struct SensorMessage *aSensor, uint8_t aFlags)
+{
+    return (aSensor->status & aFlags) == aFlags;
+}
+void setSensorStatus(struct SensorMessage *aSensor, uint8_t aFlags, int aValue)
+{
+    if (aValue) {
+        aSensor->status = (uint8_t)(aSensor->status | aFlags);
+    } else {
+        aSensor->status = (uint8_t)(aSensor->status & ~aFlags);
+    }
+}
+void dumpSensor(const struct SensorMessage *aSensor, int (*aPrint)(const char *aFormat, ...))
+{
+    unsigned int i;
+
+    aPrint("Sensor:\n");
+    aPrint("  kind: %lu\n", (unsigned long)aSensor->kind);
+    aPrint("  humidity: %lu (%g %%RH)\n", (unsigned long)aSensor->humidity, (double)getSensorHumidity_percentRH(aSensor));
+    if (aSensor->kind == 2u) {
+        aPrint("  temperature: %ld\n", (long)aSensor->kindVariant.temperature);
+    }
+    aPrint("  status: 0x%lX", (unsigned long)aSensor->status);
+    if ((aSensor->status & SENSOR_STATUS_FLAG_READY) != 0) {
+        aPrint(" ready");
+    }
+    if ((aSensor->status & SENSOR_STATUS_FLAG_FAULT) != 0) {
+        aPrint(" fault");
+    }
+    aPrint("\n");
+    aPrint("  padding:");
+    for (i = 0; i < 2u; ++i) {
+        aPrint(" %02X", (unsigned int)aSensor->padding[i]);
+    }
+    aPrint("\n");
+    for (i = 0; i < 2u; ++i) {
+        aPrint("  channels[%u].id: %lu\n", i, (unsigned long)aSensor->channels[i].id);
+        aPrint("  channels[%u].value: %ld\n", i, (long)aSensor->channels[i].value);
+    }
+    aPrint("  uptime: 0x%08lX%08lX\n", (unsigned long)((uint64_t)aSensor->uptime >> 32), (unsigned long)((uint64_t)aSensor->uptime & 0xFFFFFFFFu));
+    aPrint("  label: \"%.*s\"\n", (int)aSensor->labelLength, (const char *)aSensor->label);
+}
+void machineSensorParserStateInit(struct SensorParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Sensor;
+    access aParserState->;
+    alphtype unsigned char;
+    action kind {
+    }
+    action kind_byte {
+        aSensor->kind = (uint8_t)(((uint8_t)aSensor->kind << 8) | (uint8_t)fc);
+    }
+    action humidity {
+    }
+    action humidity_byte {
+        aSensor->humidity = (uint16_t)(((uint16_t)aSensor->humidity << 8) | (uint8_t)fc);
+    }
+    action temperature {
+    }
+    action temperature_when {
+        ((int64_t)aSensor->kind == INT64_C(2))
+    }
+    action temperature_byte {
+        aSensor->kindVariant.temperature = (int16_t)(((uint16_t)aSensor->kindVariant.temperature << 8) | (uint8_t)fc);
+    }
+    action status {
+    }
+    action status_byte {
+        aSensor->status = (uint8_t)(((uint8_t)aSensor->status << 8) | (uint8_t)fc);
+    }
+    action padding {
+    }
+    action padding_byte {
+        unsigned int i;
+        
+        for (i = 1; i < 2u; ++i) {
+            aSensor->padding[i - 1] = aSensor->padding[i];
+        }
+        aSensor->padding[1] = (uint8_t)fc;
+    }
+    action channels {
+    }
+    action channels_reset {
+        aParserState->channelsIndex = 0;
+    }
+    action channels_next {
+        ++aParserState->channelsIndex;
+    }
+    action channels_id_byte {
+        aSensor->channels[aParserState->channelsIndex].id = (uint8_t)(((uint8_t)aSensor->channels[aParserState->channelsIndex].id << 8) | (uint8_t)fc);
+    }
+    action channels_value_byte {
+        aSensor->channels[aParserState->channelsIndex].value = (int16_t)(((uint16_t)aSensor->channels[aParserState->channelsIndex].value << 8) | (uint8_t)fc);
+    }
+    action uptime {
+    }
+    action uptime_byte {
+        aSensor->uptime = (uint64_t)(((uint64_t)aSensor->uptime << 8) | (uint8_t)fc);
+    }
+    action label {
+    }
+    action label_reset {
+        aSensor->labelLength = 0;
+    }
+    action label_byte {
+        if (aSensor->labelLength < 8u) {
+            aSensor->label[aSensor->labelLength++] = (uint8_t)fc;
+        }
+    }
+    kind = any{1} $kind_byte @kind; 
+    humidity = any{2} $humidity_byte @humidity; 
+    temperature = ( any{2} $temperature_byte @temperature ) when temperature_when; 
+    status = any{1} $status_byte @status; 
+    padding = any{2} $padding_byte @padding; 
+    channels = ( any{1} $channels_id_byte any{2} $channels_value_byte @channels_next ){2} >channels_reset @channels;
+    uptime = any{8} $uptime_byte @uptime; 
+    label = ( ( 'A'..'Z' )+ 0x00 ) >label_reset $label_byte @label; 
+    main := ( any* :>> ( 0x7E ) ) kind humidity temperature status padding channels uptime label;
+}%%
+static enum RobustoParseStatus robustoParseStatusSensor(const struct SensorParserState *aParserState)
+{
+    if (aParserState->cs >= Sensor_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Sensor_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseSensor(struct SensorParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct SensorMessage *aSensor, int *aConsumedLength)
+{
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  /* Iterator "begin" pointer -- Ragel-specific variable for C code generation */
+    const unsigned char *pe = p + aInputBufferLength;  /* Iterator "end" pointer -- Ragel-specific variable for C code generation */
+
+    if (aParserState->machineInitRequired != 0) {
+        machineSensorParserStateInit(aParserState);
+    }
+
+    /* Parse starting from the state defined in `aParserState` */
+    for (;;) {
+        %% write exec;
+
+        if (aParserState->cs != Sensor_error || p == pe) {
+            break;
+        }
+
+        /* Resynchronize on the rest of the input */
+        machineSensorParserStateInit(aParserState);
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusSensor(aParserState);
+}
+int isSensorAccepted(const struct SensorParserState *aParserState)
+{
+    return aParserState->cs >= Sensor_first_final;
+}
+/* endregion Sensor parser */
@@ -0,0 +1,105 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* ============================================================================= */
+/* region Parse errors */
+/* ============================================================================= */
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5
+};
+/* endregion Parse errors */
+
+/* ============================================================================= */
+/* region Sensor types */
+/* ============================================================================= */
+
+struct SensorChannelsGroup {
+    uint8_t id;
+    int16_t value;
+};
+#define SENSOR_STATUS_FLAG_READY (1u << 0)
+#define SENSOR_STATUS_FLAG_FAULT (1u << 7)
+struct SensorMessage {
+    uint8_t kind;
+    /** Unit: %RH, once scaled */
+    uint16_t humidity;
+    union {
+        int16_t temperature;
+    } kindVariant;
+    uint8_t status;
+    uint8_t padding[2];
+    struct SensorChannelsGroup channels[2];
+    uint64_t uptime;
+    uint8_t label[8];
+    uint32_t labelLength;
+};
+struct SensorParserState {
+    /* Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off */
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint32_t channelsIndex;
+};
+/* endregion Sensor types */
+
+/* ============================================================================= */
+/* region Sizing constants */
+/* ============================================================================= */
+
+union RobustoAnyMessage {
+    struct SensorMessage Sensor;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_SENSOR_MIN_FRAME_SIZE 25u
+#define ROBUSTO_SENSOR_MAX_FRAME_SIZE 31u
+#define ROBUSTO_MIN_FRAME_SIZE 25u
+#define ROBUSTO_MAX_FRAME_SIZE 31u
+/* endregion Sizing constants */
+
+/* ============================================================================= */
+/* region API */
+/* ============================================================================= */
+
+/* Non-null: aParserState */
+void machineSensorParserStateInit(struct SensorParserState *aParserState);
+/* Non-null: aParserState, aInputBuffer, aSensor */
+enum RobustoParseStatus parseSensor(struct SensorParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct SensorMessage *aSensor, int *aConsumedLength);
+/* Non-null: aParserState */
+int isSensorAccepted(const struct SensorParserState *aParserState);
+/* Non-null: aSensor */
+float getSensorHumidity_percentRH(const struct SensorMessage *aSensor);
+/* Non-null: aSensor */
+int testSensorStatus(const struct SensorMessage *aSensor, uint8_t aFlags);
+/* Non-null: aSensor */
+void setSensorStatus(struct SensorMessage *aSensor, uint8_t aFlags, int aValue);
+/* Non-null: aSensor */
+void dumpSensor(const struct SensorMessage *aSensor, int (*aPrint)(const char *aFormat, ...));
+/* endregion API */
+
+#ifdef __cplusplus
+}  /* extern "C" */
+#endif
+
+#endif  /* OUTPUT_H */
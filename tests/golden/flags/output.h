@@ -0,0 +1,127 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+/* ROBUSTO USER CODE BEGIN Parse errors */
+/* ROBUSTO USER CODE END Parse errors */
+// endregion Parse errors
+
+// =============================================================================
+// region Status types
+// =============================================================================
+
+#define STATUS_STATE_FLAG_READY (1u << 0)
+#define STATUS_STATE_FLAG_LOW_BATTERY (1u << 1)
+#define STATUS_STATE_FLAG_FAULT (1u << 7)
+#define STATUS_WIDE_FLAG_A (UINT32_C(1) << 20)
+#define STATUS_WIDE_FLAG_B (UINT64_C(1) << 40)
+struct StatusMessage {
+    uint8_t sync[1];
+    uint32_t syncLength;
+    /** Device state */
+    uint8_t state;
+    uint64_t wide;
+};
+struct StatusParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+/* ROBUSTO USER CODE BEGIN Status types */
+/* ROBUSTO USER CODE END Status types */
+// endregion Status types
+
+// =============================================================================
+// region Bits types
+// =============================================================================
+
+#define BITS_MODE_FLAG_X (1u << 2)
+struct BitsMessage {
+    uint8_t mode;
+    uint8_t rest;
+};
+/* ROBUSTO USER CODE BEGIN Bits types */
+/* ROBUSTO USER CODE END Bits types */
+// endregion Bits types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct StatusMessage Status;
+    struct BitsMessage Bits;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_STATUS_MIN_FRAME_SIZE 10u
+#define ROBUSTO_STATUS_MAX_FRAME_SIZE 10u
+#define ROBUSTO_BITS_MIN_FRAME_SIZE 1u
+#define ROBUSTO_BITS_MAX_FRAME_SIZE 1u
+#define ROBUSTO_MIN_FRAME_SIZE 1u
+#define ROBUSTO_MAX_FRAME_SIZE 10u
+/* ROBUSTO USER CODE BEGIN Sizing constants */
+/* ROBUSTO USER CODE END Sizing constants */
+// endregion Sizing constants
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineStatusParserStateInit(struct StatusParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aStatus
+enum RobustoParseStatus parseStatus(struct StatusParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct StatusMessage *aStatus, int *aConsumedLength);
+// Non-null: aParserState
+int isStatusAccepted(const struct StatusParserState *aParserState);
+// Non-null: aStatus
+int testStatusState(const struct StatusMessage *aStatus, uint8_t aFlags);
+// Non-null: aStatus
+void setStatusState(struct StatusMessage *aStatus, uint8_t aFlags, int aValue);
+// Non-null: aStatus
+int testStatusWide(const struct StatusMessage *aStatus, uint64_t aFlags);
+// Non-null: aStatus
+void setStatusWide(struct StatusMessage *aStatus, uint64_t aFlags, int aValue);
+// Non-null: aInputBuffer, aBits
+int parseBits(const uint8_t *aInputBuffer, int aInputBufferLength, struct BitsMessage *aBits);
+// Non-null: aBits
+int testBitsMode(const struct BitsMessage *aBits, uint8_t aFlags);
+// Non-null: aBits
+void setBitsMode(struct BitsMessage *aBits, uint8_t aFlags, int aValue);
+int selfTest(void);
+/* ROBUSTO USER CODE BEGIN API */
+/* ROBUSTO USER CODE END API */
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
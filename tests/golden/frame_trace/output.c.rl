@@ -0,0 +1,135 @@
+#include "output.h"
+
+// =============================================================================
+// region Frame trace
+// =============================================================================
+
+struct RobustoTraceRecord robustoTraceRecords[ROBUSTO_TRACE_DEPTH];
+uint32_t robustoTraceCount;  // Number of traced frames
+
+// Copies the latest records, oldest first. Returns the number of copied records
+unsigned int robustoTraceDump(struct RobustoTraceRecord *aRecords, unsigned int aCapacity)
+{
+    uint32_t n = robustoTraceCount < ROBUSTO_TRACE_DEPTH ? robustoTraceCount : ROBUSTO_TRACE_DEPTH;
+    uint32_t i;
+
+    if (n > aCapacity) {
+        n = aCapacity;
+    }
+
+    for (i = 0; i < n; ++i) {
+        aRecords[i] = robustoTraceRecords[(robustoTraceCount - n + i) % ROBUSTO_TRACE_DEPTH];
+    }
+
+    return (unsigned int)n;
+}
+
+static void robustoTraceFrame(uint8_t aMessageId, uint8_t aAccepted, uint8_t aError, uint32_t aLength)
+{
+    struct RobustoTraceRecord *record = &robustoTraceRecords[robustoTraceCount % ROBUSTO_TRACE_DEPTH];
+
+    record->timestamp = boardMillis();
+    record->length = aLength;
+    record->messageId = aMessageId;
+    record->accepted = aAccepted;
+    record->error = aError;
+    ++robustoTraceCount;
+}
+// endregion Frame trace
+
+// =============================================================================
+// region Heartbeat parser
+// =============================================================================
+
+%%{
+    machine Heartbeat;
+    write data;
+}%%
+void machineHeartbeatParserStateInit(struct HeartbeatParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->traceLength = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Heartbeat;
+    access aParserState->;
+    alphtype unsigned char;
+    action uptime {
+    }
+    action uptime_byte {
+        aHeartbeat->uptime = (uint32_t)(((uint32_t)aHeartbeat->uptime << 8) | (uint8_t)fc);
+    }
+    uptime = any{4} $uptime_byte @uptime; 
+    main := ( any* :>> ( 0xAA 0x55 ) ) uptime;
+}%%
+static void robustoTraceHeartbeat(int aPreviousCs, const struct HeartbeatParserState *aParserState)
+{
+    if (aPreviousCs < Heartbeat_first_final && aParserState->cs >= Heartbeat_first_final) {
+        robustoTraceFrame(0u, 1u, aParserState->error, aParserState->traceLength);
+    } else if (aPreviousCs != Heartbeat_error && aParserState->cs == Heartbeat_error) {
+        robustoTraceFrame(0u, 0u, aParserState->error, aParserState->traceLength);
+    }
+}
+
+static enum RobustoParseStatus robustoParseStatusHeartbeat(const struct HeartbeatParserState *aParserState)
+{
+    if (aParserState->cs >= Heartbeat_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Heartbeat_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseHeartbeat(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength)
+{
+    const int previousCs = (aParserState->machineInitRequired != 0) ? Heartbeat_start : aParserState->cs;
+
+    if (aParserState->machineInitRequired != 0) {
+        machineHeartbeatParserStateInit(aParserState);
+    }
+
+    aParserState->traceLength += (uint32_t)aInputBufferLength;
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    for (;;) {
+        %% write exec;
+
+        if (aParserState->cs != Heartbeat_error || p == pe) {
+            break;
+        }
+
+        // Resynchronize on the rest of the input
+        robustoTraceHeartbeat(previousCs, aParserState);
+        machineHeartbeatParserStateInit(aParserState);
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    robustoTraceHeartbeat(previousCs, aParserState);
+    return robustoParseStatusHeartbeat(aParserState);
+}
+int isHeartbeatAccepted(const struct HeartbeatParserState *aParserState)
+{
+    return aParserState->cs >= Heartbeat_first_final;
+}
+// endregion Heartbeat parser
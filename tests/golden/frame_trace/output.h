@@ -0,0 +1,100 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region Heartbeat types
+// =============================================================================
+
+struct HeartbeatMessage {
+    uint32_t uptime;
+};
+struct HeartbeatParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint32_t traceLength;
+};
+// endregion Heartbeat types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct HeartbeatMessage Heartbeat;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_HEARTBEAT_MIN_FRAME_SIZE 6u
+#define ROBUSTO_HEARTBEAT_MAX_FRAME_SIZE 6u
+#define ROBUSTO_MIN_FRAME_SIZE 6u
+#define ROBUSTO_MAX_FRAME_SIZE 6u
+// endregion Sizing constants
+
+// =============================================================================
+// region Frame trace
+// =============================================================================
+
+#define ROBUSTO_TRACE_DEPTH 8u
+#define ROBUSTO_HEARTBEAT_TRACE_ID 0u
+struct RobustoTraceRecord {
+    uint32_t timestamp;
+    uint32_t length;
+    uint8_t messageId;
+    uint8_t accepted;
+    uint8_t error;
+};
+extern struct RobustoTraceRecord robustoTraceRecords[ROBUSTO_TRACE_DEPTH];
+extern uint32_t robustoTraceCount;
+uint32_t boardMillis(void);
+// endregion Frame trace
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineHeartbeatParserStateInit(struct HeartbeatParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aHeartbeat
+enum RobustoParseStatus parseHeartbeat(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength);
+// Non-null: aParserState
+int isHeartbeatAccepted(const struct HeartbeatParserState *aParserState);
+// Non-null: aRecords
+unsigned int robustoTraceDump(struct RobustoTraceRecord *aRecords, unsigned int aCapacity);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
@@ -0,0 +1,351 @@
+#include "output.h"
+
+// =============================================================================
+// region sensor_report parser
+// =============================================================================
+
+%%{
+    machine sensor_report;
+    write data;
+}%%
+void machinesensor_reportParserStateInit(struct sensor_reportParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine sensor_report;
+    access aParserState->;
+    alphtype unsigned char;
+    action preamble {
+    }
+    action preamble_reset {
+        asensor_report->preambleLength = 0;
+    }
+    action preamble_byte {
+        if (asensor_report->preambleLength < 64u) {
+            asensor_report->preamble[asensor_report->preambleLength++] = (uint8_t)fc;
+        }
+    }
+    action battery_level_2 {
+    }
+    action battery_level_2_byte {
+        asensor_report->battery_level_2 = (uint8_t)(((uint8_t)asensor_report->battery_level_2 << 8) | (uint8_t)fc);
+    }
+    action battery_level {
+    }
+    action battery_level_byte {
+        asensor_report->battery_level = (uint8_t)(((uint8_t)asensor_report->battery_level << 8) | (uint8_t)fc);
+    }
+    action _2nd_reading {
+        if (!((int64_t)asensor_report->_2nd_reading > (int64_t)asensor_report->battery_level_2)) {
+            aParserState->error = ROBUSTO_PARSE_ERROR_CHECK;
+            fgoto *sensor_report_error;
+        }
+    }
+    action _2nd_reading_byte {
+        asensor_report->_2nd_reading = (int16_t)(((uint16_t)asensor_report->_2nd_reading << 8) | (uint8_t)fc);
+    }
+    action rate_x25_ {
+    }
+    action rate_x25__byte {
+        asensor_report->rate_x25_ = (uint16_t)(((uint16_t)asensor_report->rate_x25_ << 8) | (uint8_t)fc);
+    }
+    preamble = 0xFE >preamble_reset $preamble_byte @preamble; 
+    battery_level_2 = any{1} $battery_level_2_byte @battery_level_2; 
+    battery_level = any{1} $battery_level_byte @battery_level; 
+    _2nd_reading = any{2} $_2nd_reading_byte @_2nd_reading; 
+    rate_x25_ = any{2} $rate_x25__byte @rate_x25_; 
+    main := preamble battery_level_2 battery_level _2nd_reading rate_x25_;
+}%%
+static enum RobustoParseStatus robustoParseStatussensor_report(const struct sensor_reportParserState *aParserState)
+{
+    if (aParserState->cs >= sensor_report_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != sensor_report_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parsesensor_report(struct sensor_reportParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct sensor_reportMessage *asensor_report, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machinesensor_reportParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatussensor_report(aParserState);
+}
+int issensor_reportAccepted(const struct sensor_reportParserState *aParserState)
+{
+    return aParserState->cs >= sensor_report_first_final;
+}
+// endregion sensor_report parser
+
+// =============================================================================
+// region switch_ parser
+// =============================================================================
+
+%%{
+    machine switch_;
+    write data;
+}%%
+void machineswitch_ParserStateInit(struct switch_ParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine switch_;
+    access aParserState->;
+    alphtype unsigned char;
+    action int_ {
+    }
+    action int__byte {
+        aswitch_->int_ = (uint8_t)(((uint8_t)aswitch_->int_ << 8) | (uint8_t)fc);
+    }
+    action p_ {
+    }
+    action p__byte {
+        aswitch_->p_ = (uint8_t)(((uint8_t)aswitch_->p_ << 8) | (uint8_t)fc);
+    }
+    int_ = any{1} $int__byte @int_; 
+    p_ = any{1} $p__byte @p_; 
+    main := int_ p_;
+}%%
+static enum RobustoParseStatus robustoParseStatusswitch_(const struct switch_ParserState *aParserState)
+{
+    if (aParserState->cs >= switch__first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != switch__error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseswitch_(struct switch_ParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct switch_Message *aswitch_, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineswitch_ParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusswitch_(aParserState);
+}
+int isswitch_Accepted(const struct switch_ParserState *aParserState)
+{
+    return aParserState->cs >= switch__first_final;
+}
+// endregion switch_ parser
+
+// =============================================================================
+// region Self-test
+// =============================================================================
+
+int selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0xFE, 0xCD, 0xAB, 0x67, 0x89, 0x45, 0x67};
+        struct sensor_reportParserState parserState;
+        struct sensor_reportMessage message = {0};
+        struct sensor_reportMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machinesensor_reportParserStateInit(&parserState);
+
+        if (parsesensor_report(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.battery_level_2 != 205u) {
+            return 1;
+        }
+
+        if (message.battery_level != 171u) {
+            return 1;
+        }
+
+        if (message._2nd_reading != 26505) {
+            return 1;
+        }
+
+        if (message.rate_x25_ != 17767u) {
+            return 1;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parsesensor_report(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.battery_level_2 != 205u) {
+            return 1;
+        }
+
+        if (splitMessage.battery_level != 171u) {
+            return 1;
+        }
+
+        if (splitMessage._2nd_reading != 26505) {
+            return 1;
+        }
+
+        if (splitMessage.rate_x25_ != 17767u) {
+            return 1;
+        }
+    }
+    {
+        // Byte order: multi-byte fields carry 0x01, 0x02, ... on the wire, and decode the same on any host
+        static const uint8_t kFrame[] = {0xFE, 0xCD, 0xAB, 0x01, 0x02, 0x01, 0x02};
+        struct sensor_reportParserState parserState;
+        struct sensor_reportMessage message = {0};
+        struct sensor_reportMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machinesensor_reportParserStateInit(&parserState);
+
+        if (parsesensor_report(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (message.battery_level_2 != 205u) {
+            return 2;
+        }
+
+        if (message.battery_level != 171u) {
+            return 2;
+        }
+
+        if (message._2nd_reading != 0x102) {
+            return 2;
+        }
+
+        if (message.rate_x25_ != 0x102u) {
+            return 2;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parsesensor_report(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (splitMessage.battery_level_2 != 205u) {
+            return 2;
+        }
+
+        if (splitMessage.battery_level != 171u) {
+            return 2;
+        }
+
+        if (splitMessage._2nd_reading != 0x102) {
+            return 2;
+        }
+
+        if (splitMessage.rate_x25_ != 0x102u) {
+            return 2;
+        }
+    }
+    {
+        static const uint8_t kFrame[] = {0xEF, 0xCD};
+        struct switch_ParserState parserState;
+        struct switch_Message message = {0};
+        struct switch_Message splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineswitch_ParserStateInit(&parserState);
+
+        if (parseswitch_(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 3;
+        }
+
+        if (message.int_ != 239u) {
+            return 3;
+        }
+
+        if (message.p_ != 205u) {
+            return 3;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseswitch_(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 3;
+        }
+
+        if (splitMessage.int_ != 239u) {
+            return 3;
+        }
+
+        if (splitMessage.p_ != 205u) {
+            return 3;
+        }
+    }
+
+    return 0;
+}
+// endregion Self-test
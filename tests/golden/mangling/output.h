@@ -0,0 +1,110 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region sensor_report types
+// =============================================================================
+
+struct sensor_reportMessage {
+    uint8_t preamble[64];
+    uint32_t preambleLength;
+    uint8_t battery_level_2;
+    uint8_t battery_level;
+    int16_t _2nd_reading;
+    uint16_t rate_x25_;
+};
+struct sensor_reportParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+// endregion sensor_report types
+
+// =============================================================================
+// region switch_ types
+// =============================================================================
+
+struct switch_Message {
+    uint8_t int_;
+    uint8_t p_;
+};
+struct switch_ParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+// endregion switch_ types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct sensor_reportMessage sensor_report;
+    struct switch_Message switch_;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_SENSOR_REPORT_MIN_FRAME_SIZE 7u
+#define ROBUSTO_SENSOR_REPORT_MAX_FRAME_SIZE 7u
+#define ROBUSTO_SWITCH__MIN_FRAME_SIZE 2u
+#define ROBUSTO_SWITCH__MAX_FRAME_SIZE 2u
+#define ROBUSTO_MIN_FRAME_SIZE 2u
+#define ROBUSTO_MAX_FRAME_SIZE 7u
+// endregion Sizing constants
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machinesensor_reportParserStateInit(struct sensor_reportParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, asensor_report
+enum RobustoParseStatus parsesensor_report(struct sensor_reportParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct sensor_reportMessage *asensor_report, int *aConsumedLength);
+// Non-null: aParserState
+int issensor_reportAccepted(const struct sensor_reportParserState *aParserState);
+// Non-null: aParserState
+void machineswitch_ParserStateInit(struct switch_ParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aswitch_
+enum RobustoParseStatus parseswitch_(struct switch_ParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct switch_Message *aswitch_, int *aConsumedLength);
+// Non-null: aParserState
+int isswitch_Accepted(const struct switch_ParserState *aParserState);
+int selfTest(void);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
@@ -0,0 +1,351 @@
+#include "output.h"
+
+// =============================================================================
+// region Frame trace
+// =============================================================================
+
+struct RobustoTraceRecord robustoTraceRecords[ROBUSTO_TRACE_DEPTH];
+uint32_t robustoTraceCount;  // Number of traced frames
+
+// Copies the latest records, oldest first. Returns the number of copied records
+unsigned int robustoTraceDump(struct RobustoTraceRecord *aRecords, unsigned int aCapacity)
+{
+    uint32_t n = robustoTraceCount < ROBUSTO_TRACE_DEPTH ? robustoTraceCount : ROBUSTO_TRACE_DEPTH;
+    uint32_t i;
+
+    if (n > aCapacity) {
+        n = aCapacity;
+    }
+
+    for (i = 0; i < n; ++i) {
+        aRecords[i] = robustoTraceRecords[(robustoTraceCount - n + i) % ROBUSTO_TRACE_DEPTH];
+    }
+
+    return (unsigned int)n;
+}
+
+static void robustoTraceFrame(uint8_t aMessageId, uint8_t aAccepted, uint8_t aError, uint32_t aLength)
+{
+    struct RobustoTraceRecord *record = &robustoTraceRecords[robustoTraceCount % ROBUSTO_TRACE_DEPTH];
+
+    record->timestamp = 0u;
+    record->length = aLength;
+    record->messageId = aMessageId;
+    record->accepted = aAccepted;
+    record->error = aError;
+    ++robustoTraceCount;
+}
+// endregion Frame trace
+
+// =============================================================================
+// region Heartbeat parser
+// =============================================================================
+
+%%{
+    machine Heartbeat;
+    write data;
+}%%
+void machineHeartbeatParserStateInit(struct HeartbeatParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->frameLength = 0;
+    aParserState->traceLength = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Heartbeat;
+    access aParserState->;
+    alphtype unsigned char;
+    action messageParsed {
+        if (aCallbacks != NULL && aCallbacks->onHeartbeatParsed != NULL) {
+            aCallbacks->onHeartbeatParsed(aHeartbeat, aCallbacks->context);
+        }
+    }
+    action sequence {
+    }
+    action sequence_byte {
+        aHeartbeat->sequence = (uint16_t)(((uint16_t)aHeartbeat->sequence << 8) | (uint8_t)fc);
+    }
+    action uptime {
+    }
+    action uptime_byte {
+        aHeartbeat->uptime = (uint32_t)(((uint32_t)aHeartbeat->uptime << 8) | (uint8_t)fc);
+    }
+    sequence = any{2} $sequence_byte @sequence; 
+    uptime = any{4} $uptime_byte @uptime; 
+    main := ( ( any* :>> ( 0xAA 0x55 ) ) sequence uptime ) @messageParsed;
+}%%
+static void robustoTraceHeartbeat(int aPreviousCs, const struct HeartbeatParserState *aParserState)
+{
+    if (aPreviousCs < Heartbeat_first_final && aParserState->cs >= Heartbeat_first_final) {
+        robustoTraceFrame(0u, 1u, aParserState->error, aParserState->traceLength);
+    } else if (aPreviousCs != Heartbeat_error && aParserState->cs == Heartbeat_error) {
+        robustoTraceFrame(0u, 0u, aParserState->error, aParserState->traceLength);
+    }
+}
+
+static enum RobustoParseStatus robustoParseStatusHeartbeat(const struct HeartbeatParserState *aParserState)
+{
+    enum RobustoParseStatus status;
+
+    if (aParserState->cs >= Heartbeat_first_final) {
+        status = ROBUSTO_PARSE_STATUS_OK;
+    } else if (aParserState->cs != Heartbeat_error) {
+        status = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    } else {
+        switch (aParserState->error) {
+            case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+                status = ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+                break;
+            case ROBUSTO_PARSE_ERROR_CHECKSUM:
+                status = ROBUSTO_PARSE_STATUS_ERROR_CRC;
+                break;
+            case ROBUSTO_PARSE_ERROR_OVERFLOW:
+                status = ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+                break;
+            default:
+                status = ROBUSTO_PARSE_STATUS_ERROR;
+                break;
+        }
+    }
+
+    return status;
+}
+
+enum RobustoParseStatus parseHeartbeatWithCallbacks(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength, const struct HeartbeatCallbacks *aCallbacks)
+{
+    int consumedLength = 0;
+    const int previousCs = (aParserState->machineInitRequired != 0) ? Heartbeat_start : aParserState->cs;
+
+    if (aParserState->machineInitRequired != 0) {
+        machineHeartbeatParserStateInit(aParserState);
+    }
+
+    aParserState->traceLength += (uint32_t)aInputBufferLength;
+
+    aParserState->frameLength += (uint32_t)aInputBufferLength;
+
+    if (aParserState->frameLength > 8u) {
+        aParserState->error = ROBUSTO_PARSE_ERROR_FRAME_LENGTH;
+        aParserState->cs = Heartbeat_error;
+    } else {
+        const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+        const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+        // Parse starting from the state defined in `aParserState`
+        for (;;) {
+            %% write exec;
+
+            if (aParserState->cs != Heartbeat_error || p == pe) {
+                break;
+            }
+
+            // Resynchronize on the rest of the input
+            robustoTraceHeartbeat(previousCs, aParserState);
+            machineHeartbeatParserStateInit(aParserState);
+        }
+
+        consumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    if (aConsumedLength != NULL) {
+        *aConsumedLength = consumedLength;
+    }
+
+    robustoTraceHeartbeat(previousCs, aParserState);
+    return robustoParseStatusHeartbeat(aParserState);
+}
+enum RobustoParseStatus parseHeartbeat(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength)
+{
+    return parseHeartbeatWithCallbacks(aParserState, aInputBuffer, aInputBufferLength, aHeartbeat, aConsumedLength, NULL);
+}
+int isHeartbeatAccepted(const struct HeartbeatParserState *aParserState)
+{
+    return (aParserState->cs >= Heartbeat_first_final) ? 1 : 0;
+}
+int serializeHeartbeat(const struct HeartbeatMessage *aHeartbeat, uint8_t *aBuffer, size_t aCapacity)
+{
+    size_t length = 0;
+    int result = -1;
+
+    if (aCapacity >= 8u) {
+        aBuffer[length++] = 0xAAu;
+        aBuffer[length++] = 0x55u;
+        aBuffer[length++] = (uint8_t)((uint16_t)aHeartbeat->sequence >> 8);
+        aBuffer[length++] = (uint8_t)(uint16_t)aHeartbeat->sequence;
+        aBuffer[length++] = (uint8_t)((uint32_t)aHeartbeat->uptime >> 24);
+        aBuffer[length++] = (uint8_t)((uint32_t)aHeartbeat->uptime >> 16);
+        aBuffer[length++] = (uint8_t)((uint32_t)aHeartbeat->uptime >> 8);
+        aBuffer[length++] = (uint8_t)(uint32_t)aHeartbeat->uptime;
+
+        result = (int)length;
+    }
+
+    return result;
+}
+// endregion Heartbeat parser
+
+// =============================================================================
+// region Log parser
+// =============================================================================
+
+%%{
+    machine Log;
+    write data;
+}%%
+void machineLogParserStateInit(struct LogParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->traceLength = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Log;
+    access aParserState->;
+    alphtype unsigned char;
+    action messageParsed {
+        if (aCallbacks != NULL && aCallbacks->onLogParsed != NULL) {
+            aCallbacks->onLogParsed(aLog, aCallbacks->context);
+        }
+    }
+    action text {
+    }
+    action text_reset {
+        aLog->textLength = 0;
+    }
+    action text_byte {
+        if (aLog->textLength < 16u) {
+            aLog->text[aLog->textLength++] = (uint8_t)fc;
+        }
+    }
+    text = ( 'a'..'z' )+ >text_reset $text_byte @text; 
+    main := ( text ) @messageParsed;
+}%%
+static void robustoTraceLog(int aPreviousCs, const struct LogParserState *aParserState)
+{
+    if (aPreviousCs < Log_first_final && aParserState->cs >= Log_first_final) {
+        robustoTraceFrame(1u, 1u, aParserState->error, aParserState->traceLength);
+    } else if (aPreviousCs != Log_error && aParserState->cs == Log_error) {
+        robustoTraceFrame(1u, 0u, aParserState->error, aParserState->traceLength);
+    }
+}
+
+static enum RobustoParseStatus robustoParseStatusLog(const struct LogParserState *aParserState)
+{
+    enum RobustoParseStatus status;
+
+    if (aParserState->cs >= Log_first_final) {
+        status = ROBUSTO_PARSE_STATUS_OK;
+    } else if (aParserState->cs != Log_error) {
+        status = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    } else {
+        switch (aParserState->error) {
+            case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+                status = ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+                break;
+            case ROBUSTO_PARSE_ERROR_CHECKSUM:
+                status = ROBUSTO_PARSE_STATUS_ERROR_CRC;
+                break;
+            case ROBUSTO_PARSE_ERROR_OVERFLOW:
+                status = ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+                break;
+            default:
+                status = ROBUSTO_PARSE_STATUS_ERROR;
+                break;
+        }
+    }
+
+    return status;
+}
+
+enum RobustoParseStatus parseLogWithCallbacks(struct LogParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct LogMessage *aLog, int *aConsumedLength, const struct LogCallbacks *aCallbacks)
+{
+    int consumedLength = 0;
+    const int previousCs = (aParserState->machineInitRequired != 0) ? Log_start : aParserState->cs;
+
+    if (aParserState->machineInitRequired != 0) {
+        machineLogParserStateInit(aParserState);
+    }
+
+    aParserState->traceLength += (uint32_t)aInputBufferLength;
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    consumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+
+    if (aConsumedLength != NULL) {
+        *aConsumedLength = consumedLength;
+    }
+
+    robustoTraceLog(previousCs, aParserState);
+    return robustoParseStatusLog(aParserState);
+}
+enum RobustoParseStatus parseLog(struct LogParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct LogMessage *aLog, int *aConsumedLength)
+{
+    return parseLogWithCallbacks(aParserState, aInputBuffer, aInputBufferLength, aLog, aConsumedLength, NULL);
+}
+int isLogAccepted(const struct LogParserState *aParserState)
+{
+    return (aParserState->cs >= Log_first_final) ? 1 : 0;
+}
+int serializeLog(const struct LogMessage *aLog, uint8_t *aBuffer, size_t aCapacity)
+{
+    size_t length = 0;
+    int result = -1;
+
+    if (aCapacity >= 16u) {
+        {
+            size_t j;
+
+            for (j = 0; j < aLog->textLength && j < 16u; ++j) {
+                aBuffer[length++] = aLog->text[j];
+            }
+        }
+
+        result = (int)length;
+    }
+
+    return result;
+}
+// endregion Log parser
+
+// =============================================================================
+// region Diagnostic strings
+// =============================================================================
+
+static const char kRobustoParseErrorString0[] = "no error";
+static const char kRobustoParseErrorString1[] = "field value out of range";
+static const char kRobustoParseErrorString2[] = "frame longer than the message's fixed length";
+static const char kRobustoParseErrorString3[] = "checksum mismatch";
+static const char kRobustoParseErrorString4[] = "length field mismatch";
+static const char kRobustoParseErrorString5[] = "field check failed";
+static const char kRobustoParseErrorString6[] = "field longer than its max length";
+static const char kRobustoParseErrorStringUnknown[] = "unknown error";
+
+static const char *const kRobustoParseErrorStrings[] = {
+    kRobustoParseErrorString0,  // ROBUSTO_PARSE_ERROR_NONE
+    kRobustoParseErrorString1,  // ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE
+    kRobustoParseErrorString2,  // ROBUSTO_PARSE_ERROR_FRAME_LENGTH
+    kRobustoParseErrorString3,  // ROBUSTO_PARSE_ERROR_CHECKSUM
+    kRobustoParseErrorString4,  // ROBUSTO_PARSE_ERROR_LENGTH
+    kRobustoParseErrorString5,  // ROBUSTO_PARSE_ERROR_CHECK
+    kRobustoParseErrorString6,  // ROBUSTO_PARSE_ERROR_OVERFLOW
+};
+
+const char *robustoParseErrorString(uint8_t aError)
+{
+    const char *ret = kRobustoParseErrorStringUnknown;
+
+    if (aError < sizeof(kRobustoParseErrorStrings) / sizeof(kRobustoParseErrorStrings[0])) {
+        ret = kRobustoParseErrorStrings[aError];
+    }
+
+    return ret;
+}
+// endregion Diagnostic strings
@@ -0,0 +1,414 @@
+#include "output.h"
+
+// =============================================================================
+// region SensorReport parser
+// =============================================================================
+
+%%{
+    machine SensorReport;
+    write data;
+}%%
+// Physical value of `voltage`, in V
+float acme_get_sensor_report_voltage_V(const struct acme_sensor_report_message_t *aSensorReport)
+{
+    return (float)aSensorReport->voltage * 0.001f + 0.0f;
+}
+void acme_machine_sensor_report_parser_state_init(struct acme_sensor_report_parser_state_t *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine SensorReport;
+    access aParserState->;
+    alphtype unsigned char;
+    action id {
+    }
+    action id_byte {
+        aSensorReport->id = (uint8_t)(((uint8_t)aSensorReport->id << 8) | (uint8_t)fc);
+    }
+    action voltage {
+    }
+    action voltage_byte {
+        aSensorReport->voltage = (uint16_t)(((uint16_t)aSensorReport->voltage << 8) | (uint8_t)fc);
+    }
+    id = any{1} $id_byte @id; 
+    voltage = any{2} $voltage_byte @voltage; 
+    main := id voltage;
+}%%
+static enum RobustoParseStatus robustoParseStatusSensorReport(const struct acme_sensor_report_parser_state_t *aParserState)
+{
+    if (aParserState->cs >= SensorReport_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != SensorReport_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus acme_parse_sensor_report(struct acme_sensor_report_parser_state_t *aParserState, const char *aInputBuffer, int aInputBufferLength, struct acme_sensor_report_message_t *aSensorReport, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        acme_machine_sensor_report_parser_state_init(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusSensorReport(aParserState);
+}
+int acme_is_sensor_report_accepted(const struct acme_sensor_report_parser_state_t *aParserState)
+{
+    return aParserState->cs >= SensorReport_first_final;
+}
+// endregion SensorReport parser
+
+// =============================================================================
+// region Heartbeat parser
+// =============================================================================
+
+%%{
+    machine Heartbeat;
+    write data;
+}%%
+void acme_machine_heartbeat_parser_state_init(struct acme_heartbeat_parser_state_t *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Heartbeat;
+    access aParserState->;
+    alphtype unsigned char;
+    action id {
+    }
+    action id_byte {
+        aHeartbeat->id = (uint8_t)(((uint8_t)aHeartbeat->id << 8) | (uint8_t)fc);
+    }
+    action uptime {
+    }
+    action uptime_byte {
+        aHeartbeat->uptime = (uint32_t)(((uint32_t)aHeartbeat->uptime << 8) | (uint8_t)fc);
+    }
+    id = any{1} $id_byte @id; 
+    uptime = any{4} $uptime_byte @uptime; 
+    main := id uptime;
+}%%
+static enum RobustoParseStatus robustoParseStatusHeartbeat(const struct acme_heartbeat_parser_state_t *aParserState)
+{
+    if (aParserState->cs >= Heartbeat_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Heartbeat_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus acme_parse_heartbeat(struct acme_heartbeat_parser_state_t *aParserState, const char *aInputBuffer, int aInputBufferLength, struct acme_heartbeat_message_t *aHeartbeat, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        acme_machine_heartbeat_parser_state_init(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusHeartbeat(aParserState);
+}
+int acme_is_heartbeat_accepted(const struct acme_heartbeat_parser_state_t *aParserState)
+{
+    return aParserState->cs >= Heartbeat_first_final;
+}
+// endregion Heartbeat parser
+
+// =============================================================================
+// region Dispatcher
+// =============================================================================
+
+void acme_robusto_dispatcher_init(struct acme_robusto_dispatcher_state_t *aState)
+{
+    aState->id = 0u;
+    aState->idLength = 0u;
+    aState->selected = ROBUSTO_DISPATCH_PENDING;
+    aState->accepted = 0u;
+}
+
+void acme_parse_any(struct acme_robusto_dispatcher_state_t *aState, const char *aInputBuffer, int aInputBufferLength, const struct acme_robusto_dispatch_callbacks_t *aCallbacks)
+{
+    while (aState->selected == ROBUSTO_DISPATCH_PENDING && aInputBufferLength > 0) {
+        aState->idBuffer[aState->idLength] = *aInputBuffer;
+        aState->id = (uint8_t)((aState->id << 8) | (uint8_t)*aInputBuffer);
+        ++aState->idLength;
+        ++aInputBuffer;
+        --aInputBufferLength;
+
+        if (aState->idLength < ROBUSTO_DISPATCH_ID_LENGTH) {
+            continue;
+        }
+
+        switch (aState->id) {
+            case ROBUSTO_SENSOR_REPORT_ID:
+                aState->selected = 1u;
+                acme_machine_sensor_report_parser_state_init(&aState->parserState.SensorReport);
+                acme_parse_sensor_report(&aState->parserState.SensorReport, aState->idBuffer, ROBUSTO_DISPATCH_ID_LENGTH, &aState->message.SensorReport, 0);
+                break;
+            case ROBUSTO_HEARTBEAT_ID:
+                aState->selected = 2u;
+                acme_machine_heartbeat_parser_state_init(&aState->parserState.Heartbeat);
+                acme_parse_heartbeat(&aState->parserState.Heartbeat, aState->idBuffer, ROBUSTO_DISPATCH_ID_LENGTH, &aState->message.Heartbeat, 0);
+                break;
+            default:
+                aState->selected = ROBUSTO_DISPATCH_UNKNOWN;
+                break;
+        }
+    }
+
+    switch (aState->selected) {
+        case 1u:
+            if (aInputBufferLength > 0) {
+                acme_parse_sensor_report(&aState->parserState.SensorReport, aInputBuffer, aInputBufferLength, &aState->message.SensorReport, 0);
+            }
+
+            if (!aState->accepted && acme_is_sensor_report_accepted(&aState->parserState.SensorReport)) {
+                aState->accepted = 1u;
+
+                if (aCallbacks->onSensorReport != 0) {
+                    aCallbacks->onSensorReport(&aState->message.SensorReport, aCallbacks->context);
+                }
+            }
+            break;
+        case 2u:
+            if (aInputBufferLength > 0) {
+                acme_parse_heartbeat(&aState->parserState.Heartbeat, aInputBuffer, aInputBufferLength, &aState->message.Heartbeat, 0);
+            }
+
+            if (!aState->accepted && acme_is_heartbeat_accepted(&aState->parserState.Heartbeat)) {
+                aState->accepted = 1u;
+
+                if (aCallbacks->onHeartbeat != 0) {
+                    aCallbacks->onHeartbeat(&aState->message.Heartbeat, aCallbacks->context);
+                }
+            }
+            break;
+        default:
+            break;
+    }
+}
+// endregion Dispatcher
+
+// =============================================================================
+// region Self-test
+// =============================================================================
+
+int selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0xEF, 0xAB, 0xCD};
+        struct acme_sensor_report_parser_state_t parserState;
+        struct acme_sensor_report_message_t message = {0};
+        struct acme_sensor_report_message_t splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        acme_machine_sensor_report_parser_state_init(&parserState);
+
+        if (acme_parse_sensor_report(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.id != 239u) {
+            return 1;
+        }
+
+        if (message.voltage != 43981u) {
+            return 1;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = acme_parse_sensor_report(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.id != 239u) {
+            return 1;
+        }
+
+        if (splitMessage.voltage != 43981u) {
+            return 1;
+        }
+    }
+    {
+        // Byte order: multi-byte fields carry 0x01, 0x02, ... on the wire, and decode the same on any host
+        static const uint8_t kFrame[] = {0xEF, 0x01, 0x02};
+        struct acme_sensor_report_parser_state_t parserState;
+        struct acme_sensor_report_message_t message = {0};
+        struct acme_sensor_report_message_t splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        acme_machine_sensor_report_parser_state_init(&parserState);
+
+        if (acme_parse_sensor_report(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (message.id != 239u) {
+            return 2;
+        }
+
+        if (message.voltage != 0x102u) {
+            return 2;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = acme_parse_sensor_report(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (splitMessage.id != 239u) {
+            return 2;
+        }
+
+        if (splitMessage.voltage != 0x102u) {
+            return 2;
+        }
+    }
+    {
+        static const uint8_t kFrame[] = {0xEF, 0x67, 0x89, 0xAB, 0xCD};
+        struct acme_heartbeat_parser_state_t parserState;
+        struct acme_heartbeat_message_t message = {0};
+        struct acme_heartbeat_message_t splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        acme_machine_heartbeat_parser_state_init(&parserState);
+
+        if (acme_parse_heartbeat(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 3;
+        }
+
+        if (message.id != 239u) {
+            return 3;
+        }
+
+        if (message.uptime != 1737075661u) {
+            return 3;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = acme_parse_heartbeat(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 3;
+        }
+
+        if (splitMessage.id != 239u) {
+            return 3;
+        }
+
+        if (splitMessage.uptime != 1737075661u) {
+            return 3;
+        }
+    }
+    {
+        // Byte order: multi-byte fields carry 0x01, 0x02, ... on the wire, and decode the same on any host
+        static const uint8_t kFrame[] = {0xEF, 0x01, 0x02, 0x03, 0x04};
+        struct acme_heartbeat_parser_state_t parserState;
+        struct acme_heartbeat_message_t message = {0};
+        struct acme_heartbeat_message_t splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        acme_machine_heartbeat_parser_state_init(&parserState);
+
+        if (acme_parse_heartbeat(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 4;
+        }
+
+        if (message.id != 239u) {
+            return 4;
+        }
+
+        if (message.uptime != 0x1020304u) {
+            return 4;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = acme_parse_heartbeat(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 4;
+        }
+
+        if (splitMessage.id != 239u) {
+            return 4;
+        }
+
+        if (splitMessage.uptime != 0x1020304u) {
+            return 4;
+        }
+    }
+
+    return 0;
+}
+// endregion Self-test
@@ -0,0 +1,150 @@
+#pragma once
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region SensorReport types
+// =============================================================================
+
+struct acme_sensor_report_message_t {
+    uint8_t id;
+    /** Unit: V, once scaled */
+    uint16_t voltage;
+};
+struct acme_sensor_report_parser_state_t {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+// endregion SensorReport types
+
+// =============================================================================
+// region Heartbeat types
+// =============================================================================
+
+struct acme_heartbeat_message_t {
+    uint8_t id;
+    uint32_t uptime;
+};
+struct acme_heartbeat_parser_state_t {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+// endregion Heartbeat types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union acme_robusto_any_message_t {
+    struct acme_sensor_report_message_t SensorReport;
+    struct acme_heartbeat_message_t Heartbeat;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union acme_robusto_any_message_t))
+#define ROBUSTO_SENSOR_REPORT_MIN_FRAME_SIZE 3u
+#define ROBUSTO_SENSOR_REPORT_MAX_FRAME_SIZE 3u
+#define ROBUSTO_HEARTBEAT_MIN_FRAME_SIZE 5u
+#define ROBUSTO_HEARTBEAT_MAX_FRAME_SIZE 5u
+#define ROBUSTO_MIN_FRAME_SIZE 3u
+#define ROBUSTO_MAX_FRAME_SIZE 5u
+// endregion Sizing constants
+
+// =============================================================================
+// region Dispatcher
+// =============================================================================
+
+#define ROBUSTO_DISPATCH_ID_LENGTH 1u
+#define ROBUSTO_DISPATCH_PENDING 0u
+#define ROBUSTO_DISPATCH_UNKNOWN 255u
+#define ROBUSTO_SENSOR_REPORT_ID 1u
+#define ROBUSTO_HEARTBEAT_ID 2u
+
+enum RobustoMessageId {
+    ROBUSTO_MESSAGE_ID_SENSOR_REPORT = ROBUSTO_SENSOR_REPORT_ID,
+    ROBUSTO_MESSAGE_ID_HEARTBEAT = ROBUSTO_HEARTBEAT_ID,
+};
+
+// Each callback is invoked once its message's frame is accepted, and may be NULL
+struct acme_robusto_dispatch_callbacks_t {
+    void *context;
+    void (*onSensorReport)(const struct acme_sensor_report_message_t *aSensorReport, void *aContext);
+    void (*onHeartbeat)(const struct acme_heartbeat_message_t *aHeartbeat, void *aContext);
+};
+
+union acme_robusto_dispatch_parser_state_t {
+    struct acme_sensor_report_parser_state_t SensorReport;
+    struct acme_heartbeat_parser_state_t Heartbeat;
+};
+
+// `selected` is the 1-based index of the message being parsed, `ROBUSTO_DISPATCH_PENDING` while
+// the ID is incomplete, or `ROBUSTO_DISPATCH_UNKNOWN` if no message has the ID
+struct acme_robusto_dispatcher_state_t {
+    uint8_t id;
+    char idBuffer[ROBUSTO_DISPATCH_ID_LENGTH];
+    uint8_t idLength;
+    uint8_t selected;
+    uint8_t accepted;
+    union acme_robusto_dispatch_parser_state_t parserState;
+    union acme_robusto_any_message_t message;
+};
+// endregion Dispatcher
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void acme_machine_sensor_report_parser_state_init(struct acme_sensor_report_parser_state_t *aParserState);
+// Non-null: aParserState, aInputBuffer, aSensorReport
+enum RobustoParseStatus acme_parse_sensor_report(struct acme_sensor_report_parser_state_t *aParserState, const char *aInputBuffer, int aInputBufferLength, struct acme_sensor_report_message_t *aSensorReport, int *aConsumedLength);
+// Non-null: aParserState
+int acme_is_sensor_report_accepted(const struct acme_sensor_report_parser_state_t *aParserState);
+// Non-null: aSensorReport
+float acme_get_sensor_report_voltage_V(const struct acme_sensor_report_message_t *aSensorReport);
+// Non-null: aParserState
+void acme_machine_heartbeat_parser_state_init(struct acme_heartbeat_parser_state_t *aParserState);
+// Non-null: aParserState, aInputBuffer, aHeartbeat
+enum RobustoParseStatus acme_parse_heartbeat(struct acme_heartbeat_parser_state_t *aParserState, const char *aInputBuffer, int aInputBufferLength, struct acme_heartbeat_message_t *aHeartbeat, int *aConsumedLength);
+// Non-null: aParserState
+int acme_is_heartbeat_accepted(const struct acme_heartbeat_parser_state_t *aParserState);
+int selfTest(void);
+// Non-null: aState
+void acme_robusto_dispatcher_init(struct acme_robusto_dispatcher_state_t *aState);
+// Non-null: aState, aInputBuffer, aCallbacks
+void acme_parse_any(struct acme_robusto_dispatcher_state_t *aState, const char *aInputBuffer, int aInputBufferLength, const struct acme_robusto_dispatch_callbacks_t *aCallbacks);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
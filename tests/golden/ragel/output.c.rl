@@ -0,0 +1,90 @@
+#include "output.h"
+
+// =============================================================================
+// region Reading parser
+// =============================================================================
+
+%%{
+    machine Reading;
+    write data noentry noerror;
+}%%
+#define Reading_error 0  // Omitted by "write data noerror"
+void machineReadingParserStateInit(struct ReadingParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init nocs;
+    aParserState->cs = Reading_start;
+}
+%%{
+    machine Reading;
+    access aParserState->;
+    alphtype unsigned char;
+    action channel {
+    }
+    action channel_byte {
+        aReading->channel = (uint8_t)(((uint8_t)aReading->channel << 8) | (uint8_t)fc);
+    }
+    action value {
+    }
+    action value_byte {
+        aReading->value = (int16_t)(((uint16_t)aReading->value << 8) | (uint8_t)fc);
+    }
+    channel = any{1} $channel_byte @channel; 
+    value = any{2} $value_byte @value; 
+    main := ( any* :>> ( 0x24 ) ) channel value;
+}%%
+static enum RobustoParseStatus robustoParseStatusReading(const struct ReadingParserState *aParserState)
+{
+    if (aParserState->cs >= Reading_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Reading_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseReading(struct ReadingParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct ReadingMessage *aReading, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineReadingParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    for (;;) {
+        %% write exec;
+
+        if (aParserState->cs != Reading_error || p == pe) {
+            break;
+        }
+
+        // Resynchronize on the rest of the input
+        machineReadingParserStateInit(aParserState);
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusReading(aParserState);
+}
+int isReadingAccepted(const struct ReadingParserState *aParserState)
+{
+    return aParserState->cs >= Reading_first_final;
+}
+// endregion Reading parser
@@ -0,0 +1,280 @@
+#include "output.h"
+
+// =============================================================================
+// region Bit reader
+// =============================================================================
+
+static uint64_t robustoReadBits(const uint8_t *aBuffer, unsigned long aBitOffset, unsigned int aBitWidth)
+{
+    uint64_t value = 0;
+    unsigned int i;
+
+    for (i = 0; i < aBitWidth; ++i) {
+        unsigned long bit = aBitOffset + i;
+        value = (value << 1) | ((aBuffer[bit / 8] >> (7 - (bit % 8))) & 1u);
+    }
+
+    return value;
+}
+static int64_t robustoSignExtend(uint64_t aValue, unsigned int aBitWidth)
+{
+    uint64_t signBit = (uint64_t)1 << (aBitWidth - 1);
+
+    return (int64_t)((aValue ^ signBit) - signBit);
+}
+// endregion Bit reader
+
+// =============================================================================
+// region Status parser
+// =============================================================================
+
+%%{
+    machine Status;
+    write data;
+}%%
+int testStatusState(const struct StatusMessage *aStatus, uint8_t aFlags)
+{
+    return (aStatus->state & aFlags) == aFlags;
+}
+void setStatusState(struct StatusMessage *aStatus, uint8_t aFlags, int aValue)
+{
+    if (aValue) {
+        aStatus->state = (uint8_t)(aStatus->state | aFlags);
+    } else {
+        aStatus->state = (uint8_t)(aStatus->state & ~aFlags);
+    }
+}
+int testStatusWide(const struct StatusMessage *aStatus, uint64_t aFlags)
+{
+    return (aStatus->wide & aFlags) == aFlags;
+}
+void setStatusWide(struct StatusMessage *aStatus, uint64_t aFlags, int aValue)
+{
+    if (aValue) {
+        aStatus->wide = (uint64_t)(aStatus->wide | aFlags);
+    } else {
+        aStatus->wide = (uint64_t)(aStatus->wide & ~aFlags);
+    }
+}
+void machineStatusParserStateInit(struct StatusParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Status;
+    access aParserState->;
+    alphtype unsigned char;
+    action sync {
+    }
+    action sync_reset {
+        aStatus->syncLength = 0;
+    }
+    action sync_byte {
+        if (aStatus->syncLength < 1u) {
+            aStatus->sync[aStatus->syncLength++] = (uint8_t)fc;
+        }
+    }
+    action state {
+    }
+    action state_byte {
+        aStatus->state = (uint8_t)(((uint8_t)aStatus->state << 8) | (uint8_t)fc);
+    }
+    action wide {
+    }
+    action wide_byte {
+        aStatus->wide = (uint64_t)(((uint64_t)aStatus->wide << 8) | (uint8_t)fc);
+    }
+    sync = 0xAA >sync_reset $sync_byte @sync; 
+    state = any{1} $state_byte @state; 
+    wide = any{8} $wide_byte @wide; 
+    main := sync state wide;
+}%%
+static enum RobustoParseStatus robustoParseStatusStatus(const struct StatusParserState *aParserState)
+{
+    if (aParserState->cs >= Status_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Status_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseStatus(struct StatusParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct StatusMessage *aStatus, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineStatusParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusStatus(aParserState);
+}
+int isStatusAccepted(const struct StatusParserState *aParserState)
+{
+    return aParserState->cs >= Status_first_final;
+}
+// endregion Status parser
+
+// =============================================================================
+// region Bits parser
+// =============================================================================
+
+int testBitsMode(const struct BitsMessage *aBits, uint8_t aFlags)
+{
+    return (aBits->mode & aFlags) == aFlags;
+}
+void setBitsMode(struct BitsMessage *aBits, uint8_t aFlags, int aValue)
+{
+    if (aValue) {
+        aBits->mode = (uint8_t)(aBits->mode | aFlags);
+    } else {
+        aBits->mode = (uint8_t)(aBits->mode & ~aFlags);
+    }
+}
+int parseBits(const uint8_t *aInputBuffer, int aInputBufferLength, struct BitsMessage *aBits)
+{
+    unsigned long bitOffset = 0;  // Position of the bit reader
+
+    if ((unsigned long)aInputBufferLength * 8UL < 8UL) {
+        return -1;
+    }
+
+    aBits->mode = (uint8_t)robustoReadBits(aInputBuffer, bitOffset, 3u);
+    bitOffset += 3u;
+    aBits->rest = (uint8_t)robustoReadBits(aInputBuffer, bitOffset, 5u);
+    bitOffset += 5u;
+    return (int)((bitOffset + 7UL) / 8UL);
+}
+// endregion Bits parser
+
+// =============================================================================
+// region Self-test
+// =============================================================================
+
+int selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0xAA, 0xCD, 0xCD, 0xEF, 0x01, 0x23, 0x45, 0x67, 0x89, 0xAB};
+        struct StatusParserState parserState;
+        struct StatusMessage message = {0};
+        struct StatusMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineStatusParserStateInit(&parserState);
+
+        if (parseStatus(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.state != 205u) {
+            return 1;
+        }
+
+        if (message.wide != UINT64_C(14839080548208970155)) {
+            return 1;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseStatus(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.state != 205u) {
+            return 1;
+        }
+
+        if (splitMessage.wide != UINT64_C(14839080548208970155)) {
+            return 1;
+        }
+    }
+    {
+        // Byte order: multi-byte fields carry 0x01, 0x02, ... on the wire, and decode the same on any host
+        static const uint8_t kFrame[] = {0xAA, 0xCD, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08};
+        struct StatusParserState parserState;
+        struct StatusMessage message = {0};
+        struct StatusMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineStatusParserStateInit(&parserState);
+
+        if (parseStatus(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (message.state != 205u) {
+            return 2;
+        }
+
+        if (message.wide != UINT64_C(0x102030405060708)) {
+            return 2;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseStatus(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (splitMessage.state != 205u) {
+            return 2;
+        }
+
+        if (splitMessage.wide != UINT64_C(0x102030405060708)) {
+            return 2;
+        }
+    }
+    {
+        static const uint8_t kFrame[] = {0xED};
+        struct BitsMessage message = {0};
+
+        if (parseBits(kFrame, (int)sizeof(kFrame), &message) != (int)sizeof(kFrame)) {
+            return 3;
+        }
+
+        if (message.mode != 7u) {
+            return 3;
+        }
+
+        if (message.rest != 13u) {
+            return 3;
+        }
+    }
+
+    return 0;
+}
+// endregion Self-test
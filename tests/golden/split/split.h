@@ -0,0 +1,33 @@
+#ifndef SPLIT_H
+#define SPLIT_H
+
+#include "split_shared.h"
+#include "split_status.h"
+#include "split_bits.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct StatusMessage Status;
+    struct BitsMessage Bits;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_STATUS_MIN_FRAME_SIZE 10u
+#define ROBUSTO_STATUS_MAX_FRAME_SIZE 10u
+#define ROBUSTO_BITS_MIN_FRAME_SIZE 1u
+#define ROBUSTO_BITS_MAX_FRAME_SIZE 1u
+#define ROBUSTO_MIN_FRAME_SIZE 1u
+#define ROBUSTO_MAX_FRAME_SIZE 10u
+// endregion Sizing constants
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // SPLIT_H
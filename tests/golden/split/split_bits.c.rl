@@ -0,0 +1,57 @@
+#include "split_bits.h"
+
+// =============================================================================
+// region Bit reader
+// =============================================================================
+
+static uint64_t robustoReadBits(const uint8_t *aBuffer, unsigned long aBitOffset, unsigned int aBitWidth)
+{
+    uint64_t value = 0;
+    unsigned int i;
+
+    for (i = 0; i < aBitWidth; ++i) {
+        unsigned long bit = aBitOffset + i;
+        value = (value << 1) | ((aBuffer[bit / 8] >> (7 - (bit % 8))) & 1u);
+    }
+
+    return value;
+}
+static int64_t robustoSignExtend(uint64_t aValue, unsigned int aBitWidth)
+{
+    uint64_t signBit = (uint64_t)1 << (aBitWidth - 1);
+
+    return (int64_t)((aValue ^ signBit) - signBit);
+}
+// endregion Bit reader
+
+// =============================================================================
+// region Bits parser
+// =============================================================================
+
+int testBitsMode(const struct BitsMessage *aBits, uint8_t aFlags)
+{
+    return (aBits->mode & aFlags) == aFlags;
+}
+void setBitsMode(struct BitsMessage *aBits, uint8_t aFlags, int aValue)
+{
+    if (aValue) {
+        aBits->mode = (uint8_t)(aBits->mode | aFlags);
+    } else {
+        aBits->mode = (uint8_t)(aBits->mode & ~aFlags);
+    }
+}
+int parseBits(const uint8_t *aInputBuffer, int aInputBufferLength, struct BitsMessage *aBits)
+{
+    unsigned long bitOffset = 0;  // Position of the bit reader
+
+    if ((unsigned long)aInputBufferLength * 8UL < 8UL) {
+        return -1;
+    }
+
+    aBits->mode = (uint8_t)robustoReadBits(aInputBuffer, bitOffset, 3u);
+    bitOffset += 3u;
+    aBits->rest = (uint8_t)robustoReadBits(aInputBuffer, bitOffset, 5u);
+    bitOffset += 5u;
+    return (int)((bitOffset + 7UL) / 8UL);
+}
+// endregion Bits parser
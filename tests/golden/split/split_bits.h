@@ -0,0 +1,37 @@
+#ifndef SPLIT_BITS_H
+#define SPLIT_BITS_H
+
+#include "split_shared.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Bits types
+// =============================================================================
+
+#define BITS_MODE_FLAG_X (1u << 2)
+struct BitsMessage {
+    uint8_t mode;
+    uint8_t rest;
+};
+// endregion Bits types
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aInputBuffer, aBits
+int parseBits(const uint8_t *aInputBuffer, int aInputBufferLength, struct BitsMessage *aBits);
+// Non-null: aBits
+int testBitsMode(const struct BitsMessage *aBits, uint8_t aFlags);
+// Non-null: aBits
+void setBitsMode(struct BitsMessage *aBits, uint8_t aFlags, int aValue);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // SPLIT_BITS_H
@@ -0,0 +1,111 @@
+#include "split.h"
+
+// =============================================================================
+// region Self-test
+// =============================================================================
+
+int selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0xAA, 0xCD, 0xCD, 0xEF, 0x01, 0x23, 0x45, 0x67, 0x89, 0xAB};
+        struct StatusParserState parserState;
+        struct StatusMessage message = {0};
+        struct StatusMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineStatusParserStateInit(&parserState);
+
+        if (parseStatus(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.state != 205u) {
+            return 1;
+        }
+
+        if (message.wide != UINT64_C(14839080548208970155)) {
+            return 1;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseStatus(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.state != 205u) {
+            return 1;
+        }
+
+        if (splitMessage.wide != UINT64_C(14839080548208970155)) {
+            return 1;
+        }
+    }
+    {
+        // Byte order: multi-byte fields carry 0x01, 0x02, ... on the wire, and decode the same on any host
+        static const uint8_t kFrame[] = {0xAA, 0xCD, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08};
+        struct StatusParserState parserState;
+        struct StatusMessage message = {0};
+        struct StatusMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineStatusParserStateInit(&parserState);
+
+        if (parseStatus(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (message.state != 205u) {
+            return 2;
+        }
+
+        if (message.wide != UINT64_C(0x102030405060708)) {
+            return 2;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseStatus(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (splitMessage.state != 205u) {
+            return 2;
+        }
+
+        if (splitMessage.wide != UINT64_C(0x102030405060708)) {
+            return 2;
+        }
+    }
+    {
+        static const uint8_t kFrame[] = {0xED};
+        struct BitsMessage message = {0};
+
+        if (parseBits(kFrame, (int)sizeof(kFrame), &message) != (int)sizeof(kFrame)) {
+            return 3;
+        }
+
+        if (message.mode != 7u) {
+            return 3;
+        }
+
+        if (message.rest != 13u) {
+            return 3;
+        }
+    }
+
+    return 0;
+}
+// endregion Self-test
@@ -0,0 +1,114 @@
+#include "split_status.h"
+
+// =============================================================================
+// region Status parser
+// =============================================================================
+
+%%{
+    machine Status;
+    write data;
+}%%
+int testStatusState(const struct StatusMessage *aStatus, uint8_t aFlags)
+{
+    return (aStatus->state & aFlags) == aFlags;
+}
+void setStatusState(struct StatusMessage *aStatus, uint8_t aFlags, int aValue)
+{
+    if (aValue) {
+        aStatus->state = (uint8_t)(aStatus->state | aFlags);
+    } else {
+        aStatus->state = (uint8_t)(aStatus->state & ~aFlags);
+    }
+}
+int testStatusWide(const struct StatusMessage *aStatus, uint64_t aFlags)
+{
+    return (aStatus->wide & aFlags) == aFlags;
+}
+void setStatusWide(struct StatusMessage *aStatus, uint64_t aFlags, int aValue)
+{
+    if (aValue) {
+        aStatus->wide = (uint64_t)(aStatus->wide | aFlags);
+    } else {
+        aStatus->wide = (uint64_t)(aStatus->wide & ~aFlags);
+    }
+}
+void machineStatusParserStateInit(struct StatusParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Status;
+    access aParserState->;
+    alphtype unsigned char;
+    action sync {
+    }
+    action sync_reset {
+        aStatus->syncLength = 0;
+    }
+    action sync_byte {
+        if (aStatus->syncLength < 1u) {
+            aStatus->sync[aStatus->syncLength++] = (uint8_t)fc;
+        }
+    }
+    action state {
+    }
+    action state_byte {
+        aStatus->state = (uint8_t)(((uint8_t)aStatus->state << 8) | (uint8_t)fc);
+    }
+    action wide {
+    }
+    action wide_byte {
+        aStatus->wide = (uint64_t)(((uint64_t)aStatus->wide << 8) | (uint8_t)fc);
+    }
+    sync = 0xAA >sync_reset $sync_byte @sync; 
+    state = any{1} $state_byte @state; 
+    wide = any{8} $wide_byte @wide; 
+    main := sync state wide;
+}%%
+static enum RobustoParseStatus robustoParseStatusStatus(const struct StatusParserState *aParserState)
+{
+    if (aParserState->cs >= Status_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Status_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseStatus(struct StatusParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct StatusMessage *aStatus, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineStatusParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusStatus(aParserState);
+}
+int isStatusAccepted(const struct StatusParserState *aParserState)
+{
+    return aParserState->cs >= Status_first_final;
+}
+// endregion Status parser
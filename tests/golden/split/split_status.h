@@ -0,0 +1,58 @@
+#ifndef SPLIT_STATUS_H
+#define SPLIT_STATUS_H
+
+#include "split_shared.h"
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Status types
+// =============================================================================
+
+#define STATUS_STATE_FLAG_READY (1u << 0)
+#define STATUS_STATE_FLAG_LOW_BATTERY (1u << 1)
+#define STATUS_STATE_FLAG_FAULT (1u << 7)
+#define STATUS_WIDE_FLAG_A (UINT32_C(1) << 20)
+#define STATUS_WIDE_FLAG_B (UINT64_C(1) << 40)
+struct StatusMessage {
+    uint8_t sync[1];
+    uint32_t syncLength;
+    /** Device state */
+    uint8_t state;
+    uint64_t wide;
+};
+struct StatusParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+// endregion Status types
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineStatusParserStateInit(struct StatusParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aStatus
+enum RobustoParseStatus parseStatus(struct StatusParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct StatusMessage *aStatus, int *aConsumedLength);
+// Non-null: aParserState
+int isStatusAccepted(const struct StatusParserState *aParserState);
+// Non-null: aStatus
+int testStatusState(const struct StatusMessage *aStatus, uint8_t aFlags);
+// Non-null: aStatus
+void setStatusState(struct StatusMessage *aStatus, uint8_t aFlags, int aValue);
+// Non-null: aStatus
+int testStatusWide(const struct StatusMessage *aStatus, uint64_t aFlags);
+// Non-null: aStatus
+void setStatusWide(struct StatusMessage *aStatus, uint64_t aFlags, int aValue);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // SPLIT_STATUS_H
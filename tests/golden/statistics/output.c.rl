@@ -0,0 +1,213 @@
+#include "output.h"
+
+/* ============================================================================= */
+/* region Checksums */
+/* ============================================================================= */
+
+uint8_t robustoCrc8_07(uint8_t aCrc, uint8_t aByte)
+{
+    unsigned int i;
+
+    aCrc ^= aByte;
+
+    for (i = 0; i < 8u; ++i) {
+        aCrc = (aCrc & 0x80u) ? (uint8_t)((aCrc << 1) ^ 0x07u) : (uint8_t)(aCrc << 1);
+    }
+
+    return aCrc;
+}
+/* endregion Checksums */
+
+/* ============================================================================= */
+/* region Telemetry parser */
+/* ============================================================================= */
+
+%%{
+    machine Telemetry;
+    write data;
+}%%
+void machineTelemetryParserStateInit(struct TelemetryParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->statistics.framesOk = 0;
+    aParserState->statistics.crcErrors = 0;
+    aParserState->statistics.resyncs = 0;
+    aParserState->statistics.overflows = 0;
+    aParserState->frameChecksum = 0x00u;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Telemetry;
+    access aParserState->;
+    alphtype unsigned char;
+    action node {
+    }
+    action node_byte {
+        aTelemetry->node = (uint8_t)(((uint8_t)aTelemetry->node << 8) | (uint8_t)fc);
+    }
+    action text {
+    }
+    action text_reset {
+        aTelemetry->textLength = 0;
+    }
+    action text_byte {
+        if (aTelemetry->textLength < 8u) {
+            aTelemetry->text[aTelemetry->textLength++] = (uint8_t)fc;
+        } else {
+            aParserState->error = ROBUSTO_PARSE_ERROR_OVERFLOW;
+            fgoto *Telemetry_error;
+        }
+    }
+    action crc {
+        if (aTelemetry->crc != aParserState->frameChecksum) {
+            aParserState->error = ROBUSTO_PARSE_ERROR_CHECKSUM;
+            fgoto *Telemetry_error;
+        }
+    }
+    action frame_checksum {
+        aParserState->frameChecksum = robustoCrc8_07(aParserState->frameChecksum, (uint8_t)fc);
+    }
+    action crc_byte {
+        aTelemetry->crc = (uint8_t)(((uint8_t)aTelemetry->crc << 8) | (uint8_t)fc);
+    }
+    node = any{1} $node_byte @node; 
+    text = ( ( 'a'..'z' )+ ';' ) >text_reset $text_byte @text; 
+    crc = any{1} $crc_byte @crc; 
+    main := ( any* :>> ( 0xAA 0x55 ) ) ( node $frame_checksum ) ( text $frame_checksum ) crc;
+}%%
+static void robustoCountTelemetry(int aPreviousCs, struct TelemetryParserState *aParserState)
+{
+    if (aPreviousCs < Telemetry_first_final && aParserState->cs >= Telemetry_first_final) {
+        ++aParserState->statistics.framesOk;
+    } else if (aPreviousCs != Telemetry_error && aParserState->cs == Telemetry_error) {
+        if (aParserState->error == ROBUSTO_PARSE_ERROR_CHECKSUM) {
+            ++aParserState->statistics.crcErrors;
+        } else if (aParserState->error == ROBUSTO_PARSE_ERROR_OVERFLOW) {
+            ++aParserState->statistics.overflows;
+        }
+    }
+}
+
+static enum RobustoParseStatus robustoParseStatusTelemetry(const struct TelemetryParserState *aParserState)
+{
+    if (aParserState->cs >= Telemetry_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Telemetry_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseTelemetry(struct TelemetryParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct TelemetryMessage *aTelemetry, int *aConsumedLength)
+{
+    const int previousCs = (aParserState->machineInitRequired != 0) ? Telemetry_start : aParserState->cs;
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  /* Iterator "begin" pointer -- Ragel-specific variable for C code generation */
+    const unsigned char *pe = p + aInputBufferLength;  /* Iterator "end" pointer -- Ragel-specific variable for C code generation */
+
+    if (aParserState->machineInitRequired != 0) {
+        const struct RobustoParserStatistics statistics = aParserState->statistics;
+
+        machineTelemetryParserStateInit(aParserState);
+        aParserState->statistics = statistics;
+    }
+
+    /* Parse starting from the state defined in `aParserState` */
+    for (;;) {
+        struct RobustoParserStatistics statistics;
+
+        %% write exec;
+
+        if (aParserState->cs != Telemetry_error || p == pe) {
+            break;
+        }
+
+        /* Resynchronize on the rest of the input */
+        robustoCountTelemetry(previousCs, aParserState);
+        ++aParserState->statistics.resyncs;
+        statistics = aParserState->statistics;
+        machineTelemetryParserStateInit(aParserState);
+        aParserState->statistics = statistics;
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    robustoCountTelemetry(previousCs, aParserState);
+    return robustoParseStatusTelemetry(aParserState);
+}
+int isTelemetryAccepted(const struct TelemetryParserState *aParserState)
+{
+    return aParserState->cs >= Telemetry_first_final;
+}
+struct RobustoParserStatistics getTelemetryStatistics(const struct TelemetryParserState *aParserState)
+{
+    return aParserState->statistics;
+}
+/* endregion Telemetry parser */
+
+/* ============================================================================= */
+/* region Self-test */
+/* ============================================================================= */
+
+int selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0xAA, 0x55, 0xEF, 0x61, 0x3B, 0xC8};
+        struct TelemetryParserState parserState;
+        struct TelemetryMessage message = {0};
+        struct TelemetryMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineTelemetryParserStateInit(&parserState);
+
+        if (parseTelemetry(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.node != 239u) {
+            return 1;
+        }
+
+        if (message.crc != 200u) {
+            return 1;
+        }
+
+        /* Feed the frame once more, one byte at a time, into a restarted parser */
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseTelemetry(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.node != 239u) {
+            return 1;
+        }
+
+        if (splitMessage.crc != 200u) {
+            return 1;
+        }
+    }
+
+    return 0;
+}
+/* endregion Self-test */
@@ -0,0 +1,102 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* ============================================================================= */
+/* region Parse errors */
+/* ============================================================================= */
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5
+};
+/* endregion Parse errors */
+
+/* ============================================================================= */
+/* region Statistics */
+/* ============================================================================= */
+
+/* Counters of a parser, which wrap around. `resyncs` counts the frames a parser has abandoned */
+/* to scan for the sync sequence anew */
+struct RobustoParserStatistics {
+    uint32_t framesOk;
+    uint32_t crcErrors;
+    uint32_t resyncs;
+    uint32_t overflows;
+};
+/* endregion Statistics */
+
+/* ============================================================================= */
+/* region Telemetry types */
+/* ============================================================================= */
+
+struct TelemetryMessage {
+    uint8_t node;
+    uint8_t text[8];
+    uint32_t textLength;
+    uint8_t crc;
+};
+struct TelemetryParserState {
+    /* Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off */
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint8_t frameChecksum;
+    struct RobustoParserStatistics statistics;
+};
+/* endregion Telemetry types */
+
+/* ============================================================================= */
+/* region Sizing constants */
+/* ============================================================================= */
+
+union RobustoAnyMessage {
+    struct TelemetryMessage Telemetry;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_TELEMETRY_MIN_FRAME_SIZE 6u
+#define ROBUSTO_TELEMETRY_MAX_FRAME_SIZE 12u
+#define ROBUSTO_MIN_FRAME_SIZE 6u
+#define ROBUSTO_MAX_FRAME_SIZE 12u
+/* endregion Sizing constants */
+
+/* ============================================================================= */
+/* region API */
+/* ============================================================================= */
+
+/* Non-null: aParserState */
+void machineTelemetryParserStateInit(struct TelemetryParserState *aParserState);
+/* Non-null: aParserState, aInputBuffer, aTelemetry */
+enum RobustoParseStatus parseTelemetry(struct TelemetryParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct TelemetryMessage *aTelemetry, int *aConsumedLength);
+/* Non-null: aParserState */
+int isTelemetryAccepted(const struct TelemetryParserState *aParserState);
+/* Non-null: aParserState */
+struct RobustoParserStatistics getTelemetryStatistics(const struct TelemetryParserState *aParserState);
+uint8_t robustoCrc8_07(uint8_t aCrc, uint8_t aByte);
+int selfTest(void);
+/* endregion API */
+
+#ifdef __cplusplus
+}  /* extern "C" */
+#endif
+
+#endif  /* OUTPUT_H */
@@ -0,0 +1,413 @@
+#include "output.h"
+
+/* ============================================================================= */
+/* region Reading parser */
+/* ============================================================================= */
+
+%%{
+    machine Reading;
+    write data;
+}%%
+void initReading(struct ReadingMessage *aReading)
+{
+    memset(aReading, 0, sizeof(*aReading));
+    aReading->id = 3u;
+    aReading->magic[0] = 0x52u;
+    aReading->magic[1] = 0x44u;
+    aReading->magicLength = 2u;
+}
+int equalsReading(const struct ReadingMessage *aLeft, const struct ReadingMessage *aRight)
+{
+    int equal = 1;
+    unsigned int i;
+
+    if (aLeft->id != aRight->id) {
+        equal = 0;
+    }
+    if (aLeft->magicLength != aRight->magicLength || memcmp(aLeft->magic, aRight->magic, (size_t)aLeft->magicLength) != 0) {
+        equal = 0;
+    }
+    if (aLeft->kind != aRight->kind) {
+        equal = 0;
+    }
+    if (aLeft->kind == 2u) {
+        if (aLeft->kindVariant.temperature != aRight->kindVariant.temperature) {
+            equal = 0;
+        }
+    }
+    for (i = 0; i < 2u; ++i) {
+        if (aLeft->channels[i].channel != aRight->channels[i].channel) {
+            equal = 0;
+        }
+        if (aLeft->channels[i].value != aRight->channels[i].value) {
+            equal = 0;
+        }
+    }
+    if (aLeft->optionsCount != aRight->optionsCount) {
+        equal = 0;
+    } else {
+        for (i = 0; i < aLeft->optionsCount; ++i) {
+            if (aLeft->options[i].tag != aRight->options[i].tag) {
+                equal = 0;
+            } else if (aLeft->options[i].tag == 1u) {
+                if (aLeft->options[i].value.interval != aRight->options[i].value.interval) {
+                    equal = 0;
+                }
+            } else if (aLeft->options[i].tag == 2u) {
+                if (memcmp(aLeft->options[i].value.name, aRight->options[i].value.name, sizeof(aLeft->options[i].value.name)) != 0) {
+                    equal = 0;
+                }
+            }
+        }
+    }
+
+    return equal;
+}
+void machineReadingParserStateInit(struct ReadingParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Reading;
+    access aParserState->;
+    alphtype unsigned char;
+    action id {
+    }
+    action id_byte {
+        aReading->id = (uint8_t)(((uint8_t)aReading->id << 8) | (uint8_t)fc);
+    }
+    action magic {
+    }
+    action magic_reset {
+        aReading->magicLength = 0;
+    }
+    action magic_byte {
+        if (aReading->magicLength < 2u) {
+            aReading->magic[aReading->magicLength++] = (uint8_t)fc;
+        }
+    }
+    action kind {
+    }
+    action kind_byte {
+        aReading->kind = (uint8_t)(((uint8_t)aReading->kind << 8) | (uint8_t)fc);
+    }
+    action temperature {
+    }
+    action temperature_when {
+        ((int64_t)aReading->kind == INT64_C(2))
+    }
+    action temperature_byte {
+        aReading->kindVariant.temperature = (int16_t)(((uint16_t)aReading->kindVariant.temperature << 8) | (uint8_t)fc);
+    }
+    action padding {
+    }
+    action padding_byte {
+        unsigned int i;
+        
+        for (i = 1; i < 2u; ++i) {
+            aReading->padding[i - 1] = aReading->padding[i];
+        }
+        aReading->padding[1] = (uint8_t)fc;
+    }
+    action channels {
+    }
+    action channels_reset {
+        aParserState->channelsIndex = 0;
+    }
+    action channels_next {
+        ++aParserState->channelsIndex;
+    }
+    action channels_channel_byte {
+        aReading->channels[aParserState->channelsIndex].channel = (uint8_t)(((uint8_t)aReading->channels[aParserState->channelsIndex].channel << 8) | (uint8_t)fc);
+    }
+    action channels_value_byte {
+        aReading->channels[aParserState->channelsIndex].value = (int16_t)(((uint16_t)aReading->channels[aParserState->channelsIndex].value << 8) | (uint8_t)fc);
+    }
+    action options {
+    }
+    action options_reset {
+        aReading->optionsCount = 0;
+    }
+    action options_record {
+        aParserState->tlvTag = 0;
+        aParserState->tlvLength = 0;
+    }
+    action options_tag {
+        aParserState->tlvTag = (aParserState->tlvTag << 8) | (uint8_t)fc;
+    }
+    action options_length {
+        aParserState->tlvLength = (aParserState->tlvLength << 8) | (uint8_t)fc;
+    }
+    action options_valueStart {
+        if (aReading->optionsCount >= 4) {
+            /* Too many records */
+            fgoto *Reading_error;
+        }
+        aReading->options[aReading->optionsCount].tag = aParserState->tlvTag;
+        aParserState->tlvValueIndex = 0;
+        if (aParserState->tlvLength == 0) {
+            aReading->optionsCount++;
+        } else {
+            fcall options_value;
+        }
+    }
+    action options_value {
+        switch (aParserState->tlvTag) {
+            case 1UL:
+                if (aParserState->tlvValueIndex == 0) {
+                    aReading->options[aReading->optionsCount].value.interval = 0;
+                }
+                aReading->options[aReading->optionsCount].value.interval = (uint16_t)((aReading->options[aReading->optionsCount].value.interval << 8) | (uint8_t)fc);
+                break;
+            case 2UL:
+                if (aParserState->tlvValueIndex < 8) {
+                    aReading->options[aReading->optionsCount].value.name[aParserState->tlvValueIndex] = (uint8_t)fc;
+                }
+                break;
+            default:
+                /* Unknown record, skip its value */
+                break;
+        }
+        aParserState->tlvValueIndex++;
+        if (aParserState->tlvValueIndex == aParserState->tlvLength) {
+            aReading->optionsCount++;
+            fret;
+        }
+    }
+    id = any{1} $id_byte @id; 
+    magic = 'RD' >magic_reset $magic_byte @magic; 
+    kind = any{1} $kind_byte @kind; 
+    temperature = ( any{2} $temperature_byte @temperature ) when temperature_when; 
+    padding = any{2} $padding_byte @padding; 
+    channels = ( any{1} $channels_channel_byte any{2} $channels_value_byte @channels_next ){2} >channels_reset @channels;
+    options_value := ( any @options_value )*;
+    options = ( any{1} >options_record $options_tag any{1} $options_length @options_valueStart )* >options_reset;
+    main := id magic kind temperature padding channels options;
+}%%
+static enum RobustoParseStatus robustoParseStatusReading(const struct ReadingParserState *aParserState)
+{
+    if (aParserState->cs >= Reading_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Reading_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseReading(struct ReadingParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct ReadingMessage *aReading, int *aConsumedLength)
+{
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  /* Iterator "begin" pointer -- Ragel-specific variable for C code generation */
+    const unsigned char *pe = p + aInputBufferLength;  /* Iterator "end" pointer -- Ragel-specific variable for C code generation */
+
+    if (aParserState->machineInitRequired != 0) {
+        machineReadingParserStateInit(aParserState);
+    }
+
+    /* Parse starting from the state defined in `aParserState` */
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusReading(aParserState);
+}
+int isReadingAccepted(const struct ReadingParserState *aParserState)
+{
+    return aParserState->cs >= Reading_first_final;
+}
+/* endregion Reading parser */
+
+/* ============================================================================= */
+/* region Settings parser */
+/* ============================================================================= */
+
+%%{
+    machine Settings;
+    write data;
+}%%
+void initSettings(struct SettingsMessage *aSettings)
+{
+    memset(aSettings, 0, sizeof(*aSettings));
+}
+int equalsSettings(const struct SettingsMessage *aLeft, const struct SettingsMessage *aRight)
+{
+    int equal = 1;
+
+    if (aLeft->rateLength != aRight->rateLength || memcmp(aLeft->rate, aRight->rate, (size_t)aLeft->rateLength) != 0) {
+        equal = 0;
+    }
+    if (aLeft->modeLength != aRight->modeLength || memcmp(aLeft->mode, aRight->mode, (size_t)aLeft->modeLength) != 0) {
+        equal = 0;
+    }
+    if (aLeft->presenceBitmap != aRight->presenceBitmap) {
+        equal = 0;
+    }
+
+    return equal;
+}
+void machineSettingsParserStateInit(struct SettingsParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Settings;
+    access aParserState->;
+    alphtype unsigned char;
+    action resetPresenceBitmap {
+        aSettings->presenceBitmap = 0;
+    }
+    action rate {
+        if (aSettings->presenceBitmap & (1UL << 0)) {
+            /* Duplicate field "rate" */
+            fgoto *Settings_error;
+        }
+        aSettings->presenceBitmap |= (1UL << 0);
+    }
+    action rate_reset {
+        aSettings->rateLength = 0;
+    }
+    action rate_byte {
+        if (aSettings->rateLength < 2u) {
+            aSettings->rate[aSettings->rateLength++] = (uint8_t)fc;
+        }
+    }
+    action mode {
+        if (aSettings->presenceBitmap & (1UL << 1)) {
+            /* Duplicate field "mode" */
+            fgoto *Settings_error;
+        }
+        aSettings->presenceBitmap |= (1UL << 1);
+    }
+    action mode_reset {
+        aSettings->modeLength = 0;
+    }
+    action mode_byte {
+        if (aSettings->modeLength < 2u) {
+            aSettings->mode[aSettings->modeLength++] = (uint8_t)fc;
+        }
+    }
+    rate = ( 'R' '0'..'9' ) >rate_reset $rate_byte @rate; 
+    mode = ( 'M' 'a'..'z' ) >mode_reset $mode_byte @mode; 
+    main := ( rate | mode )* >resetPresenceBitmap;
+}%%
+static enum RobustoParseStatus robustoParseStatusSettings(const struct SettingsParserState *aParserState)
+{
+    if (aParserState->cs >= Settings_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Settings_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseSettings(struct SettingsParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct SettingsMessage *aSettings, int *aConsumedLength)
+{
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  /* Iterator "begin" pointer -- Ragel-specific variable for C code generation */
+    const unsigned char *pe = p + aInputBufferLength;  /* Iterator "end" pointer -- Ragel-specific variable for C code generation */
+
+    if (aParserState->machineInitRequired != 0) {
+        machineSettingsParserStateInit(aParserState);
+    }
+
+    /* Parse starting from the state defined in `aParserState` */
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusSettings(aParserState);
+}
+int isSettingsAccepted(const struct SettingsParserState *aParserState)
+{
+    return aParserState->cs >= Settings_first_final;
+}
+/* endregion Settings parser */
+
+/* ============================================================================= */
+/* region Dispatcher */
+/* ============================================================================= */
+
+void robustoDispatcherInit(struct RobustoDispatcherState *aState)
+{
+    aState->id = 0u;
+    aState->idLength = 0u;
+    aState->selected = ROBUSTO_DISPATCH_PENDING;
+    aState->accepted = 0u;
+}
+
+void parseAny(struct RobustoDispatcherState *aState, const char *aInputBuffer, int aInputBufferLength, const struct RobustoDispatchCallbacks *aCallbacks)
+{
+    while (aState->selected == ROBUSTO_DISPATCH_PENDING && aInputBufferLength > 0) {
+        aState->idBuffer[aState->idLength] = *aInputBuffer;
+        aState->id = (uint8_t)((aState->id << 8) | (uint8_t)*aInputBuffer);
+        ++aState->idLength;
+        ++aInputBuffer;
+        --aInputBufferLength;
+
+        if (aState->idLength < ROBUSTO_DISPATCH_ID_LENGTH) {
+            continue;
+        }
+
+        switch (aState->id) {
+            case ROBUSTO_READING_ID:
+                aState->selected = 1u;
+                machineReadingParserStateInit(&aState->parserState.Reading);
+                parseReading(&aState->parserState.Reading, aState->idBuffer, ROBUSTO_DISPATCH_ID_LENGTH, &aState->message.Reading, 0);
+                break;
+            default:
+                aState->selected = ROBUSTO_DISPATCH_UNKNOWN;
+                break;
+        }
+    }
+
+    switch (aState->selected) {
+        case 1u:
+            if (aInputBufferLength > 0) {
+                parseReading(&aState->parserState.Reading, aInputBuffer, aInputBufferLength, &aState->message.Reading, 0);
+            }
+
+            if (!aState->accepted && isReadingAccepted(&aState->parserState.Reading)) {
+                aState->accepted = 1u;
+
+                if (aCallbacks->onReading != 0) {
+                    aCallbacks->onReading(&aState->message.Reading, aCallbacks->context);
+                }
+            }
+            break;
+        default:
+            break;
+    }
+}
+/* endregion Dispatcher */
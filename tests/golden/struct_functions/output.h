@@ -0,0 +1,183 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+#include <string.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* ============================================================================= */
+/* region Parse errors */
+/* ============================================================================= */
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5
+};
+/* endregion Parse errors */
+
+/* ============================================================================= */
+/* region Reading types */
+/* ============================================================================= */
+
+struct ReadingChannelsGroup {
+    uint8_t channel;
+    int16_t value;
+};
+struct ReadingOptionsRecord {
+    uint32_t tag;
+    union {
+        uint16_t interval;
+        uint8_t name[8];
+    } value;
+};
+struct ReadingMessage {
+    uint8_t id;
+    uint8_t magic[2];
+    uint32_t magicLength;
+    uint8_t kind;
+    union {
+        int16_t temperature;
+    } kindVariant;
+    uint8_t padding[2];
+    struct ReadingChannelsGroup channels[2];
+    struct ReadingOptionsRecord options[4];
+    uint32_t optionsCount;
+};
+struct ReadingParserState {
+    /* Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off */
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint32_t channelsIndex;
+    uint32_t tlvTag;
+    uint32_t tlvLength;
+    uint32_t tlvValueIndex;
+    int32_t stack[1];
+    int32_t top;
+};
+/* endregion Reading types */
+
+/* ============================================================================= */
+/* region Settings types */
+/* ============================================================================= */
+
+struct SettingsMessage {
+    uint8_t rate[2];
+    uint32_t rateLength;
+    uint8_t mode[2];
+    uint32_t modeLength;
+    uint32_t presenceBitmap;
+};
+struct SettingsParserState {
+    /* Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off */
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+/* endregion Settings types */
+
+/* ============================================================================= */
+/* region Sizing constants */
+/* ============================================================================= */
+
+union RobustoAnyMessage {
+    struct ReadingMessage Reading;
+    struct SettingsMessage Settings;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_READING_MIN_FRAME_SIZE 14u
+#define ROBUSTO_READING_MAX_FRAME_SIZE 54u
+#define ROBUSTO_SETTINGS_MIN_FRAME_SIZE 4u
+#define ROBUSTO_SETTINGS_MAX_FRAME_SIZE 4u
+#define ROBUSTO_MIN_FRAME_SIZE 4u
+#define ROBUSTO_MAX_FRAME_SIZE 54u
+/* endregion Sizing constants */
+
+/* ============================================================================= */
+/* region Dispatcher */
+/* ============================================================================= */
+
+#define ROBUSTO_DISPATCH_ID_LENGTH 1u
+#define ROBUSTO_DISPATCH_PENDING 0u
+#define ROBUSTO_DISPATCH_UNKNOWN 255u
+#define ROBUSTO_READING_ID 3u
+
+enum RobustoMessageId {
+    ROBUSTO_MESSAGE_ID_READING = ROBUSTO_READING_ID
+};
+
+/* Each callback is invoked once its message's frame is accepted, and may be NULL */
+struct RobustoDispatchCallbacks {
+    void *context;
+    void (*onReading)(const struct ReadingMessage *aReading, void *aContext);
+};
+
+union RobustoDispatchParserState {
+    struct ReadingParserState Reading;
+};
+
+/* `selected` is the 1-based index of the message being parsed, `ROBUSTO_DISPATCH_PENDING` while */
+/* the ID is incomplete, or `ROBUSTO_DISPATCH_UNKNOWN` if no message has the ID */
+struct RobustoDispatcherState {
+    uint8_t id;
+    char idBuffer[ROBUSTO_DISPATCH_ID_LENGTH];
+    uint8_t idLength;
+    uint8_t selected;
+    uint8_t accepted;
+    union RobustoDispatchParserState parserState;
+    union RobustoAnyMessage message;
+};
+/* endregion Dispatcher */
+
+/* ============================================================================= */
+/* region API */
+/* ============================================================================= */
+
+/* Non-null: aParserState */
+void machineReadingParserStateInit(struct ReadingParserState *aParserState);
+/* Non-null: aParserState, aInputBuffer, aReading */
+enum RobustoParseStatus parseReading(struct ReadingParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct ReadingMessage *aReading, int *aConsumedLength);
+/* Non-null: aParserState */
+int isReadingAccepted(const struct ReadingParserState *aParserState);
+/* Non-null: aReading */
+void initReading(struct ReadingMessage *aReading);
+/* Non-null: aLeft, aRight */
+int equalsReading(const struct ReadingMessage *aLeft, const struct ReadingMessage *aRight);
+/* Non-null: aParserState */
+void machineSettingsParserStateInit(struct SettingsParserState *aParserState);
+/* Non-null: aParserState, aInputBuffer, aSettings */
+enum RobustoParseStatus parseSettings(struct SettingsParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct SettingsMessage *aSettings, int *aConsumedLength);
+/* Non-null: aParserState */
+int isSettingsAccepted(const struct SettingsParserState *aParserState);
+/* Non-null: aSettings */
+void initSettings(struct SettingsMessage *aSettings);
+/* Non-null: aLeft, aRight */
+int equalsSettings(const struct SettingsMessage *aLeft, const struct SettingsMessage *aRight);
+/* Non-null: aState */
+void robustoDispatcherInit(struct RobustoDispatcherState *aState);
+/* Non-null: aState, aInputBuffer, aCallbacks */
+void parseAny(struct RobustoDispatcherState *aState, const char *aInputBuffer, int aInputBufferLength, const struct RobustoDispatchCallbacks *aCallbacks);
+/* endregion API */
+
+#ifdef __cplusplus
+}  /* extern "C" */
+#endif
+
+#endif  /* OUTPUT_H */
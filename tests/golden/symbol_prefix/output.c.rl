@@ -0,0 +1,470 @@
+#include "output.h"
+
+// =============================================================================
+// region Frame trace
+// =============================================================================
+
+struct acme_RobustoTraceRecord acme_robustoTraceRecords[ACME_ROBUSTO_TRACE_DEPTH];
+uint32_t acme_robustoTraceCount;  // Number of traced frames
+
+// Copies the latest records, oldest first. Returns the number of copied records
+unsigned int acme_robustoTraceDump(struct acme_RobustoTraceRecord *aRecords, unsigned int aCapacity)
+{
+    uint32_t n = acme_robustoTraceCount < ACME_ROBUSTO_TRACE_DEPTH ? acme_robustoTraceCount : ACME_ROBUSTO_TRACE_DEPTH;
+    uint32_t i;
+
+    if (n > aCapacity) {
+        n = aCapacity;
+    }
+
+    for (i = 0; i < n; ++i) {
+        aRecords[i] = acme_robustoTraceRecords[(acme_robustoTraceCount - n + i) % ACME_ROBUSTO_TRACE_DEPTH];
+    }
+
+    return (unsigned int)n;
+}
+
+static void acme_robustoTraceFrame(uint8_t aMessageId, uint8_t aAccepted, uint8_t aError, uint32_t aLength)
+{
+    struct acme_RobustoTraceRecord *record = &acme_robustoTraceRecords[acme_robustoTraceCount % ACME_ROBUSTO_TRACE_DEPTH];
+
+    record->timestamp = acmeMillis();
+    record->length = aLength;
+    record->messageId = aMessageId;
+    record->accepted = aAccepted;
+    record->error = aError;
+    ++acme_robustoTraceCount;
+}
+// endregion Frame trace
+
+// =============================================================================
+// region Status parser
+// =============================================================================
+
+%%{
+    machine acme_Status;
+    write data;
+}%%
+int acme_testStatusState(const struct acme_StatusMessage *aStatus, uint8_t aFlags)
+{
+    return (aStatus->state & aFlags) == aFlags;
+}
+void acme_setStatusState(struct acme_StatusMessage *aStatus, uint8_t aFlags, int aValue)
+{
+    if (aValue) {
+        aStatus->state = (uint8_t)(aStatus->state | aFlags);
+    } else {
+        aStatus->state = (uint8_t)(aStatus->state & ~aFlags);
+    }
+}
+void acme_machineStatusParserStateInit(struct acme_StatusParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->traceLength = 0;
+    aParserState->frameChecksum = 0xFFFFu;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine acme_Status;
+    access aParserState->;
+    alphtype unsigned char;
+    action id {
+    }
+    action id_byte {
+        aStatus->id = (uint8_t)(((uint8_t)aStatus->id << 8) | (uint8_t)fc);
+    }
+    action state {
+    }
+    action state_byte {
+        aStatus->state = (uint8_t)(((uint8_t)aStatus->state << 8) | (uint8_t)fc);
+    }
+    action crc {
+        if (aStatus->crc != aParserState->frameChecksum) {
+            aParserState->error = ACME_ROBUSTO_PARSE_ERROR_CHECKSUM;
+            fgoto *acme_Status_error;
+        }
+    }
+    action frame_checksum {
+        aParserState->frameChecksum = robustoCrc16_1021(aParserState->frameChecksum, (uint8_t)fc);
+    }
+    action crc_byte {
+        aStatus->crc = (uint16_t)(((uint16_t)aStatus->crc << 8) | (uint8_t)fc);
+    }
+    id = any{1} $id_byte @id; 
+    state = any{1} $state_byte @state; 
+    crc = any{2} $crc_byte @crc; 
+    main := id ( state $frame_checksum ) crc;
+}%%
+static void acme_robustoTraceStatus(int aPreviousCs, const struct acme_StatusParserState *aParserState)
+{
+    if (aPreviousCs < acme_Status_first_final && aParserState->cs >= acme_Status_first_final) {
+        acme_robustoTraceFrame(0u, 1u, aParserState->error, aParserState->traceLength);
+    } else if (aPreviousCs != acme_Status_error && aParserState->cs == acme_Status_error) {
+        acme_robustoTraceFrame(0u, 0u, aParserState->error, aParserState->traceLength);
+    }
+}
+
+static enum acme_RobustoParseStatus acme_robustoParseStatusStatus(const struct acme_StatusParserState *aParserState)
+{
+    if (aParserState->cs >= acme_Status_first_final) {
+        return ACME_ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != acme_Status_error) {
+        return ACME_ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ACME_ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ACME_ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ACME_ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ACME_ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ACME_ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ACME_ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ACME_ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum acme_RobustoParseStatus acme_parseStatus(struct acme_StatusParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct acme_StatusMessage *aStatus, int *aConsumedLength)
+{
+    const int previousCs = (aParserState->machineInitRequired != 0) ? acme_Status_start : aParserState->cs;
+
+    if (aParserState->machineInitRequired != 0) {
+        acme_machineStatusParserStateInit(aParserState);
+    }
+
+    aParserState->traceLength += (uint32_t)aInputBufferLength;
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    acme_robustoTraceStatus(previousCs, aParserState);
+    return acme_robustoParseStatusStatus(aParserState);
+}
+int acme_isStatusAccepted(const struct acme_StatusParserState *aParserState)
+{
+    return aParserState->cs >= acme_Status_first_final;
+}
+// endregion Status parser
+
+// =============================================================================
+// region Log parser
+// =============================================================================
+
+%%{
+    machine acme_Log;
+    write data;
+}%%
+void acme_machineLogParserStateInit(struct acme_LogParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->traceLength = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine acme_Log;
+    access aParserState->;
+    alphtype unsigned char;
+    action id {
+    }
+    action id_byte {
+        aLog->id = (uint8_t)(((uint8_t)aLog->id << 8) | (uint8_t)fc);
+    }
+    action text {
+    }
+    action text_reset {
+        aLog->textLength = 0;
+    }
+    action text_byte {
+        if (aLog->textLength < 16u) {
+            aLog->text[aLog->textLength++] = (uint8_t)fc;
+        }
+    }
+    action acme_selfTest {
+    }
+    action selfTest_byte {
+        aLog->selfTest = (uint8_t)(((uint8_t)aLog->selfTest << 8) | (uint8_t)fc);
+    }
+    id = any{1} $id_byte @id; 
+    text = ( 'a'..'z' )+ >text_reset $text_byte @text; 
+    acme_selfTest = any{1} $selfTest_byte @acme_selfTest; 
+    main := id text acme_selfTest;
+}%%
+static void acme_robustoTraceLog(int aPreviousCs, const struct acme_LogParserState *aParserState)
+{
+    if (aPreviousCs < acme_Log_first_final && aParserState->cs >= acme_Log_first_final) {
+        acme_robustoTraceFrame(1u, 1u, aParserState->error, aParserState->traceLength);
+    } else if (aPreviousCs != acme_Log_error && aParserState->cs == acme_Log_error) {
+        acme_robustoTraceFrame(1u, 0u, aParserState->error, aParserState->traceLength);
+    }
+}
+
+static enum acme_RobustoParseStatus acme_robustoParseStatusLog(const struct acme_LogParserState *aParserState)
+{
+    if (aParserState->cs >= acme_Log_first_final) {
+        return ACME_ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != acme_Log_error) {
+        return ACME_ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ACME_ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ACME_ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ACME_ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ACME_ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ACME_ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ACME_ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ACME_ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum acme_RobustoParseStatus acme_parseLog(struct acme_LogParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct acme_LogMessage *aLog, int *aConsumedLength)
+{
+    const int previousCs = (aParserState->machineInitRequired != 0) ? acme_Log_start : aParserState->cs;
+
+    if (aParserState->machineInitRequired != 0) {
+        acme_machineLogParserStateInit(aParserState);
+    }
+
+    aParserState->traceLength += (uint32_t)aInputBufferLength;
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    acme_robustoTraceLog(previousCs, aParserState);
+    return acme_robustoParseStatusLog(aParserState);
+}
+int acme_isLogAccepted(const struct acme_LogParserState *aParserState)
+{
+    return aParserState->cs >= acme_Log_first_final;
+}
+// endregion Log parser
+
+// =============================================================================
+// region Dispatcher
+// =============================================================================
+
+void acme_robustoDispatcherInit(struct acme_RobustoDispatcherState *aState)
+{
+    aState->id = 0u;
+    aState->idLength = 0u;
+    aState->selected = ACME_ROBUSTO_DISPATCH_PENDING;
+    aState->accepted = 0u;
+}
+
+void acme_parseAny(struct acme_RobustoDispatcherState *aState, const char *aInputBuffer, int aInputBufferLength, const struct acme_RobustoDispatchCallbacks *aCallbacks)
+{
+    while (aState->selected == ACME_ROBUSTO_DISPATCH_PENDING && aInputBufferLength > 0) {
+        aState->idBuffer[aState->idLength] = *aInputBuffer;
+        aState->id = (uint8_t)((aState->id << 8) | (uint8_t)*aInputBuffer);
+        ++aState->idLength;
+        ++aInputBuffer;
+        --aInputBufferLength;
+
+        if (aState->idLength < ACME_ROBUSTO_DISPATCH_ID_LENGTH) {
+            continue;
+        }
+
+        switch (aState->id) {
+            case ACME_ROBUSTO_STATUS_ID:
+                aState->selected = 1u;
+                acme_machineStatusParserStateInit(&aState->parserState.Status);
+                acme_parseStatus(&aState->parserState.Status, aState->idBuffer, ACME_ROBUSTO_DISPATCH_ID_LENGTH, &aState->message.Status, 0);
+                break;
+            case ACME_ROBUSTO_LOG_ID:
+                aState->selected = 2u;
+                acme_machineLogParserStateInit(&aState->parserState.Log);
+                acme_parseLog(&aState->parserState.Log, aState->idBuffer, ACME_ROBUSTO_DISPATCH_ID_LENGTH, &aState->message.Log, 0);
+                break;
+            default:
+                aState->selected = ACME_ROBUSTO_DISPATCH_UNKNOWN;
+                break;
+        }
+    }
+
+    switch (aState->selected) {
+        case 1u:
+            if (aInputBufferLength > 0) {
+                acme_parseStatus(&aState->parserState.Status, aInputBuffer, aInputBufferLength, &aState->message.Status, 0);
+            }
+
+            if (!aState->accepted && acme_isStatusAccepted(&aState->parserState.Status)) {
+                aState->accepted = 1u;
+
+                if (aCallbacks->onStatus != 0) {
+                    aCallbacks->onStatus(&aState->message.Status, aCallbacks->context);
+                }
+            }
+            break;
+        case 2u:
+            if (aInputBufferLength > 0) {
+                acme_parseLog(&aState->parserState.Log, aInputBuffer, aInputBufferLength, &aState->message.Log, 0);
+            }
+
+            if (!aState->accepted && acme_isLogAccepted(&aState->parserState.Log)) {
+                aState->accepted = 1u;
+
+                if (aCallbacks->onLog != 0) {
+                    aCallbacks->onLog(&aState->message.Log, aCallbacks->context);
+                }
+            }
+            break;
+        default:
+            break;
+    }
+}
+// endregion Dispatcher
+
+// =============================================================================
+// region Self-test
+// =============================================================================
+
+int acme_selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0xEF, 0xCD, 0xE9, 0x11};
+        struct acme_StatusParserState parserState;
+        struct acme_StatusMessage message = {0};
+        struct acme_StatusMessage splitMessage = {0};
+        enum acme_RobustoParseStatus splitStatus = ACME_ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        acme_machineStatusParserStateInit(&parserState);
+
+        if (acme_parseStatus(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ACME_ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.id != 239u) {
+            return 1;
+        }
+
+        if (message.state != 205u) {
+            return 1;
+        }
+
+        if (message.crc != 59665u) {
+            return 1;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = acme_parseStatus(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ACME_ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.id != 239u) {
+            return 1;
+        }
+
+        if (splitMessage.state != 205u) {
+            return 1;
+        }
+
+        if (splitMessage.crc != 59665u) {
+            return 1;
+        }
+    }
+    {
+        static const uint8_t kFrame[] = {0xEF, 0x61, 0xAB};
+        struct acme_LogParserState parserState;
+        struct acme_LogMessage message = {0};
+        struct acme_LogMessage splitMessage = {0};
+        enum acme_RobustoParseStatus splitStatus = ACME_ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        acme_machineLogParserStateInit(&parserState);
+
+        if (acme_parseLog(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ACME_ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (message.id != 239u) {
+            return 2;
+        }
+
+        if (message.selfTest != 171u) {
+            return 2;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = acme_parseLog(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ACME_ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (splitMessage.id != 239u) {
+            return 2;
+        }
+
+        if (splitMessage.selfTest != 171u) {
+            return 2;
+        }
+    }
+
+    return 0;
+}
+// endregion Self-test
+
+// =============================================================================
+// region Diagnostic strings
+// =============================================================================
+
+static const char acme_kRobustoParseErrorString0[] = "no error";
+static const char acme_kRobustoParseErrorString1[] = "field value out of range";
+static const char acme_kRobustoParseErrorString2[] = "frame longer than the message's fixed length";
+static const char acme_kRobustoParseErrorString3[] = "checksum mismatch";
+static const char acme_kRobustoParseErrorString4[] = "length field mismatch";
+static const char acme_kRobustoParseErrorString5[] = "field check failed";
+static const char acme_kRobustoParseErrorString6[] = "field longer than its max length";
+static const char acme_kRobustoParseErrorStringUnknown[] = "unknown error";
+
+static const char *const acme_kRobustoParseErrorStrings[] = {
+    acme_kRobustoParseErrorString0,  // ROBUSTO_PARSE_ERROR_NONE
+    acme_kRobustoParseErrorString1,  // ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE
+    acme_kRobustoParseErrorString2,  // ROBUSTO_PARSE_ERROR_FRAME_LENGTH
+    acme_kRobustoParseErrorString3,  // ROBUSTO_PARSE_ERROR_CHECKSUM
+    acme_kRobustoParseErrorString4,  // ROBUSTO_PARSE_ERROR_LENGTH
+    acme_kRobustoParseErrorString5,  // ROBUSTO_PARSE_ERROR_CHECK
+    acme_kRobustoParseErrorString6,  // ROBUSTO_PARSE_ERROR_OVERFLOW
+};
+
+const char *acme_robustoParseErrorString(uint8_t aError)
+{
+    if (aError >= sizeof(acme_kRobustoParseErrorStrings) / sizeof(acme_kRobustoParseErrorStrings[0])) {
+        return acme_kRobustoParseErrorStringUnknown;
+    }
+
+    return acme_kRobustoParseErrorStrings[aError];
+}
+// endregion Diagnostic strings
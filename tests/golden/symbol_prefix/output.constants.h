@@ -0,0 +1,50 @@
+// Length of the message ID field, in bytes
+#define ACME_ROBUSTO_DISPATCH_ID_LENGTH 1u
+// ID of message Status
+#define ACME_ROBUSTO_STATUS_ID 1u
+// ID of message Log
+#define ACME_ROBUSTO_LOG_ID 2u
+// Parse error code: no error
+#define ACME_ROBUSTO_ERROR_CODE_NONE 0u
+// Parse error code: field value out of range
+#define ACME_ROBUSTO_ERROR_CODE_VALUE_OUT_OF_RANGE 1u
+// Parse error code: frame longer than the message's fixed length
+#define ACME_ROBUSTO_ERROR_CODE_FRAME_LENGTH 2u
+// Parse error code: checksum mismatch
+#define ACME_ROBUSTO_ERROR_CODE_CHECKSUM 3u
+// Parse error code: length field mismatch
+#define ACME_ROBUSTO_ERROR_CODE_LENGTH 4u
+// Parse error code: field check failed
+#define ACME_ROBUSTO_ERROR_CODE_CHECK 5u
+// Parse error code: field longer than its max length
+#define ACME_ROBUSTO_ERROR_CODE_OVERFLOW 6u
+// Parse status: frame complete
+#define ACME_ROBUSTO_PARSE_STATUS_OK 0u
+// Parse status: frame incomplete
+#define ACME_ROBUSTO_PARSE_STATUS_INCOMPLETE 1u
+// Parse status: frame rejected on a checksum mismatch
+#define ACME_ROBUSTO_PARSE_STATUS_ERROR_CRC 2u
+// Parse status: frame rejected on a field value out of range
+#define ACME_ROBUSTO_PARSE_STATUS_ERROR_RANGE 3u
+// Parse status: frame rejected on a field longer than its max length
+#define ACME_ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW 4u
+// Parse status: frame rejected
+#define ACME_ROBUSTO_PARSE_STATUS_ERROR 5u
+// Min length of a frame of message Status, in bytes
+#define ACME_ROBUSTO_STATUS_MIN_FRAME_SIZE 4u
+// Max length of a frame of message Status, in bytes
+#define ACME_ROBUSTO_STATUS_MAX_FRAME_SIZE 4u
+// Min length of a frame of message Log, in bytes
+#define ACME_ROBUSTO_LOG_MIN_FRAME_SIZE 3u
+// Max length of a frame of message Log, in bytes
+#define ACME_ROBUSTO_LOG_MAX_FRAME_SIZE 18u
+// Min length of a frame of any message, in bytes
+#define ACME_ROBUSTO_MIN_FRAME_SIZE 3u
+// Max length of a frame of any message, in bytes
+#define ACME_ROBUSTO_MAX_FRAME_SIZE 18u
+// Number of frame trace records
+#define ACME_ROBUSTO_TRACE_DEPTH 4u
+// ID of message Status in frame trace records
+#define ACME_ROBUSTO_STATUS_TRACE_ID 0u
+// ID of message Log in frame trace records
+#define ACME_ROBUSTO_LOG_TRACE_ID 1u
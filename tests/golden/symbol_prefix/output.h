@@ -0,0 +1,194 @@
+#ifndef ACME_OUTPUT_H
+#define ACME_OUTPUT_H
+
+#include <stdint.h>
+
+// =============================================================================
+// region Runtime
+// =============================================================================
+
+#include "robusto_rt.h"
+
+#if ROBUSTO_RT_VERSION != 1
+#error "The generated code requires version 1 of the Robusto runtime library"
+#endif
+// endregion Runtime
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum acme_RobustoParseError {
+    ACME_ROBUSTO_PARSE_ERROR_NONE = 0,
+    ACME_ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ACME_ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ACME_ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ACME_ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ACME_ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ACME_ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum acme_RobustoParseStatus {
+    ACME_ROBUSTO_PARSE_STATUS_OK = 0,
+    ACME_ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ACME_ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ACME_ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ACME_ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ACME_ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region Status types
+// =============================================================================
+
+#define ACME_STATUS_STATE_FLAG_READY (1u << 0)
+struct acme_StatusMessage {
+    uint8_t id;
+    uint8_t state;
+    uint16_t crc;
+};
+struct acme_StatusParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint32_t traceLength;
+    uint16_t frameChecksum;
+};
+// endregion Status types
+
+// =============================================================================
+// region Log types
+// =============================================================================
+
+struct acme_LogMessage {
+    uint8_t id;
+    uint8_t text[16];
+    uint32_t textLength;
+    uint8_t selfTest;
+};
+struct acme_LogParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint32_t traceLength;
+};
+// endregion Log types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union acme_RobustoAnyMessage {
+    struct acme_StatusMessage Status;
+    struct acme_LogMessage Log;
+};
+#define ACME_ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union acme_RobustoAnyMessage))
+#define ACME_ROBUSTO_STATUS_MIN_FRAME_SIZE 4u
+#define ACME_ROBUSTO_STATUS_MAX_FRAME_SIZE 4u
+#define ACME_ROBUSTO_LOG_MIN_FRAME_SIZE 3u
+#define ACME_ROBUSTO_LOG_MAX_FRAME_SIZE 18u
+#define ACME_ROBUSTO_MIN_FRAME_SIZE 3u
+#define ACME_ROBUSTO_MAX_FRAME_SIZE 18u
+// endregion Sizing constants
+
+// =============================================================================
+// region Frame trace
+// =============================================================================
+
+#define ACME_ROBUSTO_TRACE_DEPTH 4u
+#define ACME_ROBUSTO_STATUS_TRACE_ID 0u
+#define ACME_ROBUSTO_LOG_TRACE_ID 1u
+struct acme_RobustoTraceRecord {
+    uint32_t timestamp;
+    uint32_t length;
+    uint8_t messageId;
+    uint8_t accepted;
+    uint8_t error;
+};
+extern struct acme_RobustoTraceRecord acme_robustoTraceRecords[ACME_ROBUSTO_TRACE_DEPTH];
+extern uint32_t acme_robustoTraceCount;
+uint32_t acmeMillis(void);
+// endregion Frame trace
+
+// =============================================================================
+// region Dispatcher
+// =============================================================================
+
+#define ACME_ROBUSTO_DISPATCH_ID_LENGTH 1u
+#define ACME_ROBUSTO_DISPATCH_PENDING 0u
+#define ACME_ROBUSTO_DISPATCH_UNKNOWN 255u
+#define ACME_ROBUSTO_STATUS_ID 1u
+#define ACME_ROBUSTO_LOG_ID 2u
+
+enum acme_RobustoMessageId {
+    ACME_ROBUSTO_MESSAGE_ID_STATUS = ACME_ROBUSTO_STATUS_ID,
+    ACME_ROBUSTO_MESSAGE_ID_LOG = ACME_ROBUSTO_LOG_ID,
+};
+
+// Each callback is invoked once its message's frame is accepted, and may be NULL
+struct acme_RobustoDispatchCallbacks {
+    void *context;
+    void (*onStatus)(const struct acme_StatusMessage *aStatus, void *aContext);
+    void (*onLog)(const struct acme_LogMessage *aLog, void *aContext);
+};
+
+union acme_RobustoDispatchParserState {
+    struct acme_StatusParserState Status;
+    struct acme_LogParserState Log;
+};
+
+// `selected` is the 1-based index of the message being parsed, `ROBUSTO_DISPATCH_PENDING` while
+// the ID is incomplete, or `ROBUSTO_DISPATCH_UNKNOWN` if no message has the ID
+struct acme_RobustoDispatcherState {
+    uint8_t id;
+    char idBuffer[ACME_ROBUSTO_DISPATCH_ID_LENGTH];
+    uint8_t idLength;
+    uint8_t selected;
+    uint8_t accepted;
+    union acme_RobustoDispatchParserState parserState;
+    union acme_RobustoAnyMessage message;
+};
+// endregion Dispatcher
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void acme_machineStatusParserStateInit(struct acme_StatusParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aStatus
+enum acme_RobustoParseStatus acme_parseStatus(struct acme_StatusParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct acme_StatusMessage *aStatus, int *aConsumedLength);
+// Non-null: aParserState
+int acme_isStatusAccepted(const struct acme_StatusParserState *aParserState);
+// Non-null: aStatus
+int acme_testStatusState(const struct acme_StatusMessage *aStatus, uint8_t aFlags);
+// Non-null: aStatus
+void acme_setStatusState(struct acme_StatusMessage *aStatus, uint8_t aFlags, int aValue);
+// Non-null: aParserState
+void acme_machineLogParserStateInit(struct acme_LogParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aLog
+enum acme_RobustoParseStatus acme_parseLog(struct acme_LogParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct acme_LogMessage *aLog, int *aConsumedLength);
+// Non-null: aParserState
+int acme_isLogAccepted(const struct acme_LogParserState *aParserState);
+int acme_selfTest(void);
+const char *acme_robustoParseErrorString(uint8_t aError);
+// Non-null: aRecords
+unsigned int acme_robustoTraceDump(struct acme_RobustoTraceRecord *aRecords, unsigned int aCapacity);
+// Non-null: aState
+void acme_robustoDispatcherInit(struct acme_RobustoDispatcherState *aState);
+// Non-null: aState, aInputBuffer, aCallbacks
+void acme_parseAny(struct acme_RobustoDispatcherState *aState, const char *aInputBuffer, int aInputBufferLength, const struct acme_RobustoDispatchCallbacks *aCallbacks);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
@@ -0,0 +1,18 @@
+#ifndef ROBUSTO_RT_H
+#define ROBUSTO_RT_H
+
+#include <stdint.h>
+
+#define ROBUSTO_RT_VERSION 1
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+uint16_t robustoCrc16_1021(uint16_t aCrc, uint8_t aByte);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // ROBUSTO_RT_H
@@ -0,0 +1,272 @@
+#include "output.h"
+
+// =============================================================================
+// region Bit reader
+// =============================================================================
+
+static uint64_t robustoReadBits(const uint8_t *aBuffer, unsigned long aBitOffset, unsigned int aBitWidth)
+{
+    uint64_t value = 0;
+    unsigned int i;
+
+    for (i = 0; i < aBitWidth; ++i) {
+        unsigned long bit = aBitOffset + i;
+        value = (value << 1) | ((aBuffer[bit / 8] >> (7 - (bit % 8))) & 1u);
+    }
+
+    return value;
+}
+static int64_t robustoSignExtend(uint64_t aValue, unsigned int aBitWidth)
+{
+    uint64_t signBit = (uint64_t)1 << (aBitWidth - 1);
+
+    return (int64_t)((aValue ^ signBit) - signBit);
+}
+// endregion Bit reader
+
+// =============================================================================
+// region Telemetry parser
+// =============================================================================
+
+%%{
+    machine Telemetry;
+    write data;
+}%%
+int64_t getTelemetryUptime_ms(const struct TelemetryMessage *aTelemetry)
+{
+    return INT64_C(946684800000) + (int64_t)aTelemetry->uptime * INT64_C(1000);
+}
+void setTelemetryUptime_ms(struct TelemetryMessage *aTelemetry, int64_t aMilliseconds)
+{
+    aTelemetry->uptime = (uint32_t)((aMilliseconds - INT64_C(946684800000)) / INT64_C(1000));
+}
+int64_t getTelemetrySample_ms(const struct TelemetryMessage *aTelemetry)
+{
+    return INT64_C(0) + (int64_t)aTelemetry->sample * INT64_C(250) / INT64_C(1000);
+}
+void setTelemetrySample_ms(struct TelemetryMessage *aTelemetry, int64_t aMilliseconds)
+{
+    aTelemetry->sample = (uint16_t)((aMilliseconds - INT64_C(0)) * INT64_C(1000) / INT64_C(250));
+}
+void machineTelemetryParserStateInit(struct TelemetryParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Telemetry;
+    access aParserState->;
+    alphtype unsigned char;
+    action sync {
+    }
+    action sync_reset {
+        aTelemetry->syncLength = 0;
+    }
+    action sync_byte {
+        if (aTelemetry->syncLength < 1u) {
+            aTelemetry->sync[aTelemetry->syncLength++] = (uint8_t)fc;
+        } else {
+            // Drop the frame, and start over on this byte
+            fhold;
+            fgoto main;
+        }
+    }
+    action uptime {
+    }
+    action uptime_byte {
+        aTelemetry->uptime = (uint32_t)(((uint32_t)aTelemetry->uptime << 8) | (uint8_t)fc);
+    }
+    action sample {
+    }
+    action sample_byte {
+        aTelemetry->sample = (uint16_t)(((uint16_t)aTelemetry->sample << 8) | (uint8_t)fc);
+    }
+    sync = 0xAA >sync_reset $sync_byte @sync; 
+    uptime = any{4} $uptime_byte @uptime; 
+    sample = any{2} $sample_byte @sample; 
+    main := sync uptime sample;
+}%%
+static enum RobustoParseStatus robustoParseStatusTelemetry(const struct TelemetryParserState *aParserState)
+{
+    if (aParserState->cs >= Telemetry_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Telemetry_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseTelemetry(struct TelemetryParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct TelemetryMessage *aTelemetry, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineTelemetryParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusTelemetry(aParserState);
+}
+int isTelemetryAccepted(const struct TelemetryParserState *aParserState)
+{
+    return aParserState->cs >= Telemetry_first_final;
+}
+// endregion Telemetry parser
+
+// =============================================================================
+// region Bits parser
+// =============================================================================
+
+int64_t getBitsT_ms(const struct BitsMessage *aBits)
+{
+    return INT64_C(-5) + (int64_t)aBits->t * INT64_C(1);
+}
+void setBitsT_ms(struct BitsMessage *aBits, int64_t aMilliseconds)
+{
+    aBits->t = (uint16_t)((aMilliseconds - INT64_C(-5)) / INT64_C(1));
+}
+int parseBits(const uint8_t *aInputBuffer, int aInputBufferLength, struct BitsMessage *aBits)
+{
+    unsigned long bitOffset = 0;  // Position of the bit reader
+
+    if ((unsigned long)aInputBufferLength * 8UL < 16UL) {
+        return -1;
+    }
+
+    aBits->t = (uint16_t)robustoReadBits(aInputBuffer, bitOffset, 12u);
+    bitOffset += 12u;
+    aBits->rest = (uint8_t)robustoReadBits(aInputBuffer, bitOffset, 4u);
+    bitOffset += 4u;
+    return (int)((bitOffset + 7UL) / 8UL);
+}
+// endregion Bits parser
+
+// =============================================================================
+// region Self-test
+// =============================================================================
+
+int selfTest(void)
+{
+    {
+        static const uint8_t kFrame[] = {0xAA, 0x67, 0x89, 0xAB, 0xCD, 0x89, 0xAB};
+        struct TelemetryParserState parserState;
+        struct TelemetryMessage message = {0};
+        struct TelemetryMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineTelemetryParserStateInit(&parserState);
+
+        if (parseTelemetry(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (message.uptime != 1737075661u) {
+            return 1;
+        }
+
+        if (message.sample != 35243u) {
+            return 1;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseTelemetry(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 1;
+        }
+
+        if (splitMessage.uptime != 1737075661u) {
+            return 1;
+        }
+
+        if (splitMessage.sample != 35243u) {
+            return 1;
+        }
+    }
+    {
+        // Byte order: multi-byte fields carry 0x01, 0x02, ... on the wire, and decode the same on any host
+        static const uint8_t kFrame[] = {0xAA, 0x01, 0x02, 0x03, 0x04, 0x01, 0x02};
+        struct TelemetryParserState parserState;
+        struct TelemetryMessage message = {0};
+        struct TelemetryMessage splitMessage = {0};
+        enum RobustoParseStatus splitStatus = ROBUSTO_PARSE_STATUS_INCOMPLETE;
+        int i;
+
+        machineTelemetryParserStateInit(&parserState);
+
+        if (parseTelemetry(&parserState, (const char *)kFrame, (int)sizeof(kFrame), &message, 0) != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (message.uptime != 0x1020304u) {
+            return 2;
+        }
+
+        if (message.sample != 0x102u) {
+            return 2;
+        }
+
+        // Feed the frame once more, one byte at a time, into a restarted parser
+        parserState.machineInitRequired = 1;
+
+        for (i = 0; i < (int)sizeof(kFrame); ++i) {
+            splitStatus = parseTelemetry(&parserState, (const char *)&kFrame[i], 1, &splitMessage, 0);
+        }
+
+        if (splitStatus != ROBUSTO_PARSE_STATUS_OK) {
+            return 2;
+        }
+
+        if (splitMessage.uptime != 0x1020304u) {
+            return 2;
+        }
+
+        if (splitMessage.sample != 0x102u) {
+            return 2;
+        }
+    }
+    {
+        static const uint8_t kFrame[] = {0xDE, 0xFD};
+        struct BitsMessage message = {0};
+
+        if (parseBits(kFrame, (int)sizeof(kFrame), &message) != (int)sizeof(kFrame)) {
+            return 3;
+        }
+
+        if (message.t != 3567u) {
+            return 3;
+        }
+
+        if (message.rest != 13u) {
+            return 3;
+        }
+    }
+
+    return 0;
+}
+// endregion Self-test
@@ -0,0 +1,111 @@
+#ifndef ACME_TIMESTAMPS_H
+#define ACME_TIMESTAMPS_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region Telemetry types
+// =============================================================================
+
+struct TelemetryMessage {
+    uint8_t sync[1];
+    uint32_t syncLength;
+    /** Seconds since 2000 */
+    uint32_t uptime;
+    uint16_t sample;
+};
+struct TelemetryParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+// endregion Telemetry types
+
+// =============================================================================
+// region Bits types
+// =============================================================================
+
+struct BitsMessage {
+    uint16_t t;
+    uint8_t rest;
+};
+// endregion Bits types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct TelemetryMessage Telemetry;
+    struct BitsMessage Bits;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_TELEMETRY_MIN_FRAME_SIZE 7u
+#define ROBUSTO_TELEMETRY_MAX_FRAME_SIZE 7u
+#define ROBUSTO_BITS_MIN_FRAME_SIZE 2u
+#define ROBUSTO_BITS_MAX_FRAME_SIZE 2u
+#define ROBUSTO_MIN_FRAME_SIZE 2u
+#define ROBUSTO_MAX_FRAME_SIZE 7u
+// endregion Sizing constants
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineTelemetryParserStateInit(struct TelemetryParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aTelemetry
+enum RobustoParseStatus parseTelemetry(struct TelemetryParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct TelemetryMessage *aTelemetry, int *aConsumedLength);
+// Non-null: aParserState
+int isTelemetryAccepted(const struct TelemetryParserState *aParserState);
+// Non-null: aTelemetry
+int64_t getTelemetryUptime_ms(const struct TelemetryMessage *aTelemetry);
+// Non-null: aTelemetry
+void setTelemetryUptime_ms(struct TelemetryMessage *aTelemetry, int64_t aMilliseconds);
+// Non-null: aTelemetry
+int64_t getTelemetrySample_ms(const struct TelemetryMessage *aTelemetry);
+// Non-null: aTelemetry
+void setTelemetrySample_ms(struct TelemetryMessage *aTelemetry, int64_t aMilliseconds);
+// Non-null: aInputBuffer, aBits
+int parseBits(const uint8_t *aInputBuffer, int aInputBufferLength, struct BitsMessage *aBits);
+// Non-null: aBits
+int64_t getBitsT_ms(const struct BitsMessage *aBits);
+// Non-null: aBits
+void setBitsT_ms(struct BitsMessage *aBits, int64_t aMilliseconds);
+int selfTest(void);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // ACME_TIMESTAMPS_H
@@ -0,0 +1,474 @@
+#include "output.h"
+
+// =============================================================================
+// region Checksums
+// =============================================================================
+
+static const uint16_t kRobustoCrc16_8005ReflectedTable[256] = {
+    0x0000u, 0xC0C1u, 0xC181u, 0x0140u, 0xC301u, 0x03C0u, 0x0280u, 0xC241u,
+    0xC601u, 0x06C0u, 0x0780u, 0xC741u, 0x0500u, 0xC5C1u, 0xC481u, 0x0440u,
+    0xCC01u, 0x0CC0u, 0x0D80u, 0xCD41u, 0x0F00u, 0xCFC1u, 0xCE81u, 0x0E40u,
+    0x0A00u, 0xCAC1u, 0xCB81u, 0x0B40u, 0xC901u, 0x09C0u, 0x0880u, 0xC841u,
+    0xD801u, 0x18C0u, 0x1980u, 0xD941u, 0x1B00u, 0xDBC1u, 0xDA81u, 0x1A40u,
+    0x1E00u, 0xDEC1u, 0xDF81u, 0x1F40u, 0xDD01u, 0x1DC0u, 0x1C80u, 0xDC41u,
+    0x1400u, 0xD4C1u, 0xD581u, 0x1540u, 0xD701u, 0x17C0u, 0x1680u, 0xD641u,
+    0xD201u, 0x12C0u, 0x1380u, 0xD341u, 0x1100u, 0xD1C1u, 0xD081u, 0x1040u,
+    0xF001u, 0x30C0u, 0x3180u, 0xF141u, 0x3300u, 0xF3C1u, 0xF281u, 0x3240u,
+    0x3600u, 0xF6C1u, 0xF781u, 0x3740u, 0xF501u, 0x35C0u, 0x3480u, 0xF441u,
+    0x3C00u, 0xFCC1u, 0xFD81u, 0x3D40u, 0xFF01u, 0x3FC0u, 0x3E80u, 0xFE41u,
+    0xFA01u, 0x3AC0u, 0x3B80u, 0xFB41u, 0x3900u, 0xF9C1u, 0xF881u, 0x3840u,
+    0x2800u, 0xE8C1u, 0xE981u, 0x2940u, 0xEB01u, 0x2BC0u, 0x2A80u, 0xEA41u,
+    0xEE01u, 0x2EC0u, 0x2F80u, 0xEF41u, 0x2D00u, 0xEDC1u, 0xEC81u, 0x2C40u,
+    0xE401u, 0x24C0u, 0x2580u, 0xE541u, 0x2700u, 0xE7C1u, 0xE681u, 0x2640u,
+    0x2200u, 0xE2C1u, 0xE381u, 0x2340u, 0xE101u, 0x21C0u, 0x2080u, 0xE041u,
+    0xA001u, 0x60C0u, 0x6180u, 0xA141u, 0x6300u, 0xA3C1u, 0xA281u, 0x6240u,
+    0x6600u, 0xA6C1u, 0xA781u, 0x6740u, 0xA501u, 0x65C0u, 0x6480u, 0xA441u,
+    0x6C00u, 0xACC1u, 0xAD81u, 0x6D40u, 0xAF01u, 0x6FC0u, 0x6E80u, 0xAE41u,
+    0xAA01u, 0x6AC0u, 0x6B80u, 0xAB41u, 0x6900u, 0xA9C1u, 0xA881u, 0x6840u,
+    0x7800u, 0xB8C1u, 0xB981u, 0x7940u, 0xBB01u, 0x7BC0u, 0x7A80u, 0xBA41u,
+    0xBE01u, 0x7EC0u, 0x7F80u, 0xBF41u, 0x7D00u, 0xBDC1u, 0xBC81u, 0x7C40u,
+    0xB401u, 0x74C0u, 0x7580u, 0xB541u, 0x7700u, 0xB7C1u, 0xB681u, 0x7640u,
+    0x7200u, 0xB2C1u, 0xB381u, 0x7340u, 0xB101u, 0x71C0u, 0x7080u, 0xB041u,
+    0x5000u, 0x90C1u, 0x9181u, 0x5140u, 0x9301u, 0x53C0u, 0x5280u, 0x9241u,
+    0x9601u, 0x56C0u, 0x5780u, 0x9741u, 0x5500u, 0x95C1u, 0x9481u, 0x5440u,
+    0x9C01u, 0x5CC0u, 0x5D80u, 0x9D41u, 0x5F00u, 0x9FC1u, 0x9E81u, 0x5E40u,
+    0x5A00u, 0x9AC1u, 0x9B81u, 0x5B40u, 0x9901u, 0x59C0u, 0x5880u, 0x9841u,
+    0x8801u, 0x48C0u, 0x4980u, 0x8941u, 0x4B00u, 0x8BC1u, 0x8A81u, 0x4A40u,
+    0x4E00u, 0x8EC1u, 0x8F81u, 0x4F40u, 0x8D01u, 0x4DC0u, 0x4C80u, 0x8C41u,
+    0x4400u, 0x84C1u, 0x8581u, 0x4540u, 0x8701u, 0x47C0u, 0x4680u, 0x8641u,
+    0x8201u, 0x42C0u, 0x4380u, 0x8341u, 0x4100u, 0x81C1u, 0x8081u, 0x4040u,
+};
+
+uint16_t robustoCrc16_8005Reflected(uint16_t aCrc, uint8_t aByte)
+{
+    return (uint16_t)((aCrc >> 8) ^ kRobustoCrc16_8005ReflectedTable[(aCrc ^ aByte) & 0xFFu]);
+}
+// endregion Checksums
+
+// =============================================================================
+// region Reading parser
+// =============================================================================
+
+%%{
+    machine Reading;
+    write data;
+}%%
+int testReadingState(const struct ReadingMessage *aReading, uint8_t aFlags)
+{
+    return (aReading->state & aFlags) == aFlags;
+}
+void setReadingState(struct ReadingMessage *aReading, uint8_t aFlags, int aValue)
+{
+    if (aValue) {
+        aReading->state = (uint8_t)(aReading->state | aFlags);
+    } else {
+        aReading->state = (uint8_t)(aReading->state & ~aFlags);
+    }
+}
+void machineReadingParserStateInit(struct ReadingParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->lengthCovered = 0;
+    aParserState->frameChecksum = 0x0000u;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Reading;
+    access aParserState->;
+    alphtype unsigned char;
+    action start {
+    }
+    action start_reset {
+        aReading->startLength = 0;
+    }
+    action start_byte {
+        if (aReading->startLength < 1u) {
+            aReading->start[aReading->startLength++] = (uint8_t)fc;
+        }
+    }
+    action length {
+    }
+    action length_count {
+        ++aParserState->lengthCovered;
+    }
+    action length_check {
+        if (aReading->length != aParserState->lengthCovered) {
+            aParserState->error = ROBUSTO_PARSE_ERROR_LENGTH;
+            fgoto *Reading_error;
+        }
+    }
+    action length_byte {
+        aReading->length = (uint8_t)(((uint8_t)aReading->length << 8) | (uint8_t)fc);
+    }
+    action kind {
+    }
+    action kind_byte {
+        aReading->kind = (uint8_t)(((uint8_t)aReading->kind << 8) | (uint8_t)fc);
+    }
+    action temperature {
+    }
+    action temperature_when {
+        ((int64_t)aReading->kind == INT64_C(1))
+    }
+    action temperature_byte {
+        aReading->kindVariant.temperature = (int16_t)(((uint16_t)aReading->kindVariant.temperature << 8) | (uint8_t)fc);
+    }
+    action humidity {
+    }
+    action humidity_when {
+        ((int64_t)aReading->kind == INT64_C(2))
+    }
+    action humidity_byte {
+        aReading->kindVariant.humidity = (uint8_t)(((uint8_t)aReading->kindVariant.humidity << 8) | (uint8_t)fc);
+    }
+    action state {
+    }
+    action state_byte {
+        aReading->state = (uint8_t)(((uint8_t)aReading->state << 8) | (uint8_t)fc);
+    }
+    action spare {
+    }
+    action offset {
+        aReading->offset = (int32_t)(((uint32_t)aReading->offset >> 1) ^ ((uint32_t)0 - ((uint32_t)aReading->offset & 1u)));
+    }
+    action offset_reset {
+        aReading->offset = 0;
+        aParserState->varintShift = 0;
+    }
+    action offset_byte {
+        aReading->offset = (int32_t)((uint32_t)aReading->offset | ((uint32_t)(fc & 0x7Fu) << aParserState->varintShift));
+        aParserState->varintShift += 7;
+    }
+    action counter {
+        aReading->counter = (uint32_t)(((uint32_t)aReading->counter & 0xFFFFFFu));
+        if (aReading->counter < 1u || aReading->counter > 1000000u) {
+            aParserState->error = ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE;
+            fgoto *Reading_error;
+        }
+    }
+    action counter_reset {
+        aReading->counter = 0;
+        aParserState->varintShift = 0;
+    }
+    action counter_byte {
+        aReading->counter = (uint32_t)((uint32_t)aReading->counter | ((uint32_t)(fc & 0x7Fu) << aParserState->varintShift));
+        aParserState->varintShift += 7;
+    }
+    action samples {
+    }
+    action samples_reset {
+        aParserState->samplesIndex = 0;
+    }
+    action samples_next {
+        ++aParserState->samplesIndex;
+    }
+    action samples_channel_byte {
+        aReading->samples[aParserState->samplesIndex].channel = (uint8_t)(((uint8_t)aReading->samples[aParserState->samplesIndex].channel << 8) | (uint8_t)fc);
+    }
+    action samples_value_byte {
+        aReading->samples[aParserState->samplesIndex].value = (int32_t)((((((uint32_t)aReading->samples[aParserState->samplesIndex].value << 8) | (uint8_t)fc) & 0xFFFFFFu) ^ 0x800000u) - 0x800000u);
+    }
+    action crc {
+        if (aReading->crc != (uint16_t)(aParserState->frameChecksum ^ 0xFFFFu)) {
+            aParserState->error = ROBUSTO_PARSE_ERROR_CHECKSUM;
+            fgoto *Reading_error;
+        }
+    }
+    action frame_checksum {
+        aParserState->frameChecksum = robustoCrc16_8005Reflected(aParserState->frameChecksum, (uint8_t)fc);
+    }
+    action crc_byte {
+        aReading->crc = (uint16_t)(((uint16_t)aReading->crc << 8) | (uint8_t)fc);
+    }
+    start = 'U' >start_reset $start_byte @start; 
+    length = any{1} $length_byte @length; 
+    kind = any{1} $kind_byte @kind; 
+    temperature = ( any{2} $temperature_byte @temperature ) when temperature_when; 
+    humidity = ( any{1} $humidity_byte @humidity ) when humidity_when; 
+    state = any{1} $state_byte @state; 
+    spare = any{2} @spare; 
+    offset = ( ( 0x80..0xFF ){,4} 0x00..0x7F ) >offset_reset $offset_byte @offset; 
+    counter = ( ( 0x80..0xFF ){,3} 0x00..0x7F ) >counter_reset $counter_byte @counter; 
+    samples = ( any{1} $samples_channel_byte any{3} $samples_value_byte @samples_next ){2} >samples_reset @samples;
+    crc = any{2} $crc_byte @crc; 
+    main := ( any* :>> ( 0x7E ) ) start ( length $frame_checksum ) ( kind $frame_checksum $length_count ) ( ( temperature $frame_checksum $length_count ) | ( humidity $frame_checksum $length_count ) ) ( state $frame_checksum $length_count ) ( spare $frame_checksum $length_count ) ( offset $frame_checksum $length_count ) ( counter $frame_checksum $length_count ) ( samples $frame_checksum $length_count @length_check ) crc;
+}%%
+static enum RobustoParseStatus robustoParseStatusReading(const struct ReadingParserState *aParserState)
+{
+    if (aParserState->cs >= Reading_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Reading_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseReading(struct ReadingParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct ReadingMessage *aReading, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineReadingParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    for (;;) {
+        %% write exec;
+
+        if (aParserState->cs != Reading_error || p == pe) {
+            break;
+        }
+
+        // Resynchronize on the rest of the input
+        machineReadingParserStateInit(aParserState);
+    }
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusReading(aParserState);
+}
+int isReadingAccepted(const struct ReadingParserState *aParserState)
+{
+    return aParserState->cs >= Reading_first_final;
+}
+// endregion Reading parser
+
+// =============================================================================
+// region Heartbeat parser
+// =============================================================================
+
+%%{
+    machine Heartbeat;
+    write data;
+}%%
+void machineHeartbeatParserStateInit(struct HeartbeatParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Heartbeat;
+    access aParserState->;
+    alphtype unsigned char;
+    action id {
+    }
+    action id_byte {
+        aHeartbeat->id = (uint8_t)(((uint8_t)aHeartbeat->id << 8) | (uint8_t)fc);
+    }
+    action uptime {
+    }
+    action uptime_byte {
+        aHeartbeat->uptime = (uint64_t)(((uint64_t)aHeartbeat->uptime << 8) | (uint8_t)fc);
+    }
+    action drift {
+        aHeartbeat->drift = (int64_t)(((uint64_t)aHeartbeat->drift >> 1) ^ ((uint64_t)0 - ((uint64_t)aHeartbeat->drift & 1u)));
+    }
+    action drift_reset {
+        aHeartbeat->drift = 0;
+        aParserState->varintShift = 0;
+    }
+    action drift_byte {
+        aHeartbeat->drift = (int64_t)((uint64_t)aHeartbeat->drift | ((uint64_t)(fc & 0x7Fu) << aParserState->varintShift));
+        aParserState->varintShift += 7;
+    }
+    id = any{1} $id_byte @id; 
+    uptime = any{8} $uptime_byte @uptime; 
+    drift = ( ( 0x80..0xFF ){,9} 0x00..0x7F ) >drift_reset $drift_byte @drift; 
+    main := id uptime drift;
+}%%
+static enum RobustoParseStatus robustoParseStatusHeartbeat(const struct HeartbeatParserState *aParserState)
+{
+    if (aParserState->cs >= Heartbeat_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Heartbeat_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseHeartbeat(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineHeartbeatParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusHeartbeat(aParserState);
+}
+int isHeartbeatAccepted(const struct HeartbeatParserState *aParserState)
+{
+    return aParserState->cs >= Heartbeat_first_final;
+}
+// endregion Heartbeat parser
+
+// =============================================================================
+// region Log parser
+// =============================================================================
+
+%%{
+    machine Log;
+    write data;
+}%%
+void machineLogParserStateInit(struct LogParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Log;
+    access aParserState->;
+    alphtype unsigned char;
+    action text {
+    }
+    action text_reset {
+        aLog->textLength = 0;
+    }
+    action text_byte {
+        if (aLog->textLength < 16u) {
+            aLog->text[aLog->textLength++] = (uint8_t)fc;
+        }
+    }
+    text = ( 'a'..'z' )+ >text_reset $text_byte @text; 
+    main := text;
+}%%
+static enum RobustoParseStatus robustoParseStatusLog(const struct LogParserState *aParserState)
+{
+    if (aParserState->cs >= Log_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Log_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseLog(struct LogParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct LogMessage *aLog, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineLogParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusLog(aParserState);
+}
+int isLogAccepted(const struct LogParserState *aParserState)
+{
+    return aParserState->cs >= Log_first_final;
+}
+// endregion Log parser
+
+// =============================================================================
+// region Dispatcher
+// =============================================================================
+
+void robustoDispatcherInit(struct RobustoDispatcherState *aState)
+{
+    aState->id = 0u;
+    aState->idLength = 0u;
+    aState->selected = ROBUSTO_DISPATCH_PENDING;
+    aState->accepted = 0u;
+}
+
+void parseAny(struct RobustoDispatcherState *aState, const char *aInputBuffer, int aInputBufferLength, const struct RobustoDispatchCallbacks *aCallbacks)
+{
+    while (aState->selected == ROBUSTO_DISPATCH_PENDING && aInputBufferLength > 0) {
+        aState->idBuffer[aState->idLength] = *aInputBuffer;
+        aState->id = (uint8_t)((aState->id << 8) | (uint8_t)*aInputBuffer);
+        ++aState->idLength;
+        ++aInputBuffer;
+        --aInputBufferLength;
+
+        if (aState->idLength < ROBUSTO_DISPATCH_ID_LENGTH) {
+            continue;
+        }
+
+        switch (aState->id) {
+            case ROBUSTO_HEARTBEAT_ID:
+                aState->selected = 1u;
+                machineHeartbeatParserStateInit(&aState->parserState.Heartbeat);
+                parseHeartbeat(&aState->parserState.Heartbeat, aState->idBuffer, ROBUSTO_DISPATCH_ID_LENGTH, &aState->message.Heartbeat, 0);
+                break;
+            default:
+                aState->selected = ROBUSTO_DISPATCH_UNKNOWN;
+                break;
+        }
+    }
+
+    switch (aState->selected) {
+        case 1u:
+            if (aInputBufferLength > 0) {
+                parseHeartbeat(&aState->parserState.Heartbeat, aInputBuffer, aInputBufferLength, &aState->message.Heartbeat, 0);
+            }
+
+            if (!aState->accepted && isHeartbeatAccepted(&aState->parserState.Heartbeat)) {
+                aState->accepted = 1u;
+
+                if (aCallbacks->onHeartbeat != 0) {
+                    aCallbacks->onHeartbeat(&aState->message.Heartbeat, aCallbacks->context);
+                }
+            }
+            break;
+        default:
+            break;
+    }
+}
+// endregion Dispatcher
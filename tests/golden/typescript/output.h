@@ -0,0 +1,201 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region Reading types
+// =============================================================================
+
+struct ReadingSamplesGroup {
+    uint8_t channel;
+    int32_t value;
+};
+/** Periodic measurements of a sensor node */
+#define READING_STATE_FLAG_READY (1u << 0)
+#define READING_STATE_FLAG_FAULT (1u << 7)
+struct ReadingMessage {
+    uint8_t start[1];
+    uint32_t startLength;
+    uint8_t length;
+    /** Selects the measurement the frame carries */
+    uint8_t kind;
+    union {
+        int16_t temperature;
+        uint8_t humidity;
+    } kindVariant;
+    uint8_t state;
+    int32_t offset;
+    uint32_t counter;
+    struct ReadingSamplesGroup samples[2];
+    uint16_t crc;
+};
+struct ReadingParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint32_t samplesIndex;
+    uint8_t varintShift;
+    uint16_t frameChecksum;
+    uint32_t lengthCovered;
+};
+// endregion Reading types
+
+// =============================================================================
+// region Heartbeat types
+// =============================================================================
+
+struct HeartbeatMessage {
+    uint8_t id;
+    /** Milliseconds since boot */
+    uint64_t uptime;
+    int64_t drift;
+};
+struct HeartbeatParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint8_t varintShift;
+};
+// endregion Heartbeat types
+
+// =============================================================================
+// region Log types
+// =============================================================================
+
+struct LogMessage {
+    uint8_t text[16];
+    uint32_t textLength;
+};
+struct LogParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+// endregion Log types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct ReadingMessage Reading;
+    struct HeartbeatMessage Heartbeat;
+    struct LogMessage Log;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_READING_MIN_FRAME_SIZE 20u
+#define ROBUSTO_READING_MAX_FRAME_SIZE 28u
+#define ROBUSTO_HEARTBEAT_MIN_FRAME_SIZE 10u
+#define ROBUSTO_HEARTBEAT_MAX_FRAME_SIZE 19u
+#define ROBUSTO_LOG_MIN_FRAME_SIZE 1u
+#define ROBUSTO_LOG_MAX_FRAME_SIZE 16u
+#define ROBUSTO_MIN_FRAME_SIZE 1u
+#define ROBUSTO_MAX_FRAME_SIZE 28u
+// endregion Sizing constants
+
+// =============================================================================
+// region Dispatcher
+// =============================================================================
+
+#define ROBUSTO_DISPATCH_ID_LENGTH 1u
+#define ROBUSTO_DISPATCH_PENDING 0u
+#define ROBUSTO_DISPATCH_UNKNOWN 255u
+#define ROBUSTO_HEARTBEAT_ID 2u
+
+enum RobustoMessageId {
+    ROBUSTO_MESSAGE_ID_HEARTBEAT = ROBUSTO_HEARTBEAT_ID,
+};
+
+// Each callback is invoked once its message's frame is accepted, and may be NULL
+struct RobustoDispatchCallbacks {
+    void *context;
+    void (*onHeartbeat)(const struct HeartbeatMessage *aHeartbeat, void *aContext);
+};
+
+union RobustoDispatchParserState {
+    struct HeartbeatParserState Heartbeat;
+};
+
+// `selected` is the 1-based index of the message being parsed, `ROBUSTO_DISPATCH_PENDING` while
+// the ID is incomplete, or `ROBUSTO_DISPATCH_UNKNOWN` if no message has the ID
+struct RobustoDispatcherState {
+    uint8_t id;
+    char idBuffer[ROBUSTO_DISPATCH_ID_LENGTH];
+    uint8_t idLength;
+    uint8_t selected;
+    uint8_t accepted;
+    union RobustoDispatchParserState parserState;
+    union RobustoAnyMessage message;
+};
+// endregion Dispatcher
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineReadingParserStateInit(struct ReadingParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aReading
+enum RobustoParseStatus parseReading(struct ReadingParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct ReadingMessage *aReading, int *aConsumedLength);
+// Non-null: aParserState
+int isReadingAccepted(const struct ReadingParserState *aParserState);
+// Non-null: aReading
+int testReadingState(const struct ReadingMessage *aReading, uint8_t aFlags);
+// Non-null: aReading
+void setReadingState(struct ReadingMessage *aReading, uint8_t aFlags, int aValue);
+// Non-null: aParserState
+void machineHeartbeatParserStateInit(struct HeartbeatParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aHeartbeat
+enum RobustoParseStatus parseHeartbeat(struct HeartbeatParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct HeartbeatMessage *aHeartbeat, int *aConsumedLength);
+// Non-null: aParserState
+int isHeartbeatAccepted(const struct HeartbeatParserState *aParserState);
+// Non-null: aParserState
+void machineLogParserStateInit(struct LogParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aLog
+enum RobustoParseStatus parseLog(struct LogParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct LogMessage *aLog, int *aConsumedLength);
+// Non-null: aParserState
+int isLogAccepted(const struct LogParserState *aParserState);
+uint16_t robustoCrc16_8005Reflected(uint16_t aCrc, uint8_t aByte);
+// Non-null: aState
+void robustoDispatcherInit(struct RobustoDispatcherState *aState);
+// Non-null: aState, aInputBuffer, aCallbacks
+void parseAny(struct RobustoDispatcherState *aState, const char *aInputBuffer, int aInputBufferLength, const struct RobustoDispatchCallbacks *aCallbacks);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
@@ -0,0 +1,414 @@
+/** Outcome of parsing a frame, as the C parsers report it */
+export enum RobustoParseStatus {
+    /** frame complete */
+    Ok = 0,
+    /** frame incomplete */
+    Incomplete = 1,
+    /** frame rejected on a checksum mismatch */
+    ErrorCrc = 2,
+    /** frame rejected on a field value out of range */
+    ErrorRange = 3,
+    /** frame rejected on a field longer than its max length */
+    ErrorOverflow = 4,
+    /** frame rejected */
+    Error = 5,
+}
+
+export interface RobustoParseResult<T> {
+    status: RobustoParseStatus;
+
+    /** The message, if the status is `Ok` */
+    message?: T;
+
+    /** Bytes of the input up to the end of the frame, if the status is `Ok` */
+    length: number;
+}
+
+/** Ends parsing with the status */
+class RobustoParseFailure {
+    readonly status: RobustoParseStatus;
+
+    constructor(status: RobustoParseStatus) {
+        this.status = status;
+    }
+}
+
+/** CRC of the bytes, computed as the C routines compute it */
+function robustoCrc(
+    data: ArrayLike<number>,
+    start: number,
+    end: number,
+    width: number,
+    polynomial: bigint,
+    init: bigint,
+    reflected: boolean,
+    xorOut: bigint,
+): bigint {
+    let crc = init;
+    let reflectedPolynomial = 0n;
+
+    for (let bit = 0; bit < width; ++bit) {
+        reflectedPolynomial |= ((polynomial >> BigInt(bit)) & 1n) << BigInt(width - 1 - bit);
+    }
+
+    for (let i = start; i < end; ++i) {
+        if (reflected) {
+            crc ^= BigInt(data[i]);
+
+            for (let bit = 0; bit < 8; ++bit) {
+                crc = (crc & 1n) !== 0n ? (crc >> 1n) ^ reflectedPolynomial : crc >> 1n;
+            }
+        } else {
+            crc ^= BigInt(data[i]) << BigInt(width - 8);
+
+            for (let bit = 0; bit < 8; ++bit) {
+                const msb = crc >> BigInt(width - 1);
+                crc = BigInt.asUintN(width, msb !== 0n ? (crc << 1n) ^ polynomial : crc << 1n);
+            }
+        }
+    }
+
+    return crc ^ xorOut;
+}
+
+/** Zigzag encoding of a signed varint's value */
+function robustoZigzag(value: bigint): bigint {
+    return value < 0n ? ~(value << 1n) : value << 1n;
+}
+
+/** Reads the fields of a frame off the input, throwing `RobustoParseFailure` */
+class RobustoFrameReader {
+    readonly input: Uint8Array;
+    offset = 0;
+
+    /** The input as Latin-1 characters, which regex fields are matched against */
+    private text?: string;
+
+    constructor(input: Uint8Array) {
+        this.input = input;
+    }
+
+    /** Skips the input preceding the first occurrence of the sequence */
+    synchronize(sequence: number[]): void {
+        for (let start = 0; start + sequence.length <= this.input.length; ++start) {
+            if (sequence.every((byte, i) => this.input[start + i] === byte)) {
+                this.offset = start;
+                return;
+            }
+        }
+
+        throw new RobustoParseFailure(RobustoParseStatus.Incomplete);
+    }
+
+    /** Takes the bytes, returning the offset of the first one */
+    skip(length: number): number {
+        if (this.offset + length > this.input.length) {
+            throw new RobustoParseFailure(RobustoParseStatus.Incomplete);
+        }
+
+        this.offset += length;
+
+        return this.offset - length;
+    }
+
+    constant(bytes: number[]): void {
+        const start = this.skip(bytes.length);
+
+        if (!bytes.every((byte, i) => this.input[start + i] === byte)) {
+            throw new RobustoParseFailure(RobustoParseStatus.Error);
+        }
+    }
+
+    /** Reads a big-endian unsigned integer */
+    integer(length: number, offset = this.skip(length)): bigint {
+        let value = 0n;
+
+        for (let i = 0; i < length; ++i) {
+            value = (value << 8n) | BigInt(this.input[offset + i]);
+        }
+
+        return value;
+    }
+
+    /** Reads a LEB128 varint of at most the given length */
+    varint(maxLength: number): bigint {
+        let value = 0n;
+
+        for (let i = 0; i < maxLength; ++i) {
+            const byte = this.input[this.skip(1)];
+            value |= BigInt(byte & 0x7F) << BigInt(7 * i);
+
+            if ((byte & 0x80) === 0) {
+                return value;
+            }
+        }
+
+        throw new RobustoParseFailure(RobustoParseStatus.Error);
+    }
+
+    range(value: bigint, min: bigint, max: bigint): void {
+        if (value < min || value > max) {
+            throw new RobustoParseFailure(RobustoParseStatus.ErrorRange);
+        }
+    }
+
+    /** Compares the length field at the offset with the bytes between the marks */
+    length(offset: number, length: number, start: number, end: number): void {
+        if (this.integer(length, offset) !== BigInt(end - start)) {
+            throw new RobustoParseFailure(RobustoParseStatus.Error);
+        }
+    }
+
+    checksum(length: number, crc: bigint): void {
+        if (this.integer(length) !== crc) {
+            throw new RobustoParseFailure(RobustoParseStatus.ErrorCrc);
+        }
+    }
+
+    /** Reads a regex field, keeping `maxLength` bytes of it. Input matching `prefixes` is cut off */
+    pattern(expression: RegExp, prefixes: RegExp, maxLength: number, truncate: boolean): Uint8Array {
+        if (this.text === undefined) {
+            this.text = Array.from(this.input, (byte) => String.fromCharCode(byte)).join("");
+        }
+
+        expression.lastIndex = this.offset;
+        prefixes.lastIndex = this.offset;
+
+        const match = expression.exec(this.text);
+
+        if (match === null) {
+            throw new RobustoParseFailure(
+                prefixes.test(this.text) ? RobustoParseStatus.Incomplete : RobustoParseStatus.Error,
+            );
+        }
+
+        if (match[0].length > maxLength && !truncate) {
+            throw new RobustoParseFailure(RobustoParseStatus.ErrorOverflow);
+        }
+
+        const start = this.skip(match[0].length);
+
+        return this.input.slice(start, start + Math.min(match[0].length, maxLength));
+    }
+}
+
+/** Composes a frame */
+class RobustoFrameWriter {
+    readonly bytes: number[] = [];
+
+    get offset(): number {
+        return this.bytes.length;
+    }
+
+    constant(bytes: number[]): void {
+        this.bytes.push(...bytes);
+    }
+
+    zeros(length: number): void {
+        for (let i = 0; i < length; ++i) {
+            this.bytes.push(0);
+        }
+    }
+
+    /** Writes the value big-endian, at the offset, or at the end of the frame */
+    integer(value: bigint, length: number, offset = this.bytes.length): void {
+        for (let i = 0; i < length; ++i) {
+            const shift = BigInt(8 * (length - 1 - i));
+            this.bytes[offset + i] = Number(BigInt.asUintN(8, value >> shift));
+        }
+    }
+
+    varint(value: bigint): void {
+        do {
+            const byte = Number(value & 0x7Fn);
+            value >>= 7n;
+            this.bytes.push(value !== 0n ? byte | 0x80 : byte);
+        } while (value !== 0n);
+    }
+
+    pattern(bytes: Uint8Array, maxLength: number): void {
+        this.bytes.push(...bytes.subarray(0, maxLength));
+    }
+}
+
+export interface ReadingSamplesGroup {
+    channel: number;
+    value: number;
+}
+
+/** Periodic measurements of a sensor node */
+export interface ReadingMessage {
+    /** Selects the measurement the frame carries */
+    kind: number;
+    temperature?: number;
+    humidity?: number;
+    state: number;
+    offset: number;
+    counter: number;
+    samples: ReadingSamplesGroup[];
+}
+
+export function parseReading(input: Uint8Array): RobustoParseResult<ReadingMessage> {
+    const reader = new RobustoFrameReader(input);
+    const message = {} as ReadingMessage;
+    let frameChecksumStart = 0;
+    let lengthStart = 0;
+    let kindStart = 0;
+    let frameChecksumEnd = 0;
+    let samplesEnd = 0;
+
+    try {
+        reader.synchronize([0x7E]);
+        reader.constant([0x7E]);
+        reader.constant([0x55]);
+        frameChecksumStart = reader.offset;
+        lengthStart = reader.offset;
+        reader.skip(1);
+        kindStart = reader.offset;
+        message.kind = Number(reader.integer(1));
+        if (message.kind === 1) {
+            message.temperature = Number(BigInt.asIntN(16, reader.integer(2)));
+        } else if (message.kind === 2) {
+            message.humidity = Number(reader.integer(1));
+        }
+        message.state = Number(reader.integer(1));
+        reader.skip(2);
+        {
+            const varint = reader.varint(5);
+            message.offset = Number(BigInt.asIntN(32, (varint >> 1n) ^ -(varint & 1n)));
+        }
+        {
+            const varint = reader.varint(4);
+            const counterValue = BigInt.asUintN(24, varint);
+            reader.range(counterValue, 1n, 1000000n);
+            message.counter = Number(counterValue);
+        }
+        message.samples = [];
+
+        for (let i = 0; i < 2; ++i) {
+            const samplesItem = {} as ReadingSamplesGroup;
+            samplesItem.channel = Number(reader.integer(1));
+            samplesItem.value = Number(BigInt.asIntN(24, reader.integer(3)));
+            message.samples.push(samplesItem);
+        }
+        frameChecksumEnd = reader.offset;
+        samplesEnd = reader.offset;
+        reader.length(lengthStart, 1, kindStart, samplesEnd);
+        reader.checksum(2, robustoCrc(input, frameChecksumStart, frameChecksumEnd, 16, 0x8005n, 0x0n, true, 0xFFFFn));
+    } catch (error) {
+        if (error instanceof RobustoParseFailure) {
+            return { status: error.status, length: 0 };
+        }
+
+        throw error;
+    }
+
+    return { status: RobustoParseStatus.Ok, message, length: reader.offset };
+}
+
+export function serializeReading(message: ReadingMessage): Uint8Array {
+    const writer = new RobustoFrameWriter();
+    let frameChecksumStart = 0;
+    let lengthStart = 0;
+    let kindStart = 0;
+    let frameChecksumEnd = 0;
+    let samplesEnd = 0;
+
+    writer.constant([0x7E]);
+    writer.constant([0x55]);
+    frameChecksumStart = writer.offset;
+    lengthStart = writer.offset;
+    writer.zeros(1);
+    kindStart = writer.offset;
+    writer.integer(BigInt(message.kind), 1);
+    if (message.kind === 1) {
+        writer.integer(BigInt(message.temperature ?? 0), 2);
+    } else if (message.kind === 2) {
+        writer.integer(BigInt(message.humidity ?? 0), 1);
+    }
+    writer.integer(BigInt(message.state), 1);
+    writer.zeros(2);
+    writer.varint(BigInt.asUintN(32, robustoZigzag(BigInt(message.offset))));
+    writer.varint(BigInt.asUintN(24, BigInt(message.counter)));
+    if (message.samples.length !== 2) {
+        throw new RangeError("samples takes 2 repetitions");
+    }
+
+    for (const samplesItem of message.samples) {
+        writer.integer(BigInt(samplesItem.channel), 1);
+        writer.integer(BigInt(samplesItem.value), 3);
+    }
+    frameChecksumEnd = writer.offset;
+    samplesEnd = writer.offset;
+    writer.integer(BigInt(samplesEnd - kindStart), 1, lengthStart);
+    writer.integer(robustoCrc(writer.bytes, frameChecksumStart, frameChecksumEnd, 16, 0x8005n, 0x0n, true, 0xFFFFn), 2);
+
+    return Uint8Array.from(writer.bytes);
+}
+
+export interface HeartbeatMessage {
+    /** Milliseconds since boot */
+    uptime: bigint;
+    drift: bigint;
+}
+
+export function parseHeartbeat(input: Uint8Array): RobustoParseResult<HeartbeatMessage> {
+    const reader = new RobustoFrameReader(input);
+    const message = {} as HeartbeatMessage;
+
+    try {
+        reader.constant([0x02]);
+        message.uptime = reader.integer(8);
+        {
+            const varint = reader.varint(10);
+            message.drift = BigInt.asIntN(64, (varint >> 1n) ^ -(varint & 1n));
+        }
+    } catch (error) {
+        if (error instanceof RobustoParseFailure) {
+            return { status: error.status, length: 0 };
+        }
+
+        throw error;
+    }
+
+    return { status: RobustoParseStatus.Ok, message, length: reader.offset };
+}
+
+export function serializeHeartbeat(message: HeartbeatMessage): Uint8Array {
+    const writer = new RobustoFrameWriter();
+
+    writer.constant([0x02]);
+    writer.integer(message.uptime, 8);
+    writer.varint(BigInt.asUintN(64, robustoZigzag(message.drift)));
+
+    return Uint8Array.from(writer.bytes);
+}
+
+export interface LogMessage {
+    text: Uint8Array;
+}
+
+export function parseLog(input: Uint8Array): RobustoParseResult<LogMessage> {
+    const reader = new RobustoFrameReader(input);
+    const message = {} as LogMessage;
+
+    try {
+        message.text = reader.pattern(/[a-z]+/y, /(?:[a-z]*(?:[a-z]|))$/y, 16, true);
+    } catch (error) {
+        if (error instanceof RobustoParseFailure) {
+            return { status: error.status, length: 0 };
+        }
+
+        throw error;
+    }
+
+    return { status: RobustoParseStatus.Ok, message, length: reader.offset };
+}
+
+export function serializeLog(message: LogMessage): Uint8Array {
+    const writer = new RobustoFrameWriter();
+
+    writer.pattern(message.text, 16);
+
+    return Uint8Array.from(writer.bytes);
+}
@@ -0,0 +1,244 @@
+#include "output.h"
+
+// =============================================================================
+// region Status parser
+// =============================================================================
+
+%%{
+    machine Status;
+    write data;
+}%%
+void machineStatusParserStateInit(struct StatusParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Status;
+    access aParserState->;
+    alphtype unsigned char;
+    action id {
+    }
+    action id_byte {
+        aStatus->id = (uint8_t)(((uint8_t)aStatus->id << 8) | (uint8_t)fc);
+    }
+    action battery {
+    }
+    action battery_byte {
+        aStatus->battery = (uint8_t)(((uint8_t)aStatus->battery << 8) | (uint8_t)fc);
+    }
+    action temperature {
+    }
+    action temperature_byte {
+        aStatus->temperature = (int16_t)(((uint16_t)aStatus->temperature << 8) | (uint8_t)fc);
+    }
+    id = any{1} $id_byte @id; 
+    battery = any{1} $battery_byte @battery; 
+    temperature = any{2} $temperature_byte @temperature; 
+    main := id battery temperature;
+}%%
+static enum RobustoParseStatus robustoParseStatusStatus(const struct StatusParserState *aParserState)
+{
+    if (aParserState->cs >= Status_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Status_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseStatus(struct StatusParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct StatusMessage *aStatus, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineStatusParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusStatus(aParserState);
+}
+int isStatusAccepted(const struct StatusParserState *aParserState)
+{
+    return aParserState->cs >= Status_first_final;
+}
+// endregion Status parser
+
+// =============================================================================
+// region Command parser
+// =============================================================================
+
+%%{
+    machine Command;
+    write data;
+}%%
+void machineCommandParserStateInit(struct CommandParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Command;
+    access aParserState->;
+    alphtype unsigned char;
+    action id {
+    }
+    action id_byte {
+        aCommand->id = (uint8_t)(((uint8_t)aCommand->id << 8) | (uint8_t)fc);
+    }
+    action code {
+    }
+    action code_byte {
+        aCommand->code = (uint8_t)(((uint8_t)aCommand->code << 8) | (uint8_t)fc);
+    }
+    action argument {
+    }
+    action argument_byte {
+        aCommand->argument = (uint16_t)(((uint16_t)aCommand->argument << 8) | (uint8_t)fc);
+    }
+    id = any{1} $id_byte @id; 
+    code = any{1} $code_byte @code; 
+    argument = any{2} $argument_byte @argument; 
+    main := id code argument;
+}%%
+static enum RobustoParseStatus robustoParseStatusCommand(const struct CommandParserState *aParserState)
+{
+    if (aParserState->cs >= Command_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Command_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseCommand(struct CommandParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct CommandMessage *aCommand, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineCommandParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    %% write exec;
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusCommand(aParserState);
+}
+int isCommandAccepted(const struct CommandParserState *aParserState)
+{
+    return aParserState->cs >= Command_first_final;
+}
+// endregion Command parser
+
+// =============================================================================
+// region Dispatcher
+// =============================================================================
+
+void robustoDispatcherInit(struct RobustoDispatcherState *aState)
+{
+    aState->id = 0u;
+    aState->idLength = 0u;
+    aState->selected = ROBUSTO_DISPATCH_PENDING;
+    aState->accepted = 0u;
+}
+
+void parseAny(struct RobustoDispatcherState *aState, const char *aInputBuffer, int aInputBufferLength, const struct RobustoDispatchCallbacks *aCallbacks)
+{
+    while (aState->selected == ROBUSTO_DISPATCH_PENDING && aInputBufferLength > 0) {
+        aState->idBuffer[aState->idLength] = *aInputBuffer;
+        aState->id = (uint8_t)((aState->id << 8) | (uint8_t)*aInputBuffer);
+        ++aState->idLength;
+        ++aInputBuffer;
+        --aInputBufferLength;
+
+        if (aState->idLength < ROBUSTO_DISPATCH_ID_LENGTH) {
+            continue;
+        }
+
+        switch (aState->id) {
+            case ROBUSTO_STATUS_ID:
+                aState->selected = 1u;
+                machineStatusParserStateInit(&aState->parserState.Status);
+                parseStatus(&aState->parserState.Status, aState->idBuffer, ROBUSTO_DISPATCH_ID_LENGTH, &aState->message.Status, 0);
+                break;
+            case ROBUSTO_COMMAND_ID:
+                aState->selected = 2u;
+                machineCommandParserStateInit(&aState->parserState.Command);
+                parseCommand(&aState->parserState.Command, aState->idBuffer, ROBUSTO_DISPATCH_ID_LENGTH, &aState->message.Command, 0);
+                break;
+            default:
+                aState->selected = ROBUSTO_DISPATCH_UNKNOWN;
+                break;
+        }
+    }
+
+    switch (aState->selected) {
+        case 1u:
+            if (aInputBufferLength > 0) {
+                parseStatus(&aState->parserState.Status, aInputBuffer, aInputBufferLength, &aState->message.Status, 0);
+            }
+
+            if (!aState->accepted && isStatusAccepted(&aState->parserState.Status)) {
+                aState->accepted = 1u;
+
+                if (aCallbacks->onStatus != 0) {
+                    aCallbacks->onStatus(&aState->message.Status, aCallbacks->context);
+                }
+            }
+            break;
+        case 2u:
+            if (aInputBufferLength > 0) {
+                parseCommand(&aState->parserState.Command, aInputBuffer, aInputBufferLength, &aState->message.Command, 0);
+            }
+
+            if (!aState->accepted && isCommandAccepted(&aState->parserState.Command)) {
+                aState->accepted = 1u;
+
+                if (aCallbacks->onCommand != 0) {
+                    aCallbacks->onCommand(&aState->message.Command, aCallbacks->context);
+                }
+            }
+            break;
+        default:
+            break;
+    }
+}
+// endregion Dispatcher
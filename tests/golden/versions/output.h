@@ -0,0 +1,151 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region Status types
+// =============================================================================
+
+struct StatusMessage {
+    uint8_t id;
+    uint8_t battery;
+    int16_t temperature;
+};
+struct StatusParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+// endregion Status types
+
+// =============================================================================
+// region Command types
+// =============================================================================
+
+struct CommandMessage {
+    uint8_t id;
+    uint8_t code;
+    uint16_t argument;
+};
+struct CommandParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+};
+// endregion Command types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct StatusMessage Status;
+    struct CommandMessage Command;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_STATUS_MIN_FRAME_SIZE 4u
+#define ROBUSTO_STATUS_MAX_FRAME_SIZE 4u
+#define ROBUSTO_COMMAND_MIN_FRAME_SIZE 4u
+#define ROBUSTO_COMMAND_MAX_FRAME_SIZE 4u
+#define ROBUSTO_MIN_FRAME_SIZE 4u
+#define ROBUSTO_MAX_FRAME_SIZE 4u
+// endregion Sizing constants
+
+// =============================================================================
+// region Dispatcher
+// =============================================================================
+
+#define ROBUSTO_DISPATCH_ID_LENGTH 1u
+#define ROBUSTO_DISPATCH_PENDING 0u
+#define ROBUSTO_DISPATCH_UNKNOWN 255u
+#define ROBUSTO_STATUS_ID 1u
+#define ROBUSTO_COMMAND_ID 2u
+
+enum RobustoMessageId {
+    ROBUSTO_MESSAGE_ID_STATUS = ROBUSTO_STATUS_ID,
+    ROBUSTO_MESSAGE_ID_COMMAND = ROBUSTO_COMMAND_ID,
+};
+
+// Each callback is invoked once its message's frame is accepted, and may be NULL
+struct RobustoDispatchCallbacks {
+    void *context;
+    void (*onStatus)(const struct StatusMessage *aStatus, void *aContext);
+    void (*onCommand)(const struct CommandMessage *aCommand, void *aContext);
+};
+
+union RobustoDispatchParserState {
+    struct StatusParserState Status;
+    struct CommandParserState Command;
+};
+
+// `selected` is the 1-based index of the message being parsed, `ROBUSTO_DISPATCH_PENDING` while
+// the ID is incomplete, or `ROBUSTO_DISPATCH_UNKNOWN` if no message has the ID
+struct RobustoDispatcherState {
+    uint8_t id;
+    char idBuffer[ROBUSTO_DISPATCH_ID_LENGTH];
+    uint8_t idLength;
+    uint8_t selected;
+    uint8_t accepted;
+    union RobustoDispatchParserState parserState;
+    union RobustoAnyMessage message;
+};
+// endregion Dispatcher
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineStatusParserStateInit(struct StatusParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aStatus
+enum RobustoParseStatus parseStatus(struct StatusParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct StatusMessage *aStatus, int *aConsumedLength);
+// Non-null: aParserState
+int isStatusAccepted(const struct StatusParserState *aParserState);
+// Non-null: aParserState
+void machineCommandParserStateInit(struct CommandParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aCommand
+enum RobustoParseStatus parseCommand(struct CommandParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct CommandMessage *aCommand, int *aConsumedLength);
+// Non-null: aParserState
+int isCommandAccepted(const struct CommandParserState *aParserState);
+// Non-null: aState
+void robustoDispatcherInit(struct RobustoDispatcherState *aState);
+// Non-null: aState, aInputBuffer, aCallbacks
+void parseAny(struct RobustoDispatcherState *aState, const char *aInputBuffer, int aInputBufferLength, const struct RobustoDispatchCallbacks *aCallbacks);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
@@ -0,0 +1,124 @@
+#include "output.h"
+
+// =============================================================================
+// region Upload parser
+// =============================================================================
+
+%%{
+    machine Upload;
+    write data;
+}%%
+void machineUploadParserStateInit(struct UploadParserState *aParserState)
+{
+    aParserState->machineInitRequired = 0;
+    aParserState->cs = 0;
+    aParserState->inputPosition = 0;
+    aParserState->error = 0;
+    %% write init;
+}
+%%{
+    machine Upload;
+    access aParserState->;
+    alphtype unsigned char;
+    action kind {
+    }
+    action kind_byte {
+        aUpload->kind = (uint8_t)(((uint8_t)aUpload->kind << 8) | (uint8_t)fc);
+    }
+    action name {
+    }
+    action name_when {
+        ((int64_t)aUpload->kind == INT64_C(1))
+    }
+    action name_reset {
+        aUpload->kindVariant.name.offset = aParserState->inputPosition + (uint32_t)(p - (const unsigned char *)aInputBuffer);
+        aUpload->kindVariant.name.length = 0;
+    }
+    action name_byte {
+        if (aUpload->kindVariant.name.length < 32u) {
+            ++aUpload->kindVariant.name.length;
+        }
+    }
+    action chunk {
+    }
+    action chunk_when {
+        ((int64_t)aUpload->kind == INT64_C(2))
+    }
+    action chunk_reset {
+        aUpload->kindVariant.chunk.offset = aParserState->inputPosition + (uint32_t)(p - (const unsigned char *)aInputBuffer);
+        aUpload->kindVariant.chunk.length = 0;
+    }
+    action chunk_byte {
+        if (aUpload->kindVariant.chunk.length < 1024u) {
+            ++aUpload->kindVariant.chunk.length;
+        }
+    }
+    action crc {
+    }
+    action crc_byte {
+        aUpload->crc = (uint8_t)(((uint8_t)aUpload->crc << 8) | (uint8_t)fc);
+    }
+    kind = any{1} $kind_byte @kind; 
+    name = ( ( 'a'..'z' )+ 0x00 ) >name_reset $name_byte @name; 
+    chunk = ( ( 0x01..0xFF )+ 0x00 ) >chunk_reset $chunk_byte @chunk; 
+    crc = any{1} $crc_byte @crc; 
+    main := ( any* :>> ( 0x7E ) ) kind ( name | chunk ) crc;
+}%%
+static enum RobustoParseStatus robustoParseStatusUpload(const struct UploadParserState *aParserState)
+{
+    if (aParserState->cs >= Upload_first_final) {
+        return ROBUSTO_PARSE_STATUS_OK;
+    }
+
+    if (aParserState->cs != Upload_error) {
+        return ROBUSTO_PARSE_STATUS_INCOMPLETE;
+    }
+
+    switch (aParserState->error) {
+        case ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE:
+            return ROBUSTO_PARSE_STATUS_ERROR_RANGE;
+        case ROBUSTO_PARSE_ERROR_CHECKSUM:
+            return ROBUSTO_PARSE_STATUS_ERROR_CRC;
+        case ROBUSTO_PARSE_ERROR_OVERFLOW:
+            return ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW;
+        default:
+            return ROBUSTO_PARSE_STATUS_ERROR;
+    }
+}
+
+enum RobustoParseStatus parseUpload(struct UploadParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct UploadMessage *aUpload, int *aConsumedLength)
+{
+    if (aParserState->machineInitRequired != 0) {
+        machineUploadParserStateInit(aParserState);
+    }
+
+    const unsigned char *p = (const unsigned char *)aInputBuffer;  // Iterator "begin" pointer -- Ragel-specific variable for C code generation
+    const unsigned char *pe = p + aInputBufferLength;  // Iterator "end" pointer -- Ragel-specific variable for C code generation
+    // Parse starting from the state defined in `aParserState`
+    for (;;) {
+        const uint32_t inputPosition = aParserState->inputPosition;
+
+        %% write exec;
+
+        if (aParserState->cs != Upload_error || p == pe) {
+            break;
+        }
+
+        // Resynchronize on the rest of the input
+        machineUploadParserStateInit(aParserState);
+        aParserState->inputPosition = inputPosition;
+    }
+
+    aParserState->inputPosition += (uint32_t)(p - (const unsigned char *)aInputBuffer);
+
+    if (aConsumedLength != 0) {
+        *aConsumedLength = (int)(p - (const unsigned char *)aInputBuffer);
+    }
+
+    return robustoParseStatusUpload(aParserState);
+}
+int isUploadAccepted(const struct UploadParserState *aParserState)
+{
+    return aParserState->cs >= Upload_first_final;
+}
+// endregion Upload parser
@@ -0,0 +1,97 @@
+#ifndef OUTPUT_H
+#define OUTPUT_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// =============================================================================
+// region Parse errors
+// =============================================================================
+
+enum RobustoParseError {
+    ROBUSTO_PARSE_ERROR_NONE = 0,
+    ROBUSTO_PARSE_ERROR_VALUE_OUT_OF_RANGE = 1,
+    ROBUSTO_PARSE_ERROR_FRAME_LENGTH = 2,
+    ROBUSTO_PARSE_ERROR_CHECKSUM = 3,
+    ROBUSTO_PARSE_ERROR_LENGTH = 4,
+    ROBUSTO_PARSE_ERROR_CHECK = 5,
+    ROBUSTO_PARSE_ERROR_OVERFLOW = 6,
+};
+
+enum RobustoParseStatus {
+    ROBUSTO_PARSE_STATUS_OK = 0,
+    ROBUSTO_PARSE_STATUS_INCOMPLETE = 1,
+    ROBUSTO_PARSE_STATUS_ERROR_CRC = 2,
+    ROBUSTO_PARSE_STATUS_ERROR_RANGE = 3,
+    ROBUSTO_PARSE_STATUS_ERROR_OVERFLOW = 4,
+    ROBUSTO_PARSE_STATUS_ERROR = 5,
+};
+// endregion Parse errors
+
+// =============================================================================
+// region Slices
+// =============================================================================
+
+// Bytes of a field, left in the parser's input: the offset counts from the first byte
+// passed since the parser state was initialized
+struct RobustoSlice {
+    uint32_t offset;
+    uint32_t length;
+};
+// endregion Slices
+
+// =============================================================================
+// region Upload types
+// =============================================================================
+
+struct UploadMessage {
+    uint8_t kind;
+    union {
+        struct RobustoSlice name;
+        struct RobustoSlice chunk;
+    } kindVariant;
+    uint8_t crc;
+};
+struct UploadParserState {
+    // Nonzero makes the next parsing call restart the machine, e.g. once a frame has been cut off
+    int machineInitRequired;
+    int cs;
+    uint8_t error;
+    uint32_t inputPosition;
+};
+// endregion Upload types
+
+// =============================================================================
+// region Sizing constants
+// =============================================================================
+
+union RobustoAnyMessage {
+    struct UploadMessage Upload;
+};
+#define ROBUSTO_MAX_MESSAGE_STRUCT_SIZE (sizeof(union RobustoAnyMessage))
+#define ROBUSTO_UPLOAD_MIN_FRAME_SIZE 5u
+#define ROBUSTO_UPLOAD_MAX_FRAME_SIZE 1027u
+#define ROBUSTO_MIN_FRAME_SIZE 5u
+#define ROBUSTO_MAX_FRAME_SIZE 1027u
+// endregion Sizing constants
+
+// =============================================================================
+// region API
+// =============================================================================
+
+// Non-null: aParserState
+void machineUploadParserStateInit(struct UploadParserState *aParserState);
+// Non-null: aParserState, aInputBuffer, aUpload
+enum RobustoParseStatus parseUpload(struct UploadParserState *aParserState, const char *aInputBuffer, int aInputBufferLength, struct UploadMessage *aUpload, int *aConsumedLength);
+// Non-null: aParserState
+int isUploadAccepted(const struct UploadParserState *aParserState);
+// endregion API
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif
+
+#endif  // OUTPUT_H
@@ -0,0 +1,549 @@
+//! Linter tests: protocols which are faulty in a particular way are linted,
+//! and the findings are checked for the expected rule and location.
+
+use robusto::bpir::representation::{Field, Message, Protocol};
+use robusto::bpir::validation::{
+    lint_protocol, rules, validate_protocol, validate_protocol_with, Diagnostic, LintLevel, LintResult,
+    LinterRegistry, MessageFieldLint, Rule, Severity, ValidationConfig,
+};
+
+fn lint(json: &str) -> Vec<Diagnostic> {
+    let protocol = Protocol::from_json(json).unwrap().resolve_message_extensions().resolve_group_definitions();
+    lint_protocol(&protocol).diagnostics
+}
+
+const EMPTY_MESSAGE_WITH_AN_ID: &str = r#"{
+    "messages": [
+        {"name": "Empty", "fields": [], "attributes": [{"Id": 1}]},
+        {
+            "name": "Ping",
+            "fields": [
+                {"name": "id", "field_type": {"Integer": {"bit_width": 8, "signed": false}}, "attributes": []}
+            ],
+            "attributes": [{"Id": 2}]
+        }
+    ],
+    "attributes": []
+}"#;
+
+#[test]
+fn message_with_an_id_but_no_fields() {
+    let diagnostics = lint(EMPTY_MESSAGE_WITH_AN_ID);
+
+    assert!(diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Error
+        && diagnostic.message_name.as_deref() == Some("Empty")
+        && diagnostic.text == "message Empty has an ID but no fields"));
+    assert!(!diagnostics.iter().any(|diagnostic| diagnostic.message_name.as_deref() == Some("Ping")));
+}
+
+#[test]
+fn protocol_lint_suppressed_by_a_field() {
+    let protocol = |attributes: &str| {
+        format!(
+            r#"{{
+                "messages": [
+                    {{
+                        "name": "Log",
+                        "fields": [
+                            {{"name": "text", "field_type": {{"Regex": {{"regex": "[a-z]+"}}}}, "attributes": [
+                                {{"MaxLength": {{"value": 200}}}}{0}
+                            ]}}
+                        ],
+                        "attributes": []
+                    }}
+                ],
+                "attributes": [{{"Target": {{"name": "Cortex-M0", "ram_budget": 64}}}}]
+            }}"#,
+            attributes
+        )
+    };
+    let is_buffer_budget = |diagnostic: &Diagnostic| diagnostic.rule == rules::BUFFER_BUDGET;
+
+    assert!(lint(&protocol("")).iter().any(is_buffer_budget));
+    assert!(!lint(&protocol(r#", {"SuppressLint": "RB042"}"#)).iter().any(is_buffer_budget));
+}
+
+#[test]
+fn faulty_protocol_validated_without_panicking() {
+    let protocol = Protocol::from_json(EMPTY_MESSAGE_WITH_AN_ID).unwrap();
+
+    assert!(validate_protocol(&protocol, &ValidationConfig::default()).has_errors());
+}
+
+#[test]
+fn regex_of_unsupported_syntax() {
+    let diagnostics = lint(
+        r#"{
+            "messages": [
+                {
+                    "name": "Log",
+                    "fields": [
+                        {"name": "text", "field_type": {"Regex": {"regex": "^[a-z]+$"}}, "attributes": [
+                            {"MaxLength": {"value": 16}}
+                        ]}
+                    ],
+                    "attributes": []
+                }
+            ],
+            "attributes": []
+        }"#,
+    );
+
+    assert!(diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Error
+        && diagnostic.rule == rules::REGEX_SYNTAX
+        && diagnostic.field_name.as_deref() == Some("text")));
+}
+
+fn regex_fields(regexes: &[&str], message_attributes: &str) -> std::string::String {
+    let fields = regexes
+        .iter()
+        .enumerate()
+        .map(|(i, regex)| {
+            format!(
+                r#"{{"name": "f{0}", "field_type": {{"Regex": {{"regex": "{1}"}}}}, "attributes": [
+                    {{"MaxLength": {{"value": 16}}}}
+                ]}}"#,
+                i, regex
+            )
+        })
+        .collect::<std::vec::Vec<_>>()
+        .join(", ");
+
+    format!(
+        r#"{{
+            "messages": [{{"name": "Log", "fields": [{0}], "attributes": [{1}]}}],
+            "attributes": []
+        }}"#,
+        fields, message_attributes
+    )
+}
+
+#[test]
+fn regex_overlap() {
+    let overlaps = |regexes: &[&str], message_attributes: &str| {
+        lint(&regex_fields(regexes, message_attributes))
+            .iter()
+            .any(|diagnostic| diagnostic.rule == rules::REGEX_OVERLAP)
+    };
+    let unordered = r#"{"FieldOrder": "Unordered"}"#;
+
+    assert!(overlaps(&["M[a-z]+", "Mo"], unordered));
+    assert!(!overlaps(&["M[a-z]+", "R[0-9]"], unordered));
+    assert!(overlaps(&["[a-z]+", "[a-z]{2};"], ""));
+    assert!(!overlaps(&["[a-z]+;", "[a-z]+"], ""));
+    assert!(!overlaps(&["[a-z]+", "9", "[a-z]+"], ""));
+}
+
+#[test]
+fn reserved_names_escaped_by_the_mangler() {
+    let protocol = |message_name: &str, field_name: &str| {
+        format!(
+            r#"{{
+                "messages": [
+                    {{
+                        "name": "{0}",
+                        "fields": [
+                            {{
+                                "name": "{1}",
+                                "field_type": {{"Integer": {{"bit_width": 8, "signed": false}}}},
+                                "attributes": []
+                            }}
+                        ],
+                        "attributes": []
+                    }}
+                ],
+                "attributes": []
+            }}"#,
+            message_name, field_name
+        )
+    };
+    let reserved_name = |message_name: &str, field_name: &str| {
+        lint(&protocol(message_name, field_name))
+            .into_iter()
+            .filter(|diagnostic| diagnostic.rule == rules::RESERVED_NAME)
+            .collect::<std::vec::Vec<_>>()
+    };
+
+    // Names which would break the generated code are escaped, so the description stays valid
+    for (message_name, field_name) in [("Status", "int"), ("Status", "main"), ("Status", "p"), ("switch", "id")] {
+        let diagnostics = reserved_name(message_name, field_name);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert!(diagnostics[0].text.ends_with("so the generated code escapes it"));
+    }
+
+    // Names reserved by other targets only are kept
+    let diagnostics = reserved_name("Status", "class");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert!(diagnostics[0].text.ends_with("reserved by C++"));
+
+    let diagnostics = reserved_name("Self", "id");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message_name.as_deref(), Some("Self"));
+    assert!(diagnostics[0].text.ends_with("reserved by Rust"));
+
+    assert!(reserved_name("Status", "id").is_empty());
+}
+
+#[test]
+fn ragel_write_data_options_the_parsers_cannot_do_without() {
+    let protocol = |write_data: &str| {
+        format!(
+            r#"{{
+                "messages": [
+                    {{
+                        "name": "Ping",
+                        "fields": [
+                            {{
+                                "name": "id",
+                                "field_type": {{"Integer": {{"bit_width": 8, "signed": false}}}},
+                                "attributes": []
+                            }}
+                        ],
+                        "attributes": []
+                    }}
+                ],
+                "attributes": [{{"Ragel": {{"write_data": {write_data}}}}}]
+            }}"#
+        )
+    };
+    let ragel_errors = |write_data: &str| {
+        lint(&protocol(write_data))
+            .into_iter()
+            .filter(|diagnostic| diagnostic.rule == rules::RAGEL && diagnostic.severity == Severity::Error)
+            .map(|diagnostic| diagnostic.text)
+            .collect::<Vec<_>>()
+    };
+
+    assert!(ragel_errors(r#"["NoEntry", "NoError"]"#).is_empty());
+    assert_eq!(
+        ragel_errors(r#"["NoFinal", "NoPrefix"]"#),
+        [
+            "\"write data nofinal\" omits constants the parsers refer to",
+            "\"write data noprefix\" omits constants the parsers refer to"
+        ]
+    );
+}
+
+/// An unsigned integer field
+fn integer(name: &str, bit_width: usize, attributes: &str) -> std::string::String {
+    format!(
+        r#"{{"name": "{0}", "field_type": {{"Integer": {{"bit_width": {1}, "signed": false}}}}, "attributes": [{2}]}}"#,
+        name, bit_width, attributes
+    )
+}
+
+/// A protocol of a single message, named `Frame`
+fn single_message(
+    fields: &[std::string::String],
+    message_attributes: &str,
+    protocol_attributes: &str,
+) -> std::string::String {
+    format!(
+        r#"{{
+            "messages": [{{"name": "Frame", "fields": [{0}], "attributes": [{1}]}}],
+            "attributes": [{2}]
+        }}"#,
+        fields.join(", "),
+        message_attributes,
+        protocol_attributes
+    )
+}
+
+/// Severities of the findings of the rule
+fn findings(json: &str, rule: Rule) -> Vec<Severity> {
+    lint(json)
+        .into_iter()
+        .filter(|diagnostic| diagnostic.rule == rule)
+        .map(|diagnostic| diagnostic.severity)
+        .collect()
+}
+
+#[test]
+fn message_defined_more_than_once() {
+    let protocol = |second_name: &str| {
+        format!(
+            r#"{{
+                "messages": [
+                    {{"name": "Ping", "fields": [{0}], "attributes": []}},
+                    {{"name": "{1}", "fields": [{0}], "attributes": []}}
+                ],
+                "attributes": []
+            }}"#,
+            integer("id", 8, ""),
+            second_name
+        )
+    };
+
+    assert_eq!(findings(&protocol("Ping"), rules::MESSAGE_NAME), [Severity::Error]);
+    assert!(findings(&protocol("Pong"), rules::MESSAGE_NAME).is_empty());
+}
+
+#[test]
+fn empty_protocol() {
+    let empty = r#"{"messages": [], "attributes": []}"#;
+
+    assert_eq!(findings(empty, rules::ROOT_MESSAGE), [Severity::Error]);
+    assert!(validate_protocol(&Protocol::from_json(empty).unwrap(), &ValidationConfig::default()).has_errors());
+    assert!(findings(&single_message(&[integer("id", 8, "")], "", ""), rules::ROOT_MESSAGE).is_empty());
+}
+
+#[test]
+fn frame_size_against_the_budget() {
+    let protocol = |budget: usize| {
+        format!(
+            r#"{{
+                "messages": [
+                    {{"name": "Fixed", "fields": [{0}, {1}], "attributes": []}},
+                    {{
+                        "name": "Log",
+                        "fields": [
+                            {{"name": "text", "field_type": {{"Regex": {{"regex": "[a-z]+"}}}}, "attributes": [
+                                {{"MaxLength": {{"value": 16}}}}
+                            ]}}
+                        ],
+                        "attributes": []
+                    }}
+                ],
+                "attributes": [{{"FrameSizeBudget": {2}}}]
+            }}"#,
+            integer("a", 32, ""),
+            integer("b", 32, ""),
+            budget
+        )
+    };
+    let frame_sizes = |budget: usize| {
+        lint(&protocol(budget))
+            .into_iter()
+            .filter(|diagnostic| diagnostic.rule == rules::FRAME_SIZE)
+            .map(|diagnostic| (diagnostic.message_name.unwrap(), diagnostic.severity))
+            .collect::<Vec<_>>()
+    };
+
+    // Frames which never fit are faulty, frames which may not fit are suspect
+    assert_eq!(
+        frame_sizes(4),
+        [("Fixed".to_string(), Severity::Error), ("Log".to_string(), Severity::Warning)]
+    );
+    assert!(frame_sizes(16).is_empty());
+}
+
+#[test]
+fn length_field_integrity() {
+    let protocol = |length_of: &str, bit_width: usize| {
+        single_message(
+            &[
+                integer("length", bit_width, &format!(r#"{{"LengthOf": {0}}}"#, length_of)),
+                integer("kind", 8, ""),
+                r#"{"name": "text", "field_type": {"Regex": {"regex": "[a-z]+"}}, "attributes": [
+                    {"MaxLength": {"value": 300}}
+                ]}"#
+                .to_string(),
+            ],
+            "",
+            "",
+        )
+    };
+
+    assert_eq!(
+        findings(&protocol(r#"{"first": "kind", "last": "missing"}"#, 16), rules::LENGTH_OF),
+        [Severity::Error]
+    );
+    assert_eq!(
+        findings(&protocol(r#"{"first": "text", "last": "kind"}"#, 16), rules::LENGTH_OF),
+        [Severity::Error]
+    );
+    assert_eq!(
+        findings(&protocol(r#"{"first": "kind", "last": "text"}"#, 8), rules::LENGTH_OF),
+        [Severity::Warning]
+    );
+    assert!(findings(&protocol(r#"{"first": "kind", "last": "text"}"#, 16), rules::LENGTH_OF).is_empty());
+}
+
+#[test]
+fn length_field_following_the_covered_fields() {
+    // Parsers check the length once the covered fields are parsed, so there is no order to keep
+    let protocol = single_message(
+        &[
+            integer("kind", 8, ""),
+            integer("value", 16, ""),
+            integer("length", 8, r#"{"LengthOf": {"first": "kind", "last": "value"}}"#),
+        ],
+        "",
+        "",
+    );
+
+    assert!(findings(&protocol, rules::LENGTH_OF).is_empty());
+}
+
+#[test]
+fn checksum_coverage() {
+    let crc = || integer("crc", 8, r#"{"Checksum": {"name": "frame", "algorithm": "Crc8"}}"#);
+    let covered = |name: &str, attribute: &str| integer(name, 8, &format!(r#"{{"{0}": "frame"}}"#, attribute));
+
+    let good = single_message(&[covered("a", "StartChecksum"), covered("b", "StopChecksum"), crc()], "", "");
+    assert!(findings(&good, rules::CHECKSUM_COVERAGE).is_empty());
+
+    // The checksum is compared once its field is parsed, so it must follow the covered fields
+    let inside = single_message(
+        &[covered("a", "StartChecksum"), crc(), covered("b", "StopChecksum")],
+        "",
+        "",
+    );
+    assert_eq!(findings(&inside, rules::CHECKSUM_COVERAGE), [Severity::Error]);
+
+    let unstopped = single_message(&[covered("a", "StartChecksum"), integer("b", 8, ""), crc()], "", "");
+    assert_eq!(findings(&unstopped, rules::CHECKSUM_COVERAGE), [Severity::Error]);
+
+    let unheld = single_message(&[covered("a", "StartChecksum"), covered("b", "StopChecksum")], "", "");
+    assert_eq!(findings(&unheld, rules::CHECKSUM_COVERAGE), [Severity::Warning]);
+}
+
+#[test]
+fn alternatives_of_a_selector() {
+    let protocol = |selector_attributes: &str, values: [u64; 2]| {
+        single_message(
+            &[
+                integer("kind", 1, selector_attributes),
+                integer("a", 8, &format!(r#"{{"ConditionalOn": {{"field": "kind", "value": {0}}}}}"#, values[0])),
+                integer("b", 8, &format!(r#"{{"ConditionalOn": {{"field": "kind", "value": {0}}}}}"#, values[1])),
+            ],
+            "",
+            "",
+        )
+    };
+
+    assert_eq!(findings(&protocol("", [0, 0]), rules::CONDITIONAL_ON), [Severity::Error]);
+    assert_eq!(findings(&protocol("", [0, 2]), rules::CONDITIONAL_ON), [Severity::Warning]);
+    assert!(findings(&protocol("", [0, 1]), rules::CONDITIONAL_ON).is_empty());
+
+    // Values no alternative claims, unless a default arm handles them
+    assert_eq!(findings(&protocol("", [0, 2]), rules::SELECTOR_EXHAUSTIVENESS), [Severity::Warning]);
+    assert!(findings(&protocol(r#""DefaultArm""#, [0, 2]), rules::SELECTOR_EXHAUSTIVENESS).is_empty());
+    assert!(findings(&protocol("", [0, 1]), rules::SELECTOR_EXHAUSTIVENESS).is_empty());
+}
+
+#[test]
+fn buffer_budget() {
+    let protocol = |ram_budget: usize| {
+        single_message(
+            &[r#"{"name": "text", "field_type": {"Regex": {"regex": "[a-z]+"}}, "attributes": [
+                {"MaxLength": {"value": 200}}
+            ]}"#
+            .to_string()],
+            "",
+            &format!(r#"{{"Target": {{"name": "Cortex-M0", "ram_budget": {0}}}}}"#, ram_budget),
+        )
+    };
+
+    assert_eq!(findings(&protocol(64), rules::BUFFER_BUDGET), [Severity::Warning]);
+    assert!(findings(&protocol(4096), rules::BUFFER_BUDGET).is_empty());
+}
+
+/// A convention of a library user: field names start in lower case
+#[derive(Default)]
+struct LowerCaseLinter {}
+
+impl MessageFieldLint for LowerCaseLinter {
+    fn rule(&self) -> Rule {
+        Rule {
+            id: "ACME001",
+            name: "lower_case",
+        }
+    }
+
+    fn lint_field(&mut self, message: &Message, field: &Field) -> LintResult {
+        match field.name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            true => LintResult::Warning(format!(
+                "field {0} of message {1} starts in upper case",
+                field.name, message.name
+            )),
+            false => LintResult::Ok,
+        }
+    }
+}
+
+#[test]
+fn registered_linter() {
+    let registered_findings = |field_attributes: &str| {
+        let json = single_message(&[integer("Id", 8, field_attributes)], "", "");
+        let protocol = Protocol::from_json(&json).unwrap();
+        let mut registry = LinterRegistry::new();
+        registry.register_message_field_lint(Box::new(LowerCaseLinter::default()));
+
+        validate_protocol_with(&protocol, &mut registry, &ValidationConfig::default())
+            .diagnostics
+            .into_iter()
+            .filter(|diagnostic| diagnostic.rule.id == "ACME001")
+            .count()
+    };
+
+    assert_eq!(registered_findings(""), 1);
+    assert_eq!(registered_findings(r#"{"SuppressLint": "ACME001"}"#), 0);
+
+    // Built-in linting knows nothing of it
+    let json = single_message(&[integer("Id", 8, "")], "", "");
+    assert!(!lint(&json).iter().any(|diagnostic| diagnostic.rule.id == "ACME001"));
+}
+
+#[test]
+fn report_rendering() {
+    let protocol = Protocol::from_json(EMPTY_MESSAGE_WITH_AN_ID).unwrap();
+    let result = validate_protocol(&protocol, &ValidationConfig::default());
+    let report = result.render_report(false);
+
+    assert!(report.starts_with(concat!(
+        "error[RB020]: message Empty has an ID but no fields\n",
+        "  --> message Empty\n",
+        "  = rule: id\n\n"
+    )));
+    // Findings about the protocol as a whole have no location
+    assert!(report.contains("\nwarning[RB031]: no message claims ID 0"));
+    assert!(report.contains("attribute to the protocol\n  = rule: dispatch\n"));
+    assert!(report.ends_with("error: the protocol is invalid due to 1 error(s); 1 warning(s) emitted\n"));
+    assert!(result.render_report(true).contains("\x1b[1;31merror[RB020]\x1b[0m"));
+
+    let json: serde_json::Value = serde_json::from_str(&result.render_json()).unwrap();
+    assert_eq!(json["errors"], 1);
+    assert_eq!(json["warnings"], 1);
+    assert_eq!(json["diagnostics"][0]["rule"]["id"], "RB020");
+    assert_eq!(json["diagnostics"][0]["message_name"], "Empty");
+
+    // Nothing to report
+    let clean = validate_protocol(
+        &Protocol::from_json(&single_message(&[integer("id", 8, "")], "", "")).unwrap(),
+        &ValidationConfig::default(),
+    );
+    assert_eq!(clean.render_report(false), "");
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&clean.render_json()).unwrap()["errors"], 0);
+}
+
+#[test]
+fn warnings_as_errors() {
+    // The budget may be exceeded, which is a warning
+    let protocol = Protocol::from_json(&single_message(
+        &[r#"{"name": "text", "field_type": {"Regex": {"regex": "[a-z]+"}}, "attributes": [
+            {"MaxLength": {"value": 16}}
+        ]}"#
+        .to_string()],
+        "",
+        r#"{"FrameSizeBudget": 4}"#,
+    ))
+    .unwrap();
+    let validate = |warnings_as_errors: bool, levels: Vec<(&str, LintLevel)>| {
+        let config = ValidationConfig {
+            warnings_as_errors,
+            levels: levels.into_iter().map(|(rule, level)| (rule.to_string(), level)).collect(),
+        };
+
+        validate_protocol(&protocol, &config)
+    };
+
+    let lenient = validate(false, vec![]);
+    assert!(lenient.has_warnings() && !lenient.has_errors());
+    assert!(validate(true, vec![]).has_errors());
+    assert!(validate(false, vec![("frame_size", LintLevel::Deny)]).has_errors());
+    assert!(!validate(true, vec![("RB039", LintLevel::Warn)]).has_errors());
+    assert!(!validate(false, vec![("RB039", LintLevel::Allow)]).has_warnings());
+}